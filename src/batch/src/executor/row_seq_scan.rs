@@ -191,6 +191,7 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
             } else {
                 None
             },
+            ..Default::default()
         };
         let value_indices = table_desc
             .get_value_indices()
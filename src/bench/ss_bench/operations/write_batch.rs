@@ -185,6 +185,7 @@ impl Operations {
                     store
                         .ingest_batch(
                             batch,
+                            vec![],
                             WriteOptions {
                                 epoch,
                                 table_id: Default::default(),
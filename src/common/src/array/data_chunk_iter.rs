@@ -365,8 +365,7 @@ impl Row {
 
 impl EstimateSize for Row {
     fn estimated_heap_size(&self) -> usize {
-        // FIXME(bugen): this is not accurate now as the heap size of some `Scalar` is not counted.
-        self.0.capacity() * std::mem::size_of::<Datum>()
+        self.0.estimated_heap_size()
     }
 }
 
@@ -130,6 +130,10 @@ impl From<TableId> for u32 {
 #[derive(Clone, Debug, PartialEq, Default, Copy)]
 pub struct TableOption {
     pub retention_seconds: Option<u32>, // second
+    /// How long the historical versions are kept for `AS OF` queries.
+    pub time_travel_retention_seconds: Option<u32>, // second
+    /// Values not smaller than the threshold are separated into blob files by compaction.
+    pub blob_value_threshold: Option<u32>, // byte
 }
 
 impl From<&piestream_pb::hummock::TableOption> for TableOption {
@@ -141,7 +145,27 @@ impl From<&piestream_pb::hummock::TableOption> for TableOption {
                 Some(table_option.retention_seconds)
             };
 
-        Self { retention_seconds }
+        let time_travel_retention_seconds = if table_option.time_travel_retention_seconds
+            == hummock::TABLE_OPTION_DUMMY_RETENTION_SECOND
+        {
+            None
+        } else {
+            Some(table_option.time_travel_retention_seconds)
+        };
+
+        let blob_value_threshold = if table_option.blob_value_threshold
+            == hummock::TABLE_OPTION_DUMMY_BLOB_VALUE_THRESHOLD
+        {
+            None
+        } else {
+            Some(table_option.blob_value_threshold)
+        };
+
+        Self {
+            retention_seconds,
+            time_travel_retention_seconds,
+            blob_value_threshold,
+        }
     }
 }
 
@@ -151,13 +175,18 @@ impl From<&TableOption> for piestream_pb::hummock::TableOption {
             retention_seconds: table_option
                 .retention_seconds
                 .unwrap_or(hummock::TABLE_OPTION_DUMMY_RETENTION_SECOND),
+            time_travel_retention_seconds: table_option
+                .time_travel_retention_seconds
+                .unwrap_or(hummock::TABLE_OPTION_DUMMY_RETENTION_SECOND),
+            blob_value_threshold: table_option
+                .blob_value_threshold
+                .unwrap_or(hummock::TABLE_OPTION_DUMMY_BLOB_VALUE_THRESHOLD),
         }
     }
 }
 
 impl TableOption {
     pub fn build_table_option(table_properties: &HashMap<String, String>) -> Self {
-        // now we only support ttl for TableOption
         let mut result = TableOption::default();
         if let Some(ttl_string) = table_properties.get(hummock::PROPERTIES_RETENTION_SECOND_KEY) {
             match ttl_string.trim().parse::<u32>() {
@@ -172,6 +201,36 @@ impl TableOption {
                 }
             };
         }
+        if let Some(retention_string) =
+            table_properties.get(hummock::PROPERTIES_TIME_TRAVEL_RETENTION_SECOND_KEY)
+        {
+            match retention_string.trim().parse::<u32>() {
+                Ok(retention_seconds_u32) => {
+                    result.time_travel_retention_seconds = Some(retention_seconds_u32)
+                }
+                Err(e) => {
+                    tracing::info!(
+                        "build_table_option parse option time_travel_retention_string {} fail {}",
+                        retention_string,
+                        e
+                    );
+                }
+            };
+        }
+        if let Some(threshold_string) =
+            table_properties.get(hummock::PROPERTIES_BLOB_VALUE_THRESHOLD_KEY)
+        {
+            match threshold_string.trim().parse::<u32>() {
+                Ok(threshold_u32) => result.blob_value_threshold = Some(threshold_u32),
+                Err(e) => {
+                    tracing::info!(
+                        "build_table_option parse option blob_value_threshold_string {} fail {}",
+                        threshold_string,
+                        e
+                    );
+                }
+            };
+        }
 
         result
     }
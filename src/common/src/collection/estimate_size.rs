@@ -61,3 +61,17 @@ impl EstimateSize for String {
         self.capacity()
     }
 }
+
+impl<T: EstimateSize> EstimateSize for Option<T> {
+    fn estimated_heap_size(&self) -> usize {
+        self.as_ref()
+            .map(EstimateSize::estimated_heap_size)
+            .unwrap_or(0)
+    }
+}
+
+impl<T: EstimateSize> EstimateSize for Box<T> {
+    fn estimated_heap_size(&self) -> usize {
+        self.as_ref().estimated_size()
+    }
+}
@@ -193,6 +193,11 @@ pub struct StorageConfig {
     #[serde(default)]
     pub file_cache: FileCacheConfig,
 
+    /// Whether to prefetch the blocks of newly compacted SSTs which replace SSTs with cached
+    /// blocks.
+    #[serde(default = "default::enable_cache_refill")]
+    pub enable_cache_refill: bool,
+
     /// Whether to enable streaming upload for sstable.
     #[serde(default = "default::min_sst_size_for_streaming_upload")]
     pub min_sst_size_for_streaming_upload: u64,
@@ -222,6 +227,13 @@ pub struct FileCacheConfig {
 
     #[serde(default = "default::file_cache_cache_meta_fallocate_unit_mb")]
     pub cache_meta_fallocate_unit_mb: usize,
+
+    /// Capacity of the file cache for sstable metas.
+    #[serde(default = "default::file_cache_meta_capacity_mb")]
+    pub meta_capacity_mb: usize,
+
+    #[serde(default = "default::file_cache_meta_total_buffer_capacity_mb")]
+    pub meta_total_buffer_capacity_mb: usize,
 }
 
 impl Default for FileCacheConfig {
@@ -252,17 +264,21 @@ pub struct DeveloperConfig {
     #[serde(default = "default::developer_stream_connector_message_buffer_size")]
     pub stream_connector_message_buffer_size: usize,
 
-    /// Limit number of cached entries (one per group key)
-    #[serde(default = "default::developer_stream_unsafe_hash_agg_cache_size")]
-    pub unsafe_stream_hash_agg_cache_size: usize,
-
-    /// Limit number of the cached entries (one per join key) on each side.
-    #[serde(default = "default::developer_unsafe_stream_join_cache_size")]
-    pub unsafe_stream_join_cache_size: usize,
-
-    /// Limit number of the cached entries in an extreme aggregation call
+    /// Limit number of the cached entries in an extreme aggregation call. Note that this only
+    /// bounds the cache within a single group. The groups themselves are evicted by the memory
+    /// manager of the compute node.
     #[serde(default = "default::developer_unsafe_stream_extreme_cache_size")]
     pub unsafe_stream_extreme_cache_size: usize,
+
+    /// Deprecated and ignored. The hash aggregation cache is now evicted by the memory manager of
+    /// the compute node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unsafe_stream_hash_agg_cache_size: Option<usize>,
+
+    /// Deprecated and ignored. The hash join cache is now evicted by the memory manager of the
+    /// compute node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unsafe_stream_join_cache_size: Option<usize>,
 }
 
 impl Default for DeveloperConfig {
@@ -384,6 +400,18 @@ mod default {
         16
     }
 
+    pub fn file_cache_meta_capacity_mb() -> usize {
+        256
+    }
+
+    pub fn file_cache_meta_total_buffer_capacity_mb() -> usize {
+        16
+    }
+
+    pub fn enable_cache_refill() -> bool {
+        true
+    }
+
     pub fn min_sst_size_for_streaming_upload() -> u64 {
         // 32MB
         32 * 1024 * 1024
@@ -409,14 +437,6 @@ mod default {
         16
     }
 
-    pub fn developer_stream_unsafe_hash_agg_cache_size() -> usize {
-        1 << 16
-    }
-
-    pub fn developer_unsafe_stream_join_cache_size() -> usize {
-        1 << 16
-    }
-
     pub fn developer_unsafe_stream_extreme_cache_size() -> usize {
         1 << 10
     }
@@ -443,5 +463,9 @@ pub mod constant {
 
         pub const TABLE_OPTION_DUMMY_RETENTION_SECOND: u32 = 0;
         pub const PROPERTIES_RETENTION_SECOND_KEY: &str = "retention_seconds";
+        pub const PROPERTIES_TIME_TRAVEL_RETENTION_SECOND_KEY: &str =
+            "time_travel_retention_seconds";
+        pub const TABLE_OPTION_DUMMY_BLOB_VALUE_THRESHOLD: u32 = 0;
+        pub const PROPERTIES_BLOB_VALUE_THRESHOLD_KEY: &str = "blob_value_threshold";
     }
 }
@@ -22,7 +22,7 @@ use crate::error::{ErrorCode, RwError};
 
 // This is a hack, &'static str is not allowed as a const generics argument.
 // TODO: refine this using the adt_const_params feature.
-const CONFIG_KEYS: [&str; 8] = [
+const CONFIG_KEYS: [&str; 9] = [
     "RW_IMPLICIT_FLUSH",
     "CREATE_COMPACTION_GROUP_FOR_MV",
     "QUERY_MODE",
@@ -31,6 +31,7 @@ const CONFIG_KEYS: [&str; 8] = [
     "DATESTYLE",
     "RW_BATCH_ENABLE_LOOKUP_JOIN",
     "MAX_SPLIT_RANGE_GAP",
+    "RW_BATCH_ENABLE_POINT_GET_CACHE",
 ];
 
 // MUST HAVE 1v1 relationship to CONFIG_KEYS. e.g. CONFIG_KEYS[IMPLICIT_FLUSH] =
@@ -43,6 +44,7 @@ const APPLICATION_NAME: usize = 4;
 const DATE_STYLE: usize = 5;
 const BATCH_ENABLE_LOOKUP_JOIN: usize = 6;
 const MAX_SPLIT_RANGE_GAP: usize = 7;
+const BATCH_ENABLE_POINT_GET_CACHE: usize = 8;
 
 trait ConfigEntry: Default + FromStr<Err = RwError> {
     fn entry_name() -> &'static str;
@@ -163,6 +165,7 @@ type ExtraFloatDigit = ConfigI32<EXTRA_FLOAT_DIGITS, 1>;
 type DateStyle = ConfigString<DATE_STYLE>;
 type BatchEnableLookupJoin = ConfigBool<BATCH_ENABLE_LOOKUP_JOIN, false>;
 type MaxSplitRangeGap = ConfigI32<MAX_SPLIT_RANGE_GAP, 8>;
+type BatchEnablePointGetCache = ConfigBool<BATCH_ENABLE_POINT_GET_CACHE, false>;
 
 #[derive(Default)]
 pub struct ConfigMap {
@@ -193,6 +196,10 @@ pub struct ConfigMap {
 
     /// It's the max gap allowed to transform small range scan scan into multi point lookup.
     max_split_range_gap: MaxSplitRangeGap,
+
+    /// If `RW_BATCH_ENABLE_POINT_GET_CACHE` is on, results of point lookups on the primary key
+    /// are cached in the frontend until a new epoch is committed.
+    batch_enable_point_get_cache: BatchEnablePointGetCache,
 }
 
 impl ConfigMap {
@@ -213,6 +220,8 @@ impl ConfigMap {
             self.batch_enable_lookup_join = val.parse()?;
         } else if key.eq_ignore_ascii_case(MaxSplitRangeGap::entry_name()) {
             self.max_split_range_gap = val.parse()?;
+        } else if key.eq_ignore_ascii_case(BatchEnablePointGetCache::entry_name()) {
+            self.batch_enable_point_get_cache = val.parse()?;
         } else {
             return Err(ErrorCode::UnrecognizedConfigurationParameter(key.to_string()).into());
         }
@@ -235,6 +244,8 @@ impl ConfigMap {
             Ok(self.date_style.to_string())
         } else if key.eq_ignore_ascii_case(BatchEnableLookupJoin::entry_name()) {
             Ok(self.batch_enable_lookup_join.to_string())
+        } else if key.eq_ignore_ascii_case(BatchEnablePointGetCache::entry_name()) {
+            Ok(self.batch_enable_point_get_cache.to_string())
         } else {
             Err(ErrorCode::UnrecognizedConfigurationParameter(key.to_string()).into())
         }
@@ -282,6 +293,11 @@ impl ConfigMap {
                 setting : self.max_split_range_gap.to_string(),
                 description : String::from("It's the max gap allowed to transform small range scan scan into multi point lookup.")
             },
+            VariableInfo{
+                name : BatchEnablePointGetCache::entry_name().to_lowercase(),
+                setting : self.batch_enable_point_get_cache.to_string(),
+                description : String::from("To cache results of point lookups on the primary key until a new epoch is committed.")
+            },
         ]
     }
 
@@ -320,4 +336,8 @@ impl ConfigMap {
             *self.max_split_range_gap as u64
         }
     }
+
+    pub fn get_batch_enable_point_get_cache(&self) -> bool {
+        *self.batch_enable_point_get_cache
+    }
 }
@@ -23,6 +23,7 @@ use piestream_pb::data::DataType as ProstDataType;
 use serde::{Deserialize, Serialize};
 
 use crate::array::{ArrayError, ArrayResult, NULL_VAL_FOR_HASH};
+use crate::collection::estimate_size::EstimateSize;
 mod native_type;
 mod ops;
 mod scalar_impl;
@@ -644,6 +645,18 @@ macro_rules! impl_scalar_impl_ref_conversion {
 
 for_all_scalar_variants! { impl_scalar_impl_ref_conversion }
 
+impl EstimateSize for ScalarImpl {
+    fn estimated_heap_size(&self) -> usize {
+        match self {
+            Self::Utf8(s) => s.estimated_heap_size(),
+            Self::Struct(s) => s.fields().iter().map(EstimateSize::estimated_size).sum(),
+            Self::List(l) => l.values().iter().map(EstimateSize::estimated_size).sum(),
+            // Other scalars are stored inline.
+            _ => 0,
+        }
+    }
+}
+
 /// Should behave the same as [`crate::array::Array::hash_at`] for non-null items.
 #[expect(clippy::derive_hash_xor_eq)]
 impl Hash for ScalarImpl {
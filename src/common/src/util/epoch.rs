@@ -77,6 +77,14 @@ impl Epoch {
         *UNIX_SINGULARITY_DATE_EPOCH + Duration::from_millis(self.physical_time())
     }
 
+    /// Returns the epoch of the given system time, which is the inverse of
+    /// [`Self::as_system_time`]. Returns `None` if the time is earlier than the singularity date.
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        time.duration_since(*UNIX_SINGULARITY_DATE_EPOCH)
+            .ok()
+            .map(|duration| Self::from_physical_time(duration.as_millis() as u64))
+    }
+
     /// Returns the epoch subtract `relative_time_ms`, which used for ttl to get epoch corresponding
     /// to the lowerbound timepoint (`src/storage/src/hummock/iterator/forward_user.rs`)
     pub fn subtract_ms(&self, relative_time_ms: u64) -> Self {
@@ -144,6 +152,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_system_time() {
+        let epoch = Epoch::now();
+        assert_eq!(
+            Epoch::from_system_time(epoch.as_system_time()).unwrap(),
+            epoch
+        );
+        assert!(Epoch::from_system_time(SystemTime::UNIX_EPOCH).is_none());
+    }
+
     #[test]
     fn test_subtract_ms() {
         {
@@ -21,6 +21,7 @@ use OrderedDatum::{NormalOrder, ReversedOrder};
 
 pub use self::serde::*;
 use crate::array::Row;
+use crate::collection::estimate_size::EstimateSize;
 use crate::types::{serialize_datum_into, Datum};
 use crate::util::sort_util::OrderType;
 
@@ -56,6 +57,21 @@ impl std::fmt::Debug for OrderedRow {
     }
 }
 
+impl EstimateSize for OrderedDatum {
+    fn estimated_heap_size(&self) -> usize {
+        match self {
+            NormalOrder(datum) => datum.estimated_heap_size(),
+            ReversedOrder(datum) => datum.0.estimated_heap_size(),
+        }
+    }
+}
+
+impl EstimateSize for OrderedRow {
+    fn estimated_heap_size(&self) -> usize {
+        self.0.estimated_heap_size()
+    }
+}
+
 impl OrderedRow {
     pub fn new(row: Row, order_types: &[OrderType]) -> Self {
         OrderedRow(
@@ -72,8 +72,8 @@ pub struct ComputeNodeOpts {
     #[clap(long, default_value = "")]
     pub file_cache_dir: String,
 
-    /// Enable managed lru cache, or use local lru cache.
-    #[clap(long)]
+    /// Deprecated and ignored. The streaming caches are always managed by the memory manager.
+    #[clap(long, hide = true)]
     pub enable_managed_cache: bool,
 }
 
@@ -17,7 +17,8 @@ use std::time::Duration;
 
 use piestream_pb::monitor_service::monitor_service_server::MonitorService;
 use piestream_pb::monitor_service::{
-    ProfilingRequest, ProfilingResponse, StackTraceRequest, StackTraceResponse,
+    ActorStatsRequest, ActorStatsResponse, ProfilingRequest, ProfilingResponse, StackTraceRequest,
+    StackTraceResponse,
 };
 use piestream_stream::task::LocalStreamManager;
 use tonic::{Request, Response, Status};
@@ -70,6 +71,15 @@ impl MonitorService for MonitorServiceImpl {
         }))
     }
 
+    #[cfg_attr(coverage, no_coverage)]
+    async fn actor_stats(
+        &self,
+        _request: Request<ActorStatsRequest>,
+    ) -> Result<Response<ActorStatsResponse>, Status> {
+        let actor_stats = self.stream_mgr.get_actor_stats().into_iter().collect();
+        Ok(Response::new(ActorStatsResponse { actor_stats }))
+    }
+
     #[cfg_attr(coverage, no_coverage)]
     async fn profiling(
         &self,
@@ -68,6 +68,16 @@ pub async fn compute_node_serve(
         config,
         if cfg!(debug_assertions) { "on" } else { "off" }
     );
+    if opts.enable_managed_cache {
+        tracing::warn!("`--enable-managed-cache` is deprecated and has no effect");
+    }
+    let developer_config = &config.streaming.developer;
+    if developer_config.unsafe_stream_hash_agg_cache_size.is_some() {
+        tracing::warn!("`unsafe_stream_hash_agg_cache_size` is deprecated and has no effect");
+    }
+    if developer_config.unsafe_stream_join_cache_size.is_some() {
+        tracing::warn!("`unsafe_stream_join_cache_size` is deprecated and has no effect");
+    }
     // Initialize all the configs
     let storage_config = Arc::new(config.storage.clone());
     let stream_config = Arc::new(config.streaming.clone());
@@ -188,7 +198,6 @@ pub async fn compute_node_serve(
         streaming_metrics.clone(),
         config.streaming.clone(),
         opts.enable_async_stack_trace,
-        opts.enable_managed_cache,
     ));
     let source_mgr = Arc::new(MemSourceManager::new(
         source_metrics,
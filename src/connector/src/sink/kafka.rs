@@ -18,19 +18,20 @@ use std::future::Future;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use itertools::Itertools;
+use piestream_common::array::{ArrayResult, Op, RowRef, StreamChunk};
+use piestream_common::catalog::{Field, Schema};
+use piestream_common::types::{DataType, DatumRef, ScalarRefImpl};
+use rdkafka::consumer::{BaseConsumer, Consumer, DefaultConsumerContext};
 use rdkafka::error::{KafkaError, KafkaResult};
 use rdkafka::message::ToBytes;
 use rdkafka::producer::{BaseRecord, DefaultProducerContext, Producer, ThreadedProducer};
 use rdkafka::types::RDKafkaErrorCode;
-use rdkafka::ClientConfig;
-use piestream_common::array::{ArrayResult, Op, RowRef, StreamChunk};
-use piestream_common::catalog::{Field, Schema};
-use piestream_common::types::{DataType, DatumRef, ScalarRefImpl};
+use rdkafka::{ClientConfig, Offset, TopicPartitionList};
 use serde::Deserialize;
 use serde_json::{json, Map, Value};
 use tracing::warn;
 
-use super::{Sink, SinkError};
+use super::{PreCommittedTransaction, Sink, SinkError};
 use crate::sink::Result;
 
 pub const KAFKA_SINK: &str = "kafka";
@@ -99,15 +100,22 @@ pub struct KafkaSink {
     pub conductor: KafkaTransactionConductor,
     state: KafkaSinkState,
     in_transaction_epoch: Option<u64>,
+    /// The epoch of the transaction pre-committed but not yet committed.
+    pre_committed_epoch: Option<u64>,
+    /// Records sent in the current transaction, as (key, payload).
+    records: Vec<(String, String)>,
 }
 
 impl KafkaSink {
     pub async fn new(config: KafkaConfig) -> Result<Self> {
+        let conductor = KafkaTransactionConductor::new(config.clone()).await?;
         Ok(KafkaSink {
-            config: config.clone(),
-            conductor: KafkaTransactionConductor::new(config).await?,
+            config,
+            conductor,
             in_transaction_epoch: None,
             state: KafkaSinkState::Init,
+            pre_committed_epoch: None,
+            records: vec![],
         })
     }
 
@@ -155,6 +163,18 @@ impl KafkaSink {
         Err(err)
     }
 
+    /// Sends a record in the current transaction, and keeps it to be persisted on pre-commit.
+    async fn send_record(&mut self, key: String, payload: String) -> Result<()> {
+        self.send(
+            BaseRecord::to(self.config.topic.as_str())
+                .key(key.as_bytes())
+                .payload(payload.as_bytes()),
+        )
+        .await?;
+        self.records.push((key, payload));
+        Ok(())
+    }
+
     fn gen_message_key(&self) -> String {
         format!(
             "{}-{}",
@@ -163,7 +183,12 @@ impl KafkaSink {
         )
     }
 
-    async fn debezium_update(&self, chunk: StreamChunk, schema: &Schema, ts_ms: u64) -> Result<()> {
+    async fn debezium_update(
+        &mut self,
+        chunk: StreamChunk,
+        schema: &Schema,
+        ts_ms: u64,
+    ) -> Result<()> {
         let mut update_cache: Option<Map<String, Value>> = None;
         for (op, row) in chunk.rows() {
             let event_object = match op {
@@ -210,29 +235,44 @@ impl KafkaSink {
                 }
             };
             if let Some(obj) = event_object {
-                self.send(
-                    BaseRecord::to(self.config.topic.as_str())
-                        .key(self.gen_message_key().as_bytes())
-                        .payload(obj.to_string().as_bytes()),
-                )
-                .await?;
+                self.send_record(self.gen_message_key(), obj.to_string())
+                    .await?;
             }
         }
         Ok(())
     }
 
-    async fn append_only(&self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
+    async fn append_only(&mut self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
         for (op, row) in chunk.rows() {
             if op == Op::Insert {
                 let record = Value::Object(record_to_json(row, schema.fields.clone())?).to_string();
-                self.send(
-                    BaseRecord::to(self.config.topic.as_str())
-                        .key(self.gen_message_key().as_bytes())
-                        .payload(record.as_bytes()),
-                )
-                .await?;
+                self.send_record(self.gen_message_key(), record).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Replays the records of `transaction` in a new transaction, and commits it along with its
+    /// epoch marked as committed for the consumer group of `epoch_consumer`.
+    async fn replay(
+        &self,
+        epoch_consumer: &BaseConsumer,
+        transaction: &PreCommittedTransaction,
+    ) -> Result<()> {
+        self.conductor.start_transaction().await?;
+        for (key, payload) in &transaction.records {
+            let record = BaseRecord::to(self.config.topic.as_str())
+                .key(key.as_bytes())
+                .payload(payload.as_bytes());
+            if let Err(e) = self.send(record).await {
+                self.conductor.abort_transaction().await?;
+                return Err(e.into());
             }
         }
+        self.conductor.flush().await?;
+        self.conductor
+            .commit_transaction_with(epoch_consumer, transaction.epoch)
+            .await?;
         Ok(())
     }
 }
@@ -274,21 +314,40 @@ impl Sink for KafkaSink {
         Ok(())
     }
 
+    async fn pre_commit(&mut self, epoch: u64) -> Result<PreCommittedTransaction> {
+        // Make sure all messages are delivered, so that the transaction can be committed once the
+        // epoch is committed.
+        self.do_with_retry(|conductor| conductor.flush()).await?;
+        self.pre_committed_epoch = Some(epoch);
+        tracing::debug!("pre-commit epoch {}", epoch);
+        Ok(PreCommittedTransaction {
+            transactional_id: self.config.identifier.clone(),
+            epoch,
+            records: std::mem::take(&mut self.records),
+        })
+    }
+
     async fn commit(&mut self) -> Result<()> {
+        let epoch = match self.in_transaction_epoch.take() {
+            Some(epoch) => epoch,
+            None => {
+                tracing::error!(
+                    "commit without begin_epoch, last success epoch {:?}",
+                    self.state
+                );
+                return Err(SinkError::Kafka(KafkaError::Canceled));
+            }
+        };
+        // The pre-committed epoch is marked as committed along with the transaction.
+        let committed_epoch = self.pre_committed_epoch.take().unwrap_or(epoch);
+
         self.do_with_retry(|conductor| conductor.flush()) // flush before commit
             .await?;
 
-        self.do_with_retry(|conductor| conductor.commit_transaction())
+        self.do_with_retry(|conductor| conductor.commit_transaction(committed_epoch))
             .await?;
-        if let Some(epoch) = self.in_transaction_epoch.take() {
-            self.state = KafkaSinkState::Running(epoch);
-        } else {
-            tracing::error!(
-                "commit without begin_epoch, last success epoch {:?}",
-                self.state
-            );
-            return Err(SinkError::Kafka(KafkaError::Canceled));
-        }
+        self.records.clear();
+        self.state = KafkaSinkState::Running(epoch);
         tracing::debug!("commit epoch {:?}", self.state);
         Ok(())
     }
@@ -298,6 +357,54 @@ impl Sink for KafkaSink {
             .await?;
         tracing::debug!("abort epoch {:?}", self.in_transaction_epoch);
         self.in_transaction_epoch = None;
+        self.pre_committed_epoch = None;
+        self.records.clear();
+        Ok(())
+    }
+
+    async fn recover(
+        &mut self,
+        pre_committed: Vec<PreCommittedTransaction>,
+        committed_epoch: u64,
+    ) -> Result<()> {
+        // The producer has aborted the pending transaction of the sink with the same
+        // transactional id on creation. The ones of other transactional ids, i.e. pre-committed
+        // by other actors before scaling, are left to time out, as the actors may be alive.
+        for transaction in pre_committed {
+            if transaction.epoch > committed_epoch {
+                // Not committed by the storage, so its messages will be replayed by the upstream.
+                tracing::info!(
+                    "abort epoch {} pre-committed before recovery",
+                    transaction.epoch
+                );
+                continue;
+            }
+
+            let other_consumer = if transaction.transactional_id == self.config.identifier {
+                None
+            } else {
+                Some(create_epoch_consumer(&self.config, &transaction.transactional_id).await?)
+            };
+            let epoch_consumer = other_consumer.as_ref().unwrap_or(&self.conductor.consumer);
+            let kafka_committed_epoch =
+                fetch_committed_epoch(epoch_consumer, &self.config.topic, self.config.timeout)
+                    .await?;
+            if kafka_committed_epoch.map_or(false, |epoch| epoch >= transaction.epoch) {
+                tracing::info!(
+                    "epoch {} pre-committed before recovery has been committed",
+                    transaction.epoch
+                );
+                continue;
+            }
+
+            // Otherwise the transaction has been or will be aborted, so replay its records.
+            self.replay(epoch_consumer, &transaction).await?;
+            tracing::info!(
+                "replay epoch {} pre-committed before recovery, {} records",
+                transaction.epoch,
+                transaction.records.len()
+            );
+        }
         Ok(())
     }
 }
@@ -430,10 +537,48 @@ fn schema_to_json(schema: &Schema) -> Value {
     })
 }
 
+/// Creates a consumer of the group named after `transactional_id`, only used to commit and fetch
+/// the epoch committed by the transactions with the transactional id.
+async fn create_epoch_consumer(
+    config: &KafkaConfig,
+    transactional_id: &str,
+) -> KafkaResult<BaseConsumer> {
+    ClientConfig::new()
+        .set("bootstrap.servers", &config.brokers)
+        .set("group.id", transactional_id)
+        .set("enable.auto.commit", "false")
+        .set("isolation.level", "read_committed")
+        .create_with_context(DefaultConsumerContext)
+        .await
+}
+
+/// Returns the epoch last committed for the consumer group of `consumer`, if any.
+async fn fetch_committed_epoch(
+    consumer: &BaseConsumer,
+    topic: &str,
+    timeout: Duration,
+) -> KafkaResult<Option<u64>> {
+    let mut partitions = TopicPartitionList::new();
+    partitions.add_partition(topic, 0);
+    let offsets = consumer.committed_offsets(partitions, timeout).await?;
+    let epoch = offsets
+        .find_partition(topic, 0)
+        .and_then(|elem| match elem.offset() {
+            Offset::Offset(epoch) => Some(epoch as u64),
+            _ => None,
+        });
+    Ok(epoch)
+}
+
 /// the struct conducts all transactions with Kafka
+///
+/// Each transaction is committed together with its epoch as the offset of partition 0 of the topic,
+/// committed for the consumer group named after the transactional id. So the last committed epoch
+/// can be told after recovery, even if the transaction was committed right before the failure.
 pub struct KafkaTransactionConductor {
     properties: KafkaConfig,
     inner: ThreadedProducer<DefaultProducerContext>,
+    consumer: BaseConsumer,
 }
 
 impl KafkaTransactionConductor {
@@ -445,20 +590,54 @@ impl KafkaTransactionConductor {
             .create()
             .await?;
 
+        // Fences the previous producers with the same transactional id, and aborts their pending
+        // transactions.
         inner.init_transactions(config.timeout).await?;
 
+        let consumer = create_epoch_consumer(&config, &config.identifier).await?;
+
         Ok(KafkaTransactionConductor {
             properties: config,
             inner,
+            consumer,
         })
     }
 
+    /// Returns the epoch of the last transaction committed with the transactional id, if any.
+    async fn committed_epoch(&self) -> KafkaResult<Option<u64>> {
+        fetch_committed_epoch(
+            &self.consumer,
+            &self.properties.topic,
+            self.properties.timeout,
+        )
+        .await
+    }
+
     #[expect(clippy::unused_async)]
     async fn start_transaction(&self) -> KafkaResult<()> {
         self.inner.begin_transaction()
     }
 
-    async fn commit_transaction(&self) -> KafkaResult<()> {
+    /// Commits the transaction, and marks `epoch` as committed atomically.
+    async fn commit_transaction(&self, epoch: u64) -> KafkaResult<()> {
+        self.commit_transaction_with(&self.consumer, epoch).await
+    }
+
+    /// Commits the transaction, and marks `epoch` as committed for the consumer group of
+    /// `epoch_consumer` atomically.
+    async fn commit_transaction_with(
+        &self,
+        epoch_consumer: &BaseConsumer,
+        epoch: u64,
+    ) -> KafkaResult<()> {
+        let mut offsets = TopicPartitionList::new();
+        offsets.add_partition_offset(&self.properties.topic, 0, Offset::Offset(epoch as i64))?;
+        let group_metadata = epoch_consumer
+            .group_metadata()
+            .ok_or(KafkaError::Canceled)?;
+        self.inner
+            .send_offsets_to_transaction(&offsets, &group_metadata, self.properties.timeout)
+            .await?;
         self.inner.commit_transaction(self.properties.timeout).await
     }
 
@@ -532,6 +711,55 @@ mod test {
         Ok(())
     }
 
+    #[ignore]
+    #[tokio::test]
+    async fn test_kafka_recover_pre_committed() -> Result<()> {
+        let properties = hashmap! {
+            "kafka.brokers".to_string() => "localhost:29092".to_string(),
+            "identifier".to_string() => "test_sink_2".to_string(),
+            "format".to_string() => "append_only".to_string(),
+            "kafka.topic".to_string() => "test_topic".to_string(),
+        };
+        let kafka_config = KafkaConfig::from_hashmap(properties)?;
+        let schema = Schema::new(vec![Field {
+            data_type: DataType::Int32,
+            name: "id".into(),
+            sub_fields: vec![],
+            type_name: "".into(),
+        }]);
+        let chunk = StreamChunk::from_pretty(
+            " i
+            + 1",
+        );
+
+        // The transaction pre-committed before a restart is aborted by the new producer, and is
+        // replayed if its epoch has been committed by the storage.
+        let mut sink = KafkaSink::new(kafka_config.clone()).await?;
+        sink.begin_epoch(1).await?;
+        sink.write_batch(chunk.clone(), &schema).await?;
+        let transaction = sink.pre_commit(1).await?;
+        assert_eq!(transaction.records.len(), 1);
+        drop(sink);
+        let mut sink = KafkaSink::new(kafka_config.clone()).await?;
+        assert_eq!(sink.conductor.committed_epoch().await?, None);
+        sink.recover(vec![transaction.clone()], 1).await?;
+        assert_eq!(sink.conductor.committed_epoch().await?, Some(1));
+
+        // Replaying it again is a no-op as the epoch is marked as committed.
+        sink.recover(vec![transaction], 1).await?;
+
+        // The transaction of an epoch not committed by the storage is left aborted.
+        sink.begin_epoch(2).await?;
+        sink.write_batch(chunk, &schema).await?;
+        let transaction = sink.pre_commit(2).await?;
+        drop(sink);
+        let mut sink = KafkaSink::new(kafka_config).await?;
+        sink.recover(vec![transaction], 1).await?;
+        assert_eq!(sink.conductor.committed_epoch().await?, Some(1));
+
+        Ok(())
+    }
+
     #[test]
     fn test_chunk_to_json() -> Result<()> {
         let chunk = StreamChunk::from_pretty(
@@ -39,12 +39,37 @@ pub trait Sink {
     // start a transaction with epoch number. Note that epoch number should be increasing.
     async fn begin_epoch(&mut self, epoch: u64) -> Result<()>;
 
+    // pre-commits the current transaction on the checkpoint of `epoch`, i.e. flushes all messages
+    // in the transaction, so that it can be committed once the epoch is committed by the storage.
+    // Only called on sinks supporting two-phase commit.
+    // Returns the transaction to be persisted, so that it can be resolved by `recover` after a
+    // failure.
+    async fn pre_commit(&mut self, epoch: u64) -> Result<PreCommittedTransaction>;
+
     // commits the current transaction and marks all messages in the transaction success.
     async fn commit(&mut self) -> Result<()>;
 
     // aborts the current transaction because some error happens. we should rollback to the last
     // commit point.
     async fn abort(&mut self) -> Result<()>;
+
+    // resolves the transactions pre-committed before recovery: makes sure each one is committed
+    // exactly once if its epoch is not later than `committed_epoch`, i.e. has been committed by
+    // the storage, otherwise aborts it. Only called on sinks supporting two-phase commit.
+    async fn recover(
+        &mut self,
+        pre_committed: Vec<PreCommittedTransaction>,
+        committed_epoch: u64,
+    ) -> Result<()>;
+}
+
+/// A transaction pre-committed by a sink, persisted in the state table of the sink executor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreCommittedTransaction {
+    pub transactional_id: String,
+    pub epoch: u64,
+    /// Records sent in the transaction, as (key, payload).
+    pub records: Vec<(String, String)>,
 }
 
 #[derive(Clone, Debug, EnumAsInner)]
@@ -107,6 +132,17 @@ impl SinkImpl {
         }
     }
 
+    /// Whether the sink commits its transactions in two phases aligned with checkpoints. If so,
+    /// `pre_commit` is called on checkpoint barriers, and `commit` is called after the epoch is
+    /// committed by the storage.
+    pub fn supports_two_phase_commit(&self) -> bool {
+        match self {
+            SinkImpl::MySql(_) => false,
+            SinkImpl::Redis(_) => false,
+            SinkImpl::Kafka(_) => true,
+        }
+    }
+
     pub async fn prepare(&mut self, schema: &Schema) -> Result<()> {
         match self {
             SinkImpl::MySql(sink) => sink.prepare(schema).await,
@@ -133,6 +169,14 @@ impl Sink for SinkImpl {
         }
     }
 
+    async fn pre_commit(&mut self, epoch: u64) -> Result<PreCommittedTransaction> {
+        match self {
+            SinkImpl::MySql(sink) => sink.pre_commit(epoch).await,
+            SinkImpl::Redis(sink) => sink.pre_commit(epoch).await,
+            SinkImpl::Kafka(sink) => sink.pre_commit(epoch).await,
+        }
+    }
+
     async fn commit(&mut self) -> Result<()> {
         match self {
             SinkImpl::MySql(sink) => sink.commit().await,
@@ -148,6 +192,18 @@ impl Sink for SinkImpl {
             SinkImpl::Kafka(sink) => sink.abort().await,
         }
     }
+
+    async fn recover(
+        &mut self,
+        pre_committed: Vec<PreCommittedTransaction>,
+        committed_epoch: u64,
+    ) -> Result<()> {
+        match self {
+            SinkImpl::MySql(sink) => sink.recover(pre_committed, committed_epoch).await,
+            SinkImpl::Redis(sink) => sink.recover(pre_committed, committed_epoch).await,
+            SinkImpl::Kafka(sink) => sink.recover(pre_committed, committed_epoch).await,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, SinkError>;
@@ -164,6 +220,8 @@ pub enum SinkError {
     JsonParse(String),
     #[error("config error: {0}")]
     Config(String),
+    #[error("not supported: {0}")]
+    NotSupported(String),
 }
 
 impl From<SinkError> for RwError {
@@ -25,7 +25,7 @@ use piestream_common::catalog::Schema;
 use piestream_common::types::{DataType, Datum, Decimal, ScalarImpl};
 use strum_macros;
 
-use crate::sink::{Result, Sink, SinkError};
+use crate::sink::{PreCommittedTransaction, Result, Sink, SinkError};
 
 pub const MYSQL_SINK: &str = "mysql";
 
@@ -209,6 +209,12 @@ impl Sink for MySqlSink {
         Ok(())
     }
 
+    async fn pre_commit(&mut self, _epoch: u64) -> Result<PreCommittedTransaction> {
+        Err(SinkError::NotSupported(
+            "two-phase commit of MySQL sink".to_string(),
+        ))
+    }
+
     async fn commit(&mut self) -> Result<()> {
         let mut txn = self.conn.start_transaction(TxOpts::default()).await?;
         for (chunk, schema) in &self.chunk_cache {
@@ -223,6 +229,16 @@ impl Sink for MySqlSink {
     async fn abort(&mut self) -> Result<()> {
         Ok(())
     }
+
+    async fn recover(
+        &mut self,
+        _pre_committed: Vec<PreCommittedTransaction>,
+        _committed_epoch: u64,
+    ) -> Result<()> {
+        Err(SinkError::NotSupported(
+            "two-phase commit of MySQL sink".to_string(),
+        ))
+    }
 }
 
 async fn write_to_mysql<'a>(
@@ -16,7 +16,7 @@ use async_trait::async_trait;
 use piestream_common::array::StreamChunk;
 use piestream_common::catalog::Schema;
 
-use crate::sink::{Result, Sink};
+use crate::sink::{PreCommittedTransaction, Result, Sink, SinkError};
 
 #[derive(Clone, Debug)]
 pub struct RedisConfig;
@@ -40,6 +40,12 @@ impl Sink for RedisSink {
         todo!()
     }
 
+    async fn pre_commit(&mut self, _epoch: u64) -> Result<PreCommittedTransaction> {
+        Err(SinkError::NotSupported(
+            "two-phase commit of Redis sink".to_string(),
+        ))
+    }
+
     async fn commit(&mut self) -> Result<()> {
         todo!()
     }
@@ -47,4 +53,14 @@ impl Sink for RedisSink {
     async fn abort(&mut self) -> Result<()> {
         todo!()
     }
+
+    async fn recover(
+        &mut self,
+        _pre_committed: Vec<PreCommittedTransaction>,
+        _committed_epoch: u64,
+    ) -> Result<()> {
+        Err(SinkError::NotSupported(
+            "two-phase commit of Redis sink".to_string(),
+        ))
+    }
 }
@@ -0,0 +1,71 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use comfy_table::{Row, Table};
+use piestream_meta::backup_restore::RestoreOpts;
+
+use crate::common::MetaServiceOpts;
+
+pub async fn backup_meta() -> anyhow::Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    let snapshot_id = meta_client.backup_meta().await?;
+    println!("Meta snapshot {} is created", snapshot_id);
+    Ok(())
+}
+
+pub async fn list_meta_snapshots() -> anyhow::Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    let mut snapshot_metadata = meta_client.list_meta_snapshot_metadata().await?;
+    snapshot_metadata.sort_by_key(|metadata| metadata.id);
+
+    let mut table = Table::new();
+    table.set_header({
+        let mut row = Row::new();
+        row.add_cell("Id".into());
+        row.add_cell("Hummock Version".into());
+        row.add_cell("Max Committed Epoch".into());
+        row.add_cell("Safe Epoch".into());
+        row.add_cell("SSTs".into());
+        row
+    });
+    for metadata in snapshot_metadata {
+        let mut row = Row::new();
+        row.add_cell(metadata.id.into());
+        row.add_cell(metadata.hummock_version_id.into());
+        row.add_cell(metadata.max_committed_epoch.into());
+        row.add_cell(metadata.safe_epoch.into());
+        row.add_cell(metadata.ssts.len().into());
+        table.add_row(row);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+pub async fn delete_meta_snapshots(snapshot_ids: Vec<u64>) -> anyhow::Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    meta_client.delete_meta_snapshot(&snapshot_ids).await?;
+    println!("Meta snapshots {:?} are deleted", snapshot_ids);
+    Ok(())
+}
+
+/// Unlike other commands, restoring writes to the meta store directly, so it doesn't require a
+/// running meta node.
+pub async fn restore_meta(opts: RestoreOpts) -> anyhow::Result<()> {
+    piestream_meta::backup_restore::restore(opts).await?;
+    println!("Meta snapshot is restored");
+    Ok(())
+}
@@ -22,8 +22,10 @@ mod disable_commit_epoch;
 mod list_version_deltas;
 mod trigger_full_gc;
 mod trigger_manual_compaction;
+mod verify;
 
 pub use disable_commit_epoch::*;
 pub use list_version_deltas::*;
 pub use trigger_full_gc::*;
 pub use trigger_manual_compaction::*;
+pub use verify::*;
@@ -14,7 +14,7 @@
 
 use std::collections::HashMap;
 
-use bytes::{Buf, Bytes};
+use bytes::Buf;
 use piestream_common::types::DataType;
 use piestream_common::util::value_encoding::deserialize_cell;
 use piestream_frontend::TableCatalog;
@@ -25,7 +25,7 @@ use piestream_object_store::object::BlockLocation;
 use piestream_rpc_client::MetaClient;
 use piestream_storage::hummock::value::HummockValue;
 use piestream_storage::hummock::{
-    Block, BlockHolder, BlockIterator, CompressionAlgorithm, SstableMeta, SstableStore,
+    Block, BlockHolder, BlockIterator, CompressionAlgorithm, Sstable, SstableStore,
 };
 use piestream_storage::monitor::StoreLocalStatistic;
 use piestream_storage::row_serde::row_serde_util::deserialize_column_id;
@@ -72,10 +72,14 @@ pub async fn sst_dump() -> anyhow::Result<()> {
 
             println!("Estimated Table Size: {}", sstable_meta.estimated_size);
             println!("Bloom Filter Size: {}", sstable_meta.bloom_filter.len());
+            println!(
+                "Compression Dictionary: {}",
+                sstable_meta.compression_dictionary_id
+            );
             println!("Key Count: {}", sstable_meta.key_count);
             println!("Version: {}", sstable_meta.version);
 
-            print_blocks(id, &table_data, sstable_store, sstable_meta).await?;
+            print_blocks(id, &table_data, sstable_store, sstable).await?;
         }
     }
     hummock_opts.shutdown().await;
@@ -108,9 +112,13 @@ async fn print_blocks(
     id: HummockSstableId,
     table_data: &TableData,
     sstable_store: &SstableStore,
-    sstable_meta: &SstableMeta,
+    sstable: &Sstable,
 ) -> anyhow::Result<()> {
+    let sstable_meta = &sstable.meta;
     let data_path = sstable_store.get_sst_data_path(id);
+    let compression_dictionary = sstable_store
+        .decoder_dictionary(sstable_meta.compression_dictionary_id)
+        .await?;
 
     println!("Blocks:");
     for (i, block_meta) in sstable_meta.block_metas.iter().enumerate() {
@@ -135,25 +143,21 @@ async fn print_blocks(
             block_meta.offset, block_meta.len, checksum, compression
         );
 
-        print_kv_pairs(
+        let block = Block::decode_with_dictionary(
             block_data,
-            table_data,
             block_meta.uncompressed_size as usize,
+            compression_dictionary.as_deref(),
         )?;
+        print_kv_pairs(Box::new(block), table_data)?;
     }
 
     Ok(())
 }
 
 /// Prints the data of KV-Pairs of a given block out to the terminal.
-fn print_kv_pairs(
-    block_data: Bytes,
-    table_data: &TableData,
-    uncompressed_capacity: usize,
-) -> anyhow::Result<()> {
+fn print_kv_pairs(block: Box<Block>, table_data: &TableData) -> anyhow::Result<()> {
     println!("\tKV-Pairs:");
 
-    let block = Box::new(Block::decode(block_data, uncompressed_capacity).unwrap());
     let holder = BlockHolder::from_owned_block(block);
     let mut block_iter = BlockIterator::new(holder);
     block_iter.seek_to_first();
@@ -164,10 +168,15 @@ fn print_kv_pairs(
 
         let full_val = block_iter.value();
         let humm_val = HummockValue::from_slice(block_iter.value())?;
-        let (is_put, user_val) = match humm_val {
-            HummockValue::Put(uval) => (true, uval),
-            HummockValue::Delete => (false, &[] as &[u8]),
+        let (value_type, user_val) = match humm_val {
+            HummockValue::Put(uval) => ("Put", uval),
+            HummockValue::Delete => ("Delete", &[] as &[u8]),
+            HummockValue::Blob(pointer) => {
+                println!("\t\t      blob: {:?}", pointer);
+                ("Blob", &[] as &[u8])
+            }
         };
+        let is_put = value_type == "Put";
 
         let epoch = get_epoch(full_key);
 
@@ -176,7 +185,7 @@ fn print_kv_pairs(
         println!("\t\t  user key: {:02x?}", user_key);
         println!("\t\tuser value: {:02x?}", user_val);
         println!("\t\t     epoch: {}", epoch);
-        println!("\t\t      type: {}", if is_put { "Put" } else { "Delete" });
+        println!("\t\t      type: {}", value_type);
 
         print_table_column(full_key, user_val, table_data, is_put)?;
 
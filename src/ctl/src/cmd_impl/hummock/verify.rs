@@ -0,0 +1,465 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+use bytes::Buf;
+use piestream_hummock_sdk::compaction_group::hummock_version_ext::{
+    HummockVersionDeltaExt, HummockVersionExt,
+};
+use piestream_hummock_sdk::key::user_key;
+use piestream_hummock_sdk::{HummockSstableId, VersionedComparator};
+use piestream_object_store::object::{BlockLocation, ObjectMetadata};
+use piestream_pb::hummock::{LevelType, SstableInfo};
+use piestream_storage::hummock::{
+    xxhash64_verify, Block, BlockHolder, BlockIterator, SstableStore,
+};
+use piestream_storage::monitor::StoreLocalStatistic;
+
+use crate::common::HummockServiceOpts;
+
+/// Problems found by [`verify`].
+#[derive(Default)]
+struct VerifyReport {
+    /// SSTs referenced by the current version but missing from the object store.
+    dangling_ssts: Vec<HummockSstableId>,
+    /// Blob files referenced by the current version but missing from the object store.
+    dangling_blobs: Vec<HummockSstableId>,
+    /// SSTs that exist but fail checksum, ordering or bloom filter checks.
+    corrupted_ssts: Vec<HummockSstableId>,
+    /// Objects in the object store that are not referenced by the current version.
+    orphan_objects: Vec<String>,
+    /// Objects that are not referenced by the current version, but are retained for older
+    /// versions or meta backups, with the reason.
+    retained_objects: Vec<(String, String)>,
+    /// Number of levels whose SSTs overlap although the level is non-overlapping.
+    overlapping_levels: usize,
+}
+
+/// Verifies that the current Hummock version is consistent with the object store.
+///
+/// If `repair` is set, SSTs that are missing from the object store are removed from the version
+/// after confirmation.
+pub async fn verify(repair: bool) -> anyhow::Result<()> {
+    let mut hummock_opts = HummockServiceOpts::from_env()?;
+    let (meta_client, hummock) = hummock_opts.create_hummock_store().await?;
+    let version = hummock
+        .local_version_manager()
+        .get_pinned_version()
+        .version();
+    let sstable_store = &*hummock.sstable_store();
+    let mut report = VerifyReport::default();
+
+    println!("Verifying version {}", version.id);
+    for level in version.get_combined_levels() {
+        if level.level_type == LevelType::Nonoverlapping as i32
+            && !verify_non_overlapping(&level.table_infos)
+        {
+            println!(
+                "Level {} (sub level {}) is non-overlapping but its SSTs overlap",
+                level.level_idx, level.sub_level_id
+            );
+            report.overlapping_levels += 1;
+        }
+        for sstable_info in &level.table_infos {
+            let id = sstable_info.id;
+            let data_path = sstable_store.get_sst_data_path(id);
+            if let Err(e) = sstable_store.store().metadata(&data_path).await {
+                println!("SST {}: data object {} not found: {}", id, data_path, e);
+                report.dangling_ssts.push(id);
+                continue;
+            }
+            if let Err(e) = verify_sst(sstable_store, sstable_info).await {
+                println!("SST {}: {}", id, e);
+                report.corrupted_ssts.push(id);
+            }
+        }
+    }
+
+    for blob_id in version.get_blob_ids() {
+        let blob_path = sstable_store.get_blob_data_path(blob_id);
+        if let Err(e) = sstable_store.store().metadata(&blob_path).await {
+            println!("Blob {}: object {} not found: {}", blob_id, blob_path, e);
+            report.dangling_blobs.push(blob_id);
+        }
+    }
+
+    let referenced_ids: HashSet<HummockSstableId> = version
+        .get_sst_ids()
+        .into_iter()
+        .chain(version.get_blob_ids())
+        .collect();
+    // SSTs removed by the deltas may still be read by older versions pinned by workers, and are
+    // not deleted by full GC, as well as the SSTs referenced by meta backups.
+    let mut retained_ids = HashMap::new();
+    for delta in meta_client
+        .list_version_deltas(0, u32::MAX)
+        .await?
+        .version_deltas
+    {
+        for id in delta
+            .get_removed_sst_ids()
+            .into_iter()
+            .chain(delta.get_inserted_blob_ids())
+        {
+            retained_ids.insert(id, "older version".to_string());
+        }
+    }
+    for snapshot in meta_client.list_meta_snapshot_metadata().await? {
+        for id in snapshot.ssts.into_iter().chain(snapshot.blob_ids) {
+            retained_ids.insert(id, format!("meta backup {}", snapshot.id));
+        }
+    }
+    classify_unreferenced_objects(
+        sstable_store,
+        sstable_store.list_ssts_from_object_store().await?,
+        &referenced_ids,
+        &retained_ids,
+        &mut report,
+    );
+
+    println!("-------------------------------------");
+    println!("Dangling SSTs: {:?}", report.dangling_ssts);
+    println!("Dangling blobs: {:?}", report.dangling_blobs);
+    println!("Corrupted SSTs: {:?}", report.corrupted_ssts);
+    println!("Overlapping levels: {}", report.overlapping_levels);
+    println!("Orphan objects: {}", report.orphan_objects.len());
+    for object in &report.orphan_objects {
+        println!("\t{}", object);
+    }
+    println!(
+        "Objects retained for older versions or meta backups: {}",
+        report.retained_objects.len()
+    );
+    for (object, reason) in &report.retained_objects {
+        println!("\t{} ({})", object, reason);
+    }
+
+    if repair && !report.dangling_ssts.is_empty() {
+        print!(
+            "Remove dangling SSTs {:?} from the version? [y/N] ",
+            report.dangling_ssts
+        );
+        std::io::stdout().flush()?;
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if input.trim().eq_ignore_ascii_case("y") {
+            let removed_sst_ids = meta_client
+                .risectl_remove_dangling_ssts(report.dangling_ssts.clone())
+                .await?;
+            println!("Removed SSTs: {:?}", removed_sst_ids);
+        } else {
+            println!("Aborted.");
+        }
+    }
+
+    hummock_opts.shutdown().await;
+    Ok(())
+}
+
+/// Reports `objects` that are not referenced by the current version, i.e. `referenced_ids`, as
+/// orphans, unless they are retained for the reason in `retained_ids`. Objects of SSTs that are
+/// being uploaded or not yet committed are also reported as orphans.
+fn classify_unreferenced_objects(
+    sstable_store: &SstableStore,
+    objects: Vec<ObjectMetadata>,
+    referenced_ids: &HashSet<HummockSstableId>,
+    retained_ids: &HashMap<HummockSstableId, String>,
+    report: &mut VerifyReport,
+) {
+    for object in objects {
+        if !(object.key.ends_with(".data")
+            || object.key.ends_with(".meta")
+            || object.key.ends_with(".blob"))
+        {
+            continue;
+        }
+        let id = sstable_store.get_sst_id_from_path(&object.key);
+        if referenced_ids.contains(&id) {
+            continue;
+        }
+        match retained_ids.get(&id) {
+            Some(reason) => report.retained_objects.push((object.key, reason.clone())),
+            None => report.orphan_objects.push(object.key),
+        }
+    }
+}
+
+/// Returns whether the key ranges of `table_infos` are sorted and pairwise disjoint.
+fn verify_non_overlapping(table_infos: &[SstableInfo]) -> bool {
+    table_infos.windows(2).all(|pair| {
+        match (pair[0].key_range.as_ref(), pair[1].key_range.as_ref()) {
+            (Some(prev), Some(next)) => {
+                !prev.inf
+                    && VersionedComparator::compare_key(&prev.right, &next.left) == Ordering::Less
+            }
+            _ => false,
+        }
+    })
+}
+
+/// Checks block checksums, key ordering, key range and bloom filter of a single SST.
+async fn verify_sst(
+    sstable_store: &SstableStore,
+    sstable_info: &SstableInfo,
+) -> anyhow::Result<()> {
+    let sstable_cache = sstable_store
+        .sstable(sstable_info, &mut StoreLocalStatistic::default())
+        .await?;
+    let sstable = sstable_cache.value().as_ref();
+    let data_path = sstable_store.get_sst_data_path(sstable_info.id);
+    let key_range = sstable_info.key_range.as_ref();
+    let compression_dictionary = sstable_store
+        .decoder_dictionary(sstable.meta.compression_dictionary_id)
+        .await?;
+
+    let mut prev_key: Option<Vec<u8>> = None;
+    for (i, block_meta) in sstable.meta.block_metas.iter().enumerate() {
+        let block_loc = BlockLocation {
+            offset: block_meta.offset as usize,
+            size: block_meta.len as usize,
+        };
+        let block_data = sstable_store
+            .store()
+            .read(&data_path, Some(block_loc))
+            .await?;
+        let len = block_data.len();
+        if len < 9 {
+            anyhow::bail!("block {} is truncated: {} bytes", i, len);
+        }
+        let checksum = (&block_data[len - 8..]).get_u64_le();
+        xxhash64_verify(&block_data[..len - 8], checksum)
+            .map_err(|e| anyhow::anyhow!("block {}: {}", i, e))?;
+
+        let block = Block::decode_with_dictionary(
+            block_data,
+            block_meta.uncompressed_size as usize,
+            compression_dictionary.as_deref(),
+        )?;
+        let mut block_iter = BlockIterator::new(BlockHolder::from_owned_block(Box::new(block)));
+        block_iter.seek_to_first();
+        while block_iter.is_valid() {
+            let key = block_iter.key();
+            if let Some(prev_key) = prev_key.as_ref() {
+                if VersionedComparator::compare_key(prev_key, key) != Ordering::Less {
+                    anyhow::bail!("block {}: key {:02x?} is out of order", i, key);
+                }
+            }
+            if let Some(key_range) = key_range {
+                if VersionedComparator::compare_key(key, &key_range.left) == Ordering::Less
+                    || (!key_range.inf
+                        && VersionedComparator::compare_key(key, &key_range.right)
+                            == Ordering::Greater)
+                {
+                    anyhow::bail!("block {}: key {:02x?} is out of the key range", i, key);
+                }
+            }
+            if sstable.surely_not_have_user_key(user_key(key)) {
+                anyhow::bail!("block {}: key {:02x?} is rejected by bloom filter", i, key);
+            }
+            prev_key = Some(key.to_vec());
+            block_iter.next();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use piestream_hummock_sdk::key::key_with_epoch;
+    use piestream_pb::hummock::KeyRange;
+    use piestream_storage::hummock::iterator::test_utils::mock_sstable_store;
+    use piestream_storage::hummock::sstable_store::SstableStoreRef;
+    use piestream_storage::hummock::test_utils::{
+        default_builder_opt_for_test, default_writer_opt_for_test, gen_test_sstable_data, put_sst,
+        test_key_of,
+    };
+    use piestream_storage::hummock::value::HummockValue;
+    use piestream_storage::hummock::{SstableBuilderOptions, SstableMeta};
+
+    use super::*;
+
+    fn sst_info_with_range(left: &[u8], right: &[u8], inf: bool) -> SstableInfo {
+        SstableInfo {
+            key_range: Some(KeyRange {
+                left: key_with_epoch(left.to_vec(), 0),
+                right: key_with_epoch(right.to_vec(), 0),
+                inf,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_non_overlapping() {
+        let sst = |left: &[u8], right: &[u8]| sst_info_with_range(left, right, false);
+        assert!(verify_non_overlapping(&[]));
+        assert!(verify_non_overlapping(&[sst(b"a", b"b"), sst(b"c", b"d")]));
+        // Overlapping.
+        assert!(!verify_non_overlapping(&[sst(b"a", b"c"), sst(b"b", b"d")]));
+        assert!(!verify_non_overlapping(&[sst(b"a", b"b"), sst(b"b", b"c")]));
+        // Out of order.
+        assert!(!verify_non_overlapping(&[sst(b"c", b"d"), sst(b"a", b"b")]));
+        // Unbounded key range followed by others.
+        assert!(!verify_non_overlapping(&[
+            sst_info_with_range(b"a", b"b", true),
+            sst(b"c", b"d")
+        ]));
+        // Missing key range.
+        assert!(!verify_non_overlapping(&[
+            sst(b"a", b"b"),
+            SstableInfo::default()
+        ]));
+    }
+
+    /// Builds an SST of `keys`, and puts it into `sstable_store` after applying `corrupt`.
+    async fn put_test_sst(
+        sstable_store: SstableStoreRef,
+        sst_id: HummockSstableId,
+        opts: SstableBuilderOptions,
+        keys: impl Iterator<Item = Vec<u8>>,
+        corrupt: impl FnOnce(&mut BytesMut, &mut SstableMeta),
+    ) -> SstableInfo {
+        let (data, mut meta) = gen_test_sstable_data(
+            opts,
+            keys.map(|key| (key, HummockValue::put(b"v".to_vec()))),
+        )
+        .await;
+        let mut data = BytesMut::from(&data[..]);
+        corrupt(&mut data, &mut meta);
+        put_sst(
+            sst_id,
+            data.freeze(),
+            meta,
+            sstable_store,
+            default_writer_opt_for_test(),
+        )
+        .await
+        .unwrap()
+    }
+
+    fn assert_err_contains(result: anyhow::Result<()>, expected: &str) {
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains(expected), "unexpected error: {}", err);
+    }
+
+    #[tokio::test]
+    async fn test_verify_sst() {
+        let sstable_store = mock_sstable_store();
+        let opts = default_builder_opt_for_test();
+        let keys = || (0..100).map(test_key_of);
+
+        let sst = put_test_sst(sstable_store.clone(), 1, opts.clone(), keys(), |_, _| {}).await;
+        verify_sst(&sstable_store, &sst).await.unwrap();
+
+        // Keys out of the key range in the version.
+        let mut narrowed_sst = sst.clone();
+        narrowed_sst.key_range.as_mut().unwrap().right = test_key_of(50);
+        assert_err_contains(
+            verify_sst(&sstable_store, &narrowed_sst).await,
+            "out of the key range",
+        );
+
+        let sst = put_test_sst(sstable_store.clone(), 2, opts.clone(), keys(), |data, _| {
+            data[0] ^= 0xff;
+        })
+        .await;
+        assert_err_contains(verify_sst(&sstable_store, &sst).await, "Checksum mismatch");
+
+        // Each key is put into its own block, so that the builder doesn't reject the keys.
+        let mut sst = put_test_sst(
+            sstable_store.clone(),
+            3,
+            SstableBuilderOptions {
+                capacity: 1024,
+                block_capacity: 1,
+                ..opts.clone()
+            },
+            [test_key_of(1), test_key_of(0)].into_iter(),
+            |_, _| {},
+        )
+        .await;
+        sst.key_range = Some(KeyRange {
+            left: test_key_of(0),
+            right: test_key_of(1),
+            inf: false,
+        });
+        assert_err_contains(verify_sst(&sstable_store, &sst).await, "out of order");
+
+        // The bloom filter is built from other keys.
+        let (_, other_meta) = gen_test_sstable_data(
+            opts.clone(),
+            (100..200).map(|i| (test_key_of(i), HummockValue::put(b"v".to_vec()))),
+        )
+        .await;
+        let sst = put_test_sst(sstable_store.clone(), 4, opts, keys(), |_, meta| {
+            meta.bloom_filter = other_meta.bloom_filter;
+        })
+        .await;
+        assert_err_contains(
+            verify_sst(&sstable_store, &sst).await,
+            "rejected by bloom filter",
+        );
+    }
+
+    #[test]
+    fn test_classify_unreferenced_objects() {
+        let sstable_store = mock_sstable_store();
+        let object = |key: String| ObjectMetadata {
+            key,
+            last_modified: 0.0,
+            total_size: 0,
+        };
+        let objects = vec![
+            object(sstable_store.get_sst_data_path(1)),
+            object(sstable_store.get_sst_data_path(2)),
+            object(sstable_store.get_blob_data_path(3)),
+            object(sstable_store.get_sst_data_path(4)),
+            object(sstable_store.get_compression_dictionary_path(5)),
+        ];
+        let referenced_ids = HashSet::from([1]);
+        let retained_ids = HashMap::from([
+            (2, "older version".to_string()),
+            (3, "meta backup 1".to_string()),
+        ]);
+        let mut report = VerifyReport::default();
+        classify_unreferenced_objects(
+            &sstable_store,
+            objects,
+            &referenced_ids,
+            &retained_ids,
+            &mut report,
+        );
+        assert_eq!(
+            report.orphan_objects,
+            vec![sstable_store.get_sst_data_path(4)]
+        );
+        assert_eq!(
+            report.retained_objects,
+            vec![
+                (
+                    sstable_store.get_sst_data_path(2),
+                    "older version".to_string()
+                ),
+                (
+                    sstable_store.get_blob_data_path(3),
+                    "meta backup 1".to_string()
+                ),
+            ]
+        );
+    }
+}
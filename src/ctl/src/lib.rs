@@ -15,6 +15,7 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use cmd_impl::bench::BenchCommands;
+use piestream_meta::backup_restore::RestoreOpts;
 
 use crate::cmd_impl::hummock::{list_pinned_snapshots, list_pinned_versions};
 
@@ -50,6 +51,9 @@ enum Commands {
     /// Commands for Benchmarks
     #[clap(subcommand)]
     Bench(BenchCommands),
+    /// Commands for backup and restore of meta
+    #[clap(subcommand)]
+    Backup(BackupCommands),
     /// Commands for tracing the compute nodes
     Trace,
     // TODO(yuhao): profile other nodes
@@ -101,6 +105,13 @@ enum HummockCommands {
         #[clap(short, long = "sst_retention_time_sec", default_value_t = 259200)]
         sst_retention_time_sec: u64,
     },
+    /// verify that SSTs referenced by the current version exist and are intact, and list orphan
+    /// objects in the object store
+    Verify {
+        /// remove SSTs missing from the object store from the version after confirmation
+        #[clap(long)]
+        repair: bool,
+    },
     /// List pinned versions of each worker.
     ListPinnedVersions {},
     /// List pinned snapshots of each worker.
@@ -156,6 +167,22 @@ enum MetaCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum BackupCommands {
+    /// create a meta snapshot, which pins the SSTs it refers to
+    Create,
+    /// list all meta snapshots
+    List,
+    /// delete meta snapshots and unpin the SSTs they refer to
+    Delete {
+        /// ids of the meta snapshots to delete
+        #[clap(required = true)]
+        snapshot_ids: Vec<u64>,
+    },
+    /// restore a meta snapshot to an empty meta store
+    Restore(RestoreOpts),
+}
+
 pub async fn start(opts: CliOpts) -> Result<()> {
     match opts.command {
         Commands::Hummock(HummockCommands::DisableCommitEpoch) => {
@@ -185,6 +212,9 @@ pub async fn start(opts: CliOpts) -> Result<()> {
         Commands::Hummock(HummockCommands::TriggerFullGc {
             sst_retention_time_sec,
         }) => cmd_impl::hummock::trigger_full_gc(sst_retention_time_sec).await?,
+        Commands::Hummock(HummockCommands::Verify { repair }) => {
+            cmd_impl::hummock::verify(repair).await?
+        }
         Commands::Hummock(HummockCommands::ListPinnedVersions {}) => list_pinned_versions().await?,
         Commands::Hummock(HummockCommands::ListPinnedSnapshots {}) => {
             list_pinned_snapshots().await?
@@ -195,6 +225,14 @@ pub async fn start(opts: CliOpts) -> Result<()> {
         }
         Commands::Table(TableCommands::List) => cmd_impl::table::list().await?,
         Commands::Bench(cmd) => cmd_impl::bench::do_bench(cmd).await?,
+        Commands::Backup(BackupCommands::Create) => cmd_impl::backup::backup_meta().await?,
+        Commands::Backup(BackupCommands::List) => cmd_impl::backup::list_meta_snapshots().await?,
+        Commands::Backup(BackupCommands::Delete { snapshot_ids }) => {
+            cmd_impl::backup::delete_meta_snapshots(snapshot_ids).await?
+        }
+        Commands::Backup(BackupCommands::Restore(opts)) => {
+            cmd_impl::backup::restore_meta(opts).await?
+        }
         Commands::Meta(MetaCommands::Pause) => cmd_impl::meta::pause().await?,
         Commands::Meta(MetaCommands::Resume) => cmd_impl::meta::resume().await?,
         Commands::Meta(MetaCommands::ClusterInfo) => cmd_impl::meta::cluster_info().await?,
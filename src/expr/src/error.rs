@@ -38,6 +38,15 @@ pub enum ExprError {
     #[error("Division by zero")]
     DivisionByZero,
 
+    #[error("{0}")]
+    InvalidArgumentForLogarithm(&'static str),
+
+    #[error("{0}")]
+    InvalidArgumentForPower(&'static str),
+
+    #[error("{0}")]
+    InvalidArgumentForWidthBucket(&'static str),
+
     #[error("Parse error: {0}")]
     Parse(&'static str),
 
@@ -54,6 +63,23 @@ pub enum ExprError {
     Internal(#[from] anyhow::Error),
 }
 
+impl ExprError {
+    /// The `SQLSTATE` code that `PostgreSQL` reports for the same error.
+    pub fn sqlstate(&self) -> &'static str {
+        match self {
+            Self::NumericOutOfRange => "22003",
+            Self::DivisionByZero => "22012",
+            Self::InvalidArgumentForLogarithm(_) => "2201E",
+            Self::InvalidArgumentForPower(_) => "2201F",
+            Self::InvalidArgumentForWidthBucket(_) => "2201G",
+            Self::Cast(..) | Self::Cast2(..) | Self::Parse(_) => "22P02",
+            Self::InvalidParam { .. } => "22023",
+            Self::MaxOneRow(_) => "21000",
+            _ => "XX000",
+        }
+    }
+}
+
 impl From<ExprError> for RwError {
     fn from(s: ExprError) -> Self {
         ErrorCode::ExprError(Box::new(s)).into()
@@ -32,6 +32,18 @@ pub enum AggKind {
     StringAgg,
     ApproxCountDistinct,
     ArrayAgg,
+    BoolAnd,
+    BoolOr,
+    BitAnd,
+    BitOr,
+    StddevSamp,
+    StddevPop,
+    VarSamp,
+    VarPop,
+    FirstValue,
+    LastValue,
+    PercentileCont,
+    PercentileDisc,
 }
 
 impl TryFrom<Type> for AggKind {
@@ -47,6 +59,18 @@ impl TryFrom<Type> for AggKind {
             Type::StringAgg => Ok(AggKind::StringAgg),
             Type::ApproxCountDistinct => Ok(AggKind::ApproxCountDistinct),
             Type::ArrayAgg => Ok(AggKind::ArrayAgg),
+            Type::BoolAnd => Ok(AggKind::BoolAnd),
+            Type::BoolOr => Ok(AggKind::BoolOr),
+            Type::BitAnd => Ok(AggKind::BitAnd),
+            Type::BitOr => Ok(AggKind::BitOr),
+            Type::StddevSamp => Ok(AggKind::StddevSamp),
+            Type::StddevPop => Ok(AggKind::StddevPop),
+            Type::VarSamp => Ok(AggKind::VarSamp),
+            Type::VarPop => Ok(AggKind::VarPop),
+            Type::FirstValue => Ok(AggKind::FirstValue),
+            Type::LastValue => Ok(AggKind::LastValue),
+            Type::PercentileCont => Ok(AggKind::PercentileCont),
+            Type::PercentileDisc => Ok(AggKind::PercentileDisc),
             Type::Unspecified => bail!("Unrecognized agg."),
         }
     }
@@ -63,6 +87,18 @@ impl AggKind {
             Self::StringAgg => Type::StringAgg,
             Self::ApproxCountDistinct => Type::ApproxCountDistinct,
             Self::ArrayAgg => Type::ArrayAgg,
+            Self::BoolAnd => Type::BoolAnd,
+            Self::BoolOr => Type::BoolOr,
+            Self::BitAnd => Type::BitAnd,
+            Self::BitOr => Type::BitOr,
+            Self::StddevSamp => Type::StddevSamp,
+            Self::StddevPop => Type::StddevPop,
+            Self::VarSamp => Type::VarSamp,
+            Self::VarPop => Type::VarPop,
+            Self::FirstValue => Type::FirstValue,
+            Self::LastValue => Type::LastValue,
+            Self::PercentileCont => Type::PercentileCont,
+            Self::PercentileDisc => Type::PercentileDisc,
         }
     }
 }
@@ -13,19 +13,21 @@
 // limitations under the License.
 
 use piestream_common::types::{DataType, ScalarImpl};
-use piestream_pb::expr::expr_node::RexNode;
+use piestream_pb::expr::expr_node::{RexNode, Type};
 use piestream_pb::expr::ExprNode;
 
 use crate::expr::expr_binary_bytes::{
-    new_ltrim_characters, new_repeat, new_rtrim_characters, new_substr_start, new_to_char,
-    new_trim_characters,
+    new_lpad, new_ltrim_characters, new_repeat, new_rpad, new_rtrim_characters, new_substr_start,
+    new_to_char, new_trim_characters,
 };
-use crate::expr::expr_binary_nonnull::{new_binary_expr, new_like_default};
+use crate::expr::expr_binary_nonnull::{new_binary_expr, new_ilike_default, new_like_default};
 use crate::expr::expr_binary_nullable::new_nullable_binary_expr;
+use crate::expr::expr_quaternary::new_quaternary_expr;
 use crate::expr::expr_quaternary_bytes::new_overlay_for_exp;
+use crate::expr::expr_ternary::new_ternary_expr;
 use crate::expr::expr_ternary_bytes::{
-    new_overlay_exp, new_replace_expr, new_split_part_expr, new_substr_start_end,
-    new_translate_expr,
+    new_lpad_fill, new_overlay_exp, new_replace_expr, new_rpad_fill, new_split_part_expr,
+    new_substr_start_end, new_translate_expr,
 };
 use crate::expr::expr_to_char_const_tmpl::{ExprToCharConstTmpl, ExprToCharConstTmplContext};
 use crate::expr::expr_unary::{
@@ -77,6 +79,38 @@ pub fn build_nullable_binary_expr_prost(prost: &ExprNode) -> Result<BoxedExpress
     )
 }
 
+pub fn build_ternary_expr_prost(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    let [first, second, third]: [_; 3] = children.try_into().unwrap();
+    let first_expr = expr_build_from_prost(&first)?;
+    let second_expr = expr_build_from_prost(&second)?;
+    let third_expr = expr_build_from_prost(&third)?;
+    new_ternary_expr(
+        prost.get_expr_type().unwrap(),
+        ret_type,
+        first_expr,
+        second_expr,
+        third_expr,
+    )
+}
+
+pub fn build_quaternary_expr_prost(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    let [first, second, third, fourth]: [_; 4] = children.try_into().unwrap();
+    let first_expr = expr_build_from_prost(&first)?;
+    let second_expr = expr_build_from_prost(&second)?;
+    let third_expr = expr_build_from_prost(&third)?;
+    let fourth_expr = expr_build_from_prost(&fourth)?;
+    new_quaternary_expr(
+        prost.get_expr_type().unwrap(),
+        ret_type,
+        first_expr,
+        second_expr,
+        third_expr,
+        fourth_expr,
+    )
+}
+
 pub fn build_overlay_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     let (children, ret_type) = get_children_and_return_type(prost)?;
     ensure!(children.len() == 3 || children.len() == 4);
@@ -183,7 +217,32 @@ pub fn build_like_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     ensure!(children.len() == 2);
     let expr_ia1 = expr_build_from_prost(&children[0])?;
     let expr_ia2 = expr_build_from_prost(&children[1])?;
-    Ok(new_like_default(expr_ia1, expr_ia2, ret_type))
+    match prost.get_expr_type().unwrap() {
+        Type::ILike => Ok(new_ilike_default(expr_ia1, expr_ia2, ret_type)),
+        _ => Ok(new_like_default(expr_ia1, expr_ia2, ret_type)),
+    }
+}
+
+pub fn build_pad_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    ensure!(children.len() == 2 || children.len() == 3);
+    let s = expr_build_from_prost(&children[0])?;
+    let length = expr_build_from_prost(&children[1])?;
+    let left = prost.get_expr_type().unwrap() == Type::Lpad;
+    if children.len() == 2 {
+        Ok(if left {
+            new_lpad(s, length, ret_type)
+        } else {
+            new_rpad(s, length, ret_type)
+        })
+    } else {
+        let fill = expr_build_from_prost(&children[2])?;
+        Ok(if left {
+            new_lpad_fill(s, length, fill, ret_type)
+        } else {
+            new_rpad_fill(s, length, fill, ret_type)
+        })
+    }
 }
 
 pub fn build_translate_expr(prost: &ExprNode) -> Result<BoxedExpression> {
@@ -232,6 +291,16 @@ pub fn build_to_char_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     }
 }
 
+pub fn build_to_timestamp_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, _) = get_children_and_return_type(prost)?;
+    ensure!(children.len() == 1 || children.len() == 2);
+    match children.len() {
+        1 => build_unary_expr_prost(prost),
+        2 => build_binary_expr_prost(prost),
+        _ => unreachable!(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::vec;
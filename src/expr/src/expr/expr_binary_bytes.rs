@@ -21,6 +21,7 @@ use super::Expression;
 use crate::expr::template::BinaryBytesExpression;
 use crate::expr::BoxedExpression;
 use crate::vector_op::concat_op::concat_op;
+use crate::vector_op::pad::{lpad, rpad};
 use crate::vector_op::repeat::repeat;
 use crate::vector_op::substr::*;
 use crate::vector_op::to_char::to_char_timestamp;
@@ -79,6 +80,37 @@ pub fn new_repeat(
         .boxed()
 }
 
+macro_rules! impl_utf8_i32 {
+    ($({ $func_name:ident, $method:ident }),*) => {
+        $(pub fn $func_name(
+            expr_ia1: BoxedExpression,
+            expr_ia2: BoxedExpression,
+            return_type: DataType,
+        ) -> BoxedExpression {
+            BinaryBytesExpression::<Utf8Array, I32Array, _>::new(
+                expr_ia1,
+                expr_ia2,
+                return_type,
+                $method,
+            )
+            .boxed()
+        })*
+    };
+}
+
+macro_rules! for_all_utf8_i32_op {
+    ($macro:ident) => {
+        $macro! {
+            { new_left, left },
+            { new_right, right },
+            { new_lpad, lpad },
+            { new_rpad, rpad }
+        }
+    };
+}
+
+for_all_utf8_i32_op! { impl_utf8_i32 }
+
 macro_rules! impl_utf8_utf8 {
     ($({ $func_name:ident, $method:ident }),*) => {
         $(pub fn $func_name(
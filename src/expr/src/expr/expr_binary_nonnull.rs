@@ -13,22 +13,34 @@
 // limitations under the License.
 
 use piestream_common::array::{
-    Array, BoolArray, DecimalArray, I32Array, IntervalArray, ListArray, NaiveDateArray,
-    NaiveDateTimeArray, StructArray, Utf8Array,
+    Array, BoolArray, DecimalArray, F64Array, I32Array, I64Array, IntervalArray, ListArray,
+    NaiveDateArray, NaiveDateTimeArray, NaiveTimeArray, StructArray, Utf8Array,
 };
 use piestream_common::types::*;
 use piestream_pb::expr::expr_node::Type;
 
-use crate::expr::expr_binary_bytes::new_concat_op;
+use crate::expr::expr_binary_bytes::{new_concat_op, new_left, new_right};
 use crate::expr::template::BinaryExpression;
 use crate::expr::BoxedExpression;
+use crate::vector_op::age::age_timestamp;
 use crate::vector_op::arithmetic_op::*;
 use crate::vector_op::bitwise_op::*;
 use crate::vector_op::cmp::*;
-use crate::vector_op::extract::{extract_from_date, extract_from_timestamp};
-use crate::vector_op::like::like_default;
+use crate::vector_op::date_trunc::{
+    date_trunc_interval, date_trunc_timestamp, date_trunc_timestampz,
+};
+use crate::vector_op::extract::{
+    extract_from_date, extract_from_interval, extract_from_time, extract_from_timestamp,
+    extract_from_timestampz,
+};
+use crate::vector_op::like::{ilike_default, like_default};
+use crate::vector_op::math::{log_decimal, pow_decimal, pow_f64, trunc_digits};
 use crate::vector_op::position::position;
 use crate::vector_op::round::round_digits;
+use crate::vector_op::starts_with::starts_with;
+use crate::vector_op::timestampz::*;
+use crate::vector_op::to_timestamp::{to_date_with_tmpl, to_timestamp_with_tmpl};
+use crate::vector_op::trigonometric::atan2_f64;
 use crate::vector_op::tumble::{tumble_start_date, tumble_start_date_time};
 use crate::{for_all_cmp_variants, ExprError, Result};
 
@@ -334,6 +346,24 @@ fn build_extract_expr(
                 DecimalArray,
                 _,
             >::new(l, r, ret, extract_from_timestamp)),
+            DataType::Timestampz => Box::new(BinaryExpression::<
+                Utf8Array,
+                I64Array,
+                DecimalArray,
+                _,
+            >::new(l, r, ret, extract_from_timestampz)),
+            DataType::Time => Box::new(BinaryExpression::<
+                Utf8Array,
+                NaiveTimeArray,
+                DecimalArray,
+                _,
+            >::new(l, r, ret, extract_from_time)),
+            DataType::Interval => Box::new(BinaryExpression::<
+                Utf8Array,
+                IntervalArray,
+                DecimalArray,
+                _,
+            >::new(l, r, ret, extract_from_interval)),
             _ => {
                 return Err(ExprError::UnsupportedFunction(format!(
                     "Extract ( {:?} ) is not supported yet!",
@@ -344,6 +374,98 @@ fn build_extract_expr(
     Ok(expr)
 }
 
+fn build_pow_expr(
+    ret: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match (l.return_type(), r.return_type()) {
+        (DataType::Float64, DataType::Float64) => {
+            Box::new(BinaryExpression::<F64Array, F64Array, F64Array, _>::new(
+                l, r, ret, pow_f64,
+            ))
+        }
+        (DataType::Decimal, DataType::Decimal) => Box::new(BinaryExpression::<
+            DecimalArray,
+            DecimalArray,
+            DecimalArray,
+            _,
+        >::new(l, r, ret, pow_decimal)),
+        (l_type, r_type) => {
+            return Err(ExprError::UnsupportedFunction(format!(
+                "pow is not supported for ({:?}, {:?})",
+                l_type, r_type
+            )))
+        }
+    };
+
+    Ok(expr)
+}
+
+fn build_date_trunc_expr(
+    ret: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match r.return_type() {
+        DataType::Timestamp => Box::new(BinaryExpression::<
+            Utf8Array,
+            NaiveDateTimeArray,
+            NaiveDateTimeArray,
+            _,
+        >::new(l, r, ret, date_trunc_timestamp)),
+        DataType::Timestampz => {
+            Box::new(BinaryExpression::<Utf8Array, I64Array, I64Array, _>::new(
+                l,
+                r,
+                ret,
+                date_trunc_timestampz,
+            ))
+        }
+        DataType::Interval => Box::new(BinaryExpression::<
+            Utf8Array,
+            IntervalArray,
+            IntervalArray,
+            _,
+        >::new(l, r, ret, date_trunc_interval)),
+        _ => {
+            return Err(ExprError::UnsupportedFunction(format!(
+                "date_trunc is not supported for {:?}",
+                r.return_type()
+            )))
+        }
+    };
+    Ok(expr)
+}
+
+fn build_at_time_zone_expr(
+    ret: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match l.return_type() {
+        DataType::Timestamp => Box::new(BinaryExpression::<
+            NaiveDateTimeArray,
+            Utf8Array,
+            I64Array,
+            _,
+        >::new(l, r, ret, timestamp_at_time_zone)),
+        DataType::Timestampz => Box::new(BinaryExpression::<
+            I64Array,
+            Utf8Array,
+            NaiveDateTimeArray,
+            _,
+        >::new(l, r, ret, timestampz_at_time_zone)),
+        _ => {
+            return Err(ExprError::UnsupportedFunction(format!(
+                "AT TIME ZONE is not supported for {:?}",
+                l.return_type()
+            )))
+        }
+    };
+    Ok(expr)
+}
+
 pub fn new_binary_expr(
     expr_type: Type,
     ret: DataType,
@@ -378,6 +500,8 @@ pub fn new_binary_expr(
                 {
                     { timestamp, interval, timestamp, timestamp_interval_add },
                     { interval, timestamp, timestamp, interval_timestamp_add },
+                    { timestampz, interval, timestampz, timestampz_interval_add },
+                    { interval, timestampz, timestampz, interval_timestampz_add },
                     { interval, date, timestamp, interval_date_add },
                     { interval, time, time, interval_time_add },
                     { date, interval, timestamp, date_interval_add },
@@ -398,6 +522,8 @@ pub fn new_binary_expr(
                 {
                     { timestamp, timestamp, interval, timestamp_timestamp_sub },
                     { timestamp, interval, timestamp, timestamp_interval_sub },
+                    { timestampz, timestampz, interval, timestampz_timestampz_sub },
+                    { timestampz, interval, timestampz, timestampz_interval_sub },
                     { date, date, int32, date_date_sub },
                     { date, interval, timestamp, date_interval_sub },
                     { time, time, interval, time_time_sub },
@@ -502,6 +628,28 @@ pub fn new_binary_expr(
             }
         }
         Type::Extract => build_extract_expr(ret, l, r)?,
+        Type::DateTrunc => build_date_trunc_expr(ret, l, r)?,
+        Type::AtTimeZone => build_at_time_zone_expr(ret, l, r)?,
+        Type::ToTimestamp => Box::new(BinaryExpression::<Utf8Array, Utf8Array, I64Array, _>::new(
+            l,
+            r,
+            ret,
+            to_timestamp_with_tmpl,
+        )),
+        Type::ToDate => Box::new(
+            BinaryExpression::<Utf8Array, Utf8Array, NaiveDateArray, _>::new(
+                l,
+                r,
+                ret,
+                to_date_with_tmpl,
+            ),
+        ),
+        Type::Age => Box::new(BinaryExpression::<
+            NaiveDateTimeArray,
+            NaiveDateTimeArray,
+            IntervalArray,
+            _,
+        >::new(l, r, ret, age_timestamp)),
         Type::RoundDigit => Box::new(
             BinaryExpression::<DecimalArray, I32Array, DecimalArray, _>::new(
                 l,
@@ -510,11 +658,37 @@ pub fn new_binary_expr(
                 round_digits,
             ),
         ),
+        Type::TruncDigit => Box::new(
+            BinaryExpression::<DecimalArray, I32Array, DecimalArray, _>::new(
+                l,
+                r,
+                ret,
+                trunc_digits,
+            ),
+        ),
+        Type::Pow => build_pow_expr(ret, l, r)?,
+        Type::Log => Box::new(BinaryExpression::<
+            DecimalArray,
+            DecimalArray,
+            DecimalArray,
+            _,
+        >::new(l, r, ret, log_decimal)),
+        Type::Atan2 => Box::new(BinaryExpression::<F64Array, F64Array, F64Array, _>::new(
+            l, r, ret, atan2_f64,
+        )),
         Type::Position => Box::new(BinaryExpression::<Utf8Array, Utf8Array, I32Array, _>::new(
             l, r, ret, position,
         )),
+        Type::StartsWith => Box::new(BinaryExpression::<Utf8Array, Utf8Array, BoolArray, _>::new(
+            l,
+            r,
+            ret,
+            starts_with,
+        )),
         Type::TumbleStart => new_tumble_start(l, r, ret)?,
         Type::ConcatOp => new_concat_op(l, r, ret),
+        Type::Left => new_left(l, r, ret),
+        Type::Right => new_right(l, r, ret),
 
         tp => {
             return Err(ExprError::UnsupportedFunction(format!(
@@ -573,6 +747,19 @@ pub fn new_like_default(
     ))
 }
 
+pub fn new_ilike_default(
+    expr_ia1: BoxedExpression,
+    expr_ia2: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(BinaryExpression::<Utf8Array, Utf8Array, BoolArray, _>::new(
+        expr_ia1,
+        expr_ia2,
+        return_type,
+        ilike_default,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::NaiveDate;
@@ -0,0 +1,166 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use piestream_common::array::{
+    Array, ArrayBuilder, ArrayImpl, ArrayRef, DataChunk, Row, Utf8ArrayBuilder,
+};
+use piestream_common::types::{DataType, Datum, Scalar};
+use piestream_pb::expr::expr_node::{RexNode, Type};
+use piestream_pb::expr::ExprNode;
+
+use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression, Expression};
+use crate::vector_op::format::format;
+use crate::{bail, ensure, ExprError, Result};
+
+/// `format(formatstr, args...)`. All arguments are cast to `varchar` by the frontend.
+#[derive(Debug)]
+pub struct FormatExpression {
+    formatstr_expr: BoxedExpression,
+    arg_exprs: Vec<BoxedExpression>,
+}
+
+impl Expression for FormatExpression {
+    fn return_type(&self) -> DataType {
+        DataType::Varchar
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let formatstr_column = self.formatstr_expr.eval_checked(input)?;
+        let formatstr_column = formatstr_column.as_utf8();
+
+        let arg_columns = self
+            .arg_exprs
+            .iter()
+            .map(|c| c.eval_checked(input))
+            .collect::<Result<Vec<_>>>()?;
+        let arg_columns_ref = arg_columns.iter().map(|c| c.as_utf8()).collect_vec();
+
+        let row_len = input.capacity();
+        let vis = input.vis();
+        let mut builder = Utf8ArrayBuilder::new(row_len);
+        let mut args = Vec::with_capacity(arg_columns_ref.len());
+
+        for row_idx in 0..row_len {
+            if !vis.is_set(row_idx) {
+                builder.append(None);
+                continue;
+            }
+            let Some(formatstr) = formatstr_column.value_at(row_idx) else {
+                builder.append(None);
+                continue;
+            };
+            args.clear();
+            args.extend(arg_columns_ref.iter().map(|c| c.value_at(row_idx)));
+            builder.append(Some(&format(formatstr, &args)?));
+        }
+        Ok(Arc::new(ArrayImpl::from(builder.finish())))
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let Some(formatstr) = self.formatstr_expr.eval_row(input)? else {
+            return Ok(None);
+        };
+        let args = self
+            .arg_exprs
+            .iter()
+            .map(|c| c.eval_row(input))
+            .collect::<Result<Vec<_>>>()?;
+        let args = args
+            .iter()
+            .map(|arg| arg.as_ref().map(|s| s.as_utf8().as_str()))
+            .collect_vec();
+        Ok(Some(format(formatstr.as_utf8(), &args)?.to_scalar_value()))
+    }
+}
+
+impl FormatExpression {
+    pub fn new(formatstr_expr: BoxedExpression, arg_exprs: Vec<BoxedExpression>) -> Self {
+        FormatExpression {
+            formatstr_expr,
+            arg_exprs,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ExprNode> for FormatExpression {
+    type Error = ExprError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        ensure!(prost.get_expr_type().unwrap() == Type::Format);
+
+        let RexNode::FuncCall(func_call_node) = prost.get_rex_node().unwrap() else {
+            bail!("Expected RexNode::FuncCall");
+        };
+
+        let children = &func_call_node.children;
+        ensure!(!children.is_empty());
+        let formatstr_expr = expr_build_from_prost(&children[0])?;
+
+        let arg_exprs = children[1..]
+            .iter()
+            .map(expr_build_from_prost)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(FormatExpression::new(formatstr_expr, arg_exprs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::DataChunkTestExt;
+    use piestream_pb::data::data_type::TypeName;
+    use piestream_pb::data::DataType as ProstDataType;
+    use piestream_pb::expr::FunctionCall;
+
+    use super::*;
+    use crate::expr::test_utils::{make_input_ref, make_string_literal};
+
+    #[test]
+    fn test_eval_format_expr() {
+        let expr = ExprNode {
+            expr_type: Type::Format as i32,
+            return_type: Some(ProstDataType {
+                type_name: TypeName::Varchar as i32,
+                ..Default::default()
+            }),
+            rex_node: Some(RexNode::FuncCall(FunctionCall {
+                children: vec![
+                    make_string_literal("%s = %L"),
+                    make_input_ref(0, TypeName::Varchar),
+                    make_input_ref(1, TypeName::Varchar),
+                ],
+            })),
+        };
+        let expr = FormatExpression::try_from(&expr).unwrap();
+
+        let chunk = DataChunk::from_pretty(
+            "T T
+             a b
+             c .
+             . It's",
+        );
+        let actual = expr.eval(&chunk).unwrap();
+        let actual = actual.as_utf8();
+        assert_eq!(actual.value_at(0), Some("a = 'b'"));
+        assert_eq!(actual.value_at(1), Some("c = NULL"));
+        assert_eq!(actual.value_at(2), Some(" = 'It''s'"));
+
+        let row = Row::new(vec![Some("x".to_string().to_scalar_value()), None]);
+        let expected: Datum = Some("x = NULL".to_string().to_scalar_value());
+        assert_eq!(expr.eval_row(&row).unwrap(), expected);
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use piestream_common::array::{ArrayRef, DataChunk, Row};
+use piestream_common::types::{DataType, Datum};
+use piestream_pb::expr::expr_node::{RexNode, Type};
+use piestream_pb::expr::ExprNode;
+
+use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression, Expression};
+use crate::{bail, ensure, ExprError, Result};
+
+/// `greatest(...)` and `least(...)` select the largest or smallest value from their arguments.
+/// Like `PostgreSQL`, `NULL` arguments are ignored and the result is `NULL` only if all of them
+/// are `NULL`.
+#[derive(Debug)]
+pub struct GreatestLeastExpression {
+    return_type: DataType,
+    children: Vec<BoxedExpression>,
+    /// Whether to select the largest value, i.e. `greatest`.
+    greatest: bool,
+}
+
+impl GreatestLeastExpression {
+    pub fn new(return_type: DataType, children: Vec<BoxedExpression>, greatest: bool) -> Self {
+        GreatestLeastExpression {
+            return_type,
+            children,
+            greatest,
+        }
+    }
+
+    fn pick(&self, datums: impl Iterator<Item = Datum>) -> Datum {
+        let non_null = datums.flatten();
+        if self.greatest {
+            non_null.max()
+        } else {
+            non_null.min()
+        }
+    }
+}
+
+impl Expression for GreatestLeastExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let children_array = self
+            .children
+            .iter()
+            .map(|c| c.eval_checked(input))
+            .collect::<Result<Vec<_>>>()?;
+
+        let len = children_array[0].len();
+        let mut builder = self.return_type.create_array_builder(len);
+        let vis = input.vis();
+
+        for i in 0..len {
+            let data = if vis.is_set(i) {
+                self.pick(children_array.iter().map(|array| array.datum_at(i)))
+            } else {
+                None
+            };
+            builder.append_datum(&data);
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let datums = self
+            .children
+            .iter()
+            .map(|c| c.eval_row(input))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.pick(datums.into_iter()))
+    }
+}
+
+impl<'a> TryFrom<&'a ExprNode> for GreatestLeastExpression {
+    type Error = ExprError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        let expr_type = prost.get_expr_type().unwrap();
+        ensure!(expr_type == Type::Greatest || expr_type == Type::Least);
+
+        let ret_type = DataType::from(prost.get_return_type().unwrap());
+        let RexNode::FuncCall(func_call_node) = prost.get_rex_node().unwrap() else {
+            bail!("Expected RexNode::FuncCall");
+        };
+
+        let children = func_call_node
+            .children
+            .iter()
+            .map(expr_build_from_prost)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(GreatestLeastExpression::new(
+            ret_type,
+            children,
+            expr_type == Type::Greatest,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::{DataChunk, Row};
+    use piestream_common::test_prelude::DataChunkTestExt;
+    use piestream_common::types::ScalarImpl;
+    use piestream_pb::data::data_type::TypeName;
+    use piestream_pb::data::DataType as ProstDataType;
+    use piestream_pb::expr::expr_node::{RexNode, Type};
+    use piestream_pb::expr::{ExprNode, FunctionCall};
+
+    use super::*;
+    use crate::expr::test_utils::make_input_ref;
+
+    fn make_function(kind: Type, children: Vec<ExprNode>, ret: TypeName) -> ExprNode {
+        ExprNode {
+            expr_type: kind as i32,
+            return_type: Some(ProstDataType {
+                type_name: ret as i32,
+                ..Default::default()
+            }),
+            rex_node: Some(RexNode::FuncCall(FunctionCall { children })),
+        }
+    }
+
+    fn make_expr(kind: Type) -> GreatestLeastExpression {
+        let children = (0..3).map(|i| make_input_ref(i, TypeName::Int32)).collect();
+        GreatestLeastExpression::try_from(&make_function(kind, children, TypeName::Int32)).unwrap()
+    }
+
+    #[test]
+    fn test_greatest_least() {
+        let data_chunk = DataChunk::from_pretty(
+            "i i i
+             1 3 2
+             . 2 .
+             . . .",
+        );
+
+        let greatest = make_expr(Type::Greatest);
+        let res = greatest.eval(&data_chunk).unwrap();
+        assert_eq!(res.datum_at(0), Some(ScalarImpl::Int32(3)));
+        assert_eq!(res.datum_at(1), Some(ScalarImpl::Int32(2)));
+        assert_eq!(res.datum_at(2), None);
+
+        let least = make_expr(Type::Least);
+        let res = least.eval(&data_chunk).unwrap();
+        assert_eq!(res.datum_at(0), Some(ScalarImpl::Int32(1)));
+        assert_eq!(res.datum_at(1), Some(ScalarImpl::Int32(2)));
+        assert_eq!(res.datum_at(2), None);
+
+        let row = Row::new(vec![Some(5.into()), None, Some(4.into())]);
+        assert_eq!(least.eval_row(&row).unwrap(), Some(ScalarImpl::Int32(4)));
+    }
+}
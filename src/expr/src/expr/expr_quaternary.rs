@@ -0,0 +1,119 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! For expression that only accept four values as input (e.g. `width_bucket`).
+
+use piestream_common::array::{DecimalArray, F64Array, I32Array};
+use piestream_common::types::DataType;
+use piestream_pb::expr::expr_node::Type as ProstType;
+
+use crate::expr::template::QuaternaryExpression;
+use crate::expr::BoxedExpression;
+use crate::vector_op::math::{width_bucket_decimal, width_bucket_f64};
+use crate::{ExprError, Result};
+
+pub fn new_quaternary_expr(
+    expr_type: ProstType,
+    return_type: DataType,
+    expr_ia1: BoxedExpression,
+    expr_ia2: BoxedExpression,
+    expr_ia3: BoxedExpression,
+    expr_ia4: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match (expr_type, expr_ia1.return_type()) {
+        (ProstType::WidthBucket, DataType::Float64) => Box::new(QuaternaryExpression::<
+            F64Array,
+            F64Array,
+            F64Array,
+            I32Array,
+            I32Array,
+            _,
+        >::new(
+            expr_ia1,
+            expr_ia2,
+            expr_ia3,
+            expr_ia4,
+            return_type,
+            width_bucket_f64,
+        )),
+        (ProstType::WidthBucket, DataType::Decimal) => Box::new(QuaternaryExpression::<
+            DecimalArray,
+            DecimalArray,
+            DecimalArray,
+            I32Array,
+            I32Array,
+            _,
+        >::new(
+            expr_ia1,
+            expr_ia2,
+            expr_ia3,
+            expr_ia4,
+            return_type,
+            width_bucket_decimal,
+        )),
+        (tp, _) => {
+            return Err(ExprError::UnsupportedFunction(format!(
+                "{:?}({:?}, {:?}, {:?}, {:?})",
+                tp,
+                expr_ia1.return_type(),
+                expr_ia2.return_type(),
+                expr_ia3.return_type(),
+                expr_ia4.return_type(),
+            )));
+        }
+    };
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::Row;
+    use piestream_common::types::{OrderedF64, ScalarImpl};
+    use piestream_pb::data::data_type::TypeName;
+
+    use super::*;
+    use crate::expr::build_from_prost;
+    use crate::expr::test_utils::make_expression;
+
+    #[test]
+    fn test_width_bucket() {
+        let expr = make_expression(
+            ProstType::WidthBucket,
+            &[
+                TypeName::Double,
+                TypeName::Double,
+                TypeName::Double,
+                TypeName::Int32,
+            ],
+            &[0, 1, 2, 3],
+        );
+        let expr = build_from_prost(&expr).unwrap();
+
+        let row = Row::new(vec![
+            Some(OrderedF64::from(5.35).into()),
+            Some(OrderedF64::from(0.024).into()),
+            Some(OrderedF64::from(10.06).into()),
+            Some(5.into()),
+        ]);
+        assert_eq!(expr.eval_row(&row).unwrap(), Some(ScalarImpl::Int32(3)));
+
+        let row = Row::new(vec![
+            Some(OrderedF64::from(5.35).into()),
+            Some(OrderedF64::from(0.024).into()),
+            Some(OrderedF64::from(10.06).into()),
+            Some(0.into()),
+        ]);
+        assert!(expr.eval_row(&row).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use piestream_common::array::{ArrayBuilder, ArrayImpl, ArrayRef, DataChunk, F64ArrayBuilder, Row};
+use piestream_common::types::{DataType, Datum, OrderedF64};
+use piestream_pb::expr::expr_node::{RexNode, Type};
+use piestream_pb::expr::ExprNode;
+
+use super::Expression;
+use crate::{bail, ensure, ExprError, Result};
+
+/// `random()` returns a random value in the range `0.0 <= x < 1.0` for each row. As it is
+/// volatile, it can only be used in batch queries.
+#[derive(Debug, Default)]
+pub struct RandomExpression {}
+
+impl<'a> TryFrom<&'a ExprNode> for RandomExpression {
+    type Error = ExprError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        ensure!(prost.get_expr_type().unwrap() == Type::Random);
+        ensure!(DataType::from(prost.get_return_type().unwrap()) == DataType::Float64);
+
+        let RexNode::FuncCall(func_call_node) = prost.get_rex_node().unwrap() else {
+            bail!("Expected RexNode::FuncCall");
+        };
+        ensure!(func_call_node.get_children().is_empty());
+
+        Ok(RandomExpression::default())
+    }
+}
+
+impl Expression for RandomExpression {
+    fn return_type(&self) -> DataType {
+        DataType::Float64
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let mut builder = F64ArrayBuilder::new(input.capacity());
+        for _ in 0..input.capacity() {
+            builder.append(Some(OrderedF64::from(rand::random::<f64>())));
+        }
+        Ok(Arc::new(ArrayImpl::from(builder.finish())))
+    }
+
+    fn eval_row(&self, _input: &Row) -> Result<Datum> {
+        Ok(Some(OrderedF64::from(rand::random::<f64>()).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::DataChunk;
+    use piestream_common::types::{OrderedF64, ScalarImpl};
+
+    use super::RandomExpression;
+    use crate::expr::Expression;
+
+    #[test]
+    fn test_random_expr_eval() {
+        let expr = RandomExpression::default();
+        let chunk = DataChunk::new_dummy(100);
+        let res = expr.eval(&chunk).unwrap();
+        assert_eq!(res.len(), 100);
+        for i in 0..res.len() {
+            let Some(ScalarImpl::Float64(v)) = res.datum_at(i) else {
+                panic!("random() should return a non-null float64");
+            };
+            assert!(OrderedF64::from(0.0) <= v && v < OrderedF64::from(1.0));
+        }
+    }
+}
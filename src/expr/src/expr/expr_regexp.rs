@@ -12,19 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use itertools::Itertools;
 use regex::Regex;
 use piestream_common::array::{
-    Array, ArrayBuilder, ArrayMeta, ArrayRef, DataChunk, ListArrayBuilder, ListRef, ListValue, Row,
-    Utf8Array,
+    Array, ArrayBuilder, ArrayMeta, ArrayRef, BoolArrayBuilder, DataChunk, ListArrayBuilder,
+    ListRef, ListValue, Row, Utf8Array, Utf8ArrayBuilder,
 };
 use piestream_common::types::{DataType, Datum, Scalar, ScalarImpl};
 use piestream_pb::expr::expr_node::{RexNode, Type};
 use piestream_pb::expr::ExprNode;
 
-use super::{build_from_prost as expr_build_from_prost, Expression};
+use super::{build_from_prost as expr_build_from_prost, BoxedExpression, Expression};
+use crate::vector_op::regexp::{
+    regexp_replace, regexp_split_to_array, similar_to_regex, RegexpFlags,
+};
 use crate::{bail, ensure, ExprError, Result};
 
 #[derive(Debug)]
@@ -151,3 +155,482 @@ impl Expression for RegexpMatchExpression {
         })
     }
 }
+
+/// Returns the value of `node` if it is a non-null string constant.
+fn constant_utf8(node: &ExprNode) -> Result<Option<String>> {
+    let Ok(RexNode::Constant(value)) = node.get_rex_node() else {
+        return Ok(None);
+    };
+    match ScalarImpl::from_proto_bytes(value.get_body(), node.get_return_type().unwrap())? {
+        ScalarImpl::Utf8(s) => Ok(Some(s)),
+        _ => bail!("Expected a string constant"),
+    }
+}
+
+/// Parses the optional `flags` argument, which must be a constant.
+fn parse_flags(node: Option<&ExprNode>, func_name: &str) -> Result<RegexpFlags> {
+    let Some(node) = node else {
+        return Ok(RegexpFlags::default());
+    };
+    let Some(flags) = constant_utf8(node)? else {
+        return Err(ExprError::UnsupportedFunction(format!(
+            "non-constant flags in {}",
+            func_name
+        )));
+    };
+    RegexpFlags::parse(&flags)
+}
+
+/// How a pattern string is compiled into a [`Regex`].
+#[derive(Debug, Clone, Copy)]
+enum PatternKind {
+    /// POSIX-style regular expression with flags.
+    Posix(RegexpFlags),
+    /// SQL regular expression of `SIMILAR TO`.
+    Similar,
+}
+
+impl PatternKind {
+    fn compile(&self, pattern: &str) -> Result<Regex> {
+        match self {
+            Self::Posix(flags) => flags.build(pattern),
+            Self::Similar => Ok(Regex::new(&similar_to_regex(pattern)?)?),
+        }
+    }
+}
+
+/// The pattern argument of a regular expression function. A constant pattern is compiled once
+/// when the expression is built, other patterns are compiled for every row.
+#[derive(Debug)]
+enum PatternArg {
+    Constant(Regex),
+    NonConstant {
+        expr: BoxedExpression,
+        kind: PatternKind,
+    },
+}
+
+impl PatternArg {
+    fn build(node: &ExprNode, kind: PatternKind) -> Result<Self> {
+        match constant_utf8(node)? {
+            Some(pattern) => Ok(Self::Constant(kind.compile(&pattern)?)),
+            None => Ok(Self::NonConstant {
+                expr: expr_build_from_prost(node)?,
+                kind,
+            }),
+        }
+    }
+
+    /// Evaluates the pattern strings. Returns `None` for a constant pattern.
+    fn eval(&self, input: &DataChunk) -> Result<Option<ArrayRef>> {
+        match self {
+            Self::Constant(_) => Ok(None),
+            Self::NonConstant { expr, .. } => expr.eval_checked(input).map(Some),
+        }
+    }
+
+    /// Evaluates the pattern string of a row. Returns `None` for a constant pattern.
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        match self {
+            Self::Constant(_) => Ok(None),
+            Self::NonConstant { expr, .. } => expr.eval_row(input),
+        }
+    }
+
+    /// Gets the regex to use given the evaluated pattern string, or `None` if the pattern is
+    /// `NULL`.
+    fn regex<'a>(&'a self, pattern: Option<&str>) -> Result<Option<Cow<'a, Regex>>> {
+        match self {
+            Self::Constant(regex) => Ok(Some(Cow::Borrowed(regex))),
+            Self::NonConstant { kind, .. } => pattern
+                .map(|pattern| kind.compile(pattern).map(Cow::Owned))
+                .transpose(),
+        }
+    }
+}
+
+fn datum_as_str(datum: &Datum) -> Option<&str> {
+    datum.as_ref().map(|s| s.as_utf8().as_str())
+}
+
+/// `regexp_replace(text, pattern, replacement [, flags])`
+#[derive(Debug)]
+pub struct RegexpReplaceExpression {
+    text: BoxedExpression,
+    pattern: PatternArg,
+    replacement: BoxedExpression,
+    global: bool,
+}
+
+impl<'a> TryFrom<&'a ExprNode> for RegexpReplaceExpression {
+    type Error = ExprError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        ensure!(prost.get_expr_type().unwrap() == Type::RegexpReplace);
+        let RexNode::FuncCall(func_call_node) = prost.get_rex_node().unwrap() else {
+            bail!("Expected RexNode::FuncCall");
+        };
+        let children = &func_call_node.children;
+        ensure!(children.len() == 3 || children.len() == 4);
+        let flags = parse_flags(children.get(3), "regexp_replace")?;
+        Ok(Self {
+            text: expr_build_from_prost(&children[0])?,
+            pattern: PatternArg::build(&children[1], PatternKind::Posix(flags))?,
+            replacement: expr_build_from_prost(&children[2])?,
+            global: flags.global,
+        })
+    }
+}
+
+impl RegexpReplaceExpression {
+    fn replace_one(
+        &self,
+        text: Option<&str>,
+        pattern: Option<&str>,
+        replacement: Option<&str>,
+    ) -> Result<Option<String>> {
+        let (Some(text), Some(replacement)) = (text, replacement) else {
+            return Ok(None);
+        };
+        let Some(regex) = self.pattern.regex(pattern)? else {
+            return Ok(None);
+        };
+        Ok(Some(regexp_replace(text, &regex, replacement, self.global)))
+    }
+}
+
+impl Expression for RegexpReplaceExpression {
+    fn return_type(&self) -> DataType {
+        DataType::Varchar
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let text_arr = self.text.eval_checked(input)?;
+        let text_arr = text_arr.as_utf8();
+        let pattern_arr = self.pattern.eval(input)?;
+        let replacement_arr = self.replacement.eval_checked(input)?;
+        let replacement_arr = replacement_arr.as_utf8();
+
+        let mut builder = Utf8ArrayBuilder::new(input.capacity());
+        for (i, vis) in input.vis().iter().enumerate() {
+            if !vis {
+                builder.append(None);
+                continue;
+            }
+            let result = self.replace_one(
+                text_arr.value_at(i),
+                pattern_arr
+                    .as_ref()
+                    .and_then(|arr| arr.as_utf8().value_at(i)),
+                replacement_arr.value_at(i),
+            )?;
+            builder.append(result.as_deref());
+        }
+        Ok(Arc::new(builder.finish().into()))
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let text = self.text.eval_row(input)?;
+        let pattern = self.pattern.eval_row(input)?;
+        let replacement = self.replacement.eval_row(input)?;
+        Ok(self
+            .replace_one(
+                datum_as_str(&text),
+                datum_as_str(&pattern),
+                datum_as_str(&replacement),
+            )?
+            .map(Scalar::to_scalar_value))
+    }
+}
+
+/// `regexp_split_to_array(text, pattern [, flags])`
+#[derive(Debug)]
+pub struct RegexpSplitToArrayExpression {
+    text: BoxedExpression,
+    pattern: PatternArg,
+}
+
+impl<'a> TryFrom<&'a ExprNode> for RegexpSplitToArrayExpression {
+    type Error = ExprError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        ensure!(prost.get_expr_type().unwrap() == Type::RegexpSplitToArray);
+        let RexNode::FuncCall(func_call_node) = prost.get_rex_node().unwrap() else {
+            bail!("Expected RexNode::FuncCall");
+        };
+        let children = &func_call_node.children;
+        ensure!(children.len() == 2 || children.len() == 3);
+        let flags = parse_flags(children.get(2), "regexp_split_to_array")?;
+        if flags.global {
+            return Err(ExprError::InvalidParam {
+                name: "flags",
+                reason: "regexp_split_to_array() does not support the \"global\" option"
+                    .to_string(),
+            });
+        }
+        Ok(Self {
+            text: expr_build_from_prost(&children[0])?,
+            pattern: PatternArg::build(&children[1], PatternKind::Posix(flags))?,
+        })
+    }
+}
+
+impl RegexpSplitToArrayExpression {
+    fn split_one(&self, text: Option<&str>, pattern: Option<&str>) -> Result<Datum> {
+        let Some(text) = text else {
+            return Ok(None);
+        };
+        let Some(regex) = self.pattern.regex(pattern)? else {
+            return Ok(None);
+        };
+        Ok(Some(regexp_split_to_array(text, &regex).to_scalar_value()))
+    }
+}
+
+impl Expression for RegexpSplitToArrayExpression {
+    fn return_type(&self) -> DataType {
+        DataType::List {
+            datatype: Box::new(DataType::Varchar),
+        }
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let text_arr = self.text.eval_checked(input)?;
+        let text_arr = text_arr.as_utf8();
+        let pattern_arr = self.pattern.eval(input)?;
+
+        let mut builder = self.return_type().create_array_builder(input.capacity());
+        for (i, vis) in input.vis().iter().enumerate() {
+            let datum = if vis {
+                self.split_one(
+                    text_arr.value_at(i),
+                    pattern_arr
+                        .as_ref()
+                        .and_then(|arr| arr.as_utf8().value_at(i)),
+                )?
+            } else {
+                None
+            };
+            builder.append_datum(&datum);
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let text = self.text.eval_row(input)?;
+        let pattern = self.pattern.eval_row(input)?;
+        self.split_one(datum_as_str(&text), datum_as_str(&pattern))
+    }
+}
+
+/// `text SIMILAR TO pattern`
+#[derive(Debug)]
+pub struct SimilarToExpression {
+    text: BoxedExpression,
+    pattern: PatternArg,
+}
+
+impl<'a> TryFrom<&'a ExprNode> for SimilarToExpression {
+    type Error = ExprError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        ensure!(prost.get_expr_type().unwrap() == Type::SimilarTo);
+        let RexNode::FuncCall(func_call_node) = prost.get_rex_node().unwrap() else {
+            bail!("Expected RexNode::FuncCall");
+        };
+        let children = &func_call_node.children;
+        ensure!(children.len() == 2);
+        Ok(Self {
+            text: expr_build_from_prost(&children[0])?,
+            pattern: PatternArg::build(&children[1], PatternKind::Similar)?,
+        })
+    }
+}
+
+impl SimilarToExpression {
+    fn match_one(&self, text: Option<&str>, pattern: Option<&str>) -> Result<Option<bool>> {
+        let Some(text) = text else {
+            return Ok(None);
+        };
+        let Some(regex) = self.pattern.regex(pattern)? else {
+            return Ok(None);
+        };
+        Ok(Some(regex.is_match(text)))
+    }
+}
+
+impl Expression for SimilarToExpression {
+    fn return_type(&self) -> DataType {
+        DataType::Boolean
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let text_arr = self.text.eval_checked(input)?;
+        let text_arr = text_arr.as_utf8();
+        let pattern_arr = self.pattern.eval(input)?;
+
+        let mut builder = BoolArrayBuilder::new(input.capacity());
+        for (i, vis) in input.vis().iter().enumerate() {
+            let result = if vis {
+                self.match_one(
+                    text_arr.value_at(i),
+                    pattern_arr
+                        .as_ref()
+                        .and_then(|arr| arr.as_utf8().value_at(i)),
+                )?
+            } else {
+                None
+            };
+            builder.append(result);
+        }
+        Ok(Arc::new(builder.finish().into()))
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let text = self.text.eval_row(input)?;
+        let pattern = self.pattern.eval_row(input)?;
+        Ok(self
+            .match_one(datum_as_str(&text), datum_as_str(&pattern))?
+            .map(Scalar::to_scalar_value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::DataChunkTestExt;
+    use piestream_pb::data::data_type::TypeName;
+    use piestream_pb::data::DataType as ProstDataType;
+    use piestream_pb::expr::FunctionCall;
+
+    use super::*;
+    use crate::expr::test_utils::{make_input_ref, make_string_literal};
+
+    fn make_function(kind: Type, children: Vec<ExprNode>, ret: ProstDataType) -> ExprNode {
+        ExprNode {
+            expr_type: kind as i32,
+            return_type: Some(ret),
+            rex_node: Some(RexNode::FuncCall(FunctionCall { children })),
+        }
+    }
+
+    fn varchar() -> ProstDataType {
+        ProstDataType {
+            type_name: TypeName::Varchar as i32,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_regexp_replace() {
+        let chunk = DataChunk::from_pretty(
+            "T          T
+             foobarbaz  b(..)
+             abcabc     [ac]
+             abc        .
+             .          b",
+        );
+        let constant = RegexpReplaceExpression::try_from(&make_function(
+            Type::RegexpReplace,
+            vec![
+                make_input_ref(0, TypeName::Varchar),
+                make_string_literal("b(..)"),
+                make_string_literal("X\\1Y"),
+                make_string_literal("g"),
+            ],
+            varchar(),
+        ))
+        .unwrap();
+        assert!(matches!(constant.pattern, PatternArg::Constant(_)));
+        let non_constant = RegexpReplaceExpression::try_from(&make_function(
+            Type::RegexpReplace,
+            vec![
+                make_input_ref(0, TypeName::Varchar),
+                make_input_ref(1, TypeName::Varchar),
+                make_string_literal("-"),
+            ],
+            varchar(),
+        ))
+        .unwrap();
+
+        let res = constant.eval(&chunk).unwrap();
+        let res = res.as_utf8();
+        assert_eq!(res.value_at(0), Some("fooXarYXazY"));
+        assert_eq!(res.value_at(1), Some("aXcaYbc"));
+        assert_eq!(res.value_at(2), Some("abc"));
+        assert_eq!(res.value_at(3), None);
+
+        let res = non_constant.eval(&chunk).unwrap();
+        let res = res.as_utf8();
+        assert_eq!(res.value_at(0), Some("foo-baz"));
+        assert_eq!(res.value_at(1), Some("-bcabc"));
+        assert_eq!(res.value_at(2), None);
+        assert_eq!(res.value_at(3), None);
+
+        let row = Row::new(vec![
+            Some("abc".to_string().to_scalar_value()),
+            Some("B".to_string().to_scalar_value()),
+        ]);
+        assert_eq!(
+            non_constant.eval_row(&row).unwrap(),
+            Some("abc".to_string().to_scalar_value())
+        );
+    }
+
+    #[test]
+    fn test_regexp_split_to_array() {
+        let expr = RegexpSplitToArrayExpression::try_from(&make_function(
+            Type::RegexpSplitToArray,
+            vec![
+                make_input_ref(0, TypeName::Varchar),
+                make_string_literal("\\s+"),
+            ],
+            ProstDataType {
+                type_name: TypeName::List as i32,
+                field_type: vec![varchar()],
+                ..Default::default()
+            },
+        ))
+        .unwrap();
+        let row = Row::new(vec![Some("hello  world".to_string().to_scalar_value())]);
+        assert_eq!(
+            expr.eval_row(&row).unwrap(),
+            Some(
+                ListValue::new(vec![
+                    Some("hello".to_string().to_scalar_value()),
+                    Some("world".to_string().to_scalar_value()),
+                ])
+                .to_scalar_value()
+            )
+        );
+        assert_eq!(expr.eval_row(&Row::new(vec![None])).unwrap(), None);
+    }
+
+    #[test]
+    fn test_similar_to() {
+        let chunk = DataChunk::from_pretty(
+            "T
+             abc
+             abd
+             xbc
+             .",
+        );
+        let expr = SimilarToExpression::try_from(&make_function(
+            Type::SimilarTo,
+            vec![
+                make_input_ref(0, TypeName::Varchar),
+                make_string_literal("a%(c|x)"),
+            ],
+            ProstDataType {
+                type_name: TypeName::Boolean as i32,
+                ..Default::default()
+            },
+        ))
+        .unwrap();
+        let res = expr.eval(&chunk).unwrap();
+        let res = res.as_bool();
+        assert_eq!(res.value_at(0), Some(true));
+        assert_eq!(res.value_at(1), Some(false));
+        assert_eq!(res.value_at(2), Some(false));
+        assert_eq!(res.value_at(3), None);
+    }
+}
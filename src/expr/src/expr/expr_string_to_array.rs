@@ -0,0 +1,191 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use piestream_common::array::{Array, ArrayBuilder, ArrayRef, DataChunk, Row, Utf8Array};
+use piestream_common::types::{DataType, Datum, Scalar};
+use piestream_pb::expr::expr_node::{RexNode, Type};
+use piestream_pb::expr::ExprNode;
+
+use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression, Expression};
+use crate::vector_op::string_to_array::string_to_array;
+use crate::{bail, ensure, ExprError, Result};
+
+/// `string_to_array(string, delimiter [, null_string])`. Unlike most functions, a `NULL`
+/// delimiter or `null_string` does not make the result `NULL`.
+#[derive(Debug)]
+pub struct StringToArrayExpression {
+    string_expr: BoxedExpression,
+    delimiter_expr: BoxedExpression,
+    null_string_expr: Option<BoxedExpression>,
+}
+
+impl StringToArrayExpression {
+    pub fn new(
+        string_expr: BoxedExpression,
+        delimiter_expr: BoxedExpression,
+        null_string_expr: Option<BoxedExpression>,
+    ) -> Self {
+        StringToArrayExpression {
+            string_expr,
+            delimiter_expr,
+            null_string_expr,
+        }
+    }
+
+    fn split_one(
+        string: Option<&str>,
+        delimiter: Option<&str>,
+        null_string: Option<&str>,
+    ) -> Datum {
+        string.map(|s| string_to_array(s, delimiter, null_string).to_scalar_value())
+    }
+}
+
+impl Expression for StringToArrayExpression {
+    fn return_type(&self) -> DataType {
+        DataType::List {
+            datatype: Box::new(DataType::Varchar),
+        }
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let string_column = self.string_expr.eval_checked(input)?;
+        let string_column: &Utf8Array = string_column.as_ref().into();
+        let delimiter_column = self.delimiter_expr.eval_checked(input)?;
+        let delimiter_column: &Utf8Array = delimiter_column.as_ref().into();
+        let null_string_column = self
+            .null_string_expr
+            .as_ref()
+            .map(|e| e.eval_checked(input))
+            .transpose()?;
+
+        let mut builder = self.return_type().create_array_builder(input.capacity());
+        for (row_idx, vis) in input.vis().iter().enumerate() {
+            let datum = if vis {
+                Self::split_one(
+                    string_column.value_at(row_idx),
+                    delimiter_column.value_at(row_idx),
+                    null_string_column
+                        .as_ref()
+                        .and_then(|c| c.as_utf8().value_at(row_idx)),
+                )
+            } else {
+                None
+            };
+            builder.append_datum(&datum);
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let string = self.string_expr.eval_row(input)?;
+        let delimiter = self.delimiter_expr.eval_row(input)?;
+        let null_string = match &self.null_string_expr {
+            Some(e) => e.eval_row(input)?,
+            None => None,
+        };
+        fn as_str(datum: &Datum) -> Option<&str> {
+            datum.as_ref().map(|s| s.as_utf8().as_str())
+        }
+        Ok(Self::split_one(
+            as_str(&string),
+            as_str(&delimiter),
+            as_str(&null_string),
+        ))
+    }
+}
+
+impl<'a> TryFrom<&'a ExprNode> for StringToArrayExpression {
+    type Error = ExprError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        ensure!(prost.get_expr_type().unwrap() == Type::StringToArray);
+
+        let RexNode::FuncCall(func_call_node) = prost.get_rex_node().unwrap() else {
+            bail!("Expected RexNode::FuncCall");
+        };
+
+        let children = &func_call_node.children;
+        ensure!(children.len() == 2 || children.len() == 3);
+        let string_expr = expr_build_from_prost(&children[0])?;
+        let delimiter_expr = expr_build_from_prost(&children[1])?;
+        let null_string_expr = children.get(2).map(expr_build_from_prost).transpose()?;
+        Ok(StringToArrayExpression::new(
+            string_expr,
+            delimiter_expr,
+            null_string_expr,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::{DataChunkTestExt, ListValue};
+    use piestream_pb::data::data_type::TypeName;
+    use piestream_pb::data::DataType as ProstDataType;
+    use piestream_pb::expr::FunctionCall;
+
+    use super::*;
+    use crate::expr::test_utils::make_input_ref;
+
+    #[test]
+    fn test_eval_string_to_array() {
+        let expr = ExprNode {
+            expr_type: Type::StringToArray as i32,
+            return_type: Some(ProstDataType {
+                type_name: TypeName::List as i32,
+                field_type: vec![ProstDataType {
+                    type_name: TypeName::Varchar as i32,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            rex_node: Some(RexNode::FuncCall(FunctionCall {
+                children: vec![
+                    make_input_ref(0, TypeName::Varchar),
+                    make_input_ref(1, TypeName::Varchar),
+                ],
+            })),
+        };
+        let expr = StringToArrayExpression::try_from(&expr).unwrap();
+
+        let chunk = DataChunk::from_pretty(
+            "T     T
+             a,b,c ,
+             xy    .
+             .     ,",
+        );
+        let actual = expr.eval(&chunk).unwrap();
+        let list = |values: &[&str]| {
+            Some(
+                ListValue::new(
+                    values
+                        .iter()
+                        .map(|v| Some(v.to_string().to_scalar_value()))
+                        .collect(),
+                )
+                .to_scalar_value(),
+            )
+        };
+        assert_eq!(actual.datum_at(0), list(&["a", "b", "c"]));
+        assert_eq!(actual.datum_at(1), list(&["x", "y"]));
+        assert_eq!(actual.datum_at(2), None);
+
+        let row = Row::new(vec![Some("1|2".to_string().to_scalar_value()), None]);
+        assert_eq!(expr.eval_row(&row).unwrap(), list(&["1", "|", "2"]));
+    }
+}
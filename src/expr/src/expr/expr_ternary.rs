@@ -0,0 +1,99 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! For expression that only accept three values as input (e.g. `make_date`).
+
+use piestream_common::array::{F64Array, I32Array, NaiveDateArray, NaiveTimeArray};
+use piestream_common::types::DataType;
+use piestream_pb::expr::expr_node::Type as ProstType;
+
+use crate::expr::template::TernaryExpression;
+use crate::expr::BoxedExpression;
+use crate::vector_op::make_date::{make_date, make_time};
+use crate::{ExprError, Result};
+
+pub fn new_ternary_expr(
+    expr_type: ProstType,
+    return_type: DataType,
+    expr_ia1: BoxedExpression,
+    expr_ia2: BoxedExpression,
+    expr_ia3: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match expr_type {
+        ProstType::MakeDate => Box::new(TernaryExpression::<
+            I32Array,
+            I32Array,
+            I32Array,
+            NaiveDateArray,
+            _,
+        >::new(
+            expr_ia1, expr_ia2, expr_ia3, return_type, make_date
+        )),
+        ProstType::MakeTime => Box::new(TernaryExpression::<
+            I32Array,
+            I32Array,
+            F64Array,
+            NaiveTimeArray,
+            _,
+        >::new(
+            expr_ia1, expr_ia2, expr_ia3, return_type, make_time
+        )),
+        tp => {
+            return Err(ExprError::UnsupportedFunction(format!(
+                "{:?}({:?}, {:?}, {:?})",
+                tp,
+                expr_ia1.return_type(),
+                expr_ia2.return_type(),
+                expr_ia3.return_type(),
+            )));
+        }
+    };
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use piestream_common::array::Row;
+    use piestream_common::types::{NaiveDateWrapper, ScalarImpl};
+    use piestream_pb::data::data_type::TypeName;
+
+    use super::*;
+    use crate::expr::build_from_prost;
+    use crate::expr::test_utils::make_expression;
+
+    #[test]
+    fn test_make_date() {
+        let expr = make_expression(
+            ProstType::MakeDate,
+            &[TypeName::Int32, TypeName::Int32, TypeName::Int32],
+            &[0, 1, 2],
+        );
+        let expr = build_from_prost(&expr).unwrap();
+
+        let row = Row::new(vec![Some(2022.into()), Some(8.into()), Some(3.into())]);
+        assert_eq!(
+            expr.eval_row(&row).unwrap(),
+            Some(ScalarImpl::NaiveDate(NaiveDateWrapper::new(
+                NaiveDate::from_ymd(2022, 8, 3)
+            )))
+        );
+
+        let row = Row::new(vec![Some(2022.into()), Some(8.into()), None]);
+        assert_eq!(expr.eval_row(&row).unwrap(), None);
+
+        let row = Row::new(vec![Some(2022.into()), Some(2.into()), Some(30.into())]);
+        assert!(expr.eval_row(&row).is_err());
+    }
+}
@@ -20,6 +20,7 @@ use piestream_common::types::DataType;
 use crate::expr::template::TernaryBytesExpression;
 use crate::expr::BoxedExpression;
 use crate::vector_op::overlay::overlay;
+use crate::vector_op::pad::{lpad_fill, rpad_fill};
 use crate::vector_op::replace::replace;
 use crate::vector_op::split_part::split_part;
 use crate::vector_op::substr::substr_start_for;
@@ -93,6 +94,40 @@ pub fn new_split_part_expr(
     )
 }
 
+pub fn new_lpad_fill(
+    s: BoxedExpression,
+    length: BoxedExpression,
+    fill: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(
+        TernaryBytesExpression::<Utf8Array, I32Array, Utf8Array, _>::new(
+            s,
+            length,
+            fill,
+            return_type,
+            lpad_fill,
+        ),
+    )
+}
+
+pub fn new_rpad_fill(
+    s: BoxedExpression,
+    length: BoxedExpression,
+    fill: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(
+        TernaryBytesExpression::<Utf8Array, I32Array, Utf8Array, _>::new(
+            s,
+            length,
+            fill,
+            return_type,
+            rpad_fill,
+        ),
+    )
+}
+
 pub fn new_overlay_exp(
     s: BoxedExpression,
     new_sub_str: BoxedExpression,
@@ -28,12 +28,17 @@ use crate::vector_op::bitwise_op::general_bitnot;
 use crate::vector_op::cast::*;
 use crate::vector_op::cmp::{is_false, is_not_false, is_not_true, is_true};
 use crate::vector_op::conjunction;
+use crate::vector_op::initcap::initcap;
 use crate::vector_op::length::{bit_length, length_default, octet_length};
 use crate::vector_op::lower::lower;
 use crate::vector_op::ltrim::ltrim;
+use crate::vector_op::math::*;
 use crate::vector_op::md5::md5;
+use crate::vector_op::reverse::reverse;
 use crate::vector_op::round::*;
 use crate::vector_op::rtrim::rtrim;
+use crate::vector_op::timestampz::f64_sec_to_timestampz;
+use crate::vector_op::trigonometric::*;
 use crate::vector_op::trim::trim;
 use crate::vector_op::upper::upper;
 use crate::{for_all_cast_variants, ExprError, Result};
@@ -106,6 +111,15 @@ macro_rules! gen_round_expr {
     };
 }
 
+macro_rules! gen_float64_expr {
+    ($expr_name:literal, $child:expr, $ret:expr, $float64_func:ident) => {
+        gen_unary_impl! {
+            [$expr_name, $child, $ret],
+            { float64, float64, $float64_func },
+        }
+    };
+}
+
 pub fn new_unary_expr(
     expr_type: ProstType,
     return_type: DataType,
@@ -219,6 +233,16 @@ pub fn new_unary_expr(
             return_type,
             md5,
         )),
+        (ProstType::Reverse, _, _) => Box::new(UnaryBytesExpression::<Utf8Array, _>::new(
+            child_expr,
+            return_type,
+            reverse,
+        )),
+        (ProstType::Initcap, _, _) => Box::new(UnaryBytesExpression::<Utf8Array, _>::new(
+            child_expr,
+            return_type,
+            initcap,
+        )),
         (ProstType::Ascii, _, _) => Box::new(UnaryExpression::<Utf8Array, I32Array, _>::new(
             child_expr,
             return_type,
@@ -271,6 +295,45 @@ pub fn new_unary_expr(
         (ProstType::Round, _, _) => {
             gen_round_expr! {"Ceil", child_expr, return_type, round_f64, round_decimal}
         }
+        (ProstType::Sqrt, _, _) => {
+            gen_round_expr! {"Sqrt", child_expr, return_type, sqrt_f64, sqrt_decimal}
+        }
+        (ProstType::Exp, _, _) => {
+            gen_round_expr! {"Exp", child_expr, return_type, exp_f64, exp_decimal}
+        }
+        (ProstType::Ln, _, _) => {
+            gen_round_expr! {"Ln", child_expr, return_type, ln_f64, ln_decimal}
+        }
+        (ProstType::Log10, _, _) => {
+            gen_round_expr! {"Log10", child_expr, return_type, log10_f64, log10_decimal}
+        }
+        (ProstType::Sign, _, _) => {
+            gen_round_expr! {"Sign", child_expr, return_type, sign_f64, sign_decimal}
+        }
+        (ProstType::Trunc, _, _) => {
+            gen_round_expr! {"Trunc", child_expr, return_type, trunc_f64, trunc_decimal}
+        }
+        (ProstType::Cbrt, _, _) => gen_float64_expr!("Cbrt", child_expr, return_type, cbrt_f64),
+        (ProstType::Sin, _, _) => gen_float64_expr!("Sin", child_expr, return_type, sin_f64),
+        (ProstType::Cos, _, _) => gen_float64_expr!("Cos", child_expr, return_type, cos_f64),
+        (ProstType::Tan, _, _) => gen_float64_expr!("Tan", child_expr, return_type, tan_f64),
+        (ProstType::Cot, _, _) => gen_float64_expr!("Cot", child_expr, return_type, cot_f64),
+        (ProstType::Asin, _, _) => gen_float64_expr!("Asin", child_expr, return_type, asin_f64),
+        (ProstType::Acos, _, _) => gen_float64_expr!("Acos", child_expr, return_type, acos_f64),
+        (ProstType::Atan, _, _) => gen_float64_expr!("Atan", child_expr, return_type, atan_f64),
+        (ProstType::Degrees, _, _) => {
+            gen_float64_expr!("Degrees", child_expr, return_type, degrees_f64)
+        }
+        (ProstType::Radians, _, _) => {
+            gen_float64_expr!("Radians", child_expr, return_type, radians_f64)
+        }
+        (ProstType::ToTimestamp, DataType::Timestampz, DataType::Float64) => {
+            Box::new(UnaryExpression::<F64Array, I64Array, _>::new(
+                child_expr,
+                return_type,
+                f64_sec_to_timestampz,
+            ))
+        }
         (expr, ret, child) => {
             return Err(ExprError::UnsupportedFunction(format!(
                 "{:?}({:?}) -> {:?}",
@@ -23,13 +23,19 @@ mod expr_case;
 mod expr_coalesce;
 mod expr_concat_ws;
 mod expr_field;
+mod expr_format;
+mod expr_greatest_least;
 mod expr_in;
 mod expr_input_ref;
 mod expr_is_null;
 mod expr_literal;
 mod expr_nested_construct;
+mod expr_quaternary;
 mod expr_quaternary_bytes;
+mod expr_random;
 mod expr_regexp;
+mod expr_string_to_array;
+mod expr_ternary;
 mod expr_ternary_bytes;
 mod expr_to_char_const_tmpl;
 pub mod expr_unary;
@@ -53,9 +59,16 @@ use crate::expr::expr_case::CaseExpression;
 use crate::expr::expr_coalesce::CoalesceExpression;
 use crate::expr::expr_concat_ws::ConcatWsExpression;
 use crate::expr::expr_field::FieldExpression;
+use crate::expr::expr_format::FormatExpression;
+use crate::expr::expr_greatest_least::GreatestLeastExpression;
 use crate::expr::expr_in::InExpression;
 use crate::expr::expr_nested_construct::NestedConstructExpression;
-use crate::expr::expr_regexp::RegexpMatchExpression;
+use crate::expr::expr_random::RandomExpression;
+use crate::expr::expr_regexp::{
+    RegexpMatchExpression, RegexpReplaceExpression, RegexpSplitToArrayExpression,
+    SimilarToExpression,
+};
+use crate::expr::expr_string_to_array::StringToArrayExpression;
 use crate::expr::expr_vnode::VnodeExpression;
 use crate::ExprError;
 
@@ -102,18 +115,26 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
         // Fixed number of arguments and based on `Unary/Binary/Ternary/...Expression`
         Cast | Upper | Lower | Md5 | Not | IsTrue | IsNotTrue | IsFalse | IsNotFalse | IsNull
         | IsNotNull | Neg | Ascii | Abs | Ceil | Floor | Round | BitwiseNot | CharLength
-        | BoolOut | OctetLength | BitLength => build_unary_expr_prost(prost),
+        | BoolOut | OctetLength | BitLength | Sqrt | Cbrt | Exp | Ln | Log10 | Sign | Trunc
+        | Sin | Cos | Tan | Cot | Asin | Acos | Atan | Degrees | Radians | Reverse | Initcap => {
+            build_unary_expr_prost(prost)
+        }
         Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual | Add
         | Subtract | Multiply | Divide | Modulus | Extract | RoundDigit | TumbleStart
         | Position | BitwiseShiftLeft | BitwiseShiftRight | BitwiseAnd | BitwiseOr | BitwiseXor
-        | ConcatOp => build_binary_expr_prost(prost),
+        | ConcatOp | DateTrunc | ToDate | Age | AtTimeZone | Pow | Log | TruncDigit | Atan2
+        | Left | Right | StartsWith => build_binary_expr_prost(prost),
+        MakeDate | MakeTime => build_ternary_expr_prost(prost),
+        WidthBucket => build_quaternary_expr_prost(prost),
         And | Or | IsDistinctFrom | IsNotDistinctFrom | ArrayAccess => {
             build_nullable_binary_expr_prost(prost)
         }
         ToChar => build_to_char_expr(prost),
+        ToTimestamp => build_to_timestamp_expr(prost),
         Length => build_length_expr(prost),
         Replace => build_replace_expr(prost),
-        Like => build_like_expr(prost),
+        Like | ILike => build_like_expr(prost),
+        Lpad | Rpad => build_pad_expr(prost),
         Repeat => build_repeat_expr(prost),
         SplitPart => build_split_part_expr(prost),
         Translate => build_translate_expr(prost),
@@ -136,6 +157,11 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
         Array => NestedConstructExpression::try_from(prost).map(Expression::boxed),
         Row => NestedConstructExpression::try_from(prost).map(Expression::boxed),
         RegexpMatch => RegexpMatchExpression::try_from(prost).map(Expression::boxed),
+        RegexpReplace => RegexpReplaceExpression::try_from(prost).map(Expression::boxed),
+        RegexpSplitToArray => RegexpSplitToArrayExpression::try_from(prost).map(Expression::boxed),
+        SimilarTo => SimilarToExpression::try_from(prost).map(Expression::boxed),
+        Format => FormatExpression::try_from(prost).map(Expression::boxed),
+        StringToArray => StringToArrayExpression::try_from(prost).map(Expression::boxed),
         ArrayCat | ArrayAppend | ArrayPrepend => {
             // Now we implement these three functions as a single expression for the
             // sake of simplicity. If performance matters at some time, we can split
@@ -143,6 +169,8 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
             ArrayConcatExpression::try_from(prost).map(Expression::boxed)
         }
         Vnode => VnodeExpression::try_from(prost).map(Expression::boxed),
+        Greatest | Least => GreatestLeastExpression::try_from(prost).map(Expression::boxed),
+        Random => RandomExpression::try_from(prost).map(Expression::boxed),
         _ => Err(ExprError::UnsupportedFunction(format!(
             "{:?}",
             prost.get_expr_type()
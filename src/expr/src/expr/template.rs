@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Template macro to generate code for unary/binary/ternary expression.
+//! Template macro to generate code for unary/binary/ternary/quaternary expression.
 
 use std::fmt;
 use std::sync::Arc;
@@ -326,6 +326,7 @@ macro_rules! gen_expr_nullable {
 gen_expr_normal!(UnaryExpression, { IA1 }, { 'ia1 });
 gen_expr_normal!(BinaryExpression, { IA1, IA2 }, { 'ia1, 'ia2 });
 gen_expr_normal!(TernaryExpression, { IA1, IA2, IA3 }, { 'ia1, 'ia2, 'ia3 });
+gen_expr_normal!(QuaternaryExpression, { IA1, IA2, IA3, IA4 }, { 'ia1, 'ia2, 'ia3, 'ia4 });
 
 gen_expr_bytes!(UnaryBytesExpression, { IA1 }, { 'ia1 });
 gen_expr_bytes!(BinaryBytesExpression, { IA1, IA2 }, { 'ia1, 'ia2 });
@@ -379,6 +380,7 @@ macro_rules! for_all_cmp_variants {
             { float32, decimal, float64, $general_f },
             { float64, decimal, float64, $general_f },
             { timestamp, timestamp, timestamp, $general_f },
+            { timestampz, timestampz, timestampz, $general_f },
             { interval, interval, interval, $general_f },
             { time, time, time, $general_f },
             { date, date, date, $general_f },
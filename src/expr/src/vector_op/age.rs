@@ -0,0 +1,108 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use piestream_common::types::{IntervalUnit, NaiveDateTimeWrapper};
+
+use crate::{ExprError, Result};
+
+const MS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+fn days_in_month(year: i32, month: u32) -> i32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    let first = NaiveDate::from_ymd(year, month, 1);
+    let next = NaiveDate::from_ymd(next_year, next_month, 1);
+    (next - first).num_days() as i32
+}
+
+fn ms_of_day(time: &NaiveDateTime) -> i64 {
+    time.num_seconds_from_midnight() as i64 * 1000 + (time.nanosecond() / 1_000_000) as i64
+}
+
+/// `age(timestamp, timestamp)`: subtracts the arguments, producing a "symbolic" result that uses
+/// years and months rather than just days, e.g. `age('2001-04-10', '1957-06-13')` is
+/// `43 years 9 mons 27 days`.
+#[inline(always)]
+pub fn age_timestamp(l: NaiveDateTimeWrapper, r: NaiveDateTimeWrapper) -> Result<IntervalUnit> {
+    // Always compute `later - earlier` so that borrowing is well-defined, and negate at the end.
+    let (later, earlier, negative) = if l >= r {
+        (l.0, r.0, false)
+    } else {
+        (r.0, l.0, true)
+    };
+
+    let mut years = later.year() - earlier.year();
+    let mut months = later.month() as i32 - earlier.month() as i32;
+    let mut days = later.day() as i32 - earlier.day() as i32;
+    let mut ms = ms_of_day(&later) - ms_of_day(&earlier);
+
+    if ms < 0 {
+        ms += MS_PER_DAY;
+        days -= 1;
+    }
+    if days < 0 {
+        // Borrow the length of the month that `earlier` falls in, like `PostgreSQL` does.
+        days += days_in_month(earlier.year(), earlier.month());
+        months -= 1;
+    }
+    if months < 0 {
+        months += 12;
+        years -= 1;
+    }
+
+    let months = years
+        .checked_mul(12)
+        .and_then(|m| m.checked_add(months))
+        .ok_or(ExprError::NumericOutOfRange)?;
+    let interval = IntervalUnit::new(months, days, ms);
+    Ok(if negative {
+        interval.negative()
+    } else {
+        interval
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector_op::cast::str_to_timestamp;
+
+    fn age(l: &str, r: &str) -> IntervalUnit {
+        age_timestamp(str_to_timestamp(l).unwrap(), str_to_timestamp(r).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_age() {
+        assert_eq!(
+            age("2001-04-10", "1957-06-13"),
+            IntervalUnit::new(43 * 12 + 9, 27, 0)
+        );
+        assert_eq!(
+            age("1957-06-13", "2001-04-10"),
+            IntervalUnit::new(43 * 12 + 9, 27, 0).negative()
+        );
+        assert_eq!(
+            age("2022-03-01 01:00:00", "2022-02-28 02:30:00"),
+            IntervalUnit::new(0, 0, 22 * 3_600_000 + 30 * 60_000)
+        );
+        assert_eq!(
+            age("2022-03-01 03:00:00", "2022-01-31 02:00:00"),
+            IntervalUnit::new(1, 1, 3_600_000)
+        );
+    }
+}
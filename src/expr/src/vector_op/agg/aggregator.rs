@@ -27,9 +27,11 @@ use crate::vector_op::agg::approx_count_distinct::ApproxCountDistinct;
 use crate::vector_op::agg::array_agg::create_array_agg_state;
 use crate::vector_op::agg::count_star::CountStar;
 use crate::vector_op::agg::filter::*;
+use crate::vector_op::agg::first_last_value::create_first_last_value_state;
 use crate::vector_op::agg::functions::*;
 use crate::vector_op::agg::general_agg::*;
 use crate::vector_op::agg::general_distinct_agg::*;
+use crate::vector_op::agg::percentile::create_percentile_state;
 use crate::vector_op::agg::string_agg::create_string_agg_state;
 use crate::Result;
 
@@ -107,6 +109,30 @@ impl AggStateFactory {
                 let agg_col_idx = arg.get_input()?.get_column_idx() as usize;
                 create_array_agg_state(return_type.clone(), agg_col_idx, order_pairs)?
             }
+            (AggKind::FirstValue | AggKind::LastValue, [arg]) => {
+                let agg_col_idx = arg.get_input()?.get_column_idx() as usize;
+                create_first_last_value_state(
+                    return_type.clone(),
+                    agg_col_idx,
+                    order_pairs,
+                    agg_kind == AggKind::LastValue,
+                )?
+            }
+            (AggKind::PercentileCont | AggKind::PercentileDisc, [agg_arg, fraction_arg]) => {
+                assert_eq!(
+                    DataType::from(fraction_arg.get_type().unwrap()),
+                    DataType::Float64
+                );
+                let agg_col_idx = agg_arg.get_input()?.get_column_idx() as usize;
+                let fraction_col_idx = fraction_arg.get_input()?.get_column_idx() as usize;
+                create_percentile_state(
+                    return_type.clone(),
+                    agg_col_idx,
+                    fraction_col_idx,
+                    order_pairs,
+                    agg_kind == AggKind::PercentileDisc,
+                )?
+            }
             (agg_kind, [arg]) => {
                 // other unary agg call
                 let input_type = DataType::from(arg.get_type()?);
@@ -240,6 +266,14 @@ pub fn create_agg_state_unary(
         (Max, max_struct, struct_type, struct_type, None),
         (Max, max_str, varchar, varchar, None),
         (Max, max_list, list, list, None),
+        (BoolAnd, bool_and, boolean, boolean, None),
+        (BoolOr, bool_or, boolean, boolean, None),
+        (BitAnd, bit_and, int16, int16, None),
+        (BitAnd, bit_and, int32, int32, None),
+        (BitAnd, bit_and, int64, int64, None),
+        (BitOr, bit_or, int16, int16, None),
+        (BitOr, bit_or, int32, int32, None),
+        (BitOr, bit_or, int64, int64, None),
         // Global Agg
         (Sum, sum, int64, int64, None),
     ];
@@ -293,5 +327,10 @@ mod tests {
         test_create! { decimal_type, Min, decimal_type, is_ok }
         test_create! { bool_type, Min, bool_type, is_ok } // TODO(#359): revert to is_err
         test_create! { char_type, Min, char_type, is_ok }
+
+        test_create! { bool_type, BoolAnd, bool_type, is_ok }
+        test_create! { int64_type, BoolOr, bool_type, is_err }
+        test_create! { int64_type, BitAnd, int64_type, is_ok }
+        test_create! { decimal_type, BitOr, decimal_type, is_err }
     }
 }
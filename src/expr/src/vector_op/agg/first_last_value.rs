@@ -0,0 +1,167 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piestream_common::array::{ArrayBuilderImpl, DataChunk, RowRef};
+use piestream_common::types::{DataType, Datum};
+use piestream_common::util::ordered::OrderedRow;
+use piestream_common::util::sort_util::{OrderPair, OrderType};
+
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::Result;
+
+/// `first_value` / `last_value` aggregator. It keeps the row with the smallest (`first_value`) or
+/// largest (`last_value`) order key seen so far. Without order by, it keeps the first or last row
+/// it receives.
+#[derive(Clone)]
+struct FirstLastValue {
+    return_type: DataType,
+    agg_col_idx: usize,
+    order_col_indices: Vec<usize>,
+    order_types: Vec<OrderType>,
+    is_last: bool,
+    result: Option<(OrderedRow, Datum)>,
+}
+
+impl FirstLastValue {
+    fn new(
+        return_type: DataType,
+        agg_col_idx: usize,
+        order_pairs: Vec<OrderPair>,
+        is_last: bool,
+    ) -> Self {
+        let (order_col_indices, order_types) = order_pairs
+            .into_iter()
+            .map(|p| (p.column_idx, p.order_type))
+            .unzip();
+        FirstLastValue {
+            return_type,
+            agg_col_idx,
+            order_col_indices,
+            order_types,
+            is_last,
+            result: None,
+        }
+    }
+
+    fn push_row(&mut self, row: RowRef<'_>) {
+        let key = OrderedRow::new(
+            row.row_by_indices(&self.order_col_indices),
+            &self.order_types,
+        );
+        let replace = match &self.result {
+            None => true,
+            // ties are resolved by arrival order
+            Some((current, _)) if self.is_last => &key >= current,
+            Some((current, _)) => &key < current,
+        };
+        if replace {
+            let datum = row.value_at(self.agg_col_idx).map(|x| x.into_scalar_impl());
+            self.result = Some((key, datum));
+        }
+    }
+}
+
+impl Aggregator for FirstLastValue {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn update_single(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        let (row, vis) = input.row_at(row_id);
+        assert!(vis);
+        self.push_row(row);
+        Ok(())
+    }
+
+    fn update_multi(
+        &mut self,
+        input: &DataChunk,
+        start_row_id: usize,
+        end_row_id: usize,
+    ) -> Result<()> {
+        for row_id in start_row_id..end_row_id {
+            self.update_single(input, row_id)?;
+        }
+        Ok(())
+    }
+
+    fn output(&mut self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        let datum = self.result.take().and_then(|(_, datum)| datum);
+        builder.append_datum(&datum);
+        Ok(())
+    }
+}
+
+pub fn create_first_last_value_state(
+    return_type: DataType,
+    agg_col_idx: usize,
+    order_pairs: Vec<OrderPair>,
+    is_last: bool,
+) -> Result<Box<dyn Aggregator>> {
+    Ok(Box::new(FirstLastValue::new(
+        return_type,
+        agg_col_idx,
+        order_pairs,
+        is_last,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::Array;
+    use piestream_common::test_prelude::DataChunkTestExt;
+
+    use super::*;
+
+    fn eval(chunk: &DataChunk, order_pairs: Vec<OrderPair>, is_last: bool) -> Result<Option<i32>> {
+        let return_type = DataType::Int32;
+        let mut agg = create_first_last_value_state(return_type.clone(), 0, order_pairs, is_last)?;
+        let mut builder = return_type.create_array_builder(0);
+        agg.update_multi(chunk, 0, chunk.cardinality())?;
+        agg.output(&mut builder)?;
+        Ok(builder.finish().as_int32().value_at(0))
+    }
+
+    #[test]
+    fn test_first_last_value() -> Result<()> {
+        let chunk = DataChunk::from_pretty(
+            "i   i
+             123 3
+             456 2
+             789 2
+             .   9",
+        );
+        assert_eq!(eval(&chunk, vec![], false)?, Some(123));
+        assert_eq!(eval(&chunk, vec![], true)?, None);
+
+        let order_by = vec![OrderPair::new(1, OrderType::Ascending)];
+        assert_eq!(eval(&chunk, order_by.clone(), false)?, Some(456));
+        assert_eq!(eval(&chunk, order_by, true)?, None);
+
+        let order_by = vec![
+            OrderPair::new(1, OrderType::Descending),
+            OrderPair::new(0, OrderType::Descending),
+        ];
+        assert_eq!(eval(&chunk, order_by.clone(), false)?, None);
+        assert_eq!(eval(&chunk, order_by, true)?, Some(456));
+        Ok(())
+    }
+
+    #[test]
+    fn test_first_value_empty() -> Result<()> {
+        let chunk = DataChunk::from_pretty("i i");
+        assert_eq!(eval(&chunk, vec![], false)?, None);
+        Ok(())
+    }
+}
@@ -55,7 +55,7 @@ where
 }
 
 use std::convert::From;
-use std::ops::Add;
+use std::ops::{Add, BitAnd, BitOr};
 
 use piestream_common::types::ScalarRef;
 
@@ -125,6 +125,48 @@ pub fn max_list<'a>(r: Option<ListRef<'a>>, i: Option<ListRef<'a>>) -> Result<Op
     max(r, i)
 }
 
+pub fn bool_and(result: Option<bool>, input: Option<bool>) -> Result<Option<bool>> {
+    let res = match (result, input) {
+        (None, _) => input,
+        (_, None) => result,
+        (Some(r), Some(i)) => Some(r && i),
+    };
+    Ok(res)
+}
+
+pub fn bool_or(result: Option<bool>, input: Option<bool>) -> Result<Option<bool>> {
+    let res = match (result, input) {
+        (None, _) => input,
+        (_, None) => result,
+        (Some(r), Some(i)) => Some(r || i),
+    };
+    Ok(res)
+}
+
+pub fn bit_and<T>(result: Option<T>, input: Option<T>) -> Result<Option<T>>
+where
+    T: BitAnd<Output = T> + Copy,
+{
+    let res = match (result, input) {
+        (None, _) => input,
+        (_, None) => result,
+        (Some(r), Some(i)) => Some(r & i),
+    };
+    Ok(res)
+}
+
+pub fn bit_or<T>(result: Option<T>, input: Option<T>) -> Result<Option<T>>
+where
+    T: BitOr<Output = T> + Copy,
+{
+    let res = match (result, input) {
+        (None, _) => input,
+        (_, None) => result,
+        (Some(r), Some(i)) => Some(r | i),
+    };
+    Ok(res)
+}
+
 /// Note the following corner cases:
 ///
 /// ```slt
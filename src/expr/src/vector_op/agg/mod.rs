@@ -17,11 +17,14 @@ mod approx_count_distinct;
 mod array_agg;
 mod count_star;
 mod filter;
+mod first_last_value;
 mod functions;
 mod general_agg;
 mod general_distinct_agg;
 mod general_sorted_grouper;
+mod percentile;
 mod string_agg;
 
 pub use aggregator::{AggStateFactory, BoxedAggState};
 pub use general_sorted_grouper::{create_sorted_grouper, BoxedSortedGrouper, EqGroups};
+pub use percentile::{check_percentile_fraction, percentile_cont, percentile_disc_index};
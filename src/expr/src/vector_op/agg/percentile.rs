@@ -0,0 +1,245 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piestream_common::array::{ArrayBuilderImpl, DataChunk, RowRef};
+use piestream_common::types::{DataType, Datum, ScalarImpl};
+use piestream_common::util::ordered::OrderedRow;
+use piestream_common::util::sort_util::{OrderPair, OrderType};
+
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::{ExprError, Result};
+
+/// Checks that the fraction given to `percentile_cont` / `percentile_disc` is within `[0, 1]`.
+pub fn check_percentile_fraction(fraction: f64) -> Result<()> {
+    if (0.0..=1.0).contains(&fraction) {
+        Ok(())
+    } else {
+        Err(ExprError::InvalidParam {
+            name: "fraction",
+            reason: format!("percentile value {} is not between 0 and 1", fraction),
+        })
+    }
+}
+
+/// Computes `percentile_cont` over `sorted` values, interpolating linearly between the two
+/// nearest values if needed. `sorted` must not be empty.
+pub fn percentile_cont(sorted: &[f64], fraction: f64) -> f64 {
+    let pos = fraction * (sorted.len() - 1) as f64;
+    let (lower, upper) = (pos.floor() as usize, pos.ceil() as usize);
+    if lower == upper {
+        sorted[lower]
+    } else {
+        sorted[lower] + (pos - lower as f64) * (sorted[upper] - sorted[lower])
+    }
+}
+
+/// Returns the index of the value that `percentile_disc` picks among `len` sorted values, i.e. the
+/// first one whose position in the ordering is greater than or equal to `fraction`. `len` must
+/// not be zero.
+pub fn percentile_disc_index(len: usize, fraction: f64) -> usize {
+    ((fraction * len as f64).ceil() as usize).clamp(1, len) - 1
+}
+
+/// `percentile_cont` / `percentile_disc` aggregator. It collects all non-null input values along
+/// with their order key, and sorts them when the output is requested.
+#[derive(Clone)]
+struct Percentile {
+    return_type: DataType,
+    agg_col_idx: usize,
+    fraction_col_idx: usize,
+    order_col_indices: Vec<usize>,
+    order_types: Vec<OrderType>,
+    is_disc: bool,
+    fraction: Option<f64>,
+    unordered_values: Vec<(OrderedRow, ScalarImpl)>,
+}
+
+impl Percentile {
+    fn new(
+        return_type: DataType,
+        agg_col_idx: usize,
+        fraction_col_idx: usize,
+        order_pairs: Vec<OrderPair>,
+        is_disc: bool,
+    ) -> Self {
+        let (order_col_indices, order_types) = order_pairs
+            .into_iter()
+            .map(|p| (p.column_idx, p.order_type))
+            .unzip();
+        Percentile {
+            return_type,
+            agg_col_idx,
+            fraction_col_idx,
+            order_col_indices,
+            order_types,
+            is_disc,
+            fraction: None,
+            unordered_values: vec![],
+        }
+    }
+
+    fn push_row(&mut self, row: RowRef<'_>) -> Result<()> {
+        if self.fraction.is_none() {
+            if let Some(fraction) = row.value_at(self.fraction_col_idx) {
+                let fraction = fraction.into_scalar_impl().into_float64().into_inner();
+                check_percentile_fraction(fraction)?;
+                self.fraction = Some(fraction);
+            }
+        }
+        // null values are ignored
+        if let Some(value) = row.value_at(self.agg_col_idx) {
+            let key = OrderedRow::new(
+                row.row_by_indices(&self.order_col_indices),
+                &self.order_types,
+            );
+            self.unordered_values.push((key, value.into_scalar_impl()));
+        }
+        Ok(())
+    }
+
+    fn get_result_and_reset(&mut self) -> Datum {
+        let mut rows = std::mem::take(&mut self.unordered_values);
+        let fraction = self.fraction.take()?;
+        if rows.is_empty() {
+            return None;
+        }
+        rows.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        if self.is_disc {
+            let idx = percentile_disc_index(rows.len(), fraction);
+            Some(rows.swap_remove(idx).1)
+        } else {
+            let sorted = rows
+                .into_iter()
+                .map(|(_, v)| v.into_float64().into_inner())
+                .collect::<Vec<_>>();
+            Some(percentile_cont(&sorted, fraction).into())
+        }
+    }
+}
+
+impl Aggregator for Percentile {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn update_single(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        let (row, vis) = input.row_at(row_id);
+        assert!(vis);
+        self.push_row(row)
+    }
+
+    fn update_multi(
+        &mut self,
+        input: &DataChunk,
+        start_row_id: usize,
+        end_row_id: usize,
+    ) -> Result<()> {
+        self.unordered_values.reserve(end_row_id - start_row_id);
+        for row_id in start_row_id..end_row_id {
+            self.update_single(input, row_id)?;
+        }
+        Ok(())
+    }
+
+    fn output(&mut self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        builder.append_datum(&self.get_result_and_reset());
+        Ok(())
+    }
+}
+
+pub fn create_percentile_state(
+    return_type: DataType,
+    agg_col_idx: usize,
+    fraction_col_idx: usize,
+    order_pairs: Vec<OrderPair>,
+    is_disc: bool,
+) -> Result<Box<dyn Aggregator>> {
+    Ok(Box::new(Percentile::new(
+        return_type,
+        agg_col_idx,
+        fraction_col_idx,
+        order_pairs,
+        is_disc,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::Array;
+    use piestream_common::test_prelude::DataChunkTestExt;
+
+    use super::*;
+
+    #[test]
+    fn test_percentile_functions() {
+        assert_eq!(percentile_cont(&[1.0, 2.0, 3.0, 4.0], 0.5), 2.5);
+        assert_eq!(percentile_cont(&[1.0, 2.0, 3.0, 4.0], 0.0), 1.0);
+        assert_eq!(percentile_cont(&[1.0, 2.0, 3.0, 4.0], 1.0), 4.0);
+        assert_eq!(percentile_cont(&[7.0], 0.3), 7.0);
+        assert_eq!(percentile_disc_index(4, 0.5), 1);
+        assert_eq!(percentile_disc_index(4, 0.0), 0);
+        assert_eq!(percentile_disc_index(4, 1.0), 3);
+        assert_eq!(percentile_disc_index(4, 0.51), 2);
+        assert!(check_percentile_fraction(1.5).is_err());
+    }
+
+    #[test]
+    fn test_percentile_cont() -> Result<()> {
+        let chunk = DataChunk::from_pretty(
+            "F F
+             4 0.25
+             1 0.25
+             . 0.25
+             3 0.25
+             2 0.25",
+        );
+        let mut agg = create_percentile_state(
+            DataType::Float64,
+            0,
+            1,
+            vec![OrderPair::new(0, OrderType::Ascending)],
+            false,
+        )?;
+        let mut builder = DataType::Float64.create_array_builder(0);
+        agg.update_multi(&chunk, 0, chunk.cardinality())?;
+        agg.output(&mut builder)?;
+        let output = builder.finish();
+        assert_eq!(output.as_float64().value_at(0), Some(1.75.into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_percentile_disc_desc() -> Result<()> {
+        let chunk = DataChunk::from_pretty(
+            "i F
+             4 0.25
+             1 0.25
+             3 0.25
+             2 0.25",
+        );
+        let mut agg = create_percentile_state(
+            DataType::Int32,
+            0,
+            1,
+            vec![OrderPair::new(0, OrderType::Descending)],
+            true,
+        )?;
+        let mut builder = DataType::Int32.create_array_builder(0);
+        agg.update_multi(&chunk, 0, chunk.cardinality())?;
+        agg.output(&mut builder)?;
+        let output = builder.finish();
+        assert_eq!(output.as_int32().value_at(0), Some(4));
+        Ok(())
+    }
+}
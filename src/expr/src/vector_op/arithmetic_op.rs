@@ -85,10 +85,16 @@ pub fn general_mod<T1, T2, T3>(l: T1, r: T2) -> Result<T3>
 where
     T1: TryInto<T3> + Debug,
     T2: TryInto<T3> + Debug,
-    T3: CheckedRem,
+    T3: CheckedRem + Zero,
 {
     general_atm(l, r, |a, b| {
-        a.checked_rem(&b).ok_or(ExprError::NumericOutOfRange)
+        a.checked_rem(&b).ok_or_else(|| {
+            if b.is_zero() {
+                ExprError::DivisionByZero
+            } else {
+                ExprError::NumericOutOfRange
+            }
+        })
     })
 }
 
@@ -100,9 +100,14 @@ fn parse_naive_time(s: &str) -> Result<NaiveTime> {
 
 #[inline(always)]
 pub fn str_to_timestampz(elem: &str) -> Result<i64> {
-    DateTime::parse_from_str(elem, "%Y-%m-%d %H:%M:%S %:z")
-        .map(|ret| ret.timestamp_nanos() / 1000)
-        .map_err(|_| ExprError::Parse(PARSE_ERROR_STR_TO_TIMESTAMP))
+    // Accept both `1999-01-08 04:05:06 +08:00` and RFC 3339 (`1999-01-08T04:05:06+08:00`). Strings
+    // without an offset are interpreted in UTC.
+    if let Ok(ret) = DateTime::parse_from_str(elem, "%Y-%m-%d %H:%M:%S%.f %:z")
+        .or_else(|_| DateTime::parse_from_rfc3339(elem))
+    {
+        return utc_naive_to_timestampz(ret.naive_utc());
+    }
+    utc_naive_to_timestampz(parse_naive_datetime(elem)?)
 }
 
 #[inline(always)]
@@ -191,6 +196,53 @@ pub fn timestamp_to_time(elem: NaiveDateTimeWrapper) -> Result<NaiveTimeWrapper>
     Ok(NaiveTimeWrapper(elem.0.time()))
 }
 
+/// Microseconds per second, the precision `Timestampz` is stored in.
+const USECS_PER_SEC: i64 = 1_000_000;
+
+/// Converts a `Timestampz`, i.e. microseconds since the Unix epoch, to the UTC date time.
+#[inline(always)]
+pub fn timestampz_to_utc_naive(elem: i64) -> Result<NaiveDateTime> {
+    let secs = elem.div_euclid(USECS_PER_SEC);
+    let nsecs = (elem.rem_euclid(USECS_PER_SEC) * 1000) as u32;
+    NaiveDateTime::from_timestamp_opt(secs, nsecs).ok_or(ExprError::NumericOutOfRange)
+}
+
+/// Converts a UTC date time to `Timestampz`, i.e. microseconds since the Unix epoch.
+#[inline(always)]
+pub fn utc_naive_to_timestampz(elem: NaiveDateTime) -> Result<i64> {
+    elem.timestamp()
+        .checked_mul(USECS_PER_SEC)
+        .and_then(|us| us.checked_add(elem.timestamp_subsec_micros() as i64))
+        .ok_or(ExprError::NumericOutOfRange)
+}
+
+// TODO: Casts between `timestamp` and `timestamptz` should respect the session time zone. As the
+// session time zone is always UTC for now, the wall clock time is interpreted in UTC.
+#[inline(always)]
+pub fn timestamp_to_timestampz(elem: NaiveDateTimeWrapper) -> Result<i64> {
+    utc_naive_to_timestampz(elem.0)
+}
+
+#[inline(always)]
+pub fn date_to_timestampz(elem: NaiveDateWrapper) -> Result<i64> {
+    utc_naive_to_timestampz(elem.0.and_hms(0, 0, 0))
+}
+
+#[inline(always)]
+pub fn timestampz_to_timestamp(elem: i64) -> Result<NaiveDateTimeWrapper> {
+    timestampz_to_utc_naive(elem).map(NaiveDateTimeWrapper)
+}
+
+#[inline(always)]
+pub fn timestampz_to_date(elem: i64) -> Result<NaiveDateWrapper> {
+    timestampz_to_utc_naive(elem).map(|t| NaiveDateWrapper(t.date()))
+}
+
+#[inline(always)]
+pub fn timestampz_to_time(elem: i64) -> Result<NaiveTimeWrapper> {
+    timestampz_to_utc_naive(elem).map(|t| NaiveTimeWrapper(t.time()))
+}
+
 /// In `PostgreSQL`, casting from interval to time discards the days part.
 #[inline(always)]
 pub fn interval_to_time(elem: IntervalUnit) -> Result<NaiveTimeWrapper> {
@@ -319,9 +371,14 @@ macro_rules! for_all_cast_variants {
             { decimal, float64, to_f64 },
 
             { date, timestamp, general_cast },
+            { date, timestampz, date_to_timestampz },
             { time, interval, general_cast },
             { timestamp, date, timestamp_to_date },
             { timestamp, time, timestamp_to_time },
+            { timestamp, timestampz, timestamp_to_timestampz },
+            { timestampz, date, timestampz_to_date },
+            { timestampz, time, timestampz_to_time },
+            { timestampz, timestamp, timestampz_to_timestamp },
             { interval, time, interval_to_time }
         }
     };
@@ -501,6 +558,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_str_to_timestampz() {
+        let expected = 1_659_522_842_000_000;
+        assert_eq!(
+            str_to_timestampz("2022-08-03 10:34:02 +00:00").unwrap(),
+            expected
+        );
+        assert_eq!(
+            str_to_timestampz("2022-08-03 18:34:02 +08:00").unwrap(),
+            expected
+        );
+        assert_eq!(str_to_timestampz("2022-08-03T10:34:02Z").unwrap(), expected);
+        assert_eq!(str_to_timestampz("2022-08-03 10:34:02").unwrap(), expected);
+        assert_eq!(
+            timestampz_to_timestamp(expected).unwrap(),
+            str_to_timestamp("2022-08-03 10:34:02").unwrap()
+        );
+        assert_eq!(
+            timestamp_to_timestampz(str_to_timestamp("1969-12-31 23:59:59.5").unwrap()).unwrap(),
+            -500_000
+        );
+        assert_eq!(timestampz_to_utc_naive(-500_000).unwrap().timestamp(), -1);
+    }
+
     #[test]
     fn integer_cast_to_bool() {
         use super::*;
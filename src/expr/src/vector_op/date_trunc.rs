@@ -0,0 +1,163 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use piestream_common::types::{IntervalUnit, NaiveDateTimeWrapper};
+
+use crate::vector_op::cast::{timestampz_to_utc_naive, utc_naive_to_timestampz};
+use crate::{ExprError, Result};
+
+fn invalid_field(field: &str) -> ExprError {
+    ExprError::InvalidParam {
+        name: "field",
+        reason: format!("unit \"{}\" not recognized", field),
+    }
+}
+
+/// The first year of the century or millennium of `year`. There is no year 0 in the Gregorian
+/// calendar, so the first century starts with year 1 (and `chrono` counts 1 BC as year 0).
+fn first_year_of(year: i32, span: i32) -> i32 {
+    (year - 1).div_euclid(span) * span + 1
+}
+
+fn truncate_date_time(field: &str, time: NaiveDateTime) -> Result<NaiveDateTime> {
+    let date = time.date();
+    let first_day_of_year = |year| NaiveDate::from_ymd_opt(year, 1, 1);
+    let truncated =
+        match field.to_ascii_lowercase().as_str() {
+            "microseconds" => time.with_nanosecond(time.nanosecond() / 1_000 * 1_000),
+            "milliseconds" => time.with_nanosecond(time.nanosecond() / 1_000_000 * 1_000_000),
+            "second" => time.with_nanosecond(0),
+            "minute" => date.and_hms_opt(time.hour(), time.minute(), 0),
+            "hour" => date.and_hms_opt(time.hour(), 0, 0),
+            "day" => date.and_hms_opt(0, 0, 0),
+            "week" => (date - Duration::days(date.weekday().num_days_from_monday() as i64))
+                .and_hms_opt(0, 0, 0),
+            "month" => NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+                .and_then(|d| d.and_hms_opt(0, 0, 0)),
+            "quarter" => NaiveDate::from_ymd_opt(date.year(), (date.month() - 1) / 3 * 3 + 1, 1)
+                .and_then(|d| d.and_hms_opt(0, 0, 0)),
+            "year" => first_day_of_year(date.year()).and_then(|d| d.and_hms_opt(0, 0, 0)),
+            "decade" => first_day_of_year(date.year().div_euclid(10) * 10)
+                .and_then(|d| d.and_hms_opt(0, 0, 0)),
+            "century" => first_day_of_year(first_year_of(date.year(), 100))
+                .and_then(|d| d.and_hms_opt(0, 0, 0)),
+            "millennium" => first_day_of_year(first_year_of(date.year(), 1000))
+                .and_then(|d| d.and_hms_opt(0, 0, 0)),
+            _ => return Err(invalid_field(field)),
+        };
+    truncated.ok_or(ExprError::NumericOutOfRange)
+}
+
+#[inline(always)]
+pub fn date_trunc_timestamp(
+    field: &str,
+    timestamp: NaiveDateTimeWrapper,
+) -> Result<NaiveDateTimeWrapper> {
+    truncate_date_time(field, timestamp.0).map(NaiveDateTimeWrapper)
+}
+
+/// `timestamptz` values are truncated in UTC.
+#[inline(always)]
+pub fn date_trunc_timestampz(field: &str, usecs: i64) -> Result<i64> {
+    utc_naive_to_timestampz(truncate_date_time(field, timestampz_to_utc_naive(usecs)?)?)
+}
+
+#[inline(always)]
+pub fn date_trunc_interval(field: &str, interval: IntervalUnit) -> Result<IntervalUnit> {
+    let months = interval.get_months();
+    let days = interval.get_days();
+    let ms = interval.get_ms();
+    let truncated = match field.to_ascii_lowercase().as_str() {
+        "microseconds" | "milliseconds" => interval,
+        "second" => IntervalUnit::new(months, days, ms / 1_000 * 1_000),
+        "minute" => IntervalUnit::new(months, days, ms / 60_000 * 60_000),
+        "hour" => IntervalUnit::new(months, days, ms / 3_600_000 * 3_600_000),
+        "day" => IntervalUnit::new(months, days, 0),
+        "month" => IntervalUnit::new(months, 0, 0),
+        "quarter" => IntervalUnit::new(months / 3 * 3, 0, 0),
+        "year" => IntervalUnit::new(months / 12 * 12, 0, 0),
+        "decade" => IntervalUnit::new(months / 120 * 120, 0, 0),
+        "century" => IntervalUnit::new(months / 1_200 * 1_200, 0, 0),
+        "millennium" => IntervalUnit::new(months / 12_000 * 12_000, 0, 0),
+        // `week` is not allowed for intervals since months are not whole weeks.
+        _ => return Err(invalid_field(field)),
+    };
+    Ok(truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::types::IntervalUnit;
+
+    use super::*;
+    use crate::vector_op::cast::str_to_timestamp;
+
+    #[test]
+    fn test_date_trunc_timestamp() {
+        let time = str_to_timestamp("2022-08-17 21:36:41.123456").unwrap();
+        let cases = [
+            ("microseconds", "2022-08-17 21:36:41.123456"),
+            ("milliseconds", "2022-08-17 21:36:41.123"),
+            ("second", "2022-08-17 21:36:41"),
+            ("minute", "2022-08-17 21:36:00"),
+            ("HOUR", "2022-08-17 21:00:00"),
+            ("day", "2022-08-17 00:00:00"),
+            ("week", "2022-08-15 00:00:00"),
+            ("month", "2022-08-01 00:00:00"),
+            ("quarter", "2022-07-01 00:00:00"),
+            ("year", "2022-01-01 00:00:00"),
+            ("decade", "2020-01-01 00:00:00"),
+            ("century", "2001-01-01 00:00:00"),
+            ("millennium", "2001-01-01 00:00:00"),
+        ];
+        for (field, expected) in cases {
+            assert_eq!(
+                date_trunc_timestamp(field, time).unwrap(),
+                str_to_timestamp(expected).unwrap(),
+                "{}",
+                field
+            );
+        }
+        assert!(date_trunc_timestamp("fortnight", time).is_err());
+    }
+
+    #[test]
+    fn test_date_trunc_timestampz() {
+        // 2022-08-17 21:36:41.5 UTC
+        let usecs = 1_660_772_201_500_000;
+        assert_eq!(
+            date_trunc_timestampz("day", usecs).unwrap(),
+            1_660_694_400_000_000
+        );
+        assert_eq!(
+            date_trunc_timestampz("second", usecs).unwrap(),
+            1_660_772_201_000_000
+        );
+    }
+
+    #[test]
+    fn test_date_trunc_interval() {
+        let interval = IntervalUnit::new(27, 3, 3_723_000);
+        assert_eq!(
+            date_trunc_interval("hour", interval).unwrap(),
+            IntervalUnit::new(27, 3, 3_600_000)
+        );
+        assert_eq!(
+            date_trunc_interval("year", interval).unwrap(),
+            IntervalUnit::new(24, 0, 0)
+        );
+        assert!(date_trunc_interval("week", interval).is_err());
+    }
+}
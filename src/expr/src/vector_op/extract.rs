@@ -12,19 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use chrono::{Datelike, Timelike};
-use piestream_common::types::{Decimal, NaiveDateTimeWrapper, NaiveDateWrapper};
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use piestream_common::types::{
+    Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, NaiveTimeWrapper,
+};
 
+use crate::vector_op::cast::timestampz_to_utc_naive;
 use crate::{bail, Result};
 
+/// Microseconds since midnight, including the fractional second part.
+fn micros_of_day<T: Timelike>(time: &T) -> i64 {
+    time.num_seconds_from_midnight() as i64 * 1_000_000 + (time.nanosecond() / 1000) as i64
+}
+
 fn extract_time<T>(time: T, time_unit: &str) -> Result<Decimal>
 where
     T: Timelike,
 {
+    let micros_of_minute = time.second() as i64 * 1_000_000 + (time.nanosecond() / 1000) as i64;
     match time_unit {
         "HOUR" => Ok(time.hour().into()),
         "MINUTE" => Ok(time.minute().into()),
-        "SECOND" => Ok(time.second().into()),
+        "SECOND" => Ok(Decimal::new(micros_of_minute, 6).normalize()),
+        "MILLISECOND" => Ok(Decimal::new(micros_of_minute, 3).normalize()),
+        "MICROSECOND" => Ok(micros_of_minute.into()),
         _ => bail!("Unsupported time unit {} in extract function", time_unit),
     }
 }
@@ -33,28 +44,85 @@ fn extract_date<T>(date: T, time_unit: &str) -> Result<Decimal>
 where
     T: Datelike,
 {
+    let year = date.year();
     match time_unit {
         "DAY" => Ok(date.day().into()),
         "MONTH" => Ok(date.month().into()),
-        "YEAR" => Ok(date.year().into()),
+        "YEAR" => Ok(year.into()),
+        "QUARTER" => Ok(((date.month() - 1) / 3 + 1).into()),
+        "WEEK" => Ok(date.iso_week().week().into()),
+        "ISOYEAR" => Ok(date.iso_week().year().into()),
         // Sun = 0 and Sat = 6
         "DOW" => Ok(date.weekday().num_days_from_sunday().into()),
+        // Mon = 1 and Sun = 7
+        "ISODOW" => Ok(date.weekday().number_from_monday().into()),
         "DOY" => Ok(date.ordinal().into()),
+        "DECADE" => Ok(year.div_euclid(10).into()),
+        // There is no year 0, so the first century starts with year 1.
+        "CENTURY" => Ok(if year > 0 {
+            (year + 99) / 100
+        } else {
+            -((99 - (year - 1)) / 100)
+        }
+        .into()),
+        "MILLENNIUM" => Ok(if year > 0 {
+            (year + 999) / 1000
+        } else {
+            -((999 - (year - 1)) / 1000)
+        }
+        .into()),
         _ => bail!("Unsupported time unit {} in extract function", time_unit),
     }
 }
 
+fn extract_date_time(time: NaiveDateTime, time_unit: &str) -> Result<Decimal> {
+    if time_unit == "EPOCH" {
+        let micros = time.timestamp() * 1_000_000 + time.timestamp_subsec_micros() as i64;
+        return Ok(Decimal::new(micros, 6).normalize());
+    }
+    extract_date(time, time_unit).or_else(|_| extract_time(time, time_unit))
+}
+
 pub fn extract_from_date(time_unit: &str, date: NaiveDateWrapper) -> Result<Decimal> {
+    if time_unit == "EPOCH" {
+        return Ok(date.0.and_hms(0, 0, 0).timestamp().into());
+    }
     extract_date(date.0, time_unit)
 }
 
 pub fn extract_from_timestamp(time_unit: &str, timestamp: NaiveDateTimeWrapper) -> Result<Decimal> {
-    let time = timestamp.0;
-    let mut res = extract_date(time, time_unit);
-    if res.is_err() {
-        res = extract_time(time, time_unit);
+    extract_date_time(timestamp.0, time_unit)
+}
+
+/// Fields of `timestamptz` are extracted in UTC.
+pub fn extract_from_timestampz(time_unit: &str, usecs: i64) -> Result<Decimal> {
+    if time_unit == "EPOCH" {
+        return Ok(Decimal::new(usecs, 6).normalize());
+    }
+    extract_date_time(timestampz_to_utc_naive(usecs)?, time_unit)
+}
+
+pub fn extract_from_time(time_unit: &str, time: NaiveTimeWrapper) -> Result<Decimal> {
+    if time_unit == "EPOCH" {
+        return Ok(Decimal::new(micros_of_day(&time.0), 6).normalize());
+    }
+    extract_time(time.0, time_unit)
+}
+
+/// Like `PostgreSQL`, a month is regarded as 30 days when extracting `EPOCH` from an interval.
+pub fn extract_from_interval(time_unit: &str, interval: IntervalUnit) -> Result<Decimal> {
+    let ms = interval.get_ms();
+    match time_unit {
+        "EPOCH" => Ok(Decimal::new(interval.total_ms(), 3).normalize()),
+        "YEAR" => Ok(interval.get_years().into()),
+        "MONTH" => Ok((interval.get_months() % 12).into()),
+        "DAY" => Ok(interval.get_days().into()),
+        "HOUR" => Ok((ms / 3_600_000).into()),
+        "MINUTE" => Ok((ms / 60_000 % 60).into()),
+        "SECOND" => Ok(Decimal::new(ms % 60_000, 3).normalize()),
+        "MILLISECOND" => Ok((ms % 60_000).into()),
+        _ => bail!("Unsupported time unit {} in extract function", time_unit),
     }
-    res
 }
 
 #[cfg(test)]
@@ -82,5 +150,37 @@ mod tests {
         assert_eq!(extract_from_timestamp("HOUR", time).unwrap(), 12.into());
         assert_eq!(extract_from_timestamp("MINUTE", time).unwrap(), 4.into());
         assert_eq!(extract_from_timestamp("SECOND", time).unwrap(), 2.into());
+        assert_eq!(
+            extract_from_timestamp("EPOCH", time).unwrap(),
+            1_637_582_642.into()
+        );
+        assert_eq!(extract_from_timestamp("QUARTER", time).unwrap(), 4.into());
+        assert_eq!(extract_from_timestamp("CENTURY", time).unwrap(), 21.into());
+        assert_eq!(
+            extract_from_timestampz("EPOCH", 1_637_582_642_500_000).unwrap(),
+            Decimal::new(16_375_826_425, 1)
+        );
+        assert_eq!(
+            extract_from_timestampz("HOUR", 1_637_582_642_500_000).unwrap(),
+            12.into()
+        );
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = IntervalUnit::new(14, 3, 3_723_500);
+        assert_eq!(extract_from_interval("YEAR", interval).unwrap(), 1.into());
+        assert_eq!(extract_from_interval("MONTH", interval).unwrap(), 2.into());
+        assert_eq!(extract_from_interval("DAY", interval).unwrap(), 3.into());
+        assert_eq!(extract_from_interval("HOUR", interval).unwrap(), 1.into());
+        assert_eq!(extract_from_interval("MINUTE", interval).unwrap(), 2.into());
+        assert_eq!(
+            extract_from_interval("SECOND", interval).unwrap(),
+            Decimal::new(35, 1)
+        );
+        assert_eq!(
+            extract_from_interval("EPOCH", IntervalUnit::new(0, 1, 1500)).unwrap(),
+            Decimal::new(864015, 1)
+        );
     }
 }
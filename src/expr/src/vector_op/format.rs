@@ -0,0 +1,189 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::{ExprError, Result};
+
+fn invalid(reason: impl Into<String>) -> ExprError {
+    ExprError::InvalidParam {
+        name: "format",
+        reason: reason.into(),
+    }
+}
+
+/// Formats arguments according to a format string, like `PostgreSQL`'s `format()`.
+///
+/// Supported format specifiers are `%s` (simple string, `NULL` as empty), `%I` (SQL identifier,
+/// quoted if necessary), `%L` (SQL literal, `NULL` as unquoted `NULL`) and `%%`. A specifier may
+/// refer to an argument explicitly as in `%2$s`; otherwise it consumes the argument following
+/// the last one used.
+pub fn format(formatstr: &str, args: &[Option<&str>]) -> Result<String> {
+    let mut result = String::with_capacity(formatstr.len());
+    let mut chars = formatstr.chars().peekable();
+    let mut next_arg = 0;
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'%') {
+            chars.next();
+            result.push('%');
+            continue;
+        }
+
+        let index = match parse_position(&mut chars)? {
+            Some(position) => position,
+            None => next_arg,
+        };
+        let spec = chars
+            .next()
+            .ok_or_else(|| invalid("unterminated format() type specifier"))?;
+        let arg = *args
+            .get(index)
+            .ok_or_else(|| invalid("too few arguments for format()"))?;
+        next_arg = index + 1;
+
+        match spec {
+            's' => result.push_str(arg.unwrap_or_default()),
+            'I' => {
+                let arg = arg.ok_or_else(|| {
+                    invalid("null values cannot be formatted as an SQL identifier")
+                })?;
+                quote_ident(arg, &mut result);
+            }
+            'L' => match arg {
+                Some(arg) => quote_literal(arg, &mut result),
+                None => result.push_str("NULL"),
+            },
+            c => {
+                return Err(invalid(format!(
+                    "unrecognized format() type specifier \"{}\"",
+                    c
+                )))
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Parses an optional explicit argument position `n$`, returning the zero-based index.
+fn parse_position(chars: &mut Peekable<Chars<'_>>) -> Result<Option<usize>> {
+    if !chars.peek().map_or(false, char::is_ascii_digit) {
+        return Ok(None);
+    }
+    let mut position = 0usize;
+    while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+        chars.next();
+        position = position
+            .checked_mul(10)
+            .and_then(|p| p.checked_add(digit as usize))
+            .ok_or_else(|| invalid("number is out of range"))?;
+    }
+    if chars.next() != Some('$') {
+        return Err(invalid("width is not supported in format()"));
+    }
+    if position == 0 {
+        return Err(invalid(
+            "format specifies argument 0, but arguments are numbered from 1",
+        ));
+    }
+    Ok(Some(position - 1))
+}
+
+/// Quotes `ident` as an SQL identifier if it is not a plain lower-case identifier.
+fn quote_ident(ident: &str, result: &mut String) {
+    let needs_quote = !ident.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        || !ident
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !needs_quote {
+        result.push_str(ident);
+        return;
+    }
+    result.push('"');
+    for c in ident.chars() {
+        if c == '"' {
+            result.push('"');
+        }
+        result.push(c);
+    }
+    result.push('"');
+}
+
+/// Quotes `literal` as an SQL string literal, using the escape string syntax if it contains
+/// backslashes.
+fn quote_literal(literal: &str, result: &mut String) {
+    if literal.contains('\\') {
+        result.push('E');
+    }
+    result.push('\'');
+    for c in literal.chars() {
+        if c == '\'' || c == '\\' {
+            result.push(c);
+        }
+        result.push(c);
+    }
+    result.push('\'');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let cases: &[(&str, &[Option<&str>], &str)] = &[
+            ("Hello %s", &[Some("World")], "Hello World"),
+            (
+                "Testing %s, %s, %s, %%",
+                &[Some("one"), Some("two"), Some("three")],
+                "Testing one, two, three, %",
+            ),
+            (
+                "INSERT INTO %I VALUES(%L)",
+                &[Some("Foo bar"), Some("O'Reilly")],
+                "INSERT INTO \"Foo bar\" VALUES('O''Reilly')",
+            ),
+            (
+                "INSERT INTO %I VALUES(%L)",
+                &[Some("locations"), Some("C:\\Program Files")],
+                "INSERT INTO locations VALUES(E'C:\\\\Program Files')",
+            ),
+            ("%s|%L", &[None, None], "|NULL"),
+            ("%2$s %1$s %s", &[Some("a"), Some("b")], "b a b"),
+        ];
+        for (formatstr, args, expected) in cases {
+            assert_eq!(format(formatstr, args).unwrap(), *expected);
+        }
+    }
+
+    #[test]
+    fn test_format_error() {
+        let cases: &[(&str, &[Option<&str>])] = &[
+            ("%s %s", &[Some("a")]),
+            ("%I", &[None]),
+            ("%x", &[Some("a")]),
+            ("%", &[Some("a")]),
+            ("%0$s", &[Some("a")]),
+        ];
+        for (formatstr, args) in cases {
+            assert!(format(formatstr, args).is_err(), "{}", formatstr);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piestream_common::array::{BytesGuard, BytesWriter};
+
+use crate::Result;
+
+/// Converts the first letter of each word to upper case and the rest to lower case. Words are
+/// sequences of alphanumeric characters separated by non-alphanumeric characters.
+#[inline(always)]
+pub fn initcap(s: &str, writer: BytesWriter) -> Result<BytesGuard> {
+    let mut result = String::with_capacity(s.len());
+    let mut in_word = false;
+    for c in s.chars() {
+        if in_word {
+            result.extend(c.to_lowercase());
+        } else {
+            result.extend(c.to_uppercase());
+        }
+        in_word = c.is_alphanumeric();
+    }
+    writer.write_ref(&result).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::{Array, ArrayBuilder, Utf8ArrayBuilder};
+
+    use super::*;
+
+    #[test]
+    fn test_initcap() -> Result<()> {
+        let cases = [
+            ("hi THOMAS", "Hi Thomas"),
+            ("foo_bar-baz qux", "Foo_Bar-Baz Qux"),
+            ("1st place", "1st Place"),
+            ("élan vital", "Élan Vital"),
+        ];
+
+        for (s, expected) in cases {
+            let builder = Utf8ArrayBuilder::new(1);
+            let writer = builder.writer();
+            let guard = initcap(s, writer)?;
+            let array = guard.into_inner().finish();
+            let v = array.value_at(0).unwrap();
+            assert_eq!(v, expected);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{NaiveDate, NaiveTime};
+use piestream_common::types::{NaiveDateWrapper, NaiveTimeWrapper, OrderedF64};
+
+use crate::{ExprError, Result};
+
+/// `make_date(year, month, day)`. Like `PostgreSQL`, negative years count BC.
+#[inline(always)]
+pub fn make_date(year: i32, month: i32, day: i32) -> Result<NaiveDateWrapper> {
+    let out_of_range = || ExprError::InvalidParam {
+        name: "date",
+        reason: format!("date field value out of range: {}-{}-{}", year, month, day),
+    };
+    // There is no year 0, and `chrono` counts 1 BC as year 0.
+    let chrono_year = match year {
+        0 => return Err(out_of_range()),
+        y if y < 0 => y + 1,
+        y => y,
+    };
+    let month = u32::try_from(month).map_err(|_| out_of_range())?;
+    let day = u32::try_from(day).map_err(|_| out_of_range())?;
+    NaiveDate::from_ymd_opt(chrono_year, month, day)
+        .map(NaiveDateWrapper)
+        .ok_or_else(out_of_range)
+}
+
+/// `make_time(hour, min, sec)`.
+#[inline(always)]
+pub fn make_time(hour: i32, min: i32, sec: OrderedF64) -> Result<NaiveTimeWrapper> {
+    let out_of_range = || ExprError::InvalidParam {
+        name: "time",
+        reason: format!("time field value out of range: {}:{}:{}", hour, min, sec),
+    };
+    let sec = sec.0;
+    if !(0.0..60.0).contains(&sec) {
+        return Err(out_of_range());
+    }
+    let hour = u32::try_from(hour).map_err(|_| out_of_range())?;
+    let min = u32::try_from(min).map_err(|_| out_of_range())?;
+    let micros = (sec * 1e6).round() as u32;
+    NaiveTime::from_hms_micro_opt(hour, min, micros / 1_000_000, micros % 1_000_000)
+        .map(NaiveTimeWrapper)
+        .ok_or_else(out_of_range)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Datelike;
+
+    use super::*;
+    use crate::vector_op::cast::{str_to_date, str_to_time};
+
+    #[test]
+    fn test_make_date() {
+        assert_eq!(
+            make_date(2022, 8, 3).unwrap(),
+            str_to_date("2022-08-03").unwrap()
+        );
+        assert_eq!(make_date(-44, 3, 15).unwrap().0.year(), -43);
+        assert!(make_date(2022, 2, 30).is_err());
+        assert!(make_date(0, 1, 1).is_err());
+        assert!(make_date(2022, -1, 1).is_err());
+    }
+
+    #[test]
+    fn test_make_time() {
+        assert_eq!(
+            make_time(8, 15, 23.5.into()).unwrap(),
+            str_to_time("08:15:23.5").unwrap()
+        );
+        assert!(make_time(24, 0, 0.0.into()).is_err());
+        assert!(make_time(8, 15, 60.0.into()).is_err());
+    }
+}
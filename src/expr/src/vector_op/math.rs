@@ -0,0 +1,376 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::{FromPrimitive, ToPrimitive};
+use piestream_common::types::{Decimal, OrderedF64};
+use rust_decimal::{Decimal as RustDecimal, RoundingStrategy};
+
+use crate::{ExprError, Result};
+
+/// Checks the result of a float operation for overflow and underflow the way `PostgreSQL` does:
+/// an infinite result is only allowed from an infinite input, and zero only from a zero input.
+#[inline(always)]
+fn check_float(input_inf: bool, zero_is_ok: bool, res: f64) -> Result<OrderedF64> {
+    if res.is_infinite() && !input_inf {
+        return Err(ExprError::NumericOutOfRange);
+    }
+    if res == 0.0 && !zero_is_ok {
+        return Err(ExprError::NumericOutOfRange);
+    }
+    Ok(res.into())
+}
+
+/// Evaluates a numeric function through its `float8` counterpart. `NaN` and infinities map to the
+/// corresponding special values of [`Decimal`].
+#[inline(always)]
+fn decimal_by_f64(
+    input: Decimal,
+    f: impl FnOnce(OrderedF64) -> Result<OrderedF64>,
+) -> Result<Decimal> {
+    let input = input.to_f64().ok_or(ExprError::NumericOutOfRange)?;
+    let res = f(input.into())?;
+    Decimal::from_f64(res.0)
+        .map(|d| d.normalize())
+        .ok_or(ExprError::NumericOutOfRange)
+}
+
+#[inline(always)]
+pub fn sqrt_f64(input: OrderedF64) -> Result<OrderedF64> {
+    if input.0 < 0.0 {
+        return Err(ExprError::InvalidArgumentForPower(
+            "cannot take square root of a negative number",
+        ));
+    }
+    Ok(input.0.sqrt().into())
+}
+
+#[inline(always)]
+pub fn sqrt_decimal(input: Decimal) -> Result<Decimal> {
+    decimal_by_f64(input, sqrt_f64)
+}
+
+#[inline(always)]
+pub fn cbrt_f64(input: OrderedF64) -> Result<OrderedF64> {
+    Ok(input.0.cbrt().into())
+}
+
+#[inline(always)]
+pub fn pow_f64(l: OrderedF64, r: OrderedF64) -> Result<OrderedF64> {
+    let (x, y) = (l.0, r.0);
+    if x == 0.0 && y < 0.0 {
+        return Err(ExprError::InvalidArgumentForPower(
+            "zero raised to a negative power is undefined",
+        ));
+    }
+    if x < 0.0 && y.is_finite() && y.fract() != 0.0 {
+        return Err(ExprError::InvalidArgumentForPower(
+            "a negative number raised to a non-integer power yields a complex result",
+        ));
+    }
+    let res = x.powf(y);
+    if res.is_infinite() && x.is_finite() && y.is_finite() {
+        return Err(ExprError::NumericOutOfRange);
+    }
+    Ok(res.into())
+}
+
+#[inline(always)]
+pub fn pow_decimal(l: Decimal, r: Decimal) -> Result<Decimal> {
+    let r = r.to_f64().ok_or(ExprError::NumericOutOfRange)?;
+    decimal_by_f64(l, |l| pow_f64(l, r.into()))
+}
+
+#[inline(always)]
+pub fn exp_f64(input: OrderedF64) -> Result<OrderedF64> {
+    if input.0.is_nan() {
+        return Ok(input);
+    }
+    // `exp(-inf)` is exactly zero.
+    check_float(
+        input.0.is_infinite(),
+        input.0 == f64::NEG_INFINITY,
+        input.0.exp(),
+    )
+}
+
+#[inline(always)]
+pub fn exp_decimal(input: Decimal) -> Result<Decimal> {
+    decimal_by_f64(input, exp_f64)
+}
+
+#[inline(always)]
+fn check_log_argument(input: f64) -> Result<()> {
+    if input == 0.0 {
+        Err(ExprError::InvalidArgumentForLogarithm(
+            "cannot take logarithm of zero",
+        ))
+    } else if input < 0.0 {
+        Err(ExprError::InvalidArgumentForLogarithm(
+            "cannot take logarithm of a negative number",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[inline(always)]
+pub fn ln_f64(input: OrderedF64) -> Result<OrderedF64> {
+    check_log_argument(input.0)?;
+    Ok(input.0.ln().into())
+}
+
+#[inline(always)]
+pub fn ln_decimal(input: Decimal) -> Result<Decimal> {
+    decimal_by_f64(input, ln_f64)
+}
+
+#[inline(always)]
+pub fn log10_f64(input: OrderedF64) -> Result<OrderedF64> {
+    check_log_argument(input.0)?;
+    Ok(input.0.log10().into())
+}
+
+#[inline(always)]
+pub fn log10_decimal(input: Decimal) -> Result<Decimal> {
+    decimal_by_f64(input, log10_f64)
+}
+
+/// `log(base, input)`
+#[inline(always)]
+pub fn log_decimal(base: Decimal, input: Decimal) -> Result<Decimal> {
+    let base = base.to_f64().ok_or(ExprError::NumericOutOfRange)?;
+    check_log_argument(base)?;
+    if base == 1.0 {
+        return Err(ExprError::DivisionByZero);
+    }
+    decimal_by_f64(input, |input| {
+        check_log_argument(input.0)?;
+        Ok((input.0.ln() / base.ln()).into())
+    })
+}
+
+#[inline(always)]
+pub fn sign_f64(input: OrderedF64) -> Result<OrderedF64> {
+    let res = match input.0 {
+        x if x.is_nan() => f64::NAN,
+        x if x > 0.0 => 1.0,
+        x if x < 0.0 => -1.0,
+        _ => 0.0,
+    };
+    Ok(res.into())
+}
+
+#[inline(always)]
+pub fn sign_decimal(input: Decimal) -> Result<Decimal> {
+    let res = match input {
+        Decimal::Normalized(d) if d.is_zero() => Decimal::zero(),
+        Decimal::Normalized(d) if d.is_sign_negative() => Decimal::from(-1),
+        Decimal::Normalized(_) | Decimal::PositiveInf => Decimal::from(1),
+        Decimal::NegativeInf => Decimal::from(-1),
+        Decimal::NaN => Decimal::NaN,
+    };
+    Ok(res)
+}
+
+#[inline(always)]
+pub fn trunc_f64(input: OrderedF64) -> Result<OrderedF64> {
+    Ok(input.0.trunc().into())
+}
+
+#[inline(always)]
+pub fn trunc_decimal(input: Decimal) -> Result<Decimal> {
+    trunc_digits(input, 0)
+}
+
+/// Truncates toward zero to `digits` decimal places. A negative `digits` truncates to the left of
+/// the decimal point, e.g. `trunc(1234.5, -2) = 1200`.
+#[inline(always)]
+pub fn trunc_digits<D: Into<i32>>(input: Decimal, digits: D) -> Result<Decimal> {
+    let digits = digits.into();
+    let Decimal::Normalized(d) = input else {
+        return Ok(input);
+    };
+    if digits >= 0 {
+        return Ok(Decimal::Normalized(
+            d.round_dp_with_strategy(digits as u32, RoundingStrategy::ToZero),
+        ));
+    }
+    // A `Decimal` has at most 29 significant digits.
+    if digits < -28 {
+        return Ok(Decimal::zero());
+    }
+    let factor = RustDecimal::from_i128_with_scale(10i128.pow(digits.unsigned_abs()), 0);
+    Ok(Decimal::Normalized((d / factor).trunc() * factor))
+}
+
+/// Returns the number of the bucket in which `operand` falls, in a histogram having `count`
+/// equal-width buckets spanning the range `low` to `high`. Returns `0` or `count + 1` for an input
+/// outside that range.
+#[inline(always)]
+pub fn width_bucket_f64(
+    operand: OrderedF64,
+    low: OrderedF64,
+    high: OrderedF64,
+    count: i32,
+) -> Result<i32> {
+    let (operand, low, high) = (operand.0, low.0, high.0);
+    if count <= 0 {
+        return Err(ExprError::InvalidArgumentForWidthBucket(
+            "count must be greater than zero",
+        ));
+    }
+    if operand.is_nan() || low.is_nan() || high.is_nan() {
+        return Err(ExprError::InvalidArgumentForWidthBucket(
+            "operand, lower bound, and upper bound cannot be NaN",
+        ));
+    }
+    if low.is_infinite() || high.is_infinite() {
+        return Err(ExprError::InvalidArgumentForWidthBucket(
+            "lower and upper bounds must be finite",
+        ));
+    }
+    let bucket = if low < high {
+        if operand < low {
+            0
+        } else if operand >= high {
+            count.checked_add(1).ok_or(ExprError::NumericOutOfRange)?
+        } else {
+            ((operand - low) / (high - low) * count as f64) as i32 + 1
+        }
+    } else if low > high {
+        if operand > low {
+            0
+        } else if operand <= high {
+            count.checked_add(1).ok_or(ExprError::NumericOutOfRange)?
+        } else {
+            ((low - operand) / (low - high) * count as f64) as i32 + 1
+        }
+    } else {
+        return Err(ExprError::InvalidArgumentForWidthBucket(
+            "lower bound cannot equal upper bound",
+        ));
+    };
+    Ok(bucket)
+}
+
+#[inline(always)]
+pub fn width_bucket_decimal(
+    operand: Decimal,
+    low: Decimal,
+    high: Decimal,
+    count: i32,
+) -> Result<i32> {
+    let to_f64 = |d: Decimal| -> Result<OrderedF64> {
+        Ok(d.to_f64().ok_or(ExprError::NumericOutOfRange)?.into())
+    };
+    width_bucket_f64(to_f64(operand)?, to_f64(low)?, to_f64(high)?, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::assert_matches::assert_matches;
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn f(v: f64) -> OrderedF64 {
+        OrderedF64::from(v)
+    }
+
+    fn d(v: &str) -> Decimal {
+        Decimal::from_str(v).unwrap()
+    }
+
+    #[test]
+    fn test_sqrt_cbrt() {
+        assert_eq!(sqrt_f64(f(16.0)).unwrap(), f(4.0));
+        assert_eq!(sqrt_f64(f(f64::INFINITY)).unwrap(), f(f64::INFINITY));
+        assert!(sqrt_f64(f(f64::NAN)).unwrap().0.is_nan());
+        assert_matches!(
+            sqrt_f64(f(-1.0)),
+            Err(ExprError::InvalidArgumentForPower(_))
+        );
+        assert_eq!(sqrt_decimal(d("2.25")).unwrap(), d("1.5"));
+        assert_eq!(
+            sqrt_decimal(Decimal::PositiveInf).unwrap(),
+            Decimal::PositiveInf
+        );
+        assert_eq!(cbrt_f64(f(-27.0)).unwrap(), f(-3.0));
+    }
+
+    #[test]
+    fn test_pow_exp() {
+        assert_eq!(pow_f64(f(2.0), f(10.0)).unwrap(), f(1024.0));
+        assert_eq!(pow_f64(f(-2.0), f(3.0)).unwrap(), f(-8.0));
+        assert_eq!(pow_f64(f(1.0), f(f64::NAN)).unwrap(), f(1.0));
+        assert!(pow_f64(f(0.0), f(-1.0)).is_err());
+        assert!(pow_f64(f(-2.0), f(0.5)).is_err());
+        assert_matches!(
+            pow_f64(f(10.0), f(400.0)),
+            Err(ExprError::NumericOutOfRange)
+        );
+        assert_eq!(pow_decimal(d("1.5"), d("2")).unwrap(), d("2.25"));
+
+        assert_eq!(exp_f64(f(0.0)).unwrap(), f(1.0));
+        assert_eq!(exp_f64(f(f64::NEG_INFINITY)).unwrap(), f(0.0));
+        assert!(exp_f64(f(1000.0)).is_err());
+        assert!(exp_f64(f(-1000.0)).is_err());
+        assert_eq!(exp_decimal(d("0")).unwrap(), d("1"));
+    }
+
+    #[test]
+    fn test_log() {
+        assert_eq!(ln_f64(f(1.0)).unwrap(), f(0.0));
+        assert_eq!(log10_f64(f(1000.0)).unwrap(), f(3.0));
+        assert_eq!(log10_decimal(d("100")).unwrap(), d("2"));
+        assert_eq!(log_decimal(d("2"), d("8")).unwrap(), d("3"));
+        assert_matches!(
+            ln_f64(f(0.0)),
+            Err(ExprError::InvalidArgumentForLogarithm(_))
+        );
+        assert!(log10_f64(f(-1.0)).is_err());
+        assert_matches!(log_decimal(d("1"), d("8")), Err(ExprError::DivisionByZero));
+        assert_eq!(ln_decimal(Decimal::NaN).unwrap(), Decimal::NaN);
+    }
+
+    #[test]
+    fn test_sign_trunc() {
+        assert_eq!(sign_f64(f(-0.5)).unwrap(), f(-1.0));
+        assert_eq!(sign_f64(f(0.0)).unwrap(), f(0.0));
+        assert_eq!(sign_decimal(d("12.3")).unwrap(), d("1"));
+        assert_eq!(sign_decimal(Decimal::NegativeInf).unwrap(), d("-1"));
+
+        assert_eq!(trunc_f64(f(-42.8)).unwrap(), f(-42.0));
+        assert_eq!(trunc_decimal(d("42.8")).unwrap(), d("42"));
+        assert_eq!(trunc_digits(d("42.4382"), 2).unwrap(), d("42.43"));
+        assert_eq!(trunc_digits(d("-42.4382"), 2).unwrap(), d("-42.43"));
+        assert_eq!(trunc_digits(d("1234.5"), -2).unwrap(), d("1200"));
+        assert_eq!(trunc_digits(Decimal::NaN, 2).unwrap(), Decimal::NaN);
+    }
+
+    #[test]
+    fn test_width_bucket() {
+        assert_eq!(width_bucket_f64(f(5.35), f(0.024), f(10.06), 5).unwrap(), 3);
+        assert_eq!(width_bucket_f64(f(-1.0), f(0.0), f(10.0), 5).unwrap(), 0);
+        assert_eq!(width_bucket_f64(f(10.0), f(0.0), f(10.0), 5).unwrap(), 6);
+        assert_eq!(width_bucket_f64(f(7.0), f(10.0), f(0.0), 5).unwrap(), 2);
+        assert!(width_bucket_f64(f(1.0), f(0.0), f(10.0), 0).is_err());
+        assert!(width_bucket_f64(f(1.0), f(1.0), f(1.0), 5).is_err());
+        assert!(width_bucket_f64(f(f64::NAN), f(0.0), f(10.0), 5).is_err());
+        assert_eq!(
+            width_bucket_decimal(d("5.35"), d("0.024"), d("10.06"), 5).unwrap(),
+            3
+        );
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod age;
 pub mod agg;
 pub mod arithmetic_op;
 pub mod array_access;
@@ -21,22 +22,35 @@ pub mod cast;
 pub mod cmp;
 pub mod concat_op;
 pub mod conjunction;
+pub mod date_trunc;
 pub mod extract;
+pub mod format;
+pub mod initcap;
 pub mod length;
 pub mod like;
 pub mod lower;
 pub mod ltrim;
+pub mod make_date;
+pub mod math;
 pub mod md5;
 pub mod overlay;
+pub mod pad;
 pub mod position;
+pub mod regexp;
 pub mod repeat;
 pub mod replace;
+pub mod reverse;
 pub mod round;
 pub mod rtrim;
 pub mod split_part;
+pub mod starts_with;
+pub mod string_to_array;
 pub mod substr;
+pub mod timestampz;
 pub mod to_char;
+pub mod to_timestamp;
 pub mod translate;
+pub mod trigonometric;
 pub mod trim;
 pub mod trim_characters;
 pub mod tumble;
@@ -0,0 +1,107 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piestream_common::array::{BytesGuard, BytesWriter};
+
+use crate::Result;
+
+/// Returns the byte offset of the `n`-th character of `s`, or the length of `s` if it has fewer
+/// characters.
+fn char_offset(s: &str, n: usize) -> usize {
+    s.char_indices().nth(n).map_or(s.len(), |(i, _)| i)
+}
+
+/// Extends `s` to `length` characters by prepending spaces. If `s` is already longer than
+/// `length`, it is truncated on the right.
+#[inline(always)]
+pub fn lpad(s: &str, length: i32, writer: BytesWriter) -> Result<BytesGuard> {
+    lpad_fill(s, length, " ", writer)
+}
+
+/// Extends `s` to `length` characters by prepending the characters of `fill`, repeated if
+/// necessary. If `s` is already longer than `length`, it is truncated on the right.
+#[inline(always)]
+pub fn lpad_fill(s: &str, length: i32, fill: &str, writer: BytesWriter) -> Result<BytesGuard> {
+    let length = length.max(0) as usize;
+    let s_len = s.chars().count();
+    if length <= s_len || fill.is_empty() {
+        return writer
+            .write_ref(&s[..char_offset(s, length)])
+            .map_err(Into::into);
+    }
+    let padding: String = fill.chars().cycle().take(length - s_len).collect();
+    let mut writer = writer.begin();
+    writer.write_ref(&padding)?;
+    writer.write_ref(s)?;
+    writer.finish().map_err(Into::into)
+}
+
+/// Extends `s` to `length` characters by appending spaces. If `s` is already longer than
+/// `length`, it is truncated.
+#[inline(always)]
+pub fn rpad(s: &str, length: i32, writer: BytesWriter) -> Result<BytesGuard> {
+    rpad_fill(s, length, " ", writer)
+}
+
+/// Extends `s` to `length` characters by appending the characters of `fill`, repeated if
+/// necessary. If `s` is already longer than `length`, it is truncated.
+#[inline(always)]
+pub fn rpad_fill(s: &str, length: i32, fill: &str, writer: BytesWriter) -> Result<BytesGuard> {
+    let length = length.max(0) as usize;
+    let s_len = s.chars().count();
+    if length <= s_len || fill.is_empty() {
+        return writer
+            .write_ref(&s[..char_offset(s, length)])
+            .map_err(Into::into);
+    }
+    let padding: String = fill.chars().cycle().take(length - s_len).collect();
+    let mut writer = writer.begin();
+    writer.write_ref(s)?;
+    writer.write_ref(&padding)?;
+    writer.finish().map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::{Array, ArrayBuilder, Utf8ArrayBuilder};
+
+    use super::*;
+
+    #[test]
+    fn test_pad() -> Result<()> {
+        let cases = [
+            ("hi", 5, "xy", "xyxhi", "hixyx"),
+            ("hi", 5, " ", "   hi", "hi   "),
+            ("hello", 3, "xy", "hel", "hel"),
+            ("hello", 0, "xy", "", ""),
+            ("hello", -1, "xy", "", ""),
+            ("hi", 5, "", "hi", "hi"),
+            ("你好", 4, "世界", "世界你好", "你好世界"),
+            ("你好世界", 3, "!", "你好世", "你好世"),
+        ];
+
+        for (s, length, fill, expected_l, expected_r) in cases {
+            let builder = Utf8ArrayBuilder::new(1);
+            let guard = lpad_fill(s, length, fill, builder.writer())?;
+            let array = guard.into_inner().finish();
+            assert_eq!(array.value_at(0).unwrap(), expected_l);
+
+            let builder = Utf8ArrayBuilder::new(1);
+            let guard = rpad_fill(s, length, fill, builder.writer())?;
+            let array = guard.into_inner().finish();
+            assert_eq!(array.value_at(0).unwrap(), expected_r);
+        }
+        Ok(())
+    }
+}
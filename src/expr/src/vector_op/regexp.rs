@@ -0,0 +1,242 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Kernels of the regular expression functions. Compiling the pattern is left to the caller so
+//! that constant patterns only need to be compiled once, see `expr::expr_regexp`.
+
+use piestream_common::array::ListValue;
+use piestream_common::types::Scalar;
+use regex::{Regex, RegexBuilder};
+
+use crate::{ExprError, Result};
+
+/// Options parsed from the `flags` argument of the regular expression functions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegexpFlags {
+    /// `i`: case-insensitive matching.
+    pub case_insensitive: bool,
+    /// `g`: replace all matches instead of the first one.
+    pub global: bool,
+}
+
+impl RegexpFlags {
+    pub fn parse(flags: &str) -> Result<Self> {
+        let mut parsed = Self::default();
+        for flag in flags.chars() {
+            match flag {
+                'i' => parsed.case_insensitive = true,
+                'c' => parsed.case_insensitive = false,
+                'g' => parsed.global = true,
+                _ => {
+                    return Err(ExprError::InvalidParam {
+                        name: "flags",
+                        reason: format!("invalid regular expression option: \"{}\"", flag),
+                    })
+                }
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Compiles `pattern` with these flags.
+    pub fn build(&self, pattern: &str) -> Result<Regex> {
+        Ok(RegexBuilder::new(pattern)
+            .case_insensitive(self.case_insensitive)
+            .build()?)
+    }
+}
+
+/// Replaces the first match of `regex` in `text`, or all matches if `global` is set.
+///
+/// The replacement uses `PostgreSQL`'s syntax: `\n` refers to the `n`-th capture group, `\&` to
+/// the whole match, and `\\` is a literal backslash.
+pub fn regexp_replace(text: &str, regex: &Regex, replacement: &str, global: bool) -> String {
+    let replacement = convert_replacement(replacement);
+    if global {
+        regex.replace_all(text, replacement.as_str()).into_owned()
+    } else {
+        regex.replace(text, replacement.as_str()).into_owned()
+    }
+}
+
+/// Converts a `PostgreSQL` replacement string to the syntax of the `regex` crate.
+fn convert_replacement(replacement: &str) -> String {
+    let mut converted = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.peek() {
+                Some(&d @ '1'..='9') => {
+                    chars.next();
+                    converted.push_str("${");
+                    converted.push(d);
+                    converted.push('}');
+                }
+                Some('&') => {
+                    chars.next();
+                    converted.push_str("${0}");
+                }
+                Some('\\') => {
+                    chars.next();
+                    converted.push('\\');
+                }
+                _ => converted.push('\\'),
+            },
+            '$' => converted.push_str("$$"),
+            c => converted.push(c),
+        }
+    }
+    converted
+}
+
+/// Splits `text` using `regex` as the delimiter.
+///
+/// Like `PostgreSQL`, zero-length matches at the beginning or the end of `text`, or right after a
+/// previous match, are ignored.
+pub fn regexp_split_to_array(text: &str, regex: &Regex) -> ListValue {
+    let mut parts = vec![];
+    let mut start = 0;
+    for mat in regex.find_iter(text) {
+        if mat.start() == mat.end()
+            && (mat.start() == 0 || mat.start() == text.len() || mat.start() == start)
+        {
+            continue;
+        }
+        parts.push(&text[start..mat.start()]);
+        start = mat.end();
+    }
+    parts.push(&text[start..]);
+    ListValue::new(
+        parts
+            .into_iter()
+            .map(|part| Some(part.to_string().to_scalar_value()))
+            .collect(),
+    )
+}
+
+/// Translates a SQL regular expression used by `SIMILAR TO` into an anchored POSIX-style regular
+/// expression. `\` is the escape character.
+pub fn similar_to_regex(pattern: &str) -> Result<String> {
+    let mut translated = String::with_capacity(pattern.len() + 8);
+    translated.push_str("^(?:");
+    let mut chars = pattern.chars();
+    let mut in_bracket = false;
+    while let Some(c) = chars.next() {
+        if in_bracket {
+            if c == ']' {
+                in_bracket = false;
+            }
+            translated.push(c);
+            continue;
+        }
+        match c {
+            '\\' => {
+                let escaped = chars.next().ok_or_else(|| ExprError::InvalidParam {
+                    name: "pattern",
+                    reason: "invalid escape string: pattern must not end with escape character"
+                        .to_string(),
+                })?;
+                translated.push_str(&regex::escape(escaped.encode_utf8(&mut [0; 4])));
+            }
+            '%' => translated.push_str(".*"),
+            '_' => translated.push('.'),
+            '[' => {
+                in_bracket = true;
+                translated.push('[');
+            }
+            '.' | '^' | '$' => {
+                translated.push('\\');
+                translated.push(c);
+            }
+            c => translated.push(c),
+        }
+    }
+    translated.push_str(")$");
+    Ok(translated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: &[&str]) -> ListValue {
+        ListValue::new(
+            values
+                .iter()
+                .map(|v| Some(v.to_string().to_scalar_value()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_regexp_replace() {
+        let cases = [
+            ("Thomas", ".[mN]a.", "M", "", "ThM"),
+            ("Thomas", ".[mN]a.", "M", "i", "ThM"),
+            ("foobarbaz", "b(..)", "X\\1Y", "g", "fooXarYXazY"),
+            ("foobarbaz", "b(..)", "X\\1Y", "", "fooXarYbaz"),
+            ("abc", "B", "[\\&]", "gi", "a[b]c"),
+            ("a.b", "\\.", "$", "", "a$b"),
+        ];
+        for (text, pattern, replacement, flags, expected) in cases {
+            let flags = RegexpFlags::parse(flags).unwrap();
+            let regex = flags.build(pattern).unwrap();
+            assert_eq!(
+                regexp_replace(text, &regex, replacement, flags.global),
+                expected
+            );
+        }
+        assert!(RegexpFlags::parse("x").is_err());
+    }
+
+    #[test]
+    fn test_regexp_split_to_array() {
+        let cases: &[(&str, &str, &[&str])] = &[
+            (
+                "the quick brown fox",
+                "\\s+",
+                &["the", "quick", "brown", "fox"],
+            ),
+            ("abc", "", &["a", "b", "c"]),
+            ("a,b,", ",", &["a", "b", ""]),
+            ("", ",", &[""]),
+            ("axxb", "x*", &["a", "b"]),
+        ];
+        for (text, pattern, expected) in cases {
+            let regex = Regex::new(pattern).unwrap();
+            assert_eq!(regexp_split_to_array(text, &regex), list(expected));
+        }
+    }
+
+    #[test]
+    fn test_similar_to() {
+        let cases = [
+            ("abc", "abc", true),
+            ("abc", "a", false),
+            ("abc", "%(b|d)%", true),
+            ("abc", "(b|c)%", false),
+            ("-abc-", "%[a-c]+%", true),
+            ("a.c", "a.c", true),
+            ("abc", "a.c", false),
+            ("abc", "a_c", true),
+            ("a%c", "a\\%c", true),
+            ("abc", "a\\%c", false),
+        ];
+        for (text, pattern, expected) in cases {
+            let regex = Regex::new(&similar_to_regex(pattern).unwrap()).unwrap();
+            assert_eq!(regex.is_match(text), expected, "{} {}", text, pattern);
+        }
+        assert!(similar_to_regex("abc\\").is_err());
+    }
+}
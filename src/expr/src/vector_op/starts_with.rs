@@ -0,0 +1,39 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Result;
+
+#[inline(always)]
+pub fn starts_with(s: &str, prefix: &str) -> Result<bool> {
+    Ok(s.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starts_with() {
+        let cases = [
+            ("alphabet", "alph", true),
+            ("alphabet", "", true),
+            ("alphabet", "beta", false),
+            ("", "a", false),
+        ];
+
+        for (s, prefix, expected) in cases {
+            assert_eq!(starts_with(s, prefix).unwrap(), expected);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piestream_common::array::ListValue;
+use piestream_common::types::{Datum, Scalar};
+
+/// Splits `s` into an array of strings at each occurrence of `delimiter`.
+///
+/// Like `PostgreSQL`, a `NULL` delimiter splits `s` into individual characters, and an empty
+/// delimiter returns `s` as a single element. Elements equal to `null_string` are replaced with
+/// `NULL`.
+pub fn string_to_array(s: &str, delimiter: Option<&str>, null_string: Option<&str>) -> ListValue {
+    let to_datum = |elem: &str| -> Datum {
+        if Some(elem) == null_string {
+            None
+        } else {
+            Some(elem.to_string().to_scalar_value())
+        }
+    };
+    let values = if s.is_empty() {
+        vec![]
+    } else {
+        match delimiter {
+            None => s
+                .char_indices()
+                .map(|(i, c)| to_datum(&s[i..i + c.len_utf8()]))
+                .collect(),
+            Some("") => vec![to_datum(s)],
+            Some(delimiter) => s.split(delimiter).map(to_datum).collect(),
+        }
+    };
+    ListValue::new(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: &[Option<&str>]) -> ListValue {
+        ListValue::new(
+            values
+                .iter()
+                .map(|v| v.map(|s| s.to_string().to_scalar_value()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_string_to_array() {
+        let cases: &[(&str, Option<&str>, Option<&str>, &[Option<&str>])] = &[
+            (
+                "xx~^~yy~^~zz",
+                Some("~^~"),
+                None,
+                &[Some("xx"), Some("yy"), Some("zz")],
+            ),
+            (
+                "xx~^~yy~^~zz",
+                Some("~^~"),
+                Some("yy"),
+                &[Some("xx"), None, Some("zz")],
+            ),
+            ("abc", None, None, &[Some("a"), Some("b"), Some("c")]),
+            ("abc", Some(""), None, &[Some("abc")]),
+            ("", Some(","), None, &[]),
+            ("a,,b", Some(","), Some(""), &[Some("a"), None, Some("b")]),
+        ];
+        for (s, delimiter, null_string, expected) in cases {
+            assert_eq!(string_to_array(s, *delimiter, *null_string), list(expected));
+        }
+    }
+}
@@ -45,6 +45,32 @@ pub fn substr_start_for(
     writer.write_ref(&s[begin..end]).map_err(Into::into)
 }
 
+/// Returns the first `n` characters of `s`. When `n` is negative, returns all but the last `|n|`
+/// characters.
+#[inline(always)]
+pub fn left(s: &str, n: i32, writer: BytesWriter) -> Result<BytesGuard> {
+    let n = if n >= 0 {
+        n as usize
+    } else {
+        s.chars().count().saturating_sub(n.unsigned_abs() as usize)
+    };
+    let end = s.char_indices().nth(n).map_or(s.len(), |(i, _)| i);
+    writer.write_ref(&s[..end]).map_err(Into::into)
+}
+
+/// Returns the last `n` characters of `s`. When `n` is negative, returns all but the first `|n|`
+/// characters.
+#[inline(always)]
+pub fn right(s: &str, n: i32, writer: BytesWriter) -> Result<BytesGuard> {
+    let skip = if n >= 0 {
+        s.chars().count().saturating_sub(n as usize)
+    } else {
+        n.unsigned_abs() as usize
+    };
+    let start = s.char_indices().nth(skip).map_or(s.len(), |(i, _)| i);
+    writer.write_ref(&s[start..]).map_err(Into::into)
+}
+
 #[cfg(test)]
 mod tests {
     use piestream_common::array::{Array, ArrayBuilder, Utf8ArrayBuilder};
@@ -87,4 +113,29 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_left_right() -> Result<()> {
+        let cases = [
+            ("abcde", 2, "ab", "de"),
+            ("abcde", -2, "abc", "cde"),
+            ("abcde", 0, "", ""),
+            ("abcde", 10, "abcde", "abcde"),
+            ("abcde", -10, "", ""),
+            ("你好世界", 3, "你好世", "好世界"),
+        ];
+
+        for (s, n, expected_left, expected_right) in cases {
+            let builder = Utf8ArrayBuilder::new(1);
+            let guard = left(s, n, builder.writer())?;
+            let array = guard.into_inner().finish();
+            assert_eq!(array.value_at(0).unwrap(), expected_left);
+
+            let builder = Utf8ArrayBuilder::new(1);
+            let guard = right(s, n, builder.writer())?;
+            let array = guard.into_inner().finish();
+            assert_eq!(array.value_at(0).unwrap(), expected_right);
+        }
+        Ok(())
+    }
 }
@@ -49,6 +49,10 @@ fn test_arithmetic() {
         general_mod::<Decimal, i32, Decimal>(Decimal::from_str("2.0").unwrap(), 2).unwrap(),
         Decimal::from_str("0").unwrap()
     );
+    assert_matches!(
+        general_mod::<Decimal, i32, Decimal>(Decimal::from_str("2.0").unwrap(), 0),
+        Err(ExprError::DivisionByZero)
+    );
     assert_eq!(
         general_neg::<Decimal>(Decimal::from_str("1.0").unwrap()).unwrap(),
         Decimal::from_str("-1.0").unwrap()
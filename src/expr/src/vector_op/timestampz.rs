@@ -0,0 +1,175 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{FixedOffset, NaiveDateTime, TimeZone};
+use num_traits::ToPrimitive;
+use piestream_common::types::{CheckedAdd, IntervalUnit, NaiveDateTimeWrapper, OrderedF64};
+
+use crate::vector_op::arithmetic_op::timestamp_timestamp_sub;
+use crate::vector_op::cast::{timestampz_to_utc_naive, utc_naive_to_timestampz};
+use crate::{ExprError, Result};
+
+// Calendar arithmetic on `timestamptz` (adding months or days) is done in UTC, which is the only
+// session time zone supported for now.
+
+#[inline(always)]
+pub fn timestampz_interval_add<T1, T2, T3>(l: i64, r: IntervalUnit) -> Result<i64> {
+    let time = NaiveDateTimeWrapper(timestampz_to_utc_naive(l)?);
+    let time = time.checked_add(r).ok_or(ExprError::NumericOutOfRange)?;
+    utc_naive_to_timestampz(time.0)
+}
+
+#[inline(always)]
+pub fn interval_timestampz_add<T1, T2, T3>(l: IntervalUnit, r: i64) -> Result<i64> {
+    timestampz_interval_add::<T2, T1, T3>(r, l)
+}
+
+#[inline(always)]
+pub fn timestampz_interval_sub<T1, T2, T3>(l: i64, r: IntervalUnit) -> Result<i64> {
+    timestampz_interval_add::<T1, T2, T3>(l, r.negative())
+}
+
+#[inline(always)]
+pub fn timestampz_timestampz_sub<T1, T2, T3>(l: i64, r: i64) -> Result<IntervalUnit> {
+    timestamp_timestamp_sub::<T1, T2, T3>(
+        NaiveDateTimeWrapper(timestampz_to_utc_naive(l)?),
+        NaiveDateTimeWrapper(timestampz_to_utc_naive(r)?),
+    )
+}
+
+/// Parses a time zone for `AT TIME ZONE`.
+///
+/// Only `UTC`/`GMT`/`Z` and ISO 8601 offsets like `+08`, `+08:00` or `-0530` are supported, as
+/// there is no time zone database available. Note that unlike POSIX-style zone names, a positive
+/// offset here means east of Greenwich, which matches the output of `timestamptz`.
+pub fn parse_time_zone(time_zone: &str) -> Result<FixedOffset> {
+    let invalid = || ExprError::InvalidParam {
+        name: "time_zone",
+        reason: format!("time zone \"{}\" not recognized", time_zone),
+    };
+    let tz = time_zone.trim();
+    if ["UTC", "GMT", "Z", "Etc/UTC", "Etc/GMT"]
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(tz))
+    {
+        return Ok(FixedOffset::east(0));
+    }
+    let sign = match tz.as_bytes().first() {
+        Some(b'+') => 1,
+        Some(b'-') => -1,
+        _ => return Err(invalid()),
+    };
+    let digits = tz[1..].replace(':', "");
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.parse::<i32>().unwrap(), 0),
+        4 => (
+            digits[..2].parse::<i32>().unwrap(),
+            digits[2..].parse::<i32>().unwrap(),
+        ),
+        _ => return Err(invalid()),
+    };
+    if hours > 15 || minutes >= 60 {
+        return Err(invalid());
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+/// `timestamp AT TIME ZONE zone`: interprets the wall clock time in `zone`.
+#[inline(always)]
+pub fn timestamp_at_time_zone(input: NaiveDateTimeWrapper, time_zone: &str) -> Result<i64> {
+    let offset = parse_time_zone(time_zone)?;
+    let time = offset
+        .from_local_datetime(&input.0)
+        .single()
+        .ok_or(ExprError::NumericOutOfRange)?;
+    utc_naive_to_timestampz(time.naive_utc())
+}
+
+/// `timestamptz AT TIME ZONE zone`: the wall clock time in `zone`.
+#[inline(always)]
+pub fn timestampz_at_time_zone(input: i64, time_zone: &str) -> Result<NaiveDateTimeWrapper> {
+    let offset = parse_time_zone(time_zone)?;
+    let time = offset.from_utc_datetime(&timestampz_to_utc_naive(input)?);
+    Ok(NaiveDateTimeWrapper(time.naive_local()))
+}
+
+/// `to_timestamp(double precision)`: converts Unix epoch seconds to `timestamptz`.
+#[inline(always)]
+pub fn f64_sec_to_timestampz(elem: OrderedF64) -> Result<i64> {
+    let usecs = (elem.0 * 1e6)
+        .round()
+        .to_i64()
+        .ok_or(ExprError::NumericOutOfRange)?;
+    // Make sure the result can be converted back to a date time.
+    NaiveDateTime::from_timestamp_opt(usecs.div_euclid(1_000_000), 0)
+        .ok_or(ExprError::NumericOutOfRange)?;
+    Ok(usecs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector_op::cast::str_to_timestamp;
+
+    #[test]
+    fn test_timestampz_interval() {
+        // 2022-01-31 00:00:00 UTC
+        let time = 1_643_587_200_000_000;
+        // 2022-02-28 00:00:00 UTC
+        assert_eq!(
+            timestampz_interval_add::<i64, IntervalUnit, i64>(time, IntervalUnit::from_month(1))
+                .unwrap(),
+            1_646_006_400_000_000
+        );
+        assert_eq!(
+            timestampz_interval_sub::<i64, IntervalUnit, i64>(time, IntervalUnit::from_millis(1))
+                .unwrap(),
+            time - 1_000
+        );
+        assert_eq!(
+            timestampz_timestampz_sub::<i64, i64, IntervalUnit>(time + 90_000_000_000, time)
+                .unwrap(),
+            IntervalUnit::new(0, 1, 3_600_000)
+        );
+    }
+
+    #[test]
+    fn test_time_zone() {
+        assert_eq!(parse_time_zone("utc").unwrap(), FixedOffset::east(0));
+        assert_eq!(parse_time_zone("+08").unwrap(), FixedOffset::east(8 * 3600));
+        assert_eq!(
+            parse_time_zone("-05:30").unwrap(),
+            FixedOffset::west(5 * 3600 + 30 * 60)
+        );
+        assert!(parse_time_zone("Asia/Shanghai").is_err());
+        assert!(parse_time_zone("+25").is_err());
+
+        let local = str_to_timestamp("2022-08-03 18:34:02").unwrap();
+        let usecs = timestamp_at_time_zone(local, "+08:00").unwrap();
+        assert_eq!(usecs, 1_659_522_842_000_000);
+        assert_eq!(timestampz_at_time_zone(usecs, "+08:00").unwrap(), local);
+    }
+
+    #[test]
+    fn test_to_timestamp() {
+        assert_eq!(
+            f64_sec_to_timestampz(1_659_522_842.5.into()).unwrap(),
+            1_659_522_842_500_000
+        );
+        assert!(f64_sec_to_timestampz(f64::INFINITY.into()).is_err());
+    }
+}
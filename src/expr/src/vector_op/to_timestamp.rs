@@ -0,0 +1,77 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use piestream_common::types::NaiveDateWrapper;
+
+use crate::vector_op::cast::utc_naive_to_timestampz;
+use crate::vector_op::to_char::compile_pattern_to_chrono;
+use crate::{ExprError, Result};
+
+const PARSE_ERROR_TO_TIMESTAMP: &str = "Can't parse string with the given template";
+
+/// Parses `s` with a template already compiled by [`compile_pattern_to_chrono`]. Templates without
+/// time fields produce midnight.
+fn parse_with_chrono_tmpl(s: &str, chrono_tmpl: &str) -> Result<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, chrono_tmpl)
+        .or_else(|_| NaiveDate::parse_from_str(s, chrono_tmpl).map(|d| d.and_hms(0, 0, 0)))
+        .map_err(|_| ExprError::Parse(PARSE_ERROR_TO_TIMESTAMP))
+}
+
+/// `to_timestamp(text, text)`. The result is interpreted in UTC.
+#[inline(always)]
+pub fn to_timestamp_with_tmpl(s: &str, tmpl: &str) -> Result<i64> {
+    let chrono_tmpl = compile_pattern_to_chrono(tmpl);
+    utc_naive_to_timestampz(parse_with_chrono_tmpl(s, &chrono_tmpl)?)
+}
+
+/// `to_date(text, text)`.
+#[inline(always)]
+pub fn to_date_with_tmpl(s: &str, tmpl: &str) -> Result<NaiveDateWrapper> {
+    let chrono_tmpl = compile_pattern_to_chrono(tmpl);
+    NaiveDate::parse_from_str(s, &chrono_tmpl)
+        .map(NaiveDateWrapper)
+        .map_err(|_| ExprError::Parse(PARSE_ERROR_TO_TIMESTAMP))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector_op::cast::str_to_date;
+
+    #[test]
+    fn test_to_timestamp() {
+        assert_eq!(
+            to_timestamp_with_tmpl("2022-08-03 10:34:02", "YYYY-MM-DD HH24:MI:SS").unwrap(),
+            1_659_522_842_000_000
+        );
+        assert_eq!(
+            to_timestamp_with_tmpl("03/08/2022", "DD/MM/YYYY").unwrap(),
+            1_659_484_800_000_000
+        );
+        assert!(to_timestamp_with_tmpl("2022-08-03", "HH24:MI").is_err());
+    }
+
+    #[test]
+    fn test_to_date() {
+        assert_eq!(
+            to_date_with_tmpl("20220803", "YYYYMMDD").unwrap(),
+            str_to_date("2022-08-03").unwrap()
+        );
+        assert_eq!(
+            to_date_with_tmpl("2022-08-03 10:34", "YYYY-MM-DD HH24:MI").unwrap(),
+            str_to_date("2022-08-03").unwrap()
+        );
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piestream_common::types::OrderedF64;
+
+use crate::{ExprError, Result};
+
+/// Like `PostgreSQL`, trigonometric functions reject infinite inputs and arguments outside of
+/// their domain with "input is out of range", and pass `NaN` through.
+#[inline(always)]
+fn check_finite(input: OrderedF64) -> Result<f64> {
+    if input.0.is_infinite() {
+        Err(ExprError::NumericOutOfRange)
+    } else {
+        Ok(input.0)
+    }
+}
+
+#[inline(always)]
+pub fn sin_f64(input: OrderedF64) -> Result<OrderedF64> {
+    Ok(check_finite(input)?.sin().into())
+}
+
+#[inline(always)]
+pub fn cos_f64(input: OrderedF64) -> Result<OrderedF64> {
+    Ok(check_finite(input)?.cos().into())
+}
+
+#[inline(always)]
+pub fn tan_f64(input: OrderedF64) -> Result<OrderedF64> {
+    Ok(check_finite(input)?.tan().into())
+}
+
+#[inline(always)]
+pub fn cot_f64(input: OrderedF64) -> Result<OrderedF64> {
+    Ok((1.0 / check_finite(input)?.tan()).into())
+}
+
+#[inline(always)]
+pub fn asin_f64(input: OrderedF64) -> Result<OrderedF64> {
+    if input.0.abs() > 1.0 {
+        return Err(ExprError::NumericOutOfRange);
+    }
+    Ok(input.0.asin().into())
+}
+
+#[inline(always)]
+pub fn acos_f64(input: OrderedF64) -> Result<OrderedF64> {
+    if input.0.abs() > 1.0 {
+        return Err(ExprError::NumericOutOfRange);
+    }
+    Ok(input.0.acos().into())
+}
+
+#[inline(always)]
+pub fn atan_f64(input: OrderedF64) -> Result<OrderedF64> {
+    Ok(input.0.atan().into())
+}
+
+#[inline(always)]
+pub fn atan2_f64(y: OrderedF64, x: OrderedF64) -> Result<OrderedF64> {
+    Ok(y.0.atan2(x.0).into())
+}
+
+#[inline(always)]
+pub fn degrees_f64(input: OrderedF64) -> Result<OrderedF64> {
+    Ok(input.0.to_degrees().into())
+}
+
+#[inline(always)]
+pub fn radians_f64(input: OrderedF64) -> Result<OrderedF64> {
+    Ok(input.0.to_radians().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use super::*;
+
+    fn f(v: f64) -> OrderedF64 {
+        OrderedF64::from(v)
+    }
+
+    #[test]
+    fn test_trigonometric() {
+        assert_eq!(sin_f64(f(0.0)).unwrap(), f(0.0));
+        assert_eq!(cos_f64(f(0.0)).unwrap(), f(1.0));
+        assert!((tan_f64(f(FRAC_PI_4)).unwrap().0 - 1.0).abs() < 1e-12);
+        assert!((cot_f64(f(FRAC_PI_4)).unwrap().0 - 1.0).abs() < 1e-12);
+        assert!(sin_f64(f(f64::INFINITY)).is_err());
+        assert!(sin_f64(f(f64::NAN)).unwrap().0.is_nan());
+
+        assert_eq!(asin_f64(f(1.0)).unwrap(), f(FRAC_PI_2));
+        assert_eq!(acos_f64(f(1.0)).unwrap(), f(0.0));
+        assert!(asin_f64(f(1.5)).is_err());
+        assert!(acos_f64(f(-1.5)).is_err());
+        assert_eq!(atan_f64(f(f64::INFINITY)).unwrap(), f(FRAC_PI_2));
+        assert_eq!(atan2_f64(f(1.0), f(0.0)).unwrap(), f(FRAC_PI_2));
+
+        assert!((degrees_f64(f(PI)).unwrap().0 - 180.0).abs() < 1e-12);
+        assert!((radians_f64(f(180.0)).unwrap().0 - PI).abs() < 1e-12);
+    }
+}
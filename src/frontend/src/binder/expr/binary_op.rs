@@ -43,7 +43,17 @@ impl Binder {
             BinaryOperator::And => ExprType::And,
             BinaryOperator::Or => ExprType::Or,
             BinaryOperator::Like => ExprType::Like,
-            BinaryOperator::NotLike => return self.bind_not_like(bound_left, bound_right),
+            BinaryOperator::NotLike => {
+                return self.bind_not(ExprType::Like, bound_left, bound_right)
+            }
+            BinaryOperator::ILike => ExprType::ILike,
+            BinaryOperator::NotILike => {
+                return self.bind_not(ExprType::ILike, bound_left, bound_right)
+            }
+            BinaryOperator::SimilarTo => ExprType::SimilarTo,
+            BinaryOperator::NotSimilarTo => {
+                return self.bind_not(ExprType::SimilarTo, bound_left, bound_right)
+            }
             BinaryOperator::BitwiseOr => ExprType::BitwiseOr,
             BinaryOperator::BitwiseAnd => ExprType::BitwiseAnd,
             BinaryOperator::PGBitwiseXor => ExprType::BitwiseXor,
@@ -64,11 +74,16 @@ impl Binder {
         Ok(FunctionCall::new(func_type, vec![bound_left, bound_right])?.into())
     }
 
-    /// Apply a NOT on top of LIKE.
-    fn bind_not_like(&mut self, left: ExprImpl, right: ExprImpl) -> Result<ExprImpl> {
+    /// Apply a NOT on top of a pattern matching operator, e.g. LIKE.
+    fn bind_not(
+        &mut self,
+        func_type: ExprType,
+        left: ExprImpl,
+        right: ExprImpl,
+    ) -> Result<ExprImpl> {
         Ok(FunctionCall::new(
             ExprType::Not,
-            vec![FunctionCall::new(ExprType::Like, vec![left, right])?.into()],
+            vec![FunctionCall::new(func_type, vec![left, right])?.into()],
         )?
         .into())
     }
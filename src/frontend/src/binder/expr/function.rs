@@ -25,8 +25,9 @@ use piestream_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr, WindowSpe
 use crate::binder::bind_context::Clause;
 use crate::binder::{Binder, BoundQuery, BoundSetExpr};
 use crate::expr::{
-    AggCall, Expr, ExprImpl, ExprType, FunctionCall, Literal, OrderBy, Subquery, SubqueryKind,
-    TableFunction, TableFunctionType, WindowFunction, WindowFunctionType,
+    AggCall, Expr, ExprImpl, ExprType, FunctionCall, Literal, OrderBy,
+    OrderByExpr as BoundOrderByExpr, Subquery, SubqueryKind, TableFunction, TableFunctionType,
+    WindowFunction, WindowFunctionType,
 };
 use crate::utils::Condition;
 
@@ -54,7 +55,14 @@ impl Binder {
         };
 
         // agg calls
-        if let Ok(kind) = function_name.parse() {
+        let agg_kind = match function_name.as_str() {
+            // aliases of aggregate functions
+            "stddev" => Ok(AggKind::StddevSamp),
+            "variance" => Ok(AggKind::VarSamp),
+            "every" => Ok(AggKind::BoolAnd),
+            name => name.parse(),
+        };
+        if let Ok(kind) = agg_kind {
             if f.over.is_some() {
                 return Err(ErrorCode::NotImplemented(
                     format!("aggregate function as over window function: {}", kind),
@@ -65,9 +73,9 @@ impl Binder {
             return self.bind_agg(f, kind);
         }
 
-        if f.distinct || !f.order_by.is_empty() || f.filter.is_some() {
+        if f.distinct || !f.order_by.is_empty() || f.filter.is_some() || f.within_group.is_some() {
             return Err(ErrorCode::InvalidInputSyntax(format!(
-                    "DISTINCT, ORDER BY or FILTER is only allowed in aggregation functions, but `{}` is not an aggregation function", function_name
+                    "DISTINCT, ORDER BY, FILTER or WITHIN GROUP is only allowed in aggregation functions, but `{}` is not an aggregation function", function_name
                 )
                 )
                 .into());
@@ -118,9 +126,45 @@ impl Binder {
                     ExprType::Round
                 }
             }
-            "ceil" => ExprType::Ceil,
+            "ceil" | "ceiling" => ExprType::Ceil,
             "floor" => ExprType::Floor,
             "abs" => ExprType::Abs,
+            "trunc" => {
+                if inputs.len() >= 2 {
+                    ExprType::TruncDigit
+                } else {
+                    ExprType::Trunc
+                }
+            }
+            "mod" => ExprType::Modulus,
+            "sqrt" => ExprType::Sqrt,
+            "cbrt" => ExprType::Cbrt,
+            "pow" | "power" => ExprType::Pow,
+            "exp" => ExprType::Exp,
+            "ln" => ExprType::Ln,
+            "log10" => ExprType::Log10,
+            "log" => {
+                if inputs.len() >= 2 {
+                    ExprType::Log
+                } else {
+                    ExprType::Log10
+                }
+            }
+            "sign" => ExprType::Sign,
+            "width_bucket" => ExprType::WidthBucket,
+            "greatest" => ExprType::Greatest,
+            "least" => ExprType::Least,
+            "random" => ExprType::Random,
+            "sin" => ExprType::Sin,
+            "cos" => ExprType::Cos,
+            "tan" => ExprType::Tan,
+            "cot" => ExprType::Cot,
+            "asin" => ExprType::Asin,
+            "acos" => ExprType::Acos,
+            "atan" => ExprType::Atan,
+            "atan2" => ExprType::Atan2,
+            "degrees" => ExprType::Degrees,
+            "radians" => ExprType::Radians,
             // string
             "substr" => ExprType::Substr,
             "length" => ExprType::Length,
@@ -147,6 +191,34 @@ impl Binder {
             "octet_length" => ExprType::OctetLength,
             "bit_length" => ExprType::BitLength,
             "regexp_match" => ExprType::RegexpMatch,
+            "regexp_replace" => ExprType::RegexpReplace,
+            "regexp_split_to_array" => ExprType::RegexpSplitToArray,
+            "lpad" => ExprType::Lpad,
+            "rpad" => ExprType::Rpad,
+            "left" => ExprType::Left,
+            "right" => ExprType::Right,
+            "reverse" => ExprType::Reverse,
+            "initcap" => ExprType::Initcap,
+            "strpos" => ExprType::Position,
+            "starts_with" => ExprType::StartsWith,
+            "format" => ExprType::Format,
+            "string_to_array" => ExprType::StringToArray,
+            // date and time
+            "date_trunc" => ExprType::DateTrunc,
+            "date_part" => return Self::rewrite_date_part_to_extract(inputs),
+            "to_timestamp" => ExprType::ToTimestamp,
+            "to_date" => ExprType::ToDate,
+            "age" => ExprType::Age,
+            "make_date" => ExprType::MakeDate,
+            "make_time" => ExprType::MakeTime,
+            "make_timestamp" => {
+                inputs = Self::rewrite_make_timestamp_to_add(inputs)?;
+                ExprType::Add
+            }
+            "timezone" => {
+                inputs = Self::rewrite_timezone_inputs(inputs)?;
+                ExprType::AtTimeZone
+            }
             // array
             "array_cat" => ExprType::ArrayCat,
             "array_append" => ExprType::ArrayAppend,
@@ -215,6 +287,35 @@ impl Binder {
             .map(|arg| self.bind_function_arg(arg))
             .flatten_ok()
             .try_collect()?;
+        let (inputs, within_group) = match (kind, f.within_group.take()) {
+            (AggKind::PercentileCont | AggKind::PercentileDisc, Some(within_group)) => {
+                if f.distinct || !f.order_by.is_empty() {
+                    return Err(ErrorCode::InvalidInputSyntax(format!(
+                        "DISTINCT or ORDER BY is not allowed in ordered-set aggregate {}",
+                        kind
+                    ))
+                    .into());
+                }
+                let (inputs, within_group) =
+                    self.bind_ordered_set_agg_inputs(kind, inputs, *within_group)?;
+                (inputs, Some(within_group))
+            }
+            (AggKind::PercentileCont | AggKind::PercentileDisc, None) => {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "WITHIN GROUP is required for ordered-set aggregate {}",
+                    kind
+                ))
+                .into());
+            }
+            (_, Some(_)) => {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "WITHIN GROUP is only allowed in ordered-set aggregates, but `{}` is not",
+                    kind
+                ))
+                .into());
+            }
+            (_, None) => (inputs, None),
+        };
         if f.distinct {
             match &kind {
                 AggKind::Count if inputs.is_empty() => {
@@ -291,17 +392,49 @@ impl Binder {
             )
             .into());
         }
-        let order_by = OrderBy::new(
-            f.order_by
-                .into_iter()
-                .map(|e| self.bind_order_by_expr(e))
-                .try_collect()?,
-        );
+        let order_by = match within_group {
+            Some(within_group) => OrderBy::new(vec![within_group]),
+            None => OrderBy::new(
+                f.order_by
+                    .into_iter()
+                    .map(|e| self.bind_order_by_expr(e))
+                    .try_collect()?,
+            ),
+        };
         Ok(ExprImpl::AggCall(Box::new(AggCall::new(
             kind, inputs, f.distinct, order_by, filter,
         )?)))
     }
 
+    /// Binds the inputs of an ordered-set aggregate like
+    /// `percentile_cont(fraction) WITHIN GROUP (ORDER BY x)`. The returned inputs are `[x,
+    /// fraction]`, where the aggregated column `x` comes first as in other aggregates, and the
+    /// ordering is the bound `WITHIN GROUP` clause.
+    fn bind_ordered_set_agg_inputs(
+        &mut self,
+        kind: AggKind,
+        direct_args: Vec<ExprImpl>,
+        within_group: piestream_sqlparser::ast::OrderByExpr,
+    ) -> Result<(Vec<ExprImpl>, BoundOrderByExpr)> {
+        let [fraction]: [ExprImpl; 1] = direct_args.try_into().map_err(|_| {
+            ErrorCode::InvalidInputSyntax(format!("{} takes exactly one direct argument", kind))
+        })?;
+        if !fraction.is_const() {
+            return Err(ErrorCode::NotImplemented(
+                format!("non-constant fraction in {}", kind),
+                None.into(),
+            )
+            .into());
+        }
+        let fraction = fraction.cast_implicit(DataType::Float64)?;
+
+        let mut within_group = self.bind_order_by_expr(within_group)?;
+        if kind == AggKind::PercentileCont {
+            within_group.expr = within_group.expr.cast_implicit(DataType::Float64)?;
+        }
+        Ok((vec![within_group.expr.clone(), fraction], within_group))
+    }
+
     pub(super) fn bind_window_function(
         &mut self,
         WindowSpec {
@@ -364,6 +497,44 @@ impl Binder {
         }
     }
 
+    /// Rewrite `date_part(field, source)` as `extract(upper(field) from source)::float8`.
+    fn rewrite_date_part_to_extract(inputs: Vec<ExprImpl>) -> Result<ExprImpl> {
+        let [field, source]: [ExprImpl; 2] = inputs.try_into().map_err(|_| {
+            ErrorCode::BindError("Function `date_part` must contain 2 arguments".to_string())
+        })?;
+        let field = FunctionCall::new(ExprType::Upper, vec![field])?.into();
+        let extract: ExprImpl = FunctionCall::new(ExprType::Extract, vec![field, source])?.into();
+        extract.cast_explicit(DataType::Float64)
+    }
+
+    /// Rewrite `make_timestamp(year, month, day, hour, min, sec)` as
+    /// `make_date(year, month, day) + make_time(hour, min, sec)`.
+    fn rewrite_make_timestamp_to_add(mut inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
+        if inputs.len() != 6 {
+            return Err(ErrorCode::BindError(
+                "Function `make_timestamp` must contain 6 arguments".to_string(),
+            )
+            .into());
+        }
+        let time_inputs = inputs.split_off(3);
+        Ok(vec![
+            FunctionCall::new(ExprType::MakeDate, inputs)?.into(),
+            FunctionCall::new(ExprType::MakeTime, time_inputs)?.into(),
+        ])
+    }
+
+    /// `timezone(zone, timestamp)` is the same as `timestamp AT TIME ZONE zone`.
+    fn rewrite_timezone_inputs(mut inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
+        if inputs.len() != 2 {
+            return Err(ErrorCode::BindError(
+                "Function `timezone` must contain 2 arguments".to_string(),
+            )
+            .into());
+        }
+        inputs.swap(0, 1);
+        Ok(inputs)
+    }
+
     fn rewrite_two_bool_inputs(mut inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
         if inputs.len() != 2 {
             return Err(
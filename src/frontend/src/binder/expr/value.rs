@@ -68,25 +68,31 @@ impl Binder {
         s: String,
         leading_field: Option<AstDateTimeField>,
     ) -> Result<Literal> {
-        let interval =
-            IntervalUnit::parse_with_fields(&s, leading_field.map(Self::bind_date_time_field))?;
+        let leading_field = leading_field.map(Self::bind_date_time_field).transpose()?;
+        let interval = IntervalUnit::parse_with_fields(&s, leading_field)?;
         let datum = Some(ScalarImpl::Interval(interval));
         let literal = Literal::new(datum, DataType::Interval);
 
         Ok(literal)
     }
 
-    fn bind_date_time_field(field: AstDateTimeField) -> DateTimeField {
+    fn bind_date_time_field(field: AstDateTimeField) -> Result<DateTimeField> {
         // This is a binder function rather than `impl From<AstDateTimeField> for DateTimeField`,
         // so that the `sqlparser` crate and the `common` crate are kept independent.
-        match field {
+        let field = match field {
             AstDateTimeField::Year => DateTimeField::Year,
             AstDateTimeField::Month => DateTimeField::Month,
             AstDateTimeField::Day => DateTimeField::Day,
             AstDateTimeField::Hour => DateTimeField::Hour,
             AstDateTimeField::Minute => DateTimeField::Minute,
             AstDateTimeField::Second => DateTimeField::Second,
-        }
+            AstDateTimeField::Literal(s) => {
+                return Err(
+                    ErrorCode::BindError(format!("unsupported interval field: {}", s)).into(),
+                )
+            }
+        };
+        Ok(field)
     }
 
     /// `ARRAY[...]` is represented as an function call at the binder stage.
@@ -74,6 +74,8 @@ pub struct Binder {
     next_values_id: usize,
     /// Map the cte's name to its Relation::Subquery.
     cte_to_relation: HashMap<String, (BoundQuery, TableAlias)>,
+    /// The epoch specified by `AS OF`, at which all the tables in a batch query are read.
+    as_of_epoch: Option<u64>,
 }
 
 impl Binder {
@@ -88,6 +90,7 @@ impl Binder {
             next_subquery_id: 0,
             next_values_id: 0,
             cte_to_relation: HashMap::new(),
+            as_of_epoch: None,
         }
     }
 
@@ -96,6 +99,11 @@ impl Binder {
         self.bind_statement(stmt)
     }
 
+    /// Returns the epoch specified by `AS OF` in the bound statement, if any.
+    pub fn as_of_epoch(&self) -> Option<u64> {
+        self.as_of_epoch
+    }
+
     fn push_context(&mut self) {
         let new_context = std::mem::take(&mut self.context);
         let new_lateral_contexts = std::mem::take(&mut self.lateral_contexts);
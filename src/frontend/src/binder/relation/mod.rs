@@ -14,11 +14,14 @@
 
 use std::collections::hash_map::Entry;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime};
 
 use itertools::Itertools;
 use piestream_common::catalog::{Field, TableId, DEFAULT_SCHEMA_NAME, RW_TABLE_FUNCTION_NAME};
 use piestream_common::error::{internal_error, ErrorCode, Result, RwError};
-use piestream_sqlparser::ast::{FunctionArg, Ident, ObjectName, TableAlias, TableFactor};
+use piestream_common::util::epoch::Epoch;
+use piestream_expr::vector_op::cast::str_to_timestampz;
+use piestream_sqlparser::ast::{AsOf, FunctionArg, Ident, ObjectName, TableAlias, TableFactor};
 
 use super::bind_context::ColumnBinding;
 use crate::binder::{Binder, BoundSetExpr};
@@ -311,9 +314,73 @@ impl Binder {
         Ok((schema, table_id))
     }
 
+    /// Resolves the epoch of `AS OF` on `relation`. A batch query is read at a single epoch, so
+    /// all `AS OF` in the query must specify the same epoch.
+    fn bind_as_of(&mut self, relation: &Relation, as_of: AsOf) -> Result<()> {
+        let Relation::BaseTable(table) = relation else {
+            return Err(ErrorCode::BindError(
+                "AS OF is only supported on tables and materialized views".to_string(),
+            )
+            .into());
+        };
+        let retention_seconds = table
+            .table_catalog
+            .properties
+            .time_travel_retention_seconds()
+            .ok_or_else(|| {
+                ErrorCode::BindError(format!(
+                    "time travel is not enabled on \"{}\", set `time_travel_retention_seconds` in WITH options to enable it",
+                    table.name
+                ))
+            })?;
+        let epoch = match as_of {
+            AsOf::Epoch(epoch) => epoch,
+            AsOf::Timestamp(ts) => {
+                let micros = str_to_timestampz(&ts).map_err(|e| {
+                    ErrorCode::BindError(format!("invalid timestamp of AS OF: {}", e))
+                })?;
+                let time = SystemTime::UNIX_EPOCH + Duration::from_micros(micros.max(0) as u64);
+                Epoch::from_system_time(time)
+                    .ok_or_else(|| {
+                        ErrorCode::BindError(format!("timestamp of AS OF is too early: {}", ts))
+                    })?
+                    .0
+            }
+        };
+        let now = Epoch::now();
+        if epoch > now.0 {
+            return Err(
+                ErrorCode::BindError(format!("epoch {} of AS OF is in the future", epoch)).into(),
+            );
+        }
+        if epoch < now.subtract_ms(retention_seconds.get() as u64 * 1000).0 {
+            return Err(ErrorCode::BindError(format!(
+                "epoch {} of AS OF is out of the retention window of \"{}\" ({} seconds)",
+                epoch, table.name, retention_seconds
+            ))
+            .into());
+        }
+        match self.as_of_epoch {
+            Some(bound_epoch) if bound_epoch != epoch => Err(ErrorCode::BindError(
+                "all the tables in a query must be read AS OF the same epoch".to_string(),
+            )
+            .into()),
+            _ => {
+                self.as_of_epoch = Some(epoch);
+                Ok(())
+            }
+        }
+    }
+
     pub(super) fn bind_table_factor(&mut self, table_factor: TableFactor) -> Result<Relation> {
         match table_factor {
-            TableFactor::Table { name, alias } => self.bind_relation_by_name(name, alias),
+            TableFactor::Table { name, alias, as_of } => {
+                let relation = self.bind_relation_by_name(name, alias)?;
+                if let Some(as_of) = as_of {
+                    self.bind_as_of(&relation, as_of)?;
+                }
+                Ok(relation)
+            }
             TableFactor::TableFunction { name, alias, args } => {
                 let func_name = &name.0[0].value;
                 if func_name.eq_ignore_ascii_case(RW_TABLE_FUNCTION_NAME) {
@@ -60,10 +60,13 @@ pub trait CatalogWriter: Send + Sync {
         owner: UserId,
     ) -> Result<()>;
 
+    /// Create a materialized view. If `background` is set, return as soon as the creation is
+    /// started, and the materialized view will be visible once the backfill is finished.
     async fn create_materialized_view(
         &self,
         table: ProstTable,
         graph: StreamFragmentGraph,
+        background: bool,
     ) -> Result<()>;
 
     async fn create_materialized_source(
@@ -142,10 +145,11 @@ impl CatalogWriter for CatalogWriterImpl {
         &self,
         table: ProstTable,
         graph: StreamFragmentGraph,
+        background: bool,
     ) -> Result<()> {
         let (_, version) = self
             .meta_client
-            .create_materialized_view(table, graph)
+            .create_materialized_view(table, graph, background)
             .await?;
         self.wait_version(version).await
     }
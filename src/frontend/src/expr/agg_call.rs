@@ -108,6 +108,43 @@ impl AggCall {
                 datatype: Box::new(input.clone()),
             },
             (AggKind::ArrayAgg, _) => return invalid(),
+
+            // BoolAnd, BoolOr
+            (AggKind::BoolAnd | AggKind::BoolOr, [DataType::Boolean]) => DataType::Boolean,
+            (AggKind::BoolAnd | AggKind::BoolOr, _) => return invalid(),
+
+            // BitAnd, BitOr
+            (AggKind::BitAnd | AggKind::BitOr, [input]) => match input {
+                DataType::Int16 | DataType::Int32 | DataType::Int64 => input.clone(),
+                _ => return invalid(),
+            },
+            (AggKind::BitAnd | AggKind::BitOr, _) => return invalid(),
+
+            // StddevSamp, StddevPop, VarSamp, VarPop
+            (
+                AggKind::StddevSamp | AggKind::StddevPop | AggKind::VarSamp | AggKind::VarPop,
+                [input],
+            ) => match input {
+                DataType::Int16 | DataType::Int32 | DataType::Int64 | DataType::Decimal => {
+                    DataType::Decimal
+                }
+                DataType::Float32 | DataType::Float64 => DataType::Float64,
+                _ => return invalid(),
+            },
+            (AggKind::StddevSamp | AggKind::StddevPop | AggKind::VarSamp | AggKind::VarPop, _) => {
+                return invalid()
+            }
+
+            // FirstValue, LastValue
+            (AggKind::FirstValue | AggKind::LastValue, [input]) => input.clone(),
+            (AggKind::FirstValue | AggKind::LastValue, _) => return invalid(),
+
+            // PercentileCont, PercentileDisc: the first input is the `WITHIN GROUP` ordering
+            // column and the second one is the fraction.
+            (AggKind::PercentileCont, [DataType::Float64, DataType::Float64]) => DataType::Float64,
+            (AggKind::PercentileCont, _) => return invalid(),
+            (AggKind::PercentileDisc, [input, DataType::Float64]) => input.clone(),
+            (AggKind::PercentileDisc, _) => return invalid(),
         };
 
         Ok(return_type)
@@ -330,6 +330,31 @@ impl ExprImpl {
         visitor.visit_expr(self)
     }
 
+    /// Checks whether the expression calls a volatile function such as `random()`, whose result
+    /// differs between evaluations and thus can neither be folded nor used in streaming.
+    pub fn has_volatile_function(&self) -> bool {
+        struct Has {}
+
+        impl ExprVisitor<bool> for Has {
+            fn merge(a: bool, b: bool) -> bool {
+                a | b
+            }
+
+            fn visit_function_call(&mut self, func_call: &FunctionCall) -> bool {
+                func_call.get_expr_type() == ExprType::Random
+                    || func_call
+                        .inputs()
+                        .iter()
+                        .map(|expr| self.visit_expr(expr))
+                        .reduce(Self::merge)
+                        .unwrap_or_default()
+            }
+        }
+
+        let mut visitor = Has {};
+        visitor.visit_expr(self)
+    }
+
     /// Collect `CorrelatedInputRef`s in `ExprImpl` by relative `depth`, return their indices, and
     /// assign absolute `correlated_id` for them.
     pub fn collect_correlated_indices_by_depth_and_assign_id(
@@ -381,7 +406,7 @@ impl ExprImpl {
 
     /// Checks whether this is a constant expr that can be evaluated over a dummy chunk.
     /// Equivalent to `!has_input_ref && !has_agg_call && !has_subquery &&
-    /// !has_correlated_input_ref` but checks them in one pass.
+    /// !has_correlated_input_ref && !has_volatile_function` but checks them in one pass.
     pub fn is_const(&self) -> bool {
         struct Has {
             has: bool,
@@ -392,7 +417,13 @@ impl ExprImpl {
             fn visit_expr(&mut self, expr: &ExprImpl) {
                 match expr {
                     ExprImpl::Literal(_inner) => {}
-                    ExprImpl::FunctionCall(inner) => self.visit_function_call(inner),
+                    ExprImpl::FunctionCall(inner) => {
+                        if inner.get_expr_type() == ExprType::Random {
+                            self.has = true;
+                        } else {
+                            self.visit_function_call(inner)
+                        }
+                    }
                     _ => self.has = true,
                 }
             }
@@ -76,6 +76,16 @@ static AGG_FUNC_SIG_MAP: LazyLock<AggFuncSigMap> = LazyLock::new(|| {
         A::Count,
         A::Avg,
         A::ApproxCountDistinct,
+        A::BoolAnd,
+        A::BoolOr,
+        A::BitAnd,
+        A::BitOr,
+        A::StddevSamp,
+        A::StddevPop,
+        A::VarSamp,
+        A::VarPop,
+        A::FirstValue,
+        A::LastValue,
     ] {
         for input in all_types {
             match AggCall::infer_return_type(&agg, &[DataType::from(input)]) {
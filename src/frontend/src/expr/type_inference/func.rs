@@ -120,6 +120,14 @@ fn infer_type_for_special(
             ensure_arity!("coalesce", 1 <= | inputs |);
             align_types(inputs.iter_mut()).map(Some)
         }
+        ExprType::Greatest => {
+            ensure_arity!("greatest", 1 <= | inputs |);
+            align_types(inputs.iter_mut()).map(Some)
+        }
+        ExprType::Least => {
+            ensure_arity!("least", 1 <= | inputs |);
+            align_types(inputs.iter_mut()).map(Some)
+        }
         ExprType::ConcatWs => {
             ensure_arity!("concat_ws", 2 <= | inputs |);
             let inputs_owned = std::mem::take(inputs);
@@ -135,6 +143,21 @@ fn infer_type_for_special(
                 .try_collect()?;
             Ok(Some(DataType::Varchar))
         }
+        ExprType::Format => {
+            ensure_arity!("format", 1 <= | inputs |);
+            let inputs_owned = std::mem::take(inputs);
+            *inputs = inputs_owned
+                .into_iter()
+                .enumerate()
+                .map(|(i, input)| match i {
+                    // 0-th arg is the format string
+                    0 => input.cast_implicit(DataType::Varchar),
+                    // subsequent can be any type, using the output format
+                    _ => input.cast_output(),
+                })
+                .try_collect()?;
+            Ok(Some(DataType::Varchar))
+        }
         ExprType::ConcatOp => {
             let inputs_owned = std::mem::take(inputs);
             *inputs = inputs_owned
@@ -170,6 +193,21 @@ fn infer_type_for_special(
                 datatype: Box::new(DataType::Varchar),
             }))
         }
+        ExprType::RegexpSplitToArray | ExprType::StringToArray => {
+            if func_type == ExprType::RegexpSplitToArray {
+                ensure_arity!("regexp_split_to_array", 2 <= | inputs | <= 3);
+            } else {
+                ensure_arity!("string_to_array", 2 <= | inputs | <= 3);
+            }
+            let inputs_owned = std::mem::take(inputs);
+            *inputs = inputs_owned
+                .into_iter()
+                .map(|input| input.cast_implicit(DataType::Varchar))
+                .try_collect()?;
+            Ok(Some(DataType::List {
+                datatype: Box::new(DataType::Varchar),
+            }))
+        }
         ExprType::ArrayCat => {
             ensure_arity!("array_cat", | inputs | == 2);
             let left_type = inputs[0].return_type();
@@ -716,6 +754,34 @@ fn build_type_derive_map() -> FuncSigMap {
     build_round_funcs(&mut map, E::Ceil);
     build_round_funcs(&mut map, E::Floor);
 
+    // mathematical functions
+    for e in [E::Sqrt, E::Exp, E::Ln, E::Log10, E::Sign, E::Trunc] {
+        build_round_funcs(&mut map, e);
+    }
+    map.insert(E::TruncDigit, vec![T::Decimal, T::Int32], T::Decimal);
+    map.insert(E::Pow, vec![T::Float64, T::Float64], T::Float64);
+    map.insert(E::Pow, vec![T::Decimal, T::Decimal], T::Decimal);
+    map.insert(E::Log, vec![T::Decimal, T::Decimal], T::Decimal);
+    for e in [
+        E::Cbrt,
+        E::Sin,
+        E::Cos,
+        E::Tan,
+        E::Cot,
+        E::Asin,
+        E::Acos,
+        E::Atan,
+        E::Degrees,
+        E::Radians,
+    ] {
+        map.insert(e, vec![T::Float64], T::Float64);
+    }
+    map.insert(E::Atan2, vec![T::Float64, T::Float64], T::Float64);
+    for t in [T::Float64, T::Decimal] {
+        map.insert(E::WidthBucket, vec![t, t, t, T::Int32], T::Int32);
+    }
+    map.insert(E::Random, vec![], T::Float64);
+
     // temporal expressions
     for (base, delta) in [
         (T::Date, T::Int32),
@@ -741,27 +807,55 @@ fn build_type_derive_map() -> FuncSigMap {
         map.insert(E::Divide, vec![T::Interval, t], T::Interval);
     }
 
-    for t in [T::Timestamp, T::Time, T::Date] {
+    for t in [T::Timestamp, T::Timestampz, T::Time, T::Date, T::Interval] {
         map.insert(E::Extract, vec![T::Varchar, t], T::Decimal);
     }
+    for t in [T::Timestamp, T::Timestampz, T::Interval] {
+        map.insert(E::DateTrunc, vec![T::Varchar, t], t);
+    }
+    map.insert(E::ToTimestamp, vec![T::Float64], T::Timestampz);
+    map.insert(E::ToTimestamp, vec![T::Varchar, T::Varchar], T::Timestampz);
+    map.insert(E::ToDate, vec![T::Varchar, T::Varchar], T::Date);
+    map.insert(E::Age, vec![T::Timestamp, T::Timestamp], T::Interval);
+    map.insert(E::MakeDate, vec![T::Int32, T::Int32, T::Int32], T::Date);
+    map.insert(E::MakeTime, vec![T::Int32, T::Int32, T::Float64], T::Time);
+    map.insert(E::AtTimeZone, vec![T::Timestamp, T::Varchar], T::Timestampz);
+    map.insert(E::AtTimeZone, vec![T::Timestampz, T::Varchar], T::Timestamp);
     for t in [T::Timestamp, T::Date] {
         map.insert(E::TumbleStart, vec![t, T::Interval], T::Timestamp);
     }
 
     // string expressions
-    for e in [E::Trim, E::Ltrim, E::Rtrim, E::Lower, E::Upper, E::Md5] {
+    for e in [
+        E::Trim,
+        E::Ltrim,
+        E::Rtrim,
+        E::Lower,
+        E::Upper,
+        E::Md5,
+        E::Reverse,
+        E::Initcap,
+    ] {
         map.insert(e, vec![T::Varchar], T::Varchar);
     }
     for e in [E::Trim, E::Ltrim, E::Rtrim] {
         map.insert(e, vec![T::Varchar, T::Varchar], T::Varchar);
     }
-    for e in [E::Repeat, E::Substr] {
+    for e in [E::Repeat, E::Substr, E::Left, E::Right, E::Lpad, E::Rpad] {
         map.insert(e, vec![T::Varchar, T::Int32], T::Varchar);
     }
+    for e in [E::Lpad, E::Rpad] {
+        map.insert(e, vec![T::Varchar, T::Int32, T::Varchar], T::Varchar);
+    }
     map.insert(E::Substr, vec![T::Varchar, T::Int32, T::Int32], T::Varchar);
-    for e in [E::Replace, E::Translate] {
+    for e in [E::Replace, E::Translate, E::RegexpReplace] {
         map.insert(e, vec![T::Varchar, T::Varchar, T::Varchar], T::Varchar);
     }
+    map.insert(
+        E::RegexpReplace,
+        vec![T::Varchar, T::Varchar, T::Varchar, T::Varchar],
+        T::Varchar,
+    );
     map.insert(
         E::Overlay,
         vec![T::Varchar, T::Varchar, T::Int32],
@@ -782,7 +876,9 @@ fn build_type_derive_map() -> FuncSigMap {
         map.insert(e, vec![T::Varchar], T::Int32);
     }
     map.insert(E::Position, vec![T::Varchar, T::Varchar], T::Int32);
-    map.insert(E::Like, vec![T::Varchar, T::Varchar], T::Boolean);
+    for e in [E::Like, E::ILike, E::SimilarTo, E::StartsWith] {
+        map.insert(e, vec![T::Varchar, T::Varchar], T::Boolean);
+    }
     map.insert(
         E::SplitPart,
         vec![T::Varchar, T::Varchar, T::Int32],
@@ -0,0 +1,158 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use piestream_common::catalog::valid_table_name;
+use piestream_common::error::ErrorCode::PermissionDenied;
+use piestream_common::error::{ErrorCode, Result, RwError};
+use piestream_sqlparser::ast::{ObjectName, SetVariableValue, Value};
+
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::binder::Binder;
+use crate::session::OptimizerContext;
+
+/// Parses the parallelism of `SET PARALLELISM`, which is a positive integer or `auto`. Returns
+/// `None` for `auto`.
+fn parse_parallelism(parallelism: &SetVariableValue) -> Result<Option<u32>> {
+    match parallelism {
+        SetVariableValue::Ident(ident) if ident.real_value() == "auto" => return Ok(None),
+        SetVariableValue::Literal(Value::Number(n)) => {
+            if let Ok(n @ 1..) = n.parse::<u32>() {
+                return Ok(Some(n));
+            }
+        }
+        _ => {}
+    }
+    Err(ErrorCode::InvalidInputSyntax(format!(
+        "invalid parallelism: {}, expected a positive integer or `auto`",
+        parallelism
+    ))
+    .into())
+}
+
+/// Handles `ALTER TABLE/MATERIALIZED VIEW/SINK <name> SET PARALLELISM = <n> | auto`. The
+/// `stmt_type` tells which kind of streaming job `obj_name` refers to.
+pub async fn handle_alter_parallelism(
+    context: OptimizerContext,
+    obj_name: ObjectName,
+    parallelism: SetVariableValue,
+    stmt_type: StatementType,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let (schema_name, real_name) = Binder::resolve_table_name(obj_name)?;
+    let parallelism = parse_parallelism(&parallelism)?;
+
+    let job_id = {
+        let reader = session.env().catalog_reader().read_guard();
+        let (id, owner) = match stmt_type {
+            StatementType::ALTER_SINK => {
+                let sink = reader.get_sink_by_name(session.database(), &schema_name, &real_name)?;
+                (sink.id, sink.owner)
+            }
+            _ => {
+                let table =
+                    reader.get_table_by_name(session.database(), &schema_name, &real_name)?;
+                // If associated source is `Some`, then it is a actually a materialized source /
+                // table v2.
+                match (stmt_type, table.associated_source_id().is_some()) {
+                    (StatementType::ALTER_TABLE, false) => {
+                        return Err(RwError::from(ErrorCode::InvalidInputSyntax(
+                            "Use `ALTER MATERIALIZED VIEW` to alter a materialized view."
+                                .to_owned(),
+                        )))
+                    }
+                    (StatementType::ALTER_MATERIALIZED_VIEW, true) => {
+                        return Err(RwError::from(ErrorCode::InvalidInputSyntax(
+                            "Use `ALTER TABLE` to alter a table.".to_owned(),
+                        )))
+                    }
+                    _ => {}
+                }
+                if table.is_index || !valid_table_name(&real_name) {
+                    return Err(RwError::from(ErrorCode::InvalidInputSyntax(
+                        "Cannot alter the parallelism of an index or an internal table.".to_owned(),
+                    )));
+                }
+                (table.id().table_id(), table.owner)
+            }
+        };
+
+        let schema_owner = reader
+            .get_schema_by_name(session.database(), &schema_name)
+            .unwrap()
+            .owner();
+        if session.user_id() != owner
+            && session.user_id() != schema_owner
+            && !check_super_user(&session)
+        {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+        id
+    };
+
+    session
+        .env()
+        .meta_client()
+        .alter_parallelism(job_id, parallelism)
+        .await?;
+
+    Ok(PgResponse::empty_result(stmt_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_alter_parallelism_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 smallint);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create materialized view mv as select v1 from t;")
+            .await
+            .unwrap();
+
+        frontend
+            .run_sql("alter table t set parallelism = 4;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter materialized view mv set parallelism to auto;")
+            .await
+            .unwrap();
+
+        // Wrong kind of the streaming job.
+        assert!(frontend
+            .run_sql("alter materialized view t set parallelism = 2;")
+            .await
+            .is_err());
+        assert!(frontend
+            .run_sql("alter table mv set parallelism = 2;")
+            .await
+            .is_err());
+        // Invalid parallelism.
+        assert!(frontend
+            .run_sql("alter table t set parallelism = 0;")
+            .await
+            .is_err());
+        assert!(frontend
+            .run_sql("alter table t set parallelism = foo;")
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,47 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
+use pgwire::pg_response::{PgResponse, StatementType};
+use pgwire::types::Row;
+use piestream_common::error::Result;
+
+use super::RwPgResponse;
+use crate::session::OptimizerContext;
+
+/// Cancel the creating streaming jobs, returns the ids of the jobs which are actually canceled.
+pub(super) async fn handle_cancel(
+    context: OptimizerContext,
+    job_ids: Vec<u32>,
+) -> Result<RwPgResponse> {
+    let canceled_jobs = context
+        .session_ctx
+        .env()
+        .meta_client()
+        .cancel_creating_jobs(job_ids)
+        .await?;
+
+    let rows = canceled_jobs
+        .into_iter()
+        .map(|id| Row::new(vec![Some(id.to_string().into())]))
+        .collect_vec();
+
+    Ok(PgResponse::new_for_stream(
+        StatementType::CANCEL_COMMAND,
+        Some(rows.len() as i32),
+        rows.into(),
+        vec![PgFieldDescriptor::new("Id".to_owned(), TypeOid::Varchar)],
+    ))
+}
@@ -14,8 +14,11 @@
 
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::Arc;
 
+use anyhow::anyhow;
 use fixedbitset::FixedBitSet;
+use futures::StreamExt;
 use itertools::Itertools;
 use pgwire::pg_response::{PgResponse, StatementType};
 use piestream_common::catalog::{IndexId, TableDesc, TableId, DEFAULT_SCHEMA_NAME};
@@ -23,8 +26,9 @@ use piestream_common::error::{ErrorCode, Result, RwError};
 use piestream_pb::catalog::{Index as ProstIndex, Table as ProstTable};
 use piestream_pb::user::grant_privilege::{Action, Object};
 use piestream_sqlparser::ast::{Ident, ObjectName, OrderByExpr};
+use piestream_sqlparser::parser::Parser;
 
-use super::RwPgResponse;
+use super::{query, RwPgResponse};
 use crate::binder::Binder;
 use crate::catalog::check_schema_writable;
 use crate::expr::{Expr, ExprImpl, InputRef};
@@ -34,6 +38,7 @@ use crate::optimizer::property::{Distribution, FieldOrder, Order, RequiredDist};
 use crate::optimizer::{PlanRef, PlanRoot};
 use crate::session::{OptimizerContext, OptimizerContextRef, SessionImpl};
 use crate::stream_fragmenter::build_graph;
+use crate::WithOptions;
 
 pub(crate) fn gen_create_index_plan(
     session: &SessionImpl,
@@ -321,6 +326,60 @@ fn check_columns(columns: Vec<OrderByExpr>) -> Result<Vec<Ident>> {
         .try_collect::<_, Vec<_>, _>()
 }
 
+/// Check that no rows of `table_name` share the same values of the index `columns`, which is
+/// required to build a unique index on them. Like PostgreSQL, rows with a null in any of the index
+/// columns never conflict, so they are not checked.
+///
+/// Note that the rows inserted after the check are not validated.
+async fn check_unique_index_columns(
+    session: Arc<SessionImpl>,
+    index_name: &ObjectName,
+    table_name: &ObjectName,
+    columns: &[OrderByExpr],
+) -> Result<()> {
+    let column_list = columns.iter().map(|c| c.expr.to_string()).join(", ");
+    let not_null = columns
+        .iter()
+        .map(|c| format!("{} IS NOT NULL", c.expr))
+        .join(" AND ");
+    let sql = format!(
+        "SELECT {column_list} FROM {table_name} WHERE {not_null} GROUP BY {column_list} HAVING COUNT(*) > 1 LIMIT 1"
+    );
+    let stmt = Parser::parse_sql(&sql)
+        .map_err(|e| ErrorCode::InternalError(e.to_string()))?
+        .remove(0);
+    let context = OptimizerContext::new(session, Arc::from(sql), WithOptions::default());
+
+    let mut response = query::handle_query(context, stmt, false).await?;
+    while let Some(rows) = response.values_stream().next().await {
+        let rows = rows.map_err(|e| ErrorCode::InternalError(e.to_string()))?;
+        if let Some(row) = rows.first() {
+            let values = row
+                .values()
+                .iter()
+                .map(|v| match v {
+                    Some(v) => String::from_utf8_lossy(v).into_owned(),
+                    None => "null".to_string(),
+                })
+                .join(", ");
+            return Err(ErrorCode::CatalogError(
+                anyhow!(
+                    "could not create unique index \"{}\": key ({})=({}) is duplicated",
+                    index_name,
+                    column_list,
+                    values
+                )
+                .into(),
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Create an index on the table. If `unique` is set, the build fails if there are duplicated
+/// values of the index columns in the table.
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_create_index(
     context: OptimizerContext,
     if_not_exists: bool,
@@ -329,6 +388,7 @@ pub async fn handle_create_index(
     columns: Vec<OrderByExpr>,
     include: Vec<Ident>,
     distributed_by: Vec<Ident>,
+    unique: bool,
 ) -> Result<RwPgResponse> {
     let session = context.session_ctx.clone();
 
@@ -362,7 +422,7 @@ pub async fn handle_create_index(
             context.into(),
             name.clone(),
             table_name.clone(),
-            columns,
+            columns.clone(),
             include,
             distributed_by,
         )?;
@@ -377,6 +437,10 @@ pub async fn handle_create_index(
         serde_json::to_string_pretty(&graph).unwrap()
     );
 
+    if unique {
+        check_unique_index_columns(session.clone(), &name, &table_name, &columns).await?;
+    }
+
     let catalog_writer = session.env().catalog_writer();
     catalog_writer
         .create_index(index, index_table, graph)
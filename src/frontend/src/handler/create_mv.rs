@@ -65,7 +65,14 @@ pub fn gen_create_mv_plan(
 
     let bound = {
         let mut binder = Binder::new(session);
-        binder.bind_query(query)?
+        let bound = binder.bind_query(query)?;
+        if binder.as_of_epoch().is_some() {
+            return Err(ErrorCode::BindError(
+                "AS OF is not allowed in a streaming query".to_string(),
+            )
+            .into());
+        }
+        bound
     };
 
     if let BoundSetExpr::Select(select) = &bound.body {
@@ -112,6 +119,7 @@ pub async fn handle_create_mv(
     query: Query,
 ) -> Result<RwPgResponse> {
     let session = context.session_ctx.clone();
+    let background = context.with_options.background();
 
     let (table, graph) = {
         {
@@ -132,7 +140,7 @@ pub async fn handle_create_mv(
 
     let catalog_writer = session.env().catalog_writer();
     catalog_writer
-        .create_materialized_view(table, graph)
+        .create_materialized_view(table, graph, background)
         .await?;
 
     Ok(PgResponse::empty_result(
@@ -205,6 +213,26 @@ pub mod tests {
         assert_eq!(columns, expected_columns);
     }
 
+    #[tokio::test]
+    async fn test_create_mv_in_background() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        let sql = "create table t(x varchar)";
+        frontend.run_sql(sql).await.unwrap();
+
+        let sql = "create materialized view mv1 with (background = true) as select x from t";
+        frontend.run_sql(sql).await.unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader();
+        let table = catalog_reader
+            .read_guard()
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "mv1")
+            .unwrap()
+            .clone();
+        assert_eq!(table.name(), "mv1");
+    }
+
     /// When creating MV, The only thing to allow without explicit alias is `InputRef`.
     #[tokio::test]
     async fn test_no_alias() {
@@ -26,6 +26,7 @@ use piestream_pb::catalog::{
     TableSourceInfo,
 };
 use piestream_pb::plan_common::ColumnCatalog as ProstColumnCatalog;
+use piestream_pb::stream_plan::HandleConflictBehavior;
 use piestream_sqlparser::ast::{
     ColumnDef, ColumnOption, DataType as AstDataType, ObjectName, TableConstraint,
 };
@@ -222,20 +223,28 @@ pub(crate) fn gen_create_table_plan(
 
 /// Generate a stream plan with `StreamSource` + `StreamMaterialize`, it resembles a
 /// `CREATE MATERIALIZED VIEW AS SELECT * FROM <source>`.
+///
+/// If the primary key is user-defined instead of the `_row_id`, the rows conflicting on it are
+/// handled by the `StreamMaterialize` according to the `pk_conflict` option.
 pub(crate) fn gen_materialized_source_plan(
     context: OptimizerContextRef,
     source: ProstSource,
     owner: u32,
 ) -> Result<(PlanRef, ProstTable)> {
+    let row_id_index = {
+        let (Info::StreamSource(StreamSourceInfo { row_id_index, .. })
+        | Info::TableSource(TableSourceInfo { row_id_index, .. })) = source.info.as_ref().unwrap();
+        row_id_index.as_ref().map(|index| index.index as _)
+    };
+    let handle_pk_conflict_behavior = match row_id_index {
+        Some(_) => HandleConflictBehavior::NoCheck,
+        None => context.inner().with_options.pk_conflict_behavior()?,
+    };
+
     let materialize = {
         // Manually assemble the materialization plan for the table.
         let source_node: PlanRef =
             StreamSource::new(LogicalSource::new(Rc::new((&source).into()), context)).into();
-        let row_id_index = {
-            let (Info::StreamSource(StreamSourceInfo { row_id_index, .. })
-            | Info::TableSource(TableSourceInfo { row_id_index, .. })) = source.info.unwrap();
-            row_id_index.as_ref().map(|index| index.index as _)
-        };
         let mut required_cols = FixedBitSet::with_capacity(source_node.schema().len());
         required_cols.toggle_range(..);
         let mut out_names = source_node.schema().names();
@@ -252,6 +261,7 @@ pub(crate) fn gen_materialized_source_plan(
             out_names,
         )
         .gen_create_mv_plan(source.name.clone(), "".into())?
+        .with_handle_pk_conflict_behavior(handle_pk_conflict_behavior)
     };
     let mut table = materialize
         .table()
@@ -19,18 +19,23 @@ use std::task::{Context, Poll};
 use futures::stream::{self, BoxStream};
 use futures::{Stream, StreamExt};
 use pgwire::pg_response::StatementType::{ABORT, BEGIN, COMMIT, ROLLBACK, START_TRANSACTION};
-use pgwire::pg_response::{PgResponse, RowSetResult};
+use pgwire::pg_response::{PgResponse, RowSetResult, StatementType};
 use pgwire::pg_server::BoxedError;
 use pgwire::types::Row;
 use piestream_common::error::{ErrorCode, Result};
-use piestream_sqlparser::ast::{DropStatement, ObjectType, Statement};
+use piestream_sqlparser::ast::{
+    AlterSinkOperation, AlterTableOperation, AlterViewOperation, DropStatement, ObjectType,
+    ShowObject, Statement,
+};
 
 use self::util::DataChunkToRowSetAdapter;
 use crate::scheduler::{DistributedQueryStream, LocalQueryStream};
 use crate::session::{OptimizerContext, SessionImpl};
 use crate::utils::WithOptions;
 
+mod alter_parallelism;
 pub mod alter_user;
+mod cancel_job;
 mod create_database;
 pub mod create_index;
 pub mod create_mv;
@@ -154,9 +159,47 @@ pub async fn handle(
         } => create_schema::handle_create_schema(context, schema_name, if_not_exists).await,
         Statement::CreateUser(stmt) => create_user::handle_create_user(context, stmt).await,
         Statement::AlterUser(stmt) => alter_user::handle_alter_user(context, stmt).await,
+        Statement::AlterTable {
+            name,
+            operation: AlterTableOperation::SetParallelism { parallelism },
+        } => {
+            alter_parallelism::handle_alter_parallelism(
+                context,
+                name,
+                parallelism,
+                StatementType::ALTER_TABLE,
+            )
+            .await
+        }
+        Statement::AlterView {
+            name,
+            materialized: true,
+            operation: AlterViewOperation::SetParallelism { parallelism },
+        } => {
+            alter_parallelism::handle_alter_parallelism(
+                context,
+                name,
+                parallelism,
+                StatementType::ALTER_MATERIALIZED_VIEW,
+            )
+            .await
+        }
+        Statement::AlterSink {
+            name,
+            operation: AlterSinkOperation::SetParallelism { parallelism },
+        } => {
+            alter_parallelism::handle_alter_parallelism(
+                context,
+                name,
+                parallelism,
+                StatementType::ALTER_SINK,
+            )
+            .await
+        }
         Statement::Grant { .. } => handle_privilege::handle_grant_privilege(context, stmt).await,
         Statement::Revoke { .. } => handle_privilege::handle_revoke_privilege(context, stmt).await,
         Statement::Describe { name } => describe::handle_describe(context, name),
+        Statement::ShowObjects(ShowObject::Jobs) => show::handle_show_jobs(context).await,
         Statement::ShowObjects(show_object) => show::handle_show_object(context, show_object),
         Statement::Drop(DropStatement {
             object_type,
@@ -202,6 +245,7 @@ pub async fn handle(
             ..
         } => create_mv::handle_create_mv(context, name, *query).await,
         Statement::Flush => flush::handle_flush(context).await,
+        Statement::CancelJobs(job_ids) => cancel_job::handle_cancel(context, job_ids).await,
         Statement::SetVariable {
             local: _,
             variable,
@@ -217,12 +261,6 @@ pub async fn handle(
             unique,
             if_not_exists,
         } => {
-            if unique {
-                return Err(
-                    ErrorCode::NotImplemented("create unique index".into(), None.into()).into(),
-                );
-            }
-
             create_index::handle_create_index(
                 context,
                 if_not_exists,
@@ -231,6 +269,7 @@ pub async fn handle(
                 columns.to_vec(),
                 include,
                 distributed_by,
+                unique,
             )
             .await
         }
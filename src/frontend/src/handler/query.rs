@@ -30,7 +30,7 @@ use crate::planner::Planner;
 use crate::scheduler::plan_fragmenter::Query;
 use crate::scheduler::{
     BatchPlanFragmenter, DistributedQueryStream, ExecutionContext, ExecutionContextRef,
-    LocalQueryExecution, LocalQueryStream,
+    LocalQueryExecution, LocalQueryStream, PointGetExecution,
 };
 use crate::session::{OptimizerContext, OptimizerContextRef, SessionImpl};
 use crate::PlanRef;
@@ -39,13 +39,19 @@ pub fn gen_batch_query_plan(
     session: &SessionImpl,
     context: OptimizerContextRef,
     stmt: Statement,
-) -> Result<(PlanRef, QueryMode, Vec<PgFieldDescriptor>)> {
+) -> Result<(PlanRef, QueryMode, Vec<PgFieldDescriptor>, Option<u64>)> {
     let stmt_type = to_statement_type(&stmt);
 
-    let bound = {
+    let (bound, as_of_epoch) = {
         let mut binder = Binder::new(session);
-        binder.bind(stmt)?
+        let bound = binder.bind(stmt)?;
+        (bound, binder.as_of_epoch())
     };
+    if as_of_epoch.is_some() && stmt_type.is_dml() {
+        return Err(
+            ErrorCode::NotImplemented("AS OF in DML statements".to_string(), None.into()).into(),
+        );
+    }
 
     let check_items = resolve_privileges(&bound);
     check_privileges(session, &check_items)?;
@@ -82,10 +88,11 @@ pub fn gen_batch_query_plan(
         .map(to_pg_field)
         .collect::<Vec<PgFieldDescriptor>>();
 
-    match query_mode {
-        QueryMode::Local => Ok((logical.gen_batch_local_plan()?, query_mode, pg_descs)),
-        QueryMode::Distributed => Ok((logical.gen_batch_distributed_plan()?, query_mode, pg_descs)),
-    }
+    let plan = match query_mode {
+        QueryMode::Local => logical.gen_batch_local_plan()?,
+        QueryMode::Distributed => logical.gen_batch_distributed_plan()?,
+    };
+    Ok((plan, query_mode, pg_descs, as_of_epoch))
 }
 
 pub async fn handle_query(
@@ -96,33 +103,54 @@ pub async fn handle_query(
     let stmt_type = to_statement_type(&stmt);
     let session = context.session_ctx.clone();
     let query_start_time = Instant::now();
+    // Results of point lookups are cached by the statement with parameters filled.
+    let cache_key = session
+        .config()
+        .get_batch_enable_point_get_cache()
+        .then(|| format!("{}:{}", session.database(), stmt));
 
     // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
-    let (query, query_mode, pg_descs) = {
-        let (plan, query_mode, pg_descs) = gen_batch_query_plan(&session, context.into(), stmt)?;
+    let (query, point_get, query_mode, pg_descs, as_of_epoch) = {
+        let (plan, query_mode, pg_descs, as_of_epoch) =
+            gen_batch_query_plan(&session, context.into(), stmt)?;
 
         tracing::trace!(
             "Generated query plan: {:?}, query_mode:{:?}",
             plan.explain_to_string()?,
             query_mode
         );
-        let plan_fragmenter = BatchPlanFragmenter::new(
-            session.env().worker_node_manager_ref(),
-            session.env().catalog_reader().clone(),
-        );
-        (plan_fragmenter.split(plan)?, query_mode, pg_descs)
+
+        // Point lookups on the primary key skip the plan fragmenter and the scheduler.
+        let point_get = if query_mode == QueryMode::Local && as_of_epoch.is_none() {
+            PointGetExecution::try_new(&plan, session.env())?
+        } else {
+            None
+        };
+        let query = if point_get.is_none() {
+            let plan_fragmenter = BatchPlanFragmenter::new(
+                session.env().worker_node_manager_ref(),
+                session.env().catalog_reader().clone(),
+            );
+            Some(plan_fragmenter.split(plan)?)
+        } else {
+            None
+        };
+        (query, point_get, query_mode, pg_descs, as_of_epoch)
     };
     tracing::trace!("Generated query after plan fragmenter: {:?}", &query);
 
     let mut row_stream = match query_mode {
-        QueryMode::Local => PgResponseStream::LocalQuery(DataChunkToRowSetAdapter::new(
-            local_execute(session.clone(), query).await?,
-            format,
-        )),
+        QueryMode::Local => {
+            let stream = match point_get {
+                Some(point_get) => point_get_execute(session.clone(), point_get, cache_key).await?,
+                None => local_execute(session.clone(), query.unwrap(), as_of_epoch).await?,
+            };
+            PgResponseStream::LocalQuery(DataChunkToRowSetAdapter::new(stream, format))
+        }
         // Local mode do not support cancel tasks.
         QueryMode::Distributed => {
             PgResponseStream::DistributedQuery(DataChunkToRowSetAdapter::new(
-                distribute_execute(session.clone(), query).await?,
+                distribute_execute(session.clone(), query.unwrap(), as_of_epoch).await?,
                 format,
             ))
         }
@@ -190,25 +218,35 @@ fn to_statement_type(stmt: &Statement) -> StatementType {
 pub async fn distribute_execute(
     session: Arc<SessionImpl>,
     query: Query,
+    as_of_epoch: Option<u64>,
 ) -> Result<DistributedQueryStream> {
     let execution_context: ExecutionContextRef = ExecutionContext::new(session.clone()).into();
     let query_manager = execution_context.session().env().query_manager().clone();
     query_manager
-        .schedule(execution_context, query)
+        .schedule(execution_context, query, as_of_epoch)
         .await
         .map_err(|err| err.into())
 }
 
-async fn local_execute(session: Arc<SessionImpl>, query: Query) -> Result<LocalQueryStream> {
+async fn local_execute(
+    session: Arc<SessionImpl>,
+    query: Query,
+    as_of_epoch: Option<u64>,
+) -> Result<LocalQueryStream> {
     let front_env = session.env();
 
     // Acquire hummock snapshot for local execution.
     let hummock_snapshot_manager = front_env.hummock_snapshot_manager();
     let query_id = query.query_id().clone();
-    let epoch = hummock_snapshot_manager
-        .acquire(&query_id)
-        .await?
-        .committed_epoch;
+    let epoch = match as_of_epoch {
+        Some(epoch) => {
+            hummock_snapshot_manager
+                .acquire_specific(&query_id, epoch)
+                .await?
+        }
+        None => hummock_snapshot_manager.acquire(&query_id).await?,
+    }
+    .committed_epoch;
 
     // TODO: Passing sql here
     let execution =
@@ -221,6 +259,38 @@ async fn local_execute(session: Arc<SessionImpl>, query: Query) -> Result<LocalQ
     rsp
 }
 
+async fn point_get_execute(
+    session: Arc<SessionImpl>,
+    execution: PointGetExecution,
+    cache_key: Option<String>,
+) -> Result<LocalQueryStream> {
+    let front_env = session.env();
+
+    // Acquire hummock snapshot for the point lookup.
+    let hummock_snapshot_manager = front_env.hummock_snapshot_manager();
+    let query_id = execution.query_id().clone();
+    let epoch = hummock_snapshot_manager
+        .acquire(&query_id)
+        .await?
+        .committed_epoch;
+
+    let cache = hummock_snapshot_manager.point_get_cache();
+    let rsp = match cache_key {
+        Some(key) => match cache.get(&key, epoch) {
+            Some(cached) => cached,
+            None => {
+                execution.stream_rows(epoch, session.auth_context(), Some((cache.clone(), key)))
+            }
+        },
+        None => execution.stream_rows(epoch, session.auth_context(), None),
+    };
+
+    // Release hummock snapshot for the point lookup.
+    hummock_snapshot_manager.release(epoch, &query_id).await;
+
+    Ok(rsp)
+}
+
 async fn flush_for_write(session: &SessionImpl, stmt_type: StatementType) -> Result<()> {
     match stmt_type {
         StatementType::INSERT | StatementType::DELETE | StatementType::UPDATE => {
@@ -90,6 +90,7 @@ pub fn handle_show_object(context: OptimizerContext, command: ShowObject) -> Res
             .iter_sink()
             .map(|t| t.name.clone())
             .collect(),
+        ShowObject::Jobs => unreachable!("`SHOW JOBS` is handled by `handle_show_jobs`"),
         ShowObject::Columns { table } => {
             let columns = get_columns_from_table(&session, table)?;
             let rows = col_descs_to_rows(columns);
@@ -119,6 +120,52 @@ pub fn handle_show_object(context: OptimizerContext, command: ShowObject) -> Res
     ))
 }
 
+/// Returns the percentage of the upstream snapshot consumed by the backfill. The consumed rows
+/// include the rows from the upstream after the creation started, and the snapshot rows are
+/// estimated from the storage, so the percentage is capped at 100.
+fn progress_percentage(consumed_rows: u64, snapshot_rows: u64) -> f64 {
+    if snapshot_rows == 0 {
+        100.0
+    } else {
+        (consumed_rows as f64 * 100.0 / snapshot_rows as f64).min(100.0)
+    }
+}
+
+/// Show the progress of all creating materialized views, which is the percentage of the upstream
+/// snapshot rows consumed by the backfill actors.
+pub async fn handle_show_jobs(context: OptimizerContext) -> Result<RwPgResponse> {
+    let ddl_progress = context
+        .session_ctx
+        .env()
+        .meta_client()
+        .get_ddl_progress()
+        .await?;
+
+    let rows = ddl_progress
+        .into_iter()
+        .sorted_by_key(|p| p.id)
+        .map(|p| {
+            let progress = progress_percentage(p.consumed_rows, p.snapshot_rows);
+            Row::new(vec![
+                Some(p.id.to_string().into()),
+                Some(p.name.into()),
+                Some(format!("{:.2}%", progress).into()),
+            ])
+        })
+        .collect_vec();
+
+    Ok(PgResponse::new_for_stream(
+        StatementType::SHOW_COMMAND,
+        Some(rows.len() as i32),
+        rows.into(),
+        vec![
+            PgFieldDescriptor::new("Id".to_owned(), TypeOid::Varchar),
+            PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
+            PgFieldDescriptor::new("Progress".to_owned(), TypeOid::Varchar),
+        ],
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -126,6 +173,7 @@ mod tests {
 
     use futures_async_stream::for_await;
 
+    use super::progress_percentage;
     use crate::test_utils::{create_proto_file, LocalFrontend, PROTO_FILE_DATA};
 
     #[tokio::test]
@@ -158,6 +206,22 @@ mod tests {
         assert_eq!(rows, vec!["Row([Some(b\"t2\")])".to_string()]);
     }
 
+    #[test]
+    fn test_progress_percentage() {
+        assert_eq!(progress_percentage(0, 0), 100.0);
+        assert_eq!(progress_percentage(0, 400), 0.0);
+        assert_eq!(progress_percentage(100, 400), 25.0);
+        // The upstream may have changed since the creation started.
+        assert_eq!(progress_percentage(500, 400), 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_show_jobs() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let rows = frontend.query_formatted_result("SHOW JOBS").await;
+        assert!(rows.is_empty());
+    }
+
     #[tokio::test]
     async fn test_show_column() {
         let proto_file = create_proto_file(PROTO_FILE_DATA);
@@ -18,14 +18,17 @@ use std::task::{Context, Poll};
 use bytes::Bytes;
 use futures::Stream;
 use itertools::Itertools;
+use pgwire::error::SqlStateError;
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::RowSetResult;
 use pgwire::pg_server::BoxedError;
 use pgwire::types::Row;
-use pin_project_lite::pin_project;
 use piestream_common::array::DataChunk;
 use piestream_common::catalog::{ColumnDesc, Field};
+use piestream_common::error::{ErrorCode, RwError};
 use piestream_common::types::{DataType, ScalarRefImpl};
+use piestream_expr::ExprError;
+use pin_project_lite::pin_project;
 
 pin_project! {
     /// Wrapper struct that converts a stream of DataChunk to a stream of RowSet based on formatting
@@ -148,12 +151,34 @@ pub fn data_type_to_type_oid(data_type: DataType) -> TypeOid {
     }
 }
 
+/// Convert the error to be reported to the client, with the `SQLSTATE` code of the error from
+/// expression evaluation if any.
+pub fn to_pg_error(error: RwError) -> BoxedError {
+    if let ErrorCode::ExprError(e) = error.inner() {
+        if let Some(e) = e.downcast_ref::<ExprError>() {
+            return Box::new(SqlStateError::new(e.sqlstate(), error));
+        }
+    }
+    Box::new(error)
+}
+
 #[cfg(test)]
 mod tests {
     use piestream_common::array::*;
 
     use super::*;
 
+    #[test]
+    fn test_to_pg_error() {
+        let error = to_pg_error(ExprError::DivisionByZero.into());
+        let error = error.downcast_ref::<SqlStateError>().unwrap();
+        assert_eq!(error.code(), "22012");
+        assert_eq!(error.to_string(), "Expr error: Division by zero");
+
+        let error = to_pg_error(ErrorCode::InternalError("unknown".to_string()).into());
+        assert!(error.downcast_ref::<SqlStateError>().is_none());
+    }
+
     #[test]
     fn test_to_pg_field() {
         let field = Field::with_name(DataType::Int32, "v1");
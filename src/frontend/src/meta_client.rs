@@ -14,6 +14,7 @@
 
 use std::collections::HashMap;
 
+use piestream_pb::ddl_service::DdlProgress;
 use piestream_pb::hummock::HummockSnapshot;
 use piestream_pb::meta::list_table_fragments_response::TableFragmentInfo;
 use piestream_rpc_client::error::Result;
@@ -40,6 +41,14 @@ pub trait FrontendMetaClient: Send + Sync {
     async fn unpin_snapshot(&self) -> Result<()>;
 
     async fn unpin_snapshot_before(&self, epoch: u64) -> Result<()>;
+
+    async fn pin_specific_snapshot(&self, epoch: u64) -> Result<HummockSnapshot>;
+
+    async fn alter_parallelism(&self, table_id: u32, parallelism: Option<u32>) -> Result<()>;
+
+    async fn get_ddl_progress(&self) -> Result<Vec<DdlProgress>>;
+
+    async fn cancel_creating_jobs(&self, job_ids: Vec<u32>) -> Result<Vec<u32>>;
 }
 
 pub struct FrontendMetaClientImpl(pub MetaClient);
@@ -72,4 +81,20 @@ impl FrontendMetaClient for FrontendMetaClientImpl {
     async fn unpin_snapshot_before(&self, epoch: u64) -> Result<()> {
         self.0.unpin_snapshot_before(epoch).await
     }
+
+    async fn pin_specific_snapshot(&self, epoch: u64) -> Result<HummockSnapshot> {
+        self.0.pin_specific_snapshot(epoch).await
+    }
+
+    async fn alter_parallelism(&self, table_id: u32, parallelism: Option<u32>) -> Result<()> {
+        self.0.alter_parallelism(table_id, parallelism).await
+    }
+
+    async fn get_ddl_progress(&self) -> Result<Vec<DdlProgress>> {
+        self.0.get_ddl_progress().await
+    }
+
+    async fn cancel_creating_jobs(&self, job_ids: Vec<u32>) -> Result<Vec<u32>> {
+        self.0.cancel_creating_jobs(job_ids).await
+    }
 }
@@ -72,10 +72,25 @@ impl ToDistributedBatch for BatchSimpleAgg {
         let dist_input = self.input().to_distributed()?;
 
         // TODO: distinct agg cannot use 2-phase agg yet.
-        if dist_input.distribution().satisfies(&RequiredDist::AnyShard)
+        let can_use_two_phase = dist_input.distribution().satisfies(&RequiredDist::AnyShard)
             && self.logical.agg_calls().iter().all(|call| !call.distinct)
-            && !self.logical.is_agg_result_affected_by_order()
-        {
+            && !self.logical.is_agg_result_affected_by_order();
+        // Agg calls that cannot be split into two phases fall back to a single-phase plan.
+        let total_agg_types = if can_use_two_phase {
+            self.logical
+                .agg_calls()
+                .iter()
+                .enumerate()
+                .map(|(partial_output_idx, agg_call)| {
+                    agg_call.partial_to_total_agg_call(partial_output_idx)
+                })
+                .collect::<Result<Vec<_>>>()
+                .ok()
+        } else {
+            None
+        };
+
+        if let Some(total_agg_types) = total_agg_types {
             // partial agg
             let partial_agg = self.clone_with_input(dist_input).into();
 
@@ -84,15 +99,6 @@ impl ToDistributedBatch for BatchSimpleAgg {
                 BatchExchange::new(partial_agg, Order::any(), Distribution::Single).into();
 
             // insert total agg
-            let total_agg_types = self
-                .logical
-                .agg_calls()
-                .iter()
-                .enumerate()
-                .map(|(partial_output_idx, agg_call)| {
-                    agg_call.partial_to_total_agg_call(partial_output_idx)
-                })
-                .collect();
             let total_agg_logical =
                 LogicalAgg::new(total_agg_types, self.logical.group_key().to_vec(), exchange);
             Ok(BatchSimpleAgg::new(total_agg_logical).into())
@@ -17,6 +17,7 @@ use std::rc::Rc;
 
 use itertools::Itertools;
 use piestream_common::catalog::{Field, FieldDisplay, Schema, TableDesc};
+use piestream_common::error::{ErrorCode, Result};
 use piestream_common::types::{DataType, IntervalUnit};
 use piestream_expr::expr::AggKind;
 use piestream_pb::expr::agg_call::OrderByField as ProstAggOrderByField;
@@ -289,24 +290,43 @@ impl PlanAggCall {
         }
     }
 
-    pub fn partial_to_total_agg_call(&self, partial_output_idx: usize) -> PlanAggCall {
+    /// Converts a partial agg call into the total agg call consuming its output. Returns an error
+    /// for agg kinds that cannot be split into two phases.
+    pub fn partial_to_total_agg_call(&self, partial_output_idx: usize) -> Result<PlanAggCall> {
         let total_agg_kind = match &self.agg_kind {
-            AggKind::Min | AggKind::Max | AggKind::StringAgg => self.agg_kind,
+            AggKind::Min
+            | AggKind::Max
+            | AggKind::StringAgg
+            | AggKind::BoolAnd
+            | AggKind::BoolOr
+            | AggKind::BitAnd
+            | AggKind::BitOr => self.agg_kind,
             AggKind::Count | AggKind::Sum | AggKind::ApproxCountDistinct => AggKind::Sum,
-            AggKind::Avg => {
-                panic!("Avg aggregation should have been rewritten to Sum+Count")
-            }
-            AggKind::ArrayAgg => {
-                panic!("2-phase ArrayAgg is not supported yet")
+            // Avg and Stddev/Var should have been rewritten to Sum+Count before reaching here.
+            AggKind::Avg
+            | AggKind::StddevSamp
+            | AggKind::StddevPop
+            | AggKind::VarSamp
+            | AggKind::VarPop
+            | AggKind::ArrayAgg
+            | AggKind::FirstValue
+            | AggKind::LastValue
+            | AggKind::PercentileCont
+            | AggKind::PercentileDisc => {
+                return Err(ErrorCode::NotImplemented(
+                    format!("2-phase {} aggregation", self.agg_kind),
+                    None.into(),
+                )
+                .into());
             }
         };
-        PlanAggCall {
+        Ok(PlanAggCall {
             agg_kind: total_agg_kind,
             inputs: vec![InputRef::new(partial_output_idx, self.return_type.clone())],
             order_by_fields: vec![], // order must make no difference when we use 2-phase agg
             filter: Condition::true_cond(),
             ..self.clone()
-        }
+        })
     }
 
     pub fn count_star() -> Self {
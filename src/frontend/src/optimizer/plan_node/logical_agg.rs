@@ -18,8 +18,8 @@ use std::{fmt, iter};
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use piestream_common::catalog::{Field, FieldDisplay, Schema};
-use piestream_common::error::{ErrorCode, Result, TrackingIssue};
-use piestream_common::types::DataType;
+use piestream_common::error::{ErrorCode, Result, RwError, TrackingIssue};
+use piestream_common::types::{DataType, ScalarImpl};
 use piestream_common::util::sort_util::OrderType;
 use piestream_expr::expr::AggKind;
 use piestream_pb::stream_plan::{agg_call_state, AggCallState as AggCallStateProst};
@@ -174,18 +174,43 @@ impl LogicalAgg {
         self.agg_calls()
             .iter()
             .map(|agg_call| match agg_call.agg_kind {
-                AggKind::Min | AggKind::Max | AggKind::StringAgg | AggKind::ArrayAgg => {
-                    if !in_append_only {
+                AggKind::Min
+                | AggKind::Max
+                | AggKind::StringAgg
+                | AggKind::ArrayAgg
+                | AggKind::BoolAnd
+                | AggKind::BoolOr
+                | AggKind::BitAnd
+                | AggKind::BitOr
+                | AggKind::FirstValue
+                | AggKind::LastValue
+                | AggKind::PercentileCont
+                | AggKind::PercentileDisc => {
+                    // These can be maintained with a single value for append-only input, except
+                    // for those depending on the order or the full set of input values.
+                    let needs_materialized_input = !in_append_only
+                        || matches!(
+                            agg_call.agg_kind,
+                            AggKind::FirstValue
+                                | AggKind::LastValue
+                                | AggKind::PercentileCont
+                                | AggKind::PercentileDisc
+                        );
+                    if needs_materialized_input {
                         let mut sort_column_set = BTreeSet::new();
                         let sort_keys = {
                             match agg_call.agg_kind {
-                                AggKind::Min => {
+                                AggKind::Min | AggKind::BoolAnd => {
                                     vec![(OrderType::Ascending, agg_call.inputs[0].index)]
                                 }
-                                AggKind::Max => {
+                                AggKind::Max | AggKind::BoolOr => {
                                     vec![(OrderType::Descending, agg_call.inputs[0].index)]
                                 }
-                                AggKind::StringAgg | AggKind::ArrayAgg => agg_call
+                                AggKind::StringAgg
+                                | AggKind::ArrayAgg
+                                | AggKind::FirstValue
+                                | AggKind::PercentileCont
+                                | AggKind::PercentileDisc => agg_call
                                     .order_by_fields
                                     .iter()
                                     .map(|o| {
@@ -194,12 +219,35 @@ impl LogicalAgg {
                                         (o.direction.to_order(), col_idx)
                                     })
                                     .collect(),
+                                // the state of `last_value` is sorted in the reverse order, so
+                                // that the result is always the first entry
+                                AggKind::LastValue => agg_call
+                                    .order_by_fields
+                                    .iter()
+                                    .map(|o| {
+                                        let col_idx = o.input.index;
+                                        sort_column_set.insert(col_idx);
+                                        let order_type = match o.direction.to_order() {
+                                            OrderType::Ascending => OrderType::Descending,
+                                            OrderType::Descending => OrderType::Ascending,
+                                        };
+                                        (order_type, col_idx)
+                                    })
+                                    .collect(),
+                                AggKind::BitAnd | AggKind::BitOr => vec![],
                                 _ => unreachable!(),
                             }
                         };
 
                         let include_keys = match agg_call.agg_kind {
-                            AggKind::StringAgg | AggKind::ArrayAgg => agg_call
+                            AggKind::StringAgg
+                            | AggKind::ArrayAgg
+                            | AggKind::BitAnd
+                            | AggKind::BitOr
+                            | AggKind::FirstValue
+                            | AggKind::LastValue
+                            | AggKind::PercentileCont
+                            | AggKind::PercentileDisc => agg_call
                                 .inputs
                                 .iter()
                                 .map(|i| i.index)
@@ -216,6 +264,9 @@ impl LogicalAgg {
                 AggKind::Sum | AggKind::Count | AggKind::Avg | AggKind::ApproxCountDistinct => {
                     AggCallState::ResultValueState
                 }
+                AggKind::StddevSamp | AggKind::StddevPop | AggKind::VarSamp | AggKind::VarPop => {
+                    unreachable!("stddev/variance should have been rewritten to sum and count")
+                }
             })
             .collect()
     }
@@ -234,7 +285,7 @@ impl LogicalAgg {
                 .map(|(partial_output_idx, agg_call)| {
                     agg_call.partial_to_total_agg_call(partial_output_idx)
                 })
-                .collect(),
+                .try_collect()?,
             vec![],
             exchange,
         ));
@@ -287,7 +338,7 @@ impl LogicalAgg {
                     .map(|(partial_output_idx, agg_call)| {
                         agg_call.partial_to_total_agg_call(n_local_group_key + partial_output_idx)
                     })
-                    .collect(),
+                    .try_collect()?,
                 self.group_key().to_vec(),
                 exchange,
             ));
@@ -304,7 +355,7 @@ impl LogicalAgg {
                             agg_call
                                 .partial_to_total_agg_call(n_local_group_key + partial_output_idx)
                         })
-                        .collect(),
+                        .try_collect()?,
                     self.group_key().to_vec(),
                     exchange,
                 ),
@@ -375,9 +426,17 @@ impl LogicalAgg {
 
     /// Check if the aggregation result will be affected by order by clause, if any.
     pub(crate) fn is_agg_result_affected_by_order(&self) -> bool {
-        self.agg_calls()
-            .iter()
-            .any(|call| matches!(call.agg_kind, AggKind::StringAgg | AggKind::ArrayAgg))
+        self.agg_calls().iter().any(|call| {
+            matches!(
+                call.agg_kind,
+                AggKind::StringAgg
+                    | AggKind::ArrayAgg
+                    | AggKind::FirstValue
+                    | AggKind::LastValue
+                    | AggKind::PercentileCont
+                    | AggKind::PercentileDisc
+            )
+        })
     }
 
     // Check if the output of the aggregation needs to be sorted and return ordering req by group
@@ -451,7 +510,7 @@ struct LogicalAggBuilder {
     /// the agg calls
     agg_calls: Vec<PlanAggCall>,
     /// the error during the expression rewriting
-    error: Option<ErrorCode>,
+    error: Option<RwError>,
     /// If `is_in_filter_clause` is true, it means that
     /// we are processing filter clause.
     /// This field is needed because input refs in these clauses
@@ -492,7 +551,7 @@ impl LogicalAggBuilder {
     fn rewrite_with_error(&mut self, expr: ExprImpl) -> Result<ExprImpl> {
         let rewritten_expr = self.rewrite_expr(expr);
         if let Some(error) = self.error.take() {
-            return Err(error.into());
+            return Err(error);
         }
         Ok(rewritten_expr)
     }
@@ -560,10 +619,7 @@ impl LogicalAggBuilder {
     /// 3. rewrite it as an `InputRef` to the agg result in select list.
     ///
     /// Note that the rewriter does not traverse into inputs of agg calls.
-    fn try_rewrite_agg_call(
-        &mut self,
-        agg_call: AggCall,
-    ) -> std::result::Result<ExprImpl, ErrorCode> {
+    fn try_rewrite_agg_call(&mut self, agg_call: AggCall) -> Result<ExprImpl> {
         let return_type = agg_call.return_type();
         let (agg_kind, inputs, distinct, mut order_by, filter) = agg_call.decompose();
         match &agg_kind {
@@ -572,7 +628,15 @@ impl LogicalAggBuilder {
             | AggKind::Sum
             | AggKind::Count
             | AggKind::Avg
-            | AggKind::ApproxCountDistinct => {
+            | AggKind::ApproxCountDistinct
+            | AggKind::BoolAnd
+            | AggKind::BoolOr
+            | AggKind::BitAnd
+            | AggKind::BitOr
+            | AggKind::StddevSamp
+            | AggKind::StddevPop
+            | AggKind::VarSamp
+            | AggKind::VarPop => {
                 // this order by is unnecessary.
                 order_by = OrderBy::new(vec![]);
             }
@@ -588,6 +652,13 @@ impl LogicalAggBuilder {
         let filter = filter.rewrite_expr(self);
         self.is_in_filter_clause = false;
 
+        if matches!(
+            agg_kind,
+            AggKind::StddevSamp | AggKind::StddevPop | AggKind::VarSamp | AggKind::VarPop
+        ) {
+            return self.try_rewrite_stddev_var(agg_kind, return_type, inputs, distinct, filter);
+        }
+
         let inputs: Vec<_> = inputs
             .iter()
             .map(|expr| {
@@ -674,6 +745,122 @@ impl LogicalAggBuilder {
     }
 }
 
+impl LogicalAggBuilder {
+    /// Rewrite `stddev_*` and `var_*` with `sum(x)`, `sum(x * x)` and `count(x)`, so that they
+    /// can be computed incrementally in the same way as `avg`:
+    ///
+    /// ```text
+    /// var_pop(x)  = (sum(x * x) - sum(x) * sum(x) / count(x)) / count(x)
+    /// var_samp(x) = (sum(x * x) - sum(x) * sum(x) / count(x)) / (count(x) - 1)
+    /// stddev_pop(x)  = sqrt(var_pop(x))
+    /// stddev_samp(x) = sqrt(var_samp(x))
+    /// ```
+    ///
+    /// The result is NULL when `count(x)` is 0 (or 1 for the sample variants).
+    fn try_rewrite_stddev_var(
+        &mut self,
+        agg_kind: AggKind,
+        return_type: DataType,
+        inputs: Vec<ExprImpl>,
+        distinct: bool,
+        filter: Condition,
+    ) -> Result<ExprImpl> {
+        assert_eq!(inputs.len(), 1);
+
+        // Compute on the return type, which is wide enough to avoid overflowing `x * x`.
+        let input = inputs
+            .into_iter()
+            .exactly_one()
+            .unwrap()
+            .cast_implicit(return_type.clone())?;
+        let squared_input: ExprImpl =
+            FunctionCall::new(ExprType::Multiply, vec![input.clone(), input.clone()])?.into();
+
+        let sum = self.push_unary_agg_call(AggKind::Sum, &input, distinct, &filter)?;
+        let sum_of_squares =
+            self.push_unary_agg_call(AggKind::Sum, &squared_input, distinct, &filter)?;
+        let count = self.push_unary_agg_call(AggKind::Count, &input, distinct, &filter)?;
+
+        let square_of_sum = FunctionCall::new(ExprType::Multiply, vec![sum.clone(), sum])?;
+        let numerator = FunctionCall::new(
+            ExprType::Subtract,
+            vec![
+                sum_of_squares,
+                FunctionCall::new(ExprType::Divide, vec![square_of_sum.into(), count.clone()])?
+                    .into(),
+            ],
+        )?;
+
+        let is_sample = matches!(agg_kind, AggKind::StddevSamp | AggKind::VarSamp);
+        let denominator = if is_sample {
+            FunctionCall::new(
+                ExprType::Subtract,
+                vec![
+                    count.clone(),
+                    Literal::new(Some(ScalarImpl::Int64(1)), DataType::Int64).into(),
+                ],
+            )?
+            .into()
+        } else {
+            count.clone()
+        };
+        let variance = FunctionCall::new(ExprType::Divide, vec![numerator.into(), denominator])?;
+        // Rounding errors of floating-point numbers may lead to a slightly negative variance, which
+        // must be clamped before taking its square root.
+        let mut result: ExprImpl = FunctionCall::new(
+            ExprType::Greatest,
+            vec![
+                variance.into(),
+                ExprImpl::literal_int(0).cast_implicit(return_type.clone())?,
+            ],
+        )?
+        .into();
+        if matches!(agg_kind, AggKind::StddevSamp | AggKind::StddevPop) {
+            result = FunctionCall::new(ExprType::Sqrt, vec![result])?.into();
+        }
+
+        let null_threshold: ExprImpl = Literal::new(
+            Some(ScalarImpl::Int64(if is_sample { 1 } else { 0 })),
+            DataType::Int64,
+        )
+        .into();
+        let is_null = FunctionCall::new(ExprType::LessThanOrEqual, vec![count, null_threshold])?;
+        Ok(FunctionCall::new(
+            ExprType::Case,
+            vec![
+                is_null.into(),
+                Literal::new(None, return_type).into(),
+                result,
+            ],
+        )?
+        .into())
+    }
+
+    /// Push an agg call without order by on `input` and return the reference to its result.
+    fn push_unary_agg_call(
+        &mut self,
+        agg_kind: AggKind,
+        input: &ExprImpl,
+        distinct: bool,
+        filter: &Condition,
+    ) -> Result<ExprImpl> {
+        let index = self.input_proj_builder.add_expr(input).map_err(|err| {
+            ErrorCode::NotImplemented(format!("{err} inside aggregation calls"), None.into())
+        })?;
+        let input = InputRef::new(index, input.return_type());
+        let return_type = AggCall::infer_return_type(&agg_kind, &[input.return_type()])?;
+        self.agg_calls.push(PlanAggCall {
+            agg_kind,
+            return_type: return_type.clone(),
+            inputs: vec![input],
+            distinct,
+            order_by_fields: vec![],
+            filter: filter.clone(),
+        });
+        Ok(InputRef::new(self.group_key.len() + self.agg_calls.len() - 1, return_type).into())
+    }
+}
+
 impl ExprRewriter for LogicalAggBuilder {
     fn rewrite_agg_call(&mut self, agg_call: AggCall) -> ExprImpl {
         let dummy = Literal::new(None, agg_call.return_type()).into();
@@ -714,20 +901,26 @@ impl ExprRewriter for LogicalAggBuilder {
             )
             .into()
         } else {
-            self.error = Some(ErrorCode::InvalidInputSyntax(
-                "column must appear in the GROUP BY clause or be used in an aggregate function"
-                    .into(),
-            ));
+            self.error = Some(
+                ErrorCode::InvalidInputSyntax(
+                    "column must appear in the GROUP BY clause or be used in an aggregate function"
+                        .into(),
+                )
+                .into(),
+            );
             expr
         }
     }
 
     fn rewrite_subquery(&mut self, subquery: crate::expr::Subquery) -> ExprImpl {
         if subquery.is_correlated() {
-            self.error = Some(ErrorCode::NotImplemented(
-                "correlated subquery in HAVING or SELECT with agg".into(),
-                2275.into(),
-            ));
+            self.error = Some(
+                ErrorCode::NotImplemented(
+                    "correlated subquery in HAVING or SELECT with agg".into(),
+                    2275.into(),
+                )
+                .into(),
+            );
         }
         subquery.into()
     }
@@ -16,7 +16,7 @@ use std::fmt;
 
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
-use piestream_common::error::Result;
+use piestream_common::error::{ErrorCode, Result};
 
 use super::{
     generic, ColPrunable, CollectInputRef, LogicalProject, PlanBase, PlanRef, PlanTreeNodeUnary,
@@ -183,6 +183,18 @@ impl ToBatch for LogicalFilter {
 
 impl ToStream for LogicalFilter {
     fn to_stream(&self) -> Result<PlanRef> {
+        if self
+            .predicate()
+            .conjunctions
+            .iter()
+            .any(|e| e.has_volatile_function())
+        {
+            return Err(ErrorCode::NotImplemented(
+                "volatile functions such as random() in streaming queries".to_string(),
+                None.into(),
+            )
+            .into());
+        }
         let new_input = self.input().to_stream()?;
         let new_logical = self.clone_with_input(new_input);
         Ok(StreamFilter::new(new_logical).into())
@@ -941,6 +941,18 @@ impl ToBatch for LogicalJoin {
 
 impl ToStream for LogicalJoin {
     fn to_stream(&self) -> Result<PlanRef> {
+        if self
+            .on()
+            .conjunctions
+            .iter()
+            .any(|e| e.has_volatile_function())
+        {
+            return Err(ErrorCode::NotImplemented(
+                "volatile functions such as random() in streaming queries".to_string(),
+                None.into(),
+            )
+            .into());
+        }
         let predicate = EqJoinPredicate::create(
             self.left().schema().len(),
             self.right().schema().len(),
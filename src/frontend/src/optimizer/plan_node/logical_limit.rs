@@ -14,12 +14,13 @@
 
 use std::fmt;
 
-use piestream_common::error::{ErrorCode, Result, RwError};
+use piestream_common::error::Result;
 
 use super::{
-    gen_filter_and_pushdown, BatchLimit, ColPrunable, PlanBase, PlanRef, PlanTreeNodeUnary,
-    PredicatePushdown, ToBatch, ToStream,
+    gen_filter_and_pushdown, BatchLimit, ColPrunable, LogicalTopN, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, ToBatch, ToStream,
 };
+use crate::optimizer::property::{FieldOrder, Order};
 use crate::utils::{ColIndexMapping, Condition};
 
 /// `LogicalLimit` fetches up to `limit` rows from `offset`
@@ -112,10 +113,17 @@ impl ToBatch for LogicalLimit {
 
 impl ToStream for LogicalLimit {
     fn to_stream(&self) -> Result<PlanRef> {
-        Err(RwError::from(ErrorCode::NotImplemented(
-            "there is no limit stream operator".to_string(),
-            None.into(),
-        )))
+        // There is no dedicated limit stream operator. Instead, we use a Top-N ordered by the
+        // stream key, so that the subset of rows kept is deterministic and can be maintained
+        // correctly when upstream rows are deleted.
+        let order = Order::new(
+            self.input()
+                .logical_pk()
+                .iter()
+                .map(|idx| FieldOrder::ascending(*idx))
+                .collect(),
+        );
+        LogicalTopN::new(self.input(), self.limit, self.offset, false, order).to_stream()
     }
 
     fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
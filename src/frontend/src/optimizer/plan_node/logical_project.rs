@@ -19,7 +19,7 @@ use std::string::String;
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use piestream_common::catalog::{Field, Schema};
-use piestream_common::error::Result;
+use piestream_common::error::{ErrorCode, Result};
 
 use super::{
     gen_filter_and_pushdown, generic, BatchProject, ColPrunable, PlanBase, PlanRef,
@@ -392,6 +392,13 @@ impl ToBatch for LogicalProject {
 
 impl ToStream for LogicalProject {
     fn to_stream_with_dist_required(&self, required_dist: &RequiredDist) -> Result<PlanRef> {
+        if self.exprs().iter().any(|e| e.has_volatile_function()) {
+            return Err(ErrorCode::NotImplemented(
+                "volatile functions such as random() in streaming queries".to_string(),
+                None.into(),
+            )
+            .into());
+        }
         let input_required = if required_dist.satisfies(&RequiredDist::AnyShard) {
             RequiredDist::Any
         } else {
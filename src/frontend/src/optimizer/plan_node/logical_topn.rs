@@ -204,6 +204,12 @@ impl LogicalTopN {
             return gen_single_plan(stream_input);
         }
 
+        // if the limit is unbounded, e.g. `OFFSET` without `LIMIT`, the local phase would keep all
+        // the rows in its cache without reducing the input of the global phase
+        if self.limit() == LIMIT_ALL_COUNT {
+            return gen_single_plan(stream_input);
+        }
+
         match input_dist {
             Distribution::Single | Distribution::SomeShard => gen_single_plan(stream_input),
             Distribution::Broadcast => Err(RwError::from(ErrorCode::NotImplemented(
@@ -411,11 +417,6 @@ impl ToBatch for LogicalTopN {
 
 impl ToStream for LogicalTopN {
     fn to_stream(&self) -> Result<PlanRef> {
-        if self.offset() != 0 && self.limit() == LIMIT_ALL_COUNT {
-            return Err(RwError::from(ErrorCode::InvalidInputSyntax(
-                "OFFSET without LIMIT in streaming mode".to_string(),
-            )));
-        }
         if self.limit() == 0 {
             return Err(RwError::from(ErrorCode::InvalidInputSyntax(
                 "LIMIT 0 in streaming mode".to_string(),
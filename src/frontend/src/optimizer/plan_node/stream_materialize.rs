@@ -22,6 +22,7 @@ use piestream_common::catalog::{ColumnDesc, TableId};
 use piestream_common::error::ErrorCode::InternalError;
 use piestream_common::error::Result;
 use piestream_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use piestream_pb::stream_plan::HandleConflictBehavior;
 
 use super::{PlanRef, PlanTreeNodeUnary, StreamNode};
 use crate::catalog::column_catalog::ColumnCatalog;
@@ -43,6 +44,9 @@ pub struct StreamMaterialize {
     /// Child of Materialize plan
     input: PlanRef,
     table: TableCatalog,
+    /// How to handle the rows whose pk conflicts with the existing rows. Only tables with a
+    /// user-defined primary key need to check the conflicts.
+    handle_pk_conflict_behavior: HandleConflictBehavior,
 }
 
 impl StreamMaterialize {
@@ -67,7 +71,18 @@ impl StreamMaterialize {
     #[must_use]
     pub fn new(input: PlanRef, table: TableCatalog) -> Self {
         let base = Self::derive_plan_base(&input).unwrap();
-        Self { base, input, table }
+        Self {
+            base,
+            input,
+            table,
+            handle_pk_conflict_behavior: HandleConflictBehavior::NoCheck,
+        }
+    }
+
+    #[must_use]
+    pub fn with_handle_pk_conflict_behavior(mut self, behavior: HandleConflictBehavior) -> Self {
+        self.handle_pk_conflict_behavior = behavior;
+        self
     }
 
     /// Create a materialize node.
@@ -187,7 +202,12 @@ impl StreamMaterialize {
             definition,
         };
 
-        Ok(Self { base, input, table })
+        Ok(Self {
+            base,
+            input,
+            table,
+            handle_pk_conflict_behavior: HandleConflictBehavior::NoCheck,
+        })
     }
 
     /// Get a reference to the stream materialize's table.
@@ -241,7 +261,8 @@ impl PlanTreeNodeUnary for StreamMaterialize {
     }
 
     fn clone_with_input(&self, input: PlanRef) -> Self {
-        let new = Self::new(input, self.table().clone());
+        let new = Self::new(input, self.table().clone())
+            .with_handle_pk_conflict_behavior(self.handle_pk_conflict_behavior);
         assert_eq!(new.plan_base().schema, self.plan_base().schema);
         assert_eq!(new.plan_base().logical_pk, self.plan_base().logical_pk);
         new
@@ -265,6 +286,7 @@ impl StreamNode for StreamMaterialize {
                 .map(FieldOrder::to_protobuf)
                 .collect(),
             table: Some(self.table().to_internal_table_prost()),
+            handle_pk_conflict_behavior: self.handle_pk_conflict_behavior as i32,
         })
     }
 }
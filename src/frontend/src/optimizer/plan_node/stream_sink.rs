@@ -14,13 +14,17 @@
 
 use std::fmt;
 
+use piestream_common::catalog::Field;
 use piestream_common::error::Result;
+use piestream_common::types::DataType;
+use piestream_common::util::sort_util::OrderType;
 use piestream_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 
+use super::utils::TableCatalogBuilder;
 use super::{PlanBase, PlanRef, StreamNode};
 use crate::optimizer::plan_node::PlanTreeNodeUnary;
 use crate::stream_fragmenter::BuildFragmentGraphState;
-use crate::WithOptions;
+use crate::{TableCatalog, WithOptions};
 
 /// [`StreamSink`] represents a table/connector sink at the very end of the graph.
 #[derive(Debug, Clone)]
@@ -56,6 +60,33 @@ impl StreamSink {
             properties,
         }
     }
+
+    /// The internal table of sink stores the transaction pre-committed by each sink actor under
+    /// the first vnode owned by the actor, and its schema is irrelevant to input schema.
+    pub fn infer_internal_table_catalog(&self) -> TableCatalog {
+        let mut builder =
+            TableCatalogBuilder::new(self.ctx().inner().with_options.internal_table_subset());
+
+        let field = |data_type: DataType, name: &str| Field {
+            data_type,
+            name: name.to_string(),
+            sub_fields: vec![],
+            type_name: "".to_string(),
+        };
+
+        // Each row is a record of the transaction pre-committed under the vnode.
+        let vnode_col_idx = builder.add_column(&field(DataType::Int16, "vnode"));
+        let seq_col_idx = builder.add_column(&field(DataType::Int64, "seq"));
+        builder.add_column(&field(DataType::Varchar, "transactional_id"));
+        builder.add_column(&field(DataType::Int64, "pre_committed_epoch"));
+        builder.add_column(&field(DataType::Varchar, "record_key"));
+        builder.add_column(&field(DataType::Varchar, "record_payload"));
+        builder.add_order_column(vnode_col_idx, OrderType::Ascending);
+        builder.add_order_column(seq_col_idx, OrderType::Ascending);
+        builder.set_vnode_col_idx(vnode_col_idx);
+
+        builder.build(vec![vnode_col_idx])
+    }
 }
 
 impl PlanTreeNodeUnary for StreamSink {
@@ -79,7 +110,7 @@ impl fmt::Display for StreamSink {
 }
 
 impl StreamNode for StreamSink {
-    fn to_stream_prost_body(&self, _state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
         use piestream_pb::stream_plan::*;
 
         let input = self.input.clone();
@@ -90,6 +121,11 @@ impl StreamNode for StreamSink {
             table_id: table_desc.table_id.table_id(),
             column_ids: vec![], // TODO(nanderstabel): fix empty Vector
             properties: self.properties.inner().clone(),
+            table: Some(
+                self.infer_internal_table_catalog()
+                    .with_id(state.gen_table_id_wrapped())
+                    .to_internal_table_prost(),
+            ),
         })
     }
 }
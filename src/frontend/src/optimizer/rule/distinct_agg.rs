@@ -268,7 +268,19 @@ impl DistinctAggRule {
                     | AggKind::Avg
                     | AggKind::StringAgg
                     | AggKind::ApproxCountDistinct
-                    | AggKind::ArrayAgg => (),
+                    | AggKind::ArrayAgg
+                    | AggKind::BoolAnd
+                    | AggKind::BoolOr
+                    | AggKind::BitAnd
+                    | AggKind::BitOr
+                    | AggKind::StddevSamp
+                    | AggKind::StddevPop
+                    | AggKind::VarSamp
+                    | AggKind::VarPop
+                    | AggKind::FirstValue
+                    | AggKind::LastValue
+                    | AggKind::PercentileCont
+                    | AggKind::PercentileDisc => (),
                     AggKind::Count => {
                         indices_of_count.push(i);
                         agg_call.agg_kind = AggKind::Sum;
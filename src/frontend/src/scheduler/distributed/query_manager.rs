@@ -103,13 +103,18 @@ impl QueryManager {
         &self,
         context: ExecutionContextRef,
         query: Query,
+        as_of_epoch: Option<u64>,
     ) -> SchedulerResult<DistributedQueryStream> {
         let query_id = query.query_id().clone();
-        let epoch = self
-            .hummock_snapshot_manager
-            .acquire(&query_id)
-            .await?
-            .committed_epoch;
+        let epoch = match as_of_epoch {
+            Some(epoch) => {
+                self.hummock_snapshot_manager
+                    .acquire_specific(&query_id, epoch)
+                    .await?
+            }
+            None => self.hummock_snapshot_manager.acquire(&query_id).await?,
+        }
+        .committed_epoch;
         let query_id = query.query_id.clone();
         let query_execution = Arc::new(QueryExecution::new(
             context.clone(),
@@ -26,13 +26,12 @@ use tracing::error;
 
 use crate::meta_client::FrontendMetaClient;
 use crate::scheduler::plan_fragmenter::QueryId;
-use crate::scheduler::{SchedulerError, SchedulerResult};
+use crate::scheduler::{PointGetCacheRef, SchedulerError, SchedulerResult};
 
 const MAX_WAIT_EPOCH_REQUEST_NUM: usize = 4096;
 // const UNPIN_INTERVAL_SECS: u64 = 10;
 const UNPIN_INTERVAL_SECS: u64 = 120;
 
-
 /// Cache of hummock snapshot in meta.
 pub struct HummockSnapshotManager {
     /// Send epoch-related operations to `HummockSnapshotManagerCore` for async batch handling.
@@ -47,6 +46,10 @@ pub struct HummockSnapshotManager {
     /// `current_epoch` is always in the shared buffer, so it will never be gc before the data
     /// of `committed_epoch`.
     max_current_epoch: Arc<AtomicU64>,
+
+    /// Results of point lookups at `max_committed_epoch`, invalidated once a new epoch is
+    /// committed.
+    point_get_cache: PointGetCacheRef,
 }
 pub type HummockSnapshotManagerRef = Arc<HummockSnapshotManager>;
 
@@ -56,6 +59,12 @@ enum EpochOperation {
         query_id: QueryId,
         sender: Callback<SchedulerResult<HummockSnapshot>>,
     },
+    /// Request a specific committed epoch for a time-travel query.
+    RequestSpecificEpoch {
+        query_id: QueryId,
+        epoch: u64,
+        sender: Callback<SchedulerResult<HummockSnapshot>>,
+    },
     ReleaseEpoch {
         query_id: QueryId,
         epoch: u64,
@@ -78,6 +87,7 @@ impl HummockSnapshotManager {
             );
             let mut unpin_batches = vec![];
             let mut pin_batches = vec![];
+            let mut specific_pin_batches = vec![];
             let mut unpin_interval =
                 tokio::time::interval(Duration::from_secs(UNPIN_INTERVAL_SECS));
             let mut last_unpin_time = Instant::now();
@@ -92,6 +102,13 @@ impl HummockSnapshotManager {
                     Some(EpochOperation::RequestEpoch { query_id, sender }) => {
                         pin_batches.push((query_id, sender));
                     }
+                    Some(EpochOperation::RequestSpecificEpoch {
+                        query_id,
+                        epoch,
+                        sender,
+                    }) => {
+                        specific_pin_batches.push((query_id, epoch, sender));
+                    }
                     Some(EpochOperation::ReleaseEpoch { query_id, epoch }) => {
                         unpin_batches.push((query_id, epoch));
                     }
@@ -103,6 +120,13 @@ impl HummockSnapshotManager {
                         EpochOperation::RequestEpoch { query_id, sender } => {
                             pin_batches.push((query_id, sender));
                         }
+                        EpochOperation::RequestSpecificEpoch {
+                            query_id,
+                            epoch,
+                            sender,
+                        } => {
+                            specific_pin_batches.push((query_id, epoch, sender));
+                        }
                         EpochOperation::ReleaseEpoch { query_id, epoch } => {
                             unpin_batches.push((query_id, epoch));
                         }
@@ -112,6 +136,11 @@ impl HummockSnapshotManager {
                 if !unpin_batches.is_empty() {
                     manager.release_epoch(&mut unpin_batches);
                 }
+                if !specific_pin_batches.is_empty() {
+                    manager
+                        .pin_specific_epoch_for_queries(&mut specific_pin_batches)
+                        .await;
+                }
 
                 let need_unpin = last_unpin_time.elapsed().as_secs() >= UNPIN_INTERVAL_SECS;
                 if !pin_batches.is_empty() || need_unpin {
@@ -131,6 +160,7 @@ impl HummockSnapshotManager {
             sender,
             max_committed_epoch,
             max_current_epoch,
+            point_get_cache: Default::default(),
         }
     }
 
@@ -152,11 +182,46 @@ impl HummockSnapshotManager {
         })
     }
 
+    /// Acquire the snapshot of a specific committed `epoch` for a time-travel query. The epoch is
+    /// pinned in meta until it's released by [`Self::release`].
+    pub async fn acquire_specific(
+        &self,
+        query_id: &QueryId,
+        epoch: u64,
+    ) -> SchedulerResult<HummockSnapshot> {
+        let (sender, rc) = once_channel();
+        let msg = EpochOperation::RequestSpecificEpoch {
+            query_id: query_id.clone(),
+            epoch,
+            sender,
+        };
+        self.sender.send(msg).await.map_err(|_| {
+            SchedulerError::Internal(anyhow!(
+                "Failed to get epoch {} for query: {:?}",
+                epoch,
+                query_id
+            ))
+        })?;
+        rc.await.unwrap_or_else(|e| {
+            Err(SchedulerError::Internal(anyhow!(
+                "Failed to get epoch {} for query: {:?}, the rpc thread may panic: {:?}",
+                epoch,
+                query_id,
+                e
+            )))
+        })
+    }
+
     pub fn update_epoch(&self, epoch: HummockSnapshot) {
         self.max_committed_epoch
             .fetch_max(epoch.committed_epoch, Ordering::Relaxed);
         self.max_current_epoch
             .fetch_max(epoch.current_epoch, Ordering::Relaxed);
+        self.point_get_cache.invalidate(epoch.committed_epoch);
+    }
+
+    pub fn point_get_cache(&self) -> &PointGetCacheRef {
+        &self.point_get_cache
     }
 
     pub async fn release(&self, epoch: u64, query_id: &QueryId) {
@@ -267,6 +332,46 @@ impl HummockSnapshotManagerCore {
         }
     }
 
+    /// Pin the specific epochs in meta for time-travel queries. The queries are registered before
+    /// pinning, so that `unpin_snapshot_before` never unpins the epochs while they are running.
+    async fn pin_specific_epoch_for_queries(
+        &mut self,
+        batches: &mut Vec<(QueryId, u64, Callback<SchedulerResult<HummockSnapshot>>)>,
+    ) {
+        for (query_id, epoch, cb) in batches.drain(..) {
+            let max_committed_epoch = self.max_committed_epoch.load(Ordering::Relaxed);
+            if epoch > max_committed_epoch {
+                let _ = cb.send(Err(SchedulerError::Internal(anyhow!(
+                    "epoch {} is not committed yet, max committed epoch is {}",
+                    epoch,
+                    max_committed_epoch
+                ))));
+                continue;
+            }
+            self.epoch_to_query_ids
+                .entry(epoch)
+                .or_default()
+                .insert(query_id.clone());
+            match self.meta_client.pin_specific_snapshot(epoch).await {
+                Ok(_) => {
+                    let _ = cb.send(Ok(HummockSnapshot {
+                        committed_epoch: epoch,
+                        current_epoch: epoch,
+                    }));
+                }
+                Err(e) => {
+                    self.release_epoch(&mut vec![(query_id.clone(), epoch)]);
+                    let _ = cb.send(Err(SchedulerError::Internal(anyhow!(
+                        "Failed to pin epoch {} for query: {:?} because of RPC Error: {:?}",
+                        epoch,
+                        query_id,
+                        e
+                    ))));
+                }
+            }
+        }
+    }
+
     pub fn release_epoch(&mut self, queries: &mut Vec<(QueryId, u64)>) {
         for (query_id, epoch) in queries.drain(..) {
             let query_ids = self.epoch_to_query_ids.get_mut(&epoch);
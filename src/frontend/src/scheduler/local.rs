@@ -38,6 +38,7 @@ use tracing::debug;
 use uuid::Uuid;
 
 use super::plan_fragmenter::{PartitionInfo, QueryStageRef};
+use crate::handler::util::to_pg_error;
 use crate::optimizer::plan_node::PlanNodeType;
 use crate::scheduler::plan_fragmenter::{ExecutionPlanNode, Query, StageId};
 use crate::scheduler::task_context::FrontendBatchTaskContext;
@@ -48,6 +49,12 @@ pub struct LocalQueryStream {
     data_stream: BoxedDataChunkStream,
 }
 
+impl LocalQueryStream {
+    pub(super) fn new(data_stream: BoxedDataChunkStream) -> Self {
+        Self { data_stream }
+    }
+}
+
 impl Stream for LocalQueryStream {
     type Item = Result<DataChunk, BoxedError>;
 
@@ -57,7 +64,7 @@ impl Stream for LocalQueryStream {
             Poll::Ready(chunk) => match chunk {
                 Some(chunk_result) => match chunk_result {
                     Ok(chunk) => Poll::Ready(Some(Ok(chunk))),
-                    Err(err) => Poll::Ready(Some(Err(Box::new(err)))),
+                    Err(err) => Poll::Ready(Some(Err(to_pg_error(err)))),
                 },
                 None => Poll::Ready(None),
             },
@@ -30,6 +30,8 @@ pub mod plan_fragmenter;
 pub use plan_fragmenter::BatchPlanFragmenter;
 mod local;
 pub use local::*;
+mod point_get;
+pub use point_get::*;
 
 use crate::scheduler::task_context::FrontendBatchTaskContext;
 
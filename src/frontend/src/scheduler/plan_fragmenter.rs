@@ -525,7 +525,7 @@ fn vnode_mapping_to_owner_mapping(vnode_mapping: VnodeMapping) -> HashMap<Parall
     m.into_iter().map(|(k, v)| (k, v.finish())).collect()
 }
 
-fn bitmap_with_single_vnode(vnode: usize, num_vnodes: usize) -> Bitmap {
+pub(super) fn bitmap_with_single_vnode(vnode: usize, num_vnodes: usize) -> Bitmap {
     let mut bitmap = BitmapBuilder::zeroed(num_vnodes);
     bitmap.set(vnode as usize, true);
     bitmap.finish()
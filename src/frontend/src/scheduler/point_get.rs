@@ -0,0 +1,367 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fast path for point lookups on the primary key of a table, e.g., `SELECT * FROM mv WHERE pk
+//! = ?` issued repeatedly by a serving layer. Such queries skip the plan fragmenter and the
+//! scheduler, and are sent directly to the worker owning the vnode of the key, where the lookup is
+//! served by `StorageTable::get_row`.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use parking_lot::Mutex;
+use piestream_batch::executor::ExecutorBuilder;
+use piestream_batch::task::TaskId;
+use piestream_common::array::DataChunk;
+use piestream_common::error::RwError;
+use piestream_common::types::VirtualNode;
+use piestream_common::util::scan_range::full_range;
+use piestream_pb::batch_plan::exchange_info::DistributionMode;
+use piestream_pb::batch_plan::exchange_source::LocalExecutePlan::Plan;
+use piestream_pb::batch_plan::plan_node::NodeBody;
+use piestream_pb::batch_plan::{
+    ExchangeInfo, ExchangeNode, ExchangeSource, LocalExecutePlan, PlanFragment,
+    PlanNode as PlanNodeProst, TaskId as ProstTaskId, TaskOutputId,
+};
+use piestream_pb::common::WorkerNode;
+use piestream_pb::plan_common::Field as FieldProst;
+use tracing::debug;
+use uuid::Uuid;
+
+use super::plan_fragmenter::{bitmap_with_single_vnode, QueryId};
+use crate::optimizer::plan_node::{BatchSeqScan, PlanNodeType, ToBatchProst};
+use crate::scheduler::task_context::FrontendBatchTaskContext;
+use crate::scheduler::{LocalQueryStream, SchedulerResult};
+use crate::session::{AuthContext, FrontendEnv};
+use crate::PlanRef;
+
+/// Max number of results kept in [`PointGetCache`] for a single epoch.
+const POINT_GET_CACHE_CAPACITY: usize = 4096;
+
+pub struct PointGetExecution {
+    query_id: QueryId,
+    /// The `RowSeqScan` node with a single point scan range.
+    scan_node: NodeBody,
+    /// Schema of the scan output.
+    schema: Vec<FieldProst>,
+    /// The worker owning the vnode of the key.
+    worker_node: WorkerNode,
+    front_env: FrontendEnv,
+}
+
+/// Returns the scan and the vnode of the key if the local batch `plan` is a point lookup on the
+/// full primary key, i.e., a `BatchSeqScan` with a single point scan range right below the root
+/// exchange.
+fn match_point_get(plan: &PlanRef) -> Option<(BatchSeqScan, VirtualNode)> {
+    if plan.node_type() != PlanNodeType::BatchExchange {
+        return None;
+    }
+    let input = plan.inputs();
+    let scan = input.first()?.as_batch_seq_scan()?;
+    if scan.logical().is_sys_table() {
+        return None;
+    }
+
+    let [scan_range] = scan.scan_ranges() else {
+        return None;
+    };
+    let table_desc = scan.logical().table_desc();
+    let pk_indices = table_desc.order_column_indices();
+    if scan_range.eq_conds.len() != pk_indices.len() || scan_range.range != full_range() {
+        return None;
+    }
+    let vnode = scan_range.try_compute_vnode(&table_desc.distribution_key, &pk_indices)?;
+    Some((scan.clone(), vnode))
+}
+
+impl PointGetExecution {
+    /// Returns `None` if the local batch `plan` is not a point lookup on the full primary key, or
+    /// the worker owning the key is unknown.
+    pub fn try_new(plan: &PlanRef, front_env: &FrontendEnv) -> SchedulerResult<Option<Self>> {
+        let Some((scan, vnode)) = match_point_get(plan) else {
+            return Ok(None);
+        };
+        let table_desc = scan.logical().table_desc();
+
+        let Some(vnode_mapping) = front_env
+            .catalog_reader()
+            .read_guard()
+            .get_table_by_id(&table_desc.table_id)
+            .ok()
+            .and_then(|table| {
+                front_env
+                    .worker_node_manager()
+                    .get_fragment_mapping(&table.fragment_id)
+            })
+        else {
+            return Ok(None);
+        };
+        let parallel_unit_id = vnode_mapping[vnode as usize];
+        let worker_node = front_env
+            .worker_node_manager()
+            .get_workers_by_parallel_unit_ids(&[parallel_unit_id])?
+            .remove(0);
+
+        let mut scan_node = scan.to_batch_prost_body();
+        match &mut scan_node {
+            NodeBody::RowSeqScan(scan_node) => {
+                scan_node.vnode_bitmap = Some(
+                    bitmap_with_single_vnode(vnode as usize, vnode_mapping.len()).to_protobuf(),
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        Ok(Some(Self {
+            query_id: QueryId::default(),
+            scan_node,
+            schema: plan.schema().to_prost(),
+            worker_node,
+            front_env: front_env.clone(),
+        }))
+    }
+
+    pub fn query_id(&self) -> &QueryId {
+        &self.query_id
+    }
+
+    /// Build an exchange node fetching the result of the lookup from the owning worker.
+    fn create_plan_node(&self, epoch: u64) -> PlanNodeProst {
+        let scan_fragment = PlanFragment {
+            root: Some(PlanNodeProst {
+                children: vec![],
+                identity: Uuid::new_v4().to_string(),
+                node_body: Some(self.scan_node.clone()),
+            }),
+            exchange_info: Some(ExchangeInfo {
+                mode: DistributionMode::Single as i32,
+                ..Default::default()
+            }),
+        };
+        let exchange_source = ExchangeSource {
+            task_output_id: Some(TaskOutputId {
+                task_id: Some(ProstTaskId {
+                    task_id: 0,
+                    stage_id: 1,
+                    query_id: self.query_id.id.clone(),
+                }),
+                output_id: 0,
+            }),
+            host: Some(self.worker_node.host.as_ref().unwrap().clone()),
+            local_execute_plan: Some(Plan(LocalExecutePlan {
+                plan: Some(scan_fragment),
+                epoch,
+            })),
+        };
+
+        PlanNodeProst {
+            children: vec![],
+            identity: Uuid::new_v4().to_string(),
+            node_body: Some(NodeBody::Exchange(ExchangeNode {
+                sources: vec![exchange_source],
+                input_schema: self.schema.clone(),
+            })),
+        }
+    }
+
+    /// Execute the lookup at `epoch`. If `cache` is given, the result is put into the cache with
+    /// the key once the lookup finishes.
+    #[try_stream(ok = DataChunk, error = RwError)]
+    async fn run_inner(
+        self,
+        epoch: u64,
+        auth_context: Arc<AuthContext>,
+        cache: Option<(PointGetCacheRef, String)>,
+    ) {
+        debug!("Starting to run point get: {:?}", self.query_id);
+
+        let context = FrontendBatchTaskContext::new(self.front_env.clone(), auth_context);
+        let task_id = TaskId {
+            query_id: self.query_id.id.clone(),
+            stage_id: 0,
+            task_id: 0,
+        };
+
+        let plan_node = self.create_plan_node(epoch);
+        let executor = ExecutorBuilder::new(&plan_node, &task_id, context, epoch);
+        let executor = executor.build().await?;
+
+        let mut chunks = vec![];
+        #[for_await]
+        for chunk in executor.execute() {
+            let chunk = chunk?;
+            if cache.is_some() {
+                chunks.push(chunk.clone());
+            }
+            yield chunk;
+        }
+
+        if let Some((cache, key)) = cache {
+            cache.insert(key, epoch, chunks);
+        }
+    }
+
+    pub fn stream_rows(
+        self,
+        epoch: u64,
+        auth_context: Arc<AuthContext>,
+        cache: Option<(PointGetCacheRef, String)>,
+    ) -> LocalQueryStream {
+        LocalQueryStream::new(Box::pin(self.run_inner(epoch, auth_context, cache)))
+    }
+}
+
+/// Results of point lookups keyed by the statement with parameters filled, which are only valid
+/// for a single committed epoch. All the results are invalidated once a new epoch is committed.
+#[derive(Default)]
+pub struct PointGetCache {
+    inner: Mutex<PointGetCacheInner>,
+}
+
+#[derive(Default)]
+struct PointGetCacheInner {
+    epoch: u64,
+    results: HashMap<String, Vec<DataChunk>>,
+}
+
+pub type PointGetCacheRef = Arc<PointGetCache>;
+
+impl PointGetCache {
+    /// Get the cached result of `key` at `epoch`.
+    pub fn get(&self, key: &str, epoch: u64) -> Option<LocalQueryStream> {
+        let inner = self.inner.lock();
+        if inner.epoch != epoch {
+            return None;
+        }
+        let chunks = inner.results.get(key)?.clone();
+        Some(LocalQueryStream::new(
+            futures::stream::iter(chunks.into_iter().map(Ok)).boxed(),
+        ))
+    }
+
+    fn insert(&self, key: String, epoch: u64, chunks: Vec<DataChunk>) {
+        let mut inner = self.inner.lock();
+        if epoch > inner.epoch {
+            inner.epoch = epoch;
+            inner.results.clear();
+        }
+        if epoch == inner.epoch && inner.results.len() < POINT_GET_CACHE_CAPACITY {
+            inner.results.insert(key, chunks);
+        }
+    }
+
+    /// Invalidate all results cached before the newly committed `epoch`.
+    pub fn invalidate(&self, epoch: u64) {
+        let mut inner = self.inner.lock();
+        if epoch > inner.epoch {
+            inner.epoch = epoch;
+            inner.results.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::DataChunkTestExt;
+    use piestream_sqlparser::parser::Parser;
+
+    use super::*;
+    use crate::handler::query::gen_batch_query_plan;
+    use crate::session::OptimizerContext;
+    use crate::test_utils::LocalFrontend;
+    use crate::WithOptions;
+
+    /// Returns whether the local batch plan of `sql` takes the point-get fast path.
+    fn is_point_get(frontend: &LocalFrontend, sql: &str) -> bool {
+        let session = frontend.session_ref();
+        let stmt = Parser::parse_sql(sql).unwrap().remove(0);
+        let context =
+            OptimizerContext::new(session.clone(), Arc::from(sql), WithOptions::default());
+        let (plan, ..) = gen_batch_query_plan(&session, context.into(), stmt).unwrap();
+        match_point_get(&plan).is_some()
+    }
+
+    #[tokio::test]
+    async fn test_match_point_get() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("SET QUERY_MODE TO local").await.unwrap();
+        frontend
+            .run_sql("create table t (k int primary key, v int)")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table t2 (k1 int, k2 int, v int, primary key (k1, k2))")
+            .await
+            .unwrap();
+
+        // Equality on the full primary key.
+        assert!(is_point_get(&frontend, "select * from t where k = 1"));
+        assert!(is_point_get(
+            &frontend,
+            "select * from t2 where k1 = 1 and k2 = 2"
+        ));
+        // Pruned columns are still scanned by the point lookup.
+        assert!(is_point_get(&frontend, "select v from t where k = 1"));
+
+        // Equality on a prefix of the primary key.
+        assert!(!is_point_get(&frontend, "select * from t2 where k1 = 1"));
+        // Range on the primary key.
+        assert!(!is_point_get(&frontend, "select * from t where k > 1"));
+        assert!(!is_point_get(
+            &frontend,
+            "select * from t where k = 1 or k = 2"
+        ));
+        // Extra predicates are evaluated by a filter above the scan.
+        assert!(!is_point_get(
+            &frontend,
+            "select * from t where k = 1 and v = 2"
+        ));
+        // Expressions are evaluated by a projection above the scan.
+        assert!(!is_point_get(&frontend, "select v + 1 from t where k = 1"));
+        // No predicate on the primary key.
+        assert!(!is_point_get(&frontend, "select * from t where v = 1"));
+        assert!(!is_point_get(&frontend, "select * from t"));
+    }
+
+    async fn collect(stream: LocalQueryStream) -> Vec<DataChunk> {
+        stream.map(|chunk| chunk.unwrap()).collect().await
+    }
+
+    #[tokio::test]
+    async fn test_point_get_cache() {
+        let cache = PointGetCache::default();
+        let chunk = DataChunk::from_pretty(
+            "i i
+             1 2",
+        );
+
+        cache.insert("q1".to_string(), 1, vec![chunk.clone()]);
+        assert_eq!(
+            collect(cache.get("q1", 1).unwrap()).await,
+            vec![chunk.clone()]
+        );
+        assert!(cache.get("q2", 1).is_none());
+        assert!(cache.get("q1", 2).is_none());
+
+        // Results of a stale epoch are not cached.
+        cache.invalidate(2);
+        assert!(cache.get("q1", 1).is_none());
+        cache.insert("q1".to_string(), 1, vec![chunk.clone()]);
+        assert!(cache.get("q1", 2).is_none());
+
+        cache.insert("q1".to_string(), 2, vec![chunk.clone()]);
+        assert_eq!(collect(cache.get("q1", 2).unwrap()).await, vec![chunk]);
+    }
+}
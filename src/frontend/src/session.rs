@@ -52,7 +52,7 @@ use crate::catalog::catalog_service::{CatalogReader, CatalogWriter, CatalogWrite
 use crate::catalog::root_catalog::Catalog;
 use crate::expr::CorrelatedId;
 use crate::handler::handle;
-use crate::handler::util::to_pg_field;
+use crate::handler::util::{to_pg_error, to_pg_field};
 use crate::meta_client::{FrontendMetaClient, FrontendMetaClientImpl};
 use crate::monitor::FrontendMetrics;
 use crate::observer::observer_manager::FrontendObserverNode;
@@ -659,7 +659,7 @@ impl Session<PgResponseStream> for SessionImpl {
         let stmt = stmts.swap_remove(0);
         let rsp = handle(self, stmt, sql, format).await.map_err(|e| {
             tracing::error!("failed to handle sql:\n{}:\n{}", sql, e);
-            e
+            to_pg_error(e)
         })?;
         Ok(rsp)
     }
@@ -697,6 +697,13 @@ impl Session<PgResponseStream> for SessionImpl {
                         PgFieldDescriptor::new("Type".to_owned(), TypeOid::Varchar),
                     ]
                 }
+                ShowObject::Jobs => {
+                    vec![
+                        PgFieldDescriptor::new("Id".to_owned(), TypeOid::Varchar),
+                        PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
+                        PgFieldDescriptor::new("Progress".to_owned(), TypeOid::Varchar),
+                    ]
+                }
                 _ => {
                     vec![PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar)]
                 }
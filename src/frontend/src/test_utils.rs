@@ -32,6 +32,7 @@ use piestream_pb::catalog::{
     Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
     Source as ProstSource, Table as ProstTable,
 };
+use piestream_pb::ddl_service::DdlProgress;
 use piestream_pb::hummock::HummockSnapshot;
 use piestream_pb::meta::list_table_fragments_response::TableFragmentInfo;
 use piestream_pb::stream_plan::StreamFragmentGraph;
@@ -213,6 +214,7 @@ impl CatalogWriter for MockCatalogWriter {
         &self,
         mut table: ProstTable,
         _graph: StreamFragmentGraph,
+        _background: bool,
     ) -> Result<()> {
         table.id = self.gen_id();
         self.catalog.write().create_table(&table);
@@ -229,7 +231,7 @@ impl CatalogWriter for MockCatalogWriter {
         let source_id = self.create_source_inner(source)?;
         table.optional_associated_source_id =
             Some(OptionalAssociatedSourceId::AssociatedSourceId(source_id));
-        self.create_materialized_view(table, graph).await?;
+        self.create_materialized_view(table, graph, false).await?;
         Ok(())
     }
 
@@ -628,6 +630,29 @@ impl FrontendMetaClient for MockFrontendMetaClient {
     async fn unpin_snapshot_before(&self, _epoch: u64) -> RpcResult<()> {
         Ok(())
     }
+
+    async fn pin_specific_snapshot(&self, _epoch: u64) -> RpcResult<HummockSnapshot> {
+        Ok(HummockSnapshot {
+            committed_epoch: 0,
+            current_epoch: 0,
+        })
+    }
+
+    async fn alter_parallelism(
+        &self,
+        _table_id: u32,
+        _parallelism: Option<u32>,
+    ) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn get_ddl_progress(&self) -> RpcResult<Vec<DdlProgress>> {
+        Ok(vec![])
+    }
+
+    async fn cancel_creating_jobs(&self, _job_ids: Vec<u32>) -> RpcResult<Vec<u32>> {
+        Ok(vec![])
+    }
 }
 
 #[cfg(test)]
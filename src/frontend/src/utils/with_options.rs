@@ -18,6 +18,7 @@ use std::num::NonZeroU32;
 
 use itertools::Itertools;
 use piestream_common::error::{ErrorCode, RwError};
+use piestream_pb::stream_plan::HandleConflictBehavior;
 use piestream_sqlparser::ast::{
     CreateSinkStatement, CreateSourceStatement, SqlOption, Statement, Value,
 };
@@ -25,11 +26,18 @@ use piestream_sqlparser::ast::{
 use crate::catalog::source_catalog::KAFKA_CONNECTOR;
 
 mod options {
-    use piestream_common::catalog::hummock::PROPERTIES_RETENTION_SECOND_KEY;
+    use piestream_common::catalog::hummock::{
+        PROPERTIES_BLOB_VALUE_THRESHOLD_KEY, PROPERTIES_RETENTION_SECOND_KEY,
+        PROPERTIES_TIME_TRAVEL_RETENTION_SECOND_KEY,
+    };
 
     pub const APPEND_ONLY: &str = "appendonly";
+    pub const BACKGROUND: &str = "background";
+    pub const BLOB_VALUE_THRESHOLD: &str = PROPERTIES_BLOB_VALUE_THRESHOLD_KEY;
     pub const CONNECTOR: &str = "connector";
+    pub const PK_CONFLICT: &str = "pk_conflict";
     pub const RETENTION_SECONDS: &str = PROPERTIES_RETENTION_SECOND_KEY;
+    pub const TIME_TRAVEL_RETENTION_SECONDS: &str = PROPERTIES_TIME_TRAVEL_RETENTION_SECOND_KEY;
 }
 
 /// Options or properties extracted from the `WITH` clause of DDLs.
@@ -69,6 +77,39 @@ impl WithOptions {
             .and_then(|s| s.parse().ok())
     }
 
+    /// Parse the time-travel retention seconds from the options.
+    pub fn time_travel_retention_seconds(&self) -> Option<NonZeroU32> {
+        self.inner
+            .get(options::TIME_TRAVEL_RETENTION_SECONDS)
+            .and_then(|s| s.parse().ok())
+    }
+
+    /// Whether the streaming job should be created in background.
+    pub fn background(&self) -> bool {
+        self.inner
+            .get(options::BACKGROUND)
+            .map(|s| s.eq_ignore_ascii_case("true"))
+            .unwrap_or(false)
+    }
+
+    /// Parse how to handle the rows conflicting on the primary key of a table from the options.
+    /// Defaults to overwriting the existing row.
+    pub fn pk_conflict_behavior(&self) -> Result<HandleConflictBehavior, RwError> {
+        match self.inner.get(options::PK_CONFLICT) {
+            None => Ok(HandleConflictBehavior::Overwrite),
+            Some(val) if val.eq_ignore_ascii_case("overwrite") => {
+                Ok(HandleConflictBehavior::Overwrite)
+            }
+            Some(val) if val.eq_ignore_ascii_case("ignore") => Ok(HandleConflictBehavior::Ignore),
+            Some(val) => Err(ErrorCode::InvalidParameterValue(format!(
+                "invalid {}: {}, expect \"overwrite\" or \"ignore\"",
+                options::PK_CONFLICT,
+                val
+            ))
+            .into()),
+        }
+    }
+
     /// Parse the append only property from the options.
     pub fn append_only(&self) -> bool {
         if let Some(val) = self.inner.get(options::APPEND_ONLY) {
@@ -101,9 +142,14 @@ impl WithOptions {
 
     /// Get the subset of the options for internal table catalogs.
     ///
-    /// Currently only `retention_seconds` is included.
+    /// Currently only `retention_seconds`, `time_travel_retention_seconds` and
+    /// `blob_value_threshold` are included.
     pub fn internal_table_subset(&self) -> Self {
-        self.subset([options::RETENTION_SECONDS])
+        self.subset([
+            options::RETENTION_SECONDS,
+            options::TIME_TRAVEL_RETENTION_SECONDS,
+            options::BLOB_VALUE_THRESHOLD,
+        ])
     }
 }
 
@@ -0,0 +1,256 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use piestream_hummock_sdk::compaction_group::hummock_version_ext::HummockVersionExt;
+use piestream_pb::backup_service::{MetaSnapshotManifest, MetaSnapshotMetadata};
+use tokio::sync::Mutex;
+
+use crate::backup_restore::meta_snapshot::build_meta_snapshot;
+use crate::backup_restore::storage::MetaSnapshotStorage;
+use crate::backup_restore::MetaSnapshotId;
+use crate::hummock::HummockManagerRef;
+use crate::manager::MetaSrvEnv;
+use crate::storage::MetaStore;
+use crate::{MetaError, MetaResult};
+
+pub type BackupManagerRef<S> = Arc<BackupManager<S>>;
+
+/// `BackupManager` creates, lists and deletes meta snapshots.
+pub struct BackupManager<S: MetaStore> {
+    env: MetaSrvEnv<S>,
+    hummock_manager: HummockManagerRef<S>,
+    storage: MetaSnapshotStorage,
+    /// The latest manifest. The lock also serializes all modifications of meta snapshots.
+    manifest: Mutex<MetaSnapshotManifest>,
+}
+
+impl<S> BackupManager<S>
+where
+    S: MetaStore,
+{
+    pub async fn new(
+        env: MetaSrvEnv<S>,
+        hummock_manager: HummockManagerRef<S>,
+    ) -> MetaResult<Self> {
+        let storage = MetaSnapshotStorage::from_url(
+            &env.opts.backup_storage_url,
+            &env.opts.backup_storage_directory,
+        )
+        .await;
+        Self::with_storage(env, hummock_manager, storage).await
+    }
+
+    pub(crate) async fn with_storage(
+        env: MetaSrvEnv<S>,
+        hummock_manager: HummockManagerRef<S>,
+        storage: MetaSnapshotStorage,
+    ) -> MetaResult<Self> {
+        let manifest = storage.get_manifest().await?;
+        hummock_manager
+            .restore_ssts_pinned_by_backup(
+                manifest
+                    .snapshot_metadata
+                    .iter()
+                    .map(|metadata| {
+                        let pinned_ids = metadata
+                            .ssts
+                            .iter()
+                            .chain(metadata.blob_ids.iter())
+                            .cloned()
+                            .collect();
+                        (metadata.id, pinned_ids)
+                    })
+                    .collect(),
+            )
+            .await;
+        Ok(Self {
+            env,
+            hummock_manager,
+            storage,
+            manifest: Mutex::new(manifest),
+        })
+    }
+
+    /// Takes a new meta snapshot and returns its id.
+    pub async fn backup(&self) -> MetaResult<MetaSnapshotId> {
+        let mut manifest = self.manifest.lock().await;
+        let id = manifest
+            .snapshot_metadata
+            .iter()
+            .map(|metadata| metadata.id)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let snapshot = build_meta_snapshot(self.env.meta_store(), id).await?;
+        let hummock_version = snapshot.hummock_version.as_ref().unwrap();
+        let ssts: HashSet<_> = hummock_version.get_sst_ids().into_iter().collect();
+        let blob_ids: HashSet<_> = hummock_version.get_blob_ids().into_iter().collect();
+        // Pin the SSTs and their blob files before the snapshot becomes visible, so that they won't
+        // be vacuumed.
+        self.hummock_manager
+            .pin_ssts_for_backup(id, ssts.union(&blob_ids).cloned().collect())
+            .await?;
+
+        let mut new_manifest = manifest.clone();
+        new_manifest.manifest_id += 1;
+        new_manifest.snapshot_metadata.push(MetaSnapshotMetadata {
+            id,
+            hummock_version_id: hummock_version.id,
+            max_committed_epoch: hummock_version.max_committed_epoch,
+            safe_epoch: hummock_version.safe_epoch,
+            ssts: ssts.into_iter().sorted().collect(),
+            blob_ids: blob_ids.into_iter().sorted().collect(),
+        });
+        let result = async {
+            self.storage.put_snapshot(&snapshot).await?;
+            self.storage.put_manifest(&new_manifest).await
+        }
+        .await;
+        if let Err(e) = result {
+            self.hummock_manager.unpin_ssts_for_backup(id).await;
+            return Err(e);
+        }
+        *manifest = new_manifest;
+        tracing::info!("meta snapshot {} is created", id);
+        Ok(id)
+    }
+
+    /// Deletes the meta snapshots and unpins the SSTs referenced by them.
+    pub async fn delete(&self, ids: &[MetaSnapshotId]) -> MetaResult<()> {
+        let mut manifest = self.manifest.lock().await;
+        if let Some(id) = ids
+            .iter()
+            .find(|id| !manifest.snapshot_metadata.iter().any(|m| m.id == **id))
+        {
+            return Err(MetaError::catalog_not_found(
+                "meta snapshot",
+                id.to_string(),
+            ));
+        }
+        let mut new_manifest = manifest.clone();
+        new_manifest.manifest_id += 1;
+        new_manifest
+            .snapshot_metadata
+            .retain(|metadata| !ids.contains(&metadata.id));
+        self.storage.put_manifest(&new_manifest).await?;
+        *manifest = new_manifest;
+        // The snapshots are no longer visible, so it's safe to unpin the SSTs even if the objects
+        // fail to be deleted.
+        for id in ids {
+            self.hummock_manager.unpin_ssts_for_backup(*id).await;
+        }
+        self.storage.delete_snapshots(ids).await?;
+        tracing::info!("meta snapshots {:?} are deleted", ids);
+        Ok(())
+    }
+
+    pub async fn list(&self) -> Vec<MetaSnapshotMetadata> {
+        self.manifest.lock().await.snapshot_metadata.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use piestream_object_store::object::object_metrics::ObjectStoreMetrics;
+    use piestream_object_store::object::{InMemObjectStore, ObjectStore, ObjectStoreImpl};
+    use piestream_pb::hummock::HummockVersion;
+
+    use super::*;
+    use crate::backup_restore::meta_snapshot::backup_column_families;
+    use crate::backup_restore::restore::restore_meta_snapshot;
+    use crate::hummock::test_utils::{add_test_tables, setup_compute_env};
+    use crate::model::MetadataModel;
+    use crate::storage::MemStore;
+
+    #[tokio::test]
+    async fn test_backup_and_restore() {
+        let (env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
+        add_test_tables(hummock_manager.as_ref(), worker_node.id).await;
+        let store = ObjectStoreImpl::InMem(
+            InMemObjectStore::new().monitored(Arc::new(ObjectStoreMetrics::unused())),
+        );
+        let backup_manager = BackupManager::with_storage(
+            env.clone(),
+            hummock_manager.clone(),
+            MetaSnapshotStorage::new(store, "backup"),
+        )
+        .await
+        .unwrap();
+        assert!(backup_manager.list().await.is_empty());
+
+        let id = backup_manager.backup().await.unwrap();
+        let current_version = hummock_manager.get_current_version().await;
+        let snapshot_metadata = backup_manager.list().await;
+        assert_eq!(snapshot_metadata.len(), 1);
+        assert_eq!(snapshot_metadata[0].id, id);
+        assert_eq!(snapshot_metadata[0].hummock_version_id, current_version.id);
+        assert_eq!(
+            snapshot_metadata[0].ssts,
+            current_version
+                .get_sst_ids()
+                .into_iter()
+                .sorted()
+                .collect_vec()
+        );
+        assert_eq!(
+            snapshot_metadata[0].blob_ids,
+            current_version
+                .get_blob_ids()
+                .into_iter()
+                .sorted()
+                .collect_vec()
+        );
+        assert_eq!(
+            backup_manager
+                .storage
+                .get_manifest()
+                .await
+                .unwrap()
+                .manifest_id,
+            1
+        );
+
+        // Restore to a new meta store.
+        let snapshot = backup_manager.storage.get_snapshot(id).await.unwrap();
+        let meta_store = MemStore::new();
+        restore_meta_snapshot(&meta_store, snapshot.clone())
+            .await
+            .unwrap();
+        for cf in backup_column_families() {
+            assert_eq!(
+                meta_store.list_cf(&cf).await.unwrap(),
+                env.meta_store().list_cf(&cf).await.unwrap()
+            );
+        }
+        assert_eq!(
+            HummockVersion::list(&meta_store).await.unwrap(),
+            vec![current_version]
+        );
+        // The target meta store must be empty.
+        restore_meta_snapshot(&meta_store, snapshot)
+            .await
+            .unwrap_err();
+
+        backup_manager.delete(&[id]).await.unwrap();
+        assert!(backup_manager.list().await.is_empty());
+        assert!(backup_manager.storage.get_snapshot(id).await.is_err());
+        backup_manager.delete(&[id]).await.unwrap_err();
+    }
+}
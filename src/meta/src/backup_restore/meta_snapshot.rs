@@ -0,0 +1,101 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use prost::Message;
+use piestream_common::bail;
+use piestream_hummock_sdk::compaction_group::hummock_version_ext::HummockVersionExt;
+use piestream_pb::backup_service::meta_snapshot::{ColumnFamily, KeyValue};
+use piestream_pb::backup_service::MetaSnapshot;
+use piestream_pb::catalog::{Database, Index, Schema, Sink, Source, Table};
+use piestream_pb::hummock::{HummockVersion, HummockVersionDelta};
+use piestream_pb::user::UserInfo;
+
+use crate::backup_restore::MetaSnapshotId;
+use crate::hummock::compaction_group::CompactionGroup;
+use crate::model::{MetadataModel, MetadataModelError, TableFragments, Worker};
+use crate::storage::{MetaStore, Snapshot, DEFAULT_COLUMN_FAMILY};
+use crate::stream::SourceActorInfo;
+use crate::MetaResult;
+
+/// Column families included in a meta snapshot.
+///
+/// Leader info, pinned versions and snapshots, compaction status and assignments are excluded,
+/// as they are rebuilt when a meta node starts from the restored meta store. Hummock version and
+/// deltas are not listed either, since the snapshot carries the version they redo to.
+pub(crate) fn backup_column_families() -> Vec<String> {
+    vec![
+        DEFAULT_COLUMN_FAMILY.to_string(),
+        Worker::cf_name(),
+        Database::cf_name(),
+        Schema::cf_name(),
+        Table::cf_name(),
+        Source::cf_name(),
+        Sink::cf_name(),
+        Index::cf_name(),
+        UserInfo::cf_name(),
+        TableFragments::cf_name(),
+        SourceActorInfo::cf_name(),
+        CompactionGroup::cf_name(),
+    ]
+}
+
+/// Builds a meta snapshot from a consistent view of `meta_store`.
+pub(crate) async fn build_meta_snapshot<S: MetaStore>(
+    meta_store: &S,
+    id: MetaSnapshotId,
+) -> MetaResult<MetaSnapshot> {
+    let snapshot = meta_store.snapshot().await;
+    let mut column_families = vec![];
+    for cf in backup_column_families() {
+        let kvs = snapshot
+            .list_cf_with_key(&cf)
+            .await?
+            .into_iter()
+            .map(|(key, value)| KeyValue { key, value })
+            .collect();
+        column_families.push(ColumnFamily { name: cf, kvs });
+    }
+
+    // Redo the deltas on the checkpoint version, the same as how `HummockManager` loads its
+    // current version.
+    let mut hummock_version = match snapshot.list_cf(&HummockVersion::cf_name()).await?.first() {
+        Some(bytes) => {
+            HummockVersion::decode(bytes.as_slice()).map_err(MetadataModelError::from)?
+        }
+        None => bail!("hummock version is not initialized"),
+    };
+    let hummock_version_deltas: BTreeMap<_, _> = snapshot
+        .list_cf(&HummockVersionDelta::cf_name())
+        .await?
+        .iter()
+        .map(|bytes| {
+            HummockVersionDelta::decode(bytes.as_slice())
+                .map(|version_delta| (version_delta.id, version_delta))
+        })
+        .collect::<Result<_, _>>()
+        .map_err(MetadataModelError::from)?;
+    for version_delta in hummock_version_deltas.values() {
+        if version_delta.prev_id == hummock_version.id {
+            hummock_version.apply_version_delta(version_delta);
+        }
+    }
+
+    Ok(MetaSnapshot {
+        id,
+        hummock_version: Some(hummock_version),
+        column_families,
+    })
+}
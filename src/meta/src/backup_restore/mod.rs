@@ -0,0 +1,30 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Backup and restore of the meta store, together with the Hummock version it refers to.
+//!
+//! A meta snapshot is taken from a consistent view of the meta store, and is stored in a
+//! separate object store along with a manifest listing all the existing snapshots. SSTs
+//! referenced by a meta snapshot are pinned in [`crate::hummock::HummockManager`] until the
+//! snapshot is deleted.
+
+mod backup_manager;
+mod meta_snapshot;
+mod restore;
+mod storage;
+
+pub use backup_manager::*;
+pub use restore::*;
+
+pub type MetaSnapshotId = u64;
@@ -0,0 +1,123 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use clap::Parser;
+use etcd_client::{Client as EtcdClient, ConnectOptions};
+use piestream_common::bail;
+use piestream_pb::backup_service::MetaSnapshot;
+use piestream_pb::hummock::HummockVersion;
+
+use crate::backup_restore::meta_snapshot::backup_column_families;
+use crate::backup_restore::storage::MetaSnapshotStorage;
+use crate::backup_restore::MetaSnapshotId;
+use crate::model::MetadataModel;
+use crate::storage::{EtcdMetaStore, MetaStore};
+use crate::{MetaError, MetaResult};
+
+/// Restores a meta snapshot to an empty etcd meta store. A meta node started on the restored
+/// meta store recovers the cluster as of the meta snapshot.
+#[derive(Debug, Parser)]
+pub struct RestoreOpts {
+    /// Id of the meta snapshot to restore.
+    #[clap(long)]
+    meta_snapshot_id: MetaSnapshotId,
+
+    #[clap(long, default_value_t = String::from(""))]
+    etcd_endpoints: String,
+
+    /// Enable authentication with etcd. By default disabled.
+    #[clap(long)]
+    etcd_auth: bool,
+
+    /// Username of etcd, required when --etcd-auth is enabled.
+    /// Default value is read from the 'ETCD_USERNAME' environment variable.
+    #[clap(long, env = "ETCD_USERNAME", default_value = "")]
+    etcd_username: String,
+
+    /// Password of etcd, required when --etcd-auth is enabled.
+    /// Default value is read from the 'ETCD_PASSWORD' environment variable.
+    #[clap(long, env = "ETCD_PASSWORD", default_value = "")]
+    etcd_password: String,
+
+    /// Remote object store where meta snapshots are stored, e.g. `s3://bucket`.
+    #[clap(long)]
+    backup_storage_url: String,
+
+    /// Directory of meta snapshots in the remote object store.
+    #[clap(long, default_value = "backup")]
+    backup_storage_directory: String,
+}
+
+pub async fn restore(opts: RestoreOpts) -> MetaResult<()> {
+    let storage =
+        MetaSnapshotStorage::from_url(&opts.backup_storage_url, &opts.backup_storage_directory)
+            .await;
+    let manifest = storage.get_manifest().await?;
+    if !manifest
+        .snapshot_metadata
+        .iter()
+        .any(|metadata| metadata.id == opts.meta_snapshot_id)
+    {
+        return Err(MetaError::catalog_not_found(
+            "meta snapshot",
+            opts.meta_snapshot_id.to_string(),
+        ));
+    }
+    let snapshot = storage.get_snapshot(opts.meta_snapshot_id).await?;
+
+    let mut options =
+        ConnectOptions::default().with_keep_alive(Duration::from_secs(3), Duration::from_secs(5));
+    if opts.etcd_auth {
+        options = options.with_user(opts.etcd_username, opts.etcd_password);
+    }
+    let endpoints = opts
+        .etcd_endpoints
+        .split(',')
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>();
+    let client = EtcdClient::connect(endpoints, Some(options))
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to connect etcd {}", e))?;
+    restore_meta_snapshot(&EtcdMetaStore::new(client), snapshot).await?;
+    tracing::info!("meta snapshot {} is restored", opts.meta_snapshot_id);
+    Ok(())
+}
+
+/// Writes `snapshot` to `meta_store`, which must not contain any data to be restored.
+pub(crate) async fn restore_meta_snapshot<S: MetaStore>(
+    meta_store: &S,
+    snapshot: MetaSnapshot,
+) -> MetaResult<()> {
+    for cf in backup_column_families()
+        .into_iter()
+        .chain([HummockVersion::cf_name()])
+    {
+        if !meta_store.list_cf(&cf).await?.is_empty() {
+            bail!("meta store is not empty: column family {} exists", cf);
+        }
+    }
+    for cf in snapshot.column_families {
+        for kv in cf.kvs {
+            meta_store.put_cf(&cf.name, kv.key, kv.value).await?;
+        }
+    }
+    snapshot
+        .hummock_version
+        .expect("hummock version of meta snapshot should be set")
+        .insert(meta_store)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use prost::Message;
+use piestream_object_store::object::object_metrics::ObjectStoreMetrics;
+use piestream_object_store::object::{parse_remote_object_store, ObjectStoreImpl};
+use piestream_pb::backup_service::{MetaSnapshot, MetaSnapshotManifest};
+
+use crate::backup_restore::MetaSnapshotId;
+use crate::model::MetadataModelError;
+use crate::MetaResult;
+
+const MANIFEST_FILE_NAME: &str = "manifest";
+
+/// `MetaSnapshotStorage` stores meta snapshots and the manifest in an object store, under
+/// `{directory}/{id}.snapshot` and `{directory}/manifest` respectively.
+pub(crate) struct MetaSnapshotStorage {
+    store: ObjectStoreImpl,
+    directory: String,
+}
+
+impl MetaSnapshotStorage {
+    pub async fn from_url(url: &str, directory: &str) -> Self {
+        let store = parse_remote_object_store(url, Arc::new(ObjectStoreMetrics::unused())).await;
+        Self::new(store, directory)
+    }
+
+    pub fn new(store: ObjectStoreImpl, directory: &str) -> Self {
+        Self {
+            store,
+            directory: directory.to_string(),
+        }
+    }
+
+    /// Returns an empty manifest if none has been written yet.
+    pub async fn get_manifest(&self) -> MetaResult<MetaSnapshotManifest> {
+        let path = self.manifest_path();
+        if self.store.list(&path).await?.is_empty() {
+            return Ok(MetaSnapshotManifest::default());
+        }
+        let bytes = self.store.read(&path, None).await?;
+        Ok(MetaSnapshotManifest::decode(bytes).map_err(MetadataModelError::from)?)
+    }
+
+    pub async fn put_manifest(&self, manifest: &MetaSnapshotManifest) -> MetaResult<()> {
+        self.store
+            .upload(&self.manifest_path(), Bytes::from(manifest.encode_to_vec()))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_snapshot(&self, id: MetaSnapshotId) -> MetaResult<MetaSnapshot> {
+        let bytes = self.store.read(&self.snapshot_path(id), None).await?;
+        Ok(MetaSnapshot::decode(bytes).map_err(MetadataModelError::from)?)
+    }
+
+    pub async fn put_snapshot(&self, snapshot: &MetaSnapshot) -> MetaResult<()> {
+        self.store
+            .upload(
+                &self.snapshot_path(snapshot.id),
+                Bytes::from(snapshot.encode_to_vec()),
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_snapshots(&self, ids: &[MetaSnapshotId]) -> MetaResult<()> {
+        let paths = ids
+            .iter()
+            .map(|id| self.snapshot_path(*id))
+            .collect::<Vec<_>>();
+        self.store.delete_objects(&paths).await?;
+        Ok(())
+    }
+
+    fn manifest_path(&self) -> String {
+        format!("{}/{}", self.directory, MANIFEST_FILE_NAME)
+    }
+
+    fn snapshot_path(&self, id: MetaSnapshotId) -> String {
+        format!("{}/{}.snapshot", self.directory, id)
+    }
+}
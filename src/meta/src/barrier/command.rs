@@ -154,6 +154,19 @@ impl Command {
         matches!(self, Self::Plain(Some(Mutation::Pause(_))))
     }
 
+    /// The name of the command, used for display.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Plain(None) => "Barrier",
+            Command::Plain(Some(Mutation::Pause(_))) => "Pause",
+            Command::Plain(Some(Mutation::Resume(_))) => "Resume",
+            Command::Plain(Some(_)) => "Plain",
+            Command::DropMaterializedView(_) => "DropMaterializedView",
+            Command::CreateMaterializedView { .. } => "CreateMaterializedView",
+            Command::RescheduleFragment(_) => "RescheduleFragment",
+        }
+    }
+
     pub fn need_checkpoint(&self) -> bool {
         // todo! Reviewing the flow of different command to reduce the amount of checkpoint
         !matches!(self, Command::Plain(None | Some(Mutation::Resume(_))))
@@ -401,6 +414,17 @@ where
         }
     }
 
+    /// For `CreateMaterializedView`, returns the id of the table to create. For other commands,
+    /// returns `None`.
+    pub fn table_to_create(&self) -> Option<TableId> {
+        match &self.command {
+            Command::CreateMaterializedView {
+                table_fragments, ..
+            } => Some(table_fragments.table_id()),
+            _ => None,
+        }
+    }
+
     /// Do some stuffs after barriers are collected and the new storage version is committed, for
     /// the given command.
     pub async fn post_collect(&self) -> MetaResult<()> {
@@ -582,4 +606,15 @@ where
 
         Ok(())
     }
+
+    /// Do some stuffs when the creation of a `CreateMaterializedView` is canceled before it's
+    /// finished. The actors and fragments are cleaned up by the `DropMaterializedView` command.
+    pub async fn post_cancel(&self) -> MetaResult<()> {
+        if matches!(self.command, Command::CreateMaterializedView { .. }) {
+            // Release the snapshot pinned in `post_collect`.
+            self.snapshot_manager.unpin(self.prev_epoch).await?;
+        }
+
+        Ok(())
+    }
 }
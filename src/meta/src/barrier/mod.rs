@@ -21,19 +21,21 @@ use std::time::Duration;
 use fail::fail_point;
 use futures::future::try_join_all;
 use itertools::Itertools;
-use prometheus::HistogramTimer;
 use piestream_common::bail;
 use piestream_common::catalog::TableId;
 use piestream_common::util::epoch::INVALID_EPOCH;
+use piestream_hummock_sdk::compaction_group::hummock_version_ext::HummockVersionExt;
 use piestream_hummock_sdk::{HummockSstableId, LocalSstableInfo};
 use piestream_pb::common::worker_node::State::Running;
 use piestream_pb::common::WorkerType;
+use piestream_pb::ddl_service::DdlProgress;
 use piestream_pb::meta::table_fragments::actor_status::ActorState;
 use piestream_pb::stream_plan::Barrier;
 use piestream_pb::stream_service::{
     BarrierCompleteRequest, BarrierCompleteResponse, InjectBarrierRequest,
 };
 use piestream_rpc_client::StreamClientPoolRef;
+use prometheus::HistogramTimer;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::oneshot::{Receiver, Sender};
 use tokio::task::JoinHandle;
@@ -59,6 +61,7 @@ use crate::{MetaError, MetaResult};
 
 mod command;
 mod info;
+mod monitor;
 mod notifier;
 mod progress;
 mod recovery;
@@ -66,6 +69,7 @@ mod schedule;
 mod snapshot;
 
 pub use self::command::{Command, Reschedule};
+pub use self::monitor::{BarrierMonitorRef, FinishedBarrier, InFlightBarrier};
 pub use self::schedule::BarrierScheduler;
 
 /// Scheduled command with its notifiers.
@@ -133,6 +137,12 @@ pub struct GlobalBarrierManager<S: MetaStore> {
 
     metrics: Arc<MetaMetrics>,
 
+    /// Records the in-flight and recently finished barriers for the dashboard.
+    barrier_monitor: BarrierMonitorRef,
+
+    /// The progress of creating mviews, refreshed from the tracker every time a barrier completes.
+    ddl_progress: parking_lot::Mutex<Vec<DdlProgress>>,
+
     pub(crate) env: MetaSrvEnv<S>,
 }
 
@@ -472,6 +482,8 @@ where
             snapshot_manager,
             source_manager,
             metrics,
+            barrier_monitor: Default::default(),
+            ddl_progress: Default::default(),
             env,
         }
     }
@@ -586,11 +598,44 @@ where
             notifiers.iter_mut().for_each(Notifier::notify_to_send);
 
             checkpoint_control.enqueue_command(command_ctx.clone(), notifiers);
+            self.barrier_monitor.on_inject(&command_ctx);
             self.inject_barrier(command_ctx, barrier_complete_tx.clone())
                 .await;
         }
     }
 
+    pub fn barrier_monitor(&self) -> BarrierMonitorRef {
+        self.barrier_monitor.clone()
+    }
+
+    /// Get the progress of all creating mviews.
+    pub fn get_ddl_progress(&self) -> Vec<DdlProgress> {
+        self.ddl_progress.lock().clone()
+    }
+
+    /// For `CreateMaterializedView`, returns the number of keys of the upstream tables in the
+    /// current storage version, which is used as the number of rows of the snapshot to consume.
+    /// For other commands, returns 0.
+    async fn upstream_total_key_count(&self, command_ctx: &CommandContext<S>) -> u64 {
+        let Command::CreateMaterializedView { table_fragments, .. } = &command_ctx.command else {
+            return 0;
+        };
+        let upstream_table_ids = table_fragments.dependent_table_ids();
+        if upstream_table_ids.is_empty() {
+            return 0;
+        }
+
+        let version = self.hummock_manager.get_current_version().await;
+        version
+            .get_combined_levels()
+            .into_iter()
+            .flat_map(|level| &level.table_infos)
+            .flat_map(|sst| &sst.table_stats)
+            .filter(|(table_id, _)| upstream_table_ids.contains(&TableId::new(**table_id)))
+            .map(|(_, stats)| stats.total_key_count)
+            .sum()
+    }
+
     /// Inject a barrier to all CNs and spawn a task to collect it
     async fn inject_barrier(
         &self,
@@ -727,6 +772,7 @@ where
             return;
         }
         // change the state is Complete
+        self.barrier_monitor.on_collect(prev_epoch);
         let mut complete_nodes = checkpoint_control.barrier_completed(prev_epoch, result.unwrap());
         // try commit complete nodes
         let (mut index, mut err_msg) = (0, None);
@@ -761,6 +807,8 @@ where
     ) {
         checkpoint_control.clear_changes();
         for node in fail_nodes {
+            self.barrier_monitor
+                .on_finish(node.command_ctx.prev_epoch.0, false);
             if let Some(timer) = node.timer {
                 timer.observe_duration();
             }
@@ -774,6 +822,7 @@ where
         if self.enable_recovery {
             // If failed, enter recovery mode.
             *tracker = CreateMviewProgressTracker::new();
+            self.ddl_progress.lock().clear();
             let new_epoch = self.recovery(state.in_flight_prev_epoch).await;
             state.in_flight_prev_epoch = new_epoch;
             state
@@ -842,22 +891,42 @@ where
                     notifier.notify_collected();
                 });
 
+                // If a creating mview is dropped, stop tracking it. The notifiers of its command
+                // are dropped without notifying, so that the creation procedure will be aborted.
+                let mut canceled_actors = HashSet::new();
+                if let Command::DropMaterializedView(table_id) = &node.command_ctx.command {
+                    if let Some(command) = tracker.cancel(*table_id) {
+                        command.context.post_cancel().await?;
+                        canceled_actors.extend(command.context.actors_to_track());
+                    }
+                }
+
                 // Save `finished_commands` for Create MVs.
                 let finished_commands = {
                     let mut commands = vec![];
-                    if let Some(command) = tracker.add(TrackingCommand {
-                        context: node.command_ctx.clone(),
-                        notifiers,
-                    }) {
+                    let upstream_total_key_count =
+                        self.upstream_total_key_count(&node.command_ctx).await;
+                    if let Some(command) = tracker.add(
+                        TrackingCommand {
+                            context: node.command_ctx.clone(),
+                            notifiers,
+                        },
+                        upstream_total_key_count,
+                    ) {
                         commands.push(command);
                     }
-                    for progress in resps.iter().flat_map(|r| &r.create_mview_progress) {
+                    for progress in resps
+                        .iter()
+                        .flat_map(|r| &r.create_mview_progress)
+                        .filter(|p| !canceled_actors.contains(&p.chain_actor_id))
+                    {
                         if let Some(command) = tracker.update(progress) {
                             commands.push(command);
                         }
                     }
                     commands
                 };
+                *self.ddl_progress.lock() = tracker.gen_ddl_progress();
 
                 for command in finished_commands {
                     // The command is ready to finish. We can now call `pre_finish`.
@@ -875,6 +944,7 @@ where
 
                 node.timer.take().unwrap().observe_duration();
                 node.wait_commit_timer.take().unwrap().observe_duration();
+                self.barrier_monitor.on_finish(prev_epoch, true);
 
                 Ok(())
             }
@@ -0,0 +1,176 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use super::command::CommandContext;
+use crate::storage::MetaStore;
+
+/// The number of the latest finished barriers kept in the history.
+const BARRIER_HISTORY_SIZE: usize = 1000;
+
+/// Barrier in-flight or waiting to be committed, keyed by the prev epoch.
+#[derive(Debug, Clone, Serialize)]
+pub struct InFlightBarrier {
+    pub prev_epoch: u64,
+    pub curr_epoch: u64,
+    pub checkpoint: bool,
+    pub command: &'static str,
+    /// Whether the barrier has been collected from all compute nodes, and is waiting for the
+    /// barriers of previous epochs to be committed.
+    pub collected: bool,
+    pub elapsed_ms: u64,
+}
+
+/// Barrier that is committed, or failed and triggered recovery.
+#[derive(Debug, Clone, Serialize)]
+pub struct FinishedBarrier {
+    pub prev_epoch: u64,
+    pub curr_epoch: u64,
+    pub checkpoint: bool,
+    pub command: &'static str,
+    pub succeeded: bool,
+    pub latency_ms: u64,
+}
+
+struct BarrierEntry {
+    barrier: InFlightBarrier,
+    injected_at: Instant,
+}
+
+#[derive(Default)]
+struct BarrierMonitorInner {
+    in_flight: BTreeMap<u64, BarrierEntry>,
+    history: VecDeque<FinishedBarrier>,
+}
+
+/// Tracks the in-flight barriers and the latency of the latest finished barriers, so that they
+/// can be inspected in the dashboard without Prometheus.
+#[derive(Default)]
+pub struct BarrierMonitor {
+    inner: Mutex<BarrierMonitorInner>,
+}
+
+pub type BarrierMonitorRef = Arc<BarrierMonitor>;
+
+impl BarrierMonitor {
+    pub(super) fn on_inject<S: MetaStore>(&self, command_ctx: &CommandContext<S>) {
+        let barrier = InFlightBarrier {
+            prev_epoch: command_ctx.prev_epoch.0,
+            curr_epoch: command_ctx.curr_epoch.0,
+            checkpoint: command_ctx.checkpoint,
+            command: command_ctx.command.name(),
+            collected: false,
+            elapsed_ms: 0,
+        };
+        self.inner.lock().in_flight.insert(
+            barrier.prev_epoch,
+            BarrierEntry {
+                barrier,
+                injected_at: Instant::now(),
+            },
+        );
+    }
+
+    pub(super) fn on_collect(&self, prev_epoch: u64) {
+        if let Some(entry) = self.inner.lock().in_flight.get_mut(&prev_epoch) {
+            entry.barrier.collected = true;
+        }
+    }
+
+    pub(super) fn on_finish(&self, prev_epoch: u64, succeeded: bool) {
+        let mut inner = self.inner.lock();
+        if let Some(entry) = inner.in_flight.remove(&prev_epoch) {
+            if inner.history.len() == BARRIER_HISTORY_SIZE {
+                inner.history.pop_front();
+            }
+            inner.history.push_back(FinishedBarrier {
+                prev_epoch,
+                curr_epoch: entry.barrier.curr_epoch,
+                checkpoint: entry.barrier.checkpoint,
+                command: entry.barrier.command,
+                succeeded,
+                latency_ms: entry.injected_at.elapsed().as_millis() as u64,
+            });
+        }
+    }
+
+    /// Returns the in-flight barriers in the order of epoch.
+    pub fn in_flight_barriers(&self) -> Vec<InFlightBarrier> {
+        self.inner
+            .lock()
+            .in_flight
+            .values()
+            .map(|entry| InFlightBarrier {
+                elapsed_ms: entry.injected_at.elapsed().as_millis() as u64,
+                ..entry.barrier.clone()
+            })
+            .collect()
+    }
+
+    /// Returns the latest finished barriers in the order of epoch.
+    pub fn barrier_history(&self) -> Vec<FinishedBarrier> {
+        self.inner.lock().history.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inject(monitor: &BarrierMonitor, prev_epoch: u64) {
+        let barrier = InFlightBarrier {
+            prev_epoch,
+            curr_epoch: prev_epoch + 1,
+            checkpoint: true,
+            command: "Barrier",
+            collected: false,
+            elapsed_ms: 0,
+        };
+        monitor.inner.lock().in_flight.insert(
+            prev_epoch,
+            BarrierEntry {
+                barrier,
+                injected_at: Instant::now(),
+            },
+        );
+    }
+
+    #[test]
+    fn test_barrier_monitor() {
+        let monitor = BarrierMonitor::default();
+        for epoch in 0..BARRIER_HISTORY_SIZE as u64 + 2 {
+            inject(&monitor, epoch);
+        }
+        monitor.on_collect(1);
+        let in_flight = monitor.in_flight_barriers();
+        assert_eq!(in_flight.len(), BARRIER_HISTORY_SIZE + 2);
+        assert!(!in_flight[0].collected);
+        assert!(in_flight[1].collected);
+
+        for epoch in 0..BARRIER_HISTORY_SIZE as u64 + 1 {
+            monitor.on_finish(epoch, epoch != 0);
+        }
+        let history = monitor.barrier_history();
+        assert_eq!(history.len(), BARRIER_HISTORY_SIZE);
+        assert_eq!(history[0].prev_epoch, 1);
+        assert!(history.iter().all(|b| b.succeeded));
+        assert_eq!(monitor.in_flight_barriers().len(), 1);
+    }
+}
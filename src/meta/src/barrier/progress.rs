@@ -16,7 +16,9 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use piestream_common::catalog::TableId;
 use piestream_common::util::epoch::Epoch;
+use piestream_pb::ddl_service::DdlProgress;
 use piestream_pb::stream_service::barrier_complete_response::CreateMviewProgress;
 
 use super::command::CommandContext;
@@ -26,11 +28,25 @@ use crate::storage::MetaStore;
 
 type CreateMviewEpoch = Epoch;
 
+type ConsumedRows = u64;
+
 #[derive(Clone, Copy)]
 enum ChainState {
     ConsumingSnapshot,
-    ConsumingUpstream(Epoch),
-    Done,
+    ConsumingUpstream(Epoch, ConsumedRows),
+    Done(ConsumedRows),
+}
+
+impl ChainState {
+    /// Returns the number of rows consumed by the chain so far.
+    fn consumed_rows(&self) -> ConsumedRows {
+        match self {
+            ChainState::ConsumingSnapshot => 0,
+            ChainState::ConsumingUpstream(_, consumed_rows) | ChainState::Done(consumed_rows) => {
+                *consumed_rows
+            }
+        }
+    }
 }
 
 /// Progress of all actors containing chain nodes while creating mview.
@@ -38,11 +54,18 @@ struct Progress {
     states: HashMap<ActorId, ChainState>,
 
     done_count: usize,
+
+    /// Sum of the rows consumed by all chains.
+    consumed_rows: u64,
+
+    /// Number of keys of the upstream tables when the creation started, which is the estimated
+    /// number of rows of the snapshot to consume.
+    upstream_total_key_count: u64,
 }
 
 impl Progress {
     /// Create a [`Progress`] for some creating mview, with all `actors` containing the chain nodes.
-    fn new(actors: impl IntoIterator<Item = ActorId>) -> Self {
+    fn new(actors: impl IntoIterator<Item = ActorId>, upstream_total_key_count: u64) -> Self {
         let states = actors
             .into_iter()
             .map(|a| (a, ChainState::ConsumingSnapshot))
@@ -52,19 +75,23 @@ impl Progress {
         Self {
             states,
             done_count: 0,
+            consumed_rows: 0,
+            upstream_total_key_count,
         }
     }
 
     /// Update the progress of `actor`.
     fn update(&mut self, actor: ActorId, new_state: ChainState) {
         match self.states.get_mut(&actor).unwrap() {
-            state @ (ChainState::ConsumingSnapshot | ChainState::ConsumingUpstream(_)) => {
-                if matches!(new_state, ChainState::Done) {
+            state @ (ChainState::ConsumingSnapshot | ChainState::ConsumingUpstream(..)) => {
+                if matches!(new_state, ChainState::Done(_)) {
                     self.done_count += 1;
                 }
+                self.consumed_rows -= state.consumed_rows();
+                self.consumed_rows += new_state.consumed_rows();
                 *state = new_state;
             }
-            ChainState::Done => panic!("should not report done multiple times"),
+            ChainState::Done(_) => panic!("should not report done multiple times"),
         }
     }
 
@@ -78,6 +105,11 @@ impl Progress {
     fn actors(&self) -> impl Iterator<Item = ActorId> + '_ {
         self.states.keys().cloned()
     }
+
+    /// Returns the number of rows consumed by all chains and the number of rows of the snapshot.
+    fn rows(&self) -> (u64, u64) {
+        (self.consumed_rows, self.upstream_total_key_count)
+    }
 }
 
 /// The command tracking by the [`CreateMviewProgressTracker`].
@@ -107,10 +139,15 @@ impl<S: MetaStore> CreateMviewProgressTracker<S> {
         }
     }
 
-    /// Add a new create-mview DDL command to track.
+    /// Add a new create-mview DDL command to track. The `upstream_total_key_count` is the number of
+    /// keys of the upstream tables, against which the consumed rows are reported as the progress.
     ///
     /// If the actors to track is empty, return the given command as it can be finished immediately.
-    pub fn add(&mut self, command: TrackingCommand<S>) -> Option<TrackingCommand<S>> {
+    pub fn add(
+        &mut self,
+        command: TrackingCommand<S>,
+        upstream_total_key_count: u64,
+    ) -> Option<TrackingCommand<S>> {
         let actors = command.context.actors_to_track();
         if actors.is_empty() {
             // The command can be finished immediately.
@@ -122,12 +159,49 @@ impl<S: MetaStore> CreateMviewProgressTracker<S> {
             self.actor_map.insert(actor, ddl_epoch);
         }
 
-        let progress = Progress::new(actors);
+        let progress = Progress::new(actors, upstream_total_key_count);
         let old = self.progress_map.insert(ddl_epoch, (progress, command));
         assert!(old.is_none());
         None
     }
 
+    /// Stop tracking the creating mview of `table_id`, returns the command if it's being tracked.
+    ///
+    /// The notifiers of the returned command should be dropped without notifying, so that the
+    /// creation procedure will be aborted.
+    pub fn cancel(&mut self, table_id: TableId) -> Option<TrackingCommand<S>> {
+        let epoch = self
+            .progress_map
+            .iter()
+            .find(|(_, (_, command))| command.context.table_to_create() == Some(table_id))
+            .map(|(epoch, _)| *epoch)?;
+
+        let (progress, command) = self.progress_map.remove(&epoch).unwrap();
+        for actor in progress.actors() {
+            self.actor_map.remove(&actor);
+        }
+        tracing::debug!("cancel tracking creating mview {}", table_id);
+        Some(command)
+    }
+
+    /// Returns the progress of all creating mviews. The `name` is left empty since it's unknown to
+    /// the barrier manager.
+    pub fn gen_ddl_progress(&self) -> Vec<DdlProgress> {
+        self.progress_map
+            .values()
+            .filter_map(|(progress, command)| {
+                let table_id = command.context.table_to_create()?;
+                let (consumed_rows, snapshot_rows) = progress.rows();
+                Some(DdlProgress {
+                    id: table_id.table_id,
+                    consumed_rows,
+                    snapshot_rows,
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
     /// Update the progress of `actor` according to the Prost struct.
     ///
     /// If all actors in this MV have finished, returns the command.
@@ -138,9 +212,9 @@ impl<S: MetaStore> CreateMviewProgressTracker<S> {
         };
 
         let new_state = if progress.done {
-            ChainState::Done
+            ChainState::Done(progress.consumed_rows)
         } else {
-            ChainState::ConsumingUpstream(progress.consumed_epoch.into())
+            ChainState::ConsumingUpstream(progress.consumed_epoch.into(), progress.consumed_rows)
         };
 
         match self.progress_map.entry(epoch) {
@@ -169,3 +243,149 @@ impl<S: MetaStore> CreateMviewProgressTracker<S> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use piestream_pb::stream_plan::Dispatcher;
+    use tokio::sync::oneshot;
+
+    use super::*;
+    use crate::barrier::info::BarrierActorInfo;
+    use crate::barrier::snapshot::SnapshotManager;
+    use crate::barrier::{BarrierScheduler, Command};
+    use crate::hummock::test_utils::setup_compute_env;
+    use crate::manager::{CatalogManager, FragmentManager};
+    use crate::model::TableFragments;
+    use crate::storage::MemStore;
+    use crate::stream::SourceManager;
+
+    /// Create the tracking command of creating the mview `table_id`, whose chain actors `actors`
+    /// are fed by the upstream actor `0`.
+    async fn create_tracking_command(
+        table_id: TableId,
+        actors: Vec<ActorId>,
+        epoch: u64,
+    ) -> (TrackingCommand<MemStore>, oneshot::Receiver<()>) {
+        let (env, hummock_manager, _, _) = setup_compute_env(80).await;
+        let catalog_manager = Arc::new(CatalogManager::new(env.clone()).await.unwrap());
+        let fragment_manager = Arc::new(FragmentManager::new(env.clone()).await.unwrap());
+        let (barrier_scheduler, _) =
+            BarrierScheduler::new_pair(hummock_manager.clone(), env.opts.checkpoint_frequency);
+        let source_manager = Arc::new(
+            SourceManager::new(
+                env.clone(),
+                barrier_scheduler,
+                catalog_manager,
+                fragment_manager.clone(),
+            )
+            .await
+            .unwrap(),
+        );
+
+        let command = Command::CreateMaterializedView {
+            table_fragments: TableFragments::new(table_id, BTreeMap::new()),
+            table_sink_map: Default::default(),
+            dispatchers: maplit::hashmap! {
+                0 => vec![Dispatcher {
+                    downstream_actor_id: actors,
+                    ..Default::default()
+                }],
+            },
+            source_state: Default::default(),
+        };
+        let context = CommandContext::new(
+            fragment_manager,
+            SnapshotManager::new(hummock_manager).into(),
+            env.stream_client_pool_ref(),
+            BarrierActorInfo {
+                node_map: Default::default(),
+                actor_map: Default::default(),
+                actor_map_to_send: Default::default(),
+            },
+            Epoch::from(epoch - 1),
+            Epoch::from(epoch),
+            command,
+            true,
+            source_manager,
+        );
+
+        let (finished_tx, finished_rx) = oneshot::channel();
+        let command = TrackingCommand {
+            context: Arc::new(context),
+            notifiers: vec![Notifier {
+                finished: Some(finished_tx),
+                ..Default::default()
+            }],
+        };
+        (command, finished_rx)
+    }
+
+    fn progress(actor: ActorId, done: bool, consumed_rows: u64) -> CreateMviewProgress {
+        CreateMviewProgress {
+            chain_actor_id: actor,
+            done,
+            consumed_epoch: 1,
+            consumed_rows,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_track_progress() {
+        let mut tracker = CreateMviewProgressTracker::new();
+        let table_id = TableId::new(1);
+        let (command, finished_rx) = create_tracking_command(table_id, vec![1, 2], 2).await;
+        assert!(tracker.add(command, 400).is_none());
+
+        let ddl_progress = tracker.gen_ddl_progress();
+        assert_eq!(ddl_progress.len(), 1);
+        assert_eq!(ddl_progress[0].id, table_id.table_id);
+        assert_eq!(ddl_progress[0].consumed_rows, 0);
+        assert_eq!(ddl_progress[0].snapshot_rows, 400);
+
+        assert!(tracker.update(&progress(1, false, 100)).is_none());
+        assert!(tracker.update(&progress(2, false, 50)).is_none());
+        assert!(tracker.update(&progress(1, false, 150)).is_none());
+        assert_eq!(tracker.gen_ddl_progress()[0].consumed_rows, 200);
+
+        assert!(tracker.update(&progress(1, true, 200)).is_none());
+        let command = tracker.update(&progress(2, true, 200)).unwrap();
+        assert!(tracker.gen_ddl_progress().is_empty());
+
+        command
+            .notifiers
+            .into_iter()
+            .for_each(Notifier::notify_finished);
+        finished_rx.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let mut tracker = CreateMviewProgressTracker::new();
+        let (command_1, finished_rx_1) = create_tracking_command(TableId::new(1), vec![1], 2).await;
+        let (command_2, _finished_rx_2) =
+            create_tracking_command(TableId::new(2), vec![2], 3).await;
+        assert!(tracker.add(command_1, 100).is_none());
+        assert!(tracker.add(command_2, 100).is_none());
+        assert!(tracker.update(&progress(1, false, 10)).is_none());
+
+        // Canceling an untracked mview is a no-op.
+        assert!(tracker.cancel(TableId::new(3)).is_none());
+        assert_eq!(tracker.gen_ddl_progress().len(), 2);
+
+        let command = tracker.cancel(TableId::new(1)).unwrap();
+        assert_eq!(command.context.table_to_create(), Some(TableId::new(1)));
+        assert!(tracker.cancel(TableId::new(1)).is_none());
+        let ddl_progress = tracker.gen_ddl_progress();
+        assert_eq!(ddl_progress.len(), 1);
+        assert_eq!(ddl_progress[0].id, 2);
+
+        // Dropping the notifiers without notifying aborts the creating procedure.
+        drop(command);
+        assert!(finished_rx_1.await.is_err());
+
+        // The other mview is still tracked and can be finished.
+        assert!(tracker.update(&progress(2, true, 100)).is_some());
+    }
+}
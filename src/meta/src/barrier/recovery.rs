@@ -177,11 +177,16 @@ where
     /// wait until get a sufficient amount of new CNs
     /// return "map of `ActorId` in expired CN to new CN id" and "map of `WorkerId` to
     /// `WorkerNode` struct in new CNs"
+    ///
+    /// A new CN with at least as many parallel units as the expired CN has in use is preferred, so
+    /// that the migrated actors keep running on distinct parallel units. If no new CN qualifies,
+    /// the one with the most parallel units is chosen, and the migrated actors share them.
     async fn get_migrate_map_plan(
         &self,
         info: &BarrierActorInfo,
         expired_workers: &[WorkerId],
     ) -> (HashMap<ActorId, WorkerId>, HashMap<WorkerId, WorkerNode>) {
+        let worker_parallel_units = self.fragment_manager.worker_parallel_units().await;
         let mut cur = 0;
         let mut migrate_map = HashMap::new();
         let mut node_map = HashMap::new();
@@ -190,13 +195,38 @@ where
                 .cluster_manager
                 .list_worker_node(WorkerType::ComputeNode, Some(State::Running))
                 .await;
-            let new_nodes = current_nodes
+            let mut new_nodes = current_nodes
                 .into_iter()
                 .filter(|node| {
                     !info.node_map.contains_key(&node.id) && !node_map.contains_key(&node.id)
                 })
                 .collect_vec();
-            for new_node in new_nodes {
+            while !new_nodes.is_empty() {
+                let required_parallel_units = worker_parallel_units
+                    .get(&expired_workers[cur])
+                    .map_or(0, |parallel_units| parallel_units.len());
+                let index = match new_nodes
+                    .iter()
+                    .position(|node| node.parallel_units.len() >= required_parallel_units)
+                {
+                    Some(index) => index,
+                    None => {
+                        let index = new_nodes
+                            .iter()
+                            .position_max_by_key(|node| node.parallel_units.len())
+                            .unwrap();
+                        debug!(
+                            "no new worker has {} parallel units to migrate the actors of expired \
+                             worker {}, choose worker {} with {} parallel units",
+                            required_parallel_units,
+                            expired_workers[cur],
+                            new_nodes[index].id,
+                            new_nodes[index].parallel_units.len()
+                        );
+                        index
+                    }
+                };
+                let new_node = new_nodes.swap_remove(index);
                 let actors = info.actor_map.get(&expired_workers[cur]).unwrap();
                 for actor in actors {
                     migrate_map.insert(*actor, new_node.id);
@@ -16,16 +16,19 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
-use axum::extract::{Extension, Path};
+use axum::extract::{Extension, Path, Query};
 use axum::http::{Method, StatusCode};
 use axum::response::{Html, IntoResponse};
 use axum::routing::{get, get_service};
 use axum::Router;
+use piestream_rpc_client::ComputeClientPoolRef;
 use tower::ServiceBuilder;
 use tower_http::add_extension::AddExtensionLayer;
 use tower_http::cors::{self, CorsLayer};
 use tower_http::services::ServeDir;
 
+use crate::barrier::BarrierMonitorRef;
+use crate::hummock::HummockManagerRef;
 use crate::manager::{ClusterManagerRef, FragmentManagerRef};
 use crate::storage::MetaStore;
 
@@ -34,6 +37,9 @@ pub struct DashboardService<S: MetaStore> {
     pub dashboard_addr: SocketAddr,
     pub cluster_manager: ClusterManagerRef<S>,
     pub fragment_manager: FragmentManagerRef<S>,
+    pub hummock_manager: HummockManagerRef<S>,
+    pub barrier_monitor: BarrierMonitorRef,
+    pub compute_clients: ComputeClientPoolRef,
 
     // TODO: replace with catalog manager.
     pub meta_store: Arc<S>,
@@ -42,15 +48,23 @@ pub struct DashboardService<S: MetaStore> {
 pub type Service<S> = Arc<DashboardService<S>>;
 
 mod handlers {
+    use std::collections::{BTreeMap, HashMap};
+    use std::time::{Duration, Instant};
+
     use axum::Json;
     use itertools::Itertools;
+    use piestream_common::util::addr::HostAddr;
     use piestream_pb::catalog::{Source, Table};
     use piestream_pb::common::WorkerNode;
+    use piestream_pb::hummock::{Level, LevelType};
     use piestream_pb::meta::{ActorLocation, TableFragments as ProstTableFragments};
+    use piestream_pb::monitor_service::{ActorStats, StackTraceResponse};
     use piestream_pb::stream_plan::StreamActor;
+    use serde::{Deserialize, Serialize};
     use serde_json::json;
 
     use super::*;
+    use crate::barrier::{FinishedBarrier, InFlightBarrier};
     use crate::model::TableFragments;
 
     pub struct DashboardError(anyhow::Error);
@@ -158,6 +172,216 @@ mod handlers {
             .collect_vec();
         Ok(Json(table_fragments))
     }
+
+    /// The default and maximum sampling interval of [`list_fragment_stats`].
+    const DEFAULT_STATS_INTERVAL_MS: u64 = 1000;
+    const MAX_STATS_INTERVAL_MS: u64 = 10000;
+
+    #[derive(Deserialize)]
+    pub struct FragmentStatsParams {
+        interval_ms: Option<u64>,
+    }
+
+    #[derive(Serialize)]
+    pub struct FragmentStats {
+        table_id: u32,
+        fragment_id: u32,
+        actor_count: usize,
+        in_records_per_sec: f64,
+        out_records_per_sec: f64,
+        /// The ratio of time the actors are blocked by the downstream, in `[0, 1]`.
+        backpressure_ratio: f64,
+    }
+
+    /// Lists the running compute nodes. Nodes that are starting or have been deleted can't serve
+    /// the RPCs, which would fail the whole request.
+    async fn collect_compute_nodes<S: MetaStore>(srv: &Service<S>) -> Vec<WorkerNode> {
+        use piestream_pb::common::worker_node::State;
+        use piestream_pb::common::WorkerType;
+
+        srv.cluster_manager
+            .list_worker_node(WorkerType::ComputeNode, Some(State::Running))
+            .await
+    }
+
+    async fn collect_actor_stats<S: MetaStore>(
+        srv: &Service<S>,
+    ) -> Result<HashMap<u32, ActorStats>> {
+        let mut all_actor_stats = HashMap::new();
+        for node in collect_compute_nodes(srv).await {
+            let client = srv.compute_clients.get(&node).await.map_err(err)?;
+            let actor_stats = client.actor_stats().await.map_err(err)?.actor_stats;
+            all_actor_stats.extend(actor_stats);
+        }
+        Ok(all_actor_stats)
+    }
+
+    /// Samples the statistics of all actors twice with the given interval, and reports the
+    /// throughput and backpressure of each fragment during the interval.
+    pub async fn list_fragment_stats<S: MetaStore>(
+        Query(params): Query<FragmentStatsParams>,
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<Vec<FragmentStats>>> {
+        let interval_ms = params
+            .interval_ms
+            .unwrap_or(DEFAULT_STATS_INTERVAL_MS)
+            .clamp(1, MAX_STATS_INTERVAL_MS);
+
+        let start = Instant::now();
+        let before = collect_actor_stats(&srv).await?;
+        tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+        let after = collect_actor_stats(&srv).await?;
+        let elapsed_secs = start.elapsed().as_secs_f64();
+
+        let table_fragments = srv
+            .fragment_manager
+            .list_table_fragments()
+            .await
+            .map_err(err)?;
+        let stats = table_fragments
+            .iter()
+            .flat_map(|table_fragments| {
+                let table_id = table_fragments.table_id().table_id();
+                table_fragments
+                    .fragments()
+                    .into_iter()
+                    .map(move |fragment| {
+                        let mut actor_count = 0;
+                        let (mut in_records, mut out_records, mut blocking_ns) = (0, 0, 0);
+                        for actor in &fragment.actors {
+                            if let (Some(before), Some(after)) =
+                                (before.get(&actor.actor_id), after.get(&actor.actor_id))
+                            {
+                                actor_count += 1;
+                                in_records +=
+                                    after.in_record_count.saturating_sub(before.in_record_count);
+                                out_records += after
+                                    .out_record_count
+                                    .saturating_sub(before.out_record_count);
+                                blocking_ns += after
+                                    .output_blocking_duration_ns
+                                    .saturating_sub(before.output_blocking_duration_ns);
+                            }
+                        }
+                        let backpressure_ratio = if actor_count == 0 {
+                            0.0
+                        } else {
+                            (blocking_ns as f64 / 1e9 / elapsed_secs / actor_count as f64).min(1.0)
+                        };
+                        FragmentStats {
+                            table_id,
+                            fragment_id: fragment.fragment_id,
+                            actor_count,
+                            in_records_per_sec: in_records as f64 / elapsed_secs,
+                            out_records_per_sec: out_records as f64 / elapsed_secs,
+                            backpressure_ratio,
+                        }
+                    })
+            })
+            .collect_vec();
+
+        Ok(Json(stats))
+    }
+
+    pub async fn list_in_flight_barriers<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<Vec<InFlightBarrier>>> {
+        Ok(Json(srv.barrier_monitor.in_flight_barriers()))
+    }
+
+    pub async fn list_barrier_history<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<Vec<FinishedBarrier>>> {
+        Ok(Json(srv.barrier_monitor.barrier_history()))
+    }
+
+    #[derive(Serialize)]
+    pub struct LevelShape {
+        level_idx: u32,
+        level_type: String,
+        sub_level_id: u64,
+        sst_count: usize,
+        total_file_size: u64,
+    }
+
+    impl From<&Level> for LevelShape {
+        fn from(level: &Level) -> Self {
+            Self {
+                level_idx: level.level_idx,
+                level_type: LevelType::from_i32(level.level_type)
+                    .map(|t| t.as_str_name().to_string())
+                    .unwrap_or_default(),
+                sub_level_id: level.sub_level_id,
+                sst_count: level.table_infos.len(),
+                total_file_size: level.total_file_size,
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    pub struct CompactionGroupShape {
+        compaction_group_id: u64,
+        l0_sub_levels: Vec<LevelShape>,
+        levels: Vec<LevelShape>,
+    }
+
+    /// Lists the LSM shape of each compaction group in the current Hummock version.
+    pub async fn list_compaction_group_shapes<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<Vec<CompactionGroupShape>>> {
+        let version = srv.hummock_manager.get_current_version().await;
+        let shapes = version
+            .levels
+            .iter()
+            .sorted_by_key(|(id, _)| **id)
+            .map(|(id, levels)| CompactionGroupShape {
+                compaction_group_id: *id,
+                l0_sub_levels: levels
+                    .l0
+                    .iter()
+                    .flat_map(|l0| l0.sub_levels.iter().map(LevelShape::from))
+                    .collect(),
+                levels: levels.levels.iter().map(LevelShape::from).collect(),
+            })
+            .collect();
+        Ok(Json(shapes))
+    }
+
+    #[derive(Serialize)]
+    pub struct StackTraces {
+        actor_traces: BTreeMap<u32, String>,
+        rpc_traces: BTreeMap<String, String>,
+    }
+
+    /// Gathers the async stack traces of actors and RPCs from all compute nodes.
+    pub async fn list_stack_traces<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<StackTraces>> {
+        let mut actor_traces = BTreeMap::new();
+        let mut rpc_traces = BTreeMap::new();
+        for node in collect_compute_nodes(&srv).await {
+            let client = srv.compute_clients.get(&node).await.map_err(err)?;
+            let StackTraceResponse {
+                actor_traces: node_actor_traces,
+                rpc_traces: node_rpc_traces,
+            } = client.stack_trace().await.map_err(err)?;
+            actor_traces.extend(node_actor_traces);
+            let host = node
+                .host
+                .as_ref()
+                .map(HostAddr::from)
+                .ok_or_else(|| err(anyhow!("host of worker {} not found", node.id)))?;
+            rpc_traces.extend(
+                node_rpc_traces
+                    .into_iter()
+                    .map(|(k, v)| (format!("{} ({})", host, k), v)),
+            );
+        }
+        Ok(Json(StackTraces {
+            actor_traces,
+            rpc_traces,
+        }))
+    }
 }
 
 impl<S> DashboardService<S>
@@ -175,6 +399,11 @@ where
             .route("/fragments2", get(list_fragments::<S>))
             .route("/materialized_views", get(list_materialized_views::<S>))
             .route("/sources", get(list_sources::<S>))
+            .route("/fragment_stats", get(list_fragment_stats::<S>))
+            .route("/barriers/in_flight", get(list_in_flight_barriers::<S>))
+            .route("/barriers/history", get(list_barrier_history::<S>))
+            .route("/compaction_groups", get(list_compaction_group_shapes::<S>))
+            .route("/stack_traces", get(list_stack_traces::<S>))
             .layer(
                 ServiceBuilder::new()
                     .layer(AddExtensionLayer::new(srv.clone()))
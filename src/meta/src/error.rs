@@ -15,6 +15,7 @@
 use std::backtrace::Backtrace;
 use std::sync::Arc;
 
+use piestream_object_store::object::ObjectError;
 use piestream_pb::ProstFieldNotFound;
 use piestream_rpc_client::error::RpcError;
 
@@ -129,6 +130,12 @@ impl From<RpcError> for MetaError {
     }
 }
 
+impl From<ObjectError> for MetaError {
+    fn from(e: ObjectError) -> Self {
+        MetaErrorInner::Internal(e.into()).into()
+    }
+}
+
 impl From<anyhow::Error> for MetaError {
     fn from(a: anyhow::Error) -> Self {
         MetaErrorInner::Internal(a).into()
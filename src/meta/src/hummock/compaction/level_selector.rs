@@ -19,6 +19,7 @@
 
 use std::sync::Arc;
 
+use piestream_common::config::constant::hummock::CompactionFilterFlag;
 use piestream_hummock_sdk::HummockCompactionTaskId;
 use piestream_pb::hummock::hummock_version::Levels;
 use piestream_pb::hummock::CompactionConfig;
@@ -29,7 +30,8 @@ use crate::hummock::compaction::min_overlap_compaction_picker::MinOverlappingPic
 use crate::hummock::compaction::overlap_strategy::OverlapStrategy;
 use crate::hummock::compaction::{
     create_overlap_strategy, CompactionInput, CompactionPicker, CompactionTask,
-    LevelCompactionPicker, ManualCompactionOption, TierCompactionPicker,
+    LevelCompactionPicker, ManualCompactionOption, ReclaimContext, SpaceReclaimCompactionPicker,
+    TierCompactionPicker, TtlReclaimCompactionPicker,
 };
 use crate::hummock::level_handler::LevelHandler;
 
@@ -72,6 +74,8 @@ pub struct SelectContext {
 pub struct DynamicLevelSelector {
     config: Arc<CompactionConfig>,
     overlap_strategy: Arc<dyn OverlapStrategy>,
+    /// Enables picking compactions to reclaim space when no level needs compaction.
+    reclaim_context: Option<Arc<ReclaimContext>>,
 }
 
 impl Default for DynamicLevelSelector {
@@ -87,9 +91,15 @@ impl DynamicLevelSelector {
         DynamicLevelSelector {
             config,
             overlap_strategy,
+            reclaim_context: None,
         }
     }
 
+    pub fn with_reclaim_context(mut self, reclaim_context: Arc<ReclaimContext>) -> Self {
+        self.reclaim_context = Some(reclaim_context);
+        self
+    }
+
     fn create_compaction_picker(
         &self,
         select_level: usize,
@@ -180,6 +190,19 @@ impl DynamicLevelSelector {
         ctx
     }
 
+    /// Returns the estimated bytes to compact before the LSM tree reaches its target shape, that
+    /// is, all the bytes in L0 plus the bytes exceeding the target size of each level.
+    pub fn compaction_pending_bytes(&self, levels: &Levels) -> u64 {
+        let ctx = self.calculate_level_base_size(levels);
+        let l0_bytes = levels.l0.as_ref().map(|l0| l0.total_file_size).unwrap_or(0);
+        levels.levels.iter().fold(l0_bytes, |pending_bytes, level| {
+            pending_bytes
+                + level
+                    .total_file_size
+                    .saturating_sub(ctx.level_max_bytes[level.level_idx as usize])
+        })
+    }
+
     fn get_priority_levels(&self, levels: &Levels, handlers: &[LevelHandler]) -> SelectContext {
         let mut ctx = self.calculate_level_base_size(levels);
 
@@ -259,6 +282,46 @@ impl DynamicLevelSelector {
             target_file_size,
         }
     }
+
+    /// Picks SSTs full of dropped or expired data from the bottommost level upwards, which may
+    /// otherwise stay in the bottom levels for a long time.
+    fn pick_reclaim_compaction(
+        &self,
+        task_id: HummockCompactionTaskId,
+        levels: &Levels,
+        level_handlers: &mut [LevelHandler],
+        base_level: usize,
+    ) -> Option<CompactionTask> {
+        let reclaim_context = self.reclaim_context.as_ref()?;
+        let compaction_filter_flag =
+            CompactionFilterFlag::from_bits(self.config.compaction_filter_mask).unwrap_or_default();
+        for level in (base_level..=self.config.max_level as usize).rev() {
+            // Only pick SSTs whose garbage can be removed by the compaction filter, otherwise they
+            // would be picked again and again.
+            let mut pickers: Vec<Box<dyn CompactionPicker>> = vec![];
+            if compaction_filter_flag.contains(CompactionFilterFlag::STATE_CLEAN) {
+                pickers.push(Box::new(SpaceReclaimCompactionPicker::new(
+                    level,
+                    self.config.max_compaction_bytes,
+                    reclaim_context.clone(),
+                )));
+            }
+            if compaction_filter_flag.contains(CompactionFilterFlag::TTL) {
+                pickers.push(Box::new(TtlReclaimCompactionPicker::new(
+                    level,
+                    self.config.max_compaction_bytes,
+                    reclaim_context.clone(),
+                )));
+            }
+            for picker in pickers {
+                if let Some(ret) = picker.pick_compaction(levels, level_handlers) {
+                    ret.add_pending_task(task_id, level_handlers);
+                    return Some(self.create_compaction_task(ret, base_level));
+                }
+            }
+        }
+        None
+    }
 }
 
 impl LevelSelector for DynamicLevelSelector {
@@ -279,7 +342,7 @@ impl LevelSelector for DynamicLevelSelector {
         let ctx = self.get_priority_levels(levels, level_handlers);
         for (score, select_level, target_level) in ctx.score_levels {
             if score <= SCORE_BASE {
-                return None;
+                break;
             }
             let picker = self.create_compaction_picker(select_level, target_level);
             if let Some(ret) = picker.pick_compaction(levels, level_handlers) {
@@ -287,7 +350,7 @@ impl LevelSelector for DynamicLevelSelector {
                 return Some(self.create_compaction_task(ret, ctx.base_level));
             }
         }
-        None
+        self.pick_reclaim_compaction(task_id, levels, level_handlers, ctx.base_level)
     }
 
     fn manual_pick_compaction(
@@ -328,7 +391,6 @@ pub mod tests {
     use std::ops::Range;
 
     use itertools::Itertools;
-    use piestream_common::config::constant::hummock::CompactionFilterFlag;
     use piestream_pb::hummock::compaction_config::CompactionMode;
     use piestream_pb::hummock::{KeyRange, Level, LevelType, OverlappingLevel, SstableInfo};
 
@@ -392,6 +454,8 @@ pub mod tests {
             meta_offset: 0,
             stale_key_count: 0,
             total_key_count: 0,
+            blob_ids: vec![],
+            table_stats: Default::default(),
         }
     }
 
@@ -530,6 +594,8 @@ pub mod tests {
         assert_eq!(ctx.level_max_bytes[2], 120);
         assert_eq!(ctx.level_max_bytes[3], 600);
         assert_eq!(ctx.level_max_bytes[4], 3000);
+        // All of L0 is pending.
+        assert_eq!(selector.compaction_pending_bytes(&levels), 600);
 
         levels.l0.as_mut().unwrap().sub_levels.clear();
         levels.l0.as_mut().unwrap().total_file_size = 0;
@@ -546,6 +612,8 @@ pub mod tests {
         assert_eq!(ctx.level_max_bytes[2], 120);
         assert_eq!(ctx.level_max_bytes[3], 600);
         assert_eq!(ctx.level_max_bytes[4], 3000);
+        // L1 exceeds its target size.
+        assert_eq!(selector.compaction_pending_bytes(&levels), 500);
     }
 
     #[test]
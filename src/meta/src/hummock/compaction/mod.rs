@@ -18,9 +18,15 @@ mod manual_compaction_picker;
 mod min_overlap_compaction_picker;
 mod overlap_strategy;
 mod prost_type;
+mod space_reclaim_compaction_picker;
+mod task_split;
 mod tier_compaction_picker;
 use piestream_hummock_sdk::prost_key_range::KeyRangeExt;
 use piestream_pb::hummock::compact_task::TaskStatus;
+pub use space_reclaim_compaction_picker::{
+    SpaceReclaimCompactionPicker, TtlReclaimCompactionPicker,
+};
+pub use task_split::split_key_ranges;
 pub use tier_compaction_picker::TierCompactionPicker;
 mod base_level_compaction_picker;
 use std::collections::{HashMap, HashSet};
@@ -91,6 +97,17 @@ pub struct CompactionTask {
     pub target_file_size: u64,
 }
 
+/// Tables of a compaction group, used to pick compactions that reclaim the space of dropped tables
+/// and expired data.
+#[derive(Clone, Debug, Default)]
+pub struct ReclaimContext {
+    /// Tables that have not been dropped.
+    pub existing_table_ids: HashSet<u32>,
+    /// Retention of the tables with TTL.
+    pub table_retention_seconds: HashMap<u32, u32>,
+    pub current_epoch_time: u64,
+}
+
 pub fn create_overlap_strategy(compaction_mode: CompactionMode) -> Arc<dyn OverlapStrategy> {
     match compaction_mode {
         CompactionMode::Range => Arc::new(RangeOverlapStrategy::default()),
@@ -117,6 +134,7 @@ impl CompactStatus {
         compaction_group_id: CompactionGroupId,
         manual_compaction_option: Option<ManualCompactionOption>,
         compaction_config: CompactionConfig,
+        reclaim_context: ReclaimContext,
     ) -> Option<CompactTask> {
         // When we compact the files, we must make the result of compaction meet the following
         // conditions, for any user key, the epoch of it in the file existing in the lower
@@ -130,7 +148,7 @@ impl CompactStatus {
                 compaction_config,
             )?
         } else {
-            self.pick_compaction(levels, task_id, compaction_config)?
+            self.pick_compaction(levels, task_id, compaction_config, reclaim_context)?
         };
 
         let select_level_id = ret.input.input_levels[0].level_idx;
@@ -139,6 +157,7 @@ impl CompactStatus {
         let compression_algorithm = match ret.compression_algorithm.as_str() {
             "Lz4" => 1,
             "Zstd" => 2,
+            "ZstdDictionary" => 3,
             _ => 0,
         };
 
@@ -161,6 +180,7 @@ impl CompactStatus {
             table_options: HashMap::default(),
             current_epoch_time: 0,
             target_sub_level_id: ret.input.target_sub_level_id,
+            parent_task_id: 0,
         };
         Some(compact_task)
     }
@@ -193,8 +213,9 @@ impl CompactStatus {
         levels: &Levels,
         task_id: HummockCompactionTaskId,
         compaction_config: CompactionConfig,
+        reclaim_context: ReclaimContext,
     ) -> Option<CompactionTask> {
-        self.create_level_selector(compaction_config)
+        self.create_level_selector(compaction_config, Some(reclaim_context))
             .pick_compaction(task_id, levels, &mut self.level_handlers)
     }
 
@@ -207,7 +228,7 @@ impl CompactStatus {
     ) -> Option<CompactionTask> {
         // manual_compaction no need to select level
         // level determined by option
-        self.create_level_selector(compaction_config)
+        self.create_level_selector(compaction_config, None)
             .manual_pick_compaction(
                 task_id,
                 levels,
@@ -240,16 +261,31 @@ impl CompactStatus {
         self.compaction_group_id
     }
 
+    /// Returns the estimated bytes to compact before `levels` reaches the shape targeted by
+    /// `compaction_config`.
+    pub fn compaction_pending_bytes(levels: &Levels, compaction_config: CompactionConfig) -> u64 {
+        let overlap_strategy = create_overlap_strategy(compaction_config.compaction_mode());
+        DynamicLevelSelector::new(Arc::new(compaction_config), overlap_strategy)
+            .compaction_pending_bytes(levels)
+    }
+
     /// Creates a level selector.
     ///
     /// The method should be lightweight because we recreate a level selector everytime so that the
     /// latest compaction config is applied to it.
-    fn create_level_selector(&self, compaction_config: CompactionConfig) -> Box<dyn LevelSelector> {
+    fn create_level_selector(
+        &self,
+        compaction_config: CompactionConfig,
+        reclaim_context: Option<ReclaimContext>,
+    ) -> Box<dyn LevelSelector> {
         let overlap_strategy = create_overlap_strategy(compaction_config.compaction_mode());
-        Box::new(DynamicLevelSelector::new(
-            Arc::new(compaction_config),
-            overlap_strategy,
-        ))
+        let selector = DynamicLevelSelector::new(Arc::new(compaction_config), overlap_strategy);
+        match reclaim_context {
+            Some(reclaim_context) => {
+                Box::new(selector.with_reclaim_context(Arc::new(reclaim_context)))
+            }
+            None => Box::new(selector),
+        }
     }
 }
 
@@ -0,0 +1,301 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use piestream_common::util::epoch::Epoch;
+use piestream_hummock_sdk::compaction_group::hummock_version_ext::HummockLevelsExt;
+use piestream_pb::hummock::hummock_version::Levels;
+use piestream_pb::hummock::{InputLevel, LevelType, SstableInfo};
+
+use super::{CompactionInput, CompactionPicker, ReclaimContext};
+use crate::hummock::level_handler::LevelHandler;
+
+/// An SST is picked by [`SpaceReclaimCompactionPicker`] if at least this percentage of its data
+/// belongs to dropped tables.
+const SPACE_RECLAIM_RATIO_THRESHOLD: u64 = 50;
+
+/// Picks SSTs whose data mostly belongs to dropped tables, and compacts them into the same level
+/// so that the data is removed by the compaction filter.
+pub struct SpaceReclaimCompactionPicker {
+    level: usize,
+    max_select_bytes: u64,
+    reclaim_context: Arc<ReclaimContext>,
+}
+
+impl SpaceReclaimCompactionPicker {
+    pub fn new(level: usize, max_select_bytes: u64, reclaim_context: Arc<ReclaimContext>) -> Self {
+        Self {
+            level,
+            max_select_bytes,
+            reclaim_context,
+        }
+    }
+
+    fn is_dominated_by_dropped_tables(&self, sst: &SstableInfo) -> bool {
+        let existing_table_ids = &self.reclaim_context.existing_table_ids;
+        if sst.table_stats.is_empty() {
+            // SSTs written before table statistics were tracked.
+            return !sst.table_ids.is_empty()
+                && sst
+                    .table_ids
+                    .iter()
+                    .all(|table_id| !existing_table_ids.contains(table_id));
+        }
+        let mut total_size = 0;
+        let mut dropped_size = 0;
+        for (table_id, stats) in &sst.table_stats {
+            let size = stats.total_key_size + stats.total_value_size;
+            total_size += size;
+            if !existing_table_ids.contains(table_id) {
+                dropped_size += size;
+            }
+        }
+        total_size > 0 && dropped_size * 100 >= total_size * SPACE_RECLAIM_RATIO_THRESHOLD
+    }
+}
+
+impl CompactionPicker for SpaceReclaimCompactionPicker {
+    fn pick_compaction(
+        &self,
+        levels: &Levels,
+        level_handlers: &[LevelHandler],
+    ) -> Option<CompactionInput> {
+        pick_reclaim_tables(
+            self.level,
+            self.max_select_bytes,
+            levels,
+            level_handlers,
+            |sst| self.is_dominated_by_dropped_tables(sst),
+        )
+    }
+}
+
+/// Picks SSTs containing data older than the retention of its table, and compacts them into the
+/// same level so that the expired data is removed by the compaction filter.
+pub struct TtlReclaimCompactionPicker {
+    level: usize,
+    max_select_bytes: u64,
+    reclaim_context: Arc<ReclaimContext>,
+}
+
+impl TtlReclaimCompactionPicker {
+    pub fn new(level: usize, max_select_bytes: u64, reclaim_context: Arc<ReclaimContext>) -> Self {
+        Self {
+            level,
+            max_select_bytes,
+            reclaim_context,
+        }
+    }
+
+    fn has_expired_data(&self, sst: &SstableInfo) -> bool {
+        let current_epoch = Epoch(self.reclaim_context.current_epoch_time);
+        sst.table_stats.iter().any(|(table_id, stats)| {
+            match self.reclaim_context.table_retention_seconds.get(table_id) {
+                Some(retention_seconds) => {
+                    let expire_epoch = current_epoch.subtract_ms(*retention_seconds as u64 * 1000);
+                    Epoch(stats.min_epoch) <= expire_epoch
+                }
+                None => false,
+            }
+        })
+    }
+}
+
+impl CompactionPicker for TtlReclaimCompactionPicker {
+    fn pick_compaction(
+        &self,
+        levels: &Levels,
+        level_handlers: &[LevelHandler],
+    ) -> Option<CompactionInput> {
+        pick_reclaim_tables(
+            self.level,
+            self.max_select_bytes,
+            levels,
+            level_handlers,
+            |sst| self.has_expired_data(sst),
+        )
+    }
+}
+
+/// Selects the first run of adjacent SSTs in `level` that all satisfy `need_reclaim`. The run is
+/// compacted into the same level, so it must be contiguous to keep the level non-overlapping.
+fn pick_reclaim_tables(
+    level: usize,
+    max_select_bytes: u64,
+    levels: &Levels,
+    level_handlers: &[LevelHandler],
+    need_reclaim: impl Fn(&SstableInfo) -> bool,
+) -> Option<CompactionInput> {
+    assert!(level > 0);
+    // The output of a pending task from the upper levels may fall in the key range of the picked
+    // SSTs.
+    if level_handlers[..level]
+        .iter()
+        .any(|handler| handler.get_pending_output_file_size(level as u32) > 0)
+    {
+        return None;
+    }
+    let mut select_input_ssts = vec![];
+    let mut select_file_size = 0;
+    for sst in &levels.get_level(level).table_infos {
+        if level_handlers[level].is_pending_compact(&sst.id) || !need_reclaim(sst) {
+            if select_input_ssts.is_empty() {
+                continue;
+            }
+            break;
+        }
+        if !select_input_ssts.is_empty() && select_file_size + sst.file_size > max_select_bytes {
+            break;
+        }
+        select_file_size += sst.file_size;
+        select_input_ssts.push(sst.clone());
+    }
+    if select_input_ssts.is_empty() {
+        return None;
+    }
+    Some(CompactionInput {
+        input_levels: vec![
+            InputLevel {
+                level_idx: level as u32,
+                level_type: LevelType::Nonoverlapping as i32,
+                table_infos: select_input_ssts,
+            },
+            InputLevel {
+                level_idx: level as u32,
+                level_type: LevelType::Nonoverlapping as i32,
+                table_infos: vec![],
+            },
+        ],
+        target_level: level,
+        target_sub_level_id: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use piestream_pb::hummock::TableStats;
+
+    use super::*;
+    use crate::hummock::compaction::level_selector::tests::{
+        generate_l0_nonoverlapping_sublevels, generate_level, generate_table,
+    };
+
+    fn generate_table_with_stats(id: u64, left: usize, stats: Vec<(u32, u64, u64)>) -> SstableInfo {
+        let mut sst = generate_table(id, 1, left, left + 99, 1);
+        for (table_id, size, min_epoch) in stats {
+            sst.table_ids.push(table_id);
+            sst.table_stats.insert(
+                table_id,
+                TableStats {
+                    total_key_count: 1,
+                    total_key_size: size,
+                    total_value_size: size,
+                    min_epoch,
+                },
+            );
+        }
+        sst
+    }
+
+    #[test]
+    fn test_space_reclaim_picker() {
+        let reclaim_context = Arc::new(ReclaimContext {
+            existing_table_ids: HashSet::from([2]),
+            ..Default::default()
+        });
+        let levels = Levels {
+            levels: vec![generate_level(
+                1,
+                vec![
+                    generate_table_with_stats(0, 0, vec![(1, 100, 1), (2, 10, 1)]),
+                    generate_table_with_stats(1, 100, vec![(1, 100, 1)]),
+                    generate_table_with_stats(2, 200, vec![(1, 10, 1), (2, 100, 1)]),
+                    generate_table_with_stats(3, 300, vec![(1, 100, 1)]),
+                ],
+            )],
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+        let mut level_handlers = vec![LevelHandler::new(0), LevelHandler::new(1)];
+        let picker = SpaceReclaimCompactionPicker::new(1, 10000, reclaim_context.clone());
+
+        let ret = picker.pick_compaction(&levels, &level_handlers).unwrap();
+        assert_eq!(ret.target_level, 1);
+        assert_eq!(ret.input_levels[0].level_idx, 1);
+        assert_eq!(ret.input_levels[1].level_idx, 1);
+        assert!(ret.input_levels[1].table_infos.is_empty());
+        let ids = ret.input_levels[0]
+            .table_infos
+            .iter()
+            .map(|sst| sst.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![0, 1]);
+        ret.add_pending_task(0, &mut level_handlers);
+
+        let ret = picker.pick_compaction(&levels, &level_handlers).unwrap();
+        assert_eq!(ret.input_levels[0].table_infos.len(), 1);
+        assert_eq!(ret.input_levels[0].table_infos[0].id, 3);
+        ret.add_pending_task(1, &mut level_handlers);
+        assert!(picker.pick_compaction(&levels, &level_handlers).is_none());
+
+        // The selected SSTs are limited by `max_select_bytes`.
+        let mut level_handlers = vec![LevelHandler::new(0), LevelHandler::new(1)];
+        let picker = SpaceReclaimCompactionPicker::new(1, 100, reclaim_context);
+        let ret = picker.pick_compaction(&levels, &level_handlers).unwrap();
+        assert_eq!(ret.input_levels[0].table_infos.len(), 1);
+        assert_eq!(ret.input_levels[0].table_infos[0].id, 0);
+
+        // Skip the level if a pending task of the upper levels outputs to it.
+        level_handlers[0].add_pending_task(2, 1, &[generate_table(4, 1, 500, 600, 1)]);
+        assert!(picker.pick_compaction(&levels, &level_handlers).is_none());
+    }
+
+    #[test]
+    fn test_ttl_reclaim_picker() {
+        let current_epoch = Epoch::from_physical_time(1_000_000);
+        let expired_epoch = current_epoch.subtract_ms(20_000).0;
+        let alive_epoch = current_epoch.subtract_ms(5_000).0;
+        let reclaim_context = Arc::new(ReclaimContext {
+            existing_table_ids: HashSet::from([1, 2]),
+            table_retention_seconds: HashMap::from([(1, 10)]),
+            current_epoch_time: current_epoch.0,
+        });
+        let levels = Levels {
+            levels: vec![generate_level(
+                1,
+                vec![
+                    generate_table_with_stats(0, 0, vec![(1, 100, alive_epoch)]),
+                    generate_table_with_stats(1, 100, vec![(2, 100, expired_epoch)]),
+                    generate_table_with_stats(
+                        2,
+                        200,
+                        vec![(1, 100, expired_epoch), (2, 100, alive_epoch)],
+                    ),
+                ],
+            )],
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+        let mut level_handlers = vec![LevelHandler::new(0), LevelHandler::new(1)];
+        let picker = TtlReclaimCompactionPicker::new(1, 10000, reclaim_context);
+
+        let ret = picker.pick_compaction(&levels, &level_handlers).unwrap();
+        assert_eq!(ret.target_level, 1);
+        assert_eq!(ret.input_levels[0].table_infos.len(), 1);
+        assert_eq!(ret.input_levels[0].table_infos[0].id, 2);
+        ret.add_pending_task(0, &mut level_handlers);
+        assert!(picker.pick_compaction(&levels, &level_handlers).is_none());
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use piestream_common::types::{VirtualNode, VIRTUAL_NODE_COUNT};
+use piestream_hummock_sdk::key::table_vnode_start_key;
+use piestream_hummock_sdk::prost_key_range::KeyRangeExt;
+use piestream_pb::hummock::{CompactTask, KeyRange};
+
+/// Splits the key range of `compact_task` at vnode boundaries into at most `split_num` ranges,
+/// each of which holds about the same size of input.
+///
+/// The input size of each table is estimated with the table stats of the input SSTs. As keys are
+/// distributed evenly among vnodes, each vnode is assumed to hold the same share of its table.
+pub fn split_key_ranges(compact_task: &CompactTask, split_num: usize) -> Vec<KeyRange> {
+    let table_sizes = estimate_table_sizes(compact_task);
+    let total_size = table_sizes.values().sum::<u64>();
+    let mut key_ranges = vec![KeyRange::new(vec![], vec![])];
+    if split_num <= 1 || total_size == 0 {
+        return key_ranges;
+    }
+    let split_size = total_size / split_num as u64;
+    let mut accumulated_size = 0;
+    for (table_id, table_size) in table_sizes {
+        for vnode in 0..VIRTUAL_NODE_COUNT {
+            if key_ranges.len() == split_num {
+                return key_ranges;
+            }
+            let size_before =
+                accumulated_size + table_size * vnode as u64 / VIRTUAL_NODE_COUNT as u64;
+            if size_before > 0 && size_before >= split_size * key_ranges.len() as u64 {
+                let key = table_vnode_start_key(table_id, vnode as VirtualNode);
+                key_ranges.last_mut().unwrap().right = key.clone();
+                key_ranges.push(KeyRange::new(key, vec![]));
+            }
+        }
+        accumulated_size += table_size;
+    }
+    key_ranges
+}
+
+/// Returns the estimated input size of each table in `compact_task`, ordered by table id, which
+/// is also the order of their keys.
+fn estimate_table_sizes(compact_task: &CompactTask) -> BTreeMap<u32, u64> {
+    let mut table_sizes = BTreeMap::new();
+    for sst in compact_task
+        .input_ssts
+        .iter()
+        .flat_map(|level| level.table_infos.iter())
+    {
+        if sst.table_stats.is_empty() {
+            // Table stats are missing in SSTs written by older versions, in which case the SST is
+            // divided evenly among its tables.
+            if sst.table_ids.is_empty() {
+                continue;
+            }
+            let size = sst.file_size / sst.table_ids.len() as u64;
+            for table_id in &sst.table_ids {
+                *table_sizes.entry(*table_id).or_default() += size;
+            }
+        } else {
+            for (table_id, stats) in &sst.table_stats {
+                *table_sizes.entry(*table_id).or_default() +=
+                    stats.total_key_size + stats.total_value_size;
+            }
+        }
+    }
+    table_sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_pb::hummock::{InputLevel, SstableInfo, TableStats};
+
+    use super::*;
+
+    fn generate_sst(id: u64, table_sizes: Vec<(u32, u64)>) -> SstableInfo {
+        let mut sst = SstableInfo {
+            id,
+            ..Default::default()
+        };
+        for (table_id, size) in table_sizes {
+            sst.table_ids.push(table_id);
+            sst.file_size += size;
+            sst.table_stats.insert(
+                table_id,
+                TableStats {
+                    total_key_count: 1,
+                    total_key_size: 0,
+                    total_value_size: size,
+                    min_epoch: 0,
+                },
+            );
+        }
+        sst
+    }
+
+    fn generate_task(ssts: Vec<SstableInfo>) -> CompactTask {
+        CompactTask {
+            input_ssts: vec![InputLevel {
+                level_idx: 0,
+                table_infos: ssts,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_split_key_ranges() {
+        let task = generate_task(vec![
+            generate_sst(1, vec![(1, 1024), (2, 1024)]),
+            generate_sst(2, vec![(1, 1024), (2, 1024)]),
+        ]);
+        assert_eq!(split_key_ranges(&task, 1).len(), 1);
+
+        // Split at the start of table 2.
+        let key_ranges = split_key_ranges(&task, 2);
+        assert_eq!(
+            key_ranges,
+            vec![
+                KeyRange::new(vec![], table_vnode_start_key(2, 0)),
+                KeyRange::new(table_vnode_start_key(2, 0), vec![]),
+            ]
+        );
+
+        // Split each table in the middle of its vnodes.
+        let key_ranges = split_key_ranges(&task, 4);
+        assert_eq!(
+            key_ranges,
+            vec![
+                KeyRange::new(vec![], table_vnode_start_key(1, 128)),
+                KeyRange::new(table_vnode_start_key(1, 128), table_vnode_start_key(2, 0)),
+                KeyRange::new(table_vnode_start_key(2, 0), table_vnode_start_key(2, 128)),
+                KeyRange::new(table_vnode_start_key(2, 128), vec![]),
+            ]
+        );
+
+        // A small table doesn't get a range of its own.
+        let task = generate_task(vec![generate_sst(1, vec![(1, 4096), (2, 16)])]);
+        let key_ranges = split_key_ranges(&task, 2);
+        assert_eq!(
+            key_ranges,
+            vec![
+                KeyRange::new(vec![], table_vnode_start_key(1, 129)),
+                KeyRange::new(table_vnode_start_key(1, 129), vec![]),
+            ]
+        );
+
+        // Nothing to split without any input.
+        assert_eq!(split_key_ranges(&generate_task(vec![]), 2).len(), 1);
+    }
+}
@@ -420,6 +420,8 @@ mod tests {
                     meta_offset: 0,
                     stale_key_count: 0,
                     total_key_count: 0,
+                    blob_ids: vec![],
+                    table_stats: Default::default(),
                 }],
             }],
             splits: vec![],
@@ -437,6 +439,7 @@ mod tests {
             table_options: HashMap::default(),
             current_epoch_time: 0,
             target_sub_level_id: 0,
+            parent_task_id: 0,
         }
     }
 
@@ -251,49 +251,76 @@ where
             compact_task_to_string(&compact_task)
         );
 
-        // 2. Assign the compaction task to a compactor.
-        match self
-            .hummock_manager
-            .assign_compaction_task(&compact_task, compactor.context_id())
-            .await
-        {
-            Ok(_) => {
-                tracing::trace!(
-                    "Assigned compaction task. {}",
-                    compact_task_to_string(&compact_task)
+        // 2. Split the compaction task if it's too large for a single compactor.
+        let compact_tasks = match self.hummock_manager.split_compact_task(&compact_task).await {
+            Ok(compact_tasks) => compact_tasks,
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to split compaction task {}: {:#?}",
+                    compact_task.task_id,
+                    err
                 );
+                vec![compact_task.clone()]
             }
-            Err(err) => {
-                tracing::warn!("Failed to assign compaction task to compactor: {:#?}", err);
-                match err {
-                    Error::CompactionTaskAlreadyAssigned(_, _) => {
-                        panic!("Compaction scheduler is the only tokio task that can assign task.");
-                    }
-                    Error::InvalidContext(context_id) => {
-                        self.compactor_manager.remove_compactor(context_id);
-                        return ScheduleStatus::AssignFailure(compact_task);
-                    }
-                    _ => {
-                        return ScheduleStatus::AssignFailure(compact_task);
+        };
+
+        for (i, compact_task) in compact_tasks.into_iter().enumerate() {
+            // Subtasks other than the first one go to other idle compactors if there's any.
+            let compactor = if i == 0 {
+                compactor.clone()
+            } else {
+                self.hummock_manager
+                    .get_idle_compactor()
+                    .await
+                    .unwrap_or_else(|| compactor.clone())
+            };
+
+            // 3. Assign the compaction task to a compactor.
+            match self
+                .hummock_manager
+                .assign_compaction_task(&compact_task, compactor.context_id())
+                .await
+            {
+                Ok(_) => {
+                    tracing::trace!(
+                        "Assigned compaction task. {}",
+                        compact_task_to_string(&compact_task)
+                    );
+                }
+                Err(err) => {
+                    tracing::warn!("Failed to assign compaction task to compactor: {:#?}", err);
+                    match err {
+                        Error::CompactionTaskAlreadyAssigned(_, _) => {
+                            panic!(
+                                "Compaction scheduler is the only tokio task that can assign task."
+                            );
+                        }
+                        Error::InvalidContext(context_id) => {
+                            self.compactor_manager.remove_compactor(context_id);
+                            return ScheduleStatus::AssignFailure(compact_task);
+                        }
+                        _ => {
+                            return ScheduleStatus::AssignFailure(compact_task);
+                        }
                     }
                 }
-            }
-        };
+            };
 
-        // 3. Send the compaction task.
-        if let Err(e) = compactor
-            .send_task(Task::CompactTask(compact_task.clone()))
-            .await
-        {
-            tracing::warn!(
-                "Failed to send task {} to {}. {:#?}",
-                compact_task.task_id,
-                compactor.context_id(),
-                e
-            );
-            self.compactor_manager
-                .pause_compactor(compactor.context_id());
-            return ScheduleStatus::SendFailure(compact_task);
+            // 4. Send the compaction task.
+            if let Err(e) = compactor
+                .send_task(Task::CompactTask(compact_task.clone()))
+                .await
+            {
+                tracing::warn!(
+                    "Failed to send task {} to {}. {:#?}",
+                    compact_task.task_id,
+                    compactor.context_id(),
+                    e
+                );
+                self.compactor_manager
+                    .pause_compactor(compactor.context_id());
+                return ScheduleStatus::SendFailure(compact_task);
+            }
         }
 
         // Bypass reschedule if we want compaction scheduling in a deterministic way
@@ -301,7 +328,7 @@ where
             return ScheduleStatus::Ok;
         }
 
-        // 4. Reschedule it with best effort, in case there are more tasks.
+        // 5. Reschedule it with best effort, in case there are more tasks.
         if let Err(e) = sched_channel.try_sched_compaction(compaction_group) {
             tracing::error!(
                 "Failed to reschedule compaction group {} after sending new task {}. {:#?}",
@@ -517,7 +544,8 @@ mod tests {
         assert_eq!(hummock_manager.list_all_tasks_ids().await.len(), 1);
         // Notified to retry cancellation.
         let mut task_to_cancel = match rx.recv().await.unwrap() {
-            LocalNotification::WorkerNodeIsDeleted(_) => {
+            LocalNotification::WorkerNodeIsDeleted(_)
+            | LocalNotification::WorkerNodeActivated(_) => {
                 panic!()
             }
             LocalNotification::CompactionTaskNeedCancel(task_to_cancel) => task_to_cancel,
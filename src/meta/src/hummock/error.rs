@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use piestream_hummock_sdk::compaction_group::StateTableId;
-use piestream_hummock_sdk::{CompactionGroupId, HummockContextId, HummockSstableId};
+use piestream_hummock_sdk::{CompactionGroupId, HummockContextId, HummockEpoch, HummockSstableId};
 use thiserror::Error;
 
 use crate::model::MetadataModelError;
@@ -37,6 +37,8 @@ pub enum Error {
     InvalidCompactionGroupMember(StateTableId),
     #[error("SST {0} is invalid")]
     InvalidSst(HummockSstableId),
+    #[error("epoch {0} is older than safe epoch {1}")]
+    ExpiredEpoch(HummockEpoch, HummockEpoch),
     #[error(transparent)]
     Internal(anyhow::Error),
 }
@@ -12,19 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use function_name::named;
 use itertools::Itertools;
-use piestream_hummock_sdk::{CompactionGroupId, HummockCompactionTaskId, HummockContextId};
-use piestream_pb::hummock::{CompactTaskAssignment, CompactionConfig};
+use piestream_hummock_sdk::{
+    CompactionGroupId, HummockCompactionTaskId, HummockContextId, HummockEpoch,
+};
+use piestream_pb::hummock::compact_task::TaskStatus;
+use piestream_pb::hummock::{CompactTask, CompactTaskAssignment, CompactionConfig, SstableInfo};
 
-use crate::hummock::compaction::CompactStatus;
+use crate::hummock::compaction::{split_key_ranges, CompactStatus};
 use crate::hummock::error::Result;
-use crate::hummock::manager::read_lock;
+use crate::hummock::manager::{commit_multi_var, read_lock, write_lock};
 use crate::hummock::HummockManager;
-use crate::model::BTreeMapTransaction;
-use crate::storage::MetaStore;
+use crate::manager::IdCategory;
+use crate::model::{BTreeMapTransaction, ValTransaction};
+use crate::storage::{MetaStore, Transaction};
 
 #[derive(Default)]
 pub struct Compaction {
@@ -32,22 +36,103 @@ pub struct Compaction {
     pub compact_task_assignment: BTreeMap<HummockCompactionTaskId, CompactTaskAssignment>,
     /// `CompactStatus` of each compaction group
     pub compaction_statuses: BTreeMap<CompactionGroupId, CompactStatus>,
+    /// Compaction tasks split into subtasks, keyed by the id of the split task. They are kept in
+    /// memory only, so split tasks are cancelled on restart like other unassigned tasks.
+    pub split_tasks: HashMap<HummockCompactionTaskId, SplitCompactTask>,
+    /// Watermarks of the compaction tasks created but not yet reported, assigned or not. They are
+    /// kept in memory only, as unassigned tasks are cancelled on restart.
+    pub pending_task_watermarks: HashMap<HummockCompactionTaskId, HummockEpoch>,
 
     pub deterministic_mode: bool,
 }
 
+/// A compaction task split by key range into subtasks. Its output is committed at once after all
+/// the subtasks succeed.
+pub struct SplitCompactTask {
+    task: CompactTask,
+    /// Ids of the subtasks, in the order of their key ranges.
+    subtask_ids: Vec<HummockCompactionTaskId>,
+    /// Output of the subtasks that have succeeded.
+    subtask_outputs: HashMap<HummockCompactionTaskId, Vec<SstableInfo>>,
+}
+
+impl SplitCompactTask {
+    fn new(task: CompactTask, subtask_ids: Vec<HummockCompactionTaskId>) -> Self {
+        Self {
+            task,
+            subtask_ids,
+            subtask_outputs: HashMap::new(),
+        }
+    }
+
+    /// Records the result of `subtask`. Returns the split task if it's finished, that is, either
+    /// all the subtasks succeed or `subtask` fails.
+    fn report_subtask(&mut self, subtask: &CompactTask) -> Option<CompactTask> {
+        let mut task = self.task.clone();
+        if subtask.task_status() != TaskStatus::Success {
+            task.set_task_status(subtask.task_status());
+            return Some(task);
+        }
+        self.subtask_outputs
+            .insert(subtask.task_id, subtask.sorted_output_ssts.clone());
+        if self.subtask_outputs.len() < self.subtask_ids.len() {
+            return None;
+        }
+        task.sorted_output_ssts = self
+            .subtask_ids
+            .iter()
+            .flat_map(|task_id| self.subtask_outputs.remove(task_id).unwrap())
+            .collect_vec();
+        task.set_task_status(TaskStatus::Success);
+        Some(task)
+    }
+}
+
+/// Result of reporting a subtask, see `HummockManager::report_subtask`.
+pub enum SubtaskReport {
+    /// The subtask is not assigned to the reporter.
+    Rejected,
+    /// The split task is still running, or has already been finished by another subtask.
+    Accepted,
+    /// The split task is finished and should be reported as a whole.
+    SplitTaskFinished(CompactTask),
+}
+
 impl Compaction {
+    /// Returns the largest watermark of the compaction tasks in flight. The safe epoch is raised to
+    /// the watermark of each task once it finishes.
+    pub fn max_task_watermark(&self) -> Option<HummockEpoch> {
+        self.pending_task_watermarks
+            .values()
+            .cloned()
+            .chain(
+                self.compact_task_assignment
+                    .values()
+                    .filter_map(|assignment| assignment.compact_task.as_ref())
+                    .map(|task| task.watermark),
+            )
+            .max()
+    }
+
     /// Cancels all tasks assigned to `context_id`.
+    ///
+    /// A split task is cancelled as a whole if any of its subtasks is assigned to `context_id`. Its
+    /// other subtasks are left running, whose results are ignored when reported. The ids of the
+    /// cancelled split tasks are returned, and the caller should remove them from `split_tasks`
+    /// once the transactions are committed.
+    #[allow(clippy::type_complexity)]
     pub fn cancel_assigned_tasks_for_context_ids(
         &mut self,
         context_ids: &[HummockContextId],
     ) -> Result<(
         BTreeMapTransaction<'_, CompactionGroupId, CompactStatus>,
         BTreeMapTransaction<'_, HummockCompactionTaskId, CompactTaskAssignment>,
+        Vec<HummockCompactionTaskId>,
     )> {
         let mut compact_statuses = BTreeMapTransaction::new(&mut self.compaction_statuses);
         let mut compact_task_assignment =
             BTreeMapTransaction::new(&mut self.compact_task_assignment);
+        let mut split_task_ids_to_cancel = vec![];
         for &context_id in context_ids {
             // Clean up compact_status.
             for assignment in compact_task_assignment.tree_ref().values() {
@@ -58,14 +143,17 @@ impl Compaction {
                     .compact_task
                     .as_ref()
                     .expect("compact_task shouldn't be None");
+                if task.parent_task_id != 0 {
+                    if self.split_tasks.contains_key(&task.parent_task_id)
+                        && !split_task_ids_to_cancel.contains(&task.parent_task_id)
+                    {
+                        split_task_ids_to_cancel.push(task.parent_task_id);
+                    }
+                    continue;
+                }
                 if let Some(mut compact_status) = compact_statuses.get_mut(task.compaction_group_id)
                 {
-                    compact_status.report_compact_task(
-                        assignment
-                            .compact_task
-                            .as_ref()
-                            .expect("compact_task shouldn't be None"),
-                    );
+                    compact_status.report_compact_task(task);
                 }
             }
             // Clean up compact_task_assignment.
@@ -84,7 +172,17 @@ impl Compaction {
                 compact_task_assignment.remove(task_id);
             }
         }
-        Ok((compact_statuses, compact_task_assignment))
+        for task_id in &split_task_ids_to_cancel {
+            let task = &self.split_tasks[task_id].task;
+            if let Some(mut compact_status) = compact_statuses.get_mut(task.compaction_group_id) {
+                compact_status.report_compact_task(task);
+            }
+        }
+        Ok((
+            compact_statuses,
+            compact_task_assignment,
+            split_task_ids_to_cancel,
+        ))
     }
 }
 
@@ -134,15 +232,192 @@ where
             })
             .collect_vec()
     }
+
+    /// Splits `compact_task` by key range into subtasks if its input is larger than
+    /// `split_compaction_task_size_mb`, so that they can run on different compactors. Returns the
+    /// task itself if it's not split.
+    ///
+    /// The split task stays pending in `CompactStatus` until all its subtasks are reported, see
+    /// `report_subtask`.
+    #[named]
+    pub async fn split_compact_task(&self, compact_task: &CompactTask) -> Result<Vec<CompactTask>> {
+        let split_size = self.env.opts.split_compaction_task_size_mb << 20;
+        let input_size = compact_task
+            .input_ssts
+            .iter()
+            .flat_map(|level| level.table_infos.iter())
+            .map(|sst| sst.file_size)
+            .sum::<u64>();
+        if split_size == 0
+            || input_size <= split_size
+            || self.env.opts.compaction_deterministic_test
+        {
+            return Ok(vec![compact_task.clone()]);
+        }
+        let split_num = std::cmp::min(
+            (input_size + split_size - 1) / split_size,
+            self.compactor_manager.compactor_num() as u64,
+        );
+        let key_ranges = split_key_ranges(compact_task, split_num as usize);
+        if key_ranges.len() <= 1 {
+            return Ok(vec![compact_task.clone()]);
+        }
+        let mut subtasks = Vec::with_capacity(key_ranges.len());
+        for key_range in key_ranges {
+            let task_id = self
+                .env
+                .id_gen_manager()
+                .generate::<{ IdCategory::HummockCompactionTask }>()
+                .await?;
+            subtasks.push(CompactTask {
+                task_id,
+                parent_task_id: compact_task.task_id,
+                splits: vec![key_range],
+                ..compact_task.clone()
+            });
+        }
+        write_lock!(self, compaction).await.split_tasks.insert(
+            compact_task.task_id,
+            SplitCompactTask::new(
+                compact_task.clone(),
+                subtasks.iter().map(|task| task.task_id).collect_vec(),
+            ),
+        );
+        tracing::info!(
+            "Split compaction task {} into {} subtasks. input size {}",
+            compact_task.task_id,
+            subtasks.len(),
+            input_size
+        );
+        Ok(subtasks)
+    }
+
+    /// Reports a subtask of a split task. Its assignment is removed no matter how the split task
+    /// goes.
+    ///
+    /// The split task is returned once it's finished, i.e. all its subtasks succeed or any of them
+    /// fails, so that the caller reports it as a whole. In the latter case, the other running
+    /// subtasks are cancelled.
+    pub(super) async fn report_subtask(
+        &self,
+        context_id: Option<HummockContextId>,
+        subtask: &CompactTask,
+        compaction: &mut Compaction,
+    ) -> Result<SubtaskReport> {
+        let assigned_task_num = compaction.compact_task_assignment.len();
+        let mut compact_task_assignment =
+            BTreeMapTransaction::new(&mut compaction.compact_task_assignment);
+        let assignee_context_id = compact_task_assignment
+            .remove(subtask.task_id)
+            .map(|assignment| assignment.context_id);
+        // For context_id is None, there is no need to check the task assignment.
+        if let Some(context_id) = context_id {
+            if assignee_context_id != Some(context_id) {
+                tracing::warn!(
+                    "Compaction subtask {} is not assigned to {}",
+                    subtask.task_id,
+                    context_id
+                );
+                return Ok(SubtaskReport::Rejected);
+            }
+        }
+        commit_multi_var!(self, context_id, compact_task_assignment)?;
+        if let Some(context_id) = assignee_context_id {
+            self.on_assigned_task_reported(context_id, subtask, assigned_task_num)
+                .await;
+        }
+
+        let split_task = match compaction.split_tasks.get_mut(&subtask.parent_task_id) {
+            Some(split_task) => split_task,
+            // The split task is already failed by another subtask, or cancelled on restart.
+            None => return Ok(SubtaskReport::Accepted),
+        };
+        let task = match split_task.report_subtask(subtask) {
+            Some(task) => task,
+            None => return Ok(SubtaskReport::Accepted),
+        };
+        let split_task = compaction
+            .split_tasks
+            .remove(&subtask.parent_task_id)
+            .unwrap();
+        if task.task_status() != TaskStatus::Success {
+            for task_id in split_task.subtask_ids {
+                let compactor =
+                    compaction
+                        .compact_task_assignment
+                        .get(&task_id)
+                        .and_then(|assignment| {
+                            self.compactor_manager.get_compactor(assignment.context_id)
+                        });
+                if let Some(compactor) = compactor {
+                    // The result of the subtask is ignored when reported.
+                    tokio::spawn(async move {
+                        let _ = compactor.cancel_task(task_id).await;
+                    });
+                }
+            }
+        }
+        Ok(SubtaskReport::SplitTaskFinished(task))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use piestream_hummock_sdk::CompactionGroupId;
+    use piestream_pb::hummock::compact_task::TaskStatus;
     use piestream_pb::hummock::{CompactTask, CompactTaskAssignment, InputLevel, SstableInfo};
 
     use crate::hummock::compaction::CompactStatus;
-    use crate::hummock::manager::compaction::Compaction;
+    use crate::hummock::manager::compaction::{Compaction, SplitCompactTask};
+
+    #[test]
+    fn test_split_compact_task_report_subtask() {
+        let task = CompactTask {
+            task_id: 1,
+            task_status: TaskStatus::Pending as i32,
+            ..Default::default()
+        };
+        let subtask = |task_id: u64, task_status: TaskStatus, sst_id: u64| CompactTask {
+            task_id,
+            parent_task_id: 1,
+            task_status: task_status as i32,
+            sorted_output_ssts: vec![SstableInfo {
+                id: sst_id,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Outputs are merged in the order of subtasks rather than the order of reports.
+        let mut split_task = SplitCompactTask::new(task.clone(), vec![2, 3]);
+        assert!(split_task
+            .report_subtask(&subtask(3, TaskStatus::Success, 30))
+            .is_none());
+        let finished_task = split_task
+            .report_subtask(&subtask(2, TaskStatus::Success, 20))
+            .unwrap();
+        assert_eq!(finished_task.task_id, 1);
+        assert_eq!(finished_task.task_status(), TaskStatus::Success);
+        assert_eq!(
+            finished_task
+                .sorted_output_ssts
+                .iter()
+                .map(|sst| sst.id)
+                .collect::<Vec<_>>(),
+            vec![20, 30]
+        );
+
+        // The split task fails once any subtask fails.
+        let mut split_task = SplitCompactTask::new(task, vec![2, 3]);
+        assert!(split_task
+            .report_subtask(&subtask(2, TaskStatus::Success, 20))
+            .is_none());
+        let finished_task = split_task
+            .report_subtask(&subtask(3, TaskStatus::ExecuteFailed, 30))
+            .unwrap();
+        assert_eq!(finished_task.task_status(), TaskStatus::ExecuteFailed);
+        assert!(finished_task.sorted_output_ssts.is_empty());
+    }
 
     #[tokio::test]
     async fn test_cancel_assigned_tasks_for_context_ids() {
@@ -180,9 +455,10 @@ mod tests {
         );
 
         // irrelevant context id
-        let (compact_status, assignment) = compaction
+        let (compact_status, assignment, split_task_ids) = compaction
             .cancel_assigned_tasks_for_context_ids(&[22])
             .unwrap();
+        assert!(split_task_ids.is_empty());
         compact_status.commit_memory();
         assignment.commit_memory();
         assert_eq!(
@@ -204,9 +480,10 @@ mod tests {
         );
 
         // target context id
-        let (compact_status, assignment) = compaction
+        let (compact_status, assignment, split_task_ids) = compaction
             .cancel_assigned_tasks_for_context_ids(&[11])
             .unwrap();
+        assert!(split_task_ids.is_empty());
         compact_status.commit_memory();
         assignment.commit_memory();
         assert_eq!(
@@ -221,4 +498,37 @@ mod tests {
         );
         assert_eq!(compaction.compact_task_assignment.len(), 0);
     }
+
+    #[test]
+    fn test_cancel_split_task_for_context_ids() {
+        let mut compaction = Compaction::default();
+        let task = CompactTask {
+            task_id: 1,
+            ..Default::default()
+        };
+        compaction
+            .split_tasks
+            .insert(1, SplitCompactTask::new(task.clone(), vec![2, 3]));
+        for task_id in [2, 3] {
+            compaction.compact_task_assignment.insert(
+                task_id,
+                CompactTaskAssignment {
+                    compact_task: Some(CompactTask {
+                        task_id,
+                        parent_task_id: 1,
+                        ..task.clone()
+                    }),
+                    context_id: 11,
+                },
+            );
+        }
+
+        // The split task is kept until the cancellation is committed.
+        let (_, _, split_task_ids) = compaction
+            .cancel_assigned_tasks_for_context_ids(&[11])
+            .unwrap();
+        assert_eq!(split_task_ids, vec![1]);
+        assert!(compaction.split_tasks.contains_key(&1));
+        assert_eq!(compaction.compact_task_assignment.len(), 2);
+    }
 }
@@ -48,7 +48,7 @@ where
         )));
         let mut compaction_guard = write_lock!(self, compaction).await;
         let compaction = compaction_guard.deref_mut();
-        let (compact_statuses, compact_task_assignment) =
+        let (compact_statuses, compact_task_assignment, cancelled_split_task_ids) =
             compaction.cancel_assigned_tasks_for_context_ids(context_ids.as_ref())?;
         for context_id in context_ids.as_ref() {
             self.compactor_manager
@@ -70,6 +70,11 @@ where
             pinned_versions,
             pinned_snapshots
         )?;
+        // Split tasks are kept in memory only, so they are dropped after the cancellation is
+        // persisted.
+        for task_id in cancelled_split_task_ids {
+            compaction.split_tasks.remove(&task_id);
+        }
 
         #[cfg(test)]
         {
@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use std::cmp;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::ops::Bound::{Excluded, Unbounded};
 use std::ops::DerefMut;
 
 use function_name::named;
@@ -23,7 +24,7 @@ use piestream_hummock_sdk::compaction_group::hummock_version_ext::{
 };
 use piestream_hummock_sdk::{HummockSstableId, HummockVersionId, INVALID_VERSION_ID};
 
-use crate::hummock::error::Result;
+use crate::hummock::error::{Error, Result};
 use crate::hummock::manager::{commit_multi_var, read_lock, write_lock};
 use crate::hummock::HummockManager;
 use crate::model::{BTreeMapTransaction, ValTransaction};
@@ -34,12 +35,19 @@ where
     S: MetaStore,
 {
     /// Gets SSTs that is safe to be deleted from object store.
+    /// SSTs pinned by meta snapshots are excluded.
     #[named]
     pub async fn get_ssts_to_delete(&self) -> Vec<HummockSstableId> {
-        read_lock!(self, versioning)
-            .await
+        let versioning_guard = read_lock!(self, versioning).await;
+        let pinned_sst_ids: HashSet<&HummockSstableId> = versioning_guard
+            .ssts_pinned_by_backup
+            .values()
+            .flatten()
+            .collect();
+        versioning_guard
             .ssts_to_delete
             .keys()
+            .filter(|sst_id| !pinned_sst_ids.contains(sst_id))
             .cloned()
             .collect_vec()
     }
@@ -104,6 +112,15 @@ where
             for delta in versioning_guard.hummock_version_deltas.values() {
                 tracked_sst_ids.extend(delta.get_removed_sst_ids());
             }
+            tracked_sst_ids.extend(versioning_guard.ssts_pinned_by_backup.values().flatten());
+            // Blob files share the id space with SSTs. A blob file is kept as long as it's
+            // referenced by any SST that may still be read, i.e. SSTs in the checkpoint version
+            // and those inserted afterwards.
+            tracked_sst_ids.extend(versioning_guard.current_version.get_blob_ids());
+            tracked_sst_ids.extend(versioning_guard.checkpoint_version.get_blob_ids());
+            for delta in versioning_guard.hummock_version_deltas.values() {
+                tracked_sst_ids.extend(delta.get_inserted_blob_ids());
+            }
             tracked_sst_ids
         };
         let to_delete = sst_ids
@@ -118,4 +135,59 @@ where
         );
         to_delete.len()
     }
+
+    /// Pins the SSTs and blob files referenced by the meta snapshot `snapshot_id`, so that they are
+    /// not deleted until [`Self::unpin_ssts_for_backup`] is called.
+    ///
+    /// Fails if any of the SSTs is neither in the current version nor removed by a delta after
+    /// the checkpoint version, or any of the blob files is referenced by none of these SSTs, i.e.
+    /// it may have been deleted or is being deleted.
+    #[named]
+    pub async fn pin_ssts_for_backup(
+        &self,
+        snapshot_id: u64,
+        sst_ids: HashSet<HummockSstableId>,
+    ) -> Result<()> {
+        let mut versioning_guard = write_lock!(self, versioning).await;
+        let mut tracked_sst_ids: HashSet<HummockSstableId> =
+            HashSet::from_iter(versioning_guard.current_version.get_sst_ids());
+        tracked_sst_ids.extend(versioning_guard.checkpoint_version.get_blob_ids());
+        for delta in versioning_guard
+            .hummock_version_deltas
+            .range((Excluded(versioning_guard.checkpoint_version.id), Unbounded))
+            .map(|(_, delta)| delta)
+        {
+            tracked_sst_ids.extend(delta.get_removed_sst_ids());
+            tracked_sst_ids.extend(delta.get_inserted_blob_ids());
+        }
+        if let Some(sst_id) = sst_ids
+            .iter()
+            .find(|sst_id| !tracked_sst_ids.contains(*sst_id))
+        {
+            return Err(Error::InvalidSst(*sst_id));
+        }
+        versioning_guard
+            .ssts_pinned_by_backup
+            .insert(snapshot_id, sst_ids);
+        Ok(())
+    }
+
+    /// Restores the SSTs pinned by existing meta snapshots, which should be called on startup
+    /// before any SST is vacuumed.
+    #[named]
+    pub async fn restore_ssts_pinned_by_backup(
+        &self,
+        ssts_pinned_by_backup: HashMap<u64, HashSet<HummockSstableId>>,
+    ) {
+        write_lock!(self, versioning).await.ssts_pinned_by_backup = ssts_pinned_by_backup;
+    }
+
+    /// Unpins the SSTs referenced by the meta snapshot `snapshot_id`.
+    #[named]
+    pub async fn unpin_ssts_for_backup(&self, snapshot_id: u64) {
+        write_lock!(self, versioning)
+            .await
+            .ssts_pinned_by_backup
+            .remove(&snapshot_id);
+    }
 }
@@ -14,7 +14,7 @@
 
 use std::borrow::{Borrow, BorrowMut};
 use std::collections::{BTreeMap, HashMap, HashSet};
-use std::ops::Bound::{Excluded, Included};
+use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::ops::DerefMut;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, LazyLock};
@@ -23,12 +23,11 @@ use std::time::Instant;
 use fail::fail_point;
 use function_name::named;
 use itertools::Itertools;
-use prost::Message;
 use piestream_common::monitor::rwlock::MonitoredRwLock;
 use piestream_common::util::epoch::{Epoch, INVALID_EPOCH};
 use piestream_hummock_sdk::compact::compact_task_to_string;
 use piestream_hummock_sdk::compaction_group::hummock_version_ext::{
-    add_new_sub_level, HummockLevelsExt, HummockVersionExt,
+    add_new_sub_level, HummockLevelsExt, HummockVersionDeltaExt, HummockVersionExt,
 };
 use piestream_hummock_sdk::{
     CompactionGroupId, HummockCompactionTaskId, HummockContextId, HummockEpoch, HummockSstableId,
@@ -45,18 +44,19 @@ use piestream_pb::hummock::{
 };
 use piestream_pb::meta::subscribe_response::{Info, Operation};
 use piestream_pb::meta::MetaLeaderInfo;
+use prost::Message;
 use tokio::sync::oneshot::Sender;
 use tokio::sync::{Notify, RwLockReadGuard, RwLockWriteGuard};
 use tokio::task::JoinHandle;
 
-use crate::hummock::compaction::{CompactStatus, ManualCompactionOption};
+use crate::hummock::compaction::{CompactStatus, ManualCompactionOption, ReclaimContext};
 use crate::hummock::compaction_group::manager::CompactionGroupManagerRef;
 use crate::hummock::compaction_group::CompactionGroup;
 use crate::hummock::compaction_scheduler::CompactionRequestChannelRef;
 use crate::hummock::error::{Error, Result};
 use crate::hummock::metrics_utils::{
-    trigger_pin_unpin_snapshot_state, trigger_pin_unpin_version_state, trigger_sst_stat,
-    trigger_version_stat,
+    trigger_compact_pending_bytes_stat, trigger_pin_unpin_snapshot_state,
+    trigger_pin_unpin_version_state, trigger_sst_stat, trigger_version_stat,
 };
 use crate::hummock::CompactorManagerRef;
 use crate::manager::{ClusterManagerRef, IdCategory, LocalNotification, MetaSrvEnv, META_NODE_ID};
@@ -144,8 +144,8 @@ macro_rules! read_lock {
         }
     };
 }
-pub(crate) use read_lock;
 use piestream_pb::hummock::pin_version_response::Payload;
+pub(crate) use read_lock;
 
 /// Acquire write lock of the lock with `lock_name`.
 /// The macro will use macro `function_name` to get the name of the function of method that calls
@@ -499,9 +499,20 @@ where
         context_id: HummockContextId,
         epoch: HummockEpoch,
     ) -> Result<HummockSnapshot> {
+        // Hold the compaction lock, so that no compaction task is created until the snapshot is
+        // pinned.
+        let compaction_guard = read_lock!(self, compaction).await;
         let max_committed_epoch = self.max_committed_epoch.load(Ordering::Relaxed);
         let max_current_epoch = self.max_current_epoch.load(Ordering::Relaxed);
         let mut guard = write_lock!(self, versioning).await;
+        // The compaction tasks in flight raise the safe epoch to their watermarks once finished.
+        let safe_epoch = std::cmp::max(
+            guard.current_version.safe_epoch,
+            compaction_guard.max_task_watermark().unwrap_or(0),
+        );
+        if epoch < safe_epoch {
+            return Err(Error::ExpiredEpoch(epoch, safe_epoch));
+        }
         let mut pinned_snapshots = BTreeMapTransaction::new(&mut guard.pinned_snapshots);
         let mut context_pinned_snapshot = pinned_snapshots.new_entry_txn_or_default(
             context_id,
@@ -515,7 +526,11 @@ where
         } else {
             max_committed_epoch
         };
-        if context_pinned_snapshot.minimal_pinned_snapshot == INVALID_EPOCH {
+        // The pinned snapshot can be moved backwards for time-travel queries, as long as the
+        // epoch is not older than the safe epoch.
+        if context_pinned_snapshot.minimal_pinned_snapshot == INVALID_EPOCH
+            || epoch_to_pin < context_pinned_snapshot.minimal_pinned_snapshot
+        {
             context_pinned_snapshot.minimal_pinned_snapshot = epoch_to_pin;
             commit_multi_var!(self, Some(context_id), context_pinned_snapshot)?;
         }
@@ -628,6 +643,25 @@ where
         Ok(())
     }
 
+    /// Returns the longest time-travel retention among all tables. As the safe epoch is shared by
+    /// all compaction groups, the watermark of every compaction task must keep the versions within
+    /// this retention.
+    async fn time_travel_retention_ms(&self) -> u64 {
+        self.compaction_group_manager
+            .compaction_groups()
+            .await
+            .iter()
+            .flat_map(|group| {
+                group
+                    .table_id_to_options()
+                    .values()
+                    .filter_map(|option| option.time_travel_retention_seconds)
+                    .collect_vec()
+            })
+            .max()
+            .map_or(0, |seconds| seconds as u64 * 1000)
+    }
+
     #[named]
     pub async fn get_compact_task_impl(
         &self,
@@ -669,19 +703,44 @@ where
                 .get_mut(&compaction_group_id)
                 .ok_or(Error::InvalidCompactionGroup(compaction_group_id))?,
         );
+        let time_travel_retention_ms = self.time_travel_retention_ms().await;
         let (current_version, watermark) = {
             let versioning_guard = read_lock!(self, versioning).await;
             let max_committed_epoch = versioning_guard.current_version.max_committed_epoch;
+            let time_travel_watermark = if time_travel_retention_ms == 0 {
+                max_committed_epoch
+            } else {
+                Epoch(max_committed_epoch)
+                    .subtract_ms(time_travel_retention_ms)
+                    .0
+            };
             let watermark = versioning_guard
                 .pinned_snapshots
                 .values()
                 .map(|v| v.minimal_pinned_snapshot)
-                .fold(max_committed_epoch, std::cmp::min);
+                .fold(time_travel_watermark, std::cmp::min);
             (versioning_guard.current_version.clone(), watermark)
         };
         if current_version.levels.get(&compaction_group_id).is_none() {
             return Err(Error::InvalidCompactionGroup(compaction_group_id));
         }
+        let existing_table_ids_from_meta = self
+            .compaction_group_manager
+            .internal_table_ids_by_compaction_group_id(compaction_group_id)
+            .await?;
+        let reclaim_context = ReclaimContext {
+            existing_table_ids: existing_table_ids_from_meta.clone(),
+            table_retention_seconds: group_config
+                .table_id_to_options()
+                .iter()
+                .filter_map(|(table_id, table_option)| {
+                    table_option
+                        .retention_seconds
+                        .map(|retention_seconds| (*table_id, retention_seconds))
+                })
+                .collect(),
+            current_epoch_time: Epoch::now().0,
+        };
         let can_trivial_move = manual_compaction_option.is_none();
         let compact_task = compact_status.get_compact_task(
             current_version.get_compaction_group_levels(compaction_group_id),
@@ -689,6 +748,7 @@ where
             compaction_group_id,
             manual_compaction_option,
             group_config.compaction_config(),
+            reclaim_context,
         );
         let mut compact_task = match compact_task {
             None => {
@@ -713,11 +773,6 @@ where
                 start_time.elapsed()
             );
         } else {
-            let existing_table_ids_from_meta = self
-                .compaction_group_manager
-                .internal_table_ids_by_compaction_group_id(compaction_group_id)
-                .await?;
-
             // to get all relational table_id from sst_info
             let table_ids = compact_task
                 .input_ssts
@@ -765,6 +820,10 @@ where
                 compaction_group_id,
             );
 
+            compaction
+                .pending_task_watermarks
+                .insert(compact_task.task_id, compact_task.watermark);
+
             tracing::trace!(
                 "For compaction group {}: pick up {} tables in level {} to compact.  cost time: {:?}",
                 compaction_group_id,
@@ -927,6 +986,24 @@ where
             None => write_lock!(self, compaction).await,
             Some(compaction_guard) => compaction_guard,
         };
+        // A subtask only finishes its part of the split task, which is reported as a whole once
+        // all its subtasks succeed or any of them fails.
+        let mut split_task;
+        let (context_id, compact_task) = if compact_task.parent_task_id == 0 {
+            (context_id, compact_task)
+        } else {
+            match self
+                .report_subtask(context_id, compact_task, compaction_guard.deref_mut())
+                .await?
+            {
+                SubtaskReport::Rejected => return Ok(false),
+                SubtaskReport::Accepted => return Ok(true),
+                SubtaskReport::SplitTaskFinished(task) => {
+                    split_task = task;
+                    (None, &mut split_task)
+                }
+            }
+        };
         let deterministic_mode = self.env.opts.compaction_deterministic_test;
         let compaction = compaction_guard.deref_mut();
         let start_time = Instant::now();
@@ -988,6 +1065,9 @@ where
             task_status != TaskStatus::Pending,
             "report pending compaction task"
         );
+        compaction
+            .pending_task_watermarks
+            .remove(&compact_task.task_id);
         if let TaskStatus::Success = task_status {
             // The compaction task is finished.
             let mut versioning_guard = write_lock!(self, versioning).await;
@@ -1035,40 +1115,9 @@ where
             commit_multi_var!(self, context_id, compact_statuses, compact_task_assignment)?;
         }
 
-        let task_label = task_status.as_str_name();
         if let Some(context_id) = assignee_context_id {
-            // A task heartbeat is removed IFF we report the task status of a task and it still has
-            // a valid assignment, OR we remove the node context from our list of nodes,
-            // in which case the associated heartbeats are forcefully purged.
-            self.compactor_manager
-                .remove_task_heartbeat(context_id, compact_task.task_id);
-            // Also, if the task is already assigned, we need to update the compaction schedule
-            // policy.
-            self.compactor_manager
-                .report_compact_task(context_id, compact_task);
-            // Tell compaction scheduler to resume compaction if there's any compactor becoming
-            // available.
-            if assigned_task_num == self.compactor_manager.max_concurrent_task_number() {
-                self.try_resume_compaction(CompactionResumeTrigger::TaskReport {
-                    original_task_num: assigned_task_num,
-                });
-            }
-            // Update compaction task count.
-            //
-            // A corner case is that the compactor is deleted
-            // immediately after it reports the task and before the meta node handles
-            // it. In that case, its host address will not be obtainable.
-            if let Some(worker) = self.cluster_manager.get_worker_by_id(context_id).await {
-                let host = worker.worker_node.host.unwrap();
-                self.metrics
-                    .compact_frequency
-                    .with_label_values(&[
-                        &format!("{}:{}", host.host, host.port),
-                        &compact_task.compaction_group_id.to_string(),
-                        task_label,
-                    ])
-                    .inc();
-            }
+            self.on_assigned_task_reported(context_id, compact_task, assigned_task_num)
+                .await;
         } else {
             // Update compaction task count. The task will be marked as `unassigned`.
             self.metrics
@@ -1076,7 +1125,7 @@ where
                 .with_label_values(&[
                     "unassigned",
                     &compact_task.compaction_group_id.to_string(),
-                    task_label,
+                    task_status.as_str_name(),
                 ])
                 .inc();
         }
@@ -1087,14 +1136,29 @@ where
             start_time.elapsed(),
         );
 
+        let compaction_config = self
+            .compaction_group_manager
+            .compaction_group(compact_task.compaction_group_id)
+            .await
+            .map(|compaction_group| compaction_group.compaction_config());
+        let versioning_guard = read_lock!(self, versioning).await;
         trigger_sst_stat(
             &self.metrics,
             compaction
                 .compaction_statuses
                 .get(&compact_task.compaction_group_id),
-            read_lock!(self, versioning).await.current_version.borrow(),
+            versioning_guard.current_version.borrow(),
             compact_task.compaction_group_id,
         );
+        if let Some(compaction_config) = compaction_config {
+            trigger_compact_pending_bytes_stat(
+                &self.metrics,
+                versioning_guard.current_version.borrow(),
+                compact_task.compaction_group_id,
+                compaction_config,
+            );
+        }
+        drop(versioning_guard);
 
         if !deterministic_mode {
             self.try_send_compaction_request(compact_task.compaction_group_id)?;
@@ -1109,6 +1173,47 @@ where
         Ok(true)
     }
 
+    /// Cleans up after a task assigned to `context_id` is reported.
+    async fn on_assigned_task_reported(
+        &self,
+        context_id: HummockContextId,
+        compact_task: &CompactTask,
+        assigned_task_num: usize,
+    ) {
+        // A task heartbeat is removed IFF we report the task status of a task and it still has
+        // a valid assignment, OR we remove the node context from our list of nodes,
+        // in which case the associated heartbeats are forcefully purged.
+        self.compactor_manager
+            .remove_task_heartbeat(context_id, compact_task.task_id);
+        // Also, if the task is already assigned, we need to update the compaction schedule
+        // policy.
+        self.compactor_manager
+            .report_compact_task(context_id, compact_task);
+        // Tell compaction scheduler to resume compaction if there's any compactor becoming
+        // available.
+        if assigned_task_num == self.compactor_manager.max_concurrent_task_number() {
+            self.try_resume_compaction(CompactionResumeTrigger::TaskReport {
+                original_task_num: assigned_task_num,
+            });
+        }
+        // Update compaction task count.
+        //
+        // A corner case is that the compactor is deleted
+        // immediately after it reports the task and before the meta node handles
+        // it. In that case, its host address will not be obtainable.
+        if let Some(worker) = self.cluster_manager.get_worker_by_id(context_id).await {
+            let host = worker.worker_node.host.unwrap();
+            self.metrics
+                .compact_frequency
+                .with_label_values(&[
+                    &format!("{}:{}", host.host, host.port),
+                    &compact_task.compaction_group_id.to_string(),
+                    compact_task.task_status().as_str_name(),
+                ])
+                .inc();
+        }
+    }
+
     fn sync_group(
         old_version_groups: Vec<CompactionGroupId>,
         compaction_groups: HashMap<CompactionGroupId, CompactionGroup>,
@@ -1372,6 +1477,12 @@ where
                 &versioning.current_version,
                 *compaction_group_id,
             );
+            trigger_compact_pending_bytes_stat(
+                &self.metrics,
+                &versioning.current_version,
+                *compaction_group_id,
+                self.get_compaction_config(*compaction_group_id).await,
+            );
         }
 
         tracing::trace!("new committed epoch {}", epoch);
@@ -1457,21 +1568,45 @@ where
         let mut checkpoint = VarTransaction::new(&mut versioning.checkpoint_version);
         let old_checkpoint_id = checkpoint.id;
         let mut new_checkpoint_id = min_pinned_version_id;
+        // Blob files referenced by the old checkpoint or by SSTs inserted in between. Those no
+        // longer referenced by the new checkpoint or by SSTs inserted afterwards can't be
+        // read anymore.
+        let mut stale_blob_ids: HashSet<HummockSstableId> =
+            HashSet::from_iter(checkpoint.get_blob_ids());
         for (_, version_delta) in versioning
             .hummock_version_deltas
             .range((Excluded(old_checkpoint_id), Included(new_checkpoint_id)))
         {
             checkpoint.apply_version_delta(version_delta);
+            stale_blob_ids.extend(version_delta.get_inserted_blob_ids());
         }
         new_checkpoint_id = checkpoint.id;
         if new_checkpoint_id == old_checkpoint_id {
             return Ok(0);
         }
+        for blob_id in checkpoint.get_blob_ids() {
+            stale_blob_ids.remove(&blob_id);
+        }
+        for (_, version_delta) in versioning
+            .hummock_version_deltas
+            .range((Excluded(new_checkpoint_id), Unbounded))
+        {
+            for blob_id in version_delta.get_inserted_blob_ids() {
+                stale_blob_ids.remove(&blob_id);
+            }
+        }
         commit_multi_var!(self, None, checkpoint)?;
         versioning.extend_ssts_to_delete_from_deltas((
             Excluded(old_checkpoint_id),
             Included(new_checkpoint_id),
         ));
+        // Blob files share the id space with SSTs. Like orphan SSTs they don't retain any delta, so
+        // the ones not yet deleted on restart are left to full GC.
+        versioning.ssts_to_delete.extend(
+            stale_blob_ids
+                .into_iter()
+                .map(|blob_id| (blob_id, INVALID_VERSION_ID)),
+        );
         #[cfg(test)]
         {
             drop(versioning_guard);
@@ -1615,6 +1750,98 @@ where
         Ok((version_new, compaction_group_ids))
     }
 
+    /// Removes SSTs from the current version, e.g. SSTs whose objects are lost or corrupted.
+    /// SSTs being compacted are skipped. Returns the ids of the removed SSTs.
+    #[named]
+    pub async fn remove_dangling_ssts(
+        &self,
+        sst_ids: &[HummockSstableId],
+    ) -> Result<Vec<HummockSstableId>> {
+        let compaction_guard = read_lock!(self, compaction).await;
+        let mut versioning_guard = write_lock!(self, versioning).await;
+        let versioning = versioning_guard.deref_mut();
+        let sst_ids: HashSet<_> = sst_ids.iter().cloned().collect();
+        let current_version = &versioning.current_version;
+        let mut new_version_delta = HummockVersionDelta {
+            id: current_version.id + 1,
+            prev_id: current_version.id,
+            max_committed_epoch: current_version.max_committed_epoch,
+            safe_epoch: current_version.safe_epoch,
+            ..Default::default()
+        };
+        let mut removed_sst_ids = vec![];
+        for (compaction_group_id, levels) in &current_version.levels {
+            let compact_status = compaction_guard
+                .compaction_statuses
+                .get(compaction_group_id);
+            for level in levels.get_level0().sub_levels.iter().chain(&levels.levels) {
+                let removed_table_ids = level
+                    .table_infos
+                    .iter()
+                    .map(|sst| sst.id)
+                    .filter(|sst_id| {
+                        sst_ids.contains(sst_id)
+                            && !compact_status
+                                .map(|compact_status| {
+                                    compact_status.level_handlers[level.level_idx as usize]
+                                        .is_pending_compact(sst_id)
+                                })
+                                .unwrap_or(false)
+                    })
+                    .collect_vec();
+                if removed_table_ids.is_empty() {
+                    continue;
+                }
+                removed_sst_ids.extend(removed_table_ids.iter().cloned());
+                new_version_delta
+                    .level_deltas
+                    .entry(*compaction_group_id)
+                    .or_default()
+                    .level_deltas
+                    .push(LevelDelta {
+                        delta_type: Some(DeltaType::IntraLevel(IntraLevelDelta {
+                            level_idx: level.level_idx,
+                            removed_table_ids,
+                            ..Default::default()
+                        })),
+                    });
+            }
+        }
+        if removed_sst_ids.is_empty() {
+            return Ok(removed_sst_ids);
+        }
+
+        let mut hummock_version_deltas =
+            BTreeMapTransaction::new(&mut versioning.hummock_version_deltas);
+        hummock_version_deltas.insert(new_version_delta.id, new_version_delta.clone());
+        commit_multi_var!(self, None, hummock_version_deltas)?;
+        versioning
+            .current_version
+            .apply_version_delta(&new_version_delta);
+        trigger_version_stat(&self.metrics, &versioning.current_version);
+        self.env
+            .notification_manager()
+            .notify_hummock_asynchronously(
+                Operation::Add,
+                Info::HummockVersionDeltas(HummockVersionDeltas {
+                    version_deltas: vec![new_version_delta],
+                }),
+            );
+        tracing::warn!(
+            "Removed SSTs {:?} from the current version",
+            removed_sst_ids
+        );
+
+        #[cfg(test)]
+        {
+            drop(versioning_guard);
+            drop(compaction_guard);
+            self.check_state_consistency().await;
+        }
+
+        Ok(removed_sst_ids)
+    }
+
     #[named]
     pub async fn disable_commit_epoch(&self) -> HummockVersion {
         let mut versioning_guard = write_lock!(self, versioning).await;
@@ -1194,3 +1194,224 @@ async fn test_extend_ssts_to_delete() {
         orphan_sst_num as usize + 3
     );
 }
+
+#[tokio::test]
+async fn test_vacuum_stale_blobs() {
+    let (_env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
+    let context_id = worker_node.id;
+    let blob_ids = get_sst_ids(hummock_manager.as_ref(), 2).await;
+    let mut test_tables = generate_test_tables(1, get_sst_ids(hummock_manager.as_ref(), 3).await);
+    test_tables[0].blob_ids = vec![blob_ids[0]];
+    test_tables[1].blob_ids = vec![blob_ids[1]];
+    register_sstable_infos_to_compaction_group(
+        hummock_manager.compaction_group_manager(),
+        &test_tables,
+        StaticCompactionGroupId::StateDefault.into(),
+    )
+    .await;
+    let ssts = to_local_sstable_info(&test_tables);
+    let sst_to_worker = ssts.iter().map(|(_, sst)| (sst.id, context_id)).collect();
+    hummock_manager
+        .commit_epoch(1, ssts, sst_to_worker)
+        .await
+        .unwrap();
+
+    // Compact the SSTs into one, which only references the first blob file.
+    hummock_manager
+        .compactor_manager_ref_for_test()
+        .add_compactor(context_id, u64::MAX);
+    let mut compact_task = hummock_manager
+        .get_compact_task(StaticCompactionGroupId::StateDefault.into())
+        .await
+        .unwrap()
+        .unwrap();
+    hummock_manager
+        .assign_compaction_task(&compact_task, context_id)
+        .await
+        .unwrap();
+    let mut output_tables = generate_test_tables(1, get_sst_ids(hummock_manager.as_ref(), 1).await);
+    output_tables[0].blob_ids = vec![blob_ids[0]];
+    register_sstable_infos_to_compaction_group(
+        hummock_manager.compaction_group_manager(),
+        &output_tables,
+        StaticCompactionGroupId::StateDefault.into(),
+    )
+    .await;
+    compact_task.sorted_output_ssts = output_tables;
+    compact_task.set_task_status(TaskStatus::Success);
+    hummock_manager
+        .report_compact_task(context_id, &mut compact_task)
+        .await
+        .unwrap();
+
+    // A backup taken before compaction pins both the SSTs and their blob files.
+    hummock_manager
+        .pin_ssts_for_backup(
+            1,
+            test_tables
+                .iter()
+                .map(|sst| sst.id)
+                .chain(blob_ids.iter().cloned())
+                .collect(),
+        )
+        .await
+        .unwrap();
+    assert!(hummock_manager.proceed_version_checkpoint().await.unwrap() > 0);
+    assert!(hummock_manager.get_ssts_to_delete().await.is_empty());
+
+    // The blob file no longer referenced is vacuumed along with the compacted SSTs.
+    hummock_manager.unpin_ssts_for_backup(1).await;
+    let ssts_to_delete = hummock_manager.get_ssts_to_delete().await;
+    assert!(ssts_to_delete.contains(&blob_ids[1]));
+    assert!(!ssts_to_delete.contains(&blob_ids[0]));
+    assert!(test_tables
+        .iter()
+        .all(|sst| ssts_to_delete.contains(&sst.id)));
+}
+
+#[tokio::test]
+async fn test_pin_specific_snapshot() {
+    let (_env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
+    let context_id = worker_node.id;
+
+    for epoch in 1..=3 {
+        let test_tables = generate_test_tables(epoch, get_sst_ids(&hummock_manager, 2).await);
+        register_sstable_infos_to_compaction_group(
+            hummock_manager.compaction_group_manager(),
+            &test_tables,
+            StaticCompactionGroupId::StateDefault.into(),
+        )
+        .await;
+        commit_from_meta_node(
+            hummock_manager.borrow(),
+            epoch,
+            to_local_sstable_info(&test_tables),
+        )
+        .await
+        .unwrap();
+    }
+
+    hummock_manager.pin_snapshot(context_id).await.unwrap();
+    assert_eq!(
+        pin_snapshots_epoch(&hummock_manager.list_pinned_snapshot().await),
+        vec![3]
+    );
+
+    // The pinned snapshot is moved backwards for a time-travel query.
+    let snapshot = hummock_manager
+        .pin_specific_snapshot(context_id, 1)
+        .await
+        .unwrap();
+    assert_eq!(snapshot.committed_epoch, 3);
+    assert_eq!(
+        pin_snapshots_epoch(&hummock_manager.list_pinned_snapshot().await),
+        vec![1]
+    );
+
+    // A later epoch doesn't move the pinned snapshot forwards.
+    hummock_manager
+        .pin_specific_snapshot(context_id, 2)
+        .await
+        .unwrap();
+    assert_eq!(
+        pin_snapshots_epoch(&hummock_manager.list_pinned_snapshot().await),
+        vec![1]
+    );
+}
+
+#[tokio::test]
+async fn test_pin_specific_snapshot_with_compaction_task_in_flight() {
+    let (_env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
+    let context_id = worker_node.id;
+
+    for epoch in 1..=3 {
+        let test_tables = generate_test_tables(epoch, get_sst_ids(&hummock_manager, 2).await);
+        register_sstable_infos_to_compaction_group(
+            hummock_manager.compaction_group_manager(),
+            &test_tables,
+            StaticCompactionGroupId::StateDefault.into(),
+        )
+        .await;
+        commit_from_meta_node(
+            hummock_manager.borrow(),
+            epoch,
+            to_local_sstable_info(&test_tables),
+        )
+        .await
+        .unwrap();
+    }
+
+    // The task raises the safe epoch to its watermark once finished, so earlier epochs can't be
+    // pinned while it's in flight, no matter it's assigned or not.
+    let mut compact_task = hummock_manager
+        .get_compact_task(StaticCompactionGroupId::StateDefault.into())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(compact_task.watermark, 3);
+    assert_eq!(hummock_manager.get_current_version().await.safe_epoch, 0);
+    assert!(matches!(
+        hummock_manager.pin_specific_snapshot(context_id, 1).await,
+        Err(Error::ExpiredEpoch(1, 3))
+    ));
+    let compactor_manager = hummock_manager.compactor_manager_ref_for_test();
+    compactor_manager.add_compactor(context_id, u64::MAX);
+    hummock_manager
+        .assign_compaction_task(&compact_task, context_id)
+        .await
+        .unwrap();
+    assert!(matches!(
+        hummock_manager.pin_specific_snapshot(context_id, 1).await,
+        Err(Error::ExpiredEpoch(1, 3))
+    ));
+    assert!(hummock_manager.list_pinned_snapshot().await.is_empty());
+
+    // The epoch can be pinned once the task is cancelled.
+    assert!(hummock_manager
+        .cancel_compact_task(&mut compact_task, TaskStatus::ManualCanceled)
+        .await
+        .unwrap());
+    hummock_manager
+        .pin_specific_snapshot(context_id, 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        pin_snapshots_epoch(&hummock_manager.list_pinned_snapshot().await),
+        vec![1]
+    );
+}
+
+#[tokio::test]
+async fn test_remove_dangling_ssts() {
+    let (_env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
+    let context_id = worker_node.id;
+    let ssts = add_ssts(1, hummock_manager.as_ref(), context_id).await;
+
+    // Unknown SSTs are ignored.
+    let removed_sst_ids = hummock_manager
+        .remove_dangling_ssts(&[ssts[0].id, u64::MAX])
+        .await
+        .unwrap();
+    assert_eq!(removed_sst_ids, vec![ssts[0].id]);
+    let current_version = hummock_manager.get_current_version().await;
+    assert!(!current_version.get_sst_ids().contains(&ssts[0].id));
+    assert_eq!(current_version.get_sst_ids().len(), ssts.len() - 1);
+
+    // SSTs being compacted are not removed.
+    let compact_task = hummock_manager
+        .get_compact_task(StaticCompactionGroupId::StateDefault.into())
+        .await
+        .unwrap()
+        .unwrap();
+    let pending_sst_id = compact_task.input_ssts[0].table_infos[0].id;
+    assert!(hummock_manager
+        .remove_dangling_ssts(&[pending_sst_id])
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(hummock_manager
+        .get_current_version()
+        .await
+        .get_sst_ids()
+        .contains(&pending_sst_id));
+}
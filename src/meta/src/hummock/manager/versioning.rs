@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::cmp;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::RangeBounds;
 
 use function_name::named;
@@ -48,6 +48,9 @@ pub struct Versioning {
     // - AND It either contains no SST to delete, or all these SSTs has been deleted. See
     //   `extend_ssts_to_delete_from_deltas`.
     pub deltas_to_delete: Vec<HummockVersionId>,
+    // These SSTs are referenced by meta snapshots (backups), thus must not be deleted.
+    // Mapping from id of a meta snapshot to the SSTs of its hummock version.
+    pub ssts_pinned_by_backup: HashMap<u64, HashSet<HummockSstableId>>,
 
     // Persistent states below
 
@@ -20,7 +20,9 @@ use itertools::enumerate;
 use prost::Message;
 use piestream_hummock_sdk::compaction_group::hummock_version_ext::HummockVersionExt;
 use piestream_hummock_sdk::{CompactionGroupId, HummockContextId};
-use piestream_pb::hummock::{HummockPinnedSnapshot, HummockPinnedVersion, HummockVersion};
+use piestream_pb::hummock::{
+    CompactionConfig, HummockPinnedSnapshot, HummockPinnedVersion, HummockVersion,
+};
 
 use crate::hummock::compaction::CompactStatus;
 use crate::rpc::metrics::MetaMetrics;
@@ -36,6 +38,21 @@ pub fn trigger_version_stat(metrics: &MetaMetrics, current_version: &HummockVers
     metrics.current_version_id.set(current_version.id as i64);
 }
 
+pub fn trigger_compact_pending_bytes_stat(
+    metrics: &MetaMetrics,
+    current_version: &HummockVersion,
+    compaction_group_id: CompactionGroupId,
+    compaction_config: CompactionConfig,
+) {
+    if let Some(levels) = current_version.levels.get(&compaction_group_id) {
+        let pending_bytes = CompactStatus::compaction_pending_bytes(levels, compaction_config);
+        metrics
+            .compact_pending_bytes
+            .with_label_values(&[&compaction_group_id.to_string()])
+            .set(pending_bytes as i64);
+    }
+}
+
 pub fn trigger_sst_stat(
     metrics: &MetaMetrics,
     compact_status: Option<&CompactStatus>,
@@ -118,6 +118,7 @@ where
                             tracing::info!("Released hummock context {}", worker_node.id);
                             sync_point!("AFTER_RELEASE_HUMMOCK_CONTEXTS_ASYNC");
                         },
+                        Some(LocalNotification::WorkerNodeActivated(_)) => {}
                         Some(LocalNotification::CompactionTaskNeedCancel(compact_task)) => {
                             let task_id = compact_task.task_id;
                             tokio_retry::RetryIf::spawn(
@@ -148,6 +148,8 @@ pub fn generate_test_tables(epoch: u64, sst_ids: Vec<HummockSstableId>) -> Vec<S
             meta_offset: 0,
             stale_key_count: 0,
             total_key_count: 0,
+            blob_ids: vec![],
+            table_stats: Default::default(),
         });
     }
     sst_info
@@ -33,6 +33,7 @@
 #![cfg_attr(coverage, feature(no_coverage))]
 #![test_runner(piestream_test_runner::test_runner::run_failpont_tests)]
 
+pub mod backup_restore;
 mod barrier;
 #[cfg(not(madsim))] // no need in simulation test
 mod dashboard;
@@ -57,6 +58,7 @@ use crate::rpc::server::{rpc_serve, AddressInfo, MetaStoreBackend};
 enum Backend {
     Mem,
     Etcd,
+    LocalFile,
 }
 
 #[derive(Debug, Parser)]
@@ -80,6 +82,10 @@ pub struct MetaNodeOpts {
     #[clap(long, default_value_t = String::from(""))]
     etcd_endpoints: String,
 
+    /// Directory of the meta store files, used when the backend is `local-file`.
+    #[clap(long, default_value = "./meta_store")]
+    local_file_store_path: String,
+
     /// Enable authentication with etcd. By default disabled.
     #[clap(long)]
     etcd_auth: bool,
@@ -146,6 +152,28 @@ pub struct MetaNodeOpts {
 
     #[clap(long, default_value = "10")]
     node_num_monitor_interval_sec: u64,
+
+    /// Compaction tasks with input larger than this are split by key range into subtasks that
+    /// run on different compactors. 0 disables the split.
+    #[clap(long, default_value = "2048")]
+    split_compaction_task_size_mb: u64,
+
+    /// Rebalance actors automatically when compute nodes join or leave the cluster. By default
+    /// disabled.
+    #[clap(long)]
+    enable_auto_scaling: bool,
+
+    /// Only log the reschedule plan generated by auto scaling, without applying it.
+    #[clap(long)]
+    auto_scaling_dry_run: bool,
+
+    /// Remote object store where meta snapshots are stored.
+    #[clap(long, default_value = "memory")]
+    backup_storage_url: String,
+
+    /// Directory of meta snapshots in the remote object store.
+    #[clap(long, default_value = "backup")]
+    backup_storage_directory: String,
 }
 
 use std::future::Future;
@@ -182,6 +210,9 @@ pub fn start(opts: MetaNodeOpts) -> Pin<Box<dyn Future<Output = ()> + Send>> {
                 },
             },
             Backend::Mem => MetaStoreBackend::Mem,
+            Backend::LocalFile => MetaStoreBackend::LocalFile {
+                path: opts.local_file_store_path,
+            },
         };
 
         let max_heartbeat_interval = Duration::from_secs(opts.max_heartbeat_interval_secs as u64);
@@ -218,6 +249,11 @@ pub fn start(opts: MetaNodeOpts) -> Pin<Box<dyn Future<Output = ()> + Send>> {
                 enable_committed_sst_sanity_check: opts.enable_committed_sst_sanity_check,
                 periodic_compaction_interval_sec: opts.periodic_compaction_interval_sec,
                 node_num_monitor_interval_sec: opts.node_num_monitor_interval_sec,
+                split_compaction_task_size_mb: opts.split_compaction_task_size_mb,
+                enable_auto_scaling: opts.enable_auto_scaling,
+                auto_scaling_dry_run: opts.auto_scaling_dry_run,
+                backup_storage_url: opts.backup_storage_url,
+                backup_storage_directory: opts.backup_storage_directory,
             },
         )
         .await
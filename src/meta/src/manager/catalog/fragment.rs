@@ -295,6 +295,30 @@ where
         }
     }
 
+    /// Persist the desired parallelism of the table fragments, `None` for `auto`.
+    pub async fn set_table_fragments_parallelism(
+        &self,
+        table_id: TableId,
+        parallelism: Option<u32>,
+    ) -> MetaResult<()> {
+        let map = &mut self.core.write().await.table_fragments;
+
+        if let Some(table_fragments) = map.get(&table_id) {
+            let mut transaction = Transaction::default();
+
+            let mut table_fragments = table_fragments.clone();
+            table_fragments.set_parallelism(parallelism);
+            table_fragments.upsert_in_transaction(&mut transaction)?;
+
+            self.env.meta_store().txn(transaction).await?;
+            map.insert(table_id, table_fragments);
+
+            Ok(())
+        } else {
+            bail!("table_fragment not exist: id={}", table_id)
+        }
+    }
+
     /// Drop table fragments info and remove downstream actor infos in fragments from its dependent
     /// tables.
     pub async fn drop_table_fragments(&self, table_id: &TableId) -> MetaResult<()> {
@@ -386,56 +410,82 @@ where
         }
     }
 
+    /// Returns the ids of the parallel units in use on each worker node.
+    pub async fn worker_parallel_units(&self) -> HashMap<WorkerId, HashSet<ParallelUnitId>> {
+        let mut parallel_units: HashMap<WorkerId, HashSet<ParallelUnitId>> = HashMap::new();
+
+        let map = &self.core.read().await.table_fragments;
+        for parallel_unit in map
+            .values()
+            .flat_map(|table_fragments| table_fragments.actor_status.values())
+            .filter_map(|status| status.parallel_unit.as_ref())
+        {
+            parallel_units
+                .entry(parallel_unit.worker_node_id)
+                .or_default()
+                .insert(parallel_unit.id);
+        }
+
+        parallel_units
+    }
+
     /// Used in [`crate::barrier::GlobalBarrierManager`]
-    /// migrate actors and update fragments, generate migrate info
+    /// migrate actors and update fragments, generate migrate info.
+    ///
+    /// Each parallel unit in use is migrated to a distinct parallel unit of the new worker node, so
+    /// that the number of actors and parallel units of each fragment, i.e. its parallelism, is
+    /// kept. If the new worker node doesn't have enough parallel units, they're reused in turn, and
+    /// the number of actors of each fragment is still kept.
     pub async fn migrate_actors(
         &self,
         migrate_map: &HashMap<ActorId, WorkerId>,
         node_map: &HashMap<WorkerId, WorkerNode>,
     ) -> MetaResult<()> {
         let mut parallel_unit_migrate_map = HashMap::new();
-        let mut pu_map: HashMap<WorkerId, Vec<&ParallelUnit>> = HashMap::new();
-        // split parallel units of node into types, map them with WorkerId
+        // The parallel units of each new node, and the number of them assigned so far.
+        let mut pu_map: HashMap<WorkerId, (Vec<&ParallelUnit>, usize)> = HashMap::new();
         for (node_id, node) in node_map {
             let pu = node.parallel_units.iter().collect_vec();
-            pu_map.insert(*node_id, pu);
+            pu_map.insert(*node_id, (pu, 0));
         }
         // update actor status and generate pu to pu migrate info
         let mut table_fragments = self.list_table_fragments().await?;
         let mut new_fragments = Vec::new();
-        table_fragments.iter_mut().for_each(|fragment| {
+        for fragment in &mut table_fragments {
             let mut flag = false;
-            fragment
-                .actor_status
-                .iter_mut()
-                .for_each(|(actor_id, status)| {
-                    if let Some(new_node_id) = migrate_map.get(actor_id) {
-                        if let Some(ref old_parallel_unit) = status.parallel_unit {
-                            flag = true;
-                            if let Entry::Vacant(e) =
-                                parallel_unit_migrate_map.entry(old_parallel_unit.id)
-                            {
-                                let new_parallel_unit =
-                                    pu_map.get_mut(new_node_id).unwrap().pop().unwrap();
-                                e.insert(new_parallel_unit.clone());
-                                status.parallel_unit = Some(new_parallel_unit.clone());
-                            } else {
-                                status.parallel_unit = Some(
-                                    parallel_unit_migrate_map
-                                        .get(&old_parallel_unit.id)
-                                        .unwrap()
-                                        .clone(),
-                                );
-                            }
-                        }
-                    };
-                });
+            for (actor_id, status) in &mut fragment.actor_status {
+                let Some(new_node_id) = migrate_map.get(actor_id) else { continue };
+                let Some(ref old_parallel_unit) = status.parallel_unit else { continue };
+                flag = true;
+                let new_parallel_unit = match parallel_unit_migrate_map.entry(old_parallel_unit.id)
+                {
+                    Entry::Occupied(e) => e.get().clone(),
+                    Entry::Vacant(e) => {
+                        let new_parallel_unit = pu_map
+                            .get_mut(new_node_id)
+                            .filter(|(pu, _)| !pu.is_empty())
+                            .map(|(pu, assigned)| {
+                                *assigned += 1;
+                                pu[(*assigned - 1) % pu.len()]
+                            })
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "no parallel unit on worker {} to migrate actor {}",
+                                    new_node_id,
+                                    actor_id
+                                )
+                            })?;
+                        e.insert(new_parallel_unit.clone()).clone()
+                    }
+                };
+                status.parallel_unit = Some(new_parallel_unit);
+            }
             if flag {
                 // update vnode mapping of updated fragments
                 fragment.update_vnode_mapping(&parallel_unit_migrate_map);
                 new_fragments.push(fragment.clone());
             }
-        });
+        }
         // update fragments
         self.batch_update_table_fragments(&new_fragments).await?;
         Ok(())
@@ -871,3 +921,123 @@ where
         Ok(info)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use piestream_pb::meta::table_fragments::fragment::FragmentDistributionType;
+    use piestream_pb::meta::table_fragments::Fragment;
+
+    use super::*;
+
+    fn parallel_units(
+        worker_id: WorkerId,
+        ids: impl IntoIterator<Item = u32>,
+    ) -> Vec<ParallelUnit> {
+        ids.into_iter()
+            .map(|id| ParallelUnit {
+                id,
+                worker_node_id: worker_id,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_parallelism_survives_recovery() -> MetaResult<()> {
+        let env = MetaSrvEnv::for_test().await;
+        let fragment_manager = FragmentManager::new(env.clone()).await?;
+
+        // A hash distributed fragment with 2 actors on the parallel units 0 and 1 of worker 1.
+        let table_id = TableId::new(1);
+        let fragment = Fragment {
+            fragment_id: 1,
+            distribution_type: FragmentDistributionType::Hash as i32,
+            actors: (1..=2)
+                .map(|actor_id| StreamActor {
+                    actor_id,
+                    fragment_id: 1,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let mut table_fragments = TableFragments::new(table_id, BTreeMap::from([(1, fragment)]));
+        table_fragments.set_actor_status(
+            (1..=2)
+                .zip_eq(parallel_units(1, 0..2))
+                .map(|(actor_id, parallel_unit)| {
+                    (
+                        actor_id,
+                        ActorStatus {
+                            parallel_unit: Some(parallel_unit),
+                            state: ActorState::Running as i32,
+                        },
+                    )
+                })
+                .collect(),
+        );
+        fragment_manager
+            .start_create_table_fragments(table_fragments)
+            .await?;
+        fragment_manager
+            .mark_table_fragments_created(table_id)
+            .await?;
+        fragment_manager
+            .set_table_fragments_parallelism(table_id, Some(2))
+            .await?;
+        assert_eq!(
+            fragment_manager.worker_parallel_units().await,
+            HashMap::from([(1, HashSet::from([0, 1]))])
+        );
+
+        // Worker 3 with 4 parallel units takes over the actors.
+        let migrate_map = HashMap::from([(1, 3), (2, 3)]);
+        let node_map = HashMap::from([(
+            3,
+            WorkerNode {
+                id: 3,
+                parallel_units: parallel_units(3, 3..7),
+                ..Default::default()
+            },
+        )]);
+        fragment_manager
+            .migrate_actors(&migrate_map, &node_map)
+            .await?;
+
+        // Reload the table fragments from the meta store, as the meta node does on restart.
+        let fragment_manager = FragmentManager::new(env).await?;
+        let table_fragments = fragment_manager
+            .select_table_fragments_by_table_id(&table_id)
+            .await?;
+        assert_eq!(table_fragments.parallelism(), Some(2));
+        assert_eq!(
+            fragment_manager.worker_parallel_units().await[&3].len(),
+            2,
+            "actors should be kept on distinct parallel units"
+        );
+
+        // Worker 3 expires, and worker 2 with a single parallel unit takes over the actors, which
+        // share the parallel unit.
+        let migrate_map = HashMap::from([(1, 2), (2, 2)]);
+        let node_map = HashMap::from([(
+            2,
+            WorkerNode {
+                id: 2,
+                parallel_units: parallel_units(2, 2..3),
+                ..Default::default()
+            },
+        )]);
+        fragment_manager
+            .migrate_actors(&migrate_map, &node_map)
+            .await?;
+        let table_fragments = fragment_manager
+            .select_table_fragments_by_table_id(&table_id)
+            .await?;
+        assert_eq!(table_fragments.actor_ids().len(), 2);
+        assert_eq!(
+            fragment_manager.worker_parallel_units().await,
+            HashMap::from([(2, HashSet::from([2]))])
+        );
+
+        Ok(())
+    }
+}
@@ -1076,6 +1076,11 @@ where
             .collect())
     }
 
+    /// Lists the tables in creating procedure, including internal tables.
+    pub async fn list_creating_tables(&self) -> Vec<Table> {
+        self.core.lock().await.database.list_creating_tables()
+    }
+
     pub async fn list_sources(&self) -> MetaResult<Vec<Source>> {
         self.core.lock().await.database.list_sources().await
     }
@@ -95,6 +95,19 @@ pub struct MetaOpts {
     pub periodic_compaction_interval_sec: u64,
     /// Interval of reporting the number of nodes in the cluster.
     pub node_num_monitor_interval_sec: u64,
+    /// Compaction tasks with input larger than this are split by key range into subtasks that
+    /// run on different compactors. 0 disables the split.
+    pub split_compaction_task_size_mb: u64,
+
+    /// Whether to rebalance actors automatically when compute nodes join or leave the cluster.
+    pub enable_auto_scaling: bool,
+    /// Only report the reschedule plan generated by auto scaling, without applying it.
+    pub auto_scaling_dry_run: bool,
+
+    /// Remote object store where meta snapshots are stored.
+    pub backup_storage_url: String,
+    /// Directory of meta snapshots in the remote object store.
+    pub backup_storage_directory: String,
 }
 
 impl Default for MetaOpts {
@@ -113,6 +126,11 @@ impl Default for MetaOpts {
             enable_committed_sst_sanity_check: false,
             periodic_compaction_interval_sec: 60,
             node_num_monitor_interval_sec: 10,
+            split_compaction_task_size_mb: 2048,
+            enable_auto_scaling: false,
+            auto_scaling_dry_run: false,
+            backup_storage_url: "memory".to_string(),
+            backup_storage_directory: "backup".to_string(),
         }
     }
 }
@@ -35,6 +35,7 @@ pub type NotificationVersion = u64;
 #[derive(Clone, Debug)]
 pub enum LocalNotification {
     WorkerNodeIsDeleted(WorkerNode),
+    WorkerNodeActivated(WorkerNode),
     CompactionTaskNeedCancel(CompactTask),
 }
 
@@ -49,6 +49,9 @@ pub struct TableFragments {
 
     /// The status of actors
     pub(crate) actor_status: BTreeMap<ActorId, ActorStatus>,
+
+    /// The desired parallelism of hash-distributed fragments. `None` means `auto`.
+    parallelism: Option<u32>,
 }
 
 impl MetadataModel for TableFragments {
@@ -65,6 +68,7 @@ impl MetadataModel for TableFragments {
             state: self.state as _,
             fragments: self.fragments.clone().into_iter().collect(),
             actor_status: self.actor_status.clone().into_iter().collect(),
+            parallelism: self.parallelism.unwrap_or(0),
         }
     }
 
@@ -74,6 +78,7 @@ impl MetadataModel for TableFragments {
             state: prost.state(),
             fragments: prost.fragments.into_iter().collect(),
             actor_status: prost.actor_status.into_iter().collect(),
+            parallelism: (prost.parallelism != 0).then_some(prost.parallelism),
         }
     }
 
@@ -90,6 +95,7 @@ impl TableFragments {
             state: State::Creating,
             fragments,
             actor_status: BTreeMap::default(),
+            parallelism: None,
         }
     }
 
@@ -121,6 +127,16 @@ impl TableFragments {
         self.state = state;
     }
 
+    /// Returns the desired parallelism of hash-distributed fragments, `None` for `auto`.
+    pub fn parallelism(&self) -> Option<u32> {
+        self.parallelism
+    }
+
+    /// Set the desired parallelism of hash-distributed fragments, `None` for `auto`.
+    pub fn set_parallelism(&mut self, parallelism: Option<u32>) {
+        self.parallelism = parallelism;
+    }
+
     /// Returns sink fragment vnode mapping.
     /// Note that: the real sink fragment is also stored as `TableFragments`, it's possible that
     /// there's no fragment with `FragmentType::Sink` exists.
@@ -54,6 +54,8 @@ pub struct MetaMetrics {
     pub compact_frequency: IntCounterVec,
 
     pub level_file_size: IntGaugeVec,
+    /// The estimated bytes to compact in each compaction group
+    pub compact_pending_bytes: IntGaugeVec,
     /// Hummock version size
     pub version_size: IntGauge,
     /// The version Id of current version.
@@ -212,6 +214,14 @@ impl MetaMetrics {
         )
         .unwrap();
 
+        let compact_pending_bytes = register_int_gauge_vec_with_registry!(
+            "storage_compact_pending_bytes",
+            "estimated bytes to compact in each compaction group",
+            &["group"],
+            registry
+        )
+        .unwrap();
+
         let worker_num = register_int_gauge_vec_with_registry!(
             "worker_num",
             "number of nodes in the cluster",
@@ -237,6 +247,7 @@ impl MetaMetrics {
             level_compact_cnt,
             compact_frequency,
             level_file_size,
+            compact_pending_bytes,
             version_size,
             current_version_id,
             checkpoint_version_id,
@@ -21,6 +21,7 @@ use prost::Message;
 use piestream_common::bail;
 use piestream_common::monitor::process_linux::monitor_process;
 use piestream_common_service::metrics_manager::MetricsManager;
+use piestream_pb::backup_service::backup_service_server::BackupServiceServer;
 use piestream_pb::ddl_service::ddl_service_server::DdlServiceServer;
 use piestream_pb::health::health_server::HealthServer;
 use piestream_pb::hummock::hummock_manager_service_server::HummockManagerServiceServer;
@@ -35,10 +36,12 @@ use tokio::sync::oneshot::Sender;
 use tokio::task::JoinHandle;
 
 use super::intercept::MetricsMiddlewareLayer;
+use super::service::backup_service::BackupServiceImpl;
 use super::service::health_service::HealthServiceImpl;
 use super::service::notification_service::NotificationServiceImpl;
 use super::service::scale_service::ScaleServiceImpl;
 use super::DdlServiceImpl;
+use crate::backup_restore::BackupManager;
 use crate::barrier::{BarrierScheduler, GlobalBarrierManager};
 use crate::hummock::compaction_group::manager::CompactionGroupManager;
 use crate::hummock::{CompactionScheduler, HummockManager};
@@ -53,7 +56,9 @@ use crate::rpc::service::hummock_service::HummockServiceImpl;
 use crate::rpc::service::stream_service::StreamServiceImpl;
 use crate::rpc::service::user_service::UserServiceImpl;
 use crate::rpc::{META_CF_NAME, META_LEADER_KEY, META_LEASE_KEY};
-use crate::storage::{EtcdMetaStore, MemStore, MetaStore, MetaStoreError, Transaction};
+use crate::storage::{
+    EtcdMetaStore, LocalFileMetaStore, MemStore, MetaStore, MetaStoreError, Transaction,
+};
 use crate::stream::{GlobalStreamManager, SourceManager};
 use crate::{hummock, MetaResult};
 
@@ -64,6 +69,9 @@ pub enum MetaStoreBackend {
         credentials: Option<(String, String)>,
     },
     Mem,
+    LocalFile {
+        path: String,
+    },
 }
 
 #[derive(Clone)]
@@ -128,6 +136,17 @@ pub async fn rpc_serve(
             )
             .await
         }
+        MetaStoreBackend::LocalFile { path } => {
+            let meta_store = Arc::new(LocalFileMetaStore::open(path)?);
+            rpc_serve_with_store(
+                meta_store,
+                address_info,
+                max_heartbeat_interval,
+                lease_interval_secs,
+                opts,
+            )
+            .await
+        }
     }
 }
 
@@ -336,17 +355,8 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         .unwrap(),
     );
 
-    #[cfg(not(madsim))]
-    if let Some(dashboard_addr) = address_info.dashboard_addr.take() {
-        let dashboard_service = crate::dashboard::DashboardService {
-            dashboard_addr,
-            cluster_manager: cluster_manager.clone(),
-            fragment_manager: fragment_manager.clone(),
-            meta_store: env.meta_store_ref(),
-        };
-        // TODO: join dashboard service back to local thread.
-        tokio::spawn(dashboard_service.serve(address_info.ui_path));
-    }
+    // Must be created before any SST is vacuumed, as it restores the SSTs pinned by meta snapshots.
+    let backup_manager = Arc::new(BackupManager::new(env.clone(), hummock_manager.clone()).await?);
 
     let catalog_manager = Arc::new(CatalogManager::new(env.clone()).await.unwrap());
 
@@ -375,6 +385,21 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         meta_metrics.clone(),
     ));
 
+    #[cfg(not(madsim))]
+    if let Some(dashboard_addr) = address_info.dashboard_addr.take() {
+        let dashboard_service = crate::dashboard::DashboardService {
+            dashboard_addr,
+            cluster_manager: cluster_manager.clone(),
+            fragment_manager: fragment_manager.clone(),
+            hummock_manager: hummock_manager.clone(),
+            barrier_monitor: barrier_manager.barrier_monitor(),
+            compute_clients: Arc::new(piestream_rpc_client::ComputeClientPool::default()),
+            meta_store: env.meta_store_ref(),
+        };
+        // TODO: join dashboard service back to local thread.
+        tokio::spawn(dashboard_service.serve(address_info.ui_path));
+    }
+
     {
         let source_manager = source_manager.clone();
         tokio::spawn(async move {
@@ -425,6 +450,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         source_manager.clone(),
         cluster_manager.clone(),
         fragment_manager.clone(),
+        barrier_manager.clone(),
         table_background_deleter,
     );
 
@@ -461,6 +487,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         fragment_manager.clone(),
     );
     let health_srv = HealthServiceImpl::new();
+    let backup_srv = BackupServiceImpl::new(backup_manager);
 
     if let Some(prometheus_addr) = address_info.prometheus_addr {
         MetricsManager::boot_metrics_service(
@@ -493,6 +520,16 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         .await,
     );
     sub_tasks.push(HummockManager::start_compaction_heartbeat(hummock_manager).await);
+    if env.opts.enable_auto_scaling {
+        sub_tasks.push(
+            GlobalStreamManager::start_auto_scale_controller(
+                stream_manager.clone(),
+                env.notification_manager_ref(),
+                env.opts.auto_scaling_dry_run,
+            )
+            .await,
+        );
+    }
     sub_tasks.push((lease_handle, lease_shutdown));
     sub_tasks.push((deleter_handle, deleter_shutdown));
     if cfg!(not(test)) {
@@ -533,6 +570,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
             .add_service(UserServiceServer::new(user_srv))
             .add_service(ScaleServiceServer::new(scale_srv))
             .add_service(HealthServer::new(health_srv))
+            .add_service(BackupServiceServer::new(backup_srv))
             .serve(address_info.listen_addr)
             .await
             .unwrap();
@@ -605,4 +643,25 @@ mod tests {
         .await
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_leader_lease_local_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let meta_store = Arc::new(LocalFileMetaStore::open(dir.path()).unwrap());
+        let (_, handle, closer) = register_leader_for_meta("node1".to_string(), meta_store, 10)
+            .await
+            .unwrap();
+        closer.send(()).unwrap();
+        handle.await.unwrap();
+
+        // The lease is persisted, so another node can't become the leader after restart.
+        let meta_store = Arc::new(LocalFileMetaStore::open(dir.path()).unwrap());
+        let ret = register_leader_for_meta("node2".to_string(), meta_store.clone(), 10).await;
+        assert!(ret.is_err());
+
+        let (leader, _, _) = register_leader_for_meta("node1".to_string(), meta_store, 10)
+            .await
+            .unwrap();
+        assert_eq!(leader.lease_id, 1);
+    }
 }
@@ -0,0 +1,72 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piestream_pb::backup_service::backup_service_server::BackupService;
+use piestream_pb::backup_service::{
+    BackupMetaRequest, BackupMetaResponse, DeleteMetaSnapshotRequest, DeleteMetaSnapshotResponse,
+    ListMetaSnapshotMetadataRequest, ListMetaSnapshotMetadataResponse,
+};
+use tonic::{Request, Response, Status};
+
+use crate::backup_restore::BackupManagerRef;
+use crate::storage::MetaStore;
+
+pub struct BackupServiceImpl<S: MetaStore> {
+    backup_manager: BackupManagerRef<S>,
+}
+
+impl<S> BackupServiceImpl<S>
+where
+    S: MetaStore,
+{
+    pub fn new(backup_manager: BackupManagerRef<S>) -> Self {
+        Self { backup_manager }
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> BackupService for BackupServiceImpl<S>
+where
+    S: MetaStore,
+{
+    #[cfg_attr(coverage, no_coverage)]
+    async fn backup_meta(
+        &self,
+        _request: Request<BackupMetaRequest>,
+    ) -> Result<Response<BackupMetaResponse>, Status> {
+        let snapshot_id = self.backup_manager.backup().await?;
+        Ok(Response::new(BackupMetaResponse { snapshot_id }))
+    }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn list_meta_snapshot_metadata(
+        &self,
+        _request: Request<ListMetaSnapshotMetadataRequest>,
+    ) -> Result<Response<ListMetaSnapshotMetadataResponse>, Status> {
+        let snapshot_metadata = self.backup_manager.list().await;
+        Ok(Response::new(ListMetaSnapshotMetadataResponse {
+            snapshot_metadata,
+        }))
+    }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn delete_meta_snapshot(
+        &self,
+        request: Request<DeleteMetaSnapshotRequest>,
+    ) -> Result<Response<DeleteMetaSnapshotResponse>, Status> {
+        let snapshot_ids = request.into_inner().snapshot_ids;
+        self.backup_manager.delete(&snapshot_ids).await?;
+        Ok(Response::new(DeleteMetaSnapshotResponse {}))
+    }
+}
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use piestream_common::catalog::CatalogVersion;
 use piestream_pb::catalog::table::OptionalAssociatedSourceId;
@@ -25,6 +25,7 @@ use piestream_pb::stream_plan::stream_node::NodeBody;
 use piestream_pb::stream_plan::{StreamFragmentGraph, StreamNode};
 use tonic::{Request, Response, Status};
 
+use crate::barrier::BarrierManagerRef;
 use crate::manager::{
     CatalogManagerRef, ClusterManagerRef, FragmentManagerRef, IdCategory, IdCategoryType,
     MetaSrvEnv, NotificationVersion, SourceId, StreamingJob, StreamingJobBackgroundDeleterRef,
@@ -46,6 +47,7 @@ pub struct DdlServiceImpl<S: MetaStore> {
     source_manager: SourceManagerRef<S>,
     cluster_manager: ClusterManagerRef<S>,
     fragment_manager: FragmentManagerRef<S>,
+    barrier_manager: BarrierManagerRef<S>,
     table_background_deleter: StreamingJobBackgroundDeleterRef,
 }
 
@@ -61,6 +63,7 @@ where
         source_manager: SourceManagerRef<S>,
         cluster_manager: ClusterManagerRef<S>,
         fragment_manager: FragmentManagerRef<S>,
+        barrier_manager: BarrierManagerRef<S>,
         table_background_deleter: StreamingJobBackgroundDeleterRef,
     ) -> Self {
         Self {
@@ -70,6 +73,7 @@ where
             source_manager,
             cluster_manager,
             fragment_manager,
+            barrier_manager,
             table_background_deleter,
         }
     }
@@ -246,9 +250,13 @@ where
         let fragment_graph = req.get_fragment_graph()?.clone();
 
         let mut stream_job = StreamingJob::MaterializedView(mview);
-        let version = self
-            .create_stream_job(&mut stream_job, fragment_graph)
-            .await?;
+        let version = if req.background {
+            self.create_stream_job_in_background(&mut stream_job, fragment_graph)
+                .await?
+        } else {
+            self.create_stream_job(&mut stream_job, fragment_graph)
+                .await?
+        };
 
         Ok(Response::new(CreateMaterializedViewResponse {
             status: None,
@@ -385,6 +393,80 @@ where
         let tables = Table::list(self.env.meta_store()).await?;
         Ok(Response::new(RisectlListStateTablesResponse { tables }))
     }
+
+    async fn alter_parallelism(
+        &self,
+        request: Request<AlterParallelismRequest>,
+    ) -> Result<Response<AlterParallelismResponse>, Status> {
+        self.env.idle_manager().record_activity();
+
+        let req = request.into_inner();
+        let parallelism = (req.parallelism != 0).then_some(req.parallelism);
+        self.stream_manager
+            .alter_parallelism(req.table_id.into(), parallelism)
+            .await?;
+
+        Ok(Response::new(AlterParallelismResponse { status: None }))
+    }
+
+    async fn get_ddl_progress(
+        &self,
+        _request: Request<GetDdlProgressRequest>,
+    ) -> Result<Response<GetDdlProgressResponse>, Status> {
+        let creating_tables: HashMap<_, _> = self
+            .catalog_manager
+            .list_creating_tables()
+            .await
+            .into_iter()
+            .map(|t| (t.id, t.name))
+            .collect();
+        let ddl_progress = self
+            .barrier_manager
+            .get_ddl_progress()
+            .into_iter()
+            .map(|mut progress| {
+                if let Some(name) = creating_tables.get(&progress.id) {
+                    progress.name = name.clone();
+                }
+                progress
+            })
+            .collect();
+
+        Ok(Response::new(GetDdlProgressResponse { ddl_progress }))
+    }
+
+    async fn cancel_creating_jobs(
+        &self,
+        request: Request<CancelCreatingJobsRequest>,
+    ) -> Result<Response<CancelCreatingJobsResponse>, Status> {
+        self.env.idle_manager().record_activity();
+
+        let req = request.into_inner();
+        let tracked_jobs: HashSet<_> = self
+            .barrier_manager
+            .get_ddl_progress()
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+
+        let mut canceled_jobs = vec![];
+        for job_id in req.job_ids {
+            // Only the jobs whose backfill is being tracked can be canceled.
+            if !tracked_jobs.contains(&job_id) {
+                tracing::warn!("job {} is not in creating, skip canceling", job_id);
+                continue;
+            }
+            self.stream_manager
+                .cancel_create_materialized_view(&job_id.into())
+                .await?;
+            canceled_jobs.push(job_id);
+        }
+
+        Ok(Response::new(CancelCreatingJobsResponse {
+            status: None,
+            canceled_jobs,
+        }))
+    }
 }
 
 impl<S> DdlServiceImpl<S>
@@ -412,6 +494,43 @@ where
         }
     }
 
+    /// `create_stream_job_in_background` prepares a stream job, and then creates it in a background
+    /// task, so that the creation won't be canceled when the client disconnects. Since the job is
+    /// invisible in catalog until it's finished, the current version of the catalog is returned.
+    async fn create_stream_job_in_background(
+        &self,
+        stream_job: &mut StreamingJob,
+        fragment_graph: StreamFragmentGraph,
+    ) -> MetaResult<NotificationVersion> {
+        let (mut ctx, table_fragments) =
+            self.prepare_stream_job(stream_job, fragment_graph).await?;
+
+        let this = self.clone();
+        let stream_job = stream_job.clone();
+        tokio::spawn(async move {
+            let result = match this
+                .stream_manager
+                .create_materialized_view(table_fragments, &mut ctx)
+                .await
+            {
+                Ok(_) => this.finish_stream_job(&stream_job, &ctx).await.map(|_| ()),
+                Err(err) => this
+                    .cancel_stream_job(&stream_job, &ctx)
+                    .await
+                    .and(Err(err)),
+            };
+            if let Err(err) = result {
+                tracing::warn!(
+                    "failed to create streaming job {} in background: {}",
+                    stream_job.id(),
+                    err
+                );
+            }
+        });
+
+        Ok(self.env.notification_manager().current_version().await)
+    }
+
     /// `prepare_stream_job` prepares a stream job and returns the context and table fragments.
     async fn prepare_stream_job(
         &self,
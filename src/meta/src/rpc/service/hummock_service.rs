@@ -453,6 +453,19 @@ where
         }))
     }
 
+    async fn rise_ctl_remove_dangling_ssts(
+        &self,
+        request: Request<RiseCtlRemoveDanglingSstsRequest>,
+    ) -> Result<Response<RiseCtlRemoveDanglingSstsResponse>, Status> {
+        let removed_sst_ids = self
+            .hummock_manager
+            .remove_dangling_ssts(&request.into_inner().sst_ids)
+            .await?;
+        Ok(Response::new(RiseCtlRemoveDanglingSstsResponse {
+            removed_sst_ids,
+        }))
+    }
+
     async fn get_assigned_compact_task_num(
         &self,
         _request: Request<GetAssignedCompactTaskNumRequest>,
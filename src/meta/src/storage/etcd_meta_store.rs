@@ -122,7 +122,7 @@ struct ListViewer {
 }
 
 impl SnapshotViewer for ListViewer {
-    type Output = Vec<Vec<u8>>;
+    type Output = Vec<(Key, Value)>;
 
     type OutputFuture<'a> = impl Future<Output = MetaStoreResult<(i64, Self::Output)>> + 'a;
 
@@ -141,8 +141,12 @@ impl SnapshotViewer for ListViewer {
                     "Etcd response missing header"
                 )));
             };
-            let value = res.kvs().iter().map(|kv| kv.value().to_vec()).collect();
-            Ok((new_revision, value))
+            let kvs = res
+                .kvs()
+                .iter()
+                .map(|kv| (kv.key()[self.key.len()..].to_vec(), kv.value().to_vec()))
+                .collect();
+            Ok((new_revision, kvs))
         }
     }
 }
@@ -150,6 +154,11 @@ impl SnapshotViewer for ListViewer {
 #[async_trait]
 impl Snapshot for EtcdSnapshot {
     async fn list_cf(&self, cf: &str) -> MetaStoreResult<Vec<Vec<u8>>> {
+        let kvs = self.list_cf_with_key(cf).await?;
+        Ok(kvs.into_iter().map(|(_, value)| value).collect())
+    }
+
+    async fn list_cf_with_key(&self, cf: &str) -> MetaStoreResult<Vec<(Key, Value)>> {
         let view = ListViewer {
             key: encode_etcd_key(cf, &[]),
         };
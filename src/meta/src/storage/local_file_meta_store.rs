@@ -0,0 +1,353 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::{Buf, BufMut};
+use itertools::Itertools;
+use parking_lot::Mutex;
+use tokio::sync::RwLock;
+
+use super::{
+    Key, MemSnapshot, MemStoreInner, MetaStore, MetaStoreError, MetaStoreResult, Operation,
+    Transaction, Value,
+};
+
+const WAL_FILE_NAME: &str = "meta.wal";
+const SNAPSHOT_FILE_NAME: &str = "meta.snapshot";
+const SNAPSHOT_TMP_FILE_NAME: &str = "meta.snapshot.tmp";
+const LOCK_FILE_NAME: &str = "meta.lock";
+
+/// The WAL is compacted into a new snapshot file once it grows beyond this size.
+const DEFAULT_WAL_COMPACTION_THRESHOLD: u64 = 64 << 20;
+
+/// Each record is prefixed by the length and the crc32 checksum of its payload.
+const RECORD_HEADER_SIZE: usize = 8;
+
+const OP_PUT: u8 = 0;
+const OP_DELETE: u8 = 1;
+
+/// A persistent meta store backed by local files, for single-node deployments without etcd.
+///
+/// All the data is kept in memory. Every transaction is appended to a write-ahead log and synced
+/// before it's applied, and the log is periodically compacted into a snapshot file. On open, the
+/// snapshot and the log are replayed to recover the data. The file I/O is done on the blocking
+/// thread pool.
+///
+/// The directory is exclusively locked, so that only one meta node can open it at a time.
+#[derive(Clone)]
+pub struct LocalFileMetaStore {
+    inner: Arc<RwLock<MemStoreInner>>,
+    wal: Arc<Mutex<Wal>>,
+}
+
+struct Wal {
+    dir: PathBuf,
+    file: File,
+    size: u64,
+    compaction_threshold: u64,
+    /// Holds the exclusive lock of the directory until the store is dropped.
+    _lock_file: File,
+}
+
+impl LocalFileMetaStore {
+    /// Open the store in `dir`, which is created if it doesn't exist.
+    pub fn open(dir: impl AsRef<Path>) -> MetaStoreResult<Self> {
+        Self::open_with_compaction_threshold(dir, DEFAULT_WAL_COMPACTION_THRESHOLD)
+    }
+
+    pub(super) fn open_with_compaction_threshold(
+        dir: impl AsRef<Path>,
+        compaction_threshold: u64,
+    ) -> MetaStoreResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(internal_error)?;
+        let lock_file = lock_dir(&dir)?;
+        let mut inner = MemStoreInner::default();
+
+        let snapshot_path = dir.join(SNAPSHOT_FILE_NAME);
+        if snapshot_path.exists() {
+            let buf = fs::read(&snapshot_path).map_err(internal_error)?;
+            // The snapshot file is renamed into place atomically, so it must be complete.
+            if replay(&mut inner, &buf)? != buf.len() {
+                return Err(internal_error(anyhow::anyhow!(
+                    "corrupted snapshot file {}",
+                    snapshot_path.display()
+                )));
+            }
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(dir.join(WAL_FILE_NAME))
+            .map_err(internal_error)?;
+        let mut buf = vec![];
+        file.read_to_end(&mut buf).map_err(internal_error)?;
+        let size = replay(&mut inner, &buf)?;
+        if size != buf.len() {
+            // The last record was not completely written before crash. It must not have been
+            // applied, so it's safe to discard it. Complete but corrupted records fail `replay`
+            // instead.
+            tracing::warn!(
+                "discard {} bytes of incomplete record at the end of meta store WAL",
+                buf.len() - size
+            );
+            file.set_len(size as u64).map_err(internal_error)?;
+            file.sync_all().map_err(internal_error)?;
+        }
+
+        Ok(Self {
+            inner: Arc::new(RwLock::new(inner)),
+            wal: Arc::new(Mutex::new(Wal {
+                dir,
+                file,
+                size: size as u64,
+                compaction_threshold,
+                _lock_file: lock_file,
+            })),
+        })
+    }
+}
+
+impl Wal {
+    fn append(&mut self, record: &[u8]) -> MetaStoreResult<()> {
+        let ret = self
+            .file
+            .write_all(record)
+            .and_then(|_| self.file.sync_data());
+        if let Err(e) = ret {
+            // Remove the partially written record, otherwise the records appended later would be
+            // lost on recovery.
+            let _ = self.file.set_len(self.size);
+            return Err(internal_error(e));
+        }
+        self.size += record.len() as u64;
+        Ok(())
+    }
+
+    /// Write `snapshot`, the encoded record of all the data, into a new snapshot file and
+    /// truncate the WAL.
+    fn compact(&mut self, snapshot: &[u8]) -> std::io::Result<()> {
+        let tmp_path = self.dir.join(SNAPSHOT_TMP_FILE_NAME);
+        let mut tmp_file = File::create(&tmp_path)?;
+        tmp_file.write_all(snapshot)?;
+        tmp_file.sync_all()?;
+        fs::rename(&tmp_path, self.dir.join(SNAPSHOT_FILE_NAME))?;
+        File::open(&self.dir)?.sync_all()?;
+
+        // Replaying the WAL on the new snapshot is idempotent, so it doesn't matter if we crash
+        // before the WAL is truncated.
+        self.file.set_len(0)?;
+        self.file.sync_all()?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MetaStore for LocalFileMetaStore {
+    type Snapshot = MemSnapshot;
+
+    async fn snapshot(&self) -> Self::Snapshot {
+        let guard = self.inner.clone().read_owned().await;
+        MemSnapshot(guard)
+    }
+
+    async fn put_cf(&self, cf: &str, key: Key, value: Value) -> MetaStoreResult<()> {
+        let mut txn = Transaction::default();
+        txn.put(cf.to_string(), key, value);
+        self.txn(txn).await
+    }
+
+    async fn delete_cf(&self, cf: &str, key: &[u8]) -> MetaStoreResult<()> {
+        let mut txn = Transaction::default();
+        txn.delete(cf.to_string(), key.to_vec());
+        self.txn(txn).await
+    }
+
+    async fn txn(&self, txn: Transaction) -> MetaStoreResult<()> {
+        let mut inner = self.inner.write().await;
+        let (conds, ops) = txn.into_parts();
+        if !inner.check_preconditions(&conds) {
+            return Err(MetaStoreError::TransactionAbort());
+        }
+
+        // Writes are serialized by the lock of `inner`, which is held until the WAL is written.
+        let record = encode_record(&ops);
+        let need_compaction = asyncify(self.wal.clone(), move |wal| {
+            wal.append(&record)?;
+            Ok(wal.size >= wal.compaction_threshold)
+        })
+        .await?;
+        inner.apply_operations(ops);
+        if need_compaction {
+            let snapshot = encode_record(
+                &inner
+                    .iter()
+                    .map(|(cf, key, value)| Operation::Put {
+                        cf: cf.clone(),
+                        key: key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect_vec(),
+            );
+            // The transaction is already persisted in the WAL, so it's fine to retry compaction
+            // on the next write.
+            if let Err(e) = asyncify(self.wal.clone(), move |wal| {
+                wal.compact(&snapshot).map_err(internal_error)
+            })
+            .await
+            {
+                tracing::warn!("failed to compact meta store WAL: {:?}", e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run `f` on the WAL on the blocking thread pool.
+async fn asyncify<T: Send + 'static>(
+    wal: Arc<Mutex<Wal>>,
+    f: impl FnOnce(&mut Wal) -> MetaStoreResult<T> + Send + 'static,
+) -> MetaStoreResult<T> {
+    tokio::task::spawn_blocking(move || f(&mut *wal.lock()))
+        .await
+        .map_err(internal_error)?
+}
+
+/// Take the exclusive lock of `dir`, failing if it's already taken by another store.
+fn lock_dir(dir: &Path) -> MetaStoreResult<File> {
+    let lock_file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .open(dir.join(LOCK_FILE_NAME))
+        .map_err(internal_error)?;
+    // SAFETY: the file descriptor is valid as long as `lock_file` is alive.
+    let ret = unsafe { libc::flock(lock_file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) };
+    if ret != 0 {
+        return Err(internal_error(anyhow::anyhow!(
+            "failed to lock meta store directory {}: {}",
+            dir.display(),
+            std::io::Error::last_os_error()
+        )));
+    }
+    Ok(lock_file)
+}
+
+fn internal_error(e: impl Into<anyhow::Error>) -> MetaStoreError {
+    MetaStoreError::Internal(e.into())
+}
+
+fn put_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.put_u32_le(bytes.len() as u32);
+    buf.put_slice(bytes);
+}
+
+fn get_bytes(buf: &mut &[u8]) -> MetaStoreResult<Vec<u8>> {
+    if buf.remaining() < 4 {
+        return Err(internal_error(anyhow::anyhow!(
+            "malformed meta store record"
+        )));
+    }
+    let len = buf.get_u32_le() as usize;
+    if buf.remaining() < len {
+        return Err(internal_error(anyhow::anyhow!(
+            "malformed meta store record"
+        )));
+    }
+    let bytes = buf[..len].to_vec();
+    buf.advance(len);
+    Ok(bytes)
+}
+
+fn encode_record(ops: &[Operation]) -> Vec<u8> {
+    let mut payload = vec![];
+    for op in ops {
+        match op {
+            Operation::Put { cf, key, value } => {
+                payload.put_u8(OP_PUT);
+                put_bytes(&mut payload, cf.as_bytes());
+                put_bytes(&mut payload, key);
+                put_bytes(&mut payload, value);
+            }
+            Operation::Delete { cf, key } => {
+                payload.put_u8(OP_DELETE);
+                put_bytes(&mut payload, cf.as_bytes());
+                put_bytes(&mut payload, key);
+            }
+        }
+    }
+    let mut record = Vec::with_capacity(RECORD_HEADER_SIZE + payload.len());
+    record.put_u32_le(payload.len() as u32);
+    record.put_u32_le(crc32fast::hash(&payload));
+    record.put_slice(&payload);
+    record
+}
+
+fn decode_operations(mut payload: &[u8]) -> MetaStoreResult<Vec<Operation>> {
+    let mut ops = vec![];
+    while payload.has_remaining() {
+        let tag = payload.get_u8();
+        let cf = String::from_utf8(get_bytes(&mut payload)?).map_err(internal_error)?;
+        let key = get_bytes(&mut payload)?;
+        let op = match tag {
+            OP_PUT => Operation::Put {
+                cf,
+                key,
+                value: get_bytes(&mut payload)?,
+            },
+            OP_DELETE => Operation::Delete { cf, key },
+            _ => {
+                return Err(internal_error(anyhow::anyhow!(
+                    "unknown meta store operation {}",
+                    tag
+                )))
+            }
+        };
+        ops.push(op);
+    }
+    Ok(ops)
+}
+
+/// Apply all the complete records in `buf` to `inner`, and return the length of them. Only the
+/// last record may be incomplete, i.e. torn by a crash. A checksum mismatch of any complete record,
+/// including the last one, is an error.
+fn replay(inner: &mut MemStoreInner, mut buf: &[u8]) -> MetaStoreResult<usize> {
+    let mut offset = 0;
+    while buf.remaining() >= RECORD_HEADER_SIZE {
+        let len = (&buf[0..4]).get_u32_le() as usize;
+        let checksum = (&buf[4..8]).get_u32_le();
+        if buf.remaining() < RECORD_HEADER_SIZE + len {
+            break;
+        }
+        let payload = &buf[RECORD_HEADER_SIZE..RECORD_HEADER_SIZE + len];
+        if crc32fast::hash(payload) != checksum {
+            return Err(internal_error(anyhow::anyhow!(
+                "checksum mismatch of meta store record at offset {}",
+                offset
+            )));
+        }
+        inner.apply_operations(decode_operations(payload)?);
+        buf.advance(RECORD_HEADER_SIZE + len);
+        offset += RECORD_HEADER_SIZE + len;
+    }
+    Ok(offset)
+}
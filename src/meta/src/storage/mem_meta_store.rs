@@ -19,10 +19,11 @@ use async_trait::async_trait;
 use tokio::sync::{OwnedRwLockReadGuard, RwLock};
 
 use super::{
-    ColumnFamily, Key, MetaStore, MetaStoreError, MetaStoreResult, Snapshot, Transaction, Value,
+    ColumnFamily, Key, MetaStore, MetaStoreError, MetaStoreResult, Operation, Precondition,
+    Snapshot, Transaction, Value,
 };
 
-pub struct MemSnapshot(OwnedRwLockReadGuard<MemStoreInner>);
+pub struct MemSnapshot(pub(super) OwnedRwLockReadGuard<MemStoreInner>);
 
 /// [`MetaStore`] implemented in memory.
 ///
@@ -34,7 +35,7 @@ pub struct MemStore {
 
 /// The first level is the cf name, the second level is the key.
 #[derive(Clone, Debug, Default)]
-struct MemStoreInner(HashMap<ColumnFamily, BTreeMap<Key, Value>>);
+pub(super) struct MemStoreInner(HashMap<ColumnFamily, BTreeMap<Key, Value>>);
 
 impl MemStoreInner {
     #[inline(always)]
@@ -46,6 +47,43 @@ impl MemStoreInner {
     fn cf_mut(&mut self, cf: &str) -> &mut BTreeMap<Key, Value> {
         self.0.entry(cf.to_string()).or_default()
     }
+
+    /// Iterates over all the key-value pairs of all the column families.
+    pub(super) fn iter(&self) -> impl Iterator<Item = (&ColumnFamily, &Key, &Value)> {
+        self.0
+            .iter()
+            .flat_map(|(cf, kvs)| kvs.iter().map(move |(k, v)| (cf, k, v)))
+    }
+
+    pub(super) fn check_preconditions(&self, conds: &[Precondition]) -> bool {
+        use super::Precondition::*;
+
+        conds.iter().all(|cond| match cond {
+            KeyExists { cf, key } => self
+                .cf_ref(cf.as_str())
+                .map(|cf| cf.contains_key(&key[..]))
+                .unwrap_or(false),
+            KeyEqual { cf, key, value } => self
+                .cf_ref(cf.as_str())
+                .and_then(|cf| cf.get(key.as_slice()))
+                .map_or(false, |v| v.eq(value)),
+        })
+    }
+
+    pub(super) fn apply_operations(&mut self, ops: Vec<Operation>) {
+        use super::Operation::*;
+
+        for op in ops {
+            match op {
+                Put { cf, key, value } => {
+                    self.cf_mut(cf.as_str()).insert(key, value);
+                }
+                Delete { cf, key } => {
+                    self.cf_mut(cf.as_str()).remove(&key);
+                }
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -58,6 +96,14 @@ impl Snapshot for MemSnapshot {
         })
     }
 
+    #[inline(always)]
+    async fn list_cf_with_key(&self, cf: &str) -> MetaStoreResult<Vec<(Key, Value)>> {
+        Ok(match self.0.cf_ref(cf) {
+            Some(cf) => cf.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            None => vec![],
+        })
+    }
+
     #[inline(always)]
     async fn get_cf(&self, cf: &str, key: &[u8]) -> MetaStoreResult<Value> {
         self.0
@@ -96,45 +142,12 @@ impl MetaStore for MemStore {
     }
 
     async fn txn(&self, txn: Transaction) -> MetaStoreResult<()> {
-        use super::Operation::*;
-        use super::Precondition::*;
-
         let mut inner = self.inner.write().await;
         let (conds, ops) = txn.into_parts();
-
-        for cond in conds {
-            match cond {
-                KeyExists { cf, key } => {
-                    if !inner
-                        .cf_ref(cf.as_str())
-                        .map(|cf| cf.contains_key(&key[..]))
-                        .unwrap_or(false)
-                    {
-                        return Err(MetaStoreError::TransactionAbort());
-                    }
-                }
-                KeyEqual { cf, key, value } => {
-                    let v = inner
-                        .cf_ref(cf.as_str())
-                        .map(|cf| cf.get(key.as_slice()))
-                        .unwrap_or(None);
-                    if !v.map_or(false, |v| v.eq(&value)) {
-                        return Err(MetaStoreError::TransactionAbort());
-                    }
-                }
-            }
-        }
-
-        for op in ops {
-            match op {
-                Put { cf, key, value } => {
-                    inner.cf_mut(cf.as_str()).insert(key, value);
-                }
-                Delete { cf, key } => {
-                    inner.cf_mut(cf.as_str()).remove(&key);
-                }
-            }
+        if !inner.check_preconditions(&conds) {
+            return Err(MetaStoreError::TransactionAbort());
         }
+        inner.apply_operations(ops);
         Ok(())
     }
 }
@@ -23,6 +23,8 @@ pub const DEFAULT_COLUMN_FAMILY: &str = "default";
 #[async_trait]
 pub trait Snapshot: Sync + Send + 'static {
     async fn list_cf(&self, cf: &str) -> MetaStoreResult<Vec<Vec<u8>>>;
+    /// Lists all the key-value pairs in `cf`, with the keys not prefixed by `cf`.
+    async fn list_cf_with_key(&self, cf: &str) -> MetaStoreResult<Vec<(Key, Value)>>;
     async fn get_cf(&self, cf: &str, key: &[u8]) -> MetaStoreResult<Vec<u8>>;
 }
 
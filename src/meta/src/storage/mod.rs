@@ -14,6 +14,7 @@
 
 mod etcd_meta_store;
 mod etcd_retry_client;
+mod local_file_meta_store;
 mod mem_meta_store;
 pub mod meta_store;
 #[cfg(test)]
@@ -25,6 +26,7 @@ pub type Key = Vec<u8>;
 pub type Value = Vec<u8>;
 
 pub use etcd_meta_store::*;
+pub use local_file_meta_store::*;
 pub use mem_meta_store::*;
 pub use meta_store::*;
 pub use transaction::*;
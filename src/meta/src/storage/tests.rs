@@ -17,8 +17,8 @@ use async_trait::async_trait;
 use itertools::Itertools;
 
 use crate::storage::{
-    Key, MemStore, MetaStore, MetaStoreError, MetaStoreResult, Operation, Snapshot, Transaction,
-    Value,
+    Key, LocalFileMetaStore, MemStore, MetaStore, MetaStoreError, MetaStoreResult, Operation,
+    Snapshot, Transaction, Value,
 };
 
 const TEST_DEFAULT_CF: &str = "TEST_DEFAULT";
@@ -95,6 +95,14 @@ async fn test_meta_store_basic<S: MetaStore>(store: &S) -> MetaStoreResult<()> {
         let snapshot = store.snapshot().await;
         let vals = snapshot.list_cf("test_cf").await?;
         assert_eq!(vals.len(), 2);
+        let kvs = snapshot.list_cf_with_key("test_cf").await?;
+        assert_eq!(
+            kvs,
+            vec![
+                (b"key_1".to_vec(), b"value_1".to_vec()),
+                (b"key_2".to_vec(), b"value_2".to_vec()),
+            ]
+        );
         let vals = snapshot.list_cf(TEST_DEFAULT_CF).await?;
         assert_eq!(vals.len(), 3);
     }
@@ -252,3 +260,77 @@ async fn test_mem_store() -> MetaStoreResult<()> {
     test_meta_store_transaction(&store).await.unwrap();
     Ok(())
 }
+
+#[tokio::test]
+async fn test_local_file_store() -> MetaStoreResult<()> {
+    let dir = tempfile::tempdir().unwrap();
+    let store = LocalFileMetaStore::open(dir.path())?;
+    test_meta_store_basic(&store).await.unwrap();
+    test_meta_store_keys_share_prefix(&store).await.unwrap();
+    test_meta_store_overlapped_cf(&store).await.unwrap();
+    test_meta_store_transaction(&store).await.unwrap();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_local_file_store_recovery() -> MetaStoreResult<()> {
+    let dir = tempfile::tempdir().unwrap();
+    // Compact the WAL on every write for the second store.
+    for compaction_threshold in [u64::MAX, 1] {
+        let store =
+            LocalFileMetaStore::open_with_compaction_threshold(dir.path(), compaction_threshold)?;
+        store.put(b"k1".to_vec(), b"v1".to_vec()).await?;
+        store.put(b"k2".to_vec(), b"v2".to_vec()).await?;
+        store.put(b"k1".to_vec(), b"v3".to_vec()).await?;
+        store.delete(b"k2").await?;
+        let mut txn = Transaction::default();
+        txn.check_equal(TEST_DEFAULT_CF.to_string(), b"k1".to_vec(), b"v1".to_vec());
+        txn.put(TEST_DEFAULT_CF.to_string(), b"k3".to_vec(), b"v3".to_vec());
+        assert_matches!(
+            store.txn(txn).await,
+            Err(MetaStoreError::TransactionAbort())
+        );
+        drop(store);
+
+        let store = LocalFileMetaStore::open(dir.path())?;
+        assert_eq!(store.get(b"k1").await?, b"v3".to_vec());
+        assert_matches!(store.get(b"k2").await, Err(MetaStoreError::ItemNotFound(_)));
+        assert_matches!(store.get(b"k3").await, Err(MetaStoreError::ItemNotFound(_)));
+        store.delete(b"k1").await?;
+    }
+
+    // An incomplete record at the end of the WAL is discarded.
+    let store = LocalFileMetaStore::open(dir.path())?;
+    store.put(b"k4".to_vec(), b"v4".to_vec()).await?;
+    drop(store);
+    let mut wal = std::fs::OpenOptions::new()
+        .append(true)
+        .open(dir.path().join("meta.wal"))
+        .unwrap();
+    std::io::Write::write_all(&mut wal, &[42, 0, 0, 0, 1]).unwrap();
+    let store = LocalFileMetaStore::open(dir.path())?;
+    assert_eq!(store.list().await?, vec![b"v4".to_vec()]);
+    store.put(b"k5".to_vec(), b"v5".to_vec()).await?;
+    drop(store);
+    let store = LocalFileMetaStore::open(dir.path())?;
+    assert_eq!(store.list().await?, vec![b"v4".to_vec(), b"v5".to_vec()]);
+
+    // The directory can't be opened by another store at the same time.
+    assert!(LocalFileMetaStore::open(dir.path()).is_err());
+    drop(store);
+
+    // A corrupted record followed by others is not discarded silently.
+    let wal_path = dir.path().join("meta.wal");
+    let mut buf = std::fs::read(&wal_path).unwrap();
+    buf[8] ^= 0xff;
+    std::fs::write(&wal_path, &buf).unwrap();
+    assert!(LocalFileMetaStore::open(dir.path()).is_err());
+
+    // Neither is a complete but corrupted record at the end of the WAL.
+    buf[8] ^= 0xff;
+    *buf.last_mut().unwrap() ^= 0xff;
+    std::fs::write(&wal_path, &buf).unwrap();
+    assert!(LocalFileMetaStore::open(dir.path()).is_err());
+    assert_eq!(std::fs::read(&wal_path).unwrap(), buf);
+    Ok(())
+}
@@ -23,6 +23,7 @@ use num_integer::Integer;
 use num_traits::abs;
 use piestream_common::bail;
 use piestream_common::buffer::{Bitmap, BitmapBuilder};
+use piestream_common::catalog::TableId;
 use piestream_common::types::{ParallelUnitId, VIRTUAL_NODE_COUNT};
 use piestream_common::util::prost::is_stream_source;
 use piestream_pb::common::{worker_node, ActorInfo, ParallelUnit, WorkerNode, WorkerType};
@@ -38,14 +39,16 @@ use piestream_pb::stream_plan::{
 use piestream_pb::stream_service::{
     BroadcastActorInfoTableRequest, BuildActorsRequest, HangingChannel, UpdateActorsRequest,
 };
+use tokio::sync::oneshot::Sender;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 use crate::barrier::{Command, Reschedule};
-use crate::manager::{IdCategory, WorkerId};
+use crate::manager::{IdCategory, LocalNotification, NotificationManagerRef, WorkerId};
 use crate::model::{ActorId, DispatcherId, FragmentId, TableFragments};
 use crate::storage::MetaStore;
 use crate::stream::mapping::actor_mapping_from_bitmaps;
-use crate::stream::GlobalStreamManager;
+use crate::stream::{GlobalStreamManager, GlobalStreamManagerRef};
 use crate::MetaResult;
 
 #[derive(Debug)]
@@ -296,10 +299,347 @@ pub(crate) fn rebalance_actor_vnode(
     result
 }
 
+/// Chooses `parallelism` parallel units out of `parallel_units` for a hash distributed fragment
+/// currently running on `current`. The parallel units in use are kept as many as possible, and the
+/// rest are picked from the workers with the fewest chosen ones, so that the fragment is spread
+/// evenly over the compute nodes.
+fn select_parallel_units(
+    current: &BTreeSet<ParallelUnitId>,
+    parallel_units: &[ParallelUnit],
+    parallelism: usize,
+) -> BTreeSet<ParallelUnitId> {
+    let parallelism = min(parallelism, parallel_units.len());
+
+    // Worker id => (parallel units in use, idle parallel units)
+    let mut worker_parallel_units: BTreeMap<u32, (Vec<ParallelUnitId>, Vec<ParallelUnitId>)> =
+        BTreeMap::new();
+    for parallel_unit in parallel_units.iter().sorted_by_key(|p| p.id) {
+        let (in_use, idle) = worker_parallel_units
+            .entry(parallel_unit.worker_node_id)
+            .or_default();
+        if current.contains(&parallel_unit.id) {
+            in_use.push(parallel_unit.id);
+        } else {
+            idle.push(parallel_unit.id);
+        }
+    }
+
+    let mut chosen = BTreeSet::new();
+    let mut chosen_count: BTreeMap<u32, usize> =
+        worker_parallel_units.keys().map(|id| (*id, 0)).collect();
+    for pick_idle in [false, true] {
+        while chosen.len() < parallelism {
+            let candidate = worker_parallel_units
+                .iter_mut()
+                .map(|(worker_id, (in_use, idle))| {
+                    (worker_id, if pick_idle { idle } else { in_use })
+                })
+                .filter(|(_, candidates)| !candidates.is_empty())
+                .min_by_key(|(worker_id, _)| chosen_count[*worker_id]);
+            match candidate {
+                Some((worker_id, candidates)) => {
+                    chosen.insert(candidates.remove(0));
+                    *chosen_count.get_mut(worker_id).unwrap() += 1;
+                }
+                None => break,
+            }
+        }
+    }
+
+    chosen
+}
+
+/// Generates a reschedule plan that balances the actors of `fragments` over `parallel_units`,
+/// which should be the parallel units of all running compute nodes. Each fragment comes with the
+/// desired parallelism of its streaming job, where `None` means `auto`.
+///
+/// - A hash distributed fragment with `auto` parallelism is spread over all the parallel units, so
+///   that newly joined compute nodes take their share of vnodes. With a fixed parallelism, it is
+///   spread over that many parallel units instead, see [`select_parallel_units`]. In both cases,
+///   the actors on unavailable parallel units are removed.
+/// - A singleton fragment is moved only if its parallel unit becomes unavailable. The new parallel
+///   unit is the one with the fewest actors after rescheduling.
+///
+/// Fragments that don't need to be changed are not included in the plan.
+pub(crate) fn generate_balanced_reschedule<'a>(
+    fragments: impl IntoIterator<Item = (&'a Fragment, Option<u32>)>,
+    actor_status: &BTreeMap<ActorId, ActorStatus>,
+    parallel_units: &[ParallelUnit],
+) -> HashMap<FragmentId, ParallelUnitReschedule> {
+    let mut plan = HashMap::new();
+    let available: BTreeSet<ParallelUnitId> = parallel_units.iter().map(|p| p.id).collect();
+    if available.is_empty() {
+        return plan;
+    }
+
+    // The number of actors on each available parallel unit after rescheduling.
+    let mut actor_count: BTreeMap<ParallelUnitId, usize> =
+        available.iter().map(|id| (*id, 0)).collect();
+    let mut singletons_to_move = vec![];
+
+    for (fragment, parallelism) in fragments {
+        let current: BTreeSet<ParallelUnitId> = fragment
+            .actors
+            .iter()
+            .filter_map(|actor| {
+                actor_status
+                    .get(&actor.actor_id)
+                    .and_then(|status| status.parallel_unit.as_ref())
+                    .map(|parallel_unit| parallel_unit.id)
+            })
+            .collect();
+        if current.is_empty() {
+            continue;
+        }
+
+        match fragment.distribution_type() {
+            FragmentDistributionType::Hash => {
+                let target = match parallelism {
+                    Some(parallelism) => {
+                        select_parallel_units(&current, parallel_units, parallelism as usize)
+                    }
+                    None => available.clone(),
+                };
+                target
+                    .iter()
+                    .for_each(|id| *actor_count.get_mut(id).unwrap() += 1);
+
+                let removed_parallel_units = current.difference(&target).cloned().collect_vec();
+                let added_parallel_units = target.difference(&current).cloned().collect_vec();
+                if !removed_parallel_units.is_empty() || !added_parallel_units.is_empty() {
+                    plan.insert(
+                        fragment.fragment_id as FragmentId,
+                        ParallelUnitReschedule {
+                            added_parallel_units,
+                            removed_parallel_units,
+                        },
+                    );
+                }
+            }
+            _ => match current.iter().find(|id| available.contains(id)) {
+                Some(id) => *actor_count.get_mut(id).unwrap() += 1,
+                None => singletons_to_move.push((fragment.fragment_id as FragmentId, current)),
+            },
+        }
+    }
+
+    for (fragment_id, current) in singletons_to_move {
+        let (target, count) = actor_count
+            .iter_mut()
+            .min_by_key(|(_, count)| **count)
+            .unwrap();
+        *count += 1;
+        plan.insert(
+            fragment_id,
+            ParallelUnitReschedule {
+                added_parallel_units: vec![*target],
+                removed_parallel_units: current.into_iter().collect(),
+            },
+        );
+    }
+
+    plan
+}
+
+/// Returns the fragments that can't be rescheduled by [`GlobalStreamManager::reschedule_actors`]:
+/// fragments of streaming jobs still being created, chain fragments, fragments on either side of
+/// a `NoShuffle` dispatcher and materialize fragments with downstream.
+fn unschedulable_fragment_ids(all_table_fragments: &[TableFragments]) -> HashSet<FragmentId> {
+    let mut actor_fragment_ids = HashMap::new();
+    let mut skipped_fragment_ids = HashSet::new();
+    for table_fragments in all_table_fragments {
+        for fragment in table_fragments.fragments.values() {
+            for actor in &fragment.actors {
+                actor_fragment_ids.insert(actor.actor_id as ActorId, fragment.fragment_id);
+            }
+        }
+        if table_fragments.state() != table_fragments::State::Created {
+            skipped_fragment_ids.extend(table_fragments.fragment_ids());
+        }
+        skipped_fragment_ids.extend(table_fragments.chain_fragment_ids());
+    }
+
+    for table_fragments in all_table_fragments {
+        for fragment in table_fragments.fragments.values() {
+            for dispatcher in fragment.actors.iter().flat_map(|a| a.dispatcher.iter()) {
+                let downstream_fragment_ids = dispatcher
+                    .downstream_actor_id
+                    .iter()
+                    .filter_map(|actor_id| actor_fragment_ids.get(actor_id))
+                    .cloned()
+                    .collect_vec();
+                if downstream_fragment_ids.is_empty() {
+                    continue;
+                }
+                if dispatcher.r#type() == DispatcherType::NoShuffle {
+                    skipped_fragment_ids.insert(fragment.fragment_id);
+                    skipped_fragment_ids.extend(downstream_fragment_ids);
+                } else if fragment.fragment_type() == FragmentType::Sink {
+                    skipped_fragment_ids.insert(fragment.fragment_id);
+                }
+            }
+        }
+    }
+
+    skipped_fragment_ids
+}
+
 impl<S> GlobalStreamManager<S>
 where
     S: MetaStore,
 {
+    /// Starts the controller that rebalances actors when compute nodes join or leave the cluster.
+    /// If `dry_run` is set, the generated plans are only reported in the log.
+    pub async fn start_auto_scale_controller(
+        stream_manager: GlobalStreamManagerRef<S>,
+        notification_manager: NotificationManagerRef<S>,
+        dry_run: bool,
+    ) -> (JoinHandle<()>, Sender<()>) {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        notification_manager.insert_local_sender(tx).await;
+        let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel();
+        let join_handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    notification = rx.recv() => {
+                        match notification {
+                            None => return,
+                            Some(
+                                LocalNotification::WorkerNodeActivated(worker_node)
+                                | LocalNotification::WorkerNodeIsDeleted(worker_node),
+                            ) if worker_node.r#type() == WorkerType::ComputeNode => {}
+                            Some(_) => continue,
+                        }
+                    }
+                    // Shutdown controller
+                    _ = &mut shutdown_rx => {
+                        tracing::info!("Auto scale controller is stopped");
+                        return;
+                    }
+                }
+
+                // Membership changes usually come in bursts, so handle them all at once.
+                while rx.try_recv().is_ok() {}
+
+                if let Err(e) = stream_manager.auto_scale(dry_run).await {
+                    tracing::warn!("Failed to auto scale. {}", e);
+                }
+            }
+        });
+
+        (join_handle, shutdown_tx)
+    }
+
+    async fn auto_scale(&self, dry_run: bool) -> MetaResult<()> {
+        let plan = self.generate_auto_scale_plan().await?;
+        if plan.is_empty() {
+            return Ok(());
+        }
+
+        if dry_run {
+            tracing::info!("Auto scale plan (dry run): {:#?}", plan);
+            return Ok(());
+        }
+
+        tracing::info!("Auto scale plan: {:#?}", plan);
+        self.reschedule_actors(plan).await
+    }
+
+    /// Generates a balanced reschedule plan over the parallel units of all running compute nodes.
+    /// Fragments that can't be rescheduled by [`Self::reschedule_actors`] are skipped.
+    pub async fn generate_auto_scale_plan(
+        &self,
+    ) -> MetaResult<HashMap<FragmentId, ParallelUnitReschedule>> {
+        let parallel_units = self.cluster_manager.list_active_parallel_units().await;
+        let all_table_fragments = self.fragment_manager.list_table_fragments().await?;
+        let skipped_fragment_ids = unschedulable_fragment_ids(&all_table_fragments);
+
+        let actor_status: BTreeMap<_, _> = all_table_fragments
+            .iter()
+            .flat_map(|table_fragments| table_fragments.actor_status.clone())
+            .collect();
+
+        Ok(generate_balanced_reschedule(
+            all_table_fragments
+                .iter()
+                .flat_map(|table_fragments| {
+                    table_fragments
+                        .fragments
+                        .values()
+                        .map(|fragment| (fragment, table_fragments.parallelism()))
+                })
+                .filter(|(fragment, _)| !skipped_fragment_ids.contains(&fragment.fragment_id)),
+            &actor_status,
+            &parallel_units,
+        ))
+    }
+
+    /// Reschedules the fragments of the streaming job `table_id` to the desired `parallelism`
+    /// (`None` for `auto`) and persists it, so that later auto scaling honors it. Singleton
+    /// fragments are kept where they are. Fails without any change if some fragments would need
+    /// to be rescheduled but can't be.
+    pub async fn alter_parallelism(
+        &self,
+        table_id: TableId,
+        parallelism: Option<u32>,
+    ) -> MetaResult<()> {
+        let parallel_units = self.cluster_manager.list_active_parallel_units().await;
+        let all_table_fragments = self.fragment_manager.list_table_fragments().await?;
+        let table_fragments = all_table_fragments
+            .iter()
+            .find(|table_fragments| table_fragments.table_id() == table_id)
+            .ok_or_else(|| anyhow!("table_fragment not exist: id={}", table_id))?;
+        if table_fragments.state() != table_fragments::State::Created {
+            bail!("the streaming job {} is still creating", table_id);
+        }
+
+        let skipped_fragment_ids = unschedulable_fragment_ids(&all_table_fragments);
+        let (fragments, skipped_fragments): (Vec<_>, Vec<_>) = table_fragments
+            .fragments
+            .values()
+            .partition(|fragment| !skipped_fragment_ids.contains(&fragment.fragment_id));
+        let unschedulable_plan = generate_balanced_reschedule(
+            skipped_fragments
+                .into_iter()
+                .map(|fragment| (fragment, parallelism)),
+            &table_fragments.actor_status,
+            &parallel_units,
+        );
+        if !unschedulable_plan.is_empty() {
+            bail!(
+                "fragments {:?} of the streaming job {} can't be rescheduled, e.g. chain fragments, \
+                 fragments connected by NoShuffle or materialize fragments with downstream",
+                unschedulable_plan.keys().sorted().collect_vec(),
+                table_id
+            );
+        }
+
+        let plan = generate_balanced_reschedule(
+            fragments
+                .into_iter()
+                .map(|fragment| (fragment, parallelism)),
+            &table_fragments.actor_status,
+            &parallel_units,
+        );
+
+        // Persist the parallelism first, so that the reschedule plan is validated against it.
+        let old_parallelism = table_fragments.parallelism();
+        self.fragment_manager
+            .set_table_fragments_parallelism(table_id, parallelism)
+            .await?;
+        if !plan.is_empty() {
+            tracing::info!("Alter parallelism plan of table {}: {:#?}", table_id, plan);
+            if let Err(e) = self.reschedule_actors(plan).await {
+                self.fragment_manager
+                    .set_table_fragments_parallelism(table_id, old_parallelism)
+                    .await?;
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Build the context for rescheduling and do some validation for the request.
     async fn build_reschedule_context(
         &self,
@@ -337,12 +677,18 @@ where
         let mut fragment_map = HashMap::new();
         let mut actor_status = BTreeMap::new();
         let mut fragment_state = HashMap::new();
+        let mut fragment_parallelism = HashMap::new();
         for table_fragments in self.fragment_manager.list_table_fragments().await? {
             fragment_state.extend(
                 table_fragments
                     .fragment_ids()
                     .map(|f| (f, table_fragments.state())),
             );
+            fragment_parallelism.extend(
+                table_fragments
+                    .fragment_ids()
+                    .map(|f| (f, table_fragments.parallelism())),
+            );
             fragment_map.extend(table_fragments.fragments.clone());
             actor_map.extend(table_fragments.actor_map());
             chain_fragment_ids.extend(table_fragments.chain_fragment_ids());
@@ -458,6 +804,29 @@ where
                     );
                 }
             }
+
+            // Check if the reschedule plan keeps the fixed parallelism of the streaming job, which
+            // can only be changed by `alter_parallelism`.
+            if let Some(parallelism) = fragment_parallelism[fragment_id] {
+                if fragment.distribution_type() == FragmentDistributionType::Hash {
+                    let new_parallelism = current_parallel_units
+                        .iter()
+                        .filter(|id| !removed_parallel_units.contains(*id))
+                        .chain(added_parallel_units)
+                        .unique()
+                        .count();
+                    let expected = min(parallelism as usize, parallel_unit_id_to_worker_id.len());
+                    if new_parallelism != expected {
+                        bail!(
+                            "fragment {} has a fixed parallelism of {}, but the reschedule plan \
+                             results in {}",
+                            fragment_id,
+                            expected,
+                            new_parallelism
+                        );
+                    }
+                }
+            }
         }
 
         Ok(RescheduleContext {
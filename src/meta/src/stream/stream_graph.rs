@@ -638,6 +638,12 @@ impl StreamGraphBuilder {
                             update_table(table, "DynamicFilterRight");
                         }
                     }
+
+                    NodeBody::Sink(node) => {
+                        if let Some(table) = &mut node.table {
+                            update_table(table, "SinkInternalTable");
+                        }
+                    }
                     _ => {}
                 }
 
@@ -1113,6 +1119,9 @@ impl ActorGraphBuilder {
             NodeBody::TopN(node) => {
                 vec![node.table.as_ref().unwrap().id]
             }
+            NodeBody::Sink(node) => {
+                vec![node.table.as_ref().unwrap().id]
+            }
             _ => {
                 vec![]
             }
@@ -24,7 +24,7 @@ use piestream_pb::catalog::Table;
 use piestream_pb::common::{ActorInfo, Buffer, WorkerType};
 use piestream_pb::meta::table_fragments::actor_status::ActorState;
 use piestream_pb::meta::table_fragments::fragment::FragmentDistributionType;
-use piestream_pb::meta::table_fragments::ActorStatus;
+use piestream_pb::meta::table_fragments::{ActorStatus, State};
 use piestream_pb::stream_plan::stream_node::NodeBody;
 use piestream_pb::stream_plan::{ActorMapping, Dispatcher, DispatcherType, StreamNode};
 use piestream_pb::stream_service::{
@@ -741,6 +741,24 @@ where
 
         Ok(())
     }
+
+    /// Cancel the creation of a materialized view whose backfill is still in progress. The actors
+    /// are stopped by a [`Command::DropMaterializedView`], after which the barrier manager aborts
+    /// the pending [`Command::CreateMaterializedView`], and the creating procedure will clean up
+    /// the rest.
+    pub async fn cancel_create_materialized_view(&self, table_id: &TableId) -> MetaResult<()> {
+        let table_fragments = self
+            .fragment_manager
+            .select_table_fragments_by_table_id(table_id)
+            .await?;
+        if table_fragments.state() != State::Creating {
+            bail!("table {} is not in creating", table_id);
+        }
+
+        self.barrier_scheduler
+            .run_command(Command::DropMaterializedView(*table_id))
+            .await
+    }
 }
 
 #[cfg(test)]
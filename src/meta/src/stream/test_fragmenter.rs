@@ -28,8 +28,8 @@ use piestream_pb::stream_plan::stream_fragment_graph::{StreamFragment, StreamFra
 use piestream_pb::stream_plan::stream_node::NodeBody;
 use piestream_pb::stream_plan::{
     agg_call_state, AggCallState, DispatchStrategy, DispatcherType, ExchangeNode, FilterNode,
-    FragmentType, MaterializeNode, ProjectNode, SimpleAggNode, SourceNode, StreamFragmentGraph,
-    StreamNode,
+    FragmentType, HandleConflictBehavior, MaterializeNode, ProjectNode, SimpleAggNode, SourceNode,
+    StreamFragmentGraph, StreamNode,
 };
 
 use crate::manager::MetaSrvEnv;
@@ -324,6 +324,7 @@ fn make_stream_fragments() -> Vec<StreamFragment> {
             table_id: 1,
             table: Some(make_internal_table(4, true)),
             column_orders: vec![make_column_order(1), make_column_order(2)],
+            handle_pk_conflict_behavior: HandleConflictBehavior::NoCheck as i32,
         })),
         fields: vec![], // TODO: fill this later
         operator_id: 7,
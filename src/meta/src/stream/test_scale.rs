@@ -14,7 +14,7 @@
 
 #[cfg(test)]
 mod tests {
-    use std::collections::{BTreeSet, HashMap};
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
 
     use itertools::Itertools;
     use maplit::btreeset;
@@ -22,13 +22,15 @@ mod tests {
     use piestream_common::types::{ParallelUnitId, VIRTUAL_NODE_COUNT};
     use piestream_common::util::compress::decompress_data;
     use piestream_pb::common::ParallelUnit;
+    use piestream_pb::meta::table_fragments::fragment::FragmentDistributionType;
+    use piestream_pb::meta::table_fragments::{ActorStatus, Fragment};
     use piestream_pb::stream_plan::{ActorMapping, StreamActor};
 
     use crate::model::ActorId;
     use crate::stream::mapping::{
         actor_mapping_from_bitmaps, build_vnode_mapping, vnode_mapping_to_bitmaps,
     };
-    use crate::stream::scale::rebalance_actor_vnode;
+    use crate::stream::scale::{generate_balanced_reschedule, rebalance_actor_vnode};
     use crate::stream::{
         actor_mapping_to_parallel_unit_mapping, parallel_unit_mapping_to_actor_mapping,
     };
@@ -385,4 +387,183 @@ mod tests {
 
         check_bitmaps(&result);
     }
+
+    fn build_fake_fragment(
+        fragment_id: u32,
+        distribution_type: FragmentDistributionType,
+        info: &[(ActorId, ParallelUnitId)],
+        actor_status: &mut BTreeMap<ActorId, ActorStatus>,
+    ) -> Fragment {
+        for (actor_id, parallel_unit_id) in info {
+            actor_status.insert(
+                *actor_id,
+                ActorStatus {
+                    parallel_unit: Some(ParallelUnit {
+                        id: *parallel_unit_id,
+                        worker_node_id: *parallel_unit_id / 2,
+                    }),
+                    ..Default::default()
+                },
+            );
+        }
+        Fragment {
+            fragment_id,
+            distribution_type: distribution_type as i32,
+            actors: info
+                .iter()
+                .map(|(actor_id, _)| StreamActor {
+                    actor_id: *actor_id,
+                    fragment_id,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn fake_parallel_units(ids: impl IntoIterator<Item = ParallelUnitId>) -> Vec<ParallelUnit> {
+        ids.into_iter()
+            .map(|id| ParallelUnit {
+                id,
+                worker_node_id: id / 2,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_balanced_reschedule_on_node_join() {
+        let mut actor_status = BTreeMap::new();
+        let hash = build_fake_fragment(
+            1,
+            FragmentDistributionType::Hash,
+            &[(1, 0), (2, 1), (3, 2), (4, 3)],
+            &mut actor_status,
+        );
+        let single = build_fake_fragment(
+            2,
+            FragmentDistributionType::Single,
+            &[(5, 0)],
+            &mut actor_status,
+        );
+
+        // no change if all parallel units are in use
+        let plan = generate_balanced_reschedule(
+            [(&hash, None), (&single, None)],
+            &actor_status,
+            &fake_parallel_units(0..4),
+        );
+        assert!(plan.is_empty());
+
+        // a new compute node with parallel units 4 and 5 joins
+        let plan = generate_balanced_reschedule(
+            [(&hash, None), (&single, None)],
+            &actor_status,
+            &fake_parallel_units(0..6),
+        );
+        assert_eq!(plan.len(), 1);
+        let reschedule = plan.get(&1).unwrap();
+        assert_eq!(reschedule.added_parallel_units, vec![4, 5]);
+        assert!(reschedule.removed_parallel_units.is_empty());
+    }
+
+    #[test]
+    fn test_balanced_reschedule_on_node_leave() {
+        let mut actor_status = BTreeMap::new();
+        let hash = build_fake_fragment(
+            1,
+            FragmentDistributionType::Hash,
+            &[(1, 0), (2, 1), (3, 2), (4, 3), (5, 4), (6, 5)],
+            &mut actor_status,
+        );
+        let singles = (0..3)
+            .map(|i| {
+                build_fake_fragment(
+                    2 + i,
+                    FragmentDistributionType::Single,
+                    &[(10 + i, 4 + i % 2)],
+                    &mut actor_status,
+                )
+            })
+            .collect_vec();
+
+        // the compute node with parallel units 4 and 5 leaves
+        let plan = generate_balanced_reschedule(
+            std::iter::once(&hash)
+                .chain(singles.iter())
+                .map(|fragment| (fragment, None)),
+            &actor_status,
+            &fake_parallel_units(0..4),
+        );
+        assert_eq!(plan.len(), 4);
+        let reschedule = plan.get(&1).unwrap();
+        assert!(reschedule.added_parallel_units.is_empty());
+        assert_eq!(reschedule.removed_parallel_units, vec![4, 5]);
+
+        // singleton fragments are spread over the remaining parallel units
+        let targets: BTreeSet<_> = (2..5)
+            .map(|fragment_id| {
+                let reschedule = plan.get(&fragment_id).unwrap();
+                assert_eq!(reschedule.added_parallel_units.len(), 1);
+                reschedule.added_parallel_units[0]
+            })
+            .collect();
+        assert_eq!(targets.len(), 3);
+    }
+
+    #[test]
+    fn test_balanced_reschedule_with_fixed_parallelism() {
+        let mut actor_status = BTreeMap::new();
+        // parallel units 0 and 1 are on worker 0, 2 and 3 on worker 1, etc.
+        let hash = build_fake_fragment(
+            1,
+            FragmentDistributionType::Hash,
+            &[(1, 0), (2, 1), (3, 2), (4, 3)],
+            &mut actor_status,
+        );
+        let single = build_fake_fragment(
+            2,
+            FragmentDistributionType::Single,
+            &[(5, 0)],
+            &mut actor_status,
+        );
+
+        // scale in: one parallel unit is kept on each worker, singleton is untouched
+        let plan = generate_balanced_reschedule(
+            [(&hash, Some(2)), (&single, Some(2))],
+            &actor_status,
+            &fake_parallel_units(0..4),
+        );
+        assert_eq!(plan.len(), 1);
+        let reschedule = plan.get(&1).unwrap();
+        assert!(reschedule.added_parallel_units.is_empty());
+        assert_eq!(reschedule.removed_parallel_units, vec![1, 3]);
+
+        // a new compute node joins, but the fixed parallelism is already satisfied
+        let plan = generate_balanced_reschedule(
+            [(&hash, Some(4)), (&single, Some(4))],
+            &actor_status,
+            &fake_parallel_units(0..6),
+        );
+        assert!(plan.is_empty());
+
+        // scale out: the new parallel units are picked from the new compute node first
+        let plan = generate_balanced_reschedule(
+            [(&hash, Some(6)), (&single, Some(6))],
+            &actor_status,
+            &fake_parallel_units(0..8),
+        );
+        assert_eq!(plan.len(), 1);
+        let reschedule = plan.get(&1).unwrap();
+        assert_eq!(reschedule.added_parallel_units, vec![4, 6]);
+        assert!(reschedule.removed_parallel_units.is_empty());
+
+        // parallelism larger than the cluster falls back to all the parallel units
+        let plan = generate_balanced_reschedule(
+            [(&hash, Some(100))],
+            &actor_status,
+            &fake_parallel_units(0..6),
+        );
+        let reschedule = plan.get(&1).unwrap();
+        assert_eq!(reschedule.added_parallel_units, vec![4, 5]);
+    }
 }
@@ -0,0 +1,810 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use fail::fail_point;
+use futures::future::try_join_all;
+use hmac::{Hmac, Mac};
+use hyper::header::{HeaderName, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, LAST_MODIFIED};
+use hyper::{Body, Method, Request, StatusCode, Uri};
+use itertools::Itertools;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use sha2::Sha256;
+use tokio::io::AsyncRead;
+use tokio::task::JoinHandle;
+
+use super::http::{check_read_size, concat_chunks, range_header, HttpClient};
+use super::object_metrics::ObjectStoreMetrics;
+use super::{
+    BlockLocation, BoxedStreamingUploader, Bytes, ObjectError, ObjectMetadata, ObjectResult,
+    ObjectStore, S3ObjectStore, StreamingUploader,
+};
+use crate::object::try_update_failure_metric;
+
+const AZBLOB_ACCOUNT_NAME_ENV: &str = "AZBLOB_ACCOUNT_NAME";
+/// The base64 encoded shared key of the storage account.
+const AZBLOB_ACCOUNT_KEY_ENV: &str = "AZBLOB_ACCOUNT_KEY";
+/// Overrides the endpoint of the storage account, e.g. to point to an emulator.
+const AZBLOB_ENDPOINT_ENV: &str = "AZBLOB_ENDPOINT";
+const AZBLOB_API_VERSION: &str = "2020-10-02";
+
+/// The number of bytes that is buffered before they are uploaded as a block.
+const AZBLOB_PART_SIZE: usize = 16 * 1024 * 1024;
+/// The number of objects deleted concurrently in `delete_objects`.
+const AZBLOB_DELETE_CONCURRENCY: usize = 100;
+
+type BlockId = String;
+
+/// Signs and sends requests to the blob service of a storage account, authorized with its shared
+/// key.
+///
+/// Reference: <https://learn.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key>
+struct AzblobClient {
+    http: HttpClient,
+    endpoint: String,
+    account: String,
+    key: Vec<u8>,
+    container: String,
+}
+
+impl AzblobClient {
+    /// Builds a signed request on the container, or on `blob` in the container if given. The
+    /// values of `query` are not url encoded.
+    fn build_request(
+        &self,
+        method: Method,
+        blob: Option<&str>,
+        query: &[(&str, String)],
+        headers: &[(&str, String)],
+        body: Bytes,
+    ) -> ObjectResult<Request<Body>> {
+        let mut uri = format!("{}/{}", self.endpoint, self.container);
+        if let Some(blob) = blob {
+            uri.push('/');
+            uri.push_str(&blob.split('/').map(urlencoding::encode).join("/"));
+        }
+        if !query.is_empty() {
+            uri.push('?');
+            uri.push_str(
+                &query
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, urlencoding::encode(value)))
+                    .join("&"),
+            );
+        }
+        let uri: Uri = uri.parse().map_err(ObjectError::http)?;
+
+        let mut headers = headers
+            .iter()
+            .map(|(name, value)| (name.to_lowercase(), value.clone()))
+            .collect_vec();
+        headers.push((
+            "x-ms-date".to_string(),
+            httpdate::fmt_http_date(SystemTime::now()),
+        ));
+        headers.push(("x-ms-version".to_string(), AZBLOB_API_VERSION.to_string()));
+        let authorization = self.sign(&method, uri.path(), query, &headers, body.len());
+
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(AUTHORIZATION, authorization);
+        for (name, value) in headers {
+            builder = builder.header(name, value);
+        }
+        builder.body(Body::from(body)).map_err(ObjectError::http)
+    }
+
+    fn sign(
+        &self,
+        method: &Method,
+        path: &str,
+        query: &[(&str, String)],
+        headers: &[(String, String)],
+        content_length: usize,
+    ) -> String {
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(header_name, _)| header_name == name)
+                .map(|(_, value)| value.as_str())
+                .unwrap_or_default()
+        };
+        let content_length = if content_length == 0 {
+            String::new()
+        } else {
+            content_length.to_string()
+        };
+        let mut string_to_sign = [
+            method.as_str(),
+            "", // Content-Encoding
+            "", // Content-Language
+            content_length.as_str(),
+            "", // Content-MD5
+            header("content-type"),
+            "", // Date, `x-ms-date` is used instead.
+            "", // If-Modified-Since
+            "", // If-Match
+            "", // If-None-Match
+            "", // If-Unmodified-Since
+            "", // Range, `x-ms-range` is used instead.
+        ]
+        .join("\n");
+        string_to_sign.push('\n');
+        for (name, value) in headers
+            .iter()
+            .filter(|(name, _)| name.starts_with("x-ms-"))
+            .sorted()
+        {
+            string_to_sign.push_str(&format!("{}:{}\n", name, value));
+        }
+        string_to_sign.push_str(&format!("/{}{}", self.account, path));
+        for (name, value) in query.iter().sorted() {
+            string_to_sign.push_str(&format!("\n{}:{}", name.to_lowercase(), value));
+        }
+
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC can take key of any size");
+        mac.update(string_to_sign.as_bytes());
+        let signature = base64::encode(mac.finalize().into_bytes());
+        format!("SharedKey {}:{}", self.account, signature)
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        blob: Option<&str>,
+        query: &[(&str, String)],
+        headers: &[(&str, String)],
+        body: Bytes,
+        allowed_status: &[StatusCode],
+    ) -> ObjectResult<hyper::Response<Body>> {
+        self.http
+            .send(
+                || self.build_request(method.clone(), blob, query, headers, body.clone()),
+                allowed_status,
+            )
+            .await
+    }
+
+    async fn put_blob(&self, blob: &str, body: Bytes) -> ObjectResult<()> {
+        let headers = [
+            ("x-ms-blob-type", "BlockBlob".to_string()),
+            (
+                CONTENT_TYPE.as_str(),
+                "application/octet-stream".to_string(),
+            ),
+        ];
+        self.send(Method::PUT, Some(blob), &[], &headers, body, &[])
+            .await?;
+        Ok(())
+    }
+}
+
+/// Generates the id of the `part_id`-th block. Ids of all blocks of a blob must have the same
+/// length.
+fn block_id(part_id: usize) -> BlockId {
+    base64::encode(format!("{:010}", part_id))
+}
+
+/// Azure block blob upload handle. Blocks are staged concurrently and committed with a block list
+/// when the upload finishes. Staged blocks that are never committed are garbage collected by the
+/// service after a week.
+///
+/// Reference: <https://learn.microsoft.com/en-us/rest/api/storageservices/understanding-block-blobs--append-blobs--and-page-blobs>
+pub struct AzblobStreamingUploader {
+    client: Arc<AzblobClient>,
+    part_size: usize,
+    /// The key of the object.
+    key: String,
+    /// Ids of the staged blocks, in order.
+    block_ids: Vec<BlockId>,
+    /// Join handles for block uploads.
+    join_handles: Vec<JoinHandle<ObjectResult<()>>>,
+    /// Buffer for data. It will store at least `part_size` bytes of data before uploading them
+    /// as a block.
+    buf: Vec<Bytes>,
+    /// Length of the data that have not been uploaded.
+    not_uploaded_len: usize,
+    /// To record metrics for uploading blocks.
+    metrics: Arc<ObjectStoreMetrics>,
+}
+
+impl AzblobStreamingUploader {
+    fn upload_next_block(&mut self) {
+        let operation_type = "azblob_upload_part";
+        let data = concat_chunks(std::mem::take(&mut self.buf), self.not_uploaded_len);
+        self.not_uploaded_len = 0;
+        let block_id = block_id(self.block_ids.len());
+        self.block_ids.push(block_id.clone());
+
+        let client = self.client.clone();
+        let key = self.key.clone();
+        let metrics = self.metrics.clone();
+        metrics
+            .operation_size
+            .with_label_values(&[operation_type])
+            .observe(data.len() as f64);
+        self.join_handles.push(tokio::spawn(async move {
+            let _timer = metrics
+                .operation_latency
+                .with_label_values(&["azblob", operation_type])
+                .start_timer();
+            let query = [("comp", "block".to_string()), ("blockid", block_id)];
+            let res = client
+                .send(Method::PUT, Some(&key), &query, &[], data, &[])
+                .await
+                .map(|_| ());
+            try_update_failure_metric(&metrics, &res, operation_type);
+            res
+        }));
+    }
+
+    async fn commit_block_list(&mut self) -> ObjectResult<()> {
+        if !self.buf.is_empty() {
+            self.upload_next_block();
+        }
+        for result in try_join_all(self.join_handles.drain(..))
+            .await
+            .map_err(ObjectError::internal)?
+        {
+            result?;
+        }
+
+        let mut block_list = String::from(r#"<?xml version="1.0" encoding="utf-8"?><BlockList>"#);
+        for block_id in &self.block_ids {
+            block_list.push_str(&format!("<Latest>{}</Latest>", block_id));
+        }
+        block_list.push_str("</BlockList>");
+        let query = [("comp", "blocklist".to_string())];
+        let headers = [(CONTENT_TYPE.as_str(), "application/xml".to_string())];
+        self.client
+            .send(
+                Method::PUT,
+                Some(&self.key),
+                &query,
+                &headers,
+                block_list.into(),
+                &[],
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamingUploader for AzblobStreamingUploader {
+    async fn write_bytes(&mut self, data: Bytes) -> ObjectResult<()> {
+        fail_point!("azblob_write_bytes_err", |_| Err(ObjectError::internal(
+            "azblob write bytes error"
+        )));
+        self.not_uploaded_len += data.len();
+        self.buf.push(data);
+
+        if self.not_uploaded_len >= self.part_size {
+            self.upload_next_block();
+        }
+        Ok(())
+    }
+
+    /// If no block has been staged, we can use `PutBlob` instead to save the `PutBlockList`
+    /// request. Otherwise stage the remaining data of the buffer as a new block and commit all
+    /// blocks.
+    async fn finish(mut self: Box<Self>) -> ObjectResult<()> {
+        fail_point!("azblob_finish_streaming_upload_err", |_| Err(
+            ObjectError::internal("azblob finish streaming upload error")
+        ));
+        if self.block_ids.is_empty() {
+            if self.buf.is_empty() {
+                return Err(ObjectError::internal("upload empty object"));
+            }
+            let data = concat_chunks(std::mem::take(&mut self.buf), self.not_uploaded_len);
+            self.client.put_blob(&self.key, data).await
+        } else if let Err(e) = self.commit_block_list().await {
+            tracing::warn!("Failed to upload object {}: {:?}", self.key, e);
+            Err(e)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn get_memory_usage(&self) -> u64 {
+        self.part_size as u64
+    }
+}
+
+/// Object store with Azure Blob backend, storing objects as block blobs.
+/// The full path to a file on Azure Blob would be `azblob://container/<data_directory>/prefix/file`
+pub struct AzblobObjectStore {
+    client: Arc<AzblobClient>,
+    part_size: usize,
+    /// For Azure Blob specific metrics.
+    metrics: Arc<ObjectStoreMetrics>,
+}
+
+#[async_trait::async_trait]
+impl ObjectStore for AzblobObjectStore {
+    fn get_object_prefix(&self, obj_id: u64) -> String {
+        // Blobs are partitioned by name, so spread sequential ids across partitions as for S3.
+        S3ObjectStore::get_object_prefix(obj_id)
+    }
+
+    async fn upload(&self, path: &str, obj: Bytes) -> ObjectResult<()> {
+        fail_point!("azblob_upload_err", |_| Err(ObjectError::internal(
+            "azblob upload error"
+        )));
+        if obj.is_empty() {
+            Err(ObjectError::internal("upload empty object"))
+        } else {
+            self.client.put_blob(path, obj).await
+        }
+    }
+
+    fn streaming_upload(&self, path: &str) -> ObjectResult<BoxedStreamingUploader> {
+        fail_point!("azblob_streaming_upload_err", |_| Err(
+            ObjectError::internal("azblob streaming upload error")
+        ));
+        Ok(Box::new(AzblobStreamingUploader {
+            client: self.client.clone(),
+            part_size: self.part_size,
+            key: path.to_string(),
+            block_ids: vec![],
+            join_handles: vec![],
+            buf: vec![],
+            not_uploaded_len: 0,
+            metrics: self.metrics.clone(),
+        }))
+    }
+
+    async fn read(&self, path: &str, block_loc: Option<BlockLocation>) -> ObjectResult<Bytes> {
+        fail_point!("azblob_read_err", |_| Err(ObjectError::internal(
+            "azblob read error"
+        )));
+        let val = self
+            .read_range(path, range_header(block_loc.as_ref(), None))
+            .await?;
+        if let Some(block_loc) = block_loc.as_ref() {
+            check_read_size(path, block_loc, &val)?;
+        }
+        Ok(val)
+    }
+
+    async fn readv(&self, path: &str, block_locs: &[BlockLocation]) -> ObjectResult<Vec<Bytes>> {
+        let futures = block_locs
+            .iter()
+            .map(|block_loc| self.read(path, Some(*block_loc)))
+            .collect_vec();
+        try_join_all(futures).await
+    }
+
+    /// The whole requested range is loaded into memory before returning.
+    async fn streaming_read(
+        &self,
+        path: &str,
+        start_pos: Option<usize>,
+    ) -> ObjectResult<Box<dyn AsyncRead + Unpin + Send + Sync>> {
+        fail_point!("azblob_streaming_read_err", |_| Err(ObjectError::internal(
+            "azblob streaming read error"
+        )));
+        let val = self.read_range(path, range_header(None, start_pos)).await?;
+        Ok(Box::new(Cursor::new(val)))
+    }
+
+    async fn metadata(&self, path: &str) -> ObjectResult<ObjectMetadata> {
+        fail_point!("azblob_metadata_err", |_| Err(ObjectError::internal(
+            "azblob metadata error"
+        )));
+        let resp = self
+            .client
+            .send(Method::HEAD, Some(path), &[], &[], Bytes::new(), &[])
+            .await?;
+        let header = |name: HeaderName| {
+            resp.headers()
+                .get(&name)
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| ObjectError::internal(format!("{} required", name)))
+        };
+        Ok(ObjectMetadata {
+            key: path.to_owned(),
+            last_modified: parse_last_modified(header(LAST_MODIFIED)?)?,
+            total_size: header(CONTENT_LENGTH)?
+                .parse::<usize>()
+                .map_err(ObjectError::internal)?,
+        })
+    }
+
+    /// Permanently deletes the whole object.
+    /// Deleting an object that does not exist is considered successful.
+    async fn delete(&self, path: &str) -> ObjectResult<()> {
+        fail_point!("azblob_delete_err", |_| Err(ObjectError::internal(
+            "azblob delete error"
+        )));
+        self.client
+            .send(
+                Method::DELETE,
+                Some(path),
+                &[],
+                &[],
+                Bytes::new(),
+                &[StatusCode::NOT_FOUND],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes the objects with the given paths permanently from the storage. If an object
+    /// specified in the request is not found, it will be considered as successfully deleted.
+    ///
+    /// The objects are deleted with concurrent requests instead of a blob batch, which would
+    /// need `multipart/mixed` bodies.
+    async fn delete_objects(&self, paths: &[String]) -> ObjectResult<()> {
+        for chunk in paths.chunks(AZBLOB_DELETE_CONCURRENCY) {
+            try_join_all(chunk.iter().map(|path| self.delete(path))).await?;
+        }
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> ObjectResult<Vec<ObjectMetadata>> {
+        let mut ret = vec![];
+        let mut next_marker: Option<String> = None;
+        // A page contains up to 5000 blobs. Use the `NextMarker` given by last response to fetch
+        // more parts of the result, until the marker is empty.
+        loop {
+            let mut query = vec![
+                ("restype", "container".to_string()),
+                ("comp", "list".to_string()),
+                ("prefix", prefix.to_string()),
+            ];
+            if let Some(marker) = next_marker.take() {
+                query.push(("marker", marker));
+            }
+            let resp = self
+                .client
+                .send(Method::GET, None, &query, &[], Bytes::new(), &[])
+                .await?;
+            let body = hyper::body::to_bytes(resp.into_body()).await?;
+            next_marker = parse_list_blobs(&body, &mut ret)?;
+            if next_marker.is_none() {
+                break;
+            }
+        }
+        Ok(ret)
+    }
+
+    fn store_media_type(&self) -> &'static str {
+        "azblob"
+    }
+}
+
+impl AzblobObjectStore {
+    /// Creates an Azure Blob object store from environment variables. The storage account is
+    /// given by `AZBLOB_ACCOUNT_NAME` and `AZBLOB_ACCOUNT_KEY`, and its endpoint can be overridden
+    /// with `AZBLOB_ENDPOINT`.
+    pub async fn new(container: String, metrics: Arc<ObjectStoreMetrics>) -> Self {
+        let account = std::env::var(AZBLOB_ACCOUNT_NAME_ENV)
+            .unwrap_or_else(|_| panic!("{} required", AZBLOB_ACCOUNT_NAME_ENV));
+        let key = std::env::var(AZBLOB_ACCOUNT_KEY_ENV)
+            .unwrap_or_else(|_| panic!("{} required", AZBLOB_ACCOUNT_KEY_ENV));
+        let endpoint = std::env::var(AZBLOB_ENDPOINT_ENV)
+            .unwrap_or_else(|_| format!("https://{}.blob.core.windows.net", account));
+        Self::with_endpoint(endpoint, account, &key, container, metrics)
+    }
+
+    fn with_endpoint(
+        endpoint: String,
+        account: String,
+        key: &str,
+        container: String,
+        metrics: Arc<ObjectStoreMetrics>,
+    ) -> Self {
+        let client = AzblobClient {
+            http: HttpClient::new(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            account,
+            key: base64::decode(key).expect("account key should be base64 encoded"),
+            container,
+        };
+        Self {
+            client: Arc::new(client),
+            part_size: AZBLOB_PART_SIZE,
+            metrics,
+        }
+    }
+
+    async fn read_range(&self, path: &str, range: Option<String>) -> ObjectResult<Bytes> {
+        let headers = range
+            .map(|range| vec![("x-ms-range", range)])
+            .unwrap_or_default();
+        let resp = self
+            .client
+            .send(Method::GET, Some(path), &[], &headers, Bytes::new(), &[])
+            .await?;
+        Ok(hyper::body::to_bytes(resp.into_body()).await?)
+    }
+}
+
+fn parse_last_modified(last_modified: &str) -> ObjectResult<f64> {
+    let time = httpdate::parse_http_date(last_modified).map_err(ObjectError::internal)?;
+    Ok(time
+        .duration_since(UNIX_EPOCH)
+        .map_err(ObjectError::internal)?
+        .as_secs_f64())
+}
+
+/// Appends the blobs in the response of `ListBlobs` to `blobs`, and returns the marker of the next
+/// page if there is one.
+fn parse_list_blobs(body: &[u8], blobs: &mut Vec<ObjectMetadata>) -> ObjectResult<Option<String>> {
+    let mut reader = Reader::from_reader(body);
+    reader.trim_text(true);
+    let mut buf = vec![];
+    let mut elements: Vec<Vec<u8>> = vec![];
+    let mut blob: Option<(String, f64, usize)> = None;
+    let mut next_marker = None;
+    loop {
+        match reader.read_event(&mut buf).map_err(ObjectError::http)? {
+            Event::Start(e) => {
+                if e.name() == b"Blob" {
+                    blob = Some(Default::default());
+                }
+                elements.push(e.name().to_vec());
+            }
+            Event::End(e) => {
+                if e.name() == b"Blob" {
+                    let (key, last_modified, total_size) = blob.take().unwrap();
+                    blobs.push(ObjectMetadata {
+                        key,
+                        last_modified,
+                        total_size,
+                    });
+                }
+                elements.pop();
+            }
+            Event::Text(e) => {
+                let text = e.unescape_and_decode(&reader).map_err(ObjectError::http)?;
+                match (elements.last().map(Vec::as_slice), blob.as_mut()) {
+                    (Some(b"Name"), Some(blob)) => blob.0 = text,
+                    (Some(b"Last-Modified"), Some(blob)) => blob.1 = parse_last_modified(&text)?,
+                    (Some(b"Content-Length"), Some(blob)) => {
+                        blob.2 = text.parse().map_err(ObjectError::internal)?
+                    }
+                    (Some(b"NextMarker"), None) if !text.is_empty() => next_marker = Some(text),
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(next_marker)
+}
+
+#[cfg(test)]
+#[cfg(not(madsim))]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::{Arc, Mutex};
+
+    use bytes::Bytes;
+    use hyper::header::{AUTHORIZATION, CONTENT_LENGTH, LAST_MODIFIED};
+    use hyper::{Body, Method, Request, Response, StatusCode};
+    use itertools::Itertools;
+
+    use super::{AzblobObjectStore, BlockId};
+    use crate::object::http::test_utils::{
+        parse_query, ranged_response, start_emulator, status_response,
+    };
+    use crate::object::object_metrics::ObjectStoreMetrics;
+    use crate::object::{BlockLocation, ObjectMetadata, ObjectStore};
+
+    /// The well-known development account of the Azure storage emulator.
+    const ACCOUNT: &str = "devstoreaccount1";
+    const ACCOUNT_KEY: &str =
+        "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
+    const CONTAINER: &str = "test-container";
+    const LAST_MODIFIED_TIME: &str = "Sat, 01 Oct 2022 08:00:00 GMT";
+    /// Blobs per page of a list response, small enough to test pagination.
+    const PAGE_SIZE: usize = 2;
+
+    /// Emulates the subset of the Blob service REST API used by `AzblobObjectStore`.
+    #[derive(Default)]
+    struct AzblobEmulator {
+        blobs: BTreeMap<String, Bytes>,
+        /// Staged blocks that are not committed yet.
+        blocks: HashMap<(String, BlockId), Bytes>,
+    }
+
+    async fn handle(emulator: Arc<Mutex<AzblobEmulator>>, req: Request<Body>) -> Response<Body> {
+        let header = |name: &str| {
+            req.headers()
+                .get(name)
+                .map(|value| value.to_str().unwrap().to_string())
+        };
+        let authorized = header(AUTHORIZATION.as_str()).map_or(false, |auth| {
+            auth.starts_with(&format!("SharedKey {}:", ACCOUNT))
+        });
+        if !authorized || header("x-ms-date").is_none() || header("x-ms-version").is_none() {
+            return status_response(StatusCode::FORBIDDEN);
+        }
+        let (range, blob_type) = (header("x-ms-range"), header("x-ms-blob-type"));
+        let method = req.method().clone();
+        let path = urlencoding::decode(req.uri().path()).unwrap().into_owned();
+        let query = parse_query(req.uri());
+        let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+
+        let mut emulator = emulator.lock().unwrap();
+        let container_path = format!("/{}/{}", ACCOUNT, CONTAINER);
+        if path == container_path {
+            assert_eq!(query["restype"], "container");
+            assert_eq!(query["comp"], "list");
+            let start: usize = query
+                .get("marker")
+                .map_or(0, |marker| marker.parse().unwrap());
+            let matched = emulator
+                .blobs
+                .iter()
+                .filter(|(name, _)| name.starts_with(&query["prefix"]))
+                .collect_vec();
+            let mut resp = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>");
+            resp.push_str("<EnumerationResults><Blobs>");
+            for (name, data) in matched.iter().skip(start).take(PAGE_SIZE) {
+                resp.push_str(&format!(
+                    "<Blob><Name>{}</Name><Properties><Last-Modified>{}</Last-Modified>\
+                     <Content-Length>{}</Content-Length><BlobType>BlockBlob</BlobType>\
+                     </Properties></Blob>",
+                    name,
+                    LAST_MODIFIED_TIME,
+                    data.len()
+                ));
+            }
+            resp.push_str("</Blobs>");
+            if start + PAGE_SIZE < matched.len() {
+                resp.push_str(&format!("<NextMarker>{}</NextMarker>", start + PAGE_SIZE));
+            } else {
+                resp.push_str("<NextMarker />");
+            }
+            resp.push_str("</EnumerationResults>");
+            return Response::new(Body::from(resp));
+        }
+
+        let name = match path.strip_prefix(&format!("{}/", container_path)) {
+            Some(name) => name.to_string(),
+            None => return status_response(StatusCode::NOT_FOUND),
+        };
+        match (method, query.get("comp").map(String::as_str)) {
+            (Method::PUT, Some("block")) => {
+                let block_id = query["blockid"].clone();
+                emulator.blocks.insert((name, block_id), body);
+                status_response(StatusCode::CREATED)
+            }
+            (Method::PUT, Some("blocklist")) => {
+                let block_list = String::from_utf8(body.to_vec()).unwrap();
+                let mut data = vec![];
+                for block in block_list.split("<Latest>").skip(1) {
+                    let block_id = block.split_once("</Latest>").unwrap().0.to_string();
+                    let block = emulator.blocks.remove(&(name.clone(), block_id)).unwrap();
+                    data.extend_from_slice(&block);
+                }
+                emulator.blobs.insert(name, data.into());
+                status_response(StatusCode::CREATED)
+            }
+            (Method::PUT, None) => {
+                assert_eq!(blob_type.as_deref(), Some("BlockBlob"));
+                emulator.blobs.insert(name, body);
+                status_response(StatusCode::CREATED)
+            }
+            (method, None) => {
+                let data = match emulator.blobs.get(&name) {
+                    Some(data) => data.clone(),
+                    None => return status_response(StatusCode::NOT_FOUND),
+                };
+                match method {
+                    Method::GET => ranged_response(&data, range.as_deref()),
+                    Method::HEAD => Response::builder()
+                        .header(CONTENT_LENGTH, data.len())
+                        .header(LAST_MODIFIED, LAST_MODIFIED_TIME)
+                        .body(Body::empty())
+                        .unwrap(),
+                    Method::DELETE => {
+                        emulator.blobs.remove(&name);
+                        status_response(StatusCode::ACCEPTED)
+                    }
+                    _ => status_response(StatusCode::METHOD_NOT_ALLOWED),
+                }
+            }
+            _ => status_response(StatusCode::BAD_REQUEST),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_azblob_object_store() {
+        let emulator = Arc::new(Mutex::new(AzblobEmulator::default()));
+        let addr = {
+            let emulator = emulator.clone();
+            start_emulator(move |req| handle(emulator.clone(), req))
+        };
+        let mut store = AzblobObjectStore::with_endpoint(
+            format!("http://{}/{}", addr, ACCOUNT),
+            ACCOUNT.to_string(),
+            ACCOUNT_KEY,
+            CONTAINER.to_string(),
+            Arc::new(ObjectStoreMetrics::unused()),
+        );
+        store.part_size = 64 * 1024;
+
+        // Upload in a single request and read it back.
+        store
+            .upload("a/small", Bytes::from("hello world"))
+            .await
+            .unwrap();
+        assert_eq!(store.read("a/small", None).await.unwrap(), "hello world");
+        let blocks = store
+            .readv(
+                "a/small",
+                &[
+                    BlockLocation { offset: 0, size: 5 },
+                    BlockLocation { offset: 6, size: 5 },
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(blocks, vec![Bytes::from("hello"), Bytes::from("world")]);
+
+        // Streaming upload of several blocks, committed with a block list.
+        let data = (0..200 * 1024).map(|i| i as u8).collect_vec();
+        let mut uploader = store.streaming_upload("a/large").unwrap();
+        for piece in data.chunks(30 * 1024) {
+            uploader
+                .write_bytes(Bytes::copy_from_slice(piece))
+                .await
+                .unwrap();
+        }
+        uploader.finish().await.unwrap();
+        assert_eq!(store.read("a/large", None).await.unwrap(), data);
+        assert!(emulator.lock().unwrap().blocks.is_empty());
+
+        // Streaming upload that fits in one request.
+        let mut uploader = store.streaming_upload("b/tiny").unwrap();
+        uploader.write_bytes(Bytes::from("tiny")).await.unwrap();
+        uploader.finish().await.unwrap();
+
+        let metadata = store.metadata("a/large").await.unwrap();
+        assert_eq!(metadata.total_size, data.len());
+        assert_eq!(metadata.last_modified, 1664611200.0);
+        let keys =
+            |objects: Vec<ObjectMetadata>| objects.into_iter().map(|obj| obj.key).collect_vec();
+        assert_eq!(
+            keys(store.list("a/").await.unwrap()),
+            vec!["a/large", "a/small"]
+        );
+        let all = store.list("").await.unwrap();
+        assert_eq!(keys(all.clone()), vec!["a/large", "a/small", "b/tiny"]);
+        assert_eq!(all[2].total_size, 4);
+        assert_eq!(all[2].last_modified, 1664611200.0);
+
+        // Missing objects are considered deleted.
+        store
+            .delete_objects(&["a/small".to_string(), "a/missing".to_string()])
+            .await
+            .unwrap();
+        store.delete("b/tiny").await.unwrap();
+        store.delete("b/tiny").await.unwrap();
+        assert_eq!(keys(store.list("").await.unwrap()), vec!["a/large"]);
+        assert!(store.read("a/small", None).await.is_err());
+    }
+}
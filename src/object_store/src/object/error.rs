@@ -31,6 +31,12 @@ enum ObjectErrorInner {
         inner: io::Error,
     },
 
+    #[error(transparent)]
+    Http(BoxedError),
+
+    #[error("http request failed with status {status}: {msg}")]
+    HttpStatus { status: u16, msg: String },
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -71,6 +77,14 @@ impl ObjectError {
     pub fn s3(err: impl Into<BoxedError>) -> Self {
         ObjectErrorInner::S3(err.into()).into()
     }
+
+    pub fn http(err: impl Into<BoxedError>) -> Self {
+        ObjectErrorInner::Http(err.into()).into()
+    }
+
+    pub fn http_status(status: u16, msg: String) -> Self {
+        ObjectErrorInner::HttpStatus { status, msg }.into()
+    }
 }
 
 impl<E> From<aws_sdk_s3::types::SdkError<E>> for ObjectError
@@ -88,4 +102,10 @@ impl From<aws_smithy_http::byte_stream::Error> for ObjectError {
     }
 }
 
+impl From<hyper::Error> for ObjectError {
+    fn from(e: hyper::Error) -> Self {
+        ObjectErrorInner::Http(e.into()).into()
+    }
+}
+
 pub type ObjectResult<T> = std::result::Result<T, ObjectError>;
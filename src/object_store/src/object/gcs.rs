@@ -0,0 +1,806 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use fail::fail_point;
+use futures::future::try_join_all;
+use hyper::header::{AUTHORIZATION, CONTENT_RANGE, CONTENT_TYPE, LOCATION, RANGE};
+use hyper::{Body, Method, Request, StatusCode};
+use itertools::Itertools;
+use serde::Deserialize;
+use tokio::io::AsyncRead;
+use tokio::sync::Mutex;
+
+use super::http::{check_read_size, concat_chunks, range_header, HttpClient};
+use super::object_metrics::ObjectStoreMetrics;
+use super::{
+    BlockLocation, BoxedStreamingUploader, Bytes, ObjectError, ObjectMetadata, ObjectResult,
+    ObjectStore, S3ObjectStore, StreamingUploader,
+};
+use crate::object::try_update_failure_metric;
+
+const GCS_DEFAULT_ENDPOINT: &str = "https://storage.googleapis.com";
+/// Overrides the endpoint of GCS, e.g. to point to an emulator.
+const GCS_ENDPOINT_ENV: &str = "GCS_ENDPOINT";
+/// A static OAuth2 access token used to authorize requests.
+const GCS_ACCESS_TOKEN_ENV: &str = "GCS_ACCESS_TOKEN";
+/// Serves access tokens of the service account attached to the GCE instance or GKE pod.
+const GCE_METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+/// Refresh a token fetched from the metadata server this long before it expires.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// Every chunk of a resumable upload except the last one must be a multiple of 256KiB.
+///
+/// Reference: <https://cloud.google.com/storage/docs/performing-resumable-uploads#chunked-upload>
+const GCS_CHUNK_ALIGNMENT: usize = 256 * 1024;
+/// The number of bytes that is buffered before they are uploaded as a chunk.
+const GCS_PART_SIZE: usize = 16 * 1024 * 1024;
+/// The number of objects deleted concurrently in `delete_objects`.
+const GCS_DELETE_CONCURRENCY: usize = 100;
+
+enum GcsCredential {
+    /// No authorization, only accepted by emulators.
+    Anonymous,
+    Static(String),
+    /// Tokens fetched from the metadata server, cached until they are about to expire.
+    Metadata(Mutex<Option<(String, Instant)>>),
+}
+
+#[derive(Deserialize)]
+struct GceToken {
+    access_token: String,
+    expires_in: u64,
+}
+
+impl GcsCredential {
+    async fn authorization(&self, client: &HttpClient) -> ObjectResult<Option<String>> {
+        match self {
+            GcsCredential::Anonymous => Ok(None),
+            GcsCredential::Static(token) => Ok(Some(format!("Bearer {}", token))),
+            GcsCredential::Metadata(cached) => {
+                let mut cached = cached.lock().await;
+                if let Some((token, expire_at)) = cached.as_ref() {
+                    if Instant::now() + TOKEN_REFRESH_MARGIN < *expire_at {
+                        return Ok(Some(format!("Bearer {}", token)));
+                    }
+                }
+                let body = client
+                    .send_and_read(|| {
+                        Request::get(GCE_METADATA_TOKEN_URL)
+                            .header("Metadata-Flavor", "Google")
+                            .body(Body::empty())
+                            .map_err(ObjectError::http)
+                    })
+                    .await?;
+                let token: GceToken = serde_json::from_slice(&body).map_err(ObjectError::http)?;
+                let authorization = format!("Bearer {}", token.access_token);
+                *cached = Some((
+                    token.access_token,
+                    Instant::now() + Duration::from_secs(token.expires_in),
+                ));
+                Ok(Some(authorization))
+            }
+        }
+    }
+}
+
+/// Resource representation of an object in the JSON API.
+#[derive(Deserialize)]
+struct GcsObject {
+    name: String,
+    /// The JSON API encodes 64-bit integers as strings.
+    size: String,
+    /// RFC 3339 timestamp of the last modification.
+    updated: String,
+}
+
+impl GcsObject {
+    fn into_metadata(self) -> ObjectResult<ObjectMetadata> {
+        let last_modified = chrono::DateTime::parse_from_rfc3339(&self.updated)
+            .map_err(ObjectError::internal)?
+            .timestamp_millis() as f64
+            / 1000.0;
+        Ok(ObjectMetadata {
+            total_size: self.size.parse::<usize>().map_err(ObjectError::internal)?,
+            key: self.name,
+            last_modified,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GcsListResponse {
+    #[serde(default)]
+    items: Vec<GcsObject>,
+    next_page_token: Option<String>,
+}
+
+fn build_request(
+    method: Method,
+    uri: &str,
+    authorization: &Option<String>,
+    headers: &[(&str, String)],
+    body: Bytes,
+) -> ObjectResult<Request<Body>> {
+    let mut builder = Request::builder().method(method).uri(uri);
+    if let Some(authorization) = authorization {
+        builder = builder.header(AUTHORIZATION, authorization);
+    }
+    for (name, value) in headers {
+        builder = builder.header(*name, value);
+    }
+    builder.body(Body::from(body)).map_err(ObjectError::http)
+}
+
+/// GCS resumable upload handle. The upload session is not initiated until the first chunk is
+/// available for upload. Unlike S3 multipart uploads, chunks of a session have to be uploaded in
+/// order.
+///
+/// Reference: <https://cloud.google.com/storage/docs/resumable-uploads>
+pub struct GcsStreamingUploader {
+    client: HttpClient,
+    credential: Arc<GcsCredential>,
+    upload_url: String,
+    /// The key of the object.
+    key: String,
+    part_size: usize,
+    /// The URI of the resumable upload session.
+    session_uri: Option<String>,
+    /// Length of the data that have been uploaded to the session.
+    uploaded_len: usize,
+    /// Buffer for data. It will store at least `part_size` bytes of data before uploading
+    /// `part_size` bytes of them as a chunk.
+    buf: Vec<Bytes>,
+    /// Length of the data in `buf`.
+    not_uploaded_len: usize,
+    /// To record metrics for uploading chunks.
+    metrics: Arc<ObjectStoreMetrics>,
+}
+
+impl GcsStreamingUploader {
+    fn new(store: &GcsObjectStore, key: String) -> Self {
+        debug_assert_eq!(store.part_size % GCS_CHUNK_ALIGNMENT, 0);
+        Self {
+            client: store.client.clone(),
+            credential: store.credential.clone(),
+            upload_url: store.upload_url(),
+            key,
+            part_size: store.part_size,
+            session_uri: None,
+            uploaded_len: 0,
+            buf: Default::default(),
+            not_uploaded_len: 0,
+            metrics: store.metrics.clone(),
+        }
+    }
+
+    async fn upload_chunk(&mut self, data: Bytes, is_last: bool) -> ObjectResult<()> {
+        let operation_type = "gcs_upload_part";
+        let authorization = self.credential.authorization(&self.client).await?;
+
+        // Lazily create the upload session.
+        if self.session_uri.is_none() {
+            let uri = format!(
+                "{}?uploadType=resumable&name={}",
+                self.upload_url,
+                urlencoding::encode(&self.key)
+            );
+            let resp = self
+                .client
+                .send(
+                    || build_request(Method::POST, &uri, &authorization, &[], Bytes::new()),
+                    &[],
+                )
+                .await?;
+            let session_uri = resp
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .ok_or_else(|| ObjectError::internal("no session uri in resumable upload"))?;
+            self.session_uri = Some(session_uri.to_string());
+        }
+
+        let start = self.uploaded_len;
+        let end = start + data.len();
+        let total = if is_last {
+            end.to_string()
+        } else {
+            "*".to_string()
+        };
+        let content_range = if data.is_empty() {
+            format!("bytes */{}", total)
+        } else {
+            format!("bytes {}-{}/{}", start, end - 1, total)
+        };
+
+        self.metrics
+            .operation_size
+            .with_label_values(&[operation_type])
+            .observe(data.len() as f64);
+        let _timer = self
+            .metrics
+            .operation_latency
+            .with_label_values(&["gcs", operation_type])
+            .start_timer();
+        let session_uri = self.session_uri.as_ref().unwrap();
+        let headers = [(CONTENT_RANGE.as_str(), content_range)];
+        let res = self
+            .client
+            .send(
+                || {
+                    build_request(
+                        Method::PUT,
+                        session_uri,
+                        &authorization,
+                        &headers,
+                        data.clone(),
+                    )
+                },
+                // Intermediate chunks are acknowledged with `308 Resume Incomplete`.
+                &[StatusCode::PERMANENT_REDIRECT],
+            )
+            .await;
+        try_update_failure_metric(&self.metrics, &res, operation_type);
+        res?;
+        self.uploaded_len = end;
+        Ok(())
+    }
+
+    /// Cancels the upload session so that the uploaded chunks are discarded.
+    async fn cancel_upload(&self) -> ObjectResult<()> {
+        let session_uri = match self.session_uri.as_ref() {
+            Some(session_uri) => session_uri,
+            None => return Ok(()),
+        };
+        let authorization = self.credential.authorization(&self.client).await?;
+        // A successfully cancelled session responds with `499 Client Closed Request`.
+        let cancelled = StatusCode::from_u16(499).unwrap();
+        self.client
+            .send(
+                || {
+                    build_request(
+                        Method::DELETE,
+                        session_uri,
+                        &authorization,
+                        &[],
+                        Bytes::new(),
+                    )
+                },
+                &[cancelled],
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamingUploader for GcsStreamingUploader {
+    async fn write_bytes(&mut self, data: Bytes) -> ObjectResult<()> {
+        fail_point!("gcs_write_bytes_err", |_| Err(ObjectError::internal(
+            "gcs write bytes error"
+        )));
+        self.not_uploaded_len += data.len();
+        self.buf.push(data);
+
+        if self.not_uploaded_len >= self.part_size {
+            let data = concat_chunks(std::mem::take(&mut self.buf), self.not_uploaded_len);
+            let chunk = data.slice(..self.part_size);
+            let rest = data.slice(self.part_size..);
+            self.not_uploaded_len = rest.len();
+            if !rest.is_empty() {
+                self.buf.push(rest);
+            }
+            if let Err(e) = self.upload_chunk(chunk, false).await {
+                // Resuming the session is not supported, so the upload has to start over.
+                if let Err(cancel_err) = self.cancel_upload().await {
+                    tracing::warn!("Failed to cancel upload of {}: {:?}", self.key, cancel_err);
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// If the upload session has not been initiated, we can use a single-request upload instead.
+    /// Otherwise upload the remaining data of the buffer as the last chunk.
+    async fn finish(mut self: Box<Self>) -> ObjectResult<()> {
+        fail_point!("gcs_finish_streaming_upload_err", |_| Err(
+            ObjectError::internal("gcs finish streaming upload error")
+        ));
+        let data = concat_chunks(std::mem::take(&mut self.buf), self.not_uploaded_len);
+        if self.session_uri.is_none() {
+            if data.is_empty() {
+                return Err(ObjectError::internal("upload empty object"));
+            }
+            let authorization = self.credential.authorization(&self.client).await?;
+            let uri = format!(
+                "{}?uploadType=media&name={}",
+                self.upload_url,
+                urlencoding::encode(&self.key)
+            );
+            let headers = [(
+                CONTENT_TYPE.as_str(),
+                "application/octet-stream".to_string(),
+            )];
+            self.client
+                .send(
+                    || build_request(Method::POST, &uri, &authorization, &headers, data.clone()),
+                    &[],
+                )
+                .await?;
+            Ok(())
+        } else if let Err(e) = self.upload_chunk(data, true).await {
+            tracing::warn!("Failed to upload object {}: {:?}", self.key, e);
+            self.cancel_upload().await?;
+            Err(e)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn get_memory_usage(&self) -> u64 {
+        self.part_size as u64
+    }
+}
+
+/// Object store with GCS backend, accessed through the JSON API.
+/// The full path to a file on GCS would be `gcs://bucket/<data_directory>/prefix/file`
+pub struct GcsObjectStore {
+    client: HttpClient,
+    endpoint: String,
+    bucket: String,
+    credential: Arc<GcsCredential>,
+    part_size: usize,
+    /// For GCS specific metrics.
+    metrics: Arc<ObjectStoreMetrics>,
+}
+
+#[async_trait::async_trait]
+impl ObjectStore for GcsObjectStore {
+    fn get_object_prefix(&self, obj_id: u64) -> String {
+        // Like S3, GCS scales better when object names are not sequential.
+        S3ObjectStore::get_object_prefix(obj_id)
+    }
+
+    async fn upload(&self, path: &str, obj: Bytes) -> ObjectResult<()> {
+        fail_point!("gcs_upload_err", |_| Err(ObjectError::internal(
+            "gcs upload error"
+        )));
+        if obj.is_empty() {
+            return Err(ObjectError::internal("upload empty object"));
+        }
+        let authorization = self.credential.authorization(&self.client).await?;
+        let uri = format!(
+            "{}?uploadType=media&name={}",
+            self.upload_url(),
+            urlencoding::encode(path)
+        );
+        let headers = [(
+            CONTENT_TYPE.as_str(),
+            "application/octet-stream".to_string(),
+        )];
+        self.client
+            .send(
+                || build_request(Method::POST, &uri, &authorization, &headers, obj.clone()),
+                &[],
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn streaming_upload(&self, path: &str) -> ObjectResult<BoxedStreamingUploader> {
+        fail_point!("gcs_streaming_upload_err", |_| Err(ObjectError::internal(
+            "gcs streaming upload error"
+        )));
+        Ok(Box::new(GcsStreamingUploader::new(self, path.to_string())))
+    }
+
+    async fn read(&self, path: &str, block_loc: Option<BlockLocation>) -> ObjectResult<Bytes> {
+        fail_point!("gcs_read_err", |_| Err(ObjectError::internal(
+            "gcs read error"
+        )));
+        let val = self
+            .read_range(path, range_header(block_loc.as_ref(), None))
+            .await?;
+        if let Some(block_loc) = block_loc.as_ref() {
+            check_read_size(path, block_loc, &val)?;
+        }
+        Ok(val)
+    }
+
+    async fn readv(&self, path: &str, block_locs: &[BlockLocation]) -> ObjectResult<Vec<Bytes>> {
+        let futures = block_locs
+            .iter()
+            .map(|block_loc| self.read(path, Some(*block_loc)))
+            .collect_vec();
+        try_join_all(futures).await
+    }
+
+    /// The whole requested range is loaded into memory before returning.
+    async fn streaming_read(
+        &self,
+        path: &str,
+        start_pos: Option<usize>,
+    ) -> ObjectResult<Box<dyn AsyncRead + Unpin + Send + Sync>> {
+        fail_point!("gcs_streaming_read_err", |_| Err(ObjectError::internal(
+            "gcs streaming read error"
+        )));
+        let val = self.read_range(path, range_header(None, start_pos)).await?;
+        Ok(Box::new(Cursor::new(val)))
+    }
+
+    async fn metadata(&self, path: &str) -> ObjectResult<ObjectMetadata> {
+        fail_point!("gcs_metadata_err", |_| Err(ObjectError::internal(
+            "gcs metadata error"
+        )));
+        let authorization = self.credential.authorization(&self.client).await?;
+        let uri = self.object_url(path);
+        let body = self
+            .client
+            .send_and_read(|| build_request(Method::GET, &uri, &authorization, &[], Bytes::new()))
+            .await?;
+        let object: GcsObject = serde_json::from_slice(&body).map_err(ObjectError::http)?;
+        object.into_metadata()
+    }
+
+    /// Permanently deletes the whole object.
+    /// Deleting an object that does not exist is considered successful.
+    async fn delete(&self, path: &str) -> ObjectResult<()> {
+        fail_point!("gcs_delete_err", |_| Err(ObjectError::internal(
+            "gcs delete error"
+        )));
+        let authorization = self.credential.authorization(&self.client).await?;
+        let uri = self.object_url(path);
+        self.client
+            .send(
+                || build_request(Method::DELETE, &uri, &authorization, &[], Bytes::new()),
+                &[StatusCode::NOT_FOUND],
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes the objects with the given paths permanently from the storage. If an object
+    /// specified in the request is not found, it will be considered as successfully deleted.
+    ///
+    /// The JSON API has no multi-object delete, so the objects are deleted with concurrent
+    /// requests instead.
+    async fn delete_objects(&self, paths: &[String]) -> ObjectResult<()> {
+        for chunk in paths.chunks(GCS_DELETE_CONCURRENCY) {
+            try_join_all(chunk.iter().map(|path| self.delete(path))).await?;
+        }
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> ObjectResult<Vec<ObjectMetadata>> {
+        let mut ret = vec![];
+        let mut next_page_token: Option<String> = None;
+        // A page contains up to 1000 objects. Use the `pageToken` given by last response to fetch
+        // more parts of the result, until no token is returned.
+        loop {
+            let authorization = self.credential.authorization(&self.client).await?;
+            let mut uri = format!(
+                "{}/storage/v1/b/{}/o?prefix={}",
+                self.endpoint,
+                self.bucket,
+                urlencoding::encode(prefix)
+            );
+            if let Some(page_token) = next_page_token.take() {
+                uri.push_str(&format!("&pageToken={}", urlencoding::encode(&page_token)));
+            }
+            let body = self
+                .client
+                .send_and_read(|| {
+                    build_request(Method::GET, &uri, &authorization, &[], Bytes::new())
+                })
+                .await?;
+            let result: GcsListResponse =
+                serde_json::from_slice(&body).map_err(ObjectError::http)?;
+            for object in result.items {
+                ret.push(object.into_metadata()?);
+            }
+            next_page_token = result.next_page_token;
+            if next_page_token.is_none() {
+                break;
+            }
+        }
+        Ok(ret)
+    }
+
+    fn store_media_type(&self) -> &'static str {
+        "gcs"
+    }
+}
+
+impl GcsObjectStore {
+    /// Creates a GCS object store from environment variables.
+    ///
+    /// Requests are authorized with the token in `GCS_ACCESS_TOKEN` if it is set. Otherwise,
+    /// tokens of the attached service account are fetched from the GCE metadata server, unless a
+    /// custom endpoint is given in `GCS_ENDPOINT`, in which case requests are not authorized.
+    pub async fn new(bucket: String, metrics: Arc<ObjectStoreMetrics>) -> Self {
+        let endpoint =
+            std::env::var(GCS_ENDPOINT_ENV).unwrap_or_else(|_| GCS_DEFAULT_ENDPOINT.to_string());
+        let credential = match std::env::var(GCS_ACCESS_TOKEN_ENV) {
+            Ok(token) => GcsCredential::Static(token),
+            Err(_) if endpoint != GCS_DEFAULT_ENDPOINT => GcsCredential::Anonymous,
+            Err(_) => GcsCredential::Metadata(Mutex::new(None)),
+        };
+        Self::with_endpoint(endpoint, bucket, credential, metrics)
+    }
+
+    fn with_endpoint(
+        endpoint: String,
+        bucket: String,
+        credential: GcsCredential,
+        metrics: Arc<ObjectStoreMetrics>,
+    ) -> Self {
+        Self {
+            client: HttpClient::new(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            bucket,
+            credential: Arc::new(credential),
+            part_size: GCS_PART_SIZE,
+            metrics,
+        }
+    }
+
+    fn object_url(&self, path: &str) -> String {
+        format!(
+            "{}/storage/v1/b/{}/o/{}",
+            self.endpoint,
+            self.bucket,
+            urlencoding::encode(path)
+        )
+    }
+
+    fn upload_url(&self) -> String {
+        format!("{}/upload/storage/v1/b/{}/o", self.endpoint, self.bucket)
+    }
+
+    async fn read_range(&self, path: &str, range: Option<String>) -> ObjectResult<Bytes> {
+        let authorization = self.credential.authorization(&self.client).await?;
+        let uri = format!("{}?alt=media", self.object_url(path));
+        let headers = range
+            .map(|range| vec![(RANGE.as_str(), range)])
+            .unwrap_or_default();
+        self.client
+            .send_and_read(|| {
+                build_request(Method::GET, &uri, &authorization, &headers, Bytes::new())
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(madsim))]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+    use std::sync::{Arc, Mutex};
+
+    use bytes::Bytes;
+    use hyper::header::{HeaderName, AUTHORIZATION, CONTENT_RANGE, HOST, LOCATION, RANGE};
+    use hyper::{Body, Method, Request, Response, StatusCode};
+    use tokio::io::AsyncReadExt;
+
+    use super::{GcsCredential, GcsObjectStore, GCS_CHUNK_ALIGNMENT};
+    use crate::object::http::test_utils::{
+        parse_query, ranged_response, start_emulator, status_response,
+    };
+    use crate::object::object_metrics::ObjectStoreMetrics;
+    use crate::object::{BlockLocation, ObjectStore};
+
+    const BUCKET: &str = "test-bucket";
+    const TOKEN: &str = "test-token";
+    /// Objects per page of a list response, small enough to test pagination.
+    const PAGE_SIZE: usize = 2;
+
+    /// Emulates the subset of the GCS JSON API used by `GcsObjectStore`.
+    #[derive(Default)]
+    struct GcsEmulator {
+        objects: BTreeMap<String, Bytes>,
+        /// Resumable upload sessions, with the name of the object and the data received so far.
+        sessions: HashMap<String, (String, Vec<u8>)>,
+        next_session_id: usize,
+    }
+
+    async fn handle(emulator: Arc<Mutex<GcsEmulator>>, req: Request<Body>) -> Response<Body> {
+        let authorization = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        if authorization != format!("Bearer {}", TOKEN) {
+            return status_response(StatusCode::UNAUTHORIZED);
+        }
+        let header = |name: HeaderName| {
+            req.headers()
+                .get(name)
+                .map(|value| value.to_str().unwrap().to_string())
+        };
+        let (host, range, content_range) = (header(HOST), header(RANGE), header(CONTENT_RANGE));
+        let method = req.method().clone();
+        let path = urlencoding::decode(req.uri().path()).unwrap().into_owned();
+        let query = parse_query(req.uri());
+        let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+
+        let mut emulator = emulator.lock().unwrap();
+        let objects_path = format!("/storage/v1/b/{}/o", BUCKET);
+        if path == format!("/upload/storage/v1/b/{}/o", BUCKET) {
+            let name = query["name"].clone();
+            if query["uploadType"] == "media" {
+                emulator.objects.insert(name, body);
+                return status_response(StatusCode::OK);
+            }
+            let session_id = emulator.next_session_id.to_string();
+            emulator.next_session_id += 1;
+            emulator.sessions.insert(session_id.clone(), (name, vec![]));
+            let location = format!("http://{}/upload/session/{}", host.unwrap(), session_id);
+            Response::builder()
+                .header(LOCATION, location)
+                .body(Body::empty())
+                .unwrap()
+        } else if let Some(session_id) = path.strip_prefix("/upload/session/") {
+            if method == Method::DELETE {
+                emulator.sessions.remove(session_id);
+                return status_response(StatusCode::from_u16(499).unwrap());
+            }
+            let content_range = content_range.unwrap();
+            let (range, total) = content_range
+                .strip_prefix("bytes ")
+                .unwrap()
+                .split_once('/')
+                .unwrap();
+            let (_, data) = emulator.sessions.get_mut(session_id).unwrap();
+            if range != "*" {
+                let start: usize = range.split_once('-').unwrap().0.parse().unwrap();
+                assert_eq!(start, data.len());
+                data.extend_from_slice(&body);
+            }
+            if total == "*" {
+                assert_eq!(body.len() % GCS_CHUNK_ALIGNMENT, 0);
+                return status_response(StatusCode::PERMANENT_REDIRECT);
+            }
+            assert_eq!(total.parse::<usize>().unwrap(), data.len());
+            let (name, data) = emulator.sessions.remove(session_id).unwrap();
+            emulator.objects.insert(name, data.into());
+            status_response(StatusCode::OK)
+        } else if path == objects_path {
+            let start: usize = query
+                .get("pageToken")
+                .map_or(0, |token| token.parse().unwrap());
+            let matched = emulator
+                .objects
+                .iter()
+                .filter(|(name, _)| name.starts_with(&query["prefix"]))
+                .collect::<Vec<_>>();
+            let items = matched
+                .iter()
+                .skip(start)
+                .take(PAGE_SIZE)
+                .map(|(name, data)| object_resource(name, data))
+                .collect::<Vec<_>>();
+            let mut resp = serde_json::json!({ "items": items });
+            if start + PAGE_SIZE < matched.len() {
+                resp["nextPageToken"] = (start + PAGE_SIZE).to_string().into();
+            }
+            Response::new(Body::from(resp.to_string()))
+        } else if let Some(name) = path.strip_prefix(&format!("{}/", objects_path)) {
+            let data = match emulator.objects.get(name) {
+                Some(data) => data.clone(),
+                None => return status_response(StatusCode::NOT_FOUND),
+            };
+            match method {
+                Method::GET if query.get("alt").map(String::as_str) == Some("media") => {
+                    ranged_response(&data, range.as_deref())
+                }
+                Method::GET => Response::new(Body::from(object_resource(name, &data).to_string())),
+                Method::DELETE => {
+                    emulator.objects.remove(name);
+                    status_response(StatusCode::NO_CONTENT)
+                }
+                _ => status_response(StatusCode::METHOD_NOT_ALLOWED),
+            }
+        } else {
+            status_response(StatusCode::NOT_FOUND)
+        }
+    }
+
+    fn object_resource(name: &str, data: &Bytes) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "size": data.len().to_string(),
+            "updated": "2022-10-01T08:00:00.500Z",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_gcs_object_store() {
+        let emulator = Arc::new(Mutex::new(GcsEmulator::default()));
+        let addr = {
+            let emulator = emulator.clone();
+            start_emulator(move |req| handle(emulator.clone(), req))
+        };
+        let mut store = GcsObjectStore::with_endpoint(
+            format!("http://{}", addr),
+            BUCKET.to_string(),
+            GcsCredential::Static(TOKEN.to_string()),
+            Arc::new(ObjectStoreMetrics::unused()),
+        );
+        store.part_size = GCS_CHUNK_ALIGNMENT;
+
+        // Upload in a single request and read it back.
+        store
+            .upload("a/small", Bytes::from("hello world"))
+            .await
+            .unwrap();
+        assert_eq!(store.read("a/small", None).await.unwrap(), "hello world");
+        let blocks = store
+            .readv(
+                "a/small",
+                &[
+                    BlockLocation { offset: 0, size: 5 },
+                    BlockLocation { offset: 6, size: 5 },
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(blocks, vec![Bytes::from("hello"), Bytes::from("world")]);
+        let mut reader = store.streaming_read("a/small", Some(6)).await.unwrap();
+        let mut buf = vec![];
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, b"world");
+
+        // Streaming upload of several chunks and a partial last one.
+        let data = (0..600 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+        let mut uploader = store.streaming_upload("a/large").unwrap();
+        for piece in data.chunks(100 * 1024) {
+            uploader
+                .write_bytes(Bytes::copy_from_slice(piece))
+                .await
+                .unwrap();
+        }
+        uploader.finish().await.unwrap();
+        assert_eq!(store.read("a/large", None).await.unwrap(), data);
+        assert!(emulator.lock().unwrap().sessions.is_empty());
+
+        // Streaming upload that fits in one request.
+        let mut uploader = store.streaming_upload("b/tiny").unwrap();
+        uploader.write_bytes(Bytes::from("tiny")).await.unwrap();
+        uploader.finish().await.unwrap();
+
+        let metadata = store.metadata("a/large").await.unwrap();
+        assert_eq!(metadata.total_size, data.len());
+        assert_eq!(metadata.last_modified, 1664611200.5);
+        let keys = |objects: Vec<crate::object::ObjectMetadata>| {
+            objects.into_iter().map(|obj| obj.key).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            keys(store.list("a/").await.unwrap()),
+            vec!["a/large", "a/small"]
+        );
+        assert_eq!(store.list("").await.unwrap().len(), 3);
+
+        // Missing objects are considered deleted.
+        store
+            .delete_objects(&["a/small".to_string(), "a/missing".to_string()])
+            .await
+            .unwrap();
+        store.delete("b/tiny").await.unwrap();
+        store.delete("b/tiny").await.unwrap();
+        assert_eq!(keys(store.list("").await.unwrap()), vec!["a/large"]);
+        assert!(store.read("a/small", None).await.is_err());
+    }
+}
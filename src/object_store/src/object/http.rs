@@ -0,0 +1,227 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shared HTTP plumbing of the object stores that talk to their backend through a plain HTTP API
+//! instead of a vendor SDK.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use hyper::client::HttpConnector;
+use hyper::{Body, Client, Request, Response, StatusCode};
+use hyper_tls::HttpsConnector;
+
+use super::{BlockLocation, ObjectError, ObjectResult};
+
+/// Number of attempts of a request before giving up, including the first one.
+const MAX_ATTEMPTS: usize = 4;
+const BASE_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Clone)]
+pub struct HttpClient {
+    inner: Client<HttpsConnector<HttpConnector>>,
+}
+
+impl HttpClient {
+    pub fn new() -> Self {
+        Self {
+            inner: Client::builder().build(HttpsConnector::new()),
+        }
+    }
+
+    /// Sends the request built by `make_request` and returns the response if it has a success
+    /// status, or if its status is one of `allowed_status`. Connection errors, throttling and
+    /// server-side errors are retried with exponential backoff, so `make_request` is called once
+    /// per attempt.
+    pub async fn send(
+        &self,
+        make_request: impl Fn() -> ObjectResult<Request<Body>>,
+        allowed_status: &[StatusCode],
+    ) -> ObjectResult<Response<Body>> {
+        let mut attempt = 1;
+        loop {
+            let request = make_request()?;
+            let description = format!("{} {}", request.method(), request.uri());
+            let err = match self.inner.request(request).await {
+                Ok(resp) => {
+                    let status = resp.status();
+                    if status.is_success() || allowed_status.contains(&status) {
+                        return Ok(resp);
+                    }
+                    let body = hyper::body::to_bytes(resp.into_body())
+                        .await
+                        .unwrap_or_default();
+                    let err = ObjectError::http_status(
+                        status.as_u16(),
+                        format!("{}: {}", description, String::from_utf8_lossy(&body)),
+                    );
+                    if !is_retryable(status) {
+                        return Err(err);
+                    }
+                    err
+                }
+                Err(e) => e.into(),
+            };
+            if attempt >= MAX_ATTEMPTS {
+                return Err(err);
+            }
+            tracing::warn!(
+                "http request {} failed at attempt {}, retrying: {}",
+                description,
+                attempt,
+                err
+            );
+            tokio::time::sleep(BASE_RETRY_DELAY * (1 << (attempt - 1))).await;
+            attempt += 1;
+        }
+    }
+
+    /// Same as `send`, but collects the body of the response.
+    pub async fn send_and_read(
+        &self,
+        make_request: impl Fn() -> ObjectResult<Request<Body>>,
+    ) -> ObjectResult<Bytes> {
+        let resp = self.send(make_request, &[]).await?;
+        Ok(hyper::body::to_bytes(resp.into_body()).await?)
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+/// Checks that a ranged read returned exactly the requested bytes.
+pub fn check_read_size(path: &str, block_loc: &BlockLocation, data: &Bytes) -> ObjectResult<()> {
+    if block_loc.size != data.len() {
+        return Err(ObjectError::internal(format!(
+            "mismatched size: expected {}, found {} when reading {} at {:?}",
+            block_loc.size,
+            data.len(),
+            path,
+            block_loc
+        )));
+    }
+    Ok(())
+}
+
+/// Generates the value of the http range header starting at `start_pos`, or `None` if the whole
+/// object should be read.
+pub fn range_header(block_loc: Option<&BlockLocation>, start_pos: Option<usize>) -> Option<String> {
+    match (block_loc, start_pos) {
+        (Some(block_loc), _) => block_loc.byte_range_specifier(),
+        (None, Some(pos)) => Some(format!("bytes={}-", pos)),
+        (None, None) => None,
+    }
+}
+
+/// Concatenates the buffered chunks of a streaming upload into one request body.
+pub fn concat_chunks(chunks: Vec<Bytes>, len: usize) -> Bytes {
+    if chunks.len() == 1 {
+        return chunks.into_iter().next().unwrap();
+    }
+    let mut buf = Vec::with_capacity(len);
+    for chunk in chunks {
+        buf.extend_from_slice(&chunk);
+    }
+    buf.into()
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+    use std::future::Future;
+    use std::net::SocketAddr;
+
+    use bytes::Bytes;
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Request, Response, Server, StatusCode, Uri};
+
+    /// Starts an HTTP server on a local port that answers requests with `handler`. It stands in
+    /// for the emulator of an object storage service in tests.
+    pub fn start_emulator<F, Fut>(handler: F) -> SocketAddr
+    where
+        F: Fn(Request<Body>) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = Response<Body>> + Send + 'static,
+    {
+        let make_service = make_service_fn(move |_| {
+            let handler = handler.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let resp = handler(req);
+                    async move { Ok::<_, Infallible>(resp.await) }
+                }))
+            }
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    pub fn parse_query(uri: &Uri) -> HashMap<String, String> {
+        uri.query()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                let (name, value) = param.split_once('=').unwrap_or((param, ""));
+                (
+                    name.to_string(),
+                    urlencoding::decode(value).unwrap().into_owned(),
+                )
+            })
+            .collect()
+    }
+
+    /// Answers a read of `data` with the part specified by the `bytes=start-[end]` range header.
+    pub fn ranged_response(data: &Bytes, range: Option<&str>) -> Response<Body> {
+        let (status, data) = match range {
+            Some(range) => {
+                let (start, end) = range
+                    .strip_prefix("bytes=")
+                    .unwrap()
+                    .split_once('-')
+                    .unwrap();
+                let start: usize = start.parse().unwrap();
+                let end = if end.is_empty() {
+                    data.len()
+                } else {
+                    end.parse::<usize>().unwrap() + 1
+                };
+                (StatusCode::PARTIAL_CONTENT, data.slice(start..end))
+            }
+            None => (StatusCode::OK, data.clone()),
+        };
+        Response::builder()
+            .status(status)
+            .body(Body::from(data))
+            .unwrap()
+    }
+
+    pub fn status_response(status: StatusCode) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .body(Body::empty())
+            .unwrap()
+    }
+}
@@ -24,8 +24,15 @@ pub mod s3;
 use async_stack_trace::StackTrace;
 pub use s3::*;
 
+pub mod gcs;
+pub use gcs::*;
+
+pub mod azblob;
+pub use azblob::*;
+
 mod disk;
 pub mod error;
+mod http;
 pub mod object_metrics;
 
 pub use error::*;
@@ -184,6 +191,8 @@ pub enum ObjectStoreImpl {
     InMem(MonitoredObjectStore<InMemObjectStore>),
     Disk(MonitoredObjectStore<DiskObjectStore>),
     S3(MonitoredObjectStore<S3ObjectStore>),
+    Gcs(MonitoredObjectStore<GcsObjectStore>),
+    Azblob(MonitoredObjectStore<AzblobObjectStore>),
     Hybrid {
         local: Box<ObjectStoreImpl>,
         remote: Box<ObjectStoreImpl>,
@@ -234,6 +243,14 @@ macro_rules! object_store_impl_method_body {
                     assert!(path.is_remote(), "get local path in pure s3 object store: {:?}", $path);
                     $dispatch_macro!(s3, $method_name, path.as_str() $(, $args)*)
                 },
+                ObjectStoreImpl::Gcs(gcs) => {
+                    assert!(path.is_remote(), "get local path in pure gcs object store: {:?}", $path);
+                    $dispatch_macro!(gcs, $method_name, path.as_str() $(, $args)*)
+                },
+                ObjectStoreImpl::Azblob(azblob) => {
+                    assert!(path.is_remote(), "get local path in pure azblob object store: {:?}", $path);
+                    $dispatch_macro!(azblob, $method_name, path.as_str() $(, $args)*)
+                },
                 ObjectStoreImpl::Hybrid {
                     local: local,
                     remote: remote,
@@ -243,12 +260,16 @@ macro_rules! object_store_impl_method_body {
                             ObjectStoreImpl::InMem(in_mem) => $dispatch_macro!(in_mem, $method_name, path.as_str() $(, $args)*),
                             ObjectStoreImpl::Disk(disk) => $dispatch_macro!(disk, $method_name, path.as_str() $(, $args)*),
                             ObjectStoreImpl::S3(_) => unreachable!("S3 cannot be used as local object store"),
+                            ObjectStoreImpl::Gcs(_) => unreachable!("GCS cannot be used as local object store"),
+                            ObjectStoreImpl::Azblob(_) => unreachable!("Azure Blob cannot be used as local object store"),
                             ObjectStoreImpl::Hybrid {..} => unreachable!("local object store of hybrid object store cannot be hybrid")
                         },
                         ObjectStorePath::Remote(_) => match remote.as_ref() {
                             ObjectStoreImpl::InMem(in_mem) => $dispatch_macro!(in_mem, $method_name, path.as_str() $(, $args)*),
                             ObjectStoreImpl::Disk(disk) => $dispatch_macro!(disk, $method_name, path.as_str() $(, $args)*),
                             ObjectStoreImpl::S3(s3) => $dispatch_macro!(s3, $method_name, path.as_str() $(, $args)*),
+                            ObjectStoreImpl::Gcs(gcs) => $dispatch_macro!(gcs, $method_name, path.as_str() $(, $args)*),
+                            ObjectStoreImpl::Azblob(azblob) => $dispatch_macro!(azblob, $method_name, path.as_str() $(, $args)*),
                             ObjectStoreImpl::Hybrid {..} => unreachable!("remote object store of hybrid object store cannot be hybrid")
                         },
                     }
@@ -282,6 +303,14 @@ macro_rules! object_store_impl_method_body_slice {
                     assert!(paths_loc.is_empty(), "get local path in pure s3 object store: {:?}", $paths);
                     $dispatch_macro!(s3, $method_name, &paths_rem $(, $args)*)
                 },
+                ObjectStoreImpl::Gcs(gcs) => {
+                    assert!(paths_loc.is_empty(), "get local path in pure gcs object store: {:?}", $paths);
+                    $dispatch_macro!(gcs, $method_name, &paths_rem $(, $args)*)
+                },
+                ObjectStoreImpl::Azblob(azblob) => {
+                    assert!(paths_loc.is_empty(), "get local path in pure azblob object store: {:?}", $paths);
+                    $dispatch_macro!(azblob, $method_name, &paths_rem $(, $args)*)
+                },
                 ObjectStoreImpl::Hybrid {
                     local: local,
                     remote: remote,
@@ -291,6 +320,8 @@ macro_rules! object_store_impl_method_body_slice {
                         ObjectStoreImpl::InMem(in_mem) =>  $dispatch_macro!(in_mem, $method_name, &paths_loc $(, $args)*),
                         ObjectStoreImpl::Disk(disk) =>  $dispatch_macro!(disk, $method_name, &paths_loc $(, $args)*),
                         ObjectStoreImpl::S3(_) => unreachable!("S3 cannot be used as local object store"),
+                        ObjectStoreImpl::Gcs(_) => unreachable!("GCS cannot be used as local object store"),
+                        ObjectStoreImpl::Azblob(_) => unreachable!("Azure Blob cannot be used as local object store"),
                         ObjectStoreImpl::Hybrid {..} => unreachable!("local object store of hybrid object store cannot be hybrid")
                     }?;
 
@@ -299,6 +330,8 @@ macro_rules! object_store_impl_method_body_slice {
                         ObjectStoreImpl::InMem(in_mem) =>  $dispatch_macro!(in_mem, $method_name, &paths_rem $(, $args)*),
                         ObjectStoreImpl::Disk(disk) =>  $dispatch_macro!(disk, $method_name, &paths_rem $(, $args)*),
                         ObjectStoreImpl::S3(s3) =>  $dispatch_macro!(s3, $method_name, &paths_rem $(, $args)*),
+                        ObjectStoreImpl::Gcs(gcs) =>  $dispatch_macro!(gcs, $method_name, &paths_rem $(, $args)*),
+                        ObjectStoreImpl::Azblob(azblob) =>  $dispatch_macro!(azblob, $method_name, &paths_rem $(, $args)*),
                         ObjectStoreImpl::Hybrid {..} => unreachable!("remote object store of hybrid object store cannot be hybrid")
                     }
                 }
@@ -368,6 +401,8 @@ impl ObjectStoreImpl {
             ObjectStoreImpl::InMem(store) => store.inner.get_object_prefix(obj_id),
             ObjectStoreImpl::Disk(store) => store.inner.get_object_prefix(obj_id),
             ObjectStoreImpl::S3(store) => store.inner.get_object_prefix(obj_id),
+            ObjectStoreImpl::Gcs(store) => store.inner.get_object_prefix(obj_id),
+            ObjectStoreImpl::Azblob(store) => store.inner.get_object_prefix(obj_id),
             ObjectStoreImpl::Hybrid { local, remote } => {
                 if is_remote {
                     remote.get_object_prefix(obj_id, true)
@@ -716,6 +751,22 @@ pub async fn parse_remote_object_store(
                 .await
                 .monitored(metrics),
         ),
+        gcs if gcs.starts_with("gcs://") => ObjectStoreImpl::Gcs(
+            GcsObjectStore::new(
+                gcs.strip_prefix("gcs://").unwrap().to_string(),
+                metrics.clone(),
+            )
+            .await
+            .monitored(metrics),
+        ),
+        azblob if azblob.starts_with("azblob://") => ObjectStoreImpl::Azblob(
+            AzblobObjectStore::new(
+                azblob.strip_prefix("azblob://").unwrap().to_string(),
+                metrics.clone(),
+            )
+            .await
+            .monitored(metrics),
+        ),
         disk if disk.starts_with("disk://") => ObjectStoreImpl::Disk(
             DiskObjectStore::new(disk.strip_prefix("disk://").unwrap()).monitored(metrics),
         ),
@@ -729,7 +780,7 @@ pub async fn parse_remote_object_store(
         }
         other => {
             unimplemented!(
-                "{} hummock remote object store only supports s3, minio, disk, memory, and memory-shared for now.",
+                "{} hummock remote object store only supports s3, minio, gcs, azblob, disk, memory, and memory-shared for now.",
                 other
             )
         }
@@ -558,7 +558,7 @@ impl S3ObjectStore {
         }
     }
 
-    fn get_object_prefix(obj_id: u64) -> String {
+    pub(crate) fn get_object_prefix(obj_id: u64) -> String {
         let prefix = crc32fast::hash(&obj_id.to_be_bytes()) % NUM_BUCKET_PREFIXES;
         let mut obj_prefix = prefix.to_string();
         obj_prefix.push('/');
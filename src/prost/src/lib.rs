@@ -17,6 +17,9 @@
 #![expect(clippy::doc_markdown)]
 #![feature(lint_reasons)]
 
+#[rustfmt::skip]
+#[cfg_attr(madsim, path = "sim/backup_service.rs")]
+pub mod backup_service;
 #[rustfmt::skip]
 #[cfg_attr(madsim, path = "sim/catalog.rs")]
 pub mod catalog;
@@ -66,6 +69,9 @@ pub mod monitor_service;
 #[cfg_attr(madsim, path = "sim/health.rs")]
 pub mod health;
 
+#[rustfmt::skip]
+#[path = "backup_service.serde.rs"]
+pub mod backup_service_serde;
 #[rustfmt::skip]
 #[path = "catalog.serde.rs"]
 pub mod catalog_serde;
@@ -28,8 +28,10 @@ pub struct ComputeNodeConfig {
     pub listen_address: String,
     pub exporter_port: u16,
     pub enable_async_stack_trace: bool,
-    pub enable_managed_cache: bool,
     pub enable_tiered_cache: bool,
+    /// Deprecated and ignored.
+    #[serde(default)]
+    pub enable_managed_cache: bool,
 
     pub provide_minio: Option<Vec<MinioConfig>>,
     pub provide_meta_node: Option<Vec<MetaNodeConfig>>,
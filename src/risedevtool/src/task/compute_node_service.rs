@@ -63,10 +63,6 @@ impl ComputeNodeService {
             cmd.arg("--enable-async-stack-trace");
         }
 
-        if config.enable_managed_cache {
-            cmd.arg("--enable-managed-cache");
-        }
-
         let provide_jaeger = config.provide_jaeger.as_ref().unwrap();
         match provide_jaeger.len() {
             0 => {}
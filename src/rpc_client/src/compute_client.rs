@@ -21,7 +21,8 @@ use piestream_common::util::addr::HostAddr;
 use piestream_pb::batch_plan::{PlanFragment, TaskId, TaskOutputId};
 use piestream_pb::monitor_service::monitor_service_client::MonitorServiceClient;
 use piestream_pb::monitor_service::{
-    ProfilingRequest, ProfilingResponse, StackTraceRequest, StackTraceResponse,
+    ActorStatsRequest, ActorStatsResponse, ProfilingRequest, ProfilingResponse, StackTraceRequest,
+    StackTraceResponse,
 };
 use piestream_pb::task_service::exchange_service_client::ExchangeServiceClient;
 use piestream_pb::task_service::task_service_client::TaskServiceClient;
@@ -145,6 +146,15 @@ impl ComputeClient {
             .into_inner())
     }
 
+    pub async fn actor_stats(&self) -> Result<ActorStatsResponse> {
+        Ok(self
+            .monitor_client
+            .to_owned()
+            .actor_stats(ActorStatsRequest::default())
+            .await?
+            .into_inner())
+    }
+
     pub async fn profile(&self, sleep_s: u64) -> Result<ProfilingResponse> {
         Ok(self
             .monitor_client
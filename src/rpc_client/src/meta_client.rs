@@ -24,6 +24,11 @@ use piestream_hummock_sdk::{
     CompactionGroupId, HummockEpoch, HummockSstableId, HummockVersionId, LocalSstableInfo,
     SstIdRange,
 };
+use piestream_pb::backup_service::backup_service_client::BackupServiceClient;
+use piestream_pb::backup_service::{
+    BackupMetaRequest, BackupMetaResponse, DeleteMetaSnapshotRequest, DeleteMetaSnapshotResponse,
+    ListMetaSnapshotMetadataRequest, ListMetaSnapshotMetadataResponse, MetaSnapshotMetadata,
+};
 use piestream_pb::catalog::{
     Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
     Source as ProstSource, Table as ProstTable,
@@ -164,10 +169,12 @@ impl MetaClient {
         &self,
         table: ProstTable,
         graph: StreamFragmentGraph,
+        background: bool,
     ) -> Result<(TableId, CatalogVersion)> {
         let request = CreateMaterializedViewRequest {
             materialized_view: Some(table),
             fragment_graph: Some(graph),
+            background,
         };
         let resp = self.inner.create_materialized_view(request).await?;
         // TODO: handle error in `resp.status` here
@@ -273,6 +280,30 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    /// Set the parallelism of a streaming job. `None` means `auto`.
+    pub async fn alter_parallelism(&self, table_id: u32, parallelism: Option<u32>) -> Result<()> {
+        let request = AlterParallelismRequest {
+            table_id,
+            parallelism: parallelism.unwrap_or(0),
+        };
+        self.inner.alter_parallelism(request).await?;
+        Ok(())
+    }
+
+    /// Get the progress of all creating materialized views.
+    pub async fn get_ddl_progress(&self) -> Result<Vec<DdlProgress>> {
+        let request = GetDdlProgressRequest {};
+        let resp = self.inner.get_ddl_progress(request).await?;
+        Ok(resp.ddl_progress)
+    }
+
+    /// Cancel the creating jobs, returns the ids of the jobs which are actually canceled.
+    pub async fn cancel_creating_jobs(&self, job_ids: Vec<u32>) -> Result<Vec<u32>> {
+        let request = CancelCreatingJobsRequest { job_ids };
+        let resp = self.inner.cancel_creating_jobs(request).await?;
+        Ok(resp.canceled_jobs)
+    }
+
     pub async fn drop_database(&self, database_id: u32) -> Result<CatalogVersion> {
         let request = DropDatabaseRequest { database_id };
         let resp = self.inner.drop_database(request).await?;
@@ -448,6 +479,26 @@ impl MetaClient {
         Ok(resp.success)
     }
 
+    pub async fn backup_meta(&self) -> Result<u64> {
+        let request = BackupMetaRequest {};
+        let resp = self.inner.backup_meta(request).await?;
+        Ok(resp.snapshot_id)
+    }
+
+    pub async fn list_meta_snapshot_metadata(&self) -> Result<Vec<MetaSnapshotMetadata>> {
+        let request = ListMetaSnapshotMetadataRequest {};
+        let resp = self.inner.list_meta_snapshot_metadata(request).await?;
+        Ok(resp.snapshot_metadata)
+    }
+
+    pub async fn delete_meta_snapshot(&self, snapshot_ids: &[u64]) -> Result<()> {
+        let request = DeleteMetaSnapshotRequest {
+            snapshot_ids: snapshot_ids.to_vec(),
+        };
+        self.inner.delete_meta_snapshot(request).await?;
+        Ok(())
+    }
+
     pub async fn risectl_get_pinned_versions_summary(
         &self,
     ) -> Result<RiseCtlGetPinnedVersionsSummaryResponse> {
@@ -466,6 +517,12 @@ impl MetaClient {
             .await
     }
 
+    pub async fn risectl_remove_dangling_ssts(&self, sst_ids: Vec<u64>) -> Result<Vec<u64>> {
+        let request = RiseCtlRemoveDanglingSstsRequest { sst_ids };
+        let resp = self.inner.rise_ctl_remove_dangling_ssts(request).await?;
+        Ok(resp.removed_sst_ids)
+    }
+
     pub async fn reset_current_version(&self) -> Result<HummockVersion> {
         let req = ResetCurrentVersionRequest {};
         Ok(self
@@ -705,6 +762,7 @@ pub struct GrpcMetaClient {
     pub stream_client: StreamManagerServiceClient<Channel>,
     pub user_client: UserServiceClient<Channel>,
     pub scale_client: ScaleServiceClient<Channel>,
+    pub backup_client: BackupServiceClient<Channel>,
 }
 
 impl GrpcMetaClient {
@@ -751,7 +809,8 @@ impl GrpcMetaClient {
         let notification_client = NotificationServiceClient::new(channel.clone());
         let stream_client = StreamManagerServiceClient::new(channel.clone());
         let user_client = UserServiceClient::new(channel.clone());
-        let scale_client = ScaleServiceClient::new(channel);
+        let scale_client = ScaleServiceClient::new(channel.clone());
+        let backup_client = BackupServiceClient::new(channel);
         Ok(Self {
             cluster_client,
             heartbeat_client,
@@ -761,6 +820,7 @@ impl GrpcMetaClient {
             stream_client,
             user_client,
             scale_client,
+            backup_client,
         })
     }
 }
@@ -790,6 +850,9 @@ macro_rules! for_all_meta_rpc {
             ,{ ddl_client, drop_schema, DropSchemaRequest, DropSchemaResponse }
             ,{ ddl_client, drop_index, DropIndexRequest, DropIndexResponse }
             ,{ ddl_client, risectl_list_state_tables, RisectlListStateTablesRequest, RisectlListStateTablesResponse }
+            ,{ ddl_client, alter_parallelism, AlterParallelismRequest, AlterParallelismResponse }
+            ,{ ddl_client, get_ddl_progress, GetDdlProgressRequest, GetDdlProgressResponse }
+            ,{ ddl_client, cancel_creating_jobs, CancelCreatingJobsRequest, CancelCreatingJobsResponse }
             ,{ hummock_client, unpin_version_before, UnpinVersionBeforeRequest, UnpinVersionBeforeResponse }
             ,{ hummock_client, get_current_version, GetCurrentVersionRequest, GetCurrentVersionResponse }
             ,{ hummock_client, reset_current_version, ResetCurrentVersionRequest, ResetCurrentVersionResponse }
@@ -814,6 +877,7 @@ macro_rules! for_all_meta_rpc {
             ,{ hummock_client, trigger_full_gc, TriggerFullGcRequest, TriggerFullGcResponse }
             ,{ hummock_client, rise_ctl_get_pinned_versions_summary, RiseCtlGetPinnedVersionsSummaryRequest, RiseCtlGetPinnedVersionsSummaryResponse }
             ,{ hummock_client, rise_ctl_get_pinned_snapshots_summary, RiseCtlGetPinnedSnapshotsSummaryRequest, RiseCtlGetPinnedSnapshotsSummaryResponse }
+            ,{ hummock_client, rise_ctl_remove_dangling_ssts, RiseCtlRemoveDanglingSstsRequest, RiseCtlRemoveDanglingSstsResponse }
             ,{ user_client, create_user, CreateUserRequest, CreateUserResponse }
             ,{ user_client, update_user, UpdateUserRequest, UpdateUserResponse }
             ,{ user_client, drop_user, DropUserRequest, DropUserResponse }
@@ -823,6 +887,9 @@ macro_rules! for_all_meta_rpc {
             ,{ scale_client, resume, ResumeRequest, ResumeResponse }
             ,{ scale_client, get_cluster_info, GetClusterInfoRequest, GetClusterInfoResponse }
             ,{ scale_client, reschedule, RescheduleRequest, RescheduleResponse }
+            ,{ backup_client, backup_meta, BackupMetaRequest, BackupMetaResponse }
+            ,{ backup_client, list_meta_snapshot_metadata, ListMetaSnapshotMetadataRequest, ListMetaSnapshotMetadataResponse }
+            ,{ backup_client, delete_meta_snapshot, DeleteMetaSnapshotRequest, DeleteMetaSnapshotResponse }
             ,{ notification_client, subscribe, SubscribeRequest, Streaming<SubscribeResponse> }
         }
     };
@@ -16,8 +16,10 @@ use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use num_traits::FromPrimitive;
 use piestream_common::array::{ListValue, StructValue};
-use piestream_common::types::{DataType, Datum, Decimal, ScalarImpl};
-use piestream_expr::vector_op::cast::{str_to_date, str_to_time, str_to_timestamp};
+use piestream_common::types::{DataType, Datum, Decimal, IntervalUnit, ScalarImpl};
+use piestream_expr::vector_op::cast::{
+    str_parse, str_to_date, str_to_time, str_to_timestamp, str_to_timestampz,
+};
 use serde_json::Value;
 #[cfg(any(
     target_feature = "sse4.2",
@@ -55,6 +57,17 @@ macro_rules! ensure_str {
     };
 }
 
+/// `timestamptz` can be either a string with an optional UTC offset, or an integer standing for
+/// microseconds since the Unix epoch.
+macro_rules! parse_timestampz_value {
+    ($v:ident) => {
+        match $v.as_i64() {
+            Some(micros) => ScalarImpl::Int64(micros),
+            None => ScalarImpl::Int64(str_to_timestampz(ensure_str!($v, "timestamptz"))?),
+        }
+    };
+}
+
 fn do_parse_json_value(dtype: &DataType, v: &Value) -> Result<ScalarImpl> {
     let v = match dtype {
         DataType::Boolean => v.as_bool().ok_or_else(|| anyhow!("expect bool"))?.into(),
@@ -105,8 +118,8 @@ fn do_parse_json_value(dtype: &DataType, v: &Value) -> Result<ScalarImpl> {
                 return Err(anyhow!(err_msg));
             }
         }
-        DataType::Timestampz => unimplemented!(),
-        DataType::Interval => unimplemented!(),
+        DataType::Timestampz => parse_timestampz_value!(v),
+        DataType::Interval => str_parse::<IntervalUnit>(ensure_str!(v, "interval"))?.into(),
     };
     Ok(v)
 }
@@ -177,8 +190,8 @@ fn do_parse_simd_json_value(dtype: &DataType, v: &BorrowedValue<'_>) -> Result<S
                 return Err(anyhow!(err_msg));
             }
         }
-        DataType::Timestampz => unimplemented!(),
-        DataType::Interval => unimplemented!(),
+        DataType::Timestampz => parse_timestampz_value!(v),
+        DataType::Interval => str_parse::<IntervalUnit>(ensure_str!(v, "interval"))?.into(),
     };
     Ok(v)
 }
@@ -103,13 +103,14 @@ mod tests {
             SourceColumnDesc::simple("date", DataType::Date, 8.into()),
             SourceColumnDesc::simple("timestamp", DataType::Timestamp, 9.into()),
             SourceColumnDesc::simple("decimal", DataType::Decimal, 10.into()),
+            SourceColumnDesc::simple("timestamptz", DataType::Timestampz, 11.into()),
         ];
 
         let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 2);
 
         for payload in [
-            br#"{"i32":1,"bool":true,"i16":1,"i64":12345678,"f32":1.23,"f64":1.2345,"varchar":"varchar","date":"2021-01-01","timestamp":"2021-01-01 16:06:12.269","decimal":12345.67890}"#.as_slice(),
-            br#"{"i32":1,"f32":12345e+10,"f64":12345,"decimal":12345}"#.as_slice(),
+            br#"{"i32":1,"bool":true,"i16":1,"i64":12345678,"f32":1.23,"f64":1.2345,"varchar":"varchar","date":"2021-01-01","timestamp":"2021-01-01 16:06:12.269","decimal":12345.67890,"timestamptz":"2021-01-01 16:06:12.269+08:00"}"#.as_slice(),
+            br#"{"i32":1,"f32":12345e+10,"f64":12345,"decimal":12345,"timestamptz":1609488372269000}"#.as_slice(),
         ] {
             let writer = builder.row_writer();
             parser.parse(payload, writer).unwrap();
@@ -171,6 +172,10 @@ mod tests {
                     Decimal::from_str("12345.67890").unwrap()
                 )))
             );
+            assert_eq!(
+                row.value_at(10).to_owned_datum(),
+                (Some(ScalarImpl::Int64(1609488372269000)))
+            );
         }
 
         {
@@ -193,6 +198,10 @@ mod tests {
                 row.value_at(9).to_owned_datum(),
                 (Some(ScalarImpl::Decimal(12345.into())))
             );
+            assert_eq!(
+                row.value_at(10).to_owned_datum(),
+                (Some(ScalarImpl::Int64(1609488372269000)))
+            );
         }
     }
 
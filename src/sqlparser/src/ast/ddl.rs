@@ -20,7 +20,9 @@ use core::fmt;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::ast::{display_comma_separated, display_separated, DataType, Expr, Ident, ObjectName};
+use crate::ast::{
+    display_comma_separated, display_separated, DataType, Expr, Ident, ObjectName, SetVariableValue,
+};
 use crate::tokenizer::Token;
 
 /// An `ALTER TABLE` (`Statement::AlterTable`) operation
@@ -75,6 +77,27 @@ pub enum AlterTableOperation {
     ChangeOwner {
         new_owner_name: Ident,
     },
+
+    /// `SET PARALLELISM TO <parallelism>`
+    SetParallelism {
+        parallelism: SetVariableValue,
+    },
+}
+
+/// An `ALTER [MATERIALIZED] VIEW` (`Statement::AlterView`) operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AlterViewOperation {
+    /// `SET PARALLELISM TO <parallelism>`
+    SetParallelism { parallelism: SetVariableValue },
+}
+
+/// An `ALTER SINK` (`Statement::AlterSink`) operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AlterSinkOperation {
+    /// `SET PARALLELISM TO <parallelism>`
+    SetParallelism { parallelism: SetVariableValue },
 }
 
 impl fmt::Display for AlterTableOperation {
@@ -129,6 +152,29 @@ impl fmt::Display for AlterTableOperation {
             AlterTableOperation::ChangeOwner { new_owner_name } => {
                 write!(f, "OWNER TO {}", new_owner_name)
             }
+            AlterTableOperation::SetParallelism { parallelism } => {
+                write!(f, "SET PARALLELISM TO {}", parallelism)
+            }
+        }
+    }
+}
+
+impl fmt::Display for AlterViewOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlterViewOperation::SetParallelism { parallelism } => {
+                write!(f, "SET PARALLELISM TO {}", parallelism)
+            }
+        }
+    }
+}
+
+impl fmt::Display for AlterSinkOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlterSinkOperation::SetParallelism { parallelism } => {
+                write!(f, "SET PARALLELISM TO {}", parallelism)
+            }
         }
     }
 }
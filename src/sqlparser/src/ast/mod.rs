@@ -32,12 +32,12 @@ use serde::{Deserialize, Serialize};
 
 pub use self::data_type::{DataType, StructField};
 pub use self::ddl::{
-    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef,
-    ReferentialAction, TableConstraint,
+    AlterColumnOperation, AlterSinkOperation, AlterTableOperation, AlterViewOperation, ColumnDef,
+    ColumnOption, ColumnOptionDef, ReferentialAction, TableConstraint,
 };
 pub use self::operator::{BinaryOperator, UnaryOperator};
 pub use self::query::{
-    Cte, Fetch, Join, JoinConstraint, JoinOperator, LateralView, OrderByExpr, Query, Select,
+    AsOf, Cte, Fetch, Join, JoinConstraint, JoinOperator, LateralView, OrderByExpr, Query, Select,
     SelectItem, SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, Top, Values, With,
 };
 pub use self::statement::*;
@@ -262,6 +262,11 @@ pub enum Expr {
         field: DateTimeField,
         expr: Box<Expr>,
     },
+    /// `<expr> AT TIME ZONE '<time_zone>'`
+    AtTimeZone {
+        timestamp: Box<Expr>,
+        time_zone: String,
+    },
     /// SUBSTRING(<expr> [FROM <expr>] [FOR <expr>])
     Substring {
         expr: Box<Expr>,
@@ -394,6 +399,15 @@ impl fmt::Display for Expr {
             Expr::Cast { expr, data_type } => write!(f, "CAST({} AS {})", expr, data_type),
             Expr::TryCast { expr, data_type } => write!(f, "TRY_CAST({} AS {})", expr, data_type),
             Expr::Extract { field, expr } => write!(f, "EXTRACT({} FROM {})", field, expr),
+            Expr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => write!(
+                f,
+                "{} AT TIME ZONE '{}'",
+                fmt_expr_with_paren(timestamp),
+                value::escape_single_quote_string(time_zone)
+            ),
             Expr::Collate { expr, collation } => write!(f, "{} COLLATE {}", expr, collation),
             Expr::Nested(ast) => write!(f, "({})", ast),
             Expr::Value(v) => write!(f, "{}", v),
@@ -698,6 +712,7 @@ pub enum ShowObject {
     Sink { schema: Option<Ident> },
     MaterializedSource { schema: Option<Ident> },
     Columns { table: ObjectName },
+    Jobs,
 }
 
 impl fmt::Display for ShowObject {
@@ -725,6 +740,7 @@ impl fmt::Display for ShowObject {
             }
             ShowObject::Sink { schema } => write!(f, "SINKS{}", fmt_schema(schema)),
             ShowObject::Columns { table } => write!(f, "COLUMNS FROM {}", table),
+            ShowObject::Jobs => f.write_str("JOBS"),
         }
     }
 }
@@ -895,6 +911,19 @@ pub enum Statement {
         name: ObjectName,
         operation: AlterTableOperation,
     },
+    /// ALTER [MATERIALIZED] VIEW
+    AlterView {
+        /// View name
+        name: ObjectName,
+        materialized: bool,
+        operation: AlterViewOperation,
+    },
+    /// ALTER SINK
+    AlterSink {
+        /// Sink name
+        name: ObjectName,
+        operation: AlterSinkOperation,
+    },
     /// DESCRIBE TABLE OR SOURCE
     Describe {
         /// Table or Source name
@@ -1002,6 +1031,10 @@ pub enum Statement {
     ///
     /// Note: piestream specific statement.
     Flush,
+    /// CANCEL JOB the creating streaming jobs.
+    ///
+    /// Note: piestream specific statement.
+    CancelJobs(Vec<u32>),
 }
 
 impl fmt::Display for Statement {
@@ -1223,6 +1256,22 @@ impl fmt::Display for Statement {
             Statement::AlterTable { name, operation } => {
                 write!(f, "ALTER TABLE {} {}", name, operation)
             }
+            Statement::AlterView {
+                name,
+                materialized,
+                operation,
+            } => {
+                write!(
+                    f,
+                    "ALTER {}VIEW {} {}",
+                    if *materialized { "MATERIALIZED " } else { "" },
+                    name,
+                    operation
+                )
+            }
+            Statement::AlterSink { name, operation } => {
+                write!(f, "ALTER SINK {} {}", name, operation)
+            }
             Statement::Drop(stmt) => write!(f, "DROP {}", stmt),
             Statement::SetVariable {
                 local,
@@ -1380,6 +1429,9 @@ impl fmt::Display for Statement {
             Statement::Flush => {
                 write!(f, "FLUSH")
             }
+            Statement::CancelJobs(job_ids) => {
+                write!(f, "CANCEL JOBS {}", display_comma_separated(job_ids))
+            }
             Statement::BEGIN { modes } => {
                 write!(f, "BEGIN")?;
                 if !modes.is_empty() {
@@ -1652,6 +1704,8 @@ pub struct Function {
     // aggregate functions may contain order_by_clause
     pub order_by: Vec<OrderByExpr>,
     pub filter: Option<Box<Expr>>,
+    // ordered-set aggregate functions may specify `WITHIN GROUP (ORDER BY x)`
+    pub within_group: Option<Box<OrderByExpr>>,
 }
 
 impl Function {
@@ -1663,6 +1717,7 @@ impl Function {
             distinct: false,
             order_by: vec![],
             filter: None,
+            within_group: None,
         }
     }
 }
@@ -1682,6 +1737,9 @@ impl fmt::Display for Function {
             },
             display_comma_separated(&self.order_by),
         )?;
+        if let Some(within_group) = &self.within_group {
+            write!(f, " WITHIN GROUP (ORDER BY {})", within_group)?;
+        }
         if let Some(o) = &self.over {
             write!(f, " OVER ({})", o)?;
         }
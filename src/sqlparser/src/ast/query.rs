@@ -289,6 +289,28 @@ impl fmt::Display for TableWithJoins {
     }
 }
 
+/// The snapshot of a table to read in a batch query, i.e. `AS OF TIMESTAMP '<timestamp>'` or
+/// `AS OF EPOCH <epoch>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AsOf {
+    Timestamp(String),
+    Epoch(u64),
+}
+
+impl fmt::Display for AsOf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsOf::Timestamp(ts) => write!(
+                f,
+                "AS OF TIMESTAMP '{}'",
+                super::value::escape_single_quote_string(ts)
+            ),
+            AsOf::Epoch(epoch) => write!(f, "AS OF EPOCH {}", epoch),
+        }
+    }
+}
+
 /// A table name or a parenthesized subquery with an optional alias
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -296,6 +318,7 @@ pub enum TableFactor {
     Table {
         name: ObjectName,
         alias: Option<TableAlias>,
+        as_of: Option<AsOf>,
     },
     Derived {
         lateral: bool,
@@ -320,8 +343,11 @@ pub enum TableFactor {
 impl fmt::Display for TableFactor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TableFactor::Table { name, alias } => {
+            TableFactor::Table { name, alias, as_of } => {
                 write!(f, "{}", name)?;
+                if let Some(as_of) = as_of {
+                    write!(f, " {}", as_of)?;
+                }
                 if let Some(alias) = alias {
                     write!(f, " AS {}", alias)?;
                 }
@@ -119,6 +119,8 @@ pub enum DateTimeField {
     Hour,
     Minute,
     Second,
+    /// Other fields accepted by `EXTRACT`, e.g. `EPOCH` or `DOW`, in upper case.
+    Literal(String),
 }
 
 impl fmt::Display for DateTimeField {
@@ -130,6 +132,7 @@ impl fmt::Display for DateTimeField {
             DateTimeField::Hour => "HOUR",
             DateTimeField::Minute => "MINUTE",
             DateTimeField::Second => "SECOND",
+            DateTimeField::Literal(s) => s,
         })
     }
 }
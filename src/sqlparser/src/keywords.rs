@@ -107,6 +107,7 @@ define_keywords!(
     CACHE,
     CALL,
     CALLED,
+    CANCEL,
     CARDINALITY,
     CASCADE,
     CASCADED,
@@ -200,6 +201,7 @@ define_keywords!(
     END_EXEC = "END-EXEC",
     END_FRAME,
     END_PARTITION,
+    EPOCH,
     EQUALS,
     ERROR,
     ESCAPE,
@@ -265,6 +267,8 @@ define_keywords!(
     IS,
     ISNULL,
     ISOLATION,
+    JOB,
+    JOBS,
     JOIN,
     JSON,
     KEY,
@@ -340,6 +344,7 @@ define_keywords!(
     OVERLAPS,
     OVERLAY,
     OWNER,
+    PARALLELISM,
     PARAMETER,
     PARQUET,
     PARTITION,
@@ -119,6 +119,8 @@ pub struct Parser {
 }
 
 impl Parser {
+    /// Like `PostgreSQL`, `AT TIME ZONE` binds tighter than multiplicative operators.
+    const AT_TIME_ZONE_PREC: u8 = 45;
     const BETWEEN_PREC: u8 = 20;
     const PLUS_MINUS_PREC: u8 = 30;
     const UNARY_NOT_PREC: u8 = 15;
@@ -201,6 +203,7 @@ impl Parser {
                 Keyword::PREPARE => Ok(self.parse_prepare()?),
                 Keyword::COMMENT => Ok(self.parse_comment()?),
                 Keyword::FLUSH => Ok(Statement::Flush),
+                Keyword::CANCEL => Ok(self.parse_cancel_job()?),
                 _ => self.expected("an SQL statement", Token::Word(w)),
             },
             Token::LParen => {
@@ -217,6 +220,19 @@ impl Parser {
         Ok(Statement::Truncate { table_name })
     }
 
+    /// Parse `CANCEL JOB[S] id [, ...]` after `CANCEL`.
+    pub fn parse_cancel_job(&mut self) -> Result<Statement, ParserError> {
+        if !self.parse_keyword(Keyword::JOB) && !self.parse_keyword(Keyword::JOBS) {
+            return self.expected("JOB or JOBS after CANCEL", self.peek_token());
+        }
+        let job_ids = self.parse_comma_separated(|p| {
+            let id = p.parse_literal_uint()?;
+            u32::try_from(id)
+                .map_err(|_| ParserError::ParserError(format!("invalid job id {}", id)))
+        })?;
+        Ok(Statement::CancelJobs(job_ids))
+    }
+
     pub fn parse_analyze(&mut self) -> Result<Statement, ParserError> {
         let table_name = self.parse_object_name()?;
 
@@ -565,6 +581,15 @@ impl Parser {
         self.expect_token(&Token::LParen)?;
         let distinct = self.parse_all_or_distinct()?;
         let (args, order_by) = self.parse_optional_args()?;
+        let within_group = if self.parse_keywords(&[Keyword::WITHIN, Keyword::GROUP]) {
+            self.expect_token(&Token::LParen)?;
+            self.expect_keywords(&[Keyword::ORDER, Keyword::BY])?;
+            let order_by_expr = self.parse_order_by_expr()?;
+            self.expect_token(&Token::RParen)?;
+            Some(Box::new(order_by_expr))
+        } else {
+            None
+        };
         let over = if self.parse_keyword(Keyword::OVER) {
             // TBD: support window names (`OVER mywin`) in place of inline specification
             self.expect_token(&Token::LParen)?;
@@ -613,6 +638,7 @@ impl Parser {
             distinct,
             order_by,
             filter,
+            within_group,
         }))
     }
 
@@ -788,7 +814,7 @@ impl Parser {
 
     pub fn parse_extract_expr(&mut self) -> Result<Expr, ParserError> {
         self.expect_token(&Token::LParen)?;
-        let field = self.parse_date_time_field()?;
+        let field = self.parse_extract_field()?;
         self.expect_keyword(Keyword::FROM)?;
         let expr = self.parse_expr()?;
         self.expect_token(&Token::RParen)?;
@@ -903,6 +929,27 @@ impl Parser {
         }
     }
 
+    /// Parse the field of `EXTRACT`. Besides the standard date/time fields, `PostgreSQL` accepts
+    /// other identifiers or strings here, e.g. `EPOCH`, `DOW` or `'millisecond'`.
+    pub fn parse_extract_field(&mut self) -> Result<DateTimeField, ParserError> {
+        match self.next_token() {
+            Token::Word(w) => match w.keyword {
+                Keyword::YEAR
+                | Keyword::MONTH
+                | Keyword::DAY
+                | Keyword::HOUR
+                | Keyword::MINUTE
+                | Keyword::SECOND => {
+                    self.prev_token();
+                    self.parse_date_time_field()
+                }
+                _ => Ok(DateTimeField::Literal(w.value.to_uppercase())),
+            },
+            Token::SingleQuotedString(s) => Ok(DateTimeField::Literal(s.to_uppercase())),
+            unexpected => self.expected("date/time field", unexpected),
+        }
+    }
+
     /// Parse an INTERVAL literal.
     ///
     /// Some syntactically valid intervals:
@@ -1015,11 +1062,17 @@ impl Parser {
                 Keyword::OR => Some(BinaryOperator::Or),
                 Keyword::LIKE => Some(BinaryOperator::Like),
                 Keyword::ILIKE => Some(BinaryOperator::ILike),
+                Keyword::SIMILAR => {
+                    self.expect_keyword(Keyword::TO)?;
+                    Some(BinaryOperator::SimilarTo)
+                }
                 Keyword::NOT => {
                     if self.parse_keyword(Keyword::LIKE) {
                         Some(BinaryOperator::NotLike)
                     } else if self.parse_keyword(Keyword::ILIKE) {
                         Some(BinaryOperator::NotILike)
+                    } else if self.parse_keywords(&[Keyword::SIMILAR, Keyword::TO]) {
+                        Some(BinaryOperator::NotSimilarTo)
                     } else {
                         None
                     }
@@ -1065,6 +1118,18 @@ impl Parser {
                         )
                     }
                 }
+                Keyword::AT => {
+                    self.expect_keywords(&[Keyword::TIME, Keyword::ZONE])?;
+                    match self.next_token() {
+                        Token::SingleQuotedString(time_zone) => Ok(Expr::AtTimeZone {
+                            timestamp: Box::new(expr),
+                            time_zone,
+                        }),
+                        unexpected => {
+                            self.expected("time zone string after AT TIME ZONE", unexpected)
+                        }
+                    }
+                }
                 Keyword::NOT | Keyword::IN | Keyword::BETWEEN => {
                     self.prev_token();
                     let negated = self.parse_keyword(Keyword::NOT);
@@ -1175,13 +1240,25 @@ impl Parser {
                 Token::Word(w) if w.keyword == Keyword::BETWEEN => Ok(Self::BETWEEN_PREC),
                 Token::Word(w) if w.keyword == Keyword::LIKE => Ok(Self::BETWEEN_PREC),
                 Token::Word(w) if w.keyword == Keyword::ILIKE => Ok(Self::BETWEEN_PREC),
+                Token::Word(w) if w.keyword == Keyword::SIMILAR => Ok(Self::BETWEEN_PREC),
                 _ => Ok(0),
             },
             Token::Word(w) if w.keyword == Keyword::IS => Ok(17),
+            Token::Word(w) if w.keyword == Keyword::AT => {
+                match (self.peek_nth_token(1), self.peek_nth_token(2)) {
+                    (Token::Word(w1), Token::Word(w2))
+                        if w1.keyword == Keyword::TIME && w2.keyword == Keyword::ZONE =>
+                    {
+                        Ok(Self::AT_TIME_ZONE_PREC)
+                    }
+                    _ => Ok(0),
+                }
+            }
             Token::Word(w) if w.keyword == Keyword::IN => Ok(Self::BETWEEN_PREC),
             Token::Word(w) if w.keyword == Keyword::BETWEEN => Ok(Self::BETWEEN_PREC),
             Token::Word(w) if w.keyword == Keyword::LIKE => Ok(Self::BETWEEN_PREC),
             Token::Word(w) if w.keyword == Keyword::ILIKE => Ok(Self::BETWEEN_PREC),
+            Token::Word(w) if w.keyword == Keyword::SIMILAR => Ok(Self::BETWEEN_PREC),
             Token::Eq
             | Token::Lt
             | Token::LtEq
@@ -1812,8 +1889,59 @@ impl Parser {
             self.parse_alter_table()
         } else if self.parse_keyword(Keyword::USER) {
             self.parse_alter_user()
+        } else if self.parse_keyword(Keyword::VIEW) {
+            self.parse_alter_view(false)
+        } else if self.parse_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW]) {
+            self.parse_alter_view(true)
+        } else if self.parse_keyword(Keyword::SINK) {
+            self.parse_alter_sink()
         } else {
-            self.expected("TABLE or USER after ALTER", self.peek_token())
+            self.expected(
+                "TABLE, USER, [MATERIALIZED] VIEW or SINK after ALTER",
+                self.peek_token(),
+            )
+        }
+    }
+
+    pub fn parse_alter_view(&mut self, materialized: bool) -> Result<Statement, ParserError> {
+        let name = self.parse_object_name()?;
+        let operation = if self.parse_keywords(&[Keyword::SET, Keyword::PARALLELISM]) {
+            AlterViewOperation::SetParallelism {
+                parallelism: self.parse_parallelism()?,
+            }
+        } else {
+            return self.expected("SET PARALLELISM after ALTER VIEW", self.peek_token());
+        };
+        Ok(Statement::AlterView {
+            name,
+            materialized,
+            operation,
+        })
+    }
+
+    pub fn parse_alter_sink(&mut self) -> Result<Statement, ParserError> {
+        let name = self.parse_object_name()?;
+        let operation = if self.parse_keywords(&[Keyword::SET, Keyword::PARALLELISM]) {
+            AlterSinkOperation::SetParallelism {
+                parallelism: self.parse_parallelism()?,
+            }
+        } else {
+            return self.expected("SET PARALLELISM after ALTER SINK", self.peek_token());
+        };
+        Ok(Statement::AlterSink { name, operation })
+    }
+
+    /// Parse the `= <parallelism>` or `TO <parallelism>` part of `SET PARALLELISM`, where the
+    /// parallelism is either a number or an identifier like `auto`.
+    fn parse_parallelism(&mut self) -> Result<SetVariableValue, ParserError> {
+        if !(self.consume_token(&Token::Eq) || self.parse_keyword(Keyword::TO)) {
+            return self.expected("equals sign or TO", self.peek_token());
+        }
+        let token = self.peek_token();
+        match (self.parse_value(), token) {
+            (Ok(value), _) => Ok(SetVariableValue::Literal(value)),
+            (Err(_), Token::Word(ident)) => Ok(SetVariableValue::Ident(ident.to_ident())),
+            (Err(_), unexpected) => self.expected("parallelism", unexpected),
         }
     }
 
@@ -1899,6 +2027,10 @@ impl Parser {
                 );
             };
             AlterTableOperation::AlterColumn { column_name, op }
+        } else if self.parse_keywords(&[Keyword::SET, Keyword::PARALLELISM]) {
+            AlterTableOperation::SetParallelism {
+                parallelism: self.parse_parallelism()?,
+            }
         } else {
             return self.expected("ADD, RENAME or DROP after ALTER TABLE", self.peek_token());
         };
@@ -2744,6 +2876,9 @@ impl Parser {
                             .expected("VIEWS or SOURCES after MATERIALIZED", self.peek_token());
                     }
                 }
+                Keyword::JOBS => {
+                    return Ok(Statement::ShowObjects(ShowObject::Jobs));
+                }
                 Keyword::COLUMNS => {
                     if self.parse_keyword(Keyword::FROM) {
                         return Ok(Statement::ShowObjects(ShowObject::Columns {
@@ -2917,12 +3052,25 @@ impl Parser {
                 let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
                 Ok(TableFactor::TableFunction { name, alias, args })
             } else {
+                let as_of = self.parse_optional_as_of()?;
                 let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
-                Ok(TableFactor::Table { name, alias })
+                Ok(TableFactor::Table { name, alias, as_of })
             }
         }
     }
 
+    /// Parse `AS OF TIMESTAMP '<timestamp>'` or `AS OF EPOCH <epoch>` after a table name.
+    pub fn parse_optional_as_of(&mut self) -> Result<Option<AsOf>, ParserError> {
+        if !self.parse_keywords(&[Keyword::AS, Keyword::OF]) {
+            return Ok(None);
+        }
+        match self.parse_one_of_keywords(&[Keyword::TIMESTAMP, Keyword::EPOCH]) {
+            Some(Keyword::TIMESTAMP) => Ok(Some(AsOf::Timestamp(self.parse_literal_string()?))),
+            Some(Keyword::EPOCH) => Ok(Some(AsOf::Epoch(self.parse_literal_uint()?))),
+            _ => self.expected("TIMESTAMP or EPOCH after AS OF", self.peek_token()),
+        }
+    }
+
     pub fn parse_derived_table_factor(
         &mut self,
         lateral: IsLateral,
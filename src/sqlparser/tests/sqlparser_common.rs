@@ -153,6 +153,7 @@ fn parse_update_with_table_alias() {
                             name: Ident::new("u"),
                             columns: vec![]
                         }),
+                        as_of: None,
                     },
                     joins: vec![]
                 },
@@ -376,7 +377,8 @@ fn parse_select_count_wildcard() {
             over: None,
             distinct: false,
             order_by: vec![],
-            filter: None
+            filter: None,
+            within_group: None
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -396,7 +398,8 @@ fn parse_select_count_distinct() {
             over: None,
             distinct: true,
             order_by: vec![],
-            filter: None
+            filter: None,
+            within_group: None
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -759,6 +762,51 @@ fn parse_ilike() {
     chk(true);
 }
 
+#[test]
+fn parse_similar_to() {
+    fn chk(negated: bool) {
+        let sql = &format!(
+            "SELECT * FROM customers WHERE name {}SIMILAR TO '%(a|b)%'",
+            if negated { "NOT " } else { "" }
+        );
+        let select = verified_only_select(sql);
+        assert_eq!(
+            Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("name"))),
+                op: if negated {
+                    BinaryOperator::NotSimilarTo
+                } else {
+                    BinaryOperator::SimilarTo
+                },
+                right: Box::new(Expr::Value(Value::SingleQuotedString(
+                    "%(a|b)%".to_string()
+                ))),
+            },
+            select.selection.unwrap()
+        );
+
+        let sql = &format!(
+            "SELECT * FROM customers WHERE name {}SIMILAR TO '%a' IS NULL",
+            if negated { "NOT " } else { "" }
+        );
+        let select = verified_only_select(sql);
+        assert_eq!(
+            Expr::IsNull(Box::new(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("name"))),
+                op: if negated {
+                    BinaryOperator::NotSimilarTo
+                } else {
+                    BinaryOperator::SimilarTo
+                },
+                right: Box::new(Expr::Value(Value::SingleQuotedString("%a".to_string()))),
+            })),
+            select.selection.unwrap()
+        );
+    }
+    chk(false);
+    chk(true);
+}
+
 #[test]
 fn parse_in_list() {
     fn chk(negated: bool) {
@@ -1093,7 +1141,8 @@ fn parse_select_having() {
                 over: None,
                 distinct: false,
                 order_by: vec![],
-                filter: None
+                filter: None,
+                within_group: None
             })),
             op: BinaryOperator::Gt,
             right: Box::new(Expr::Value(number("1"))),
@@ -1203,9 +1252,48 @@ fn parse_extract() {
     verified_stmt("SELECT EXTRACT(MINUTE FROM d)");
     verified_stmt("SELECT EXTRACT(SECOND FROM d)");
 
-    let res = parse_sql_statements("SELECT EXTRACT(MILLISECOND FROM d)");
+    one_statement_parses_to(
+        "SELECT EXTRACT(millisecond FROM d)",
+        "SELECT EXTRACT(MILLISECOND FROM d)",
+    );
+    one_statement_parses_to(
+        "SELECT EXTRACT('epoch' FROM d)",
+        "SELECT EXTRACT(EPOCH FROM d)",
+    );
+
+    let res = parse_sql_statements("SELECT EXTRACT(1 FROM d)");
+    assert_eq!(
+        ParserError::ParserError("Expected date/time field, found: 1".to_string()),
+        res.unwrap_err()
+    );
+}
+
+#[test]
+fn parse_at_time_zone() {
+    let sql = "SELECT ts AT TIME ZONE '+08:00'";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        &Expr::AtTimeZone {
+            timestamp: Box::new(Expr::Identifier(Ident::new("ts"))),
+            time_zone: "+08:00".to_string(),
+        },
+        expr_from_projection(only(&select.projection)),
+    );
+
+    one_statement_parses_to(
+        "SELECT ts + i AT TIME ZONE 'UTC'",
+        "SELECT ts + i AT TIME ZONE 'UTC'",
+    );
+    one_statement_parses_to(
+        "SELECT (ts + i) AT TIME ZONE 'UTC'",
+        "SELECT (ts + i) AT TIME ZONE 'UTC'",
+    );
+
+    let res = parse_sql_statements("SELECT ts AT TIME ZONE utc");
     assert_eq!(
-        ParserError::ParserError("Expected date/time field, found: MILLISECOND".to_string()),
+        ParserError::ParserError(
+            "Expected time zone string after AT TIME ZONE, found: utc".to_string()
+        ),
         res.unwrap_err()
     );
 }
@@ -1854,6 +1942,7 @@ fn parse_named_argument_function() {
             distinct: false,
             order_by: vec![],
             filter: None,
+            within_group: None,
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -1889,6 +1978,7 @@ fn parse_window_functions() {
             distinct: false,
             order_by: vec![],
             filter: None,
+            within_group: None,
         }),
         expr_from_projection(&select.projection[0])
     );
@@ -1927,6 +2017,7 @@ fn parse_aggregate_with_order_by() {
                 }
             ],
             filter: None,
+            within_group: None,
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -1956,6 +2047,31 @@ fn parse_aggregate_with_filter() {
                     Expr::Identifier(Ident::new("a"))
                 )))))
             })),
+            within_group: None,
+        }),
+        expr_from_projection(only(&select.projection)),
+    );
+}
+
+#[test]
+fn parse_aggregate_with_within_group() {
+    let sql = "SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY a DESC) FROM foo";
+    let select = verified_only_select(sql);
+    assert_eq!(
+        &Expr::Function(Function {
+            name: ObjectName(vec![Ident::new("percentile_cont")]),
+            args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(
+                number("0.5")
+            ))),],
+            over: None,
+            distinct: false,
+            order_by: vec![],
+            filter: None,
+            within_group: Some(Box::new(OrderByExpr {
+                expr: Expr::Identifier(Ident::new("a")),
+                asc: Some(false),
+                nulls_first: None,
+            })),
         }),
         expr_from_projection(only(&select.projection)),
     );
@@ -2171,7 +2287,7 @@ fn parse_delimited_identifiers() {
     );
     // check FROM
     match only(select.from).relation {
-        TableFactor::Table { name, alias } => {
+        TableFactor::Table { name, alias, .. } => {
             assert_eq!(vec![Ident::with_quote('"', "a table")], name.0);
             assert_eq!(Ident::with_quote('"', "alias"), alias.unwrap().name);
         }
@@ -2194,6 +2310,7 @@ fn parse_delimited_identifiers() {
             distinct: false,
             order_by: vec![],
             filter: None,
+            within_group: None,
         }),
         expr_from_projection(&select.projection[1]),
     );
@@ -2297,6 +2414,7 @@ fn parse_implicit_join() {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t1".into()]),
                     alias: None,
+                    as_of: None,
                 },
                 joins: vec![],
             },
@@ -2304,6 +2422,7 @@ fn parse_implicit_join() {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t2".into()]),
                     alias: None,
+                    as_of: None,
                 },
                 joins: vec![],
             }
@@ -2319,11 +2438,13 @@ fn parse_implicit_join() {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t1a".into()]),
                     alias: None,
+                    as_of: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t1b".into()]),
                         alias: None,
+                        as_of: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2332,11 +2453,13 @@ fn parse_implicit_join() {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t2a".into()]),
                     alias: None,
+                    as_of: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t2b".into()]),
                         alias: None,
+                        as_of: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2355,6 +2478,7 @@ fn parse_cross_join() {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new("t2")]),
                 alias: None,
+                as_of: None,
             },
             join_operator: JoinOperator::CrossJoin
         },
@@ -2373,6 +2497,7 @@ fn parse_joins_on() {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new(relation.into())]),
                 alias,
+                as_of: None,
             },
             join_operator: f(JoinConstraint::On(Expr::BinaryOp {
                 left: Box::new(Expr::Identifier("c1".into())),
@@ -2424,6 +2549,7 @@ fn parse_joins_using() {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new(relation.into())]),
                 alias,
+                as_of: None,
             },
             join_operator: f(JoinConstraint::Using(vec!["c1".into()])),
         }
@@ -2467,6 +2593,7 @@ fn parse_natural_join() {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new("t2")]),
                 alias: None,
+                as_of: None,
             },
             join_operator: f(JoinConstraint::Natural),
         }
@@ -2704,6 +2831,7 @@ fn parse_derived_tables() {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t2".into()]),
                     alias: None,
+                    as_of: None,
                 },
                 join_operator: JoinOperator::Inner(JoinConstraint::Natural),
             }],
@@ -36,8 +36,9 @@ use piestream_storage::hummock::sstable::SstableIteratorReadOptions;
 use piestream_storage::hummock::sstable_store::SstableStoreRef;
 use piestream_storage::hummock::value::HummockValue;
 use piestream_storage::hummock::{
-    CachePolicy, CompactorSstableStore, CompressionAlgorithm, MemoryLimiter, SstableBuilder,
-    SstableBuilderOptions, SstableIterator, SstableStore, SstableWriterOptions, TieredCache,
+    CachePolicy, CompactorSstableStore, CompressionAlgorithm, DeleteRangeAggregator, MemoryLimiter,
+    SstableBuilder, SstableBuilderOptions, SstableIterator, SstableStore, SstableWriterOptions,
+    TieredCache,
 };
 use piestream_storage::monitor::{StateStoreMetrics, StoreLocalStatistic};
 
@@ -51,6 +52,7 @@ pub fn mock_sstable_store() -> SstableStoreRef {
         64 << 20,
         128 << 20,
         TieredCache::none(),
+        TieredCache::none(),
     ))
 }
 
@@ -174,6 +176,7 @@ async fn compact<I: HummockIterator<Direction = Forward>>(iter: I, sstable_store
         cache_policy: CachePolicy::Disable,
         gc_delete_keys: false,
         watermark: 0,
+        blob_value_thresholds: Default::default(),
     };
     Compactor::compact_and_build_sst(
         &mut builder,
@@ -181,6 +184,7 @@ async fn compact<I: HummockIterator<Direction = Forward>>(iter: I, sstable_store
         Arc::new(StateStoreMetrics::unused()),
         iter,
         DummyCompactionFilter,
+        Arc::new(DeleteRangeAggregator::default()),
     )
     .await
     .unwrap();
@@ -130,6 +130,7 @@ fn bench_builder(
         64 << 20,
         128 << 20,
         TieredCache::none(),
+        TieredCache::none(),
     ));
 
     let mut group = c.benchmark_group("bench_multi_builder");
@@ -107,6 +107,8 @@ pub trait HummockVersionExt {
     fn level_iter<F: FnMut(&Level) -> bool>(&self, compaction_group_id: CompactionGroupId, f: F);
 
     fn get_sst_ids(&self) -> Vec<u64>;
+    /// Gets the ids of all blob files referenced by the SSTs.
+    fn get_blob_ids(&self) -> Vec<u64>;
     fn apply_version_delta(&mut self, version_delta: &HummockVersionDelta);
 }
 
@@ -142,6 +144,14 @@ impl HummockVersionExt for HummockVersion {
             .collect_vec()
     }
 
+    fn get_blob_ids(&self) -> Vec<u64> {
+        self.get_combined_levels()
+            .iter()
+            .flat_map(|level| level.table_infos.iter())
+            .flat_map(|table_info| table_info.blob_ids.iter().cloned())
+            .collect_vec()
+    }
+
     fn iter_tables<F: FnMut(&SstableInfo)>(
         &self,
         compaction_group_id: CompactionGroupId,
@@ -476,6 +486,7 @@ fn level_insert_ssts(operand: &mut Level, insert_table_infos: Vec<SstableInfo>)
 pub trait HummockVersionDeltaExt {
     fn get_removed_sst_ids(&self) -> Vec<HummockSstableId>;
     fn get_inserted_sst_ids(&self) -> Vec<HummockSstableId>;
+    fn get_inserted_blob_ids(&self) -> Vec<HummockSstableId>;
 }
 
 impl HummockVersionDeltaExt for HummockVersionDelta {
@@ -506,6 +517,20 @@ impl HummockVersionDeltaExt for HummockVersionDelta {
         }
         ret
     }
+
+    fn get_inserted_blob_ids(&self) -> Vec<HummockSstableId> {
+        let mut ret = vec![];
+        for level_deltas in self.level_deltas.values() {
+            for level_delta in &level_deltas.level_deltas {
+                if let DeltaType::IntraLevel(intra_level) = level_delta.get_delta_type().unwrap() {
+                    for sst in &intra_level.inserted_table_infos {
+                        ret.extend(sst.blob_ids.iter().cloned());
+                    }
+                }
+            }
+        }
+        ret
+    }
 }
 
 #[cfg(test)]
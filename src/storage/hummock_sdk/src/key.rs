@@ -17,6 +17,7 @@ use std::ops::{Bound, RangeBounds};
 use std::{ptr, u64};
 
 use bytes::{Buf, BufMut, BytesMut};
+use piestream_common::types::VirtualNode;
 
 use super::version_cmp::VersionedComparator;
 use crate::HummockEpoch;
@@ -69,6 +70,14 @@ pub fn get_epoch(full_key: &[u8]) -> HummockEpoch {
     HummockEpoch::MAX - HummockEpoch::from_be(epoch)
 }
 
+/// Returns whether `right`, the right bound of a key range, is exclusive. A right bound carrying
+/// `HummockEpoch::MAX` marks the end of a range tombstone or a split, as no key is ever written
+/// with that epoch.
+#[inline]
+pub fn is_exclusive_right_bound(right: &[u8]) -> bool {
+    right.len() >= EPOCH_LEN && get_epoch(right) == HummockEpoch::MAX
+}
+
 /// Extract user key without epoch part
 pub fn user_key(full_key: &[u8]) -> &[u8] {
     split_key_epoch(full_key).0
@@ -234,6 +243,14 @@ pub fn table_prefix(table_id: u32) -> Vec<u8> {
     buf.to_vec()
 }
 
+/// Returns the smallest full key of `vnode` in the table. Keys of different vnodes never
+/// interleave, so these keys can be used as boundaries to split a key range.
+pub fn table_vnode_start_key(table_id: u32, vnode: VirtualNode) -> Vec<u8> {
+    let mut user_key = table_prefix(table_id);
+    user_key.put_u8(vnode);
+    key_with_epoch(user_key, HummockEpoch::MAX)
+}
+
 /// [`FullKey`] can be created on either a `Vec<u8>` or a `&[u8]`.
 ///
 /// Its format is (`user_key`, `u64::MAX - epoch`).
@@ -306,4 +323,20 @@ mod tests {
         assert_eq!(prev_key(b"T"), b"S");
         assert_eq!(prev_key(b""), b"");
     }
+
+    #[test]
+    fn test_table_vnode_start_key() {
+        let key = table_vnode_start_key(1, 2);
+        assert_eq!(user_key(&key), b"t\x00\x00\x00\x01\x02");
+        let key_in_vnode = key_with_epoch(b"t\x00\x00\x00\x01\x02abc".to_vec(), 233);
+        let key_in_prev_vnode = key_with_epoch(b"t\x00\x00\x00\x01\x01\xff".to_vec(), 0);
+        assert_eq!(
+            VersionedComparator::compare_key(&key, &key_in_vnode),
+            std::cmp::Ordering::Less
+        );
+        assert_eq!(
+            VersionedComparator::compare_key(&key_in_prev_vnode, &key),
+            std::cmp::Ordering::Less
+        );
+    }
 }
@@ -17,6 +17,7 @@ use std::cmp;
 use bytes::Bytes;
 
 use super::version_cmp::VersionedComparator;
+use crate::key::is_exclusive_right_bound;
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct KeyRange {
@@ -46,6 +47,15 @@ impl KeyRange {
     }
 }
 
+/// Returns whether a key range ending at `right` reaches a key range starting at `left`.
+pub fn right_reaches_left(right: &[u8], left: &[u8]) -> bool {
+    match VersionedComparator::compare_key(right, left) {
+        cmp::Ordering::Greater => true,
+        cmp::Ordering::Equal => !is_exclusive_right_bound(right),
+        cmp::Ordering::Less => false,
+    }
+}
+
 pub trait KeyRangeCommon {
     fn full_key_overlap(&self, other: &Self) -> bool;
     fn full_key_extend(&mut self, other: &Self);
@@ -58,10 +68,8 @@ macro_rules! impl_key_range_common {
             fn full_key_overlap(&self, other: &Self) -> bool {
                 self.inf
                     || other.inf
-                    || (VersionedComparator::compare_key(&self.right, &other.left)
-                        != cmp::Ordering::Less
-                        && VersionedComparator::compare_key(&other.right, &self.left)
-                            != cmp::Ordering::Less)
+                    || ($crate::key_range::right_reaches_left(&self.right, &other.left)
+                        && $crate::key_range::right_reaches_left(&other.right, &self.left))
             }
 
             fn full_key_extend(&mut self, other: &Self) {
@@ -27,7 +27,7 @@ use std::ops::Deref;
 use piestream_pb::hummock::SstableInfo;
 pub use version_cmp::*;
 
-use crate::key::user_key;
+use crate::key::{is_exclusive_right_bound, user_key};
 
 pub mod compact;
 pub mod compaction_group;
@@ -113,11 +113,12 @@ impl SstIdRange {
 pub fn can_concat(ssts: &[impl Deref<Target = SstableInfo>]) -> bool {
     let len = ssts.len();
     for i in 0..len - 1 {
-        if user_key(&ssts[i].get_key_range().as_ref().unwrap().right).cmp(user_key(
-            &ssts[i + 1].get_key_range().as_ref().unwrap().left,
-        )) != Ordering::Less
-        {
-            return false;
+        let right = &ssts[i].get_key_range().as_ref().unwrap().right;
+        let left = &ssts[i + 1].get_key_range().as_ref().unwrap().left;
+        match user_key(right).cmp(user_key(left)) {
+            Ordering::Less => {}
+            Ordering::Equal if is_exclusive_right_bound(right) => {}
+            _ => return false,
         }
     }
     true
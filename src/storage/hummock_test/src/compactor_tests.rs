@@ -96,6 +96,7 @@ mod tests {
             storage
                 .ingest_batch(
                     vec![(key.clone(), StorageValue::new_put(Bytes::from(new_val)))],
+                    vec![],
                     WriteOptions {
                         epoch,
                         table_id: Default::default(),
@@ -195,6 +196,7 @@ mod tests {
             1,
             TableOption {
                 retention_seconds: 64,
+                ..Default::default()
             },
         )]);
         compact_task.current_epoch_time = 0;
@@ -771,6 +773,7 @@ mod tests {
             existing_table_id,
             TableOption {
                 retention_seconds: retention_seconds_expire_second,
+                ..Default::default()
             },
         )]);
         compact_task.current_epoch_time = epoch;
@@ -50,6 +50,7 @@ async fn test_read_version_basic() {
                 epoch,
                 compaction_group_id,
                 kv_pairs,
+                vec![],
                 TableId::from(table_id),
             )
             .await;
@@ -83,6 +84,7 @@ async fn test_read_version_basic() {
                     epoch,
                     compaction_group_id,
                     kv_pairs,
+                    vec![],
                     TableId::from(table_id),
                 )
                 .await;
@@ -136,6 +138,8 @@ async fn test_read_version_basic() {
                 meta_offset: 1,
                 stale_key_count: 1,
                 total_key_count: 1,
+                blob_ids: vec![],
+                table_stats: Default::default(),
             },
             epoch_id_vec_for_clear,
             compaction_group_id,
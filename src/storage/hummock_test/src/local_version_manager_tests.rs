@@ -58,6 +58,7 @@ async fn test_update_pinned_version() {
                 epochs[i],
                 StaticCompactionGroupId::StateDefault.into(),
                 batches[i].clone(),
+                vec![],
                 Default::default(),
             )
             .await
@@ -79,6 +80,7 @@ async fn test_update_pinned_version() {
             epochs[2],
             StaticCompactionGroupId::StateDefault.into(),
             batches[2].clone(),
+            vec![],
             Default::default(),
         )
         .await
@@ -220,6 +222,7 @@ async fn test_update_uncommitted_ssts() {
                 epochs[i],
                 StaticCompactionGroupId::StateDefault.into(),
                 kvs[i].clone(),
+                vec![],
                 Default::default(),
             )
             .await
@@ -410,6 +413,7 @@ async fn test_clear_shared_buffer() {
                 epochs[i],
                 StaticCompactionGroupId::StateDefault.into(),
                 batches[i].clone(),
+                vec![],
                 Default::default(),
             )
             .await
@@ -467,6 +471,7 @@ async fn test_sst_gc_watermark() {
                 epochs[i],
                 StaticCompactionGroupId::StateDefault.into(),
                 batches[i].clone(),
+                vec![],
                 Default::default(),
             )
             .await
@@ -104,6 +104,7 @@ async fn test_snapshot_inner(enable_sync: bool, enable_commit: bool) {
                 (Bytes::from("1"), StorageValue::new_put("test")),
                 (Bytes::from("2"), StorageValue::new_put("test")),
             ],
+            vec![],
             WriteOptions {
                 epoch: epoch1,
                 table_id: Default::default(),
@@ -137,6 +138,7 @@ async fn test_snapshot_inner(enable_sync: bool, enable_commit: bool) {
                 (Bytes::from("3"), StorageValue::new_put("test")),
                 (Bytes::from("4"), StorageValue::new_put("test")),
             ],
+            vec![],
             WriteOptions {
                 epoch: epoch2,
                 table_id: Default::default(),
@@ -171,6 +173,7 @@ async fn test_snapshot_inner(enable_sync: bool, enable_commit: bool) {
                 (Bytes::from("3"), StorageValue::new_delete()),
                 (Bytes::from("4"), StorageValue::new_delete()),
             ],
+            vec![],
             WriteOptions {
                 epoch: epoch3,
                 table_id: Default::default(),
@@ -228,6 +231,7 @@ async fn test_snapshot_range_scan_inner(enable_sync: bool, enable_commit: bool)
                 (Bytes::from("3"), StorageValue::new_put("test")),
                 (Bytes::from("4"), StorageValue::new_put("test")),
             ],
+            vec![],
             WriteOptions {
                 epoch,
                 table_id: Default::default(),
@@ -296,6 +300,7 @@ async fn test_snapshot_backward_range_scan_inner(enable_sync: bool, enable_commi
                 (Bytes::from("5"), StorageValue::new_put("test")),
                 (Bytes::from("6"), StorageValue::new_put("test")),
             ],
+            vec![],
             WriteOptions {
                 epoch,
                 table_id: Default::default(),
@@ -327,6 +332,7 @@ async fn test_snapshot_backward_range_scan_inner(enable_sync: bool, enable_commi
                 (Bytes::from("7"), StorageValue::new_put("test")),
                 (Bytes::from("8"), StorageValue::new_put("test")),
             ],
+            vec![],
             WriteOptions {
                 epoch: epoch + 1,
                 table_id: Default::default(),
@@ -24,10 +24,14 @@ use piestream_pb::hummock::{CompactTask, LevelType};
 use super::task_progress::TaskProgress;
 use crate::hummock::compactor::iterator::ConcatSstableIterator;
 use crate::hummock::compactor::{
-    CompactOutput, CompactionFilter, Compactor, CompactorContext, CompactorSstableStoreRef,
+    build_blob_value_thresholds, CompactOutput, CompactionFilter, Compactor, CompactorContext,
+    CompactorSstableStoreRef,
 };
 use crate::hummock::iterator::{Forward, HummockIterator, UnorderedMergeIteratorInner};
-use crate::hummock::{CachePolicy, CompressionAlgorithm, HummockResult, SstableBuilderOptions};
+use crate::hummock::{
+    CachePolicy, CompressionAlgorithm, DeleteRangeAggregator, HummockResult, SstableBuilderOptions,
+};
+use crate::monitor::StoreLocalStatistic;
 
 #[derive(Clone)]
 pub struct CompactorRunner {
@@ -51,6 +55,7 @@ impl CompactorRunner {
         options.compression_algorithm = match task.compression_algorithm {
             0 => CompressionAlgorithm::None,
             1 => CompressionAlgorithm::Lz4,
+            3 => CompressionAlgorithm::ZstdDictionary,
             _ => CompressionAlgorithm::Zstd,
         };
         let total_file_size = (total_file_size as f64 * 1.2).round() as usize;
@@ -62,14 +67,16 @@ impl CompactorRunner {
             right: Bytes::copy_from_slice(task.splits[split_index].get_right()),
             inf: task.splits[split_index].get_inf(),
         };
-        let compactor = Compactor::new(
+        let mut compactor = Compactor::new(
             context.context.clone(),
             options,
             key_range,
             CachePolicy::NotFill,
             task.gc_delete_keys,
             task.watermark,
+            Arc::new(build_blob_value_thresholds(&task)),
         );
+        compactor.task_config.compaction_group_id = Some(task.compaction_group_id);
 
         Self {
             compactor,
@@ -86,6 +93,7 @@ impl CompactorRunner {
         task_progress: Arc<TaskProgress>,
     ) -> HummockResult<CompactOutput> {
         let iter = self.build_sst_iter()?;
+        let del_agg = self.build_delete_range_aggregator().await?;
         let ssts = self
             .compactor
             .compact_key_range(
@@ -93,11 +101,35 @@ impl CompactorRunner {
                 compaction_filter,
                 filter_key_extractor,
                 Some(task_progress),
+                del_agg,
             )
             .await?;
         Ok((self.split_index, ssts))
     }
 
+    /// Collects the range tombstones of all input ssts. Only the tombstones not newer than the
+    /// watermark may drop keys.
+    async fn build_delete_range_aggregator(&self) -> HummockResult<Arc<DeleteRangeAggregator>> {
+        let mut tombstones = vec![];
+        let mut local_stats = StoreLocalStatistic::default();
+        for table_info in self
+            .compact_task
+            .input_ssts
+            .iter()
+            .flat_map(|level| level.table_infos.iter())
+        {
+            let table = self
+                .sstable_store
+                .sstable(table_info, &mut local_stats)
+                .await?;
+            tombstones.extend_from_slice(&table.value().meta.range_tombstone_list);
+        }
+        Ok(Arc::new(DeleteRangeAggregator::new(
+            tombstones,
+            self.compact_task.watermark,
+        )))
+    }
+
     /// Build the merge iterator based on the given input ssts.
     fn build_sst_iter(&self) -> HummockResult<impl HummockIterator<Direction = Forward>> {
         let mut table_iters = Vec::new();
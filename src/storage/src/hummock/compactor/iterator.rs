@@ -205,7 +205,22 @@ impl ConcatSstableIterator {
     }
 
     /// Resets the iterator, loads the specified SST, and seeks in that SST to `seek_key` if given.
-    async fn seek_idx(&mut self, idx: usize, seek_key: Option<&[u8]>) -> HummockResult<()> {
+    /// If the SST has no KV-pair to read (e.g. it only holds range tombstones), the iterator moves
+    /// on to the first KV-pair of the following SSTs.
+    async fn seek_idx(&mut self, mut idx: usize, mut seek_key: Option<&[u8]>) -> HummockResult<()> {
+        self.sstable_iter.take();
+        while idx < self.tables.len() {
+            self.seek_idx_inner(idx, seek_key).await?;
+            if self.is_valid() {
+                break;
+            }
+            idx += 1;
+            seek_key = None;
+        }
+        Ok(())
+    }
+
+    async fn seek_idx_inner(&mut self, idx: usize, seek_key: Option<&[u8]>) -> HummockResult<()> {
         self.sstable_iter.take();
         let seek_key: Option<&[u8]> = match (seek_key, self.key_range.left.is_empty()) {
             (Some(seek_key), false) => {
@@ -43,7 +43,7 @@ use piestream_hummock_sdk::filter_key_extractor::FilterKeyExtractorImpl;
 use piestream_hummock_sdk::key::{get_epoch, user_key, FullKey};
 use piestream_hummock_sdk::key_range::KeyRange;
 use piestream_hummock_sdk::prost_key_range::KeyRangeExt;
-use piestream_hummock_sdk::VersionedComparator;
+use piestream_hummock_sdk::{CompactionGroupId, VersionedComparator};
 use piestream_pb::hummock::compact_task::TaskStatus;
 use piestream_pb::hummock::subscribe_compact_tasks_response::Task;
 use piestream_pb::hummock::{
@@ -60,27 +60,32 @@ use tokio::task::JoinHandle;
 
 use self::task_progress::TaskProgress;
 use super::multi_builder::CapacitySplitTableBuilder;
-use super::{HummockResult, SstableBuilderOptions, SstableWriterOptions};
+use super::{CompressionAlgorithm, HummockResult, SstableBuilderOptions, SstableWriterOptions};
 use crate::hummock::compactor::compactor_runner::CompactorRunner;
 use crate::hummock::compactor::task_progress::TaskProgressGuard;
 use crate::hummock::iterator::{Forward, HummockIterator};
 use crate::hummock::multi_builder::{SplitTableOutput, TableBuilderFactory};
+use crate::hummock::sstable_store::SstableStoreRef;
 use crate::hummock::utils::MemoryLimiter;
 use crate::hummock::vacuum::Vacuum;
 use crate::hummock::{
-    validate_ssts, BatchSstableWriterFactory, CachePolicy, HummockError, SstableBuilder,
-    SstableIdManagerRef, SstableWriterFactory, StreamingSstableWriterFactory,
+    validate_ssts, BatchSstableWriterFactory, BlobWriter, CachePolicy, DeleteRangeAggregator,
+    HummockError, SstableBuilder, SstableIdManagerRef, SstableWriterFactory,
+    StreamingSstableWriterFactory,
 };
 use crate::monitor::{StateStoreMetrics, StoreLocalStatistic};
 
 pub struct RemoteBuilderFactory<F: SstableWriterFactory> {
     sstable_id_manager: SstableIdManagerRef,
+    sstable_store: SstableStoreRef,
     limiter: Arc<MemoryLimiter>,
     options: SstableBuilderOptions,
     policy: CachePolicy,
     remote_rpc_cost: Arc<AtomicU64>,
     filter_key_extractor: Arc<FilterKeyExtractorImpl>,
     sstable_writer_factory: F,
+    blob_value_thresholds: Arc<HashMap<u32, usize>>,
+    compaction_group_id: Option<CompactionGroupId>,
 }
 
 #[async_trait::async_trait]
@@ -106,12 +111,26 @@ impl<F: SstableWriterFactory> TableBuilderFactory for RemoteBuilderFactory<F> {
         let writer = self
             .sstable_writer_factory
             .create_sst_writer(table_id, writer_options)?;
-        let builder = SstableBuilder::new(
+        let mut builder = SstableBuilder::new(
             table_id,
             writer,
             self.options.clone(),
             self.filter_key_extractor.clone(),
         );
+        if !self.blob_value_thresholds.is_empty() {
+            builder = builder.with_blob_writer(BlobWriter::new(
+                self.sstable_id_manager.clone(),
+                self.sstable_store.clone(),
+                self.blob_value_thresholds.clone(),
+                self.options.capacity,
+            ));
+        }
+        if self.options.compression_algorithm == CompressionAlgorithm::ZstdDictionary
+            && let Some(compaction_group_id) = self.compaction_group_id
+        {
+            builder = builder
+                .with_compression_dictionary(self.sstable_store.clone(), compaction_group_id);
+        }
         Ok(builder)
     }
 }
@@ -122,6 +141,11 @@ pub struct TaskConfig {
     pub cache_policy: CachePolicy,
     pub gc_delete_keys: bool,
     pub watermark: u64,
+    /// Values of these tables larger than the threshold are separated into blob files.
+    pub blob_value_thresholds: Arc<HashMap<u32, usize>>,
+    /// The output SSTs share the zstd dictionary of this compaction group. `None` for shared
+    /// buffer compaction.
+    pub compaction_group_id: Option<CompactionGroupId>,
 }
 
 #[derive(Clone)]
@@ -520,6 +544,7 @@ impl Compactor {
         stats: Arc<StateStoreMetrics>,
         mut iter: impl HummockIterator<Direction = Forward>,
         mut compaction_filter: impl CompactionFilter,
+        del_agg: Arc<DeleteRangeAggregator>,
     ) -> HummockResult<()>
     where
         F: TableBuilderFactory,
@@ -576,6 +601,12 @@ impl Compactor {
                 drop = true;
             }
 
+            // Versions covered by a range tombstone below the watermark are invisible to all
+            // readers.
+            if !drop && del_agg.should_delete(user_key(iter_key), epoch) {
+                drop = true;
+            }
+
             if epoch <= task_config.watermark {
                 watermark_can_see_last_key = true;
             }
@@ -607,6 +638,7 @@ impl Compactor {
         cache_policy: CachePolicy,
         gc_delete_keys: bool,
         watermark: u64,
+        blob_value_thresholds: Arc<HashMap<u32, usize>>,
     ) -> Self {
         Self {
             context,
@@ -616,6 +648,8 @@ impl Compactor {
                 cache_policy,
                 gc_delete_keys,
                 watermark,
+                blob_value_thresholds,
+                compaction_group_id: None,
             },
         }
     }
@@ -630,6 +664,7 @@ impl Compactor {
         compaction_filter: impl CompactionFilter,
         filter_key_extractor: Arc<FilterKeyExtractorImpl>,
         task_progress: Option<Arc<TaskProgress>>,
+        del_agg: Arc<DeleteRangeAggregator>,
     ) -> HummockResult<Vec<SstableInfo>> {
         let get_id_time = Arc::new(AtomicU64::new(0));
         // Monitor time cost building shared buffer to SSTs.
@@ -649,6 +684,7 @@ impl Compactor {
                 filter_key_extractor,
                 get_id_time.clone(),
                 task_progress.clone(),
+                del_agg,
             )
             .await?
         } else {
@@ -659,6 +695,7 @@ impl Compactor {
                 filter_key_extractor,
                 get_id_time.clone(),
                 task_progress.clone(),
+                del_agg,
             )
             .await?
         };
@@ -717,15 +754,19 @@ impl Compactor {
         filter_key_extractor: Arc<FilterKeyExtractorImpl>,
         get_id_time: Arc<AtomicU64>,
         task_progress: Option<Arc<TaskProgress>>,
+        del_agg: Arc<DeleteRangeAggregator>,
     ) -> HummockResult<Vec<SplitTableOutput>> {
         let builder_factory = RemoteBuilderFactory {
             sstable_id_manager: self.context.sstable_id_manager.clone(),
+            sstable_store: self.context.sstable_store.clone(),
             limiter: self.context.read_memory_limiter.clone(),
             options: self.options.clone(),
             policy: self.task_config.cache_policy,
             remote_rpc_cost: get_id_time,
             filter_key_extractor,
             sstable_writer_factory: writer_factory,
+            blob_value_thresholds: self.task_config.blob_value_thresholds.clone(),
+            compaction_group_id: self.task_config.compaction_group_id,
         };
 
         let mut sst_builder = CapacitySplitTableBuilder::new(
@@ -733,12 +774,35 @@ impl Compactor {
             self.context.stats.clone(),
             task_progress,
         );
+
+        // Tombstones are kept unless all the data they may cover is dropped in this task.
+        let key_range = &self.task_config.key_range;
+        let split_user_key = |key: &[u8]| {
+            if key.is_empty() {
+                vec![]
+            } else {
+                user_key(key).to_vec()
+            }
+        };
+        let range_tombstones = del_agg
+            .get_tombstone_between(
+                &split_user_key(&key_range.left),
+                &split_user_key(&key_range.right),
+            )
+            .into_iter()
+            .filter(|tombstone| {
+                !self.task_config.gc_delete_keys || tombstone.sequence > self.task_config.watermark
+            })
+            .collect_vec();
+        sst_builder.add_delete_ranges(range_tombstones);
+
         Compactor::compact_and_build_sst(
             &mut sst_builder,
             &self.task_config,
             self.context.stats.clone(),
             iter,
             compaction_filter,
+            del_agg,
         )
         .await?;
         sst_builder.finish().await
@@ -795,6 +859,20 @@ fn build_multi_compaction_filter(compact_task: &CompactTask) -> MultiCompactionF
     multi_filter
 }
 
+/// Collects the tables of the task which enable key-value separation.
+fn build_blob_value_thresholds(compact_task: &CompactTask) -> HashMap<u32, usize> {
+    use piestream_common::catalog::TableOption;
+    compact_task
+        .table_options
+        .iter()
+        .filter_map(|(table_id, option)| {
+            TableOption::from(option)
+                .blob_value_threshold
+                .map(|threshold| (*table_id, threshold as usize))
+        })
+        .collect()
+}
+
 async fn generate_splits(compact_task: &mut CompactTask, context: Arc<Context>) {
     let sstable_infos = compact_task
         .input_ssts
@@ -802,7 +880,7 @@ async fn generate_splits(compact_task: &mut CompactTask, context: Arc<Context>)
         .flat_map(|level| level.table_infos.iter())
         .collect_vec();
 
-    let compaction_size = compact_task
+    let mut compaction_size = compact_task
         .input_ssts
         .iter()
         .flat_map(|level| level.table_infos.iter())
@@ -836,8 +914,26 @@ async fn generate_splits(compact_task: &mut CompactTask, context: Arc<Context>)
         }
         // sort by key, as for every data block has the same size;
         indexes.sort_by(|a, b| VersionedComparator::compare_key(a.1.as_ref(), b.1.as_ref()));
+        let mut task_range = KeyRange_vec::new(vec![], vec![]);
+        if compact_task.parent_task_id != 0 {
+            // A subtask only compacts the key range assigned to it by meta, so only the blocks
+            // inside the range are used to split it further.
+            task_range = compact_task.splits[0].clone();
+            indexes.retain(|(_, key)| {
+                (task_range.left.is_empty()
+                    || VersionedComparator::compare_key(key, &task_range.left)
+                        == std::cmp::Ordering::Greater)
+                    && (task_range.right.is_empty()
+                        || VersionedComparator::compare_key(key, &task_range.right)
+                            == std::cmp::Ordering::Less)
+            });
+            if indexes.is_empty() {
+                return;
+            }
+            compaction_size = indexes.iter().map(|block| block.0).sum::<u64>();
+        }
         let mut splits: Vec<KeyRange_vec> = vec![];
-        splits.push(KeyRange_vec::new(vec![], vec![]));
+        splits.push(KeyRange_vec::new(task_range.left.clone(), vec![]));
         let parallelism = std::cmp::min(
             indexes.len() as u64,
             context.options.max_sub_compaction as u64,
@@ -863,6 +959,7 @@ async fn generate_splits(compact_task: &mut CompactTask, context: Arc<Context>)
                 remaining_size -= data_size;
                 last_key = key;
             }
+            splits.last_mut().unwrap().right = task_range.right;
             compact_task.splits = splits;
         }
     }
@@ -30,10 +30,14 @@ use crate::hummock::compactor::context::Context;
 use crate::hummock::compactor::{CompactOutput, Compactor};
 use crate::hummock::iterator::{Forward, HummockIterator};
 use crate::hummock::shared_buffer::shared_buffer_uploader::UploadTaskPayload;
-use crate::hummock::shared_buffer::{build_ordered_merge_iter, UncommittedData};
+use crate::hummock::shared_buffer::{
+    build_ordered_merge_iter, collect_delete_range_tombstones, UncommittedData,
+};
 use crate::hummock::sstable::SstableIteratorReadOptions;
 use crate::hummock::state_store::ForwardIter;
-use crate::hummock::{CachePolicy, HummockError, HummockResult, SstableBuilderOptions};
+use crate::hummock::{
+    CachePolicy, DeleteRangeAggregator, HummockError, HummockResult, SstableBuilderOptions,
+};
 use crate::monitor::StoreLocalStatistic;
 
 /// Flush shared buffer to level0. Resulted SSTs are grouped by compaction group.
@@ -178,6 +182,17 @@ async fn compact_shared_buffer(
     let mut compaction_futures = vec![];
 
     let mut local_stats = StoreLocalStatistic::default();
+    // The payload may hold data of several epochs, so no key is dropped by range tombstones here.
+    // The tombstones are only written to the output ssts.
+    let mut range_tombstones = vec![];
+    collect_delete_range_tombstones(
+        &payload,
+        sstable_store.clone(),
+        &mut local_stats,
+        &mut range_tombstones,
+    )
+    .await?;
+    let del_agg = Arc::new(DeleteRangeAggregator::new(range_tombstones, 0));
     for (split_index, key_range) in splits.into_iter().enumerate() {
         let compactor = SharedBufferCompactRunner::new(
             split_index,
@@ -191,12 +206,17 @@ async fn compact_shared_buffer(
             stats.clone(),
             &mut local_stats,
             Arc::new(SstableIteratorReadOptions::default()),
+            None,
         )
         .await?;
         let compaction_executor = context.compaction_executor.clone();
         let multi_filter_key_extractor = multi_filter_key_extractor.clone();
-        let handle = compaction_executor
-            .spawn(async move { compactor.run(iter, multi_filter_key_extractor).await });
+        let del_agg = del_agg.clone();
+        let handle = compaction_executor.spawn(async move {
+            compactor
+                .run(iter, multi_filter_key_extractor, del_agg)
+                .await
+        });
         compaction_futures.push(handle);
     }
     local_stats.report(stats.as_ref());
@@ -262,7 +282,15 @@ impl SharedBufferCompactRunner {
     ) -> Self {
         let mut options: SstableBuilderOptions = context.options.as_ref().into();
         options.capacity = sub_compaction_sstable_size;
-        let compactor = Compactor::new(context, options, key_range, CachePolicy::Fill, false, 0);
+        let compactor = Compactor::new(
+            context,
+            options,
+            key_range,
+            CachePolicy::Fill,
+            false,
+            0,
+            Default::default(),
+        );
         Self {
             compactor,
             split_index,
@@ -273,11 +301,18 @@ impl SharedBufferCompactRunner {
         &self,
         iter: impl HummockIterator<Direction = Forward>,
         filter_key_extractor: Arc<FilterKeyExtractorImpl>,
+        del_agg: Arc<DeleteRangeAggregator>,
     ) -> HummockResult<CompactOutput> {
         let dummy_compaction_filter = DummyCompactionFilter {};
         let ssts = self
             .compactor
-            .compact_key_range(iter, dummy_compaction_filter, filter_key_extractor, None)
+            .compact_key_range(
+                iter,
+                dummy_compaction_filter,
+                filter_key_extractor,
+                None,
+                del_agg,
+            )
             .await?;
         Ok((self.split_index, ssts))
     }
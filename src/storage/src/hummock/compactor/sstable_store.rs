@@ -18,6 +18,7 @@ use bytes::Bytes;
 use piestream_object_store::object::ObjectError;
 use piestream_pb::hummock::SstableInfo;
 use tokio::io::{AsyncRead, AsyncReadExt};
+use zstd::dict::DecoderDictionary;
 
 use crate::hummock::sstable_store::{SstableStoreRef, TableHolder};
 use crate::hummock::{
@@ -76,6 +77,9 @@ impl CompactorSstableStore {
                 .map_err(HummockError::object_io_error)?,
             block_index.unwrap_or(0),
             &sst.meta,
+            self.sstable_store
+                .decoder_dictionary(sst.meta.compression_dictionary_id)
+                .await?,
         ))
     }
 }
@@ -128,6 +132,9 @@ pub struct BlockStream {
     /// streaming starts at block 2 of a given SST, then the list does not contain information
     /// about block 0 and block 1.
     block_size_vec: Vec<(usize, usize)>,
+
+    /// The prepared zstd dictionary to decompress blocks, if any.
+    compression_dictionary: Option<Arc<DecoderDictionary<'static>>>,
 }
 
 impl BlockStream {
@@ -145,6 +152,9 @@ impl BlockStream {
 
         // Meta data of the SST that is streamed.
         sst_meta: &SstableMeta,
+
+        // Prepared zstd dictionary of the SST that is streamed, if any.
+        compression_dictionary: Option<Arc<DecoderDictionary<'static>>>,
     ) -> Self {
         let metas = &sst_meta.block_metas;
 
@@ -162,6 +172,7 @@ impl BlockStream {
             byte_stream,
             block_idx: 0,
             block_size_vec: block_len_vec,
+            compression_dictionary,
         }
     }
 
@@ -191,7 +202,11 @@ impl BlockStream {
             )));
         }
 
-        let boxed_block = Box::new(Block::decode(Bytes::from(buffer), block_full_size)?);
+        let boxed_block = Box::new(Block::decode_with_dictionary(
+            Bytes::from(buffer),
+            block_full_size,
+            self.compression_dictionary.as_deref(),
+        )?);
         self.block_idx += 1;
 
         Ok(Some(BlockHolder::from_owned_block(boxed_block)))
@@ -0,0 +1,125 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use piestream_hummock_sdk::key::user_key;
+use piestream_hummock_sdk::{CompactionGroupId, HummockSstableId};
+use piestream_pb::hummock::level_delta::DeltaType;
+use piestream_pb::hummock::{HummockVersionDelta, SstableInfo};
+
+use crate::hummock::{CachePolicy, HummockResult, Sstable, SstableStoreRef};
+use crate::monitor::StoreLocalStatistic;
+
+/// Warms up the block cache after a compaction is committed. Blocks of the compaction output are
+/// fetched if their key ranges overlap blocks of the compaction input that are still cached, so
+/// that reads on hot data do not fall back to the object store once the version is switched.
+#[derive(Clone)]
+pub struct CacheRefiller {
+    sstable_store: SstableStoreRef,
+}
+
+impl CacheRefiller {
+    pub fn new(sstable_store: SstableStoreRef) -> Self {
+        Self { sstable_store }
+    }
+
+    /// Spawns a refill task for each compaction contained in `version_deltas`.
+    pub fn start_refill(&self, version_deltas: &[HummockVersionDelta]) {
+        for delta in version_deltas {
+            // SSTs of a trivial move are unchanged and their blocks are already cached.
+            if delta.trivial_move {
+                continue;
+            }
+            let mut groups: HashMap<CompactionGroupId, (Vec<HummockSstableId>, Vec<SstableInfo>)> =
+                HashMap::new();
+            for (group_id, level_deltas) in &delta.level_deltas {
+                for level_delta in &level_deltas.level_deltas {
+                    if let Some(DeltaType::IntraLevel(intra_level)) = &level_delta.delta_type {
+                        let (removed, inserted) = groups.entry(*group_id).or_default();
+                        removed.extend(intra_level.removed_table_ids.iter().cloned());
+                        inserted.extend(intra_level.inserted_table_infos.iter().cloned());
+                    }
+                }
+            }
+            for (removed, inserted) in groups.into_values() {
+                // Newly flushed SSTs have nothing to refill from.
+                if removed.is_empty() || inserted.is_empty() {
+                    continue;
+                }
+                let sstable_store = self.sstable_store.clone();
+                tokio::spawn(async move {
+                    let mut stats = StoreLocalStatistic::default();
+                    let result = Self::refill(sstable_store, removed, inserted, &mut stats).await;
+                    // Refill reads are not issued by any query, so keep them out of the metrics.
+                    stats.ignore();
+                    if let Err(e) = result {
+                        tracing::warn!("failed to refill block cache: {:?}", e);
+                    }
+                });
+            }
+        }
+    }
+
+    async fn refill(
+        sstable_store: SstableStoreRef,
+        removed: Vec<HummockSstableId>,
+        inserted: Vec<SstableInfo>,
+        stats: &mut StoreLocalStatistic,
+    ) -> HummockResult<()> {
+        let meta_cache = sstable_store.get_meta_cache();
+        let block_cache = sstable_store.get_block_cache();
+        let mut hot_ranges = vec![];
+        for sst_id in removed {
+            let Some(sst) = meta_cache.lookup(sst_id, &sst_id) else {
+                continue;
+            };
+            for block_index in 0..sst.value().block_count() {
+                if block_cache.get(sst_id, block_index as u64).is_some() {
+                    hot_ranges.push(block_user_key_range(sst.value(), block_index));
+                }
+            }
+        }
+        if hot_ranges.is_empty() {
+            return Ok(());
+        }
+
+        for info in inserted {
+            let sst = sstable_store.sstable(&info, stats).await?;
+            for block_index in 0..sst.value().block_count() {
+                let (left, right) = block_user_key_range(sst.value(), block_index);
+                let overlapped = hot_ranges
+                    .iter()
+                    .any(|(hot_left, hot_right)| left <= *hot_right && *hot_left <= right);
+                if overlapped {
+                    sstable_store
+                        .get(sst.value(), block_index as u64, CachePolicy::Fill, stats)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the user key range covered by the block, with both ends inclusive.
+fn block_user_key_range(sst: &Sstable, block_index: usize) -> (Vec<u8>, Vec<u8>) {
+    let block_metas = &sst.meta.block_metas;
+    let left = user_key(&block_metas[block_index].smallest_key).to_vec();
+    let right = match block_metas.get(block_index + 1) {
+        Some(next) => user_key(&next.smallest_key).to_vec(),
+        None => user_key(&sst.meta.largest_key).to_vec(),
+    };
+    (left, right)
+}
@@ -21,10 +21,11 @@ use futures::future::{select, try_join_all, Either};
 use futures::FutureExt;
 use itertools::Itertools;
 use piestream_hummock_sdk::HummockEpoch;
+use piestream_pb::hummock::pin_version_response::Payload;
 use tokio::sync::{mpsc, oneshot};
 use tracing::{error, info};
 
-use crate::hummock::event_handler::HummockEvent;
+use crate::hummock::event_handler::{CacheRefiller, HummockEvent};
 use crate::hummock::local_version::local_version_manager::LocalVersionManager;
 use crate::hummock::local_version::upload_handle_manager::UploadHandleManager;
 use crate::hummock::local_version::SyncUncommittedDataStage;
@@ -94,6 +95,7 @@ pub struct HummockEventHandler {
     shared_buffer_event_receiver: mpsc::UnboundedReceiver<HummockEvent>,
     upload_handle_manager: UploadHandleManager,
     pending_sync_requests: HashMap<HummockEpoch, oneshot::Sender<HummockResult<SyncResult>>>,
+    cache_refiller: Option<CacheRefiller>,
 }
 
 impl HummockEventHandler {
@@ -108,9 +110,15 @@ impl HummockEventHandler {
             shared_buffer_event_receiver,
             upload_handle_manager: UploadHandleManager::new(),
             pending_sync_requests: Default::default(),
+            cache_refiller: None,
         }
     }
 
+    pub fn with_cache_refiller(mut self, cache_refiller: CacheRefiller) -> Self {
+        self.cache_refiller = Some(cache_refiller);
+        self
+    }
+
     fn try_flush_shared_buffer(&mut self) {
         // Keep issuing new flush task until flush is not needed or we can issue
         // no more task
@@ -320,6 +328,11 @@ impl HummockEventHandler {
                     }
 
                     HummockEvent::VersionUpdate(version_payload) => {
+                        if let (Some(cache_refiller), Payload::VersionDeltas(version_deltas)) =
+                            (&self.cache_refiller, &version_payload)
+                        {
+                            cache_refiller.start_refill(&version_deltas.delta);
+                        }
                         self.local_version_manager
                             .try_update_pinned_version(version_payload);
                     }
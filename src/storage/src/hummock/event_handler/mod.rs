@@ -20,6 +20,8 @@ use crate::hummock::shared_buffer::shared_buffer_batch::SharedBufferBatch;
 use crate::hummock::HummockResult;
 use crate::store::SyncResult;
 
+mod cache_refiller;
+pub use cache_refiller::CacheRefiller;
 mod hummock_event_handler;
 pub use hummock_event_handler::{BufferTracker, HummockEventHandler};
 
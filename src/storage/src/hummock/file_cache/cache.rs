@@ -536,4 +536,29 @@ mod tests {
             assert_eq!(cache.get(&key).await.unwrap().as_deref(), slot.as_deref());
         }
     }
+
+    #[tokio::test]
+    async fn test_recovery_with_other_version() {
+        let dir = tempdir();
+
+        let holder = Arc::new(FlushHolder::default());
+        let cache = create_file_cache_manager_for_test(dir.path(), vec![holder.clone()]).await;
+        for i in 0..SHARDSU64 {
+            cache.insert(key(i), vec![b'x'; BS]).unwrap();
+        }
+        holder.trigger();
+        holder.wait().await;
+        cache.buffer_flusher_notifier.notify_one();
+        holder.trigger();
+        holder.wait().await;
+        drop(cache);
+
+        // Files written by another version are discarded instead of being restored.
+        std::fs::write(dir.path().join("version"), u32::MAX.to_be_bytes()).unwrap();
+        let cache = create_file_cache_manager_for_test(dir.path(), vec![holder.clone()]).await;
+        assert_eq!(cache.store.cache_file_len(), 0);
+        for i in 0..SHARDSU64 {
+            assert_eq!(cache.get(&key(i)).await.unwrap().as_deref(), None);
+        }
+    }
 }
@@ -16,6 +16,7 @@ use std::marker::PhantomData;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use bytes::Buf;
 use itertools::Itertools;
 use nix::sys::statfs::{statfs, FsType as NixFsType, EXT4_SUPER_MAGIC};
 use parking_lot::RwLock;
@@ -32,6 +33,11 @@ use crate::hummock::{HashBuilder, TieredCacheKey, TieredCacheValue};
 
 const META_FILE_FILENAME: &str = "meta";
 const CACHE_FILE_FILENAME: &str = "cache";
+const VERSION_FILE_FILENAME: &str = "version";
+
+/// Version of the layout of the meta file and the cache file. Files of other versions are removed
+/// when the store is opened, instead of being restored.
+const VERSION: u32 = 1;
 
 const FREELIST_DEFAULT_CAPACITY: usize = 64;
 
@@ -213,6 +219,7 @@ where
         if !PathBuf::from(options.dir.as_str()).exists() {
             std::fs::create_dir_all(options.dir.as_str())?;
         }
+        Self::check_version(&options.dir)?;
 
         // Get file system type and block size by `statfs(2)`.
         let fs_stat = statfs(options.dir.as_str())?;
@@ -290,6 +297,34 @@ where
         PathBuf::from(&self.dir).join(CACHE_FILE_FILENAME)
     }
 
+    /// Removes the meta file and the cache file if they are written by another version.
+    fn check_version(dir: &str) -> Result<()> {
+        let version_path = PathBuf::from(dir).join(VERSION_FILE_FILENAME);
+        let version = match std::fs::read(&version_path) {
+            Ok(buf) if buf.len() == 4 => Some((&buf[..]).get_u32()),
+            Ok(_) => None,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        if version == Some(VERSION) {
+            return Ok(());
+        }
+        for filename in [META_FILE_FILENAME, CACHE_FILE_FILENAME] {
+            let path = PathBuf::from(dir).join(filename);
+            if path.exists() {
+                tracing::warn!(
+                    "remove file cache {:?} of version {:?}, current version: {}",
+                    path,
+                    version,
+                    VERSION
+                );
+                std::fs::remove_file(path)?;
+            }
+        }
+        std::fs::write(version_path, VERSION.to_be_bytes())?;
+        Ok(())
+    }
+
     pub async fn restore<S: HashBuilder>(
         &self,
         indices: &Arc<LruCache<K, SlotId>>,
@@ -301,6 +336,14 @@ where
             // Wrap the read guard, or there will be deadlock when evicting entries.
             let res = { self.meta_file.read().await.get(slot) };
             if let Some((block_loc, key)) = res {
+                // The cache file may be shorter than recorded if the node crashed before the
+                // data was persisted.
+                let end = block_loc.bidx as usize * self.block_size
+                    + block_loc.blen(self.block_size as u32) as usize;
+                if end > self.cache_file.len() {
+                    self.meta_file.write().await.free(slot);
+                    continue;
+                }
                 indices.insert(
                     key.clone(),
                     hash_builder.hash_one(&key),
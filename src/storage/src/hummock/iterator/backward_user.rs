@@ -13,18 +13,20 @@
 // limitations under the License.
 
 use std::ops::Bound::{self, *};
+use std::sync::Arc;
 
 use piestream_hummock_sdk::key::{get_epoch, key_with_epoch, user_key as to_user_key};
 use piestream_hummock_sdk::HummockEpoch;
 
 use crate::hummock::iterator::merge_inner::UnorderedMergeIteratorInner;
 use crate::hummock::iterator::{
-    Backward, BackwardUserIteratorType, DirectedUserIterator, DirectedUserIteratorBuilder,
-    HummockIterator, UserIteratorPayloadType,
+    fetch_blob_value, Backward, BackwardUserIteratorType, DirectedUserIterator,
+    DirectedUserIteratorBuilder, HummockIterator, UserIteratorPayloadType,
 };
 use crate::hummock::local_version::pinned_version::PinnedVersion;
-use crate::hummock::value::HummockValue;
-use crate::hummock::{BackwardSstableIterator, HummockResult};
+use crate::hummock::sstable_store::SstableStoreRef;
+use crate::hummock::value::{BlobPointer, HummockValue};
+use crate::hummock::{BackwardSstableIterator, DeleteRangeAggregator, HummockResult};
 use crate::monitor::StoreLocalStatistic;
 
 /// [`BackwardUserIterator`] can be used by user directly.
@@ -41,6 +43,10 @@ pub struct BackwardUserIterator<I: HummockIterator<Direction = Backward>> {
     /// Last user value
     last_val: Vec<u8>,
 
+    /// Pointer to the last user value if it is separated into a blob file. The value is fetched
+    /// only when the key is returned, since newer versions may overwrite it.
+    last_blob: Option<BlobPointer>,
+
     /// Last user key value is deleted
     last_delete: bool,
 
@@ -59,6 +65,12 @@ pub struct BackwardUserIterator<I: HummockIterator<Direction = Backward>> {
     /// Ensures the SSTs needed by `iterator` won't be vacuumed.
     _version: Option<PinnedVersion>,
 
+    /// Range tombstones visible at `read_epoch`.
+    delete_range_agg: Arc<DeleteRangeAggregator>,
+
+    /// Reads values separated into blob files.
+    sstable_store: Option<SstableStoreRef>,
+
     /// Store scan statistic
     stats: StoreLocalStatistic,
 }
@@ -71,6 +83,8 @@ impl<I: HummockIterator<Direction = Backward>> BackwardUserIterator<I> {
         read_epoch: u64,
         min_epoch: u64,
         version: Option<PinnedVersion>,
+        delete_range_agg: Arc<DeleteRangeAggregator>,
+        sstable_store: Option<SstableStoreRef>,
     ) -> Self {
         Self {
             iterator,
@@ -79,11 +93,14 @@ impl<I: HummockIterator<Direction = Backward>> BackwardUserIterator<I> {
             just_met_new_key: false,
             last_key: Vec::new(),
             last_val: Vec::new(),
+            last_blob: None,
             last_delete: true,
             read_epoch,
             min_epoch,
             stats: StoreLocalStatistic::default(),
             _version: version,
+            delete_range_agg,
+            sstable_store,
         }
     }
 
@@ -97,6 +114,7 @@ impl<I: HummockIterator<Direction = Backward>> BackwardUserIterator<I> {
 
     fn reset(&mut self) {
         self.last_key.clear();
+        self.last_blob = None;
         self.just_met_new_key = false;
         self.last_delete = true;
         self.out_of_range = false;
@@ -157,7 +175,7 @@ impl<I: HummockIterator<Direction = Backward>> BackwardUserIterator<I> {
                         // covered all situation. 2(a)
                         self.just_met_new_key = true;
                         self.stats.processed_key_count += 1;
-                        return Ok(());
+                        return self.fetch_last_blob().await;
                     } else {
                         // 2(b)
                         self.last_key.clear();
@@ -176,22 +194,43 @@ impl<I: HummockIterator<Direction = Backward>> BackwardUserIterator<I> {
                 // been seeing the same key for too many times.
 
                 // 1 and 2(a)
+                let deleted_by_range = self.delete_range_agg.should_delete(key, epoch);
                 match self.iterator.value() {
-                    HummockValue::Put(val) => {
+                    HummockValue::Put(val) if !deleted_by_range => {
                         self.last_val.clear();
                         self.last_val.extend_from_slice(val);
+                        self.last_blob = None;
                         self.last_delete = false;
                     }
-                    HummockValue::Delete => {
+                    HummockValue::Blob(pointer) if !deleted_by_range => {
+                        self.last_blob = Some(pointer);
+                        self.last_delete = false;
+                    }
+                    HummockValue::Put(_) | HummockValue::Blob(_) | HummockValue::Delete => {
+                        self.last_blob = None;
                         self.last_delete = true;
                     }
                 }
             }
             self.iterator.next().await?;
         }
+        if !self.last_delete && !self.out_of_range {
+            self.fetch_last_blob().await?;
+        }
         Ok(()) // not valid, EOF
     }
 
+    /// Fetches the last user value if it is separated into a blob file.
+    async fn fetch_last_blob(&mut self) -> HummockResult<()> {
+        if let Some(pointer) = self.last_blob.take() {
+            let val =
+                fetch_blob_value(self.sstable_store.as_ref(), &pointer, &mut self.stats).await?;
+            self.last_val.clear();
+            self.last_val.extend_from_slice(&val);
+        }
+        Ok(())
+    }
+
     /// Returns the key with the newest version. Thus no version in it, and only the `user_key` will
     /// be returned.
     ///
@@ -278,7 +317,15 @@ impl BackwardUserIterator<BackwardUserIteratorType> {
         >,
         key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
     ) -> Self {
-        Self::with_epoch(iterator, key_range, HummockEpoch::MAX, 0, None)
+        Self::with_epoch(
+            iterator,
+            key_range,
+            HummockEpoch::MAX,
+            0,
+            None,
+            Arc::new(DeleteRangeAggregator::default()),
+            None,
+        )
     }
 
     /// Creates [`BackwardUserIterator`] with maximum epoch.
@@ -289,7 +336,15 @@ impl BackwardUserIterator<BackwardUserIteratorType> {
         key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
         min_epoch: HummockEpoch,
     ) -> Self {
-        Self::with_epoch(iterator, key_range, HummockEpoch::MAX, min_epoch, None)
+        Self::with_epoch(
+            iterator,
+            key_range,
+            HummockEpoch::MAX,
+            min_epoch,
+            None,
+            Arc::new(DeleteRangeAggregator::default()),
+            None,
+        )
     }
 }
 
@@ -305,10 +360,18 @@ impl DirectedUserIteratorBuilder for BackwardUserIterator<BackwardUserIteratorTy
         read_epoch: u64,
         min_epoch: u64,
         version: Option<PinnedVersion>,
+        delete_range_agg: Arc<DeleteRangeAggregator>,
+        sstable_store: Option<SstableStoreRef>,
     ) -> DirectedUserIterator {
         let iterator = UnorderedMergeIteratorInner::new(iterator_iter);
         DirectedUserIterator::Backward(BackwardUserIterator::with_epoch(
-            iterator, key_range, read_epoch, min_epoch, version,
+            iterator,
+            key_range,
+            read_epoch,
+            min_epoch,
+            version,
+            delete_range_agg,
+            sstable_store,
         ))
     }
 }
@@ -320,9 +383,9 @@ mod tests {
     use std::collections::BTreeMap;
     use std::ops::Bound::*;
 
+    use piestream_hummock_sdk::key::{prev_key, user_key};
     use rand::distributions::Alphanumeric;
     use rand::{thread_rng, Rng};
-    use piestream_hummock_sdk::key::{prev_key, user_key};
 
     use super::*;
     use crate::hummock::iterator::test_utils::{
@@ -922,6 +985,7 @@ mod tests {
                 match inserts.first_key_value().unwrap().1 {
                     HummockValue::Put(_) => 1,
                     HummockValue::Delete => 0,
+                    HummockValue::Blob(_) => unreachable!(),
                 }
             })
             .reduce(|accum, item| accum + item)
@@ -1133,10 +1197,7 @@ mod tests {
     }
 
     fn clone_sst(sst: &Sstable) -> Sstable {
-        Sstable {
-            id: sst.id,
-            meta: sst.meta.clone(),
-        }
+        Sstable::new(sst.id, sst.meta.clone())
     }
 
     #[tokio::test]
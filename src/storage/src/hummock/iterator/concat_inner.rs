@@ -62,12 +62,17 @@ impl<TI: SstableIteratorType> ConcatIteratorInner<TI> {
     }
 
     /// Seeks to a table, and then seeks to the key if `seek_key` is given.
-    async fn seek_idx(&mut self, idx: usize, seek_key: Option<&[u8]>) -> HummockResult<()> {
-        if idx >= self.tables.len() {
-            if let Some(old_iter) = self.sstable_iter.take() {
-                old_iter.collect_local_statistic(&mut self.stats);
+    ///
+    /// Tables without any key (e.g. holding only range tombstones) are skipped.
+    async fn seek_idx(&mut self, mut idx: usize, mut seek_key: Option<&[u8]>) -> HummockResult<()> {
+        loop {
+            if idx >= self.tables.len() {
+                if let Some(old_iter) = self.sstable_iter.take() {
+                    old_iter.collect_local_statistic(&mut self.stats);
+                }
+                break;
             }
-        } else {
+
             let table = self
                 .sstable_store
                 .sstable(&self.tables[idx], &mut self.stats)
@@ -87,6 +92,11 @@ impl<TI: SstableIteratorType> ConcatIteratorInner<TI> {
 
             self.sstable_iter = Some(sstable_iter);
             self.cur_idx = idx;
+            if self.is_valid() || seek_key.is_some() {
+                break;
+            }
+            idx += 1;
+            seek_key = None;
         }
         Ok(())
     }
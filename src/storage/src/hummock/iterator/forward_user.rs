@@ -13,18 +13,20 @@
 // limitations under the License.
 
 use std::ops::Bound::{self, *};
+use std::sync::Arc;
 
 use piestream_hummock_sdk::key::{get_epoch, key_with_epoch, user_key as to_user_key};
 use piestream_hummock_sdk::HummockEpoch;
 
 use crate::hummock::iterator::merge_inner::UnorderedMergeIteratorInner;
 use crate::hummock::iterator::{
-    DirectedUserIterator, DirectedUserIteratorBuilder, Forward, ForwardUserIteratorType,
-    HummockIterator, UserIteratorPayloadType,
+    fetch_blob_value, DirectedUserIterator, DirectedUserIteratorBuilder, Forward,
+    ForwardUserIteratorType, HummockIterator, UserIteratorPayloadType,
 };
 use crate::hummock::local_version::pinned_version::PinnedVersion;
+use crate::hummock::sstable_store::SstableStoreRef;
 use crate::hummock::value::HummockValue;
-use crate::hummock::{HummockResult, SstableIterator};
+use crate::hummock::{DeleteRangeAggregator, HummockResult, SstableIterator};
 use crate::monitor::StoreLocalStatistic;
 
 /// [`UserIterator`] can be used by user directly.
@@ -53,6 +55,12 @@ pub struct UserIterator<I: HummockIterator<Direction = Forward>> {
     /// Ensures the SSTs needed by `iterator` won't be vacuumed.
     _version: Option<PinnedVersion>,
 
+    /// Range tombstones visible at `read_epoch`.
+    delete_range_agg: Arc<DeleteRangeAggregator>,
+
+    /// Reads values separated into blob files.
+    sstable_store: Option<SstableStoreRef>,
+
     stats: StoreLocalStatistic,
 }
 
@@ -65,6 +73,8 @@ impl<I: HummockIterator<Direction = Forward>> UserIterator<I> {
         read_epoch: u64,
         min_epoch: u64,
         version: Option<PinnedVersion>,
+        delete_range_agg: Arc<DeleteRangeAggregator>,
+        sstable_store: Option<SstableStoreRef>,
     ) -> Self {
         Self {
             iterator,
@@ -76,6 +86,8 @@ impl<I: HummockIterator<Direction = Forward>> UserIterator<I> {
             min_epoch,
             stats: StoreLocalStatistic::default(),
             _version: version,
+            delete_range_agg,
+            sstable_store,
         }
     }
 
@@ -102,28 +114,41 @@ impl<I: HummockIterator<Direction = Forward>> UserIterator<I> {
                 self.last_key.extend_from_slice(key);
 
                 // handle delete operation
+                let deleted_by_range = self.delete_range_agg.should_delete(key, epoch);
                 match self.iterator.value() {
-                    HummockValue::Put(val) => {
+                    HummockValue::Put(val) if !deleted_by_range => {
                         self.last_val.clear();
                         self.last_val.extend_from_slice(val);
-
-                        // handle range scan
-                        match &self.key_range.1 {
-                            Included(end_key) => self.out_of_range = key > end_key.as_slice(),
-                            Excluded(end_key) => self.out_of_range = key >= end_key.as_slice(),
-                            Unbounded => {}
-                        };
-
-                        self.stats.processed_key_count += 1;
-                        return Ok(());
                     }
-                    // It means that the key is deleted from the storage.
-                    // Deleted kv and the previous versions (if any) of the key should not be
-                    // returned to user.
-                    HummockValue::Delete => {
+                    HummockValue::Blob(pointer) if !deleted_by_range => {
+                        let val = fetch_blob_value(
+                            self.sstable_store.as_ref(),
+                            &pointer,
+                            &mut self.stats,
+                        )
+                        .await?;
+                        self.last_val.clear();
+                        self.last_val.extend_from_slice(&val);
+                    }
+                    // It means that the key is deleted from the storage, either by a point delete
+                    // or by a range tombstone. Deleted kv and the previous versions (if any) of the
+                    // key should not be returned to user.
+                    HummockValue::Put(_) | HummockValue::Blob(_) | HummockValue::Delete => {
                         self.stats.skip_delete_key_count += 1;
+                        self.iterator.next().await?;
+                        continue;
                     }
                 }
+
+                // handle range scan
+                match &self.key_range.1 {
+                    Included(end_key) => self.out_of_range = key > end_key.as_slice(),
+                    Excluded(end_key) => self.out_of_range = key >= end_key.as_slice(),
+                    Unbounded => {}
+                };
+
+                self.stats.processed_key_count += 1;
+                return Ok(());
             } else {
                 self.stats.skip_multi_version_key_count += 1;
             }
@@ -217,7 +242,15 @@ impl UserIterator<ForwardUserIteratorType> {
         iterator: ForwardUserIteratorType,
         key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
     ) -> Self {
-        Self::new(iterator, key_range, HummockEpoch::MAX, 0, None)
+        Self::new(
+            iterator,
+            key_range,
+            HummockEpoch::MAX,
+            0,
+            None,
+            Arc::new(DeleteRangeAggregator::default()),
+            None,
+        )
     }
 
     pub(crate) fn for_test_with_epoch(
@@ -226,7 +259,15 @@ impl UserIterator<ForwardUserIteratorType> {
         read_epoch: u64,
         min_epoch: u64,
     ) -> Self {
-        Self::new(iterator, key_range, read_epoch, min_epoch, None)
+        Self::new(
+            iterator,
+            key_range,
+            read_epoch,
+            min_epoch,
+            None,
+            Arc::new(DeleteRangeAggregator::default()),
+            None,
+        )
     }
 }
 
@@ -240,10 +281,18 @@ impl DirectedUserIteratorBuilder for UserIterator<ForwardUserIteratorType> {
         read_epoch: u64,
         min_epoch: u64,
         version: Option<PinnedVersion>,
+        delete_range_agg: Arc<DeleteRangeAggregator>,
+        sstable_store: Option<SstableStoreRef>,
     ) -> DirectedUserIterator {
         let iterator = UnorderedMergeIteratorInner::new(iterator_iter);
         DirectedUserIterator::Forward(Self::new(
-            iterator, key_range, read_epoch, min_epoch, version,
+            iterator,
+            key_range,
+            read_epoch,
+            min_epoch,
+            version,
+            delete_range_agg,
+            sstable_store,
         ))
     }
 }
@@ -15,6 +15,9 @@
 use std::future::Future;
 use std::marker::PhantomData;
 use std::ops::{Bound, Deref, DerefMut};
+use std::sync::Arc;
+
+use bytes::Bytes;
 
 use super::{HummockResult, HummockValue};
 
@@ -39,7 +42,12 @@ use crate::hummock::iterator::HummockIteratorUnion::{First, Fourth, Second, Thir
 use crate::hummock::local_version::pinned_version::PinnedVersion;
 use crate::hummock::shared_buffer::shared_buffer_batch::SharedBufferBatchIterator;
 use crate::hummock::shared_buffer::SharedBufferIteratorType;
-use crate::hummock::{BackwardSstableIterator, SstableIterator, SstableIteratorType};
+use crate::hummock::sstable_store::SstableStoreRef;
+use crate::hummock::value::BlobPointer;
+use crate::hummock::{
+    BackwardSstableIterator, DeleteRangeAggregator, HummockError, SstableIterator,
+    SstableIteratorType,
+};
 
 #[cfg(any(test, feature = "test"))]
 pub mod test_utils;
@@ -375,9 +383,27 @@ pub trait DirectedUserIteratorBuilder {
         read_epoch: u64,
         min_epoch: u64,
         version: Option<PinnedVersion>,
+        delete_range_agg: Arc<DeleteRangeAggregator>,
+        sstable_store: Option<SstableStoreRef>,
     ) -> DirectedUserIterator;
 }
 
+/// Fetches a value separated into a blob file. User iterators without `sstable_store` only iterate
+/// over in-memory data and are not expected to meet blob values.
+pub(crate) async fn fetch_blob_value(
+    sstable_store: Option<&SstableStoreRef>,
+    pointer: &BlobPointer,
+    stats: &mut StoreLocalStatistic,
+) -> HummockResult<Bytes> {
+    match sstable_store {
+        Some(sstable_store) => sstable_store.get_blob_value(pointer, stats).await,
+        None => Err(HummockError::other(format!(
+            "cannot read blob {} without sstable store",
+            pointer.blob_id
+        ))),
+    }
+}
+
 impl DirectedUserIterator {
     #[inline(always)]
     pub async fn next(&mut self) -> HummockResult<()> {
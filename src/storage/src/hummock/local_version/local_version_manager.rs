@@ -282,11 +282,13 @@ impl LocalVersionManager {
         epoch: HummockEpoch,
         compaction_group_id: CompactionGroupId,
         kv_pairs: Vec<(Bytes, StorageValue)>,
+        delete_ranges: Vec<(Bytes, Bytes)>,
         table_id: TableId,
     ) -> SharedBufferBatch {
         let sorted_items = Self::build_shared_buffer_item_batches(kv_pairs, epoch);
         SharedBufferBatch::build(
             sorted_items,
+            delete_ranges,
             epoch,
             Some(self.buffer_tracker.get_memory_limiter().as_ref()),
             compaction_group_id,
@@ -307,10 +309,17 @@ impl LocalVersionManager {
         epoch: HummockEpoch,
         compaction_group_id: CompactionGroupId,
         kv_pairs: Vec<(Bytes, StorageValue)>,
+        delete_ranges: Vec<(Bytes, Bytes)>,
         table_id: TableId,
     ) -> HummockResult<usize> {
         let batch = self
-            .build_shared_buffer_batch(epoch, compaction_group_id, kv_pairs, table_id)
+            .build_shared_buffer_batch(
+                epoch,
+                compaction_group_id,
+                kv_pairs,
+                delete_ranges,
+                table_id,
+            )
             .await;
         let batch_size = batch.size();
         self.write_shared_buffer_inner(batch.epoch(), batch);
@@ -81,7 +81,7 @@ use crate::hummock::compaction_group_client::CompactionGroupClientImpl;
 #[cfg(any(test, feature = "test"))]
 use crate::hummock::compaction_group_client::DummyCompactionGroupClient;
 use crate::hummock::conflict_detector::ConflictDetector;
-use crate::hummock::event_handler::{HummockEvent, HummockEventHandler};
+use crate::hummock::event_handler::{CacheRefiller, HummockEvent, HummockEventHandler};
 use crate::hummock::local_version::pinned_version::{start_pinned_version_worker, PinnedVersion};
 use crate::hummock::observer_manager::HummockObserverNode;
 use crate::hummock::shared_buffer::shared_buffer_batch::SharedBufferBatch;
@@ -213,8 +213,12 @@ impl HummockStorage {
             event_tx.clone(),
         );
 
-        let hummock_event_handler =
+        let mut hummock_event_handler =
             HummockEventHandler::new(local_version_manager.clone(), event_rx);
+        if options.enable_cache_refill {
+            hummock_event_handler = hummock_event_handler
+                .with_cache_refiller(CacheRefiller::new(sstable_store.clone()));
+        }
 
         // Buffer size manager.
         tokio::spawn(hummock_event_handler.start_hummock_event_handler_worker());
@@ -278,8 +282,19 @@ pub async fn get_from_sstable_info(
     let sstable = sstable_store_ref.sstable(sstable_info, local_stats).await?;
 
     let ukey = user_key(internal_key);
+    // Versions older than the largest covering range tombstone are deleted by it.
+    let delete_sequence = sstable
+        .value()
+        .max_delete_range_sequence(ukey, key::get_epoch(internal_key));
+    let deleted_by_range = || {
+        if delete_sequence > 0 {
+            Some(HummockValue::Delete)
+        } else {
+            None
+        }
+    };
     if check_bloom_filter && !hit_sstable_bloom_filter(sstable.value(), ukey, local_stats) {
-        return Ok(None);
+        return Ok(deleted_by_range());
     }
 
     // TODO: now SstableIterator does not use prefetch through SstableIteratorReadOptions, so we
@@ -292,16 +307,24 @@ pub async fn get_from_sstable_info(
     iter.seek(internal_key).await?;
     // Iterator has sought passed the borders.
     if !iter.is_valid() {
-        return Ok(None);
+        return Ok(deleted_by_range());
     }
 
     // Iterator gets us the key, we tell if it's the key we want
     // or key next to it.
     let value = match key::user_key(iter.key()) == ukey {
+        true if key::get_epoch(iter.key()) < delete_sequence => Some(HummockValue::Delete),
         true => Some(iter.value().to_bytes()),
-        false => None,
+        false => deleted_by_range(),
     };
     iter.collect_local_statistic(local_stats);
+    // The value is separated into a blob file. Fetch it from there.
+    if let Some(HummockValue::Blob(pointer)) = value {
+        let blob_value = sstable_store_ref
+            .get_blob_value(&pointer, local_stats)
+            .await?;
+        return Ok(Some(HummockValue::Put(blob_value)));
+    }
 
     Ok(value)
 }
@@ -321,6 +344,23 @@ pub fn hit_sstable_bloom_filter(
     !surely_not_have
 }
 
+/// Checks whether the sstable may contain keys with `prefix` by its prefix bloom filter. Used by
+/// prefix iterators to skip sstables.
+pub fn hit_sstable_prefix_bloom_filter(
+    sstable_info_ref: &Sstable,
+    prefix: &[u8],
+    local_stats: &mut StoreLocalStatistic,
+) -> bool {
+    local_stats.prefix_bloom_filter_check_counts += 1;
+    let surely_not_have = sstable_info_ref.surely_not_have_prefix(prefix);
+
+    if surely_not_have {
+        local_stats.prefix_bloom_filter_skip_sst_count += 1;
+    }
+
+    !surely_not_have
+}
+
 /// Get `user_value` from `OrderSortedUncommittedData`. If not get successful, return None.
 pub async fn get_from_order_sorted_uncommitted_data(
     sstable_store_ref: SstableStoreRef,
@@ -340,6 +380,9 @@ pub async fn get_from_order_sorted_uncommitted_data(
                     if let Some(data) = get_from_batch(&batch, key, local_stats) {
                         return Ok((Some(data), table_counts));
                     }
+                    if batch.check_delete_by_range(key) {
+                        return Ok((Some(HummockValue::Delete), table_counts));
+                    }
                 }
 
                 UncommittedData::Sst((_, sstable_info)) => {
@@ -36,7 +36,10 @@ use crate::hummock::shared_buffer::shared_buffer_uploader::UploadTaskPayload;
 use crate::hummock::sstable::SstableIteratorReadOptions;
 use crate::hummock::state_store::HummockIteratorType;
 use crate::hummock::utils::{filter_single_sst, range_overlap};
-use crate::hummock::{HummockResult, SstableIteratorType, SstableStore};
+use crate::hummock::{
+    hit_sstable_prefix_bloom_filter, DeleteRangeTombstone, HummockResult, SstableIteratorType,
+    SstableStore,
+};
 use crate::monitor::{StateStoreMetrics, StoreLocalStatistic};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -118,12 +121,15 @@ pub type SharedBufferIteratorType<
     >,
 >;
 
+/// Builds the merge iterator of `uncommitted_data`. Ssts that surely do not contain `prefix_hint`
+/// are skipped.
 pub(crate) async fn build_ordered_merge_iter<T: HummockIteratorType>(
     uncommitted_data: &OrderSortedUncommittedData,
     sstable_store: Arc<SstableStore>,
     _stats: Arc<StateStoreMetrics>,
     local_stats: &mut StoreLocalStatistic,
     read_options: Arc<SstableIteratorReadOptions>,
+    prefix_hint: Option<&[u8]>,
 ) -> HummockResult<SharedBufferIteratorType<T::Direction, T::SstableIteratorType>> {
     let mut ordered_iters = Vec::with_capacity(uncommitted_data.len());
     for data_list in uncommitted_data {
@@ -137,6 +143,12 @@ pub(crate) async fn build_ordered_merge_iter<T: HummockIteratorType>(
                 }
                 UncommittedData::Sst((_, table_info)) => {
                     let table = sstable_store.sstable(table_info, local_stats).await?;
+                    if let Some(prefix_hint) = prefix_hint {
+                        if !hit_sstable_prefix_bloom_filter(table.value(), prefix_hint, local_stats)
+                        {
+                            continue;
+                        }
+                    }
                     data_iters.push(UncommittedDataIteratorType::Second(
                         T::SstableIteratorType::create(
                             table,
@@ -160,6 +172,27 @@ pub(crate) async fn build_ordered_merge_iter<T: HummockIteratorType>(
     Ok(OrderedMergeIteratorInner::new(ordered_iters))
 }
 
+/// Collects the range tombstones of the batches and ssts in `uncommitted_data` into `tombstones`.
+pub(crate) async fn collect_delete_range_tombstones(
+    uncommitted_data: &OrderSortedUncommittedData,
+    sstable_store: Arc<SstableStore>,
+    local_stats: &mut StoreLocalStatistic,
+    tombstones: &mut Vec<DeleteRangeTombstone>,
+) -> HummockResult<()> {
+    for data in uncommitted_data.iter().flatten() {
+        match data {
+            UncommittedData::Batch(batch) => {
+                tombstones.extend(batch.get_delete_range_tombstones());
+            }
+            UncommittedData::Sst((_, table_info)) => {
+                let table = sstable_store.sstable(table_info, local_stats).await?;
+                tombstones.extend_from_slice(&table.value().meta.range_tombstone_list);
+            }
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 pub struct SharedBuffer {
     uncommitted_data: KeyIndexedUncommittedData,
@@ -29,13 +29,15 @@ use crate::hummock::iterator::{
 };
 use crate::hummock::utils::MemoryTracker;
 use crate::hummock::value::HummockValue;
-use crate::hummock::{key, HummockEpoch, HummockResult, MemoryLimiter};
+use crate::hummock::{key, DeleteRangeTombstone, HummockEpoch, HummockResult, MemoryLimiter};
 
 pub(crate) type SharedBufferItem = (Bytes, HummockValue<Bytes>);
 pub type SharedBufferBatchId = u64;
 
 pub(crate) struct SharedBufferBatchInner {
     payload: Vec<SharedBufferItem>,
+    /// `[start, end)` user key ranges deleted in the epoch of the batch.
+    range_tombstones: Vec<(Bytes, Bytes)>,
     size: usize,
     _tracker: Option<MemoryTracker>,
     batch_id: SharedBufferBatchId,
@@ -53,15 +55,15 @@ impl Debug for SharedBufferBatchInner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "SharedBufferBatchInner {{ payload: {:?}, size: {} }}",
-            self.payload, self.size
+            "SharedBufferBatchInner {{ payload: {:?}, range_tombstones: {:?}, size: {} }}",
+            self.payload, self.range_tombstones, self.size
         )
     }
 }
 
 impl PartialEq for SharedBufferBatchInner {
     fn eq(&self, other: &Self) -> bool {
-        self.payload == other.payload
+        self.payload == other.payload && self.range_tombstones == other.range_tombstones
     }
 }
 
@@ -92,6 +94,7 @@ impl SharedBufferBatch {
         Self {
             inner: Arc::new(SharedBufferBatchInner {
                 payload: sorted_items,
+                range_tombstones: vec![],
                 size,
                 _tracker: None,
                 batch_id: SHARED_BUFFER_BATCH_ID_GENERATOR.fetch_add(1, Relaxed),
@@ -104,12 +107,19 @@ impl SharedBufferBatch {
 
     pub async fn build(
         sorted_items: Vec<SharedBufferItem>,
+        mut range_tombstones: Vec<(Bytes, Bytes)>,
         epoch: HummockEpoch,
         limiter: Option<&MemoryLimiter>,
         compaction_group_id: CompactionGroupId,
         table_id: TableId,
     ) -> Self {
-        let size = Self::measure_batch_size(&sorted_items);
+        range_tombstones.retain(|(start, end)| start < end);
+        range_tombstones.sort();
+        let size = Self::measure_batch_size(&sorted_items)
+            + range_tombstones
+                .iter()
+                .map(|(start, end)| start.len() + end.len())
+                .sum::<usize>();
         let tracker = if let Some(limiter) = limiter {
             limiter.require_memory(size as u64).await
         } else {
@@ -124,6 +134,7 @@ impl SharedBufferBatch {
         Self {
             inner: Arc::new(SharedBufferBatchInner {
                 payload: sorted_items,
+                range_tombstones,
                 size,
                 _tracker: tracker,
                 batch_id: SHARED_BUFFER_BATCH_ID_GENERATOR.fetch_add(1, Relaxed),
@@ -142,7 +153,7 @@ impl SharedBufferBatch {
                 k.len() + {
                     match v {
                         HummockValue::Put(val) => val.len(),
-                        HummockValue::Delete => 0,
+                        HummockValue::Delete | HummockValue::Blob(_) => 0,
                     }
                 }
             })
@@ -161,6 +172,27 @@ impl SharedBufferBatch {
         }
     }
 
+    /// Returns whether `user_key` is covered by a range tombstone of the batch. Note that a key
+    /// written in the batch is not deleted by the tombstones of the same batch.
+    pub fn check_delete_by_range(&self, user_key: &[u8]) -> bool {
+        self.inner
+            .range_tombstones
+            .iter()
+            .any(|(start, end)| start.as_ref() <= user_key && user_key < end.as_ref())
+    }
+
+    pub fn get_delete_range_tombstones(&self) -> Vec<DeleteRangeTombstone> {
+        self.inner
+            .range_tombstones
+            .iter()
+            .map(|(start, end)| DeleteRangeTombstone::new(start.to_vec(), end.to_vec(), self.epoch))
+            .collect()
+    }
+
+    pub fn has_range_tombstone(&self) -> bool {
+        !self.inner.range_tombstones.is_empty()
+    }
+
     pub fn into_directed_iter<D: HummockIteratorDirection>(self) -> SharedBufferBatchIterator<D> {
         SharedBufferBatchIterator::<D>::new(self.inner)
     }
@@ -185,12 +217,37 @@ impl SharedBufferBatch {
         &self.inner.last().unwrap().0
     }
 
+    /// Returns the smallest user key of the batch, including the start of range tombstones.
     pub fn start_user_key(&self) -> &[u8] {
-        key::user_key(&self.inner.first().unwrap().0)
+        self.inner
+            .first()
+            .map(|(k, _)| key::user_key(k))
+            .into_iter()
+            .chain(
+                self.inner
+                    .range_tombstones
+                    .iter()
+                    .map(|(start, _)| start.as_ref()),
+            )
+            .min()
+            .unwrap()
     }
 
+    /// Returns the largest user key of the batch, including the (exclusive) end of range
+    /// tombstones.
     pub fn end_user_key(&self) -> &[u8] {
-        key::user_key(&self.inner.last().unwrap().0)
+        self.inner
+            .last()
+            .map(|(k, _)| key::user_key(k))
+            .into_iter()
+            .chain(
+                self.inner
+                    .range_tombstones
+                    .iter()
+                    .map(|(_, end)| end.as_ref()),
+            )
+            .max()
+            .unwrap()
     }
 
     pub fn epoch(&self) -> u64 {
@@ -571,4 +628,46 @@ mod tests {
         }
         assert!(!iter.is_valid());
     }
+
+    #[tokio::test]
+    async fn test_shared_buffer_batch_range_tombstone() {
+        let epoch = 2;
+        let test_user_key = |idx| user_key(&iterator_test_key_of(idx)).to_vec();
+        let shared_buffer_items = vec![(
+            Bytes::from(iterator_test_key_of_epoch(2, epoch)),
+            HummockValue::put(Bytes::from("value2")),
+        )];
+        let range_tombstones = vec![(Bytes::from(test_user_key(1)), Bytes::from(test_user_key(4)))];
+        let shared_buffer_batch = SharedBufferBatch::build(
+            shared_buffer_items,
+            range_tombstones,
+            epoch,
+            None,
+            StaticCompactionGroupId::StateDefault.into(),
+            Default::default(),
+        )
+        .await;
+
+        // The key range covers the tombstone.
+        assert_eq!(shared_buffer_batch.start_user_key(), test_user_key(1));
+        assert_eq!(shared_buffer_batch.end_user_key(), test_user_key(4));
+
+        assert!(!shared_buffer_batch.check_delete_by_range(&test_user_key(0)));
+        assert!(shared_buffer_batch.check_delete_by_range(&test_user_key(1)));
+        assert!(shared_buffer_batch.check_delete_by_range(&test_user_key(3)));
+        assert!(!shared_buffer_batch.check_delete_by_range(&test_user_key(4)));
+        // The key written in the batch is not deleted by the tombstone of the same epoch.
+        assert_eq!(
+            shared_buffer_batch.get(&test_user_key(2)),
+            Some(HummockValue::put(Bytes::from("value2")))
+        );
+        assert_eq!(
+            shared_buffer_batch.get_delete_range_tombstones(),
+            vec![DeleteRangeTombstone::new(
+                test_user_key(1),
+                test_user_key(4),
+                epoch
+            )]
+        );
+    }
 }
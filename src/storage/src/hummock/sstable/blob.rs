@@ -0,0 +1,97 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::{BufMut, BytesMut};
+use piestream_hummock_sdk::HummockSstableId;
+
+use crate::hummock::sstable_store::SstableStoreRef;
+use crate::hummock::{BlobPointer, HummockResult, SstableIdManagerRef};
+
+/// Writes large user values into blob files for key-value separation, so that they are not
+/// rewritten by later compactions. Only the [`BlobPointer`] is stored in the sstable.
+///
+/// A blob file takes a new id from the SST id space, and is uploaded once it reaches `capacity`
+/// or the writer finishes.
+pub struct BlobWriter {
+    sstable_id_manager: SstableIdManagerRef,
+    sstable_store: SstableStoreRef,
+    /// Maps `table_id` to the threshold of value size to be separated.
+    thresholds: Arc<HashMap<u32, usize>>,
+    capacity: usize,
+    /// Id and data of the blob file being written.
+    current: Option<(HummockSstableId, BytesMut)>,
+}
+
+impl BlobWriter {
+    pub fn new(
+        sstable_id_manager: SstableIdManagerRef,
+        sstable_store: SstableStoreRef,
+        thresholds: Arc<HashMap<u32, usize>>,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            sstable_id_manager,
+            sstable_store,
+            thresholds,
+            capacity,
+            current: None,
+        }
+    }
+
+    /// Returns whether the value of the table should be written to blob files.
+    pub fn should_separate(&self, table_id: Option<u32>, value: &[u8]) -> bool {
+        table_id
+            .and_then(|table_id| self.thresholds.get(&table_id))
+            .map(|threshold| value.len() >= *threshold)
+            .unwrap_or(false)
+    }
+
+    /// Appends `value` to the current blob file and returns the pointer to it.
+    pub async fn append(&mut self, value: &[u8]) -> HummockResult<BlobPointer> {
+        if self.current.is_none() {
+            let blob_id = self.sstable_id_manager.get_new_sst_id().await?;
+            self.current = Some((blob_id, BytesMut::with_capacity(self.capacity)));
+        }
+        let (blob_id, buf) = self.current.as_mut().unwrap();
+        let pointer = BlobPointer {
+            blob_id: *blob_id,
+            offset: buf.len() as u32,
+            len: value.len() as u32,
+        };
+        buf.put_slice(value);
+        if buf.len() >= self.capacity {
+            self.flush().await?;
+        }
+        Ok(pointer)
+    }
+
+    /// Uploads the current blob file, if any.
+    async fn flush(&mut self) -> HummockResult<()> {
+        if let Some((blob_id, buf)) = self.current.take() {
+            self.sstable_store
+                .put_blob_data(blob_id, buf.freeze())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Uploads the remaining data. The blob files must be uploaded before the sstables referencing
+    /// them are reported.
+    pub async fn finish(mut self) -> HummockResult<()> {
+        self.flush().await
+    }
+}
@@ -15,9 +15,11 @@
 use std::cmp::Ordering;
 use std::io::{Read, Write};
 use std::ops::Range;
+use std::sync::Arc;
 
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use piestream_hummock_sdk::VersionedComparator;
+use zstd::dict::{DecoderDictionary, EncoderDictionary};
 use {lz4, zstd};
 
 use super::utils::{bytes_diff, xxhash64_verify, CompressionAlgorithm};
@@ -27,6 +29,51 @@ use crate::hummock::{HummockError, HummockResult};
 pub const DEFAULT_BLOCK_SIZE: usize = 4 * 1024;
 pub const DEFAULT_RESTART_INTERVAL: usize = 16;
 pub const DEFAULT_ENTRY_SIZE: usize = 24; // table_id(u64) + primary_key(u64) + epoch(u64)
+const ZSTD_COMPRESSION_LEVEL: i32 = 4;
+
+/// Prepares the zstd `dictionary` for compressing blocks, so that it is digested once rather than
+/// once per block. Returns `None` if `dictionary` is empty.
+pub fn prepare_encoder_dictionary(dictionary: &[u8]) -> Option<Arc<EncoderDictionary<'static>>> {
+    if dictionary.is_empty() {
+        return None;
+    }
+    Some(Arc::new(EncoderDictionary::copy(
+        dictionary,
+        ZSTD_COMPRESSION_LEVEL,
+    )))
+}
+
+/// Prepares the zstd `dictionary` for decompressing blocks. Returns `None` if `dictionary` is
+/// empty.
+pub fn prepare_decoder_dictionary(dictionary: &[u8]) -> Option<Arc<DecoderDictionary<'static>>> {
+    if dictionary.is_empty() {
+        return None;
+    }
+    Some(Arc::new(DecoderDictionary::copy(dictionary)))
+}
+
+/// A zstd dictionary trained once for a compaction group and shared by the sstables it compacts
+/// into. It is stored as an object named by `id`, which is referenced from the meta of each
+/// sstable compressed with it.
+#[derive(Clone)]
+pub struct CompressionDictionary {
+    /// Derived from the content, so that a dictionary is only stored once. Never 0.
+    pub id: u64,
+    pub data: Bytes,
+    pub encoder_dictionary: Arc<EncoderDictionary<'static>>,
+}
+
+impl CompressionDictionary {
+    /// Returns `None` if `data` is empty.
+    pub fn new(data: Bytes) -> Option<Self> {
+        let encoder_dictionary = prepare_encoder_dictionary(&data)?;
+        Some(Self {
+            id: xxhash64_checksum(&data).max(1),
+            data,
+            encoder_dictionary,
+        })
+    }
+}
 
 #[derive(Clone)]
 pub struct Block {
@@ -40,6 +87,16 @@ pub struct Block {
 
 impl Block {
     pub fn decode(buf: Bytes, uncompressed_capacity: usize) -> HummockResult<Self> {
+        Self::decode_with_dictionary(buf, uncompressed_capacity, None)
+    }
+
+    /// Decodes a block which may be compressed with the zstd `dictionary` of its sstable, prepared
+    /// by [`prepare_decoder_dictionary`].
+    pub fn decode_with_dictionary(
+        buf: Bytes,
+        uncompressed_capacity: usize,
+        dictionary: Option<&DecoderDictionary<'_>>,
+    ) -> HummockResult<Self> {
         // Verify checksum.
         let xxhash64_checksum = (&buf[buf.len() - 8..]).get_u64_le();
         xxhash64_verify(&buf[..buf.len() - 8], xxhash64_checksum)?;
@@ -69,6 +126,17 @@ impl Block {
                 debug_assert_eq!(decoded.capacity(), uncompressed_capacity);
                 Bytes::from(decoded)
             }
+            CompressionAlgorithm::ZstdDictionary => {
+                let dictionary = dictionary.ok_or_else(|| {
+                    HummockError::decode_error("zstd dictionary is required to decode block")
+                })?;
+                let decoded = zstd::bulk::Decompressor::with_prepared_dictionary(dictionary)
+                    .and_then(|mut decompressor| {
+                        decompressor.decompress(compressed_data, uncompressed_capacity)
+                    })
+                    .map_err(HummockError::decode_error)?;
+                Bytes::from(decoded)
+            }
         };
 
         Ok(Self::decode_from_raw(buf))
@@ -225,6 +293,8 @@ pub struct BlockBuilder {
     entry_count: usize,
     /// Compression algorithm.
     compression_algorithm: CompressionAlgorithm,
+    /// Prepared dictionary for [`CompressionAlgorithm::ZstdDictionary`].
+    compression_dictionary: Option<Arc<EncoderDictionary<'static>>>,
 }
 
 impl BlockBuilder {
@@ -239,9 +309,17 @@ impl BlockBuilder {
             last_key: vec![],
             entry_count: 0,
             compression_algorithm: options.compression_algorithm,
+            compression_dictionary: None,
         }
     }
 
+    pub fn set_compression_dictionary(
+        &mut self,
+        dictionary: Option<Arc<EncoderDictionary<'static>>>,
+    ) {
+        self.compression_dictionary = dictionary;
+    }
+
     /// Appends a kv pair to the block.
     ///
     /// NOTE: Key must be added in ASCEND order.
@@ -320,46 +398,23 @@ impl BlockBuilder {
     ///
     /// Panic if there is compression error.
     pub fn build(&mut self) -> &[u8] {
+        self.build_uncompressed();
+        self.buf = seal_block(
+            std::mem::take(&mut self.buf),
+            self.compression_algorithm,
+            self.compression_dictionary.as_deref(),
+        );
+        self.buf.as_ref()
+    }
+
+    /// Finishes building block without compression method and checksum. The block can be sealed
+    /// later by [`seal_block`].
+    pub fn build_uncompressed(&mut self) -> &[u8] {
         assert!(self.entry_count > 0);
         for restart_point in &self.restart_points {
             self.buf.put_u32_le(*restart_point);
         }
         self.buf.put_u32_le(self.restart_points.len() as u32);
-        match self.compression_algorithm {
-            CompressionAlgorithm::None => (),
-            CompressionAlgorithm::Lz4 => {
-                let mut encoder = lz4::EncoderBuilder::new()
-                    .level(4)
-                    .build(BytesMut::with_capacity(self.buf.len()).writer())
-                    .map_err(HummockError::encode_error)
-                    .unwrap();
-                encoder
-                    .write_all(&self.buf[..])
-                    .map_err(HummockError::encode_error)
-                    .unwrap();
-                let (writer, result) = encoder.finish();
-                result.map_err(HummockError::encode_error).unwrap();
-                self.buf = writer.into_inner();
-            }
-            CompressionAlgorithm::Zstd => {
-                let mut encoder =
-                    zstd::Encoder::new(BytesMut::with_capacity(self.buf.len()).writer(), 4)
-                        .map_err(HummockError::encode_error)
-                        .unwrap();
-                encoder
-                    .write_all(&self.buf[..])
-                    .map_err(HummockError::encode_error)
-                    .unwrap();
-                let writer = encoder
-                    .finish()
-                    .map_err(HummockError::encode_error)
-                    .unwrap();
-                self.buf = writer.into_inner();
-            }
-        };
-        self.compression_algorithm.encode(&mut self.buf);
-        let checksum = xxhash64_checksum(&self.buf);
-        self.buf.put_u64_le(checksum);
         self.buf.as_ref()
     }
 
@@ -370,6 +425,69 @@ impl BlockBuilder {
     }
 }
 
+/// Compresses an uncompressed block built by [`BlockBuilder::build_uncompressed`], and appends the
+/// compression method and checksum. [`CompressionAlgorithm::ZstdDictionary`] falls back to
+/// [`CompressionAlgorithm::Zstd`] if there is no `dictionary`.
+///
+/// # Panics
+///
+/// Panic if there is compression error.
+pub fn seal_block(
+    mut buf: BytesMut,
+    compression_algorithm: CompressionAlgorithm,
+    dictionary: Option<&EncoderDictionary<'_>>,
+) -> BytesMut {
+    let compression_algorithm = match (compression_algorithm, dictionary) {
+        (CompressionAlgorithm::ZstdDictionary, None) => CompressionAlgorithm::Zstd,
+        (algorithm, _) => algorithm,
+    };
+    match compression_algorithm {
+        CompressionAlgorithm::None => (),
+        CompressionAlgorithm::Lz4 => {
+            let mut encoder = lz4::EncoderBuilder::new()
+                .level(4)
+                .build(BytesMut::with_capacity(buf.len()).writer())
+                .map_err(HummockError::encode_error)
+                .unwrap();
+            encoder
+                .write_all(&buf[..])
+                .map_err(HummockError::encode_error)
+                .unwrap();
+            let (writer, result) = encoder.finish();
+            result.map_err(HummockError::encode_error).unwrap();
+            buf = writer.into_inner();
+        }
+        CompressionAlgorithm::Zstd => {
+            let mut encoder = zstd::Encoder::new(
+                BytesMut::with_capacity(buf.len()).writer(),
+                ZSTD_COMPRESSION_LEVEL,
+            )
+            .map_err(HummockError::encode_error)
+            .unwrap();
+            encoder
+                .write_all(&buf[..])
+                .map_err(HummockError::encode_error)
+                .unwrap();
+            let writer = encoder
+                .finish()
+                .map_err(HummockError::encode_error)
+                .unwrap();
+            buf = writer.into_inner();
+        }
+        CompressionAlgorithm::ZstdDictionary => {
+            let compressed = zstd::bulk::Compressor::with_prepared_dictionary(dictionary.unwrap())
+                .and_then(|mut compressor| compressor.compress(&buf[..]))
+                .map_err(HummockError::encode_error)
+                .unwrap();
+            buf = BytesMut::from(&compressed[..]);
+        }
+    };
+    compression_algorithm.encode(&mut buf);
+    let checksum = xxhash64_checksum(&buf);
+    buf.put_u64_le(checksum);
+    buf
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
@@ -459,6 +577,40 @@ mod tests {
         assert!(!bi.is_valid());
     }
 
+    #[test]
+    fn test_dictionary_compressed_block_enc_dec() {
+        let options = BlockBuilderOptions {
+            compression_algorithm: CompressionAlgorithm::ZstdDictionary,
+            ..Default::default()
+        };
+        let mut builder = BlockBuilder::new(options);
+        // Any content can be used as a raw zstd dictionary.
+        let dictionary = b"k1v01k2v02k3v03";
+        builder.set_compression_dictionary(prepare_encoder_dictionary(dictionary));
+        builder.add(&full_key(b"k1", 1), b"v01");
+        builder.add(&full_key(b"k2", 2), b"v02");
+        let capacity = builder.uncompressed_block_size();
+        let buf = Bytes::from(builder.build().to_vec());
+        assert!(Block::decode(buf.clone(), capacity).is_err());
+        let dictionary = prepare_decoder_dictionary(dictionary);
+        let block =
+            Box::new(Block::decode_with_dictionary(buf, capacity, dictionary.as_deref()).unwrap());
+        let mut bi = BlockIterator::new(BlockHolder::from_owned_block(block));
+
+        bi.seek_to_first();
+        assert!(bi.is_valid());
+        assert_eq!(&full_key(b"k1", 1)[..], bi.key());
+        assert_eq!(b"v01", bi.value());
+
+        bi.next();
+        assert!(bi.is_valid());
+        assert_eq!(&full_key(b"k2", 2)[..], bi.key());
+        assert_eq!(b"v02", bi.value());
+
+        bi.next();
+        assert!(!bi.is_valid());
+    }
+
     pub fn full_key(user_key: &[u8], epoch: u64) -> Bytes {
         let mut buf = BytesMut::with_capacity(user_key.len() + 8);
         buf.put_slice(user_key);
@@ -12,28 +12,38 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
+use itertools::Itertools;
 use piestream_common::config::StorageConfig;
 use piestream_hummock_sdk::filter_key_extractor::{
     FilterKeyExtractorImpl, FullKeyFilterKeyExtractor,
 };
-use piestream_hummock_sdk::key::{get_table_id, user_key};
-use piestream_pb::hummock::SstableInfo;
+use piestream_hummock_sdk::key::{get_epoch, get_table_id, key_with_epoch, user_key};
+use piestream_hummock_sdk::{CompactionGroupId, HummockEpoch, HummockSstableId};
+use piestream_pb::hummock::{SstableInfo, TableStats};
 
 use super::bloom::Bloom;
 use super::utils::CompressionAlgorithm;
 use super::{
-    BlockBuilder, BlockBuilderOptions, BlockMeta, SstableMeta, SstableWriter, DEFAULT_BLOCK_SIZE,
-    DEFAULT_ENTRY_SIZE, DEFAULT_RESTART_INTERVAL, VERSION,
+    seal_block, BlobWriter, BlockBuilder, BlockBuilderOptions, BlockMeta, CompressionDictionary,
+    DeleteRangeTombstone, SstableMeta, SstableWriter, DEFAULT_BLOCK_SIZE, DEFAULT_ENTRY_SIZE,
+    DEFAULT_RESTART_INTERVAL, VERSION,
 };
+use crate::hummock::sstable_store::SstableStoreRef;
 use crate::hummock::value::HummockValue;
 use crate::hummock::HummockResult;
 
 pub const DEFAULT_SSTABLE_SIZE: usize = 4 * 1024 * 1024;
 pub const DEFAULT_BLOOM_FALSE_POSITIVE: f64 = 0.1;
+/// Uncompressed size of the leading blocks sampled to train the zstd dictionary of a compaction
+/// group.
+const COMPRESSION_DICTIONARY_SAMPLE_SIZE: usize = 1024 * 1024;
+/// Sampled blocks are split into chunks of this size, as zstd trains better on many small samples.
+const COMPRESSION_DICTIONARY_SAMPLE_CHUNK_SIZE: usize = 1024;
+const MAX_COMPRESSION_DICTIONARY_SIZE: usize = 16 * 1024;
 #[derive(Clone, Debug)]
 pub struct SstableBuilderOptions {
     /// Approximate sstable capacity.
@@ -79,6 +89,9 @@ pub struct SstableBuilderOutput<WO> {
     pub writer_output: WO,
     pub avg_key_size: usize,
     pub avg_value_size: usize,
+    pub compression_algorithm: CompressionAlgorithm,
+    /// Uncompressed size of blocks divided by compressed size.
+    pub compression_ratio: f64,
 }
 
 pub struct SstableBuilder<W: SstableWriter> {
@@ -95,12 +108,32 @@ pub struct SstableBuilder<W: SstableWriter> {
     last_table_id: u32,
     /// Hashes of user keys.
     user_key_hashes: Vec<u32>,
+    /// Hashes of the prefixes extracted by `filter_key_extractor`.
+    prefix_key_hashes: Vec<u32>,
     last_full_key: Vec<u8>,
+    /// Range tombstones to be stored in the meta.
+    range_tombstones: Vec<DeleteRangeTombstone>,
     key_count: usize,
     sstable_id: u64,
     raw_value: BytesMut,
     filter_key_extractor: Arc<FilterKeyExtractorImpl>,
-    last_bloom_filter_key_length: usize,
+    last_prefix_key_length: usize,
+    /// Writes large values into blob files if key-value separation is enabled.
+    blob_writer: Option<BlobWriter>,
+    /// Blob files referenced by the added values.
+    blob_ids: BTreeSet<HummockSstableId>,
+    /// Statistics of the added keys, grouped by `table_id`.
+    table_stats: HashMap<u32, TableStats>,
+    /// Zstd dictionary of the compaction group, for [`CompressionAlgorithm::ZstdDictionary`].
+    /// `None` until the dictionary is trained if the group has none yet.
+    compression_dictionary: Option<CompressionDictionary>,
+    /// Where the dictionary is stored once trained from the leading blocks, if the group has none
+    /// yet. Blocks are compressed by plain zstd if there is no dictionary to train.
+    compression_dictionary_store: Option<(SstableStoreRef, CompactionGroupId)>,
+    /// Uncompressed blocks sampled to train the dictionary, with their indexes in `block_metas`.
+    /// They are compressed and written once the dictionary is trained.
+    pending_blocks: Vec<(usize, BytesMut)>,
+    pending_blocks_size: usize,
 
     total_key_size: usize,
     total_value_size: usize,
@@ -137,13 +170,22 @@ impl<W: SstableWriter> SstableBuilder<W> {
             block_metas: Vec::with_capacity(options.capacity / options.block_capacity + 1),
             table_ids: BTreeSet::new(),
             user_key_hashes: Vec::with_capacity(options.capacity / DEFAULT_ENTRY_SIZE + 1),
+            prefix_key_hashes: vec![],
             last_table_id: 0,
             raw_value: BytesMut::new(),
             last_full_key: vec![],
+            range_tombstones: vec![],
             key_count: 0,
             sstable_id,
             filter_key_extractor,
-            last_bloom_filter_key_length: 0,
+            last_prefix_key_length: 0,
+            blob_writer: None,
+            blob_ids: BTreeSet::new(),
+            table_stats: HashMap::new(),
+            compression_dictionary: None,
+            compression_dictionary_store: None,
+            pending_blocks: vec![],
+            pending_blocks_size: 0,
             total_key_size: 0,
             total_value_size: 0,
             stale_key_count: 0,
@@ -151,13 +193,46 @@ impl<W: SstableWriter> SstableBuilder<W> {
         }
     }
 
+    /// Enables key-value separation. Large values added later are written by `blob_writer`.
+    pub fn with_blob_writer(mut self, blob_writer: BlobWriter) -> Self {
+        self.blob_writer = Some(blob_writer);
+        self
+    }
+
+    /// Compresses blocks with the zstd dictionary of compaction group `group_id`. If the group has
+    /// no dictionary in `sstable_store` yet, it is trained from the leading blocks and stored.
+    pub fn with_compression_dictionary(
+        mut self,
+        sstable_store: SstableStoreRef,
+        group_id: CompactionGroupId,
+    ) -> Self {
+        match sstable_store.compression_dictionary(group_id) {
+            Some(dictionary) => {
+                self.block_builder
+                    .set_compression_dictionary(Some(dictionary.encoder_dictionary.clone()));
+                self.compression_dictionary = Some(dictionary);
+            }
+            None => self.compression_dictionary_store = Some((sstable_store, group_id)),
+        }
+        self
+    }
+
     /// Add kv pair to sstable.
     pub async fn add(
         &mut self,
         full_key: &[u8],
-        value: HummockValue<&[u8]>,
+        mut value: HummockValue<&[u8]>,
         is_new_user_key: bool,
     ) -> HummockResult<()> {
+        if let (Some(blob_writer), HummockValue::Put(val)) = (self.blob_writer.as_mut(), value) {
+            if blob_writer.should_separate(get_table_id(full_key), val) {
+                value = HummockValue::Blob(blob_writer.append(val).await?);
+            }
+        }
+        if let HummockValue::Blob(pointer) = value {
+            self.blob_ids.insert(pointer.blob_id);
+        }
+
         // Rotate block builder if the previous one has been built.
         if self.block_builder.is_empty() {
             self.block_metas.push(BlockMeta {
@@ -171,25 +246,29 @@ impl<W: SstableWriter> SstableBuilder<W> {
         // TODO: refine me
         value.encode(&mut self.raw_value);
         if is_new_user_key {
-            let mut extract_key = user_key(full_key);
+            let ukey = user_key(full_key);
             if let Some(table_id) = get_table_id(full_key) {
                 if self.last_table_id != table_id {
                     self.table_ids.insert(table_id);
                     self.last_table_id = table_id;
                 }
             }
-            extract_key = self.filter_key_extractor.extract(extract_key);
+            self.user_key_hashes.push(farmhash::fingerprint32(ukey));
 
-            // add bloom_filter check
-            // 1. not empty_key
+            let extract_key = self.filter_key_extractor.extract(ukey);
+            // add prefix bloom filter check
+            // 1. extract_key is non-empty. It's added even if it's the full user key, as prefix
+            // iterators only consult the prefix bloom filter
             // 2. extract_key key is not duplicate
-            if !extract_key.is_empty()
-                && (extract_key != &self.last_full_key[0..self.last_bloom_filter_key_length])
-            {
-                // avoid duplicate add to bloom filter
-                self.user_key_hashes
-                    .push(farmhash::fingerprint32(extract_key));
-                self.last_bloom_filter_key_length = extract_key.len();
+            if !extract_key.is_empty() {
+                if extract_key != &self.last_full_key[0..self.last_prefix_key_length] {
+                    // avoid duplicate add to bloom filter
+                    self.prefix_key_hashes
+                        .push(farmhash::fingerprint32(extract_key));
+                    self.last_prefix_key_length = extract_key.len();
+                }
+            } else {
+                self.last_prefix_key_length = 0;
             }
         } else {
             self.stale_key_count += 1;
@@ -199,6 +278,20 @@ impl<W: SstableWriter> SstableBuilder<W> {
         self.block_builder.add(full_key, self.raw_value.as_ref());
         self.total_key_size += full_key.len();
         self.total_value_size += self.raw_value.len();
+        if let Some(table_id) = get_table_id(full_key) {
+            let epoch = get_epoch(full_key);
+            let stats = self
+                .table_stats
+                .entry(table_id)
+                .or_insert_with(|| TableStats {
+                    min_epoch: epoch,
+                    ..Default::default()
+                });
+            stats.total_key_count += 1;
+            stats.total_key_size += full_key.len() as u64;
+            stats.total_value_size += self.raw_value.len() as u64;
+            stats.min_epoch = std::cmp::min(stats.min_epoch, epoch);
+        }
         self.raw_value.clear();
 
         self.last_full_key.clear();
@@ -212,6 +305,17 @@ impl<W: SstableWriter> SstableBuilder<W> {
         Ok(())
     }
 
+    /// Adds range tombstones to sstable. The key range of the sstable is extended to cover them.
+    pub fn add_delete_ranges(&mut self, tombstones: Vec<DeleteRangeTombstone>) {
+        for tombstone in &tombstones {
+            let start_key = key_with_epoch(tombstone.start_user_key.clone(), HummockEpoch::MAX);
+            if let Some(table_id) = get_table_id(&start_key) {
+                self.table_ids.insert(table_id);
+            }
+        }
+        self.range_tombstones.extend(tombstones);
+    }
+
     /// Finish building sst.
     ///
     /// Unlike most LSM-Tree implementations, sstable meta and data are encoded separately.
@@ -225,30 +329,51 @@ impl<W: SstableWriter> SstableBuilder<W> {
     /// | Block 0 | ... | Block N-1 | N (4B) |
     /// ```
     pub async fn finish(mut self) -> HummockResult<SstableBuilderOutput<W::Output>> {
-        let smallest_key = self.block_metas[0].smallest_key.clone();
-        let largest_key = self.last_full_key.clone();
+        let mut smallest_key = self
+            .block_metas
+            .first()
+            .map(|block_meta| block_meta.smallest_key.clone())
+            .unwrap_or_default();
+        let mut largest_key = self.last_full_key.clone();
+        // A right bound with `HummockEpoch::MAX` is exclusive, since no key is written with that
+        // epoch. See `can_concat`.
+        for tombstone in &self.range_tombstones {
+            if smallest_key.is_empty()
+                || tombstone.start_user_key.as_slice() < user_key(&smallest_key)
+            {
+                smallest_key = key_with_epoch(tombstone.start_user_key.clone(), HummockEpoch::MAX);
+            }
+            if largest_key.is_empty() || tombstone.end_user_key.as_slice() > user_key(&largest_key)
+            {
+                largest_key = key_with_epoch(tombstone.end_user_key.clone(), HummockEpoch::MAX);
+            }
+        }
 
         self.build_block().await?;
+        if !self.pending_blocks.is_empty() {
+            self.train_compression_dictionary().await?;
+        }
+        if let Some(blob_writer) = self.blob_writer.take() {
+            blob_writer.finish().await?;
+        }
         let meta_offset = self.writer.data_len() as u64;
         assert!(!smallest_key.is_empty());
 
         let mut meta = SstableMeta {
             block_metas: self.block_metas,
-            bloom_filter: if self.options.bloom_false_positive > 0.0 {
-                let bits_per_key = Bloom::bloom_bits_per_key(
-                    self.user_key_hashes.len(),
-                    self.options.bloom_false_positive,
-                );
-                Bloom::build_from_key_hashes(&self.user_key_hashes, bits_per_key)
-            } else {
-                vec![]
-            },
+            bloom_filter: build_bloom_filter(&self.user_key_hashes, &self.options),
             estimated_size: 0,
             key_count: self.key_count as u32,
             smallest_key,
             largest_key,
             version: VERSION,
             meta_offset,
+            range_tombstone_list: self.range_tombstones,
+            prefix_bloom_filter: build_bloom_filter(&self.prefix_key_hashes, &self.options),
+            compression_dictionary_id: self
+                .compression_dictionary
+                .map(|dictionary| dictionary.id)
+                .unwrap_or(0),
         };
         meta.estimated_size = meta.encoded_size() as u32 + meta_offset as u32;
         let sst_info = SstableInfo {
@@ -263,16 +388,35 @@ impl<W: SstableWriter> SstableBuilder<W> {
             meta_offset: meta.meta_offset,
             stale_key_count: self.stale_key_count,
             total_key_count: self.total_key_count,
+            blob_ids: self.blob_ids.into_iter().collect(),
+            table_stats: self.table_stats,
         };
         tracing::trace!(
-            "meta_size {} bloom_filter_size {}  add_key_counts {} ",
+            "meta_size {} bloom_filter_size {} prefix_bloom_filter_size {} add_key_counts {} ",
             meta.encoded_size(),
             meta.bloom_filter.len(),
+            meta.prefix_bloom_filter.len(),
             self.key_count,
         );
-        let bloom_filter_size = meta.bloom_filter.len();
-        let avg_key_size = self.total_key_size / self.key_count;
-        let avg_value_size = self.total_value_size / self.key_count;
+        let bloom_filter_size = meta.bloom_filter.len() + meta.prefix_bloom_filter.len();
+        let uncompressed_size = meta
+            .block_metas
+            .iter()
+            .map(|block_meta| block_meta.uncompressed_size as u64)
+            .sum::<u64>();
+        let compression_ratio = if meta_offset == 0 {
+            1.0
+        } else {
+            uncompressed_size as f64 / meta_offset as f64
+        };
+        let (avg_key_size, avg_value_size) = if self.key_count == 0 {
+            (0, 0)
+        } else {
+            (
+                self.total_key_size / self.key_count,
+                self.total_value_size / self.key_count,
+            )
+        };
 
         let writer_output = self.writer.finish(meta).await?;
         Ok(SstableBuilderOutput::<W::Output> {
@@ -281,13 +425,16 @@ impl<W: SstableWriter> SstableBuilder<W> {
             writer_output,
             avg_key_size,
             avg_value_size,
+            compression_algorithm: self.options.compression_algorithm,
+            compression_ratio,
         })
     }
 
     pub fn approximate_len(&self) -> usize {
         self.writer.data_len()
+            + self.pending_blocks_size
             + self.block_builder.approximate_len()
-            + self.user_key_hashes.len() * 4
+            + (self.user_key_hashes.len() + self.prefix_key_hashes.len()) * 4
     }
 
     async fn build_block(&mut self) -> HummockResult<()> {
@@ -296,8 +443,23 @@ impl<W: SstableWriter> SstableBuilder<W> {
             return Ok(());
         }
 
-        let mut block_meta = self.block_metas.last_mut().unwrap();
+        let block_idx = self.block_metas.len() - 1;
+        let mut block_meta = &mut self.block_metas[block_idx];
         block_meta.uncompressed_size = self.block_builder.uncompressed_block_size() as u32;
+        if self.options.compression_algorithm == CompressionAlgorithm::ZstdDictionary
+            && self.compression_dictionary_store.is_some()
+        {
+            // Keep the block as a sample until there are enough samples to train the dictionary.
+            let block = BytesMut::from(self.block_builder.build_uncompressed());
+            self.pending_blocks_size += block.len();
+            self.pending_blocks.push((block_idx, block));
+            self.block_builder.clear();
+            if self.pending_blocks_size >= COMPRESSION_DICTIONARY_SAMPLE_SIZE {
+                self.train_compression_dictionary().await?;
+            }
+            return Ok(());
+        }
+        block_meta.offset = self.writer.data_len() as u32;
         let block = self.block_builder.build();
         self.writer.write_block(block, block_meta).await?;
         block_meta.len = self.writer.data_len() as u32 - block_meta.offset;
@@ -305,6 +467,59 @@ impl<W: SstableWriter> SstableBuilder<W> {
         Ok(())
     }
 
+    /// Trains and stores the zstd dictionary of the compaction group from the pending blocks, then
+    /// compresses and writes them.
+    async fn train_compression_dictionary(&mut self) -> HummockResult<()> {
+        let (sstable_store, group_id) = self.compression_dictionary_store.take().unwrap();
+        let samples = self
+            .pending_blocks
+            .iter()
+            .flat_map(|(_, block)| block.chunks(COMPRESSION_DICTIONARY_SAMPLE_CHUNK_SIZE))
+            .collect_vec();
+        // Training fails if the samples are too few, e.g. for a small sstable. Blocks are then
+        // compressed by plain zstd, and the dictionary is trained from a later sstable.
+        let dictionary = match zstd::dict::from_samples(&samples, MAX_COMPRESSION_DICTIONARY_SIZE) {
+            Ok(dictionary) => CompressionDictionary::new(Bytes::from(dictionary)),
+            Err(e) => {
+                tracing::debug!(
+                    "failed to train compression dictionary for sst {}: {}",
+                    self.sstable_id,
+                    e
+                );
+                None
+            }
+        };
+        // Another sstable of the group may have stored its dictionary meanwhile. Use the stored
+        // one then, so that the group shares a single dictionary.
+        let dictionary = match dictionary {
+            Some(dictionary) => Some(
+                sstable_store
+                    .put_compression_dictionary(group_id, dictionary)
+                    .await?,
+            ),
+            None => None,
+        };
+        let encoder_dictionary = dictionary
+            .as_ref()
+            .map(|dictionary| dictionary.encoder_dictionary.clone());
+        self.block_builder
+            .set_compression_dictionary(encoder_dictionary.clone());
+        for (block_idx, block) in std::mem::take(&mut self.pending_blocks) {
+            let block = seal_block(
+                block,
+                CompressionAlgorithm::ZstdDictionary,
+                encoder_dictionary.as_deref(),
+            );
+            let block_meta = &mut self.block_metas[block_idx];
+            block_meta.offset = self.writer.data_len() as u32;
+            self.writer.write_block(&block, block_meta).await?;
+            block_meta.len = self.writer.data_len() as u32 - block_meta.offset;
+        }
+        self.pending_blocks_size = 0;
+        self.compression_dictionary = dictionary;
+        Ok(())
+    }
+
     pub fn len(&self) -> usize {
         self.user_key_hashes.len()
     }
@@ -319,14 +534,30 @@ impl<W: SstableWriter> SstableBuilder<W> {
     }
 }
 
+fn build_bloom_filter(key_hashes: &[u32], options: &SstableBuilderOptions) -> Vec<u8> {
+    if options.bloom_false_positive > 0.0 && !key_hashes.is_empty() {
+        let bits_per_key =
+            Bloom::bloom_bits_per_key(key_hashes.len(), options.bloom_false_positive);
+        Bloom::build_from_key_hashes(key_hashes, bits_per_key)
+    } else {
+        vec![]
+    }
+}
+
 #[cfg(test)]
 pub(super) mod tests {
+    use piestream_hummock_sdk::filter_key_extractor::FixedLengthFilterKeyExtractor;
+
     use super::*;
-    use crate::hummock::iterator::test_utils::mock_sstable_store;
+    use crate::hummock::iterator::test_utils::{
+        mock_sstable_store, mock_sstable_store_with_object_store,
+    };
+    use crate::hummock::sstable::{Block, BlockIterator, Sstable};
     use crate::hummock::test_utils::{
         default_builder_opt_for_test, gen_default_test_sstable, mock_sst_writer, test_key_of,
         test_value_of, TEST_KEYS_COUNT,
     };
+    use crate::hummock::BlockHolder;
 
     #[tokio::test]
     #[should_panic]
@@ -386,9 +617,12 @@ pub(super) mod tests {
         let table = gen_default_test_sstable(opts, 0, sstable_store).await;
 
         assert_eq!(table.has_bloom_filter(), with_blooms);
+        // The keys extracted by the full key extractor are added to the prefix bloom filter too.
+        assert_eq!(table.has_prefix_bloom_filter(), with_blooms);
         for i in 0..key_count {
             let full_key = test_key_of(i);
             assert!(!table.surely_not_have_user_key(user_key(full_key.as_slice())));
+            assert!(!table.surely_not_have_prefix(user_key(full_key.as_slice())));
         }
     }
 
@@ -397,4 +631,157 @@ pub(super) mod tests {
         test_with_bloom_filter(false).await;
         test_with_bloom_filter(true).await;
     }
+
+    #[tokio::test]
+    async fn test_prefix_bloom_filter() {
+        let prefix_len = 12;
+        let opt = default_builder_opt_for_test();
+        let mut b = SstableBuilder::new(
+            0,
+            mock_sst_writer(&opt),
+            opt,
+            Arc::new(FilterKeyExtractorImpl::FixedLength(
+                FixedLengthFilterKeyExtractor::new(prefix_len),
+            )),
+        );
+        for i in 0..TEST_KEYS_COUNT {
+            b.add(&test_key_of(i), HummockValue::put(&test_value_of(i)), true)
+                .await
+                .unwrap();
+        }
+        let output = b.finish().await.unwrap();
+        let (_, meta) = output.writer_output;
+        let table = Sstable::new(0, meta);
+
+        assert!(table.has_bloom_filter());
+        assert!(table.has_prefix_bloom_filter());
+        for i in 0..TEST_KEYS_COUNT {
+            let full_key = test_key_of(i);
+            let ukey = user_key(full_key.as_slice());
+            assert!(!table.surely_not_have_user_key(ukey));
+            assert!(!table.surely_not_have_prefix(&ukey[..prefix_len]));
+        }
+    }
+
+    async fn build_and_verify_with_compression_dictionary(
+        sstable_store: SstableStoreRef,
+        group_id: CompactionGroupId,
+    ) -> u64 {
+        let opt = SstableBuilderOptions {
+            compression_algorithm: CompressionAlgorithm::ZstdDictionary,
+            ..default_builder_opt_for_test()
+        };
+        let mut b = SstableBuilder::for_test(0, mock_sst_writer(&opt), opt)
+            .with_compression_dictionary(sstable_store.clone(), group_id);
+        for i in 0..TEST_KEYS_COUNT {
+            b.add(&test_key_of(i), HummockValue::put(&test_value_of(i)), true)
+                .await
+                .unwrap();
+        }
+        let output = b.finish().await.unwrap();
+        assert_eq!(
+            output.compression_algorithm,
+            CompressionAlgorithm::ZstdDictionary
+        );
+        assert!(output.compression_ratio > 1.0);
+        let (data, meta) = output.writer_output;
+
+        // Blocks are written in order even though they are compressed after training.
+        let compression_dictionary = sstable_store
+            .decoder_dictionary(meta.compression_dictionary_id)
+            .await
+            .unwrap();
+        let mut key_idx = 0;
+        let mut offset = 0;
+        for block_meta in &meta.block_metas {
+            assert_eq!(block_meta.offset as usize, offset);
+            offset += block_meta.len as usize;
+            let block_data = data.slice(block_meta.offset as usize..offset);
+            let block = Block::decode_with_dictionary(
+                block_data,
+                block_meta.uncompressed_size as usize,
+                compression_dictionary.as_deref(),
+            )
+            .unwrap();
+            let mut block_iter = BlockIterator::new(BlockHolder::from_owned_block(Box::new(block)));
+            block_iter.seek_to_first();
+            while block_iter.is_valid() {
+                assert_eq!(block_iter.key(), test_key_of(key_idx).as_slice());
+                key_idx += 1;
+                block_iter.next();
+            }
+        }
+        assert_eq!(key_idx, TEST_KEYS_COUNT);
+        meta.compression_dictionary_id
+    }
+
+    #[tokio::test]
+    async fn test_compression_dictionary() {
+        let sstable_store = mock_sstable_store();
+        let dictionary_id =
+            build_and_verify_with_compression_dictionary(sstable_store.clone(), 1).await;
+        assert_ne!(dictionary_id, 0);
+        assert_eq!(
+            sstable_store.compression_dictionary(1).unwrap().id,
+            dictionary_id
+        );
+
+        // The dictionary is trained once and shared by the sstables of the group.
+        assert_eq!(
+            build_and_verify_with_compression_dictionary(sstable_store.clone(), 1).await,
+            dictionary_id
+        );
+
+        // A dictionary is loaded from the object store if it's not cached.
+        let data = sstable_store
+            .store()
+            .read(
+                &sstable_store.get_compression_dictionary_path(dictionary_id),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(data, sstable_store.compression_dictionary(1).unwrap().data);
+        let other_store = mock_sstable_store_with_object_store(sstable_store.store());
+        assert!(other_store
+            .decoder_dictionary(dictionary_id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_table_stats() {
+        let opt = default_builder_opt_for_test();
+        let mut b = SstableBuilder::for_test(0, mock_sst_writer(&opt), opt);
+        let table_key = |table_id: u32, idx: usize, epoch: HummockEpoch| {
+            let mut user_key = vec![b't'];
+            user_key.extend_from_slice(&table_id.to_be_bytes());
+            user_key.extend_from_slice(format!("key_{:05}", idx).as_bytes());
+            key_with_epoch(user_key, epoch)
+        };
+        for table_id in [1, 2] {
+            for i in 0..10 {
+                let epoch = 100 + (i as u64 % 3);
+                b.add(
+                    &table_key(table_id, i, epoch),
+                    HummockValue::put(&test_value_of(i)),
+                    true,
+                )
+                .await
+                .unwrap();
+            }
+        }
+        let info = b.finish().await.unwrap().sst_info;
+        assert_eq!(info.table_ids, vec![1, 2]);
+        assert_eq!(info.table_stats.len(), 2);
+        let key_size = table_key(1, 0, 0).len() as u64;
+        for table_id in [1, 2] {
+            let stats = &info.table_stats[&table_id];
+            assert_eq!(stats.total_key_count, 10);
+            assert_eq!(stats.total_key_size, key_size * 10);
+            assert!(stats.total_value_size > 0);
+            assert_eq!(stats.min_epoch, 100);
+        }
+    }
 }
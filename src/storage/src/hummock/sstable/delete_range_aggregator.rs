@@ -0,0 +1,214 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::{Buf, BufMut};
+use itertools::Itertools;
+use piestream_hummock_sdk::HummockEpoch;
+
+use super::utils::{get_length_prefixed_slice, put_length_prefixed_slice};
+
+/// A range tombstone deletes every version of the user keys in `[start_user_key, end_user_key)`
+/// whose epoch is smaller than `sequence`. Point writes in the same epoch as the tombstone are
+/// kept.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeleteRangeTombstone {
+    pub start_user_key: Vec<u8>,
+    pub end_user_key: Vec<u8>,
+    pub sequence: HummockEpoch,
+}
+
+impl DeleteRangeTombstone {
+    pub fn new(start_user_key: Vec<u8>, end_user_key: Vec<u8>, sequence: HummockEpoch) -> Self {
+        Self {
+            start_user_key,
+            end_user_key,
+            sequence,
+        }
+    }
+
+    /// Format:
+    ///
+    /// ```plain
+    /// | start key len (4B) | start key | end key len (4B) | end key | sequence (8B) |
+    /// ```
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        put_length_prefixed_slice(buf, &self.start_user_key);
+        put_length_prefixed_slice(buf, &self.end_user_key);
+        buf.put_u64_le(self.sequence);
+    }
+
+    pub fn decode(buf: &mut &[u8]) -> Self {
+        let start_user_key = get_length_prefixed_slice(buf);
+        let end_user_key = get_length_prefixed_slice(buf);
+        let sequence = buf.get_u64_le();
+        Self {
+            start_user_key,
+            end_user_key,
+            sequence,
+        }
+    }
+
+    #[inline]
+    pub fn encoded_size(&self) -> usize {
+        16 /* key lens + sequence */ + self.start_user_key.len() + self.end_user_key.len()
+    }
+
+    /// Returns whether `user_key` falls into the range of the tombstone.
+    pub fn contains(&self, user_key: &[u8]) -> bool {
+        self.start_user_key.as_slice() <= user_key && user_key < self.end_user_key.as_slice()
+    }
+
+    /// Clips the tombstone to `[start_user_key, end_user_key)`, where an empty bound is unbounded.
+    /// Returns `None` if nothing is left.
+    pub fn clip(&self, start_user_key: &[u8], end_user_key: &[u8]) -> Option<Self> {
+        let start = std::cmp::max(self.start_user_key.as_slice(), start_user_key);
+        let end = if end_user_key.is_empty() {
+            self.end_user_key.as_slice()
+        } else {
+            std::cmp::min(self.end_user_key.as_slice(), end_user_key)
+        };
+        if start < end {
+            Some(Self::new(start.to_vec(), end.to_vec(), self.sequence))
+        } else {
+            None
+        }
+    }
+}
+
+/// [`DeleteRangeAggregator`] answers whether a key version is deleted by a set of possibly
+/// overlapping range tombstones. Only tombstones with `sequence <= watermark` take effect, which
+/// is the read epoch for reads and the safe epoch for compaction.
+///
+/// The effective tombstones are cut into non-overlapping fragments at every start and end key.
+/// Each fragment records the largest sequence covering it, so a lookup is one binary search.
+#[derive(Default, Debug)]
+pub struct DeleteRangeAggregator {
+    /// All tombstones, including the ones newer than `watermark`.
+    tombstones: Vec<DeleteRangeTombstone>,
+
+    /// `(start user key, max sequence)` of each fragment. A fragment ends at the start of the
+    /// next.
+    fragments: Vec<(Vec<u8>, HummockEpoch)>,
+}
+
+impl DeleteRangeAggregator {
+    pub fn new(mut tombstones: Vec<DeleteRangeTombstone>, watermark: HummockEpoch) -> Self {
+        tombstones.sort_by(|a, b| {
+            a.start_user_key
+                .cmp(&b.start_user_key)
+                .then_with(|| a.end_user_key.cmp(&b.end_user_key))
+                .then_with(|| b.sequence.cmp(&a.sequence))
+        });
+        tombstones.dedup();
+
+        let effective = tombstones
+            .iter()
+            .filter(|tombstone| tombstone.sequence <= watermark)
+            .collect_vec();
+        let fragments = effective
+            .iter()
+            .flat_map(|tombstone| [&tombstone.start_user_key, &tombstone.end_user_key])
+            .sorted()
+            .dedup()
+            .map(|boundary| {
+                // Every tombstone covering the start of a fragment covers the whole fragment,
+                // because the fragments are cut at all tombstone boundaries.
+                let sequence = effective
+                    .iter()
+                    .filter(|tombstone| tombstone.contains(boundary))
+                    .map(|tombstone| tombstone.sequence)
+                    .max()
+                    .unwrap_or(0);
+                (boundary.clone(), sequence)
+            })
+            .collect_vec();
+
+        Self {
+            tombstones,
+            fragments,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tombstones.is_empty()
+    }
+
+    /// Returns the largest sequence of the effective tombstones covering `user_key`, or 0 if there
+    /// is none.
+    pub fn max_sequence(&self, user_key: &[u8]) -> HummockEpoch {
+        let idx = self
+            .fragments
+            .partition_point(|(start, _)| start.as_slice() <= user_key);
+        if idx == 0 {
+            0
+        } else {
+            self.fragments[idx - 1].1
+        }
+    }
+
+    /// Returns whether the version of `user_key` written at `epoch` is deleted.
+    pub fn should_delete(&self, user_key: &[u8], epoch: HummockEpoch) -> bool {
+        epoch < self.max_sequence(user_key)
+    }
+
+    /// Returns all tombstones clipped to `[start_user_key, end_user_key)`, where an empty bound is
+    /// unbounded.
+    pub fn get_tombstone_between(
+        &self,
+        start_user_key: &[u8],
+        end_user_key: &[u8],
+    ) -> Vec<DeleteRangeTombstone> {
+        self.tombstones
+            .iter()
+            .filter_map(|tombstone| tombstone.clip(start_user_key, end_user_key))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_range_aggregator() {
+        let agg = DeleteRangeAggregator::new(
+            vec![
+                DeleteRangeTombstone::new(b"aaa".to_vec(), b"ddd".to_vec(), 10),
+                DeleteRangeTombstone::new(b"bbb".to_vec(), b"eee".to_vec(), 20),
+                DeleteRangeTombstone::new(b"ccc".to_vec(), b"fff".to_vec(), 30),
+            ],
+            25,
+        );
+        assert_eq!(agg.max_sequence(b"a"), 0);
+        assert_eq!(agg.max_sequence(b"aaa"), 10);
+        assert_eq!(agg.max_sequence(b"bbb"), 20);
+        assert_eq!(agg.max_sequence(b"ddd"), 20);
+        // The tombstone with sequence 30 is newer than the watermark.
+        assert_eq!(agg.max_sequence(b"eee"), 0);
+        assert!(agg.should_delete(b"ccc", 15));
+        assert!(!agg.should_delete(b"ccc", 20));
+        assert!(!agg.should_delete(b"zzz", 1));
+
+        let clipped = agg.get_tombstone_between(b"ccc", b"eee");
+        assert_eq!(
+            clipped,
+            vec![
+                DeleteRangeTombstone::new(b"ccc".to_vec(), b"ddd".to_vec(), 10),
+                DeleteRangeTombstone::new(b"ccc".to_vec(), b"eee".to_vec(), 20),
+                DeleteRangeTombstone::new(b"ccc".to_vec(), b"eee".to_vec(), 30),
+            ]
+        );
+        assert_eq!(agg.get_tombstone_between(b"fff", b"").len(), 0);
+    }
+}
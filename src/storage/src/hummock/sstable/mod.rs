@@ -22,10 +22,14 @@ use std::fmt::{Debug, Formatter};
 pub use block::*;
 mod block_iterator;
 pub use block_iterator::*;
+mod blob;
+pub use blob::*;
 mod bloom;
 use bloom::Bloom;
 pub mod builder;
+mod delete_range_aggregator;
 pub use builder::*;
+pub use delete_range_aggregator::*;
 pub mod writer;
 pub use writer::*;
 mod forward_sstable_iterator;
@@ -35,22 +39,26 @@ use fail::fail_point;
 pub use forward_sstable_iterator::*;
 mod backward_sstable_iterator;
 pub use backward_sstable_iterator::*;
-use piestream_hummock_sdk::HummockSstableId;
+use piestream_hummock_sdk::{HummockEpoch, HummockSstableId};
 #[cfg(test)]
 use piestream_pb::hummock::{KeyRange, SstableInfo};
 
 mod sstable_id_manager;
 mod utils;
 pub use sstable_id_manager::*;
-pub use utils::CompressionAlgorithm;
 use utils::{get_length_prefixed_slice, put_length_prefixed_slice};
+pub use utils::{xxhash64_verify, CompressionAlgorithm};
 
-use self::utils::{xxhash64_checksum, xxhash64_verify};
+use self::utils::xxhash64_checksum;
 use super::{HummockError, HummockResult};
 
 const DEFAULT_META_BUFFER_CAPACITY: usize = 4096;
 const MAGIC: u32 = 0x5785ab73;
-const VERSION: u32 = 1;
+/// Version 2 appends the range tombstone list to the meta.
+const VERSION: u32 = 4;
+const VERSION_WITHOUT_COMPRESSION_DICTIONARY: u32 = 3;
+const VERSION_WITHOUT_PREFIX_BLOOM_FILTER: u32 = 2;
+const VERSION_WITHOUT_RANGE_TOMBSTONE: u32 = 1;
 
 /// [`Sstable`] is a handle for accessing SST.
 #[derive(Clone)]
@@ -91,10 +99,42 @@ impl Sstable {
         }
     }
 
+    pub fn has_prefix_bloom_filter(&self) -> bool {
+        !self.meta.prefix_bloom_filter.is_empty()
+    }
+
+    /// Checks the prefix bloom filter, which is built over the keys extracted by the
+    /// `FilterKeyExtractor` of each table, e.g. the distribution key prefix of the pk.
+    pub fn surely_not_have_prefix(&self, prefix: &[u8]) -> bool {
+        let enable_bloom_filter: fn() -> bool = || {
+            fail_point!("disable_bloom_filter", |_| false);
+            true
+        };
+        if enable_bloom_filter() && self.has_prefix_bloom_filter() {
+            let hash = farmhash::fingerprint32(prefix);
+            let bloom = Bloom::new(&self.meta.prefix_bloom_filter);
+            bloom.surely_not_have_hash(hash)
+        } else {
+            false
+        }
+    }
+
     pub fn block_count(&self) -> usize {
         self.meta.block_metas.len()
     }
 
+    /// Returns the largest sequence among the range tombstones that cover `user_key` and are
+    /// visible at `epoch`, or 0 if there is none.
+    pub fn max_delete_range_sequence(&self, user_key: &[u8], epoch: HummockEpoch) -> HummockEpoch {
+        self.meta
+            .range_tombstone_list
+            .iter()
+            .filter(|tombstone| tombstone.sequence <= epoch && tombstone.contains(user_key))
+            .map(|tombstone| tombstone.sequence)
+            .max()
+            .unwrap_or(0)
+    }
+
     #[inline]
     pub fn estimate_size(&self) -> usize {
         8 /* id */ + self.meta.encoded_size()
@@ -114,6 +154,8 @@ impl Sstable {
             meta_offset: self.meta.meta_offset,
             stale_key_count: 0,
             total_key_count: self.meta.key_count as u64,
+            blob_ids: vec![],
+            table_stats: Default::default(),
         }
     }
 }
@@ -167,6 +209,14 @@ pub struct SstableMeta {
     pub smallest_key: Vec<u8>,
     pub largest_key: Vec<u8>,
     pub meta_offset: u64,
+    pub range_tombstone_list: Vec<DeleteRangeTombstone>,
+    /// Bloom filter over the prefixes extracted by `FilterKeyExtractor`, consulted by prefix
+    /// iterators. `bloom_filter` is built over full user keys and consulted by point gets.
+    pub prefix_bloom_filter: Vec<u8>,
+    /// Id of the zstd dictionary shared by the compaction group, to decompress blocks compressed
+    /// by [`CompressionAlgorithm::ZstdDictionary`]. See [`CompressionDictionary`]. 0 if blocks
+    /// are compressed without a dictionary.
+    pub compression_dictionary_id: u64,
     /// Format version, for further compatibility.
     pub version: u32,
 }
@@ -181,6 +231,10 @@ impl SstableMeta {
     /// | estimated size (4B) | key count (4B) |
     /// | smallest key len (4B) | smallest key |
     /// | largest key len (4B) | largest key |
+    /// | meta offset (8B) |
+    /// | M (4B) | range tombstone 0 | ... | range tombstone M-1 |
+    /// | prefix bloom filter len (4B) | prefix bloom filter |
+    /// | compression dictionary id (8B) |
     /// | checksum (8B) | version (4B) | magic (4B) |
     /// ```
     pub fn encode_to_bytes(&self) -> Vec<u8> {
@@ -201,6 +255,12 @@ impl SstableMeta {
         put_length_prefixed_slice(buf, &self.smallest_key);
         put_length_prefixed_slice(buf, &self.largest_key);
         buf.put_u64_le(self.meta_offset);
+        buf.put_u32_le(self.range_tombstone_list.len() as u32);
+        for tombstone in &self.range_tombstone_list {
+            tombstone.encode(buf);
+        }
+        put_length_prefixed_slice(buf, &self.prefix_bloom_filter);
+        buf.put_u64_le(self.compression_dictionary_id);
         let checksum = xxhash64_checksum(&buf[start_offset..]);
         buf.put_u64_le(checksum);
         buf.put_u32_le(VERSION);
@@ -218,7 +278,11 @@ impl SstableMeta {
 
         cursor -= 4;
         let version = (&buf[cursor..cursor + 4]).get_u32_le();
-        if version != VERSION {
+        if version != VERSION
+            && version != VERSION_WITHOUT_COMPRESSION_DICTIONARY
+            && version != VERSION_WITHOUT_PREFIX_BLOOM_FILTER
+            && version != VERSION_WITHOUT_RANGE_TOMBSTONE
+        {
             return Err(HummockError::invalid_format_version(version));
         }
 
@@ -238,6 +302,28 @@ impl SstableMeta {
         let smallest_key = get_length_prefixed_slice(buf);
         let largest_key = get_length_prefixed_slice(buf);
         let meta_offset = buf.get_u64_le();
+        let mut range_tombstone_list = vec![];
+        if version != VERSION_WITHOUT_RANGE_TOMBSTONE {
+            let range_tombstone_count = buf.get_u32_le() as usize;
+            range_tombstone_list.reserve(range_tombstone_count);
+            for _ in 0..range_tombstone_count {
+                range_tombstone_list.push(DeleteRangeTombstone::decode(buf));
+            }
+        }
+        let prefix_bloom_filter =
+            if version == VERSION || version == VERSION_WITHOUT_COMPRESSION_DICTIONARY {
+                get_length_prefixed_slice(buf)
+            } else {
+                // Older versions only have one bloom filter, built over the keys extracted by
+                // `FilterKeyExtractor` and consulted by both point gets and prefix iterators as
+                // before.
+                bloom_filter.clone()
+            };
+        let compression_dictionary_id = if version == VERSION {
+            buf.get_u64_le()
+        } else {
+            0
+        };
 
         Ok(Self {
             block_metas,
@@ -247,6 +333,9 @@ impl SstableMeta {
             smallest_key,
             largest_key,
             meta_offset,
+            range_tombstone_list,
+            prefix_bloom_filter,
+            compression_dictionary_id,
             version,
         })
     }
@@ -267,6 +356,15 @@ impl SstableMeta {
             + self.smallest_key.len()
             + 4 // key len
             + self.largest_key.len()
+            + 4 // range tombstone count
+            + self
+            .range_tombstone_list
+            .iter()
+            .map(|tombstone| tombstone.encoded_size())
+            .sum::<usize>()
+            + 4 // prefix bloom filter len
+            + self.prefix_bloom_filter.len()
+            + 8 // compression dictionary id
             + 8 // footer
             + 8 // checksum
             + 4 // version
@@ -306,6 +404,13 @@ mod tests {
             smallest_key: b"0-smallest-key".to_vec(),
             largest_key: b"9-largest-key".to_vec(),
             meta_offset: 123,
+            range_tombstone_list: vec![DeleteRangeTombstone::new(
+                b"1-start-key".to_vec(),
+                b"3-end-key".to_vec(),
+                100,
+            )],
+            prefix_bloom_filter: b"abcdef".to_vec(),
+            compression_dictionary_id: 123,
             version: VERSION,
         };
         let sz = meta.encoded_size();
@@ -314,4 +419,27 @@ mod tests {
         let decoded_meta = SstableMeta::decode(&mut &buf[..]).unwrap();
         assert_eq!(decoded_meta, meta);
     }
+
+    #[test]
+    pub fn test_sstable_meta_dec_without_prefix_bloom_filter() {
+        let mut buf = vec![];
+        buf.put_u32_le(0);
+        put_length_prefixed_slice(&mut buf, b"0123456789");
+        buf.put_u32_le(123);
+        buf.put_u32_le(123);
+        put_length_prefixed_slice(&mut buf, b"0-smallest-key");
+        put_length_prefixed_slice(&mut buf, b"9-largest-key");
+        buf.put_u64_le(123);
+        buf.put_u32_le(0);
+        let checksum = xxhash64_checksum(&buf);
+        buf.put_u64_le(checksum);
+        buf.put_u32_le(VERSION_WITHOUT_PREFIX_BLOOM_FILTER);
+        buf.put_u32_le(MAGIC);
+
+        let decoded_meta = SstableMeta::decode(&mut &buf[..]).unwrap();
+        // The only bloom filter is consulted by both point gets and prefix iterators.
+        assert_eq!(decoded_meta.bloom_filter, b"0123456789".to_vec());
+        assert_eq!(decoded_meta.prefix_bloom_filter, b"0123456789".to_vec());
+        assert_eq!(decoded_meta.compression_dictionary_id, 0);
+    }
 }
@@ -16,7 +16,7 @@ use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering::SeqCst;
 use std::sync::Arc;
 
-use piestream_hummock_sdk::key::FullKey;
+use piestream_hummock_sdk::key::{user_key, FullKey};
 use piestream_hummock_sdk::HummockEpoch;
 use piestream_pb::hummock::SstableInfo;
 use tokio::task::JoinHandle;
@@ -25,8 +25,8 @@ use crate::hummock::compactor::task_progress::TaskProgress;
 use crate::hummock::sstable_store::SstableStoreRef;
 use crate::hummock::value::HummockValue;
 use crate::hummock::{
-    BatchUploadWriter, CachePolicy, HummockResult, MemoryLimiter, SstableBuilder,
-    SstableBuilderOptions, SstableWriter, SstableWriterOptions,
+    BatchUploadWriter, CachePolicy, CompressionAlgorithm, DeleteRangeTombstone, HummockResult,
+    MemoryLimiter, SstableBuilder, SstableBuilderOptions, SstableWriter, SstableWriterOptions,
 };
 use crate::monitor::StateStoreMetrics;
 
@@ -58,6 +58,13 @@ where
 
     current_builder: Option<SstableBuilder<F::Writer>>,
 
+    /// Range tombstones to be split among the tables by their key ranges.
+    range_tombstones: Vec<DeleteRangeTombstone>,
+
+    /// The user key from which the current table takes over range tombstones. Empty means
+    /// unbounded.
+    tombstone_start_user_key: Vec<u8>,
+
     /// Statistics.
     pub stats: Arc<StateStoreMetrics>,
 
@@ -79,6 +86,8 @@ where
             builder_factory,
             sst_outputs: Vec::new(),
             current_builder: None,
+            range_tombstones: vec![],
+            tombstone_start_user_key: vec![],
             stats,
             task_progress,
         }
@@ -89,6 +98,8 @@ where
             builder_factory,
             sst_outputs: Vec::new(),
             current_builder: None,
+            range_tombstones: vec![],
+            tombstone_start_user_key: vec![],
             stats: Arc::new(StateStoreMetrics::unused()),
             task_progress: None,
         }
@@ -104,6 +115,13 @@ where
         self.sst_outputs.is_empty() && self.current_builder.is_none()
     }
 
+    /// Adds range tombstones to be written along with the keys. Each table takes the part of the
+    /// tombstones between its first key and the first key of the next table, so that the tables
+    /// never overlap and the tombstones still cover the gaps between them.
+    pub fn add_delete_ranges(&mut self, tombstones: Vec<DeleteRangeTombstone>) {
+        self.range_tombstones.extend(tombstones);
+    }
+
     /// Adds a user key-value pair to the underlying builders, with given `epoch`.
     ///
     /// If the current builder reaches its capacity, this function will create a new one with the
@@ -136,7 +154,8 @@ where
     ) -> HummockResult<()> {
         if let Some(builder) = self.current_builder.as_ref() {
             if is_new_user_key && builder.reach_capacity() {
-                self.seal_current().await?;
+                let next_user_key = user_key(full_key).to_vec();
+                self.seal_current_until(&next_user_key).await?;
             }
         }
 
@@ -155,7 +174,26 @@ where
     /// If there's no builder created, or current one is already sealed before, then this function
     /// will be no-op.
     pub async fn seal_current(&mut self) -> HummockResult<()> {
-        if let Some(builder) = self.current_builder.take() {
+        self.seal_current_until(&[]).await
+    }
+
+    /// Seals the current builder, which takes over range tombstones up to `end_user_key`. An empty
+    /// `end_user_key` means unbounded.
+    async fn seal_current_until(&mut self, end_user_key: &[u8]) -> HummockResult<()> {
+        if let Some(mut builder) = self.current_builder.take() {
+            let tombstones = self
+                .range_tombstones
+                .iter()
+                .filter_map(|tombstone| {
+                    tombstone.clip(&self.tombstone_start_user_key, end_user_key)
+                })
+                .collect();
+            builder.add_delete_ranges(tombstones);
+            if end_user_key.is_empty() {
+                self.range_tombstones.clear();
+            }
+            self.tombstone_start_user_key = end_user_key.to_vec();
+
             let builder_output = builder.finish().await?;
 
             {
@@ -188,6 +226,15 @@ where
                         .sstable_avg_value_size
                         .observe(builder_output.avg_value_size as _);
                 }
+
+                if builder_output.compression_algorithm != CompressionAlgorithm::None {
+                    self.stats
+                        .sstable_compression_ratio
+                        .with_label_values(&[
+                            format!("{:?}", builder_output.compression_algorithm).as_str()
+                        ])
+                        .observe(builder_output.compression_ratio);
+                }
             }
 
             self.sst_outputs.push(SplitTableOutput {
@@ -200,6 +247,15 @@ where
 
     /// Finalizes all the tables to be ids, blocks and metadata.
     pub async fn finish(mut self) -> HummockResult<Vec<SplitTableOutput>> {
+        let has_remaining_tombstone = self.range_tombstones.iter().any(|tombstone| {
+            tombstone
+                .clip(&self.tombstone_start_user_key, &[])
+                .is_some()
+        });
+        if self.current_builder.is_none() && has_remaining_tombstone {
+            // Tombstones must be kept even if no key is left.
+            self.current_builder = Some(self.builder_factory.open_builder().await?);
+        }
         self.seal_current().await?;
         Ok(self.sst_outputs)
     }
@@ -254,6 +310,10 @@ impl TableBuilderFactory for LocalTableBuilderFactory {
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+    use piestream_hummock_sdk::can_concat;
+    use piestream_hummock_sdk::key::key_with_epoch;
+
     use super::*;
     use crate::hummock::iterator::test_utils::mock_sstable_store;
     use crate::hummock::sstable::utils::CompressionAlgorithm;
@@ -346,6 +406,50 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_delete_range() {
+        let block_size = 1 << 10;
+        let table_capacity = 4 * block_size;
+        let opts = SstableBuilderOptions {
+            capacity: table_capacity,
+            block_capacity: block_size,
+            restart_interval: DEFAULT_RESTART_INTERVAL,
+            bloom_false_positive: 0.1,
+            compression_algorithm: CompressionAlgorithm::None,
+        };
+        let builder_factory = LocalTableBuilderFactory::new(1001, mock_sstable_store(), opts);
+        let mut builder = CapacitySplitTableBuilder::for_test(builder_factory);
+        builder.add_delete_ranges(vec![DeleteRangeTombstone::new(
+            b"key".to_vec(),
+            b"kez".to_vec(),
+            100,
+        )]);
+        for i in 0..table_capacity {
+            builder
+                .add_user_key(
+                    format!("key_{:05}", i).into_bytes(),
+                    HummockValue::put(b"value"),
+                    1,
+                )
+                .await
+                .unwrap();
+        }
+
+        let results = builder.finish().await.unwrap();
+        assert!(results.len() > 1);
+        let sst_infos = results.iter().map(|output| &output.sst_info).collect_vec();
+        // The tombstone is split among the tables without overlapping.
+        assert!(can_concat(&sst_infos));
+        assert_eq!(
+            sst_infos.first().unwrap().key_range.as_ref().unwrap().left,
+            key_with_epoch(b"key".to_vec(), HummockEpoch::MAX)
+        );
+        assert_eq!(
+            sst_infos.last().unwrap().key_range.as_ref().unwrap().right,
+            key_with_epoch(b"kez".to_vec(), HummockEpoch::MAX)
+        );
+    }
+
     #[tokio::test]
     async fn test_initial_not_allowed_split() {
         let opts = default_builder_opt_for_test();
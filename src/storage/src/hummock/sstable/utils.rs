@@ -91,6 +91,8 @@ pub enum CompressionAlgorithm {
     None,
     Lz4,
     Zstd,
+    /// Zstd with a dictionary trained per sstable, which is stored in the sstable meta.
+    ZstdDictionary,
 }
 
 impl CompressionAlgorithm {
@@ -99,6 +101,7 @@ impl CompressionAlgorithm {
             Self::None => 0,
             Self::Lz4 => 1,
             Self::Zstd => 2,
+            Self::ZstdDictionary => 3,
         };
         buf.put_u8(v);
     }
@@ -108,6 +111,7 @@ impl CompressionAlgorithm {
             0 => Ok(Self::None),
             1 => Ok(Self::Lz4),
             2 => Ok(Self::Zstd),
+            3 => Ok(Self::ZstdDictionary),
             _ => Err(HummockError::decode_error(
                 "not valid compression algorithm",
             )),
@@ -121,6 +125,7 @@ impl From<CompressionAlgorithm> for u8 {
             CompressionAlgorithm::None => 0,
             CompressionAlgorithm::Lz4 => 1,
             CompressionAlgorithm::Zstd => 2,
+            CompressionAlgorithm::ZstdDictionary => 3,
         }
     }
 }
@@ -131,6 +136,7 @@ impl From<CompressionAlgorithm> for u64 {
             CompressionAlgorithm::None => 0,
             CompressionAlgorithm::Lz4 => 1,
             CompressionAlgorithm::Zstd => 2,
+            CompressionAlgorithm::ZstdDictionary => 3,
         }
     }
 }
@@ -143,6 +149,7 @@ impl TryFrom<u8> for CompressionAlgorithm {
             0 => Ok(Self::None),
             1 => Ok(Self::Lz4),
             2 => Ok(Self::Zstd),
+            3 => Ok(Self::ZstdDictionary),
             _ => Err(HummockError::decode_error(
                 "not valid compression algorithm",
             )),
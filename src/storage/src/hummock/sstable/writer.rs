@@ -106,6 +106,9 @@ mod tests {
             smallest_key: Vec::new(),
             largest_key: Vec::new(),
             meta_offset: data.len() as u64,
+            range_tombstone_list: vec![],
+            prefix_bloom_filter: vec![],
+            compression_dictionary_id: 0,
             version: VERSION,
         };
 
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use std::clone::Clone;
+use std::collections::HashMap;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Instant;
@@ -20,23 +21,25 @@ use async_stack_trace::StackTrace;
 use bytes::{Buf, BufMut, Bytes};
 use fail::fail_point;
 use itertools::Itertools;
+use parking_lot::Mutex;
 use piestream_common::cache::LruCacheEventListener;
-use piestream_hummock_sdk::{is_remote_sst_id, HummockSstableId};
+use piestream_hummock_sdk::{is_remote_sst_id, CompactionGroupId, HummockSstableId};
 use piestream_object_store::object::{
     get_local_path, BlockLocation, ObjectMetadata, ObjectStoreRef, ObjectStreamingUploader,
 };
 use piestream_pb::hummock::SstableInfo;
 use tokio::task::JoinHandle;
+use zstd::dict::DecoderDictionary;
 use zstd::zstd_safe::WriteBuf;
 
 use super::utils::MemoryTracker;
 use super::{
-    Block, BlockCache, BlockMeta, Sstable, SstableMeta, SstableWriter, TieredCache, TieredCacheKey,
-    TieredCacheValue,
+    prepare_decoder_dictionary, Block, BlockCache, BlockMeta, CompressionDictionary, Sstable,
+    SstableMeta, SstableWriter, TieredCache, TieredCacheKey, TieredCacheValue,
 };
 use crate::hummock::multi_builder::UploadJoinHandle;
 use crate::hummock::{
-    BlockHolder, CacheableEntry, HummockError, HummockResult, LruCache, MemoryLimiter,
+    BlobPointer, BlockHolder, CacheableEntry, HummockError, HummockResult, LruCache, MemoryLimiter,
 };
 use crate::monitor::{MemoryCollector, StoreLocalStatistic};
 
@@ -83,6 +86,43 @@ impl TieredCacheValue for Box<Block> {
     }
 }
 
+impl TieredCacheKey for HummockSstableId {
+    fn encoded_len() -> usize {
+        8
+    }
+
+    fn encode(&self, mut buf: &mut [u8]) {
+        buf.put_u64(*self);
+    }
+
+    fn decode(mut buf: &[u8]) -> Self {
+        buf.get_u64()
+    }
+}
+
+impl TieredCacheValue for Box<Sstable> {
+    fn len(&self) -> usize {
+        self.meta.encoded_size()
+    }
+
+    fn encoded_len(&self) -> usize {
+        8 + self.meta.encoded_size()
+    }
+
+    fn encode(&self, mut buf: &mut [u8]) {
+        buf.put_u64(self.id);
+        buf.put_slice(&self.meta.encode_to_bytes());
+    }
+
+    fn decode(buf: Vec<u8>) -> Self {
+        let mut buf = &buf[..];
+        let id = buf.get_u64();
+        // The format of the file cache is versioned, so the meta is expected to be valid.
+        let meta = SstableMeta::decode(&mut buf).expect("invalid sstable meta in tiered cache");
+        Box::new(Sstable::new(id, meta))
+    }
+}
+
 pub struct BlockCacheEventListener {
     tiered_cache: TieredCache<(HummockSstableId, u64), Box<Block>>,
 }
@@ -97,6 +137,19 @@ impl LruCacheEventListener for BlockCacheEventListener {
     }
 }
 
+pub struct MetaCacheEventListener {
+    tiered_cache: TieredCache<HummockSstableId, Box<Sstable>>,
+}
+
+impl LruCacheEventListener for MetaCacheEventListener {
+    type K = HummockSstableId;
+    type T = Box<Sstable>;
+
+    fn on_release(&self, key: Self::K, value: Self::T) {
+        self.tiered_cache.insert(key, value).unwrap();
+    }
+}
+
 // END section for tiered cache
 
 // TODO: Define policy based on use cases (read / compaction / ...).
@@ -116,6 +169,12 @@ pub struct SstableStore {
     block_cache: BlockCache,
     meta_cache: Arc<LruCache<HummockSstableId, Box<Sstable>>>,
     tiered_cache: TieredCache<(HummockSstableId, u64), Box<Block>>,
+    meta_tiered_cache: TieredCache<HummockSstableId, Box<Sstable>>,
+    /// Zstd dictionaries trained for compaction groups, shared by the sstables compacted into
+    /// them.
+    compression_dictionaries: Mutex<HashMap<CompactionGroupId, CompressionDictionary>>,
+    /// Zstd dictionaries referenced by sstable metas, prepared for decompressing blocks.
+    decoder_dictionaries: Mutex<HashMap<u64, Arc<DecoderDictionary<'static>>>>,
 }
 
 impl SstableStore {
@@ -125,12 +184,19 @@ impl SstableStore {
         block_cache_capacity: usize,
         meta_cache_capacity: usize,
         tiered_cache: TieredCache<(HummockSstableId, u64), Box<Block>>,
+        meta_tiered_cache: TieredCache<HummockSstableId, Box<Sstable>>,
     ) -> Self {
         let mut shard_bits = MAX_META_CACHE_SHARD_BITS;
         while (meta_cache_capacity >> shard_bits) < MIN_BUFFER_SIZE_PER_SHARD && shard_bits > 0 {
             shard_bits -= 1;
         }
-        let meta_cache = Arc::new(LruCache::new(shard_bits, meta_cache_capacity));
+        let meta_cache = Arc::new(LruCache::with_event_listener(
+            shard_bits,
+            meta_cache_capacity,
+            Arc::new(MetaCacheEventListener {
+                tiered_cache: meta_tiered_cache.clone(),
+            }),
+        ));
         let listener = Arc::new(BlockCacheEventListener {
             tiered_cache: tiered_cache.clone(),
         });
@@ -145,6 +211,9 @@ impl SstableStore {
             ),
             meta_cache,
             tiered_cache,
+            meta_tiered_cache,
+            compression_dictionaries: Mutex::new(HashMap::new()),
+            decoder_dictionaries: Mutex::new(HashMap::new()),
         }
     }
 
@@ -164,6 +233,9 @@ impl SstableStore {
             block_cache: BlockCache::new(block_cache_capacity, 0),
             meta_cache,
             tiered_cache,
+            meta_tiered_cache: TieredCache::none(),
+            compression_dictionaries: Mutex::new(HashMap::new()),
+            decoder_dictionaries: Mutex::new(HashMap::new()),
         }
     }
 
@@ -172,7 +244,7 @@ impl SstableStore {
         self.store
             .delete(self.get_sst_data_path(sst_id).as_str())
             .await?;
-        self.meta_cache.erase(sst_id, &sst_id);
+        self.erase_meta_cache(sst_id);
         Ok(())
     }
 
@@ -182,6 +254,9 @@ impl SstableStore {
 
         for &sst_id in sst_id_list {
             paths.push(self.get_sst_data_path(sst_id));
+            // Blob files share the id space with SSTs, so the id refers to either an SST or a blob
+            // file. Deleting an object that doesn't exist is OK.
+            paths.push(self.get_blob_data_path(sst_id));
         }
 
         // Delete from storage.
@@ -189,14 +264,20 @@ impl SstableStore {
 
         // Delete from cache.
         for &sst_id in sst_id_list {
-            self.meta_cache.erase(sst_id, &sst_id);
+            self.erase_meta_cache(sst_id);
         }
 
         Ok(())
     }
 
     pub fn delete_cache(&self, sst_id: HummockSstableId) {
+        self.erase_meta_cache(sst_id);
+    }
+
+    fn erase_meta_cache(&self, sst_id: HummockSstableId) {
         self.meta_cache.erase(sst_id, &sst_id);
+        // The erased entry is moved into the tiered cache by `MetaCacheEventListener`.
+        self.meta_tiered_cache.erase(&sst_id).unwrap();
     }
 
     async fn put_sst_data(&self, sst_id: HummockSstableId, data: Bytes) -> HummockResult<()> {
@@ -207,6 +288,107 @@ impl SstableStore {
             .map_err(HummockError::object_io_error)
     }
 
+    pub async fn put_blob_data(&self, blob_id: HummockSstableId, data: Bytes) -> HummockResult<()> {
+        let data_path = self.get_blob_data_path(blob_id);
+        self.store
+            .upload(&data_path, data)
+            .await
+            .map_err(HummockError::object_io_error)
+    }
+
+    /// Returns the zstd dictionary of compaction group `group_id`, if it has been trained by this
+    /// process.
+    pub fn compression_dictionary(
+        &self,
+        group_id: CompactionGroupId,
+    ) -> Option<CompressionDictionary> {
+        self.compression_dictionaries.lock().get(&group_id).cloned()
+    }
+
+    /// Stores `dictionary` as the zstd dictionary of compaction group `group_id`, unless the group
+    /// already has one. Returns the dictionary of the group.
+    ///
+    /// Dictionaries are never deleted, as they are small and any sstable may reference them.
+    pub async fn put_compression_dictionary(
+        &self,
+        group_id: CompactionGroupId,
+        dictionary: CompressionDictionary,
+    ) -> HummockResult<CompressionDictionary> {
+        if let Some(dictionary) = self.compression_dictionary(group_id) {
+            return Ok(dictionary);
+        }
+        self.store
+            .upload(
+                &self.get_compression_dictionary_path(dictionary.id),
+                dictionary.data.clone(),
+            )
+            .await
+            .map_err(HummockError::object_io_error)?;
+        self.decoder_dictionaries
+            .lock()
+            .entry(dictionary.id)
+            .or_insert_with(|| prepare_decoder_dictionary(&dictionary.data).unwrap());
+        Ok(self
+            .compression_dictionaries
+            .lock()
+            .entry(group_id)
+            .or_insert(dictionary)
+            .clone())
+    }
+
+    /// Loads the zstd dictionary `dictionary_id` referenced by an sstable meta, prepared for
+    /// decompressing blocks. Returns `None` if `dictionary_id` is 0.
+    pub async fn decoder_dictionary(
+        &self,
+        dictionary_id: u64,
+    ) -> HummockResult<Option<Arc<DecoderDictionary<'static>>>> {
+        if dictionary_id == 0 {
+            return Ok(None);
+        }
+        let cached = self
+            .decoder_dictionaries
+            .lock()
+            .get(&dictionary_id)
+            .cloned();
+        if cached.is_some() {
+            return Ok(cached);
+        }
+        let data = self
+            .store
+            .read(&self.get_compression_dictionary_path(dictionary_id), None)
+            .await
+            .map_err(HummockError::object_io_error)?;
+        let dictionary = prepare_decoder_dictionary(&data).ok_or_else(|| {
+            HummockError::decode_error(format!("compression dictionary {} is empty", dictionary_id))
+        })?;
+        Ok(Some(
+            self.decoder_dictionaries
+                .lock()
+                .entry(dictionary_id)
+                .or_insert(dictionary)
+                .clone(),
+        ))
+    }
+
+    /// Reads the user value pointed by `pointer` from its blob file. Blob values bypass the block
+    /// cache, so that large values don't evict the blocks.
+    pub async fn get_blob_value(
+        &self,
+        pointer: &BlobPointer,
+        stats: &mut StoreLocalStatistic,
+    ) -> HummockResult<Bytes> {
+        stats.blob_value_read_count += 1;
+        let data_path = self.get_blob_data_path(pointer.blob_id);
+        let block_loc = BlockLocation {
+            offset: pointer.offset as usize,
+            size: pointer.len as usize,
+        };
+        self.store
+            .read(&data_path, Some(block_loc))
+            .await
+            .map_err(HummockError::object_io_error)
+    }
+
     pub async fn get(
         &self,
         sst: &Sstable,
@@ -216,6 +398,9 @@ impl SstableStore {
     ) -> HummockResult<BlockHolder> {
         stats.cache_data_block_total += 1;
         let tiered_cache = self.tiered_cache.clone();
+        let compression_dictionary = self
+            .decoder_dictionary(sst.meta.compression_dictionary_id)
+            .await?;
         let fetch_block = || {
             stats.cache_data_block_miss += 1;
             let block_meta = sst
@@ -233,6 +418,7 @@ impl SstableStore {
             let sst_id = sst.id;
             let use_tiered_cache = !matches!(policy, CachePolicy::Disable);
             let uncompressed_capacity = block_meta.uncompressed_size as usize;
+            let compression_dictionary = compression_dictionary.clone();
 
             async move {
                 if use_tiered_cache && let Some(holder) = tiered_cache
@@ -245,7 +431,11 @@ impl SstableStore {
                 }
 
                 let block_data = store.read(&data_path, Some(block_loc)).await?;
-                let block = Block::decode(block_data, uncompressed_capacity)?;
+                let block = Block::decode_with_dictionary(
+                    block_data,
+                    uncompressed_capacity,
+                    compression_dictionary.as_deref(),
+                )?;
                 Ok(Box::new(block))
             }
         };
@@ -293,10 +483,29 @@ impl SstableStore {
         ret
     }
 
+    pub fn get_blob_data_path(&self, blob_id: HummockSstableId) -> String {
+        let is_remote = is_remote_sst_id(blob_id);
+        let obj_prefix = self.store.get_object_prefix(blob_id, is_remote);
+        let mut ret = format!("{}/{}{}.blob", self.path, obj_prefix, blob_id);
+        if !is_remote {
+            ret = get_local_path(&ret);
+        }
+        ret
+    }
+
+    pub fn get_compression_dictionary_path(&self, dictionary_id: u64) -> String {
+        format!("{}/{}.dict", self.path, dictionary_id)
+    }
+
+    /// Gets the id of an SST or a blob file from its path.
     pub fn get_sst_id_from_path(&self, path: &str) -> HummockSstableId {
         let split = path.split(&['/', '.']).collect_vec();
         debug_assert!(split.len() > 2);
-        debug_assert!(split[split.len() - 1] == "meta" || split[split.len() - 1] == "data");
+        debug_assert!(
+            split[split.len() - 1] == "meta"
+                || split[split.len() - 1] == "data"
+                || split[split.len() - 1] == "blob"
+        );
         split[split.len() - 2]
             .parse::<HummockSstableId>()
             .expect("valid sst id")
@@ -334,6 +543,7 @@ impl SstableStore {
         self.meta_cache
             .lookup_with_request_dedup::<_, HummockError, _>(sst_id, sst_id, || {
                 let store = self.store.clone();
+                let meta_tiered_cache = self.meta_tiered_cache.clone();
                 let meta_path = self.get_sst_data_path(sst_id);
                 stats.cache_meta_block_miss += 1;
                 let stats_ptr = stats.remote_io_time.clone();
@@ -342,6 +552,15 @@ impl SstableStore {
                     size: (sst.file_size - sst.meta_offset) as usize,
                 };
                 async move {
+                    if let Some(holder) = meta_tiered_cache
+                        .get(&sst_id)
+                        .await
+                        .map_err(HummockError::tiered_cache)?
+                    {
+                        let sst = holder.into_owned();
+                        let charge = sst.meta.encoded_size();
+                        return Ok((sst, charge));
+                    }
                     let now = Instant::now();
                     let buf = store
                         .read(&meta_path, Some(loc))
@@ -365,11 +584,18 @@ impl SstableStore {
             })?
     }
 
+    /// Lists SSTs and blob files. Compression dictionaries are skipped, as they are not named by
+    /// SST ids.
     pub async fn list_ssts_from_object_store(&self) -> HummockResult<Vec<ObjectMetadata>> {
-        self.store
+        let objects = self
+            .store
             .list(&self.path)
             .await
-            .map_err(HummockError::object_io_error)
+            .map_err(HummockError::object_io_error)?;
+        Ok(objects
+            .into_iter()
+            .filter(|object| !object.key.ends_with(".dict"))
+            .collect())
     }
 
     pub fn create_sst_writer(
@@ -472,7 +698,9 @@ pub struct BatchUploadWriter {
     sstable_store: SstableStoreRef,
     policy: CachePolicy,
     buf: Vec<u8>,
-    block_info: Vec<Block>,
+    /// Compressed blocks to refill block cache. Keep the uncompressed capacity for decode, which
+    /// may require the compression dictionary referenced by the meta.
+    block_info: Vec<(Bytes, usize)>,
     tracker: Option<MemoryTracker>,
 }
 
@@ -500,10 +728,8 @@ impl SstableWriter for BatchUploadWriter {
     async fn write_block(&mut self, block: &[u8], meta: &BlockMeta) -> HummockResult<()> {
         self.buf.extend_from_slice(block);
         if let CachePolicy::Fill = self.policy {
-            self.block_info.push(Block::decode(
-                Bytes::from(block.to_vec()),
-                meta.uncompressed_size as usize,
-            )?);
+            self.block_info
+                .push((Bytes::from(block.to_vec()), meta.uncompressed_size as usize));
         }
         Ok(())
     }
@@ -526,12 +752,22 @@ impl SstableWriter for BatchUploadWriter {
                 .clone()
                 .put_sst_data(self.sst_id, data.clone())
                 .await?;
-            self.sstable_store.insert_meta_cache(self.sst_id, meta);
 
             // Add block cache.
             if CachePolicy::Fill == self.policy {
                 debug_assert!(!self.block_info.is_empty());
-                for (block_idx, block) in self.block_info.into_iter().enumerate() {
+                let compression_dictionary = self
+                    .sstable_store
+                    .decoder_dictionary(meta.compression_dictionary_id)
+                    .await?;
+                for (block_idx, (block_data, uncompressed_capacity)) in
+                    self.block_info.into_iter().enumerate()
+                {
+                    let block = Block::decode_with_dictionary(
+                        block_data,
+                        uncompressed_capacity,
+                        compression_dictionary.as_deref(),
+                    )?;
                     self.sstable_store.block_cache.insert(
                         self.sst_id,
                         block_idx as u64,
@@ -539,6 +775,7 @@ impl SstableWriter for BatchUploadWriter {
                     );
                 }
             }
+            self.sstable_store.insert_meta_cache(self.sst_id, meta);
             Ok(())
         });
         Ok(join_handle)
@@ -556,7 +793,7 @@ pub struct StreamingUploadWriter {
     /// Data are uploaded block by block, except for the size footer.
     object_uploader: ObjectStreamingUploader,
     /// Compressed blocks to refill block or meta cache. Keep the uncompressed capacity for decode.
-    blocks: Vec<Block>,
+    blocks: Vec<(Bytes, usize)>,
     data_len: usize,
     tracker: Option<MemoryTracker>,
 }
@@ -588,8 +825,8 @@ impl SstableWriter for StreamingUploadWriter {
         self.data_len += block_data.len();
         let block_data = Bytes::from(block_data.to_vec());
         if let CachePolicy::Fill = self.policy {
-            let block = Block::decode(block_data.clone(), meta.uncompressed_size as usize)?;
-            self.blocks.push(block);
+            self.blocks
+                .push((block_data.clone(), meta.uncompressed_size as usize));
         }
         self.object_uploader
             .write_bytes(block_data)
@@ -619,12 +856,22 @@ impl SstableWriter for StreamingUploadWriter {
                 .finish()
                 .await
                 .map_err(HummockError::object_io_error)?;
-            self.sstable_store.insert_meta_cache(self.sst_id, meta);
 
             // Add block cache.
             if let CachePolicy::Fill = self.policy {
                 debug_assert!(!self.blocks.is_empty());
-                for (block_idx, block) in self.blocks.into_iter().enumerate() {
+                let compression_dictionary = self
+                    .sstable_store
+                    .decoder_dictionary(meta.compression_dictionary_id)
+                    .await?;
+                for (block_idx, (block_data, uncompressed_capacity)) in
+                    self.blocks.into_iter().enumerate()
+                {
+                    let block = Block::decode_with_dictionary(
+                        block_data,
+                        uncompressed_capacity,
+                        compression_dictionary.as_deref(),
+                    )?;
                     self.sstable_store.block_cache.insert(
                         self.sst_id,
                         block_idx as u64,
@@ -632,6 +879,7 @@ impl SstableWriter for StreamingUploadWriter {
                     );
                 }
             }
+            self.sstable_store.insert_meta_cache(self.sst_id, meta);
             Ok(())
         });
         Ok(join_handle)
@@ -788,5 +1036,8 @@ mod tests {
         let data_path = sstable_store.get_sst_data_path(sst_id);
         assert_eq!(data_path, "test/123.data");
         assert_eq!(sstable_store.get_sst_id_from_path(&data_path), sst_id);
+        let blob_path = sstable_store.get_blob_data_path(sst_id);
+        assert_eq!(blob_path, "test/123.blob");
+        assert_eq!(sstable_store.get_sst_id_from_path(&blob_path), sst_id);
     }
 }
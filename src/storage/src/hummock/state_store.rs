@@ -34,7 +34,7 @@ use super::iterator::{
 };
 use super::utils::{search_sst_idx, validate_epoch};
 use super::{
-    get_from_order_sorted_uncommitted_data, get_from_sstable_info, hit_sstable_bloom_filter,
+    get_from_order_sorted_uncommitted_data, get_from_sstable_info, hit_sstable_prefix_bloom_filter,
     BackwardSstableIterator, HummockStorage, SstableIterator, SstableIteratorType,
 };
 use crate::error::StorageResult;
@@ -43,10 +43,10 @@ use crate::hummock::iterator::{
     ForwardUserIteratorType, HummockIteratorDirection, HummockIteratorUnion,
 };
 use crate::hummock::local_version::ReadVersion;
-use crate::hummock::shared_buffer::build_ordered_merge_iter;
+use crate::hummock::shared_buffer::{build_ordered_merge_iter, collect_delete_range_tombstones};
 use crate::hummock::sstable::SstableIteratorReadOptions;
 use crate::hummock::utils::prune_ssts;
-use crate::hummock::HummockResult;
+use crate::hummock::{DeleteRangeAggregator, HummockResult};
 use crate::monitor::{StateStoreMetrics, StoreLocalStatistic};
 use crate::storage_value::StorageValue;
 use crate::store::*;
@@ -112,7 +112,17 @@ impl HummockStorage {
         } = self.read_filter(&read_options, &key_range)?;
 
         let mut local_stats = StoreLocalStatistic::default();
+        // Range tombstones are collected from all data overlapping with `key_range`, even if the
+        // data is filtered out by bloom filter.
+        let mut delete_range_tombstones = vec![];
         for uncommitted_data in shared_buffer_data {
+            collect_delete_range_tombstones(
+                &uncommitted_data,
+                self.sstable_store.clone(),
+                &mut local_stats,
+                &mut delete_range_tombstones,
+            )
+            .await?;
             overlapped_iters.push(HummockIteratorUnion::Second(
                 build_ordered_merge_iter::<T>(
                     &uncommitted_data,
@@ -120,6 +130,7 @@ impl HummockStorage {
                     self.stats.clone(),
                     &mut local_stats,
                     iter_read_options.clone(),
+                    prefix_hint.as_deref(),
                 )
                 .in_span(Span::enter_with_local_parent(
                     "build_ordered_merge_iter_shared_buffer",
@@ -128,6 +139,13 @@ impl HummockStorage {
             ));
         }
         for sync_uncommitted_data in sync_uncommitted_data {
+            collect_delete_range_tombstones(
+                &sync_uncommitted_data,
+                self.sstable_store.clone(),
+                &mut local_stats,
+                &mut delete_range_tombstones,
+            )
+            .await?;
             overlapped_iters.push(HummockIteratorUnion::Second(
                 build_ordered_merge_iter::<T>(
                     &sync_uncommitted_data,
@@ -135,6 +153,7 @@ impl HummockStorage {
                     self.stats.clone(),
                     &mut local_stats,
                     iter_read_options.clone(),
+                    prefix_hint.as_deref(),
                 )
                 .in_span(Span::enter_with_local_parent(
                     "build_ordered_merge_iter_uncommitted",
@@ -186,14 +205,15 @@ impl HummockStorage {
 
                 let mut sstables = vec![];
                 for sstable_info in pruned_sstables {
+                    let sstable = self
+                        .sstable_store
+                        .sstable(sstable_info, &mut local_stats)
+                        .in_span(Span::enter_with_local_parent("get_sstable"))
+                        .await?;
+                    delete_range_tombstones
+                        .extend_from_slice(&sstable.value().meta.range_tombstone_list);
                     if let Some(bloom_filter_key) = prefix_hint.as_ref() {
-                        let sstable = self
-                            .sstable_store
-                            .sstable(sstable_info, &mut local_stats)
-                            .in_span(Span::enter_with_local_parent("get_sstable"))
-                            .await?;
-
-                        if hit_sstable_bloom_filter(
+                        if hit_sstable_prefix_bloom_filter(
                             sstable.value(),
                             bloom_filter_key,
                             &mut local_stats,
@@ -219,8 +239,10 @@ impl HummockStorage {
                         .sstable(table_info, &mut local_stats)
                         .in_span(Span::enter_with_local_parent("get_sstable"))
                         .await?;
+                    delete_range_tombstones
+                        .extend_from_slice(&sstable.value().meta.range_tombstone_list);
                     if let Some(bloom_filter_key) = prefix_hint.as_ref() {
-                        if !hit_sstable_bloom_filter(
+                        if !hit_sstable_prefix_bloom_filter(
                             sstable.value(),
                             bloom_filter_key,
                             &mut local_stats,
@@ -258,6 +280,8 @@ impl HummockStorage {
             epoch,
             min_epoch,
             Some(pinned_version),
+            Arc::new(DeleteRangeAggregator::new(delete_range_tombstones, epoch)),
+            Some(self.sstable_store()),
         );
 
         user_iterator
@@ -488,6 +512,7 @@ impl StateStore for HummockStorage {
     fn ingest_batch(
         &self,
         kv_pairs: Vec<(Bytes, StorageValue)>,
+        delete_ranges: Vec<(Bytes, Bytes)>,
         write_options: WriteOptions,
     ) -> Self::IngestBatchFuture<'_> {
         async move {
@@ -497,7 +522,13 @@ impl StateStore for HummockStorage {
             // compaction_group_id in read/write path.
             let size = self
                 .local_version_manager
-                .write_shared_buffer(epoch, compaction_group_id, kv_pairs, write_options.table_id)
+                .write_shared_buffer(
+                    epoch,
+                    compaction_group_id,
+                    kv_pairs,
+                    delete_ranges,
+                    write_options.table_id,
+                )
                 .await?;
             Ok(size)
         }
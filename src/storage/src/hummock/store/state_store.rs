@@ -47,13 +47,14 @@ use crate::hummock::shared_buffer::shared_buffer_batch::SharedBufferBatchIterato
 use crate::hummock::sstable::SstableIteratorReadOptions;
 use crate::hummock::sstable_store::SstableStoreRef;
 use crate::hummock::{
-    get_from_batch, get_from_sstable_info, SstableIdManager, SstableIdManagerRef,
+    get_from_batch, get_from_sstable_info, DeleteRangeAggregator, SstableIdManager,
+    SstableIdManagerRef,
 };
 use crate::storage_value::StorageValue;
 
 pub type UploaderRef = LocalVersionManagerRef;
 use crate::hummock::utils::{prune_ssts, search_sst_idx};
-use crate::hummock::{hit_sstable_bloom_filter, HummockResult, SstableIterator};
+use crate::hummock::{hit_sstable_prefix_bloom_filter, HummockResult, SstableIterator};
 use crate::monitor::{StateStoreMetrics, StoreLocalStatistic};
 use crate::{define_local_state_store_associated_type, StateStoreIter};
 
@@ -327,7 +328,8 @@ impl HummockStorageCore {
                 .in_span(Span::enter_with_local_parent("get_sstable"))
                 .await?;
             if let Some(prefix) = read_options.prefix_hint.as_ref() {
-                if !hit_sstable_bloom_filter(table_holder.value(), prefix, &mut local_stats) {
+                if !hit_sstable_prefix_bloom_filter(table_holder.value(), prefix, &mut local_stats)
+                {
                     continue;
                 }
             }
@@ -376,7 +378,7 @@ impl HummockStorageCore {
                             .in_span(Span::enter_with_local_parent("get_sstable"))
                             .await?;
 
-                        if hit_sstable_bloom_filter(
+                        if hit_sstable_prefix_bloom_filter(
                             sstable.value(),
                             bloom_filter_key,
                             &mut local_stats,
@@ -403,7 +405,7 @@ impl HummockStorageCore {
                         .in_span(Span::enter_with_local_parent("get_sstable"))
                         .await?;
                     if let Some(bloom_filter_key) = read_options.prefix_hint.as_ref() {
-                        if !hit_sstable_bloom_filter(
+                        if !hit_sstable_prefix_bloom_filter(
                             sstable.value(),
                             bloom_filter_key,
                             &mut local_stats,
@@ -446,7 +448,15 @@ impl HummockStorageCore {
                 ),
         );
         // TODO: may want to set `min_epoch` by retention time.
-        let mut user_iter = UserIterator::new(merge_iter, key_range, epoch, 0, Some(committed));
+        let mut user_iter = UserIterator::new(
+            merge_iter,
+            key_range,
+            epoch,
+            0,
+            Some(committed),
+            Arc::new(DeleteRangeAggregator::default()),
+            Some(self.sstable_store.clone()),
+        );
         user_iter
             .rewind()
             .in_span(Span::enter_with_local_parent("rewind"))
@@ -517,7 +527,7 @@ impl StateStore for HummockStorage {
                 .await?;
 
             let imm = uploader
-                .build_shared_buffer_batch(epoch, compaction_group_id, kv_pairs, table_id)
+                .build_shared_buffer_batch(epoch, compaction_group_id, kv_pairs, vec![], table_id)
                 .await;
             let imm_size = imm.size();
             self.core
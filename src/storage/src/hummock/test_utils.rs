@@ -102,6 +102,8 @@ pub fn gen_dummy_sst_info(id: HummockSstableId, batches: Vec<SharedBufferBatch>)
         meta_offset: 0,
         stale_key_count: 0,
         total_key_count: 0,
+        blob_ids: vec![],
+        table_stats: Default::default(),
     }
 }
 
@@ -173,6 +175,8 @@ pub async fn put_sst(
         meta_offset: meta.meta_offset,
         stale_key_count: 0,
         total_key_count: 0,
+        blob_ids: vec![],
+        table_stats: Default::default(),
     };
     let writer_output = writer.finish(meta).await?;
     writer_output.await.unwrap()?;
@@ -13,21 +13,55 @@
 // limitations under the License.
 
 use bytes::{Buf, BufMut, Bytes};
+use piestream_hummock_sdk::HummockSstableId;
 
 use super::{HummockError, HummockResult};
 use crate::storage_value::StorageValue;
 
 pub const VALUE_DELETE: u8 = 1 << 0;
 pub const VALUE_PUT: u8 = 0;
+pub const VALUE_BLOB: u8 = 1 << 1;
+
+/// Points to a user value stored in a blob file, which is written by the compactor for the tables
+/// with key-value separation enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobPointer {
+    pub blob_id: HummockSstableId,
+    pub offset: u32,
+    pub len: u32,
+}
+
+impl BlobPointer {
+    pub const ENCODED_LEN: usize = 16;
+
+    pub fn encode(&self, buffer: &mut impl BufMut) {
+        buffer.put_u64_le(self.blob_id);
+        buffer.put_u32_le(self.offset);
+        buffer.put_u32_le(self.len);
+    }
+
+    pub fn decode(buffer: &mut impl Buf) -> HummockResult<Self> {
+        if buffer.remaining() < Self::ENCODED_LEN {
+            return Err(HummockError::decode_error("blob pointer too short"));
+        }
+        Ok(Self {
+            blob_id: buffer.get_u64_le(),
+            offset: buffer.get_u32_le(),
+            len: buffer.get_u32_le(),
+        })
+    }
+}
 
 /// [`HummockValue`] can be created on either a `Vec<u8>` or a `&[u8]`.
 ///
 /// Its encoding is a 1-byte flag + storage value. For `Put`, storage value contains both value meta
-/// and user value. For `Delete`, storage value contains only value meta.
+/// and user value. For `Delete`, storage value contains only value meta. For `Blob`, the user value
+/// is stored in a blob file and only the [`BlobPointer`] is encoded.
 #[derive(Debug, Clone)]
 pub enum HummockValue<T> {
     Put(T),
     Delete,
+    Blob(BlobPointer),
 }
 
 impl<T> Copy for HummockValue<T> where T: Copy {}
@@ -37,6 +71,7 @@ impl<T: PartialEq> PartialEq for HummockValue<T> {
         match (self, other) {
             (Self::Put(l0), Self::Put(r0)) => l0.eq(r0),
             (Self::Delete, Self::Delete) => true,
+            (Self::Blob(l0), Self::Blob(r0)) => l0.eq(r0),
             _ => false,
         }
     }
@@ -52,6 +87,7 @@ where
         match self {
             HummockValue::Put(val) => 1 + val.as_ref().len(),
             HummockValue::Delete => 1,
+            HummockValue::Blob(_) => 1 + BlobPointer::ENCODED_LEN,
         }
     }
 
@@ -67,15 +103,25 @@ where
                 // set flag
                 buffer.put_u8(VALUE_DELETE);
             }
+            HummockValue::Blob(pointer) => {
+                // set flag
+                buffer.put_u8(VALUE_BLOB);
+                pointer.encode(buffer);
+            }
         }
     }
 
     /// Gets the user value out of the `HummockValue`. If the current value is `Delete`, `None` will
     /// be returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a `Blob`, which must be resolved from the blob file first.
     pub fn into_user_value(self) -> Option<T> {
         match self {
             Self::Put(val) => Some(val),
             Self::Delete => None,
+            Self::Blob(pointer) => panic!("unresolved blob value {:?}", pointer),
         }
     }
 
@@ -101,6 +147,7 @@ impl HummockValue<Vec<u8>> {
         match buffer.get_u8() {
             VALUE_PUT => Ok(Self::Put(Vec::from(buffer.chunk()))),
             VALUE_DELETE => Ok(Self::Delete),
+            VALUE_BLOB => Ok(Self::Blob(BlobPointer::decode(buffer)?)),
             _ => Err(HummockError::decode_error("non-empty but format error")),
         }
     }
@@ -109,6 +156,7 @@ impl HummockValue<Vec<u8>> {
         match self {
             HummockValue::Put(data) => HummockValue::Put(data),
             HummockValue::Delete => HummockValue::Delete,
+            HummockValue::Blob(pointer) => HummockValue::Blob(*pointer),
         }
     }
 }
@@ -122,6 +170,7 @@ impl<'a> HummockValue<&'a [u8]> {
         match buffer.get_u8() {
             VALUE_PUT => Ok(Self::Put(buffer)),
             VALUE_DELETE => Ok(Self::Delete),
+            VALUE_BLOB => Ok(Self::Blob(BlobPointer::decode(&mut buffer)?)),
             _ => Err(HummockError::decode_error("non-empty but format error")),
         }
     }
@@ -131,6 +180,7 @@ impl<'a> HummockValue<&'a [u8]> {
         match self {
             HummockValue::Put(value) => HummockValue::Put(Bytes::copy_from_slice(value)),
             HummockValue::Delete => HummockValue::Delete,
+            HummockValue::Blob(pointer) => HummockValue::Blob(pointer),
         }
     }
 }
@@ -140,6 +190,7 @@ impl HummockValue<Bytes> {
         match self {
             HummockValue::Put(data) => HummockValue::Put(&data[..]),
             HummockValue::Delete => HummockValue::Delete,
+            HummockValue::Blob(pointer) => HummockValue::Blob(*pointer),
         }
     }
 
@@ -147,6 +198,7 @@ impl HummockValue<Bytes> {
         match self {
             HummockValue::Put(data) => HummockValue::Put(data.to_vec()),
             HummockValue::Delete => HummockValue::Delete,
+            HummockValue::Blob(pointer) => HummockValue::Blob(*pointer),
         }
     }
 }
@@ -156,6 +208,7 @@ impl From<HummockValue<Vec<u8>>> for HummockValue<Bytes> {
         match data {
             HummockValue::Put(data) => HummockValue::Put(data.into()),
             HummockValue::Delete => HummockValue::Delete,
+            HummockValue::Blob(pointer) => HummockValue::Blob(pointer),
         }
     }
 }
@@ -192,4 +245,18 @@ mod tests {
             HummockValue::from_slice(&result).unwrap()
         );
     }
+
+    #[test]
+    fn test_blob_decode_encode() {
+        let value = HummockValue::<Vec<u8>>::Blob(BlobPointer {
+            blob_id: 233,
+            offset: 1024,
+            len: 4096,
+        });
+        let mut result = vec![];
+        value.encode(&mut result);
+        assert_eq!(result.len(), value.encoded_len());
+        assert_eq!(value, HummockValue::decode(&mut &result[..]).unwrap());
+        assert_eq!(value.as_slice(), HummockValue::from_slice(&result).unwrap());
+    }
 }
@@ -278,12 +278,24 @@ impl StateStore for MemoryStateStore {
     fn ingest_batch(
         &self,
         kv_pairs: Vec<(Bytes, StorageValue)>,
+        delete_ranges: Vec<(Bytes, Bytes)>,
         write_options: WriteOptions,
     ) -> Self::IngestBatchFuture<'_> {
         async move {
             let epoch = write_options.epoch;
             let mut inner = self.inner.write();
             let mut size: usize = 0;
+            for (start_key, end_key) in delete_ranges {
+                size += start_key.len() + end_key.len();
+                let mut deleted_keys: Vec<Bytes> = inner
+                    .range((start_key, Reverse(u64::MAX))..(end_key, Reverse(u64::MAX)))
+                    .map(|((key, _), _)| key.clone())
+                    .collect();
+                deleted_keys.dedup();
+                for key in deleted_keys {
+                    inner.insert((key, Reverse(epoch)), None);
+                }
+            }
             for (key, value) in kv_pairs {
                 size += key.len() + value.size();
                 inner.insert((key, Reverse(epoch)), value.user_value);
@@ -399,6 +411,7 @@ mod tests {
                     (b"a".to_vec().into(), StorageValue::new_put(b"v1".to_vec())),
                     (b"b".to_vec().into(), StorageValue::new_put(b"v1".to_vec())),
                 ],
+                vec![],
                 WriteOptions {
                     epoch: 0,
                     table_id: Default::default(),
@@ -412,6 +425,7 @@ mod tests {
                     (b"a".to_vec().into(), StorageValue::new_put(b"v2".to_vec())),
                     (b"b".to_vec().into(), StorageValue::new_delete()),
                 ],
+                vec![],
                 WriteOptions {
                     epoch: 1,
                     table_id: Default::default(),
@@ -34,6 +34,9 @@ pub struct StoreLocalStatistic {
     pub bloom_filter_true_negative_count: u64,
     pub remote_io_time: Arc<AtomicU64>,
     pub bloom_filter_check_counts: u64,
+    pub prefix_bloom_filter_check_counts: u64,
+    pub prefix_bloom_filter_skip_sst_count: u64,
+    pub blob_value_read_count: u64,
     pub get_shared_buffer_hit_counts: u64,
 
     #[cfg(all(debug_assertions, not(any(test, feature = "test"))))]
@@ -59,6 +62,9 @@ impl StoreLocalStatistic {
             Ordering::Relaxed,
         );
         self.bloom_filter_check_counts += other.bloom_filter_check_counts;
+        self.prefix_bloom_filter_check_counts += other.prefix_bloom_filter_check_counts;
+        self.prefix_bloom_filter_skip_sst_count += other.prefix_bloom_filter_skip_sst_count;
+        self.blob_value_read_count += other.blob_value_read_count;
         self.total_key_count += other.total_key_count;
         self.get_shared_buffer_hit_counts += other.get_shared_buffer_hit_counts;
 
@@ -113,6 +119,24 @@ impl StoreLocalStatistic {
                 .bloom_filter_check_counts
                 .inc_by(self.bloom_filter_check_counts);
         }
+
+        if self.prefix_bloom_filter_check_counts > 0 {
+            metrics
+                .prefix_bloom_filter_check_counts
+                .inc_by(self.prefix_bloom_filter_check_counts);
+        }
+
+        if self.prefix_bloom_filter_skip_sst_count > 0 {
+            metrics
+                .prefix_bloom_filter_skip_sst_counts
+                .inc_by(self.prefix_bloom_filter_skip_sst_count);
+        }
+
+        if self.blob_value_read_count > 0 {
+            metrics
+                .blob_value_read_counts
+                .inc_by(self.blob_value_read_count);
+        }
         if self.processed_key_count > 0 {
             metrics
                 .iter_scan_key_counts
@@ -170,6 +194,9 @@ impl StoreLocalStatistic {
             || self.bloom_filter_true_negative_count != 0
             || self.remote_io_time.load(Ordering::Relaxed) != 0
             || self.bloom_filter_check_counts != 0
+            || self.prefix_bloom_filter_check_counts != 0
+            || self.prefix_bloom_filter_skip_sst_count != 0
+            || self.blob_value_read_count != 0
     }
 }
 
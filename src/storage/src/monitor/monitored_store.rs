@@ -181,10 +181,11 @@ where
     fn ingest_batch(
         &self,
         kv_pairs: Vec<(Bytes, StorageValue)>,
+        delete_ranges: Vec<(Bytes, Bytes)>,
         write_options: WriteOptions,
     ) -> Self::IngestBatchFuture<'_> {
         async move {
-            if kv_pairs.is_empty() {
+            if kv_pairs.is_empty() && delete_ranges.is_empty() {
                 return Ok(0);
             }
 
@@ -194,7 +195,7 @@ where
             let timer = self.stats.write_batch_duration.start_timer();
             let batch_size = self
                 .inner
-                .ingest_batch(kv_pairs, write_options)
+                .ingest_batch(kv_pairs, delete_ranges, write_options)
                 .stack_trace("store_ingest_batch")
                 .await
                 .inspect_err(|e| error!("Failed in ingest_batch: {:?}", e))?;
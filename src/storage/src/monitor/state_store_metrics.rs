@@ -35,6 +35,9 @@ macro_rules! for_all_metrics {
 
             bloom_filter_true_negative_counts: GenericCounter<AtomicU64>,
             bloom_filter_check_counts: GenericCounter<AtomicU64>,
+            prefix_bloom_filter_check_counts: GenericCounter<AtomicU64>,
+            prefix_bloom_filter_skip_sst_counts: GenericCounter<AtomicU64>,
+            blob_value_read_counts: GenericCounter<AtomicU64>,
 
             range_scan_size: Histogram,
             range_scan_duration: Histogram,
@@ -80,6 +83,7 @@ macro_rules! for_all_metrics {
 
             sstable_avg_key_size: Histogram,
             sstable_avg_value_size: Histogram,
+            sstable_compression_ratio: HistogramVec,
         }
     };
 }
@@ -152,6 +156,27 @@ impl StateStoreMetrics {
         )
         .unwrap();
 
+        let prefix_bloom_filter_check_counts = register_int_counter_with_registry!(
+            "state_store_prefix_bloom_filter_check_counts",
+            "Total number of sstables checked by prefix bloom filters in prefix iterators",
+            registry
+        )
+        .unwrap();
+
+        let prefix_bloom_filter_skip_sst_counts = register_int_counter_with_registry!(
+            "state_store_prefix_bloom_filter_skip_sst_counts",
+            "Total number of sstables skipped by prefix bloom filters in prefix iterators",
+            registry
+        )
+        .unwrap();
+
+        let blob_value_read_counts = register_int_counter_with_registry!(
+            "state_store_blob_value_read_counts",
+            "Total number of values read from blob files",
+            registry
+        )
+        .unwrap();
+
         // ----- range_scan -----
         let opts = histogram_opts!(
             "state_store_range_scan_size",
@@ -423,6 +448,15 @@ impl StateStoreMetrics {
 
         let sstable_avg_value_size = register_histogram_with_registry!(opts, registry).unwrap();
 
+        let opts = histogram_opts!(
+            "state_store_sstable_compression_ratio",
+            "Uncompressed size of sstable blocks divided by compressed size",
+            exponential_buckets(0.5, 1.25, 25).unwrap() // max ~105
+        );
+
+        let sstable_compression_ratio =
+            register_histogram_vec_with_registry!(opts, &["algorithm"], registry).unwrap();
+
         Self {
             get_duration,
             get_key_size,
@@ -431,6 +465,9 @@ impl StateStoreMetrics {
 
             bloom_filter_true_negative_counts,
             bloom_filter_check_counts,
+            prefix_bloom_filter_check_counts,
+            prefix_bloom_filter_skip_sst_counts,
+            blob_value_read_counts,
 
             range_scan_size,
             range_scan_duration,
@@ -472,6 +509,7 @@ impl StateStoreMetrics {
 
             sstable_avg_key_size,
             sstable_avg_value_size,
+            sstable_compression_ratio,
         }
     }
 
@@ -152,12 +152,26 @@ pub trait StateStore: Send + Sync + 'static + Clone {
     /// - A version of a kv pair. kv pair associated with larger `Epoch` is guaranteed to be newer
     ///   then kv pair with smaller `Epoch`. Currently this version is only used to derive the
     ///   per-key modification history (e.g. in compaction), not across different keys.
+    ///
+    /// `delete_ranges` are `[start, end)` ranges of keys deleted at the epoch. They only cover
+    /// versions written in earlier epochs, so a key in `kv_pairs` is not deleted by them.
     fn ingest_batch(
         &self,
         kv_pairs: Vec<(Bytes, StorageValue)>,
+        delete_ranges: Vec<(Bytes, Bytes)>,
         write_options: WriteOptions,
     ) -> Self::IngestBatchFuture<'_>;
 
+    /// Deletes all keys in `[start_key, end_key)` written before the epoch in `write_options`.
+    fn delete_range(
+        &self,
+        start_key: Bytes,
+        end_key: Bytes,
+        write_options: WriteOptions,
+    ) -> Self::IngestBatchFuture<'_> {
+        self.ingest_batch(vec![], vec![(start_key, end_key)], write_options)
+    }
+
     /// Opens and returns an iterator for given `prefix_hint` and `full_key_range`
     /// Internally, `prefix_hint` will be used to for checking `bloom_filter` and
     /// `full_key_range` used for iter. (if the `prefix_hint` not None, it should be be included in
@@ -32,6 +32,10 @@ use crate::memory::MemoryStateStore;
 use crate::monitor::{MonitoredStateStore as Monitored, ObjectStoreMetrics, StateStoreMetrics};
 use crate::StateStore;
 
+/// Sub-directory of the file cache directory to cache sstable metas.
+#[cfg(target_os = "linux")]
+const META_FILE_CACHE_DIR: &str = "sstable_meta";
+
 /// The type erased [`StateStore`].
 #[derive(Clone, EnumAsInner)]
 pub enum StateStoreImpl {
@@ -108,11 +112,11 @@ impl StateStoreImpl {
         tiered_cache_metrics_builder: TieredCacheMetricsBuilder,
     ) -> StorageResult<Self> {
         #[cfg(not(target_os = "linux"))]
-        let tiered_cache = TieredCache::none();
+        let (tiered_cache, meta_tiered_cache) = (TieredCache::none(), TieredCache::none());
 
         #[cfg(target_os = "linux")]
-        let tiered_cache = if file_cache_dir.is_empty() {
-            TieredCache::none()
+        let (tiered_cache, meta_tiered_cache) = if file_cache_dir.is_empty() {
+            (TieredCache::none(), TieredCache::none())
         } else {
             use crate::hummock::file_cache::cache::FileCacheOptions;
             use crate::hummock::HummockError;
@@ -129,10 +133,30 @@ impl StateStoreImpl {
                     * 1024,
                 flush_buffer_hooks: vec![],
             };
+            // Sstable metas are cached in a sub-directory, as the keys and values differ from the
+            // blocks'.
+            let meta_options = FileCacheOptions {
+                dir: format!("{}/{}", file_cache_dir, META_FILE_CACHE_DIR),
+                capacity: config.file_cache.meta_capacity_mb * 1024 * 1024,
+                total_buffer_capacity: config.file_cache.meta_total_buffer_capacity_mb
+                    * 1024
+                    * 1024,
+                cache_file_fallocate_unit: config.file_cache.cache_file_fallocate_unit_mb
+                    * 1024
+                    * 1024,
+                cache_meta_fallocate_unit: config.file_cache.cache_meta_fallocate_unit_mb
+                    * 1024
+                    * 1024,
+                flush_buffer_hooks: vec![],
+            };
             let metrics = Arc::new(tiered_cache_metrics_builder.file());
-            TieredCache::file(options, metrics)
+            let tiered_cache = TieredCache::file(options, metrics.clone())
+                .await
+                .map_err(HummockError::tiered_cache)?;
+            let meta_tiered_cache = TieredCache::file(meta_options, metrics)
                 .await
-                .map_err(HummockError::tiered_cache)?
+                .map_err(HummockError::tiered_cache)?;
+            (tiered_cache, meta_tiered_cache)
         };
 
         let store = match s {
@@ -158,6 +182,7 @@ impl StateStoreImpl {
                     config.block_cache_capacity_mb * (1 << 20),
                     config.meta_cache_capacity_mb * (1 << 20),
                     tiered_cache,
+                    meta_tiered_cache,
                 ));
                 let compaction_group_client = Arc::new(CompactionGroupClientImpl::Meta(Arc::new(
                     MetaCompactionGroupClient::new(hummock_meta_client.clone()),
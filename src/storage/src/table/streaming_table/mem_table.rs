@@ -150,6 +150,15 @@ impl MemTable {
         }
     }
 
+    /// Drops all buffered operations with keys in `[start, end)`. An empty `end` means unbounded.
+    pub fn delete_range(&mut self, start: &[u8], end: &[u8]) {
+        let mut deleted = self.buffer.split_off(start);
+        if !end.is_empty() {
+            let mut remaining = deleted.split_off(end);
+            self.buffer.append(&mut remaining);
+        }
+    }
+
     pub fn into_parts(self) -> BTreeMap<Vec<u8>, RowOp> {
         self.buffer
     }
@@ -32,7 +32,7 @@ use piestream_common::util::epoch::EpochPair;
 use piestream_common::util::ordered::OrderedRowSerde;
 use piestream_common::util::sort_util::OrderType;
 use piestream_hummock_sdk::key::{
-    end_bound_of_prefix, prefixed_range, range_of_prefix, start_bound_of_excluded_prefix,
+    end_bound_of_prefix, next_key, prefixed_range, range_of_prefix, start_bound_of_excluded_prefix,
 };
 use piestream_pb::catalog::Table;
 use tracing::trace;
@@ -357,6 +357,11 @@ impl<S: StateStore> StateTable<S> {
         &self.pk_indices
     }
 
+    /// Get the vnodes owned by this table.
+    pub fn vnode_bitmap(&self) -> &Bitmap {
+        &self.vnodes
+    }
+
     pub fn is_dirty(&self) -> bool {
         self.mem_table.is_dirty()
     }
@@ -490,6 +495,53 @@ impl<S: StateStore> StateTable<S> {
             .unwrap_or_else(|e| self.handle_mem_table_error(e));
     }
 
+    /// Delete all rows with the given pk prefix, e.g. a whole group of `GroupTopN`. Instead of a
+    /// point delete per row, a single range tombstone is written to the state store at the current
+    /// epoch, and the buffered operations on these rows are dropped. Rows inserted afterwards in
+    /// the same epoch are not affected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pk_prefix` doesn't cover the distribution key, as the range tombstone is only
+    /// written under the vnode computed from the prefix.
+    pub async fn delete_with_pk_prefix(&mut self, pk_prefix: &Row) -> StorageResult<()> {
+        let prefix_len = pk_prefix.size();
+        let covers_dist_key = match self.vnode_col_idx_in_pk {
+            Some(vnode_col_idx_in_pk) => vnode_col_idx_in_pk < prefix_len,
+            None => self.dist_key_in_pk_indices.iter().all(|&d| d < prefix_len),
+        };
+        assert!(
+            covers_dist_key,
+            "pk prefix {:?} doesn't cover the distribution key {:?} in pk, table_id: {}",
+            pk_prefix,
+            self.dist_key_in_pk_indices,
+            self.table_id()
+        );
+        let vnode = self.compute_vnode(pk_prefix);
+        assert!(
+            self.vnodes.is_set(vnode as usize),
+            "vnode {} of the pk prefix is not owned by the table, table_id: {}",
+            vnode,
+            self.table_id()
+        );
+
+        let prefix_serializer = self.pk_serde.prefix(prefix_len);
+        let encoded_prefix = serialize_pk(pk_prefix, &prefix_serializer);
+        let vnode = vnode.to_be_bytes();
+        let start_key = [&vnode, &encoded_prefix[..]].concat();
+        let end_key = next_key(&start_key);
+
+        self.mem_table.delete_range(&start_key, &end_key);
+
+        let mut write_batch = self.keyspace.start_write_batch(WriteOptions {
+            epoch: self.epoch(),
+            table_id: self.table_id(),
+        });
+        write_batch.delete_range(start_key, end_key);
+        write_batch.ingest().await?;
+        Ok(())
+    }
+
     /// Write batch with a `StreamChunk` which should have the same schema with the table.
     // allow(izip, which use zip instead of zip_eq)
     #[allow(clippy::disallowed_methods)]
@@ -22,7 +22,7 @@ use piestream_common::util::sort_util::OrderType;
 use crate::error::StorageResult;
 use crate::memory::MemoryStateStore;
 use crate::table::streaming_table::state_table::StateTable;
-use crate::table::DEFAULT_VNODE;
+use crate::table::{Distribution, DEFAULT_VNODE};
 
 // test state table
 #[tokio::test]
@@ -656,6 +656,94 @@ async fn test_state_table_iter_with_prefix() {
     assert!(res.is_none());
 }
 
+#[tokio::test]
+async fn test_state_table_delete_with_pk_prefix() {
+    fn row(a: i32, b: i32) -> Row {
+        Row(vec![Some(a.into()), Some(b.into()), Some((a * b).into())])
+    }
+
+    async fn check(state: &StateTable<MemoryStateStore>) {
+        let pk_prefix = Row(vec![Some(1_i32.into())]);
+        let iter = state.iter_with_pk_prefix(&pk_prefix).await.unwrap();
+        pin_mut!(iter);
+        let res = iter.next().await.unwrap().unwrap();
+        assert_eq!(&row(1, 55), res.as_ref());
+        assert!(iter.next().await.is_none());
+
+        let pk = Row(vec![Some(1_i32.into()), Some(11_i32.into())]);
+        assert!(state.get_row(&pk).await.unwrap().is_none());
+        let pk = Row(vec![Some(2_i32.into()), Some(33_i32.into())]);
+        assert_eq!(state.get_row(&pk).await.unwrap(), Some(row(2, 33)));
+    }
+
+    let state_store = MemoryStateStore::new();
+    let order_types = vec![OrderType::Ascending, OrderType::Ascending];
+    let column_ids = vec![ColumnId::from(0), ColumnId::from(1), ColumnId::from(2)];
+    let column_descs = vec![
+        ColumnDesc::unnamed(column_ids[0], DataType::Int32),
+        ColumnDesc::unnamed(column_ids[1], DataType::Int32),
+        ColumnDesc::unnamed(column_ids[2], DataType::Int32),
+    ];
+    let pk_index = vec![0_usize, 1_usize];
+    let mut state = StateTable::new_without_distribution(
+        state_store.clone(),
+        TableId::from(0x42),
+        column_descs,
+        order_types,
+        pk_index,
+    );
+
+    let mut epoch = EpochPair::new_test_epoch(1);
+    state.init_epoch(epoch);
+    state.insert(row(1, 11));
+    state.insert(row(1, 22));
+    state.insert(row(2, 33));
+    epoch = epoch.inc();
+    state.commit(epoch).await.unwrap();
+
+    // The buffered insert is dropped together with the committed rows, while the insert after the
+    // range delete in the same epoch is kept.
+    state.insert(row(1, 44));
+    state
+        .delete_with_pk_prefix(&Row(vec![Some(1_i32.into())]))
+        .await
+        .unwrap();
+    state.insert(row(1, 55));
+    check(&state).await;
+
+    epoch = epoch.inc();
+    state.commit(epoch).await.unwrap();
+    check(&state).await;
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_state_table_delete_with_pk_prefix_not_covering_dist_key() {
+    let state_store = MemoryStateStore::new();
+    let column_descs = vec![
+        ColumnDesc::unnamed(ColumnId::from(0), DataType::Int32),
+        ColumnDesc::unnamed(ColumnId::from(1), DataType::Int32),
+        ColumnDesc::unnamed(ColumnId::from(2), DataType::Int32),
+    ];
+    let order_types = vec![OrderType::Ascending, OrderType::Ascending];
+    let pk_index = vec![0_usize, 1_usize];
+    // The distribution key is the second column of the pk.
+    let mut state = StateTable::new_with_distribution(
+        state_store,
+        TableId::from(0x42),
+        column_descs,
+        order_types,
+        pk_index,
+        Distribution::all_vnodes(vec![1]),
+        vec![0, 1, 2],
+    );
+    state.init_epoch(EpochPair::new_test_epoch(1));
+    state
+        .delete_with_pk_prefix(&Row(vec![Some(1_i32.into())]))
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn test_state_table_iter_with_pk_range() {
     let state_store = MemoryStateStore::new();
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use bytes::Bytes;
+use piestream_hummock_sdk::key::next_key;
 
 use crate::error::StorageResult;
 use crate::hummock::HummockError;
@@ -26,6 +27,9 @@ pub struct WriteBatch<'a, S: StateStore> {
 
     batch: Vec<(Bytes, StorageValue)>,
 
+    /// `[start, end)` key ranges to delete.
+    delete_ranges: Vec<(Bytes, Bytes)>,
+
     write_options: WriteOptions,
 }
 
@@ -38,6 +42,7 @@ where
         Self {
             store,
             batch: Vec::new(),
+            delete_ranges: Vec::new(),
             write_options,
         }
     }
@@ -47,6 +52,7 @@ where
         Self {
             store,
             batch: Vec::with_capacity(capacity),
+            delete_ranges: Vec::new(),
             write_options,
         }
     }
@@ -79,16 +85,21 @@ where
         }
     }
 
-    /// Returns `true` if the batch contains no key-value pairs.
+    /// Returns `true` if the batch contains no key-value pairs nor delete ranges.
     pub fn is_empty(&self) -> bool {
-        self.batch.is_empty()
+        self.batch.is_empty() && self.delete_ranges.is_empty()
+    }
+
+    /// Deletes all keys in `[start, end)` written in earlier epochs.
+    pub fn delete_range(&mut self, start: impl Into<Bytes>, end: impl Into<Bytes>) {
+        self.delete_ranges.push((start.into(), end.into()));
     }
 
     /// Ingests this batch into the associated state store.
     pub async fn ingest(mut self) -> StorageResult<()> {
         self.preprocess()?;
         self.store
-            .ingest_batch(self.batch, self.write_options)
+            .ingest_batch(self.batch, self.delete_ranges, self.write_options)
             .await?;
         Ok(())
     }
@@ -132,6 +143,18 @@ impl<'a, S: StateStore> KeySpaceWriteBatch<'a, S> {
         self.do_push(key.as_ref(), StorageValue::new_delete());
     }
 
+    /// Deletes all keys in `[start, end)` prepended by the prefix of `keyspace`, which are written
+    /// in earlier epochs. An empty `end` means the end of the `keyspace`.
+    pub fn delete_range(&mut self, start: impl AsRef<[u8]>, end: impl AsRef<[u8]>) {
+        let start = self.keyspace.prefixed_key(start);
+        let end = if end.as_ref().is_empty() {
+            next_key(self.keyspace.key())
+        } else {
+            self.keyspace.prefixed_key(end)
+        };
+        self.global.delete_range(start, end);
+    }
+
     pub async fn ingest(self) -> StorageResult<()> {
         self.global.ingest().await
     }
@@ -144,7 +167,7 @@ mod tests {
 
     use crate::memory::MemoryStateStore;
     use crate::storage_value::StorageValue;
-    use crate::store::WriteOptions;
+    use crate::store::{ReadOptions, StateStore, WriteOptions};
     use crate::Keyspace;
 
     #[tokio::test]
@@ -166,4 +189,53 @@ mod tests {
             .await
             .expect_err("Should panic here because of duplicate key.");
     }
+
+    #[tokio::test]
+    async fn test_delete_range() {
+        let state_store = MemoryStateStore::new();
+        let key_space = Keyspace::table_root(state_store.clone(), &TableId::from(0x118));
+        let write_options = |epoch| WriteOptions {
+            epoch,
+            table_id: Default::default(),
+        };
+        let read_options = |epoch| ReadOptions {
+            epoch,
+            table_id: Default::default(),
+            retention_seconds: None,
+        };
+
+        let mut key_space_batch = key_space.start_write_batch(write_options(1));
+        for key in ["aa", "bb", "cc", "dd"] {
+            key_space_batch.put(Bytes::from(key), StorageValue::new_put("444"));
+        }
+        key_space_batch.ingest().await.unwrap();
+
+        let mut key_space_batch = key_space.start_write_batch(write_options(2));
+        key_space_batch.delete_range(Bytes::from("bb"), Bytes::from("dd"));
+        key_space_batch.put(Bytes::from("cc"), StorageValue::new_put("555"));
+        key_space_batch.ingest().await.unwrap();
+
+        let get = |key: &str, epoch| {
+            let key = key_space.prefixed_key(key);
+            let state_store = state_store.clone();
+            async move {
+                state_store
+                    .get(&key, true, read_options(epoch))
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(get("bb", 1).await, Some(Bytes::from("444")));
+        assert_eq!(get("aa", 2).await, Some(Bytes::from("444")));
+        assert_eq!(get("bb", 2).await, None);
+        assert_eq!(get("cc", 2).await, Some(Bytes::from("555")));
+        assert_eq!(get("dd", 2).await, Some(Bytes::from("444")));
+
+        let mut key_space_batch = key_space.start_write_batch(write_options(3));
+        key_space_batch.delete_range(Bytes::new(), Bytes::new());
+        key_space_batch.ingest().await.unwrap();
+        for key in ["aa", "cc", "dd"] {
+            assert_eq!(get(key, 3).await, None);
+        }
+    }
 }
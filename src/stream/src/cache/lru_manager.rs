@@ -23,10 +23,11 @@ use piestream_common::util::epoch::Epoch;
 use tikv_jemalloc_ctl::{epoch as jemalloc_epoch, stats as jemalloc_stats};
 use tracing;
 
-use super::ManagedLruCache;
+use super::{CacheMetricsInfo, ManagedLruCache};
+use crate::executor::monitor::StreamingMetrics;
 
-/// When `enable_managed_cache` is set, compute node will launch a [`LruManager`] to limit the
-/// memory usage.
+/// The compute node launches a [`LruManager`] to limit the memory usage. The caches of all stateful
+/// executors are created by the manager and evicted by the watermark epoch it advances.
 pub struct LruManager {
     /// All cached data before the watermark should be evicted.
     watermark_epoch: Arc<AtomicU64>,
@@ -34,6 +35,8 @@ pub struct LruManager {
     total_memory_available_bytes: usize,
     /// Barrier interval.
     barrier_interval_ms: u32,
+
+    metrics: Arc<StreamingMetrics>,
 }
 
 pub type LruManagerRef = Arc<LruManager>;
@@ -42,40 +45,65 @@ impl LruManager {
     const EVICTION_THRESHOLD_AGGRESSIVE: f64 = 0.9;
     const EVICTION_THRESHOLD_GRACEFUL: f64 = 0.7;
 
-    pub fn new(total_memory_available_bytes: usize, barrier_interval_ms: u32) -> Arc<Self> {
+    pub fn new(
+        total_memory_available_bytes: usize,
+        barrier_interval_ms: u32,
+        metrics: Arc<StreamingMetrics>,
+    ) -> Arc<Self> {
         Arc::new(Self {
             watermark_epoch: Arc::new(0.into()),
             total_memory_available_bytes,
             barrier_interval_ms,
+            metrics,
         })
     }
 
-    pub fn create_cache<K: Hash + Eq, V>(&self) -> ManagedLruCache<K, V> {
-        ManagedLruCache {
-            inner: LruCache::unbounded(),
-            watermark_epoch: self.watermark_epoch.clone(),
-        }
+    /// Create a manager which never advances the watermark, i.e., never evicts the caches unless
+    /// `run` is called.
+    pub fn for_test() -> Arc<Self> {
+        Self::new(usize::MAX, 1000, Arc::new(StreamingMetrics::unused()))
+    }
+
+    pub fn create_cache<K: Hash + Eq, V>(
+        &self,
+        metrics_info: CacheMetricsInfo,
+    ) -> ManagedLruCache<K, V> {
+        ManagedLruCache::new_inner(
+            LruCache::unbounded(),
+            self.watermark_epoch.clone(),
+            metrics_info,
+        )
     }
 
     pub fn create_cache_with_hasher_in<K: Hash + Eq, V, S: BuildHasher, A: Clone + Allocator>(
         &self,
         hasher: S,
         alloc: A,
+        metrics_info: CacheMetricsInfo,
     ) -> ManagedLruCache<K, V, S, A> {
-        ManagedLruCache {
-            inner: LruCache::unbounded_with_hasher_in(hasher, alloc),
-            watermark_epoch: self.watermark_epoch.clone(),
-        }
+        ManagedLruCache::new_inner(
+            LruCache::unbounded_with_hasher_in(hasher, alloc),
+            self.watermark_epoch.clone(),
+            metrics_info,
+        )
     }
 
     pub fn create_cache_with_hasher<K: Hash + Eq, V, S: BuildHasher>(
         &self,
         hasher: S,
+        metrics_info: CacheMetricsInfo,
     ) -> ManagedLruCache<K, V, S> {
-        ManagedLruCache {
-            inner: LruCache::unbounded_with_hasher(hasher),
-            watermark_epoch: self.watermark_epoch.clone(),
-        }
+        ManagedLruCache::new_inner(
+            LruCache::unbounded_with_hasher(hasher),
+            self.watermark_epoch.clone(),
+            metrics_info,
+        )
+    }
+
+    /// The shared watermark epoch. Caches not created by [`LruManager::create_cache`], e.g. the
+    /// range cache of dynamic filter, can check it to decide whether to evict themselves.
+    pub fn watermark_epoch(&self) -> Arc<AtomicU64> {
+        self.watermark_epoch.clone()
     }
 
     fn set_watermark_time_ms(&self, time_ms: u64) {
@@ -149,6 +177,11 @@ impl LruManager {
                 step
             };
 
+            self.metrics.lru_watermark_step.set(step as i64);
+            self.metrics
+                .jemalloc_allocated_bytes
+                .set(cur_total_bytes_used as i64);
+
             last_total_bytes_used = cur_total_bytes_used;
             watermark_time_ms += self.barrier_interval_ms as u64 * step;
 
@@ -159,23 +192,50 @@ impl LruManager {
 
 #[cfg(test)]
 mod tests {
-    use crate::cache::EvictableHashMap;
+    use std::sync::atomic::Ordering;
 
-    #[test]
-    fn test_len_after_evict() {
-        let target_cap = 114;
-        let items_count = 514;
-        let mut map = EvictableHashMap::new(target_cap);
+    use super::LruManager;
+    use crate::cache::CacheMetricsInfo;
 
-        for i in 0..items_count {
-            map.put(i, ());
-        }
-        assert_eq!(map.len(), items_count);
-
-        map.evict_to_target_cap();
-        assert_eq!(map.len(), target_cap);
-
-        assert!(map.get(&(items_count - target_cap - 1)).is_none());
-        assert!(map.get(&(items_count - target_cap)).is_some());
+    #[test]
+    fn test_evict_by_watermark() {
+        let lru_manager = LruManager::for_test();
+        let mut cache = lru_manager.create_cache(CacheMetricsInfo::for_test());
+
+        cache.update_epoch(1);
+        cache.put(b"k1".to_vec(), b"v1".to_vec());
+        cache.update_epoch(2);
+        cache.put(b"k2".to_vec(), b"value2".to_vec());
+        let entry_size =
+            |k: &[u8], v: &[u8]| k.len() + v.len() + 2 * std::mem::size_of::<Vec<u8>>();
+        assert_eq!(
+            cache.kv_heap_size(),
+            entry_size(b"k1", b"v1") + entry_size(b"k2", b"value2")
+        );
+
+        // Nothing is evicted before the watermark advances.
+        cache.evict();
+        assert_eq!(cache.len(), 2);
+
+        lru_manager.watermark_epoch.store(2, Ordering::Relaxed);
+        cache.evict();
+        assert_eq!(cache.len(), 1);
+        assert!(cache.contains(&b"k2".to_vec()));
+        assert_eq!(cache.kv_heap_size(), entry_size(b"k2", b"value2"));
+
+        // Mutations through the guard are accounted.
+        cache
+            .get_mut(&b"k2".to_vec())
+            .unwrap()
+            .extend_from_slice(&[0; 100]);
+        assert_eq!(
+            cache.kv_heap_size(),
+            b"k2".len()
+                + cache.peek(&b"k2".to_vec()).unwrap().capacity()
+                + 2 * std::mem::size_of::<Vec<u8>>()
+        );
+
+        cache.pop(&b"k2".to_vec());
+        assert_eq!(cache.kv_heap_size(), 0);
     }
 }
@@ -19,21 +19,219 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use lru::{DefaultHasher, LruCache};
+use piestream_common::collection::estimate_size::EstimateSize;
+use prometheus::core::{AtomicI64, AtomicU64 as PromAtomicU64, GenericCounter, GenericGauge};
+
+use crate::executor::monitor::StreamingMetrics;
+use crate::task::ActorId;
+
+/// Where a [`ManagedLruCache`] reports its memory usage and hit rate.
+pub struct CacheMetricsInfo {
+    metrics: Arc<StreamingMetrics>,
+    actor_id: String,
+    /// Description of the cache, e.g. the identity of the executor and the side of a join.
+    executor: String,
+}
+
+impl CacheMetricsInfo {
+    pub fn new(
+        metrics: Arc<StreamingMetrics>,
+        actor_id: ActorId,
+        executor: impl Into<String>,
+    ) -> Self {
+        Self {
+            metrics,
+            actor_id: actor_id.to_string(),
+            executor: executor.into(),
+        }
+    }
+
+    pub fn for_test() -> Self {
+        Self::new(Arc::new(StreamingMetrics::unused()), 0, "test")
+    }
+
+    fn labels(&self) -> [&str; 2] {
+        [&self.actor_id, &self.executor]
+    }
+}
+
+/// Reports the memory usage and hit rate of a cache. The metrics are removed when dropped.
+pub struct CacheMetrics {
+    /// Lookup count and miss count since the last report.
+    lookup_count: u64,
+    miss_count: u64,
+    memory_usage_metrics: GenericGauge<AtomicI64>,
+    lookup_count_metrics: GenericCounter<PromAtomicU64>,
+    miss_count_metrics: GenericCounter<PromAtomicU64>,
+    info: CacheMetricsInfo,
+}
+
+impl CacheMetrics {
+    pub fn new(info: CacheMetricsInfo) -> Self {
+        let labels = info.labels();
+        let memory_usage_metrics = info
+            .metrics
+            .executor_cache_memory_usage
+            .with_label_values(&labels);
+        memory_usage_metrics.set(0);
+        let lookup_count_metrics = info
+            .metrics
+            .executor_cache_lookup_count
+            .with_label_values(&labels);
+        let miss_count_metrics = info
+            .metrics
+            .executor_cache_miss_count
+            .with_label_values(&labels);
+
+        Self {
+            lookup_count: 0,
+            miss_count: 0,
+            memory_usage_metrics,
+            lookup_count_metrics,
+            miss_count_metrics,
+            info,
+        }
+    }
+
+    pub fn record_lookup(&mut self, hit: bool) {
+        self.lookup_count += 1;
+        if !hit {
+            self.miss_count += 1;
+        }
+    }
+
+    /// Report the memory usage and the lookups since the last report.
+    pub fn report(&mut self, kv_heap_size: usize) {
+        self.memory_usage_metrics.set(kv_heap_size as i64);
+        self.lookup_count_metrics.inc_by(self.lookup_count);
+        self.miss_count_metrics.inc_by(self.miss_count);
+        self.lookup_count = 0;
+        self.miss_count = 0;
+    }
+}
+
+impl Drop for CacheMetrics {
+    fn drop(&mut self) {
+        let labels = self.info.labels();
+        let metrics = &self.info.metrics;
+        // Stop reporting the metrics of this cache. Errors are ignored since it's fine if the
+        // labels have already been removed.
+        let _ = metrics
+            .executor_cache_memory_usage
+            .remove_label_values(&labels);
+        let _ = metrics
+            .executor_cache_lookup_count
+            .remove_label_values(&labels);
+        let _ = metrics
+            .executor_cache_miss_count
+            .remove_label_values(&labels);
+    }
+}
 
 /// The managed cache is a lru cache that bounds the memory usage by epoch.
 /// Should be used with `LruManager`.
+///
+/// The cache keeps track of the estimated heap size of its entries, so all the modifications
+/// must go through the methods of the cache instead of the inner [`LruCache`].
 pub struct ManagedLruCache<K, V, S = DefaultHasher, A: Clone + Allocator = Global> {
-    pub(super) inner: LruCache<K, V, S, A>,
+    inner: LruCache<K, V, S, A>,
     /// The entry with epoch less than water should be evicted.
     /// Should only be updated by the `LruManager`.
-    pub(super) watermark_epoch: Arc<AtomicU64>,
+    watermark_epoch: Arc<AtomicU64>,
+    /// The estimated heap size of all keys and values in the cache.
+    kv_heap_size: usize,
+    metrics: CacheMetrics,
 }
 
-impl<K: Hash + Eq, V, S: BuildHasher, A: Clone + Allocator> ManagedLruCache<K, V, S, A> {
-    /// Evict epochs lower than the watermark
+impl<K, V, S, A: Clone + Allocator> ManagedLruCache<K, V, S, A> {
+    pub(super) fn new_inner(
+        inner: LruCache<K, V, S, A>,
+        watermark_epoch: Arc<AtomicU64>,
+        metrics_info: CacheMetricsInfo,
+    ) -> Self {
+        Self {
+            inner,
+            watermark_epoch,
+            kv_heap_size: 0,
+            metrics: CacheMetrics::new(metrics_info),
+        }
+    }
+}
+
+impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Allocator>
+    ManagedLruCache<K, V, S, A>
+{
+    /// Evict epochs lower than the watermark, and report the metrics of the cache.
     pub fn evict(&mut self) {
         let epoch = self.watermark_epoch.load(Ordering::Relaxed);
-        self.inner.evict_by_epoch(epoch);
+        while let Some((key, value)) = self.inner.pop_lru_by_epoch(epoch) {
+            self.kv_heap_size_dec(key.estimated_size() + value.estimated_size());
+        }
+        self.metrics.report(self.kv_heap_size);
+    }
+
+    /// Update the current epoch for the cache. Entries accessed later will be tagged with it.
+    pub fn update_epoch(&mut self, epoch: u64) {
+        self.inner.update_epoch(epoch);
+    }
+
+    /// Put a key-value pair into the cache, returning the old value if the key was present.
+    pub fn put(&mut self, k: K, v: V) -> Option<V> {
+        let key_size = k.estimated_size();
+        self.kv_heap_size_inc(key_size + v.estimated_size());
+        let old_val = self.inner.put(k, v);
+        if let Some(old_val) = &old_val {
+            self.kv_heap_size_dec(key_size + old_val.estimated_size());
+        }
+        old_val
+    }
+
+    /// Push a key-value pair into the cache, returning the old entry if the key was present.
+    pub fn push(&mut self, k: K, v: V) -> Option<(K, V)> {
+        self.kv_heap_size_inc(k.estimated_size() + v.estimated_size());
+        let old_kv = self.inner.push(k, v);
+        if let Some((old_key, old_val)) = &old_kv {
+            self.kv_heap_size_dec(old_key.estimated_size() + old_val.estimated_size());
+        }
+        old_kv
+    }
+
+    /// Look up a key and mark it as recently used. Counted in the hit rate of the cache.
+    pub fn get(&mut self, k: &K) -> Option<&V> {
+        let v = self.inner.get(k);
+        self.metrics.record_lookup(v.is_some());
+        v
+    }
+
+    /// Look up a key mutably and mark it as recently used. Counted in the hit rate of the cache.
+    pub fn get_mut(&mut self, k: &K) -> Option<MutGuard<'_, V>> {
+        let v = self.inner.get_mut(k);
+        self.metrics.record_lookup(v.is_some());
+        v.map(|inner| MutGuard::new(inner, &mut self.kv_heap_size))
+    }
+
+    /// Get a mutable reference to the value of a key without updating the lru order. Used for
+    /// maintaining cached entries, so it's not counted in the hit rate of the cache.
+    pub fn peek_mut(&mut self, k: &K) -> Option<MutGuard<'_, V>> {
+        self.inner
+            .peek_mut(k)
+            .map(|inner| MutGuard::new(inner, &mut self.kv_heap_size))
+    }
+
+    /// Remove a key and return its value. Counted in the hit rate of the cache.
+    pub fn pop(&mut self, k: &K) -> Option<V> {
+        let v = self.inner.pop(k);
+        self.metrics.record_lookup(v.is_some());
+        if let Some(v) = &v {
+            self.kv_heap_size_dec(k.estimated_size() + v.estimated_size());
+        }
+        v
+    }
+
+    /// Remove all entries in the cache.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+        self.kv_heap_size = 0;
     }
 
     /// An iterator visiting all values in most-recently used order. The iterator element type is
@@ -42,10 +240,17 @@ impl<K: Hash + Eq, V, S: BuildHasher, A: Clone + Allocator> ManagedLruCache<K, V
         self.iter().map(|(_k, v)| v)
     }
 
-    /// An iterator visiting all values mutably in most-recently used order. The iterator element
-    /// type is &mut V.
-    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
-        self.iter_mut().map(|(_k, v)| v)
+    /// The estimated heap size of all keys and values in the cache.
+    pub fn kv_heap_size(&self) -> usize {
+        self.kv_heap_size
+    }
+
+    fn kv_heap_size_inc(&mut self, size: usize) {
+        self.kv_heap_size = self.kv_heap_size.saturating_add(size);
+    }
+
+    fn kv_heap_size_dec(&mut self, size: usize) {
+        self.kv_heap_size = self.kv_heap_size.saturating_sub(size);
     }
 }
 
@@ -57,8 +262,44 @@ impl<K, V, S, A: Clone + Allocator> Deref for ManagedLruCache<K, V, S, A> {
     }
 }
 
-impl<K, V, S, A: Clone + Allocator> DerefMut for ManagedLruCache<K, V, S, A> {
+/// A mutable reference to a value in [`ManagedLruCache`]. The estimated heap size of the cache is
+/// updated with the new size of the value when the guard is dropped.
+pub struct MutGuard<'a, V: EstimateSize> {
+    inner: &'a mut V,
+    original_val_size: usize,
+    total_size: &'a mut usize,
+}
+
+impl<'a, V: EstimateSize> MutGuard<'a, V> {
+    fn new(inner: &'a mut V, total_size: &'a mut usize) -> Self {
+        let original_val_size = inner.estimated_size();
+        Self {
+            inner,
+            original_val_size,
+            total_size,
+        }
+    }
+}
+
+impl<'a, V: EstimateSize> Drop for MutGuard<'a, V> {
+    fn drop(&mut self) {
+        *self.total_size = self
+            .total_size
+            .saturating_sub(self.original_val_size)
+            .saturating_add(self.inner.estimated_size());
+    }
+}
+
+impl<'a, V: EstimateSize> Deref for MutGuard<'a, V> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner
+    }
+}
+
+impl<'a, V: EstimateSize> DerefMut for MutGuard<'a, V> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.inner
+        self.inner
     }
 }
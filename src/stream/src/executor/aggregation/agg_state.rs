@@ -16,6 +16,7 @@ use std::fmt::Debug;
 
 use itertools::Itertools;
 use piestream_common::array::{ArrayBuilderImpl, Op, Row};
+use piestream_common::collection::estimate_size::EstimateSize;
 use piestream_common::types::Datum;
 use piestream_storage::StateStore;
 
@@ -48,6 +49,14 @@ impl<S: StateStore> Debug for AggState<S> {
     }
 }
 
+impl<S: StateStore> EstimateSize for AggState<S> {
+    fn estimated_heap_size(&self) -> usize {
+        self.group_key.estimated_heap_size()
+            + self.managed_states.estimated_heap_size()
+            + self.prev_outputs.estimated_heap_size()
+    }
+}
+
 /// We assume the first state of aggregation is always `StreamingRowCountAgg`.
 pub const ROW_COUNT_COLUMN: usize = 0;
 
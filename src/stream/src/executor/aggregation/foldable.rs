@@ -237,6 +237,74 @@ where
     }
 }
 
+/// `BitAndable` return bitwise AND of all values.
+/// It produces the same type of output as input `S`.
+#[derive(Debug)]
+pub struct BitAndable<S>
+where
+    S: Scalar + std::ops::BitAnd<Output = S>,
+{
+    _phantom: PhantomData<S>,
+}
+
+impl<S> StreamingFoldable<S, S> for BitAndable<S>
+where
+    S: Scalar + std::ops::BitAnd<Output = S>,
+{
+    fn accumulate(
+        result: Option<&S>,
+        input: Option<S::ScalarRefType<'_>>,
+    ) -> StreamExecutorResult<Option<S>> {
+        Ok(match (result, input) {
+            (Some(x), Some(y)) => Some(x.clone() & y.to_owned_scalar()),
+            (None, Some(y)) => Some(y.to_owned_scalar()),
+            (Some(x), None) => Some(x.clone()),
+            (None, None) => None,
+        })
+    }
+
+    fn retract(
+        _result: Option<&S>,
+        _input: Option<S::ScalarRefType<'_>>,
+    ) -> StreamExecutorResult<Option<S>> {
+        bail!("insert only for bit_and")
+    }
+}
+
+/// `BitOrable` return bitwise OR of all values.
+/// It produces the same type of output as input `S`.
+#[derive(Debug)]
+pub struct BitOrable<S>
+where
+    S: Scalar + std::ops::BitOr<Output = S>,
+{
+    _phantom: PhantomData<S>,
+}
+
+impl<S> StreamingFoldable<S, S> for BitOrable<S>
+where
+    S: Scalar + std::ops::BitOr<Output = S>,
+{
+    fn accumulate(
+        result: Option<&S>,
+        input: Option<S::ScalarRefType<'_>>,
+    ) -> StreamExecutorResult<Option<S>> {
+        Ok(match (result, input) {
+            (Some(x), Some(y)) => Some(x.clone() | y.to_owned_scalar()),
+            (None, Some(y)) => Some(y.to_owned_scalar()),
+            (Some(x), None) => Some(x.clone()),
+            (None, None) => None,
+        })
+    }
+
+    fn retract(
+        _result: Option<&S>,
+        _input: Option<S::ScalarRefType<'_>>,
+    ) -> StreamExecutorResult<Option<S>> {
+        bail!("insert only for bit_or")
+    }
+}
+
 impl<R, I, S> StreamingAggState<I> for StreamingFoldAgg<R, I, S>
 where
     R: Array,
@@ -394,6 +462,8 @@ impl_fold_agg! { IntervalArray, Interval, IntervalArray }
 impl_fold_agg! { NaiveTimeArray, NaiveTime, NaiveTimeArray }
 impl_fold_agg! { NaiveDateArray, NaiveDate, NaiveDateArray }
 impl_fold_agg! { NaiveDateTimeArray, NaiveDateTime, NaiveDateTimeArray }
+// bool_and/bool_or
+impl_fold_agg! { BoolArray, Bool, BoolArray }
 // sum
 impl_fold_agg! { DecimalArray, Decimal, I64Array }
 // avg
@@ -416,6 +486,10 @@ mod tests {
 
     type TestStreamingMaxAgg<R> = StreamingFoldAgg<R, R, Maximizable<<R as Array>::OwnedItem>>;
 
+    type TestStreamingBitAndAgg<R> = StreamingFoldAgg<R, R, BitAndable<<R as Array>::OwnedItem>>;
+
+    type TestStreamingBitOrAgg<R> = StreamingFoldAgg<R, R, BitOrable<<R as Array>::OwnedItem>>;
+
     #[test]
     /// This test uses `Box<dyn StreamingAggStateImpl>` to test a state.
     fn test_primitive_sum_boxed() {
@@ -622,4 +696,31 @@ mod tests {
         .unwrap();
         assert_eq!(agg.get_output().unwrap().unwrap().as_int64(), &100);
     }
+
+    #[test]
+    fn test_bit_and_or() {
+        let mut bit_and = TestStreamingBitAndAgg::<I32Array>::default();
+        let mut bit_or = TestStreamingBitOrAgg::<I32Array>::default();
+        for agg in [
+            &mut bit_and as &mut dyn StreamingAggStateImpl,
+            &mut bit_or as &mut dyn StreamingAggStateImpl,
+        ] {
+            agg.apply_batch(
+                &[Op::Insert, Op::Insert, Op::Insert],
+                None,
+                &[&array!(I32Array, [Some(0b1100), None, Some(0b1010)]).into()],
+            )
+            .unwrap();
+        }
+        assert_eq!(bit_and.get_output().unwrap().unwrap().as_int32(), &0b1000);
+        assert_eq!(bit_or.get_output().unwrap().unwrap().as_int32(), &0b1110);
+
+        assert!(bit_and
+            .apply_batch(
+                &[Op::Delete],
+                None,
+                &[&array!(I32Array, [Some(0b1100)]).into()],
+            )
+            .is_err());
+    }
 }
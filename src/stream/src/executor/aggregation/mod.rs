@@ -63,6 +63,12 @@ pub type StreamingMinAgg<S> = StreamingFoldAgg<S, S, Minimizable<<S as Array>::O
 /// `StreamingMaxAgg` get maximum data of the same type.
 pub type StreamingMaxAgg<S> = StreamingFoldAgg<S, S, Maximizable<<S as Array>::OwnedItem>>;
 
+/// `StreamingBitAndAgg` get bitwise AND of data of the same type.
+pub type StreamingBitAndAgg<S> = StreamingFoldAgg<S, S, BitAndable<<S as Array>::OwnedItem>>;
+
+/// `StreamingBitOrAgg` get bitwise OR of data of the same type.
+pub type StreamingBitOrAgg<S> = StreamingFoldAgg<S, S, BitOrable<<S as Array>::OwnedItem>>;
+
 /// `StreamingAggState` records a state of streaming expression. For example,
 /// there will be `StreamingAggCompare` and `StreamingAggSum`.
 pub trait StreamingAggState<A: Array>: Send + Sync + 'static {
@@ -224,6 +230,16 @@ pub fn create_streaming_agg_state(
                     (Max, float32, float32, StreamingMaxAgg::<F32Array>),
                     (Max, float64, float64, StreamingMaxAgg::<F64Array>),
                     (Max, interval, interval, StreamingMaxAgg::<IntervalArray>),
+                    // BoolAnd / BoolOr
+                    (BoolAnd, boolean, boolean, StreamingMinAgg::<BoolArray>),
+                    (BoolOr, boolean, boolean, StreamingMaxAgg::<BoolArray>),
+                    // BitAnd / BitOr
+                    (BitAnd, int16, int16, StreamingBitAndAgg::<I16Array>),
+                    (BitAnd, int32, int32, StreamingBitAndAgg::<I32Array>),
+                    (BitAnd, int64, int64, StreamingBitAndAgg::<I64Array>),
+                    (BitOr, int16, int16, StreamingBitOrAgg::<I16Array>),
+                    (BitOr, int32, int32, StreamingBitOrAgg::<I32Array>),
+                    (BitOr, int64, int64, StreamingBitOrAgg::<I64Array>),
                 ]
             )
         }
@@ -243,7 +259,12 @@ pub fn create_streaming_agg_state(
                 }
             }
         }
-        _ => todo!(),
+        _ => {
+            return Err(StreamExecutorError::not_implemented(
+                "unsupported aggregate type",
+                None,
+            ))
+        }
     };
     Ok(state)
 }
@@ -86,6 +86,9 @@ impl ChainExecutor {
         // The first barrier message should be propagated.
         yield Message::Barrier(barrier);
 
+        // The number of rows consumed from the snapshot, reported to the meta as the progress.
+        let mut consumed_rows = 0;
+
         // 2. Consume the snapshot if needed. Note that the snapshot is already projected, so
         // there's no mapping required.
         if to_consume_snapshot {
@@ -94,7 +97,11 @@ impl ChainExecutor {
 
             #[for_await]
             for msg in snapshot {
-                yield msg?;
+                let msg = msg?;
+                if let Message::Chunk(chunk) = &msg {
+                    consumed_rows += chunk.cardinality() as u64;
+                }
+                yield msg;
             }
         }
 
@@ -107,7 +114,7 @@ impl ChainExecutor {
                     yield Message::Chunk(mapping(&self.upstream_indices, chunk));
                 }
                 Message::Barrier(barrier) => {
-                    self.progress.finish(barrier.epoch.curr);
+                    self.progress.finish(barrier.epoch.curr, consumed_rows);
                     yield Message::Barrier(barrier);
                 }
             }
@@ -37,6 +37,7 @@ use super::monitor::StreamingMetrics;
 use super::{
     ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef,
 };
+use crate::cache::{CacheMetricsInfo, LruManagerRef};
 use crate::common::{InfallibleExpression, StreamChunkBuilder};
 use crate::executor::{expect_first_barrier_from_aligned_stream, PROCESSING_WINDOW_SIZE};
 
@@ -68,6 +69,7 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
         mut state_table_l: StateTable<S>,
         mut state_table_r: StateTable<S>,
         is_right_table_writer: bool,
+        lru_manager: LruManagerRef,
         metrics: Arc<StreamingMetrics>,
     ) -> Self {
         // TODO: enable sanity check for dynamic filter <https://github.com/piestreamlabs/piestream/issues/3893>
@@ -75,15 +77,21 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
         state_table_r.disable_sanity_check();
 
         let schema = source_l.schema().clone();
+        let identity = format!("DynamicFilterExecutor {:X}", executor_id);
+        let range_cache = RangeCache::new(
+            state_table_l,
+            lru_manager.watermark_epoch(),
+            CacheMetricsInfo::new(metrics.clone(), ctx.id, identity.clone()),
+        );
         Self {
             ctx,
             source_l: Some(source_l),
             source_r: Some(source_r),
             key_l,
             pk_indices,
-            identity: format!("DynamicFilterExecutor {:X}", executor_id),
+            identity,
             comparator,
-            range_cache: RangeCache::new(state_table_l, usize::MAX),
+            range_cache,
             right_table: state_table_r,
             is_right_table_writer,
             metrics,
@@ -325,7 +333,7 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
                     let prev: Datum = prev_epoch_value.flatten();
                     if prev != curr {
                         let (range, latest_is_lower, is_insert) = self.get_range(&curr, prev);
-                        for (_, rows) in self.range_cache.range(range, latest_is_lower) {
+                        for (_, rows) in self.range_cache.range(range, latest_is_lower).await? {
                             for row in rows {
                                 if let Some(chunk) = stream_chunk_builder.append_row_matched(
                                     // All rows have a single identity at this point
@@ -351,14 +359,13 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
                     }
 
                     self.range_cache.flush(barrier.epoch).await?;
+                    self.range_cache.evict();
 
                     prev_epoch_value = Some(curr);
 
                     // Update the vnode bitmap for the left state table if asked.
                     if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
-                        self.range_cache
-                            .state_table
-                            .update_vnode_bitmap(vnode_bitmap);
+                        self.range_cache.update_vnode_bitmap(vnode_bitmap);
                     }
 
                     yield Message::Barrier(barrier);
@@ -388,6 +395,8 @@ impl<S: StateStore> Executor for DynamicFilterExecutor<S> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::Ordering;
+
     use piestream_common::array::stream_chunk::StreamChunkTestExt;
     use piestream_common::array::*;
     use piestream_common::catalog::{ColumnDesc, ColumnId, Field, Schema, TableId};
@@ -395,6 +404,7 @@ mod tests {
     use piestream_storage::memory::MemoryStateStore;
 
     use super::*;
+    use crate::cache::LruManager;
     use crate::executor::test_utils::{MessageSender, MockSource};
     use crate::executor::ActorContext;
 
@@ -422,6 +432,13 @@ mod tests {
 
     fn create_executor(
         comparator: ExprNodeType,
+    ) -> (MessageSender, MessageSender, BoxedMessageStream) {
+        create_executor_with_lru_manager(comparator, LruManager::for_test())
+    }
+
+    fn create_executor_with_lru_manager(
+        comparator: ExprNodeType,
+        lru_manager: LruManagerRef,
     ) -> (MessageSender, MessageSender, BoxedMessageStream) {
         let schema = Schema {
             fields: vec![Field::unnamed(DataType::Int64)],
@@ -441,11 +458,108 @@ mod tests {
             mem_state_l,
             mem_state_r,
             true,
+            lru_manager,
             Arc::new(StreamingMetrics::unused()),
         );
         (tx_l, tx_r, Box::new(executor).execute())
     }
 
+    #[tokio::test]
+    async fn test_dynamic_filter_evict_and_reload() {
+        let chunk_l1 = StreamChunk::from_pretty(
+            "  I
+             + 1
+             + 2
+             + 3
+             + 4
+             + 5",
+        );
+        let chunk_l2 = StreamChunk::from_pretty(
+            "  I
+             + 6",
+        );
+        let chunk_r1 = StreamChunk::from_pretty(
+            "  I
+             + 2",
+        );
+        let chunk_r2 = StreamChunk::from_pretty(
+            "  I
+             + 4",
+        );
+        let chunk_r3 = StreamChunk::from_pretty(
+            "  I
+             + 1",
+        );
+        let lru_manager = LruManager::for_test();
+        let (mut tx_l, mut tx_r, mut dynamic_filter) =
+            create_executor_with_lru_manager(ExprNodeType::GreaterThan, lru_manager.clone());
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        dynamic_filter.next().await.unwrap().unwrap();
+
+        // The range `(2, +inf)` is loaded in epoch 1.
+        tx_l.push_chunk(chunk_l1);
+        tx_r.push_chunk(chunk_r1);
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        let chunk = dynamic_filter.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I
+                + 3
+                + 4
+                + 5"
+            )
+        );
+        dynamic_filter.next().await.unwrap().unwrap();
+
+        // The range `(2, 4]` is served by the cache, which is evicted after this epoch since the
+        // watermark passes the epoch in which it was loaded.
+        lru_manager.watermark_epoch().store(2, Ordering::Relaxed);
+        tx_r.push_chunk(chunk_r2);
+        tx_l.push_barrier(3, false);
+        tx_r.push_barrier(3, false);
+        let chunk = dynamic_filter.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I
+                - 3
+                - 4"
+            )
+        );
+        dynamic_filter.next().await.unwrap().unwrap();
+
+        // Rows written after the eviction are only in storage.
+        tx_l.push_chunk(chunk_l2);
+        let chunk = dynamic_filter.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap().compact(),
+            StreamChunk::from_pretty(
+                " I
+                + 6"
+            )
+        );
+
+        // The range `(1, 4]` is loaded again from storage.
+        tx_r.push_chunk(chunk_r3);
+        tx_l.push_barrier(4, false);
+        tx_r.push_barrier(4, false);
+        let chunk = dynamic_filter.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I
+                + 2
+                + 3
+                + 4"
+            )
+        );
+    }
+
     #[tokio::test]
     async fn test_dynamic_filter_greater_than() {
         let chunk_l1 = StreamChunk::from_pretty(
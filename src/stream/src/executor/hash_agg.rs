@@ -33,7 +33,7 @@ use piestream_storage::StateStore;
 
 use super::aggregation::{agg_call_filter_res, for_each_agg_state_table, AggStateTable};
 use super::{expect_first_barrier, ActorContextRef, Executor, PkIndicesRef, StreamExecutorResult};
-use crate::cache::{EvictableHashMap, ExecutorCache, LruManagerRef};
+use crate::cache::{CacheMetricsInfo, LruManagerRef, ManagedLruCache};
 use crate::error::StreamResult;
 use crate::executor::aggregation::{
     generate_agg_schema, generate_managed_agg_state, AggCall, AggChangesInfo, AggState,
@@ -42,7 +42,7 @@ use crate::executor::error::StreamExecutorError;
 use crate::executor::monitor::StreamingMetrics;
 use crate::executor::{BoxedMessageStream, Message, PkIndices, PROCESSING_WINDOW_SIZE};
 
-type AggStateMap<K, S> = ExecutorCache<K, Option<Box<AggState<S>>>, PrecomputedBuildHasher>;
+type AggStateMap<K, S> = ManagedLruCache<K, Option<Box<AggState<S>>>, PrecomputedBuildHasher>;
 
 /// [`HashAggExecutor`] could process large amounts of data using a state backend. It works as
 /// follows:
@@ -96,8 +96,8 @@ struct HashAggExecutorExtra<K: HashKey, S: StateStore> {
     /// all of the aggregation functions in this executor should depend on same group of keys
     group_key_indices: Vec<usize>,
 
-    /// Lru manager. None if using local eviction.
-    lru_manager: Option<LruManagerRef>,
+    /// Lru manager which bounds the memory usage of cached groups.
+    lru_manager: LruManagerRef,
 
     /// How many times have we hit the cache of join executor
     lookup_miss_count: AtomicU64,
@@ -106,9 +106,6 @@ struct HashAggExecutorExtra<K: HashKey, S: StateStore> {
 
     metrics: Arc<StreamingMetrics>,
 
-    /// Cache size (one per group by key)
-    group_by_cache_size: usize,
-
     /// Extreme state cache size
     extreme_cache_size: usize,
 
@@ -145,9 +142,8 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
         pk_indices: PkIndices,
         executor_id: u64,
         group_key_indices: Vec<usize>,
-        group_by_cache_size: usize,
         extreme_cache_size: usize,
-        lru_manager: Option<LruManagerRef>,
+        lru_manager: LruManagerRef,
         metrics: Arc<StreamingMetrics>,
     ) -> StreamResult<Self> {
         let input_info = input.info();
@@ -166,7 +162,6 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 agg_state_tables,
                 result_table,
                 group_key_indices,
-                group_by_cache_size,
                 extreme_cache_size,
                 lru_manager,
                 group_change_set: HashSet::new(),
@@ -270,7 +265,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
         let mut futures = vec![];
         for (key, _hash_code, _) in &unique_keys {
             // Retrieve previous state from the KeyedState.
-            let states = state_map.put(key.to_owned(), None);
+            let states = state_map.pop(key);
             total_lookup_count.fetch_add(1, Ordering::Relaxed);
 
             // Mark the group as changed.
@@ -319,7 +314,8 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
 
         // Apply batch in single-thread.
         for (key, _, vis_map) in &unique_keys {
-            let agg_state = state_map.get_mut(key).unwrap().as_mut().unwrap();
+            let mut agg_state = state_map.peek_mut(key).unwrap();
+            let agg_state = agg_state.as_mut().unwrap();
             // 3. Apply batch to each of the state (per agg_call)
             for ((managed_state, agg_call), agg_state_table) in agg_state
                 .managed_states()
@@ -378,7 +374,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
         metrics
             .agg_cached_keys
             .with_label_values(&[&actor_id_str])
-            .set(state_map.values().map(|_| 1).sum());
+            .set(state_map.len() as i64);
 
         // --- Flush agg result to the result table and downtream ---
 
@@ -406,11 +402,10 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
 
                 // --- Retrieve modified states and put the changes into the array builders ---
                 for key in batch {
-                    let agg_state = state_map
-                        .get_mut(&key)
-                        .expect("changed group must have corresponding AggState")
-                        .as_mut()
-                        .unwrap();
+                    let mut agg_state = state_map
+                        .peek_mut(&key)
+                        .expect("changed group must have corresponding AggState");
+                    let agg_state = agg_state.as_mut().unwrap();
 
                     let AggChangesInfo {
                         n_appended_ops,
@@ -454,7 +449,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
             // Commit agg result of all groups.
             result_table.commit(epoch).await?;
 
-            // Evict cache to target capacity.
+            // Evict cache to bound the memory usage.
             state_map.evict();
         } else {
             // Nothing to flush.
@@ -463,6 +458,9 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 state_table.table.commit_no_data_expected(epoch);
             });
             result_table.commit_no_data_expected(epoch);
+
+            // Evict cache to bound the memory usage.
+            state_map.evict();
             return Ok(());
         }
     }
@@ -474,14 +472,10 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
         } = self;
 
         // The cached states. `HashKey -> (prev_value, value)`.
-        let mut state_map = if let Some(lru_manager) = extra.lru_manager.clone() {
-            ExecutorCache::Managed(lru_manager.create_cache_with_hasher(PrecomputedBuildHasher))
-        } else {
-            ExecutorCache::Local(EvictableHashMap::with_hasher(
-                extra.group_by_cache_size,
-                PrecomputedBuildHasher,
-            ))
-        };
+        let mut state_map = extra.lru_manager.create_cache_with_hasher(
+            PrecomputedBuildHasher,
+            CacheMetricsInfo::new(extra.metrics.clone(), extra.ctx.id, extra.identity.clone()),
+        );
 
         // First barrier
         let mut input = input.execute();
@@ -542,6 +536,7 @@ mod tests {
     use piestream_storage::memory::MemoryStateStore;
     use piestream_storage::StateStore;
 
+    use crate::cache::LruManager;
     use crate::executor::aggregation::{AggArgs, AggCall};
     use crate::executor::monitor::StreamingMetrics;
     use crate::executor::test_utils::agg_executor::{create_agg_state_table, create_result_table};
@@ -555,7 +550,6 @@ mod tests {
         agg_calls: Vec<AggCall>,
         group_key_indices: Vec<usize>,
         pk_indices: PkIndices,
-        group_by_cache_size: usize,
         extreme_cache_size: usize,
         executor_id: u64,
     ) -> Box<dyn Executor> {
@@ -590,9 +584,8 @@ mod tests {
             pk_indices,
             executor_id,
             group_key_indices,
-            group_by_cache_size,
             extreme_cache_size,
-            None,
+            LruManager::for_test(),
             Arc::new(StreamingMetrics::unused()),
         )
         .unwrap()
@@ -678,7 +671,6 @@ mod tests {
             agg_calls,
             keys,
             vec![],
-            1 << 16,
             1 << 10,
             1,
         );
@@ -780,7 +772,6 @@ mod tests {
             agg_calls,
             key_indices,
             vec![],
-            1 << 16,
             1 << 10,
             1,
         );
@@ -874,7 +865,6 @@ mod tests {
             agg_calls,
             keys,
             vec![2],
-            1 << 16,
             1 << 10,
             1,
         );
@@ -973,7 +963,6 @@ mod tests {
             agg_calls,
             keys,
             vec![2],
-            1 << 16,
             1 << 10,
             1,
         );
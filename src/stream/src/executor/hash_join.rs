@@ -422,12 +422,11 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         executor_id: u64,
         cond: Option<BoxedExpression>,
         op_info: String,
-        cache_size: usize,
         state_table_l: StateTable<S>,
         degree_state_table_l: StateTable<S>,
         state_table_r: StateTable<S>,
         degree_state_table_r: StateTable<S>,
-        lru_manager: Option<LruManagerRef>,
+        lru_manager: LruManagerRef,
         is_append_only: bool,
         metrics: Arc<StreamingMetrics>,
     ) -> Self {
@@ -537,6 +536,8 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         let need_degree_table_l = need_left_degree(T);
         let need_degree_table_r = need_right_degree(T);
 
+        let identity = format!("HashJoinExecutor {:X}", executor_id);
+
         Self {
             ctx: ctx.clone(),
             input_l: Some(input_l),
@@ -546,7 +547,6 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
             side_l: JoinSide {
                 ht: JoinHashMap::new(
                     lru_manager.clone(),
-                    cache_size,
                     join_key_data_types_l,
                     state_all_data_types_l.clone(),
                     state_table_l,
@@ -558,8 +558,9 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                     need_degree_table_l,
                     metrics.clone(),
                     ctx.id,
+                    &identity,
                     "left",
-                ), // TODO: decide the target cap
+                ),
                 join_key_indices: join_key_indices_l,
                 all_data_types: state_all_data_types_l,
                 pk_indices: state_pk_indices_l,
@@ -568,7 +569,6 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
             side_r: JoinSide {
                 ht: JoinHashMap::new(
                     lru_manager,
-                    cache_size,
                     join_key_data_types_r,
                     state_all_data_types_r.clone(),
                     state_table_r,
@@ -580,8 +580,9 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                     need_degree_table_r,
                     metrics.clone(),
                     ctx.id,
+                    &identity,
                     "right",
-                ), // TODO: decide the target cap
+                ),
                 join_key_indices: join_key_indices_r,
                 all_data_types: state_all_data_types_r,
                 pk_indices: state_pk_indices_r,
@@ -590,7 +591,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
             pk_indices,
             output_indices,
             cond,
-            identity: format!("HashJoinExecutor {:X}", executor_id),
+            identity,
             op_info,
             append_only_optimize,
             metrics,
@@ -683,8 +684,8 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
 
                     // Report metrics of cached join rows/entries
                     for (side, ht) in [("left", &self.side_l.ht), ("right", &self.side_r.ht)] {
-                        // TODO(yuhao): Those two metric calculation cost too much time (>250ms).
-                        // Those will result in that barrier is always ready
+                        // TODO(yuhao): The cached rows calculation costs too much time (>250ms).
+                        // It will result in that barrier is always ready
                         // in source. Since select barrier is preferred,
                         // chunk would never be selected.
                         // self.metrics
@@ -695,10 +696,10 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                             .join_cached_entries
                             .with_label_values(&[&actor_id_str, side])
                             .set(ht.entry_count() as i64);
-                        // self.metrics
-                        //     .join_cached_estimated_size
-                        //     .with_label_values(&[&actor_id_str, side])
-                        //     .set(ht.estimated_size() as i64);
+                        self.metrics
+                            .join_cached_estimated_size
+                            .with_label_values(&[&actor_id_str, side])
+                            .set(ht.estimated_size() as i64);
                     }
 
                     yield Message::Barrier(barrier);
@@ -714,7 +715,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         self.side_l.ht.flush(epoch).await?;
         self.side_r.ht.flush(epoch).await?;
 
-        // We need to manually evict the cache to the target capacity.
+        // We need to manually evict the cache.
         self.side_l.ht.evict();
         self.side_r.ht.evict();
 
@@ -941,6 +942,7 @@ mod tests {
     use piestream_storage::memory::MemoryStateStore;
 
     use super::*;
+    use crate::cache::LruManager;
     use crate::executor::test_utils::{MessageSender, MockSource};
     use crate::executor::{ActorContext, Barrier, EpochPair, Message};
 
@@ -1048,12 +1050,11 @@ mod tests {
             1,
             cond,
             "HashJoinExecutor".to_string(),
-            1 << 16,
             state_l,
             degree_state_l,
             state_r,
             degree_state_r,
-            None,
+            LruManager::for_test(),
             false,
             Arc::new(StreamingMetrics::unused()),
         );
@@ -1118,12 +1119,11 @@ mod tests {
             1,
             cond,
             "HashJoinExecutor".to_string(),
-            1 << 16,
             state_l,
             degree_state_l,
             state_r,
             degree_state_r,
-            None,
+            LruManager::for_test(),
             true,
             Arc::new(StreamingMetrics::unused()),
         );
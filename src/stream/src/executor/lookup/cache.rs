@@ -15,30 +15,31 @@
 use std::collections::BTreeSet;
 
 use piestream_common::array::{Op, Row, StreamChunk};
+use piestream_common::collection::estimate_size::EstimateSize;
 
-use crate::cache::{EvictableHashMap, ExecutorCache, LruManagerRef};
+use crate::cache::{CacheMetricsInfo, LruManagerRef, ManagedLruCache};
 
 /// A cache for lookup's arrangement side.
 pub struct LookupCache {
-    data: ExecutorCache<Row, BTreeSet<Row>>,
+    data: ManagedLruCache<Row, LookupEntryState>,
 }
 
 impl LookupCache {
     /// Lookup a row in cache. If not found, return `None`.
     pub fn lookup(&mut self, key: &Row) -> Option<&BTreeSet<Row>> {
-        self.data.get(key)
+        self.data.get(key).map(|entry| &entry.inner)
     }
 
     /// Update a key after lookup cache misses.
     pub fn batch_update(&mut self, key: Row, value: impl Iterator<Item = Row>) {
-        self.data.push(key, value.collect());
+        self.data.push(key, LookupEntryState::new(value));
     }
 
     /// Apply a batch from the arrangement side
     pub fn apply_batch(&mut self, chunk: StreamChunk, arrange_join_keys: &[usize]) {
         for (op, row) in chunk.rows() {
             let key = row.row_by_indices(arrange_join_keys);
-            if let Some(values) = self.data.get_mut(&key) {
+            if let Some(mut values) = self.data.peek_mut(&key) {
                 // the item is in cache, update it
                 let value = row.to_owned_row();
                 match op {
@@ -63,12 +64,47 @@ impl LookupCache {
         self.data.update_epoch(epoch);
     }
 
-    pub fn new(lru_manager: Option<LruManagerRef>, cache_size: usize) -> Self {
-        let cache = if let Some(lru_manager) = lru_manager {
-            ExecutorCache::Managed(lru_manager.create_cache())
-        } else {
-            ExecutorCache::Local(EvictableHashMap::new(cache_size))
-        };
-        Self { data: cache }
+    pub fn new(lru_manager: LruManagerRef, metrics_info: CacheMetricsInfo) -> Self {
+        Self {
+            data: lru_manager.create_cache(metrics_info),
+        }
+    }
+}
+
+/// Rows of the arrangement side under a join key, with the estimated heap size maintained on
+/// updates.
+struct LookupEntryState {
+    inner: BTreeSet<Row>,
+    estimated_content_heap_size: usize,
+}
+
+impl LookupEntryState {
+    fn new(rows: impl Iterator<Item = Row>) -> Self {
+        let inner: BTreeSet<Row> = rows.collect();
+        let estimated_content_heap_size = inner.iter().map(EstimateSize::estimated_size).sum();
+        Self {
+            inner,
+            estimated_content_heap_size,
+        }
+    }
+
+    fn insert(&mut self, row: Row) {
+        let row_size = row.estimated_size();
+        if self.inner.insert(row) {
+            self.estimated_content_heap_size += row_size;
+        }
+    }
+
+    fn remove(&mut self, row: &Row) {
+        if self.inner.remove(row) {
+            self.estimated_content_heap_size -= row.estimated_size();
+        }
+    }
+}
+
+impl EstimateSize for LookupEntryState {
+    fn estimated_heap_size(&self) -> usize {
+        // The overhead of the btree-set itself is not counted.
+        self.estimated_content_heap_size
     }
 }
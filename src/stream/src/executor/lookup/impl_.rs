@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use futures::{pin_mut, StreamExt};
 use futures_async_stream::try_stream;
 use itertools::Itertools;
@@ -23,16 +25,21 @@ use piestream_storage::table::streaming_table::state_table::StateTable;
 use piestream_storage::StateStore;
 
 use super::sides::{stream_lookup_arrange_prev_epoch, stream_lookup_arrange_this_epoch};
-use crate::cache::LruManagerRef;
+use crate::cache::{CacheMetricsInfo, LruManagerRef};
 use crate::common::StreamChunkBuilder;
 use crate::executor::error::{StreamExecutorError, StreamExecutorResult};
 use crate::executor::lookup::cache::LookupCache;
 use crate::executor::lookup::sides::{ArrangeJoinSide, ArrangeMessage, StreamJoinSide};
 use crate::executor::lookup::LookupExecutor;
-use crate::executor::{Barrier, Executor, Message, PkIndices, PROCESSING_WINDOW_SIZE};
+use crate::executor::monitor::StreamingMetrics;
+use crate::executor::{
+    ActorContextRef, Barrier, Executor, Message, PkIndices, PROCESSING_WINDOW_SIZE,
+};
 
 /// Parameters for [`LookupExecutor`].
 pub struct LookupExecutorParams<S: StateStore> {
+    pub ctx: ActorContextRef,
+
     /// The side for arrangement. Currently, it should be a
     /// `MaterializeExecutor`.
     pub arrangement: Box<dyn Executor>,
@@ -103,14 +110,17 @@ pub struct LookupExecutorParams<S: StateStore> {
 
     pub state_table: StateTable<S>,
 
-    pub lru_manager: Option<LruManagerRef>,
+    pub executor_id: u64,
+
+    pub lru_manager: LruManagerRef,
 
-    pub cache_size: usize,
+    pub metrics: Arc<StreamingMetrics>,
 }
 
 impl<S: StateStore> LookupExecutor<S> {
     pub fn new(params: LookupExecutorParams<S>) -> Self {
         let LookupExecutorParams {
+            ctx,
             arrangement,
             stream,
             arrangement_col_descs,
@@ -122,8 +132,9 @@ impl<S: StateStore> LookupExecutor<S> {
             schema: output_schema,
             column_mapping,
             state_table,
+            executor_id,
             lru_manager,
-            cache_size,
+            metrics,
         } = params;
 
         let output_column_length = stream.schema().len() + arrangement.schema().len();
@@ -219,7 +230,10 @@ impl<S: StateStore> LookupExecutor<S> {
             },
             column_mapping,
             key_indices_mapping,
-            lookup_cache: LookupCache::new(lru_manager, cache_size),
+            lookup_cache: LookupCache::new(
+                lru_manager,
+                CacheMetricsInfo::new(metrics, ctx.id, format!("LookupExecutor {:X}", executor_id)),
+            ),
         }
     }
 
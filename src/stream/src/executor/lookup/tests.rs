@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use assert_matches::assert_matches;
 use futures::StreamExt;
 use itertools::Itertools;
@@ -24,11 +26,13 @@ use piestream_storage::memory::MemoryStateStore;
 use piestream_storage::table::streaming_table::state_table::StateTable;
 use piestream_storage::StateStore;
 
+use crate::cache::LruManager;
 use crate::executor::lookup::impl_::LookupExecutorParams;
 use crate::executor::lookup::LookupExecutor;
+use crate::executor::monitor::StreamingMetrics;
 use crate::executor::test_utils::*;
 use crate::executor::{
-    Barrier, BoxedMessageStream, Executor, MaterializeExecutor, Message, PkIndices,
+    ActorContext, Barrier, BoxedMessageStream, Executor, MaterializeExecutor, Message, PkIndices,
 };
 
 fn arrangement_col_descs() -> Vec<ColumnDesc> {
@@ -226,6 +230,7 @@ async fn test_lookup_this_epoch() {
     let arrangement = create_arrangement(table_id, store.clone());
     let stream = create_source();
     let lookup_executor = Box::new(LookupExecutor::new(LookupExecutorParams {
+        ctx: ActorContext::create(0),
         arrangement,
         stream,
         arrangement_col_descs: arrangement_col_descs(),
@@ -248,8 +253,9 @@ async fn test_lookup_this_epoch() {
             arrangement_col_arrange_rules(),
             vec![1, 0],
         ),
-        lru_manager: None,
-        cache_size: 1 << 16,
+        executor_id: 1,
+        lru_manager: LruManager::for_test(),
+        metrics: Arc::new(StreamingMetrics::unused()),
     }));
     let mut lookup_executor = lookup_executor.execute();
 
@@ -291,6 +297,7 @@ async fn test_lookup_last_epoch() {
     let arrangement = create_arrangement(table_id, store.clone());
     let stream = create_source();
     let lookup_executor = Box::new(LookupExecutor::new(LookupExecutorParams {
+        ctx: ActorContext::create(0),
         arrangement,
         stream,
         arrangement_col_descs: arrangement_col_descs(),
@@ -313,8 +320,9 @@ async fn test_lookup_last_epoch() {
             arrangement_col_arrange_rules(),
             vec![1, 0],
         ),
-        lru_manager: None,
-        cache_size: 1 << 16,
+        executor_id: 1,
+        lru_manager: LruManager::for_test(),
+        metrics: Arc::new(StreamingMetrics::unused()),
     }));
     let mut lookup_executor = lookup_executor.execute();
 
@@ -21,6 +21,7 @@ use piestream_common::array::stream_chunk::Ops;
 use piestream_common::array::Op::{Delete, Insert, UpdateDelete, UpdateInsert};
 use piestream_common::array::{ArrayImpl, ListValue, Row};
 use piestream_common::buffer::Bitmap;
+use piestream_common::collection::estimate_size::EstimateSize;
 use piestream_common::types::Datum;
 use piestream_common::util::ordered::OrderedRow;
 use piestream_common::util::sort_util::OrderType;
@@ -184,6 +185,12 @@ impl<S: StateStore> ManagedArrayAggState<S> {
     }
 }
 
+impl<S: StateStore> EstimateSize for ManagedArrayAggState<S> {
+    fn estimated_heap_size(&self) -> usize {
+        self.cache.estimated_heap_size()
+    }
+}
+
 #[async_trait]
 impl<S: StateStore> ManagedTableState<S> for ManagedArrayAggState<S> {
     async fn apply_chunk(
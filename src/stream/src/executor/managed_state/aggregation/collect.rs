@@ -0,0 +1,442 @@
+// Copyright 2022 Piedb Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::marker::PhantomData;
+
+use async_trait::async_trait;
+use futures::pin_mut;
+use futures_async_stream::for_await;
+use piestream_common::array::stream_chunk::Ops;
+use piestream_common::array::Op::{Delete, Insert, UpdateDelete, UpdateInsert};
+use piestream_common::array::{ArrayImpl, Row};
+use piestream_common::buffer::Bitmap;
+use piestream_common::collection::estimate_size::EstimateSize;
+use piestream_common::types::{Datum, ScalarImpl};
+use piestream_common::util::ordered::OrderedRow;
+use piestream_common::util::sort_util::OrderType;
+use piestream_expr::expr::AggKind;
+use piestream_expr::vector_op::agg::{
+    check_percentile_fraction, percentile_cont, percentile_disc_index,
+};
+use piestream_storage::table::streaming_table::state_table::StateTable;
+use piestream_storage::StateStore;
+
+use super::{Cache, ManagedTableState};
+use crate::common::StateTableColumnMapping;
+use crate::executor::aggregation::AggCall;
+use crate::executor::error::StreamExecutorResult;
+use crate::executor::managed_state::iter_state_table;
+use crate::executor::PkIndices;
+
+/// Managed agg state for aggregations which can neither be updated incrementally nor be answered
+/// by the top entries, i.e. `bit_and`/`bit_or` and `percentile_cont`/`percentile_disc`. All
+/// non-null inputs are kept in the state table, and the output is computed from all of them.
+pub struct ManagedCollectState<S: StateStore> {
+    _phantom_data: PhantomData<S>,
+
+    /// Group key to aggregate with group.
+    /// None for simple agg, Some for group key of hash agg.
+    group_key: Option<Row>,
+
+    /// Kind of the aggregation.
+    agg_kind: AggKind,
+
+    // TODO(yuchao): remove this after we move state table insertion out.
+    /// Contains the column mapping between upstream schema and state table.
+    state_table_col_mapping: StateTableColumnMapping,
+
+    // The column to aggregate in input chunk.
+    upstream_agg_col_idx: usize,
+
+    /// The columns of the arguments in state table, the first one is the column to aggregate.
+    state_table_arg_col_indices: Vec<usize>,
+
+    /// The columns to order by in state table.
+    state_table_order_col_indices: Vec<usize>,
+
+    /// The order types of `state_table_order_col_indices`.
+    state_table_order_types: Vec<OrderType>,
+
+    /// In-memory all-or-nothing cache, values are the arguments of each input row.
+    cache: Cache<OrderedRow, Row>,
+
+    /// Whether the cache is fully synced to state table.
+    cache_synced: bool,
+}
+
+impl<S: StateStore> ManagedCollectState<S> {
+    pub fn new(
+        agg_call: &AggCall,
+        group_key: Option<&Row>,
+        pk_indices: &PkIndices,
+        col_mapping: StateTableColumnMapping,
+        row_count: usize,
+    ) -> Self {
+        let upstream_agg_col_idx = agg_call.args.val_indices()[0];
+        // map argument columns to state table column indices
+        let state_table_arg_col_indices = agg_call
+            .args
+            .val_indices()
+            .iter()
+            .map(|idx| {
+                col_mapping
+                    .upstream_to_state_table(*idx)
+                    .expect("the arguments must appear in the state table")
+            })
+            .collect();
+        // map order by columns to state table column indices
+        let (state_table_order_col_indices, state_table_order_types) = agg_call
+            .order_pairs
+            .iter()
+            .map(|o| {
+                (
+                    col_mapping
+                        .upstream_to_state_table(o.column_idx)
+                        .expect("the column to be order by must appear in the state table"),
+                    o.order_type,
+                )
+            })
+            .chain(pk_indices.iter().map(|idx| {
+                (
+                    col_mapping
+                        .upstream_to_state_table(*idx)
+                        .expect("the pk columns must appear in the state table"),
+                    OrderType::Ascending,
+                )
+            }))
+            .unzip();
+        Self {
+            _phantom_data: PhantomData,
+            group_key: group_key.cloned(),
+            agg_kind: agg_call.kind,
+            state_table_col_mapping: col_mapping,
+            upstream_agg_col_idx,
+            state_table_arg_col_indices,
+            state_table_order_col_indices,
+            state_table_order_types,
+            cache: Cache::new(usize::MAX),
+            cache_synced: row_count == 0, // if there is no row, the cache is synced initially
+        }
+    }
+
+    fn state_row_to_cache_entry(&self, state_row: &Row) -> (OrderedRow, Row) {
+        let cache_key = OrderedRow::new(
+            state_row.by_indices(&self.state_table_order_col_indices),
+            &self.state_table_order_types,
+        );
+        let cache_data = state_row.by_indices(&self.state_table_arg_col_indices);
+        (cache_key, cache_data)
+    }
+
+    fn apply_chunk_inner(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        columns: &[&ArrayImpl],
+        state_table: &mut StateTable<S>,
+    ) -> StreamExecutorResult<()> {
+        debug_assert!(super::verify_batch(ops, visibility, columns));
+
+        for (i, op) in ops
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| visibility.map(|x| x.is_set(*i)).unwrap_or(true))
+            .filter(|(i, _)| columns[self.upstream_agg_col_idx].null_bitmap().is_set(*i))
+        {
+            let state_row = Row::new(
+                self.state_table_col_mapping
+                    .upstream_columns()
+                    .iter()
+                    .map(|col_idx| columns[*col_idx].datum_at(i))
+                    .collect(),
+            );
+            let (cache_key, cache_data) = self.state_row_to_cache_entry(&state_row);
+
+            match op {
+                Insert | UpdateInsert => {
+                    if self.cache_synced {
+                        self.cache.insert(cache_key, cache_data);
+                    }
+                    state_table.insert(state_row);
+                }
+                Delete | UpdateDelete => {
+                    if self.cache_synced {
+                        self.cache.remove(cache_key);
+                    }
+                    state_table.delete(state_row);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compute the output from all cached arguments, which are in the order of the order by
+    /// columns.
+    fn get_output_from_cache(&self) -> StreamExecutorResult<Datum> {
+        let mut values = self
+            .cache
+            .iter_values()
+            .map(|args| args[0].clone().unwrap());
+        let output = match self.agg_kind {
+            AggKind::BitAnd | AggKind::BitOr => {
+                let is_and = self.agg_kind == AggKind::BitAnd;
+                values.reduce(|acc, value| match (acc, value) {
+                    (ScalarImpl::Int16(a), ScalarImpl::Int16(v)) => {
+                        ScalarImpl::Int16(if is_and { a & v } else { a | v })
+                    }
+                    (ScalarImpl::Int32(a), ScalarImpl::Int32(v)) => {
+                        ScalarImpl::Int32(if is_and { a & v } else { a | v })
+                    }
+                    (ScalarImpl::Int64(a), ScalarImpl::Int64(v)) => {
+                        ScalarImpl::Int64(if is_and { a & v } else { a | v })
+                    }
+                    _ => unreachable!("bit_and/bit_or only accept integer inputs"),
+                })
+            }
+            AggKind::PercentileCont | AggKind::PercentileDisc => {
+                // the fraction is a constant, so any row will do
+                let fraction = match self.cache.first_value().and_then(|args| args[1].clone()) {
+                    Some(fraction) => fraction.into_float64().into_inner(),
+                    None => return Ok(None),
+                };
+                check_percentile_fraction(fraction)?;
+                if self.agg_kind == AggKind::PercentileDisc {
+                    values.nth(percentile_disc_index(self.cache.len(), fraction))
+                } else {
+                    let sorted = values
+                        .map(|v| v.into_float64().into_inner())
+                        .collect::<Vec<_>>();
+                    Some(percentile_cont(&sorted, fraction).into())
+                }
+            }
+            _ => unreachable!(),
+        };
+        Ok(output)
+    }
+
+    async fn get_output_inner(
+        &mut self,
+        state_table: &StateTable<S>,
+    ) -> StreamExecutorResult<Datum> {
+        if !self.cache_synced {
+            let all_data_iter = iter_state_table(state_table, self.group_key.as_ref()).await?;
+            pin_mut!(all_data_iter);
+
+            self.cache.clear();
+            #[for_await]
+            for state_row in all_data_iter {
+                let state_row = state_row?;
+                let (cache_key, cache_data) = self.state_row_to_cache_entry(&state_row);
+                self.cache.insert(cache_key, cache_data);
+            }
+            self.cache_synced = true;
+        }
+
+        self.get_output_from_cache()
+    }
+}
+
+impl<S: StateStore> EstimateSize for ManagedCollectState<S> {
+    fn estimated_heap_size(&self) -> usize {
+        self.cache.estimated_heap_size()
+    }
+}
+
+#[async_trait]
+impl<S: StateStore> ManagedTableState<S> for ManagedCollectState<S> {
+    async fn apply_chunk(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        columns: &[&ArrayImpl], // contains all upstream columns
+        state_table: &mut StateTable<S>,
+    ) -> StreamExecutorResult<()> {
+        self.apply_chunk_inner(ops, visibility, columns, state_table)
+    }
+
+    async fn get_output(&mut self, state_table: &StateTable<S>) -> StreamExecutorResult<Datum> {
+        self.get_output_inner(state_table).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::array::{StreamChunk, StreamChunkTestExt};
+    use piestream_common::catalog::{ColumnDesc, ColumnId, TableId};
+    use piestream_common::types::{DataType, ScalarImpl};
+    use piestream_common::util::epoch::EpochPair;
+    use piestream_common::util::sort_util::{OrderPair, OrderType};
+    use piestream_expr::expr::AggKind;
+    use piestream_storage::memory::MemoryStateStore;
+    use piestream_storage::table::streaming_table::state_table::StateTable;
+
+    use super::ManagedCollectState;
+    use crate::common::StateTableColumnMapping;
+    use crate::executor::aggregation::{AggArgs, AggCall};
+    use crate::executor::managed_state::aggregation::ManagedTableState;
+    use crate::executor::StreamExecutorResult;
+
+    #[tokio::test]
+    async fn test_collect_state_bit_or() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: int32, b: int32, _row_id: int64)
+
+        let input_pk_indices = vec![2];
+        let agg_call = AggCall {
+            kind: AggKind::BitOr,
+            args: AggArgs::Unary(DataType::Int32, 0), // bit_or(a)
+            return_type: DataType::Int32,
+            order_pairs: vec![],
+            append_only: false,
+            filter: None,
+        };
+
+        let table_id = TableId::new(6666);
+        let columns = vec![
+            ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64), // _row_id
+            ColumnDesc::unnamed(ColumnId::new(1), DataType::Int32), // a
+        ];
+        let state_table_col_mapping = StateTableColumnMapping::new(vec![2, 0]);
+        let mut state_table = StateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            table_id,
+            columns,
+            vec![OrderType::Ascending],
+            vec![0], // [_row_id]
+        );
+
+        let mut managed_state = ManagedCollectState::new(
+            &agg_call,
+            None,
+            &input_pk_indices,
+            state_table_col_mapping.clone(),
+            0,
+        );
+
+        let epoch = EpochPair::new_test_epoch(1);
+        state_table.init_epoch(epoch);
+        epoch.inc();
+
+        let chunk = StreamChunk::from_pretty(
+            " i i I
+            + 1 8 123
+            + 4 2 128
+            + . 6 129
+            + 2 3 130
+            - 4 2 128",
+        );
+        let (ops, columns, visibility) = chunk.into_inner();
+        let column_refs: Vec<_> = columns.iter().map(|col| col.array_ref()).collect();
+        managed_state
+            .apply_chunk(&ops, visibility.as_ref(), &column_refs, &mut state_table)
+            .await?;
+
+        state_table.commit_for_test(epoch).await.unwrap();
+
+        let res = managed_state.get_output(&state_table).await?;
+        assert_eq!(res, Some(ScalarImpl::Int32(3)));
+
+        // test recovery (cold start)
+        let mut managed_state = ManagedCollectState::new(
+            &agg_call,
+            None,
+            &input_pk_indices,
+            state_table_col_mapping,
+            2,
+        );
+        let res = managed_state.get_output(&state_table).await?;
+        assert_eq!(res, Some(ScalarImpl::Int32(3)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_collect_state_percentile() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: float64, b: float64, _row_id: int64)
+        // where `b` is the fraction
+
+        let input_pk_indices = vec![2];
+        let make_agg_call = |kind, order_type| AggCall {
+            kind,
+            args: AggArgs::Binary([DataType::Float64, DataType::Float64], [0, 1]),
+            return_type: DataType::Float64,
+            order_pairs: vec![OrderPair::new(0, order_type)],
+            append_only: false,
+            filter: None,
+        };
+
+        let table_id = TableId::new(6666);
+        let columns = vec![
+            ColumnDesc::unnamed(ColumnId::new(0), DataType::Float64), // a
+            ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64),   // _row_id
+            ColumnDesc::unnamed(ColumnId::new(2), DataType::Float64), // b
+        ];
+        let state_table_col_mapping = StateTableColumnMapping::new(vec![0, 2, 1]);
+        let mut state_table = StateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            table_id,
+            columns,
+            vec![OrderType::Ascending, OrderType::Ascending],
+            vec![0, 1], // [a, _row_id]
+        );
+
+        let agg_call = make_agg_call(AggKind::PercentileCont, OrderType::Ascending);
+        let mut managed_state = ManagedCollectState::new(
+            &agg_call,
+            None,
+            &input_pk_indices,
+            state_table_col_mapping.clone(),
+            0,
+        );
+
+        let epoch = EpochPair::new_test_epoch(1);
+        state_table.init_epoch(epoch);
+        epoch.inc();
+
+        let chunk = StreamChunk::from_pretty(
+            " F F I
+            + 4 0.25 123
+            + 1 0.25 128
+            + . 0.25 129
+            + 3 0.25 130
+            + 2 0.25 131",
+        );
+        let (ops, columns, visibility) = chunk.into_inner();
+        let column_refs: Vec<_> = columns.iter().map(|col| col.array_ref()).collect();
+        managed_state
+            .apply_chunk(&ops, visibility.as_ref(), &column_refs, &mut state_table)
+            .await?;
+
+        state_table.commit_for_test(epoch).await.unwrap();
+
+        let res = managed_state.get_output(&state_table).await?;
+        assert_eq!(res, Some(ScalarImpl::Float64(1.75.into())));
+
+        // the same rows read back from the state table by `percentile_disc`
+        let agg_call = make_agg_call(AggKind::PercentileDisc, OrderType::Ascending);
+        let mut managed_state = ManagedCollectState::new(
+            &agg_call,
+            None,
+            &input_pk_indices,
+            state_table_col_mapping,
+            4,
+        );
+        let res = managed_state.get_output(&state_table).await?;
+        assert_eq!(res, Some(ScalarImpl::Float64(1.0.into())));
+
+        Ok(())
+    }
+}
@@ -21,6 +21,7 @@ use piestream_common::array::stream_chunk::{Op, Ops};
 use piestream_common::array::{ArrayImpl, Row};
 use piestream_common::buffer::Bitmap;
 use piestream_common::catalog::Schema;
+use piestream_common::collection::estimate_size::EstimateSize;
 use piestream_common::types::*;
 use piestream_common::util::ordered::OrderedRowSerde;
 use piestream_common::util::sort_util::OrderType;
@@ -38,9 +39,10 @@ use crate::executor::PkIndices;
 /// Memcomparable row.
 type CacheKey = Vec<u8>;
 
-/// Generic managed agg state for min/max.
+/// Generic managed agg state for min/max, `bool_and`/`bool_or` and `first_value`/`last_value`.
 /// It maintains a top N cache internally, using `HashSet`, and the sort key
-/// is composed of (agg input value, upstream pk).
+/// is composed of (agg input value, upstream pk), or (order by columns, upstream pk) for
+/// `first_value`/`last_value`.
 pub struct GenericExtremeState<S: StateStore> {
     _phantom_data: PhantomData<S>,
 
@@ -61,6 +63,10 @@ pub struct GenericExtremeState<S: StateStore> {
     /// The columns to order by in state table.
     state_table_order_col_indices: Vec<usize>,
 
+    /// Whether rows with NULL agg input value are ignored. Only `first_value`/`last_value` keep
+    /// them.
+    ignore_nulls: bool,
+
     /// Number of all items in the state store.
     total_count: usize,
 
@@ -85,7 +91,7 @@ pub struct GenericExtremeState<S: StateStore> {
 /// `ManagedValueState`, we can directly forward its async functions to `ManagedStateImpl`, instead
 /// of adding a layer of indirection caused by async traits.
 #[async_trait]
-pub trait ManagedTableState<S: StateStore>: Send + Sync + 'static {
+pub trait ManagedTableState<S: StateStore>: EstimateSize + Send + Sync + 'static {
     async fn apply_chunk(
         &mut self,
         ops: Ops<'_>,
@@ -116,15 +122,34 @@ impl<S: StateStore> GenericExtremeState<S> {
             .upstream_to_state_table(agg_call.args.val_indices()[0])
             .expect("the column to be aggregate must appear in the state table");
         // map order by columns to state table column indices
-        let (state_table_order_col_indices, state_table_order_types): (Vec<_>, Vec<_>) =
-            std::iter::once((
-                state_table_agg_col_idx,
-                match agg_call.kind {
-                    AggKind::Min => OrderType::Ascending,
-                    AggKind::Max => OrderType::Descending,
-                    _ => unreachable!(),
-                },
-            ))
+        let sort_keys = match agg_call.kind {
+            AggKind::Min | AggKind::BoolAnd => {
+                vec![(state_table_agg_col_idx, OrderType::Ascending)]
+            }
+            AggKind::Max | AggKind::BoolOr => {
+                vec![(state_table_agg_col_idx, OrderType::Descending)]
+            }
+            AggKind::FirstValue | AggKind::LastValue => agg_call
+                .order_pairs
+                .iter()
+                .map(|o| {
+                    let order_type = match (agg_call.kind, o.order_type) {
+                        (AggKind::LastValue, OrderType::Ascending) => OrderType::Descending,
+                        (AggKind::LastValue, OrderType::Descending) => OrderType::Ascending,
+                        (_, order_type) => order_type,
+                    };
+                    (
+                        col_mapping
+                            .upstream_to_state_table(o.column_idx)
+                            .expect("the column to be order by must appear in the state table"),
+                        order_type,
+                    )
+                })
+                .collect(),
+            _ => unreachable!(),
+        };
+        let (state_table_order_col_indices, state_table_order_types): (Vec<_>, Vec<_>) = sort_keys
+            .into_iter()
             .chain(pk_indices.iter().map(|idx| {
                 (
                     col_mapping
@@ -155,6 +180,7 @@ impl<S: StateStore> GenericExtremeState<S> {
             upstream_agg_col_idx,
             state_table_agg_col_idx,
             state_table_order_col_indices,
+            ignore_nulls: !matches!(agg_call.kind, AggKind::FirstValue | AggKind::LastValue),
             total_count: row_count,
             cache: Cache::new(cache_capacity),
             cache_synced: row_count == 0, // if there is no row, the cache is synced initially
@@ -188,7 +214,9 @@ impl<S: StateStore> GenericExtremeState<S> {
             .iter()
             .enumerate()
             .filter(|(i, _)| visibility.map(|x| x.is_set(*i)).unwrap_or(true))
-            .filter(|(i, _)| columns[self.upstream_agg_col_idx].null_bitmap().is_set(*i))
+            .filter(|(i, _)| {
+                !self.ignore_nulls || columns[self.upstream_agg_col_idx].null_bitmap().is_set(*i)
+            })
         {
             let state_row = Row::new(
                 self.state_table_col_mapping
@@ -261,6 +289,12 @@ impl<S: StateStore> GenericExtremeState<S> {
     }
 }
 
+impl<S: StateStore> EstimateSize for GenericExtremeState<S> {
+    fn estimated_heap_size(&self) -> usize {
+        self.cache.estimated_heap_size()
+    }
+}
+
 #[async_trait]
 impl<S: StateStore> ManagedTableState<S> for GenericExtremeState<S> {
     async fn apply_chunk(
@@ -283,14 +317,14 @@ mod tests {
     use std::collections::HashSet;
 
     use itertools::Itertools;
-    use rand::prelude::*;
     use piestream_common::array::StreamChunk;
     use piestream_common::catalog::{ColumnDesc, ColumnId, Field, TableId};
     use piestream_common::test_prelude::StreamChunkTestExt;
     use piestream_common::types::ScalarImpl;
     use piestream_common::util::epoch::EpochPair;
-    use piestream_common::util::sort_util::OrderType;
+    use piestream_common::util::sort_util::{OrderPair, OrderType};
     use piestream_storage::memory::MemoryStateStore;
+    use rand::prelude::*;
 
     use super::*;
     use crate::executor::aggregation::AggArgs;
@@ -543,6 +577,117 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_extreme_agg_state_last_value() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: varchar, b: int32, c: int32, _row_id: int64)
+
+        let input_pk_indices = vec![3]; // _row_id
+        let field1 = Field::unnamed(DataType::Varchar);
+        let field2 = Field::unnamed(DataType::Int32);
+        let field3 = Field::unnamed(DataType::Int32);
+        let field4 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3, field4]);
+        let agg_call = AggCall {
+            kind: AggKind::LastValue,
+            args: AggArgs::Unary(DataType::Varchar, 0), // last_value(a order by b)
+            return_type: DataType::Varchar,
+            order_pairs: vec![OrderPair::new(1, OrderType::Ascending)],
+            append_only: false,
+            filter: None,
+        };
+
+        let table_id = TableId::new(0x2333);
+        let columns = vec![
+            ColumnDesc::unnamed(ColumnId::new(0), DataType::Int32), // b
+            ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64), // _row_id
+            ColumnDesc::unnamed(ColumnId::new(2), DataType::Varchar), // a
+        ];
+        let state_table_col_mapping = StateTableColumnMapping::new(vec![1, 3, 0]);
+        let mut state_table = StateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            table_id,
+            columns,
+            vec![
+                OrderType::Descending, // b, reversed for AggKind::LastValue
+                OrderType::Ascending,
+            ],
+            vec![0, 1], // [b, _row_id]
+        );
+
+        let mut managed_state = GenericExtremeState::new(
+            &agg_call,
+            None,
+            &input_pk_indices,
+            state_table_col_mapping.clone(),
+            0,
+            usize::MAX,
+            &input_schema,
+        );
+
+        let epoch = EpochPair::new_test_epoch(1);
+        state_table.init_epoch(epoch);
+        epoch.inc();
+
+        {
+            let chunk = StreamChunk::from_pretty(
+                " T i i I
+                + a 1 8 123
+                + b 5 2 128
+                - b 5 2 128
+                + . 7 6 129",
+            );
+            let (ops, columns, visibility) = chunk.into_inner();
+            let column_refs: Vec<_> = columns.iter().map(|col| col.array_ref()).collect();
+            managed_state
+                .apply_chunk(&ops, visibility.as_ref(), &column_refs, &mut state_table)
+                .await?;
+
+            state_table.commit_for_test(epoch).await.unwrap();
+            epoch.inc();
+
+            // NULL values are not ignored by `last_value`
+            let res = managed_state.get_output(&state_table).await?;
+            assert_eq!(res, None);
+        }
+
+        {
+            let chunk = StreamChunk::from_pretty(
+                " T i i I
+                - . 7 6 129
+                + e 2 2 137",
+            );
+            let (ops, columns, visibility) = chunk.into_inner();
+            let column_refs: Vec<_> = columns.iter().map(|col| col.array_ref()).collect();
+            managed_state
+                .apply_chunk(&ops, visibility.as_ref(), &column_refs, &mut state_table)
+                .await?;
+
+            state_table.commit_for_test(epoch).await.unwrap();
+
+            let res = managed_state.get_output(&state_table).await?;
+            assert_eq!(res, Some(ScalarImpl::Utf8("e".to_string())));
+        }
+
+        {
+            // test recovery (cold start)
+            let row_count = managed_state.total_count;
+            let mut managed_state = GenericExtremeState::new(
+                &agg_call,
+                None,
+                &input_pk_indices,
+                state_table_col_mapping,
+                row_count,
+                usize::MAX,
+                &input_schema,
+            );
+            let res = managed_state.get_output(&state_table).await?;
+            assert_eq!(res, Some(ScalarImpl::Utf8("e".to_string())));
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_extreme_agg_state_with_null_value() -> StreamExecutorResult<()> {
         // Assumption of input schema:
@@ -21,6 +21,7 @@ use piestream_common::array::stream_chunk::Ops;
 use piestream_common::array::{ArrayImpl, Row};
 use piestream_common::buffer::Bitmap;
 use piestream_common::catalog::Schema;
+use piestream_common::collection::estimate_size::EstimateSize;
 use piestream_common::types::Datum;
 use piestream_expr::expr::AggKind;
 use piestream_storage::StateStore;
@@ -29,10 +30,12 @@ pub use value::*;
 use crate::executor::aggregation::{AggCall, AggStateTable};
 use crate::executor::error::StreamExecutorResult;
 use crate::executor::managed_state::aggregation::array_agg::ManagedArrayAggState;
+use crate::executor::managed_state::aggregation::collect::ManagedCollectState;
 use crate::executor::managed_state::aggregation::string_agg::ManagedStringAggState;
 use crate::executor::PkIndices;
 
 mod array_agg;
+mod collect;
 mod extreme;
 mod string_agg;
 mod value;
@@ -52,21 +55,25 @@ pub fn verify_batch(
     all_lengths.iter().min() == all_lengths.iter().max()
 }
 
-/// Common cache structure for managed table states (non-append-only `min`/`max`, `string_agg`).
-pub struct Cache<K: Ord, V> {
+/// Common cache structure for managed table states (non-append-only `min`/`max`, `string_agg`,
+/// etc.).
+pub struct Cache<K: Ord + EstimateSize, V: EstimateSize> {
     /// The capacity of the cache.
     capacity: usize,
     /// Ordered cache entries.
     entries: BTreeMap<K, V>,
+    /// Estimated heap size of the keys and values in the cache.
+    estimated_content_heap_size: usize,
 }
 
-impl<K: Ord, V> Cache<K, V> {
+impl<K: Ord + EstimateSize, V: EstimateSize> Cache<K, V> {
     /// Create a new cache with specified capacity and order requirements.
     /// To create a cache with unlimited capacity, use `usize::MAX` for `capacity`.
     pub fn new(capacity: usize) -> Self {
         Self {
             capacity,
             entries: Default::default(),
+            estimated_content_heap_size: 0,
         }
     }
 
@@ -88,22 +95,30 @@ impl<K: Ord, V> Cache<K, V> {
     /// Clear the cache.
     pub fn clear(&mut self) {
         self.entries.clear();
+        self.estimated_content_heap_size = 0;
     }
 
     /// Insert an entry into the cache.
     /// Key: `OrderedRow` composed of order by fields.
     /// Value: The value fields that are to be aggregated.
     pub fn insert(&mut self, key: K, value: V) {
-        self.entries.insert(key, value);
+        let key_size = key.estimated_size();
+        self.estimated_content_heap_size += key_size + value.estimated_size();
+        if let Some(old_value) = self.entries.insert(key, value) {
+            self.estimated_content_heap_size -= key_size + old_value.estimated_size();
+        }
         // evict if capacity is reached
         while self.entries.len() > self.capacity {
-            self.entries.pop_last();
+            let (key, value) = self.entries.pop_last().unwrap();
+            self.estimated_content_heap_size -= key.estimated_size() + value.estimated_size();
         }
     }
 
     /// Remove an entry from the cache.
     pub fn remove(&mut self, key: K) {
-        self.entries.remove(&key);
+        if let Some(value) = self.entries.remove(&key) {
+            self.estimated_content_heap_size -= key.estimated_size() + value.estimated_size();
+        }
     }
 
     /// Get the last (largest) key in the cache
@@ -122,6 +137,13 @@ impl<K: Ord, V> Cache<K, V> {
     }
 }
 
+impl<K: Ord + EstimateSize, V: EstimateSize> EstimateSize for Cache<K, V> {
+    fn estimated_heap_size(&self) -> usize {
+        // The overhead of the btree-map itself is not counted.
+        self.estimated_content_heap_size
+    }
+}
+
 /// All managed state for aggregation. The managed state will manage the cache and integrate
 /// the state with the underlying state store. Managed states can only be evicted from outer cache
 /// when they are not dirty.
@@ -133,6 +155,18 @@ pub enum ManagedStateImpl<S: StateStore> {
     Table(Box<dyn ManagedTableState<S>>),
 }
 
+impl<S: StateStore> EstimateSize for ManagedStateImpl<S> {
+    fn estimated_heap_size(&self) -> usize {
+        match self {
+            // The single-value states are small enough to be ignored.
+            Self::Value(_) => 0,
+            Self::Table(state) => {
+                std::mem::size_of_val(state.as_ref()) + state.estimated_heap_size()
+            }
+        }
+    }
+}
+
 impl<S: StateStore> ManagedStateImpl<S> {
     pub async fn apply_chunk(
         &mut self,
@@ -194,10 +228,25 @@ impl<S: StateStore> ManagedStateImpl<S> {
                 Self::Value(ManagedValueState::new(agg_call, prev_output.cloned())?),
             ),
             // optimization: use single-value state for append-only min/max
-            AggKind::Max | AggKind::Min if agg_call.append_only => Ok(Self::Value(
-                ManagedValueState::new(agg_call, prev_output.cloned())?,
-            )),
-            AggKind::Max | AggKind::Min => Ok(Self::Table(Box::new(GenericExtremeState::new(
+            AggKind::Max
+            | AggKind::Min
+            | AggKind::BoolAnd
+            | AggKind::BoolOr
+            | AggKind::BitAnd
+            | AggKind::BitOr
+                if agg_call.append_only =>
+            {
+                Ok(Self::Value(ManagedValueState::new(
+                    agg_call,
+                    prev_output.cloned(),
+                )?))
+            }
+            AggKind::Max
+            | AggKind::Min
+            | AggKind::BoolAnd
+            | AggKind::BoolOr
+            | AggKind::FirstValue
+            | AggKind::LastValue => Ok(Self::Table(Box::new(GenericExtremeState::new(
                 agg_call,
                 group_key,
                 pk_indices,
@@ -209,6 +258,19 @@ impl<S: StateStore> ManagedStateImpl<S> {
                 extreme_cache_size,
                 input_schema,
             )))),
+            AggKind::BitAnd
+            | AggKind::BitOr
+            | AggKind::PercentileCont
+            | AggKind::PercentileDisc => Ok(Self::Table(Box::new(ManagedCollectState::new(
+                agg_call,
+                group_key,
+                pk_indices,
+                agg_state_table
+                    .expect("bit_and/bit_or/percentile must have state table")
+                    .mapping
+                    .clone(),
+                row_count,
+            )))),
             AggKind::StringAgg => Ok(Self::Table(Box::new(ManagedStringAggState::new(
                 agg_call,
                 group_key,
@@ -229,6 +291,9 @@ impl<S: StateStore> ManagedStateImpl<S> {
                     .clone(),
                 row_count,
             )))),
+            AggKind::StddevSamp | AggKind::StddevPop | AggKind::VarSamp | AggKind::VarPop => {
+                unreachable!("stddev/variance should have been rewritten to sum and count")
+            }
         }
     }
 }
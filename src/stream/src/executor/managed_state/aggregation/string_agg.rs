@@ -21,6 +21,7 @@ use piestream_common::array::stream_chunk::Ops;
 use piestream_common::array::Op::{Delete, Insert, UpdateDelete, UpdateInsert};
 use piestream_common::array::{ArrayImpl, Row};
 use piestream_common::buffer::Bitmap;
+use piestream_common::collection::estimate_size::EstimateSize;
 use piestream_common::types::{Datum, ScalarImpl};
 use piestream_common::util::ordered::OrderedRow;
 use piestream_common::util::sort_util::OrderType;
@@ -40,6 +41,12 @@ struct StringAggData {
     value: String,
 }
 
+impl EstimateSize for StringAggData {
+    fn estimated_heap_size(&self) -> usize {
+        self.delim.estimated_heap_size() + self.value.estimated_heap_size()
+    }
+}
+
 pub struct ManagedStringAggState<S: StateStore> {
     _phantom_data: PhantomData<S>,
 
@@ -217,6 +224,12 @@ impl<S: StateStore> ManagedStringAggState<S> {
     }
 }
 
+impl<S: StateStore> EstimateSize for ManagedStringAggState<S> {
+    fn estimated_heap_size(&self) -> usize {
+        self.cache.estimated_heap_size()
+    }
+}
+
 #[async_trait]
 impl<S: StateStore> ManagedTableState<S> for ManagedStringAggState<S> {
     async fn apply_chunk(
@@ -16,20 +16,30 @@ use std::collections::btree_map::Range;
 use std::collections::{BTreeMap, HashSet};
 use std::ops::Bound::{self, *};
 use std::ops::RangeBounds;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 use anyhow::anyhow;
+use futures::{pin_mut, StreamExt};
+use itertools::Itertools;
 use piestream_common::array::Row;
-use piestream_common::types::ScalarImpl;
+use piestream_common::buffer::Bitmap;
+use piestream_common::collection::estimate_size::EstimateSize;
+use piestream_common::types::{ScalarImpl, VirtualNode};
 use piestream_common::util::epoch::EpochPair;
 use piestream_storage::table::streaming_table::state_table::StateTable;
 use piestream_storage::StateStore;
 
+use crate::cache::{CacheMetrics, CacheMetricsInfo};
 use crate::executor::error::StreamExecutorError;
 use crate::executor::StreamExecutorResult;
 
+type ScalarRange = (Bound<ScalarImpl>, Bound<ScalarImpl>);
+
 /// The `RangeCache` caches a given range of `ScalarImpl` keys and corresponding rows.
-/// It will evict keys from memory if it is above capacity and shrink its range.
-/// Values not in range will have to be retrieved from storage.
+/// The range is extended with the missing part when a range overlapping with it is requested, and
+/// the whole cache will be evicted once the watermark epoch of `LruManager` passes the epoch in
+/// which the range was first loaded. Values not in range will have to be retrieved from storage.
 pub struct RangeCache<S: StateStore> {
     // TODO: It could be potentially expensive memory-wise to store `HashSet`.
     //       The memory overhead per single row is potentially a backing Vec of size 4
@@ -40,40 +50,64 @@ pub struct RangeCache<S: StateStore> {
     //       storing as `BTreeSet<(ScalarImpl, Row)>`.
     //       We could solve it if `ScalarImpl` had a successor/predecessor function.
     cache: BTreeMap<ScalarImpl, HashSet<Row>>,
-    pub(crate) state_table: StateTable<S>,
-    /// The current range stored in the cache.
+    state_table: StateTable<S>,
+    /// The current range stored in the cache. `None` means nothing is cached.
     /// Any request for a set of values outside of this range will result in a scan
     /// from storage
-    range: (Bound<ScalarImpl>, Bound<ScalarImpl>),
+    range: Option<ScalarRange>,
+    /// The estimated heap size of the rows in the cache.
+    kv_heap_size: usize,
 
-    #[expect(dead_code)]
-    num_rows_stored: usize,
-    #[expect(dead_code)]
-    capacity: usize,
+    /// The current epoch.
+    epoch: u64,
+    /// The epoch in which the current range was first loaded.
+    ///
+    /// The cache is read and extended every time the right side changes, so it would never be
+    /// evicted if it was tagged with the epoch of the last read or extension, like the entries of
+    /// `ManagedLruCache`.
+    range_epoch: u64,
+    /// The cache will be evicted if the watermark passes `range_epoch`.
+    watermark_epoch: Arc<AtomicU64>,
+    metrics: CacheMetrics,
 }
 
 impl<S: StateStore> RangeCache<S> {
-    /// Create a [`RangeCache`] with given capacity and epoch
-    pub fn new(state_table: StateTable<S>, capacity: usize) -> Self {
+    /// Create a [`RangeCache`] with nothing cached.
+    pub fn new(
+        state_table: StateTable<S>,
+        watermark_epoch: Arc<AtomicU64>,
+        metrics_info: CacheMetricsInfo,
+    ) -> Self {
         Self {
             cache: BTreeMap::new(),
             state_table,
-            range: (Unbounded, Unbounded),
-            num_rows_stored: 0,
-            capacity,
+            range: None,
+            kv_heap_size: 0,
+            epoch: 0,
+            range_epoch: 0,
+            watermark_epoch,
+            metrics: CacheMetrics::new(metrics_info),
         }
     }
 
     pub fn init(&mut self, epoch: EpochPair) {
         self.state_table.init_epoch(epoch);
+        self.epoch = epoch.curr;
+    }
+
+    fn in_range(&self, k: &ScalarImpl) -> bool {
+        self.range.as_ref().map_or(false, |range| range.contains(k))
     }
 
     /// Insert a row and corresponding scalar value key into cache (if within range) and
     /// `StateTable`.
     pub fn insert(&mut self, k: ScalarImpl, v: Row) -> StreamExecutorResult<()> {
-        if self.range.contains(&k) {
+        if self.in_range(&k) {
+            let row_size = v.estimated_size();
             let entry = self.cache.entry(k).or_insert_with(HashSet::new);
-            entry.insert(v.clone());
+            if entry.insert(v.clone()) {
+                self.kv_heap_size += row_size;
+            }
         }
         self.state_table.insert(v);
         Ok(())
@@ -83,7 +117,7 @@ impl<S: StateStore> RangeCache<S> {
     /// `StateTable`.
     // FIXME: panic instead of returning Err
     pub fn delete(&mut self, k: &ScalarImpl, v: Row) -> StreamExecutorResult<()> {
-        if self.range.contains(k) {
+        if self.in_range(k) {
             let contains_element = self
                 .cache
                 .get_mut(k)
@@ -95,58 +129,288 @@ impl<S: StateStore> RangeCache<S> {
                     "Deleting non-existent element"
                 )));
             };
+            self.kv_heap_size = self.kv_heap_size.saturating_sub(v.estimated_size());
         }
         self.state_table.delete(v);
         Ok(())
     }
 
-    /// Return an iterator over sets of rows that satisfy the given range. Evicts entries if
-    /// exceeding capacity based on whether the latest RHS value is the lower or upper bound of
-    /// the range.
-    pub fn range(
-        &self,
-        range: (Bound<ScalarImpl>, Bound<ScalarImpl>),
+    /// Return an iterator over sets of rows that satisfy the given range. If the range is not
+    /// fully cached, the missing part is loaded from storage: the cached range is extended if it
+    /// overlaps with the given range, or replaced otherwise.
+    pub async fn range(
+        &mut self,
+        range: ScalarRange,
         _latest_is_lower: bool,
-    ) -> Range<'_, ScalarImpl, HashSet<Row>> {
-        // TODO (cache behaviour):
-        // What we want: At the end of every epoch we will try to read
-        // ranges based on the new value. The values in the range may not all be cached.
-        //
-        // If the new range is overlapping with the current range, we will keep the
-        // current range. We will then evict to capacity after the cache has been populated
-        // with the new range.
-        //
-        // If the new range is non-overlapping, we will delete the old range, and store
-        // `self.capacity` elements from the new range.
-        //
-        // We will always prefer to cache values that are closer to the latest value.
-        //
+    ) -> StreamExecutorResult<Range<'_, ScalarImpl, HashSet<Row>>> {
         // If this requested range is too large, it will cause OOM. The `StateStore`
         // layer already buffers the entire output of a range scan in `Vec`, so there is
         // currently no workarond for OOM due to large range scasns.
-        //
-        // --------------------------------------------------------------------
-        //
-        // For overlapping ranges, we will prevent double inserts,
-        // preferring the fresher in-cache value
-        //
-        // let lower_fetch_range: Option<ScalarRange>) = match self.range.0 {
-        //     Unbounded => None,
-        //     Included(x) | Excluded(x) => match range.0 {
-        //         Unbounded => (Unbounded, Included(x)),
-        //         bound @ Included(y) | Excluded(y) => if y
-        //         Included(y) | Excluded(y) => x <= y,
-        //     },
-        //     Excluded(x) =>
-        // }
-
-        self.cache.range(range)
+        let cached = match self.range.clone() {
+            Some(cached) if !is_disjoint(&cached, &range) => cached,
+            _ => {
+                self.metrics.record_lookup(false);
+                self.cache.clear();
+                self.kv_heap_size = 0;
+                self.fetch_range(range.clone()).await?;
+                self.range = Some(range.clone());
+                self.range_epoch = self.epoch;
+                return Ok(self.cache.range(range));
+            }
+        };
+
+        let lower_missing = !lower_bound_contains(&cached.0, &range.0);
+        let upper_missing = !upper_bound_contains(&cached.1, &range.1);
+        self.metrics.record_lookup(!lower_missing && !upper_missing);
+        if lower_missing {
+            self.fetch_range((range.0.clone(), invert_bound(&cached.0)))
+                .await?;
+        }
+        if upper_missing {
+            self.fetch_range((invert_bound(&cached.1), range.1.clone()))
+                .await?;
+        }
+        self.range = Some((
+            if lower_missing {
+                range.0.clone()
+            } else {
+                cached.0
+            },
+            if upper_missing {
+                range.1.clone()
+            } else {
+                cached.1
+            },
+        ));
+
+        Ok(self.cache.range(range))
+    }
+
+    /// Load the rows of the given range in all vnodes owned by the table into the cache. The range
+    /// must not overlap with the cached range.
+    async fn fetch_range(&mut self, range: ScalarRange) -> StreamExecutorResult<()> {
+        // The pk of the state table is `[left_key] + input_pk`.
+        let key_idx = self.state_table.pk_indices()[0];
+        let pk_range = (to_row_bound(&range.0), to_row_bound(&range.1));
+        let vnodes = self
+            .state_table
+            .vnode_bitmap()
+            .iter()
+            .enumerate()
+            .filter_map(|(vnode, is_set)| is_set.then_some(vnode as VirtualNode))
+            .collect_vec();
+
+        for vnode in vnodes {
+            let row_stream = self
+                .state_table
+                .iter_with_pk_range(&pk_range, vnode)
+                .await?;
+            pin_mut!(row_stream);
+            while let Some(row) = row_stream.next().await {
+                let row = row?.into_owned();
+                // Rows with a null left key are never stored.
+                let key = row[key_idx].clone().unwrap();
+                let row_size = row.estimated_size();
+                if self
+                    .cache
+                    .entry(key)
+                    .or_insert_with(HashSet::new)
+                    .insert(row)
+                {
+                    self.kv_heap_size += row_size;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evict the whole cache if the watermark epoch has passed the epoch in which the range was
+    /// loaded, and report the metrics of the cache.
+    pub fn evict(&mut self) {
+        if self.range.is_some() && self.range_epoch < self.watermark_epoch.load(Ordering::Relaxed) {
+            self.cache.clear();
+            self.range = None;
+            self.kv_heap_size = 0;
+        }
+        self.metrics.report(self.kv_heap_size);
     }
 
     /// Flush writes to the `StateTable` from the in-memory buffer.
     pub async fn flush(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
         // self.metrics.flush();
         self.state_table.commit(epoch).await?;
+        self.epoch = epoch.curr;
         Ok(())
     }
+
+    /// Update the vnode bitmap of the state table. The cache is cleared since the rows of the
+    /// new vnodes are not cached.
+    pub fn update_vnode_bitmap(&mut self, vnode_bitmap: Arc<Bitmap>) {
+        self.state_table.update_vnode_bitmap(vnode_bitmap);
+        self.cache.clear();
+        self.range = None;
+        self.kv_heap_size = 0;
+    }
+}
+
+fn to_row_bound(bound: &Bound<ScalarImpl>) -> Bound<Row> {
+    match bound {
+        Included(v) => Included(Row::new(vec![Some(v.clone())])),
+        Excluded(v) => Excluded(Row::new(vec![Some(v.clone())])),
+        Unbounded => Unbounded,
+    }
+}
+
+/// Returns whether the lower bound `outer` is not greater than the lower bound `inner`.
+fn lower_bound_contains(outer: &Bound<ScalarImpl>, inner: &Bound<ScalarImpl>) -> bool {
+    match (outer, inner) {
+        (Unbounded, _) => true,
+        (_, Unbounded) => false,
+        (Included(o), Included(i) | Excluded(i)) | (Excluded(o), Excluded(i)) => o <= i,
+        (Excluded(o), Included(i)) => o < i,
+    }
+}
+
+/// Returns whether the upper bound `outer` is not less than the upper bound `inner`.
+fn upper_bound_contains(outer: &Bound<ScalarImpl>, inner: &Bound<ScalarImpl>) -> bool {
+    match (outer, inner) {
+        (Unbounded, _) => true,
+        (_, Unbounded) => false,
+        (Included(o), Included(i) | Excluded(i)) | (Excluded(o), Excluded(i)) => o >= i,
+        (Excluded(o), Included(i)) => o > i,
+    }
+}
+
+/// Returns whether the range `outer` contains the whole range `inner`.
+#[cfg(test)]
+fn range_contains(outer: &ScalarRange, inner: &ScalarRange) -> bool {
+    lower_bound_contains(&outer.0, &inner.0) && upper_bound_contains(&outer.1, &inner.1)
+}
+
+/// Returns whether there is a gap between the two ranges, in which case the cached range can't be
+/// extended to cover both without loading the gap.
+fn is_disjoint(a: &ScalarRange, b: &ScalarRange) -> bool {
+    let below = |upper: &Bound<ScalarImpl>, lower: &Bound<ScalarImpl>| match (upper, lower) {
+        (Unbounded, _) | (_, Unbounded) => false,
+        (Included(u) | Excluded(u), Included(l) | Excluded(l)) => u < l,
+    };
+    below(&a.1, &b.0) || below(&b.1, &a.0)
+}
+
+/// Turns the bound of a range into the opposite bound of the adjacent range.
+fn invert_bound(bound: &Bound<ScalarImpl>) -> Bound<ScalarImpl> {
+    match bound {
+        Included(v) => Excluded(v.clone()),
+        Excluded(v) => Included(v.clone()),
+        Unbounded => unreachable!("the adjacent range of an unbounded range is empty"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use piestream_common::catalog::{ColumnDesc, ColumnId, TableId};
+    use piestream_common::types::DataType;
+    use piestream_common::util::sort_util::OrderType;
+    use piestream_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor::monitor::StreamingMetrics;
+
+    fn int(v: i64) -> ScalarImpl {
+        ScalarImpl::Int64(v)
+    }
+
+    #[test]
+    fn test_range_contains() {
+        let outer = (Included(int(1)), Excluded(int(10)));
+        assert!(range_contains(
+            &outer,
+            &(Included(int(1)), Excluded(int(10)))
+        ));
+        assert!(range_contains(
+            &outer,
+            &(Excluded(int(1)), Included(int(9)))
+        ));
+        assert!(!range_contains(
+            &outer,
+            &(Included(int(0)), Included(int(9)))
+        ));
+        assert!(!range_contains(
+            &outer,
+            &(Included(int(1)), Included(int(10)))
+        ));
+        assert!(!range_contains(&outer, &(Included(int(1)), Unbounded)));
+        assert!(range_contains(
+            &(Unbounded, Unbounded),
+            &(Unbounded, Included(int(1)))
+        ));
+    }
+
+    async fn cached_keys(
+        cache: &mut RangeCache<MemoryStateStore>,
+        range: ScalarRange,
+    ) -> Vec<ScalarImpl> {
+        cache
+            .range(range, true)
+            .await
+            .unwrap()
+            .map(|(k, _)| k.clone())
+            .collect_vec()
+    }
+
+    #[tokio::test]
+    async fn test_range_cache_extend_and_evict() {
+        let state_table = StateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            TableId::new(0),
+            vec![ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64)],
+            vec![OrderType::Ascending],
+            vec![0],
+        );
+        let watermark_epoch = Arc::new(AtomicU64::new(0));
+        let mut cache = RangeCache::new(
+            state_table,
+            watermark_epoch.clone(),
+            CacheMetricsInfo::new(Arc::new(StreamingMetrics::unused()), 0, "test"),
+        );
+        cache.init(EpochPair::new_test_epoch(1));
+        for v in 1..=5 {
+            cache.insert(int(v), Row::new(vec![Some(int(v))])).unwrap();
+        }
+        cache.flush(EpochPair::new_test_epoch(2)).await.unwrap();
+
+        assert_eq!(
+            cached_keys(&mut cache, (Included(int(2)), Excluded(int(3)))).await,
+            vec![int(2)]
+        );
+        // Only the missing part is loaded, and the cached rows are kept.
+        assert_eq!(
+            cached_keys(&mut cache, (Included(int(3)), Included(int(4)))).await,
+            vec![int(3), int(4)]
+        );
+        assert_eq!(cache.range, Some((Included(int(2)), Included(int(4)))));
+        assert_eq!(cache.cache.len(), 3);
+
+        // Rows out of the cached range are only written to the state table.
+        cache.insert(int(6), Row::new(vec![Some(int(6))])).unwrap();
+        assert_eq!(cache.cache.len(), 3);
+        cache.flush(EpochPair::new_test_epoch(3)).await.unwrap();
+
+        // The range was loaded in epoch 2, and it's evicted only after the watermark passes it.
+        watermark_epoch.store(2, Ordering::Relaxed);
+        cache.evict();
+        assert!(cache.range.is_some());
+        watermark_epoch.store(3, Ordering::Relaxed);
+        cache.evict();
+        assert!(cache.range.is_none());
+        assert!(cache.cache.is_empty());
+        assert_eq!(cache.kv_heap_size, 0);
+
+        // The evicted range is loaded again from storage.
+        assert_eq!(
+            cached_keys(&mut cache, (Excluded(int(1)), Unbounded)).await,
+            vec![int(2), int(3), int(4), int(5), int(6)]
+        );
+    }
 }
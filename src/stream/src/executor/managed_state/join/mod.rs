@@ -39,7 +39,7 @@ use piestream_storage::table::streaming_table::state_table::StateTable;
 use piestream_storage::StateStore;
 
 use self::iter_utils::zip_by_order_key;
-use crate::cache::{EvictableHashMap, ExecutorCache, LruManagerRef, ManagedLruCache};
+use crate::cache::{CacheMetricsInfo, LruManagerRef, ManagedLruCache};
 use crate::executor::error::StreamExecutorResult;
 use crate::executor::monitor::StreamingMetrics;
 use crate::task::ActorId;
@@ -173,8 +173,7 @@ type PkType = Vec<u8>;
 pub type StateValueType = EncodedJoinRow;
 pub type HashValueType = JoinEntryState;
 
-type JoinHashMapInner<K> =
-    ExecutorCache<K, HashValueType, PrecomputedBuildHasher, SharedStatsAlloc<Global>>;
+type JoinHashMapInner<K> = JoinManagedCache<K>;
 
 pub type JoinManagedCache<K> =
     ManagedLruCache<K, HashValueType, PrecomputedBuildHasher, SharedStatsAlloc<Global>>;
@@ -257,11 +256,10 @@ struct TableInner<S: StateStore> {
 }
 
 impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
-    /// Create a [`JoinHashMap`] with the given LRU capacity.
+    /// Create a [`JoinHashMap`] whose cache is managed by the given [`LruManagerRef`].
     #[allow(clippy::too_many_arguments)]
     pub fn new(
-        lru_manager: Option<LruManagerRef>,
-        cache_size: usize,
+        lru_manager: LruManagerRef,
         join_key_data_types: Vec<DataType>,
         state_all_data_types: Vec<DataType>,
         state_table: StateTable<S>,
@@ -273,6 +271,7 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
         need_degree_table: bool,
         metrics: Arc<StreamingMetrics>,
         actor_id: ActorId,
+        identity: &str,
         side: &'static str,
     ) -> Self {
         let alloc = StatsAlloc::new(Global).shared();
@@ -300,17 +299,11 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
             table: degree_table,
         };
 
-        let cache = if let Some(lru_manager) = lru_manager {
-            ExecutorCache::Managed(
-                lru_manager.create_cache_with_hasher_in(PrecomputedBuildHasher, alloc),
-            )
-        } else {
-            ExecutorCache::Local(EvictableHashMap::with_hasher_in(
-                cache_size,
-                PrecomputedBuildHasher,
-                alloc,
-            ))
-        };
+        let cache = lru_manager.create_cache_with_hasher_in(
+            PrecomputedBuildHasher,
+            alloc,
+            CacheMetricsInfo::new(metrics.clone(), actor_id, format!("{} {}", identity, side)),
+        );
 
         Self {
             inner: cache,
@@ -454,7 +447,7 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
 
     /// Insert a join row
     pub fn insert(&mut self, key: &K, value: JoinRow) {
-        if let Some(entry) = self.inner.get_mut(key) {
+        if let Some(mut entry) = self.inner.peek_mut(key) {
             let pk = value
                 .row
                 .extract_memcomparable_by_indices(&self.pk_serializer, &self.state.pk_indices);
@@ -471,7 +464,7 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
     pub fn insert_row(&mut self, key: &K, value: Row) {
         let join_row = JoinRow::new(value.clone(), 0);
 
-        if let Some(entry) = self.inner.get_mut(key) {
+        if let Some(mut entry) = self.inner.peek_mut(key) {
             let pk =
                 value.extract_memcomparable_by_indices(&self.pk_serializer, &self.state.pk_indices);
             entry.insert(pk, join_row.encode());
@@ -482,7 +475,7 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
 
     /// Delete a join row
     pub fn delete(&mut self, key: &K, value: JoinRow) {
-        if let Some(entry) = self.inner.get_mut(key) {
+        if let Some(mut entry) = self.inner.peek_mut(key) {
             let pk = value
                 .row
                 .extract_memcomparable_by_indices(&self.pk_serializer, &self.state.pk_indices);
@@ -498,7 +491,7 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
     /// Delete a row
     /// Used when the side does not need to update degree.
     pub fn delete_row(&mut self, key: &K, value: Row) {
-        if let Some(entry) = self.inner.get_mut(key) {
+        if let Some(mut entry) = self.inner.peek_mut(key) {
             let pk =
                 value.extract_memcomparable_by_indices(&self.pk_serializer, &self.state.pk_indices);
             entry.remove(pk);
@@ -547,11 +540,8 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
     }
 
     /// Estimated memory usage for this hash table.
-    #[expect(dead_code)]
     pub fn estimated_size(&self) -> usize {
-        self.iter()
-            .map(|(k, v)| k.estimated_size() + v.estimated_size())
-            .sum()
+        self.inner.kv_heap_size()
     }
 
     pub fn null_matched(&self) -> &FixedBitSet {
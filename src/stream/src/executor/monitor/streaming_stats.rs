@@ -16,8 +16,8 @@ use prometheus::core::{AtomicF64, AtomicI64, AtomicU64, GenericCounterVec, Gener
 use prometheus::{
     exponential_buckets, histogram_opts, register_gauge_vec_with_registry,
     register_histogram_vec_with_registry, register_histogram_with_registry,
-    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, Histogram,
-    HistogramVec, Registry,
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, Histogram, HistogramVec, IntGauge, Registry,
 };
 
 pub struct StreamingMetrics {
@@ -57,6 +57,15 @@ pub struct StreamingMetrics {
     pub agg_total_lookup_count: GenericCounterVec<AtomicU64>,
     pub agg_cached_keys: GenericGaugeVec<AtomicI64>,
 
+    // Executor caches managed by `LruManager`
+    pub executor_cache_memory_usage: GenericGaugeVec<AtomicI64>,
+    pub executor_cache_lookup_count: GenericCounterVec<AtomicU64>,
+    pub executor_cache_miss_count: GenericCounterVec<AtomicU64>,
+
+    // Memory management
+    pub lru_watermark_step: IntGauge,
+    pub jemalloc_allocated_bytes: IntGauge,
+
     /// The duration from receipt of barrier to all actors collection.
     /// And the max of all node `barrier_inflight_latency` is the latency for a barrier
     /// to flow through the graph.
@@ -310,6 +319,44 @@ impl StreamingMetrics {
         )
         .unwrap();
 
+        let executor_cache_memory_usage = register_int_gauge_vec_with_registry!(
+            "stream_executor_cache_memory_usage",
+            "Estimated memory usage of the cache in streaming executors",
+            &["actor_id", "executor"],
+            registry
+        )
+        .unwrap();
+
+        let executor_cache_lookup_count = register_int_counter_vec_with_registry!(
+            "stream_executor_cache_lookup_total_count",
+            "Total lookup count of the cache in streaming executors",
+            &["actor_id", "executor"],
+            registry
+        )
+        .unwrap();
+
+        let executor_cache_miss_count = register_int_counter_vec_with_registry!(
+            "stream_executor_cache_lookup_miss_count",
+            "Lookup miss count of the cache in streaming executors",
+            &["actor_id", "executor"],
+            registry
+        )
+        .unwrap();
+
+        let lru_watermark_step = register_int_gauge_with_registry!(
+            "lru_watermark_step",
+            "The steps increase in 1 loop of the lru manager",
+            registry
+        )
+        .unwrap();
+
+        let jemalloc_allocated_bytes = register_int_gauge_with_registry!(
+            "jemalloc_allocated_bytes",
+            "The allocated memory jemalloc reports, used by the lru manager to decide eviction",
+            registry
+        )
+        .unwrap();
+
         let opts = histogram_opts!(
             "stream_barrier_inflight_duration_seconds",
             "barrier_inflight_latency",
@@ -363,6 +410,11 @@ impl StreamingMetrics {
             agg_lookup_miss_count,
             agg_total_lookup_count,
             agg_cached_keys,
+            executor_cache_memory_usage,
+            executor_cache_lookup_count,
+            executor_cache_miss_count,
+            lru_watermark_step,
+            jemalloc_allocated_bytes,
             barrier_inflight_latency,
             barrier_sync_latency,
             sink_commit_duration,
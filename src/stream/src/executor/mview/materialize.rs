@@ -17,14 +17,16 @@ use std::sync::Arc;
 use futures::StreamExt;
 use futures_async_stream::try_stream;
 use itertools::Itertools;
+use piestream_common::array::{Op, StreamChunk};
 use piestream_common::buffer::Bitmap;
 use piestream_common::catalog::{ColumnDesc, ColumnId, Schema, TableId};
 use piestream_common::util::sort_util::OrderPair;
 use piestream_pb::catalog::Table;
+use piestream_pb::stream_plan::HandleConflictBehavior;
 use piestream_storage::table::streaming_table::state_table::StateTable;
 use piestream_storage::StateStore;
 
-use crate::executor::error::StreamExecutorError;
+use crate::executor::error::{StreamExecutorError, StreamExecutorResult};
 use crate::executor::{
     expect_first_barrier, ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor,
     ExecutorInfo, Message, PkIndicesRef,
@@ -39,6 +41,9 @@ pub struct MaterializeExecutor<S: StateStore> {
     /// Columns of arrange keys (including pk, group keys, join keys, etc.)
     arrange_columns: Vec<usize>,
 
+    /// How to handle the rows whose arrange keys conflict with the existing rows.
+    handle_pk_conflict_behavior: HandleConflictBehavior,
+
     actor_context: ActorContextRef,
 
     info: ExecutorInfo,
@@ -48,6 +53,7 @@ impl<S: StateStore> MaterializeExecutor<S> {
     /// Create a new `MaterializeExecutor` with distribution specified with `distribution_keys` and
     /// `vnodes`. For singleton distribution, `distribution_keys` should be empty and `vnodes`
     /// should be `None`.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         input: BoxedExecutor,
         store: S,
@@ -56,6 +62,7 @@ impl<S: StateStore> MaterializeExecutor<S> {
         actor_context: ActorContextRef,
         vnodes: Option<Arc<Bitmap>>,
         table_catalog: &Table,
+        handle_pk_conflict_behavior: HandleConflictBehavior,
     ) -> Self {
         let arrange_columns: Vec<usize> = key.iter().map(|k| k.column_idx).collect();
 
@@ -67,6 +74,7 @@ impl<S: StateStore> MaterializeExecutor<S> {
             input,
             state_table,
             arrange_columns: arrange_columns.clone(),
+            handle_pk_conflict_behavior,
             actor_context,
             info: ExecutorInfo {
                 schema,
@@ -105,6 +113,7 @@ impl<S: StateStore> MaterializeExecutor<S> {
             input,
             state_table,
             arrange_columns: arrange_columns.clone(),
+            handle_pk_conflict_behavior: HandleConflictBehavior::NoCheck,
             actor_context: Default::default(),
             info: ExecutorInfo {
                 schema,
@@ -127,10 +136,16 @@ impl<S: StateStore> MaterializeExecutor<S> {
         for msg in input {
             let msg = msg?;
             yield match msg {
-                Message::Chunk(chunk) => {
-                    self.state_table.write_chunk(chunk.clone());
-                    Message::Chunk(chunk)
-                }
+                Message::Chunk(chunk) => match self.handle_pk_conflict_behavior {
+                    HandleConflictBehavior::NoCheck => {
+                        self.state_table.write_chunk(chunk.clone());
+                        Message::Chunk(chunk)
+                    }
+                    _ => match self.write_chunk_with_pk_conflict(chunk).await? {
+                        Some(chunk) => Message::Chunk(chunk),
+                        None => continue,
+                    },
+                },
                 Message::Barrier(b) => {
                     // FIXME(ZBW): use a better error type
                     self.state_table.commit(b.epoch).await?;
@@ -147,6 +162,59 @@ impl<S: StateStore> MaterializeExecutor<S> {
     }
 }
 
+impl<S: StateStore> MaterializeExecutor<S> {
+    /// Write the chunk into the state table, and resolve the rows whose arrange keys conflict with
+    /// the existing rows according to `handle_pk_conflict_behavior`. Returns the changes actually
+    /// applied to the state table, or `None` if nothing is changed.
+    async fn write_chunk_with_pk_conflict(
+        &mut self,
+        chunk: StreamChunk,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        let mut changes = vec![];
+        for (op, row_ref) in chunk.rows() {
+            let row = row_ref.to_owned_row();
+            let pk = row.by_indices(&self.arrange_columns);
+            let old_row = self.state_table.get_row(&pk).await?;
+
+            match op {
+                Op::Insert | Op::UpdateInsert => match old_row {
+                    None => {
+                        self.state_table.insert(row.clone());
+                        changes.push((Op::Insert, row));
+                    }
+                    Some(old_row) => match self.handle_pk_conflict_behavior {
+                        HandleConflictBehavior::Overwrite => {
+                            if old_row != row {
+                                self.state_table.update(old_row.clone(), row.clone());
+                                changes.push((Op::UpdateDelete, old_row));
+                                changes.push((Op::UpdateInsert, row));
+                            }
+                        }
+                        HandleConflictBehavior::Ignore => {}
+                        HandleConflictBehavior::NoCheck => unreachable!(),
+                    },
+                },
+                Op::Delete | Op::UpdateDelete => {
+                    // Delete the row actually stored, which may differ from the given one if the
+                    // given one has been overwritten or ignored.
+                    if let Some(old_row) = old_row {
+                        self.state_table.delete(old_row.clone());
+                        changes.push((Op::Delete, old_row));
+                    }
+                }
+            }
+        }
+
+        if changes.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(StreamChunk::from_rows(
+            &changes,
+            &self.info.schema.data_types(),
+        )))
+    }
+}
+
 impl<S: StateStore> Executor for MaterializeExecutor<S> {
     fn execute(self: Box<Self>) -> BoxedMessageStream {
         self.execute_inner().boxed()
@@ -285,4 +353,80 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_materialize_pk_conflict() {
+        use piestream_pb::stream_plan::HandleConflictBehavior;
+
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int32),
+        ]);
+
+        let materialize = |behavior: HandleConflictBehavior| {
+            let source = MockSource::with_messages(
+                schema.clone(),
+                PkIndices::new(),
+                vec![
+                    Message::Barrier(Barrier::new_test_barrier(1)),
+                    Message::Chunk(StreamChunk::from_pretty(
+                        " i i
+                        + 1 4
+                        + 2 5",
+                    )),
+                    Message::Barrier(Barrier::new_test_barrier(2)),
+                    Message::Chunk(StreamChunk::from_pretty(
+                        " i i
+                        + 1 7
+                        - 2 9
+                        + 3 6",
+                    )),
+                    Message::Barrier(Barrier::new_test_barrier(3)),
+                ],
+            );
+            let mut executor = MaterializeExecutor::for_test(
+                Box::new(source),
+                MemoryStateStore::new(),
+                TableId::new(1),
+                vec![OrderPair::new(0, OrderType::Ascending)],
+                vec![0.into(), 1.into()],
+                1,
+            );
+            executor.handle_pk_conflict_behavior = behavior;
+            Box::new(executor).execute()
+        };
+
+        // Skip the first epoch, and get the changes applied in the second epoch.
+        async fn second_chunk(mut executor: BoxedMessageStream) -> StreamChunk {
+            for _ in 0..3 {
+                executor.next().await.unwrap().unwrap();
+            }
+            executor
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap()
+        }
+
+        assert_eq!(
+            second_chunk(materialize(HandleConflictBehavior::Overwrite)).await,
+            StreamChunk::from_pretty(
+                " i i
+                U- 1 4
+                U+ 1 7
+                -  2 5
+                +  3 6",
+            )
+        );
+        assert_eq!(
+            second_chunk(materialize(HandleConflictBehavior::Ignore)).await,
+            StreamChunk::from_pretty(
+                " i i
+                - 2 5
+                + 3 6",
+            )
+        );
+    }
 }
@@ -176,6 +176,11 @@ impl RearrangedChainExecutor {
             let mut last_rearranged_epoch = create_epoch;
             let mut stop_rearrange_tx = Some(stop_rearrange_tx);
 
+            // Record the epoch of the last phantom barrier we consumed, and the number of rows of
+            // the snapshot and the upstream we've consumed, to report the progress.
+            let mut last_consumed_epoch = create_epoch.prev;
+            let mut consumed_rows = 0;
+
             // 6. Consume the merged `rearranged` stream.
             #[for_await]
             for rearranged_msg in &mut rearranged {
@@ -187,8 +192,12 @@ impl RearrangedChainExecutor {
                     // consumed the whole snapshot and be on the upstream now.
                     RearrangedMessage::PhantomBarrier(barrier) => {
                         // Update the progress since we've consumed all chunks before this phantom.
-                        self.progress
-                            .update(last_rearranged_epoch.curr, barrier.epoch.curr);
+                        last_consumed_epoch = barrier.epoch.curr;
+                        self.progress.update(
+                            last_rearranged_epoch.curr,
+                            last_consumed_epoch,
+                            consumed_rows,
+                        );
 
                         if barrier.epoch.curr >= last_rearranged_epoch.curr {
                             // Stop the background rearrangement task.
@@ -201,10 +210,20 @@ impl RearrangedChainExecutor {
 
                     // If we received a message, yield it.
                     RearrangedMessage::RearrangedBarrier(barrier) => {
+                        // Report the rows consumed so far, so that the progress keeps moving while
+                        // we're still consuming the snapshot.
+                        self.progress.update(
+                            barrier.epoch.curr,
+                            last_consumed_epoch,
+                            consumed_rows,
+                        );
                         last_rearranged_epoch = barrier.epoch;
                         yield Message::Barrier(barrier);
                     }
-                    RearrangedMessage::Chunk(chunk) => yield Message::Chunk(chunk),
+                    RearrangedMessage::Chunk(chunk) => {
+                        consumed_rows += chunk.cardinality() as u64;
+                        yield Message::Chunk(chunk);
+                    }
                 }
             }
 
@@ -218,7 +237,7 @@ impl RearrangedChainExecutor {
             // 8. Consume remainings.
             let mut finish_on_barrier = |msg: &Message| {
                 if let Some(barrier) = msg.as_barrier() {
-                    self.progress.finish(barrier.epoch.curr);
+                    self.progress.finish(barrier.epoch.curr, consumed_rows);
                 }
             };
 
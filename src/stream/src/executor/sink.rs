@@ -17,20 +17,27 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
-use futures::StreamExt;
+use futures::{pin_mut, FutureExt, StreamExt};
 use futures_async_stream::try_stream;
+use piestream_common::array::{Row, StreamChunk};
 use piestream_common::catalog::Schema;
-use piestream_connector::sink::{Sink, SinkConfig, SinkImpl};
+use piestream_common::types::{ScalarImpl, VirtualNode};
+use piestream_connector::sink::{PreCommittedTransaction, Sink, SinkConfig, SinkImpl};
+use piestream_hummock_sdk::HummockReadEpoch;
+use piestream_storage::table::streaming_table::state_table::StateTable;
 use piestream_storage::StateStore;
 
 use super::error::{StreamExecutorError, StreamExecutorResult};
-use super::{BoxedExecutor, Executor, Message};
+use super::{expect_first_barrier, BoxedExecutor, Executor, Message};
 use crate::executor::monitor::StreamingMetrics;
 use crate::executor::PkIndices;
 
 pub struct SinkExecutor<S: StateStore> {
     input: BoxedExecutor,
-    _store: S,
+    store: S,
+    /// Persists the transaction pre-committed but not yet committed to the sink, under the first
+    /// vnode owned by the sink.
+    state_table: StateTable<S>,
     metrics: Arc<StreamingMetrics>,
     properties: HashMap<String, String>,
     identity: String,
@@ -44,7 +51,8 @@ async fn build_sink(config: SinkConfig) -> StreamExecutorResult<Box<SinkImpl>> {
 impl<S: StateStore> SinkExecutor<S> {
     pub fn new(
         materialize_executor: BoxedExecutor,
-        _store: S,
+        store: S,
+        state_table: StateTable<S>,
         metrics: Arc<StreamingMetrics>,
         mut properties: HashMap<String, String>,
         executor_id: u64,
@@ -54,7 +62,8 @@ impl<S: StateStore> SinkExecutor<S> {
         properties.insert("identifier".to_string(), format!("sink-{:?}", executor_id));
         Self {
             input: materialize_executor,
-            _store,
+            store,
+            state_table,
             metrics,
             properties,
             identity: format!("SinkExecutor_{:?}", executor_id),
@@ -62,16 +71,136 @@ impl<S: StateStore> SinkExecutor<S> {
         }
     }
 
+    /// The vnode under which the transaction pre-committed by this sink is persisted.
+    fn record_vnode(state_table: &StateTable<S>) -> VirtualNode {
+        state_table
+            .vnode_bitmap()
+            .iter()
+            .position(|is_set| is_set)
+            .expect("sink owns no vnode") as VirtualNode
+    }
+
+    /// Load the transactions pre-committed but not yet committed to the sink, persisted under the
+    /// vnodes owned by the sink, possibly by other sinks before recovery.
+    async fn load_pre_committed(
+        state_table: &StateTable<S>,
+    ) -> StreamExecutorResult<Vec<(VirtualNode, PreCommittedTransaction)>> {
+        let vnodes = state_table
+            .vnode_bitmap()
+            .iter()
+            .enumerate()
+            .filter_map(|(vnode, is_set)| is_set.then_some(vnode as VirtualNode));
+        let mut transactions = vec![];
+        for vnode in vnodes {
+            let pk_prefix = Row::new(vec![Some(ScalarImpl::Int16(vnode as i16))]);
+            let rows = state_table.iter_with_pk_prefix(&pk_prefix).await?;
+            pin_mut!(rows);
+            let mut transaction: Option<PreCommittedTransaction> = None;
+            while let Some(row) = rows.next().await {
+                let row = row?;
+                let (transactional_id, epoch, key, payload) = match &row.0[2..] {
+                    [Some(ScalarImpl::Utf8(transactional_id)), Some(ScalarImpl::Int64(epoch)), Some(ScalarImpl::Utf8(key)), Some(ScalarImpl::Utf8(payload))] => {
+                        (
+                            transactional_id.clone(),
+                            *epoch as u64,
+                            key.clone(),
+                            payload.clone(),
+                        )
+                    }
+                    _ => unreachable!("invalid row of sink state table: {:?}", row),
+                };
+                let transaction = transaction.get_or_insert_with(|| PreCommittedTransaction {
+                    transactional_id,
+                    epoch,
+                    records: vec![],
+                });
+                transaction.records.push((key, payload));
+            }
+            if let Some(transaction) = transaction {
+                transactions.push((vnode, transaction));
+            }
+        }
+        Ok(transactions)
+    }
+
+    /// Persist the pre-committed `transaction` under `vnode`. The record becomes durable along with
+    /// the checkpoint of the epoch of the transaction.
+    fn persist_pre_committed(
+        state_table: &mut StateTable<S>,
+        vnode: VirtualNode,
+        transaction: &PreCommittedTransaction,
+    ) {
+        for (seq, (key, payload)) in transaction.records.iter().enumerate() {
+            state_table.insert(Row::new(vec![
+                Some(ScalarImpl::Int16(vnode as i16)),
+                Some(ScalarImpl::Int64(seq as i64)),
+                Some(ScalarImpl::Utf8(transaction.transactional_id.clone())),
+                Some(ScalarImpl::Int64(transaction.epoch as i64)),
+                Some(ScalarImpl::Utf8(key.clone())),
+                Some(ScalarImpl::Utf8(payload.clone())),
+            ]));
+        }
+    }
+
+    /// Remove the pre-committed transaction persisted under `vnode` once it's resolved.
+    async fn clear_pre_committed(
+        state_table: &mut StateTable<S>,
+        vnode: VirtualNode,
+    ) -> StreamExecutorResult<()> {
+        let key = Row::new(vec![Some(ScalarImpl::Int16(vnode as i16))]);
+        state_table.delete_with_pk_prefix(&key).await?;
+        Ok(())
+    }
+
+    async fn commit_sink(
+        sink: &mut SinkImpl,
+        metrics: &StreamingMetrics,
+        identity: &str,
+        connector: &str,
+    ) -> StreamExecutorResult<()> {
+        let start_time = Instant::now();
+        sink.commit().await?;
+        metrics
+            .sink_commit_duration
+            .with_label_values(&[identity, connector])
+            .observe(start_time.elapsed().as_millis() as f64);
+        Ok(())
+    }
+
+    async fn write_chunks(
+        sink: &mut SinkImpl,
+        chunks: impl IntoIterator<Item = StreamChunk>,
+        schema: &Schema,
+    ) -> StreamExecutorResult<()> {
+        for chunk in chunks {
+            if let Err(e) = sink.write_batch(chunk, schema).await {
+                sink.abort().await?;
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+
     #[try_stream(ok = Message, error = StreamExecutorError)]
-    async fn execute_inner(self) {
+    async fn execute_inner(mut self) {
         let sink_config = SinkConfig::from_hashmap(self.properties.clone())?;
         let mut sink = build_sink(sink_config.clone()).await?;
+        let connector = sink_config.get_connector();
+
+        // If set, the transactions of the sink are pre-committed on checkpoint barriers, and only
+        // committed after the epoch is committed by the storage, so that the messages sent to the
+        // sink are aligned with the checkpoints.
+        let two_phase_commit = sink.supports_two_phase_commit();
 
         // the flag is required because kafka transaction requires at least one
         // message, so we should abort the transaction if the flag is true.
         let mut empty_epoch_flag = true;
         let mut in_transaction = false;
-        let mut epoch = 0;
+        // The epoch pre-committed but not yet committed.
+        let mut pre_committed_epoch: Option<u64> = None;
+        // The sink only supports one transaction at a time, so the chunks arriving before the
+        // pre-committed transaction is committed are held back until then.
+        let mut pending_chunks: Vec<StreamChunk> = vec![];
 
         let schema = self.schema().clone();
 
@@ -80,49 +209,140 @@ impl<S: StateStore> SinkExecutor<S> {
             sink.prepare(&schema).await?;
         }
 
-        let input = self.input.execute();
+        let mut input = self.input.execute();
+
+        let barrier = expect_first_barrier(&mut input).await?;
+        self.state_table.init_epoch(barrier.epoch);
+        let mut epoch = barrier.epoch.curr;
+        let record_vnode = Self::record_vnode(&self.state_table);
+
+        // After recovery, the transactions pre-committed before are committed if their epochs have
+        // been committed by the storage, i.e. are not later than the epoch recovered from.
+        // Otherwise they're aborted, and their messages will be replayed.
+        if two_phase_commit {
+            let pre_committed = Self::load_pre_committed(&self.state_table).await?;
+            tracing::info!(
+                "{} recovers pre-committed transactions {:?}",
+                self.identity,
+                pre_committed
+                    .iter()
+                    .map(|(_, transaction)| (&transaction.transactional_id, transaction.epoch))
+                    .collect::<Vec<_>>()
+            );
+            let mut vnodes = Vec::with_capacity(pre_committed.len());
+            let mut transactions = Vec::with_capacity(pre_committed.len());
+            for (vnode, transaction) in pre_committed {
+                vnodes.push(vnode);
+                transactions.push(transaction);
+            }
+            sink.recover(transactions, barrier.epoch.prev).await?;
+            for vnode in vnodes {
+                Self::clear_pre_committed(&mut self.state_table, vnode).await?;
+            }
+        }
+
+        yield Message::Barrier(barrier);
 
         #[for_await]
         for msg in input {
             match msg? {
                 Message::Chunk(chunk) => {
-                    if !in_transaction {
-                        sink.begin_epoch(epoch).await?;
-                        in_transaction = true;
+                    // Commit the pre-committed transaction once its epoch is committed, without
+                    // waiting for it on the data path.
+                    if let Some(pre_committed) = pre_committed_epoch {
+                        if let Some(result) = self
+                            .store
+                            .try_wait_epoch(HummockReadEpoch::Committed(pre_committed))
+                            .now_or_never()
+                        {
+                            result?;
+                            Self::commit_sink(&mut sink, &self.metrics, &self.identity, connector)
+                                .await?;
+                            Self::clear_pre_committed(&mut self.state_table, record_vnode).await?;
+                            pre_committed_epoch = None;
+                        }
                     }
 
                     let visible_chunk = chunk.clone().compact();
-                    if let Err(e) = sink.write_batch(visible_chunk, &schema).await {
-                        sink.abort().await?;
-                        return Err(e.into());
+                    if pre_committed_epoch.is_some() {
+                        pending_chunks.push(visible_chunk);
+                    } else {
+                        if !in_transaction {
+                            sink.begin_epoch(epoch).await?;
+                            in_transaction = true;
+                        }
+                        let chunks = pending_chunks
+                            .drain(..)
+                            .chain(std::iter::once(visible_chunk));
+                        Self::write_chunks(&mut sink, chunks, &schema).await?;
+                        empty_epoch_flag = false;
                     }
-                    empty_epoch_flag = false;
 
                     yield Message::Chunk(chunk);
                 }
                 Message::Barrier(barrier) => {
-                    if in_transaction {
+                    if let Some(pre_committed) = pre_committed_epoch {
+                        // A transaction can only be pre-committed after the previous one is
+                        // committed, so wait for the epoch on checkpoint barriers.
+                        let committed = if barrier.checkpoint {
+                            self.store
+                                .try_wait_epoch(HummockReadEpoch::Committed(pre_committed))
+                                .await?;
+                            true
+                        } else {
+                            match self
+                                .store
+                                .try_wait_epoch(HummockReadEpoch::Committed(pre_committed))
+                                .now_or_never()
+                            {
+                                Some(result) => result.map(|_| true)?,
+                                None => false,
+                            }
+                        };
+                        if committed {
+                            Self::commit_sink(&mut sink, &self.metrics, &self.identity, connector)
+                                .await?;
+                            Self::clear_pre_committed(&mut self.state_table, record_vnode).await?;
+                            pre_committed_epoch = None;
+                        }
+                    }
+
+                    if pre_committed_epoch.is_none() && !pending_chunks.is_empty() {
+                        if !in_transaction {
+                            sink.begin_epoch(epoch).await?;
+                            in_transaction = true;
+                        }
+                        Self::write_chunks(&mut sink, pending_chunks.drain(..), &schema).await?;
+                        empty_epoch_flag = false;
+                    }
+
+                    if in_transaction && (!two_phase_commit || barrier.checkpoint) {
                         if empty_epoch_flag {
                             sink.abort().await?;
                             tracing::debug!(
                                 "transaction abort due to empty epoch, epoch: {:?}",
                                 epoch
                             );
+                        } else if two_phase_commit {
+                            let transaction = sink.pre_commit(barrier.epoch.prev).await?;
+                            Self::persist_pre_committed(
+                                &mut self.state_table,
+                                record_vnode,
+                                &transaction,
+                            );
+                            pre_committed_epoch = Some(barrier.epoch.prev);
                         } else {
-                            let start_time = Instant::now();
-                            sink.commit().await?;
-                            self.metrics
-                                .sink_commit_duration
-                                .with_label_values(&[
-                                    self.identity.as_str(),
-                                    sink_config.get_connector(),
-                                ])
-                                .observe(start_time.elapsed().as_millis() as f64);
+                            Self::commit_sink(&mut sink, &self.metrics, &self.identity, connector)
+                                .await?;
                         }
+                        in_transaction = false;
+                        empty_epoch_flag = true;
+                    }
+
+                    self.state_table.commit(barrier.epoch).await?;
+                    if !in_transaction {
+                        epoch = barrier.epoch.curr;
                     }
-                    in_transaction = false;
-                    empty_epoch_flag = true;
-                    epoch = barrier.epoch.curr;
                     yield Message::Barrier(barrier);
                 }
             }
@@ -150,9 +370,172 @@ impl<S: StateStore> Executor for SinkExecutor<S> {
 
 #[cfg(test)]
 mod test {
+    use piestream_common::buffer::Bitmap;
+    use piestream_common::catalog::{DatabaseId, SchemaId};
+    use piestream_common::types::VIRTUAL_NODE_COUNT;
+    use piestream_common::util::epoch::EpochPair;
+    use piestream_pb::catalog::{ColumnIndex, Table as ProstTable};
+    use piestream_pb::data::data_type::TypeName;
+    use piestream_pb::data::DataType as ProstDataType;
+    use piestream_pb::plan_common::{ColumnCatalog, ColumnDesc, ColumnOrder};
+    use piestream_storage::memory::MemoryStateStore;
+
     use super::*;
     use crate::executor::test_utils::*;
 
+    // align with schema defined in `StreamSink::infer_internal_table_catalog`.
+    fn default_sink_internal_table(id: u32) -> ProstTable {
+        let make_column = |column_type: TypeName, column_id: i32| -> ColumnCatalog {
+            ColumnCatalog {
+                column_desc: Some(ColumnDesc {
+                    column_type: Some(ProstDataType {
+                        type_name: column_type as i32,
+                        ..Default::default()
+                    }),
+                    column_id,
+                    ..Default::default()
+                }),
+                is_hidden: false,
+            }
+        };
+
+        let columns = vec![
+            make_column(TypeName::Int16, 0),
+            make_column(TypeName::Int64, 1),
+            make_column(TypeName::Varchar, 2),
+            make_column(TypeName::Int64, 3),
+            make_column(TypeName::Varchar, 4),
+            make_column(TypeName::Varchar, 5),
+        ];
+        ProstTable {
+            id,
+            schema_id: SchemaId::placeholder() as u32,
+            database_id: DatabaseId::placeholder() as u32,
+            name: String::new(),
+            columns,
+            is_index: false,
+            value_indices: vec![0, 1, 2, 3, 4, 5],
+            pk: vec![
+                ColumnOrder {
+                    index: 0,
+                    order_type: 1,
+                },
+                ColumnOrder {
+                    index: 1,
+                    order_type: 1,
+                },
+            ],
+            distribution_key: vec![0],
+            vnode_col_idx: Some(ColumnIndex { index: 0 }),
+            ..Default::default()
+        }
+    }
+
+    fn vnodes(range: std::ops::Range<usize>) -> Arc<Bitmap> {
+        Arc::new(
+            (0..VIRTUAL_NODE_COUNT)
+                .map(|vnode| range.contains(&vnode))
+                .collect(),
+        )
+    }
+
+    fn transaction(transactional_id: &str, epoch: u64, len: usize) -> PreCommittedTransaction {
+        PreCommittedTransaction {
+            transactional_id: transactional_id.to_string(),
+            epoch,
+            records: (0..len)
+                .map(|i| {
+                    (
+                        format!("{}-{}", transactional_id, i),
+                        format!("{{\"v\":{}}}", i),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recover_pre_committed() {
+        let store = MemoryStateStore::new();
+        let table = default_sink_internal_table(0x2333);
+        let half = VIRTUAL_NODE_COUNT / 2;
+
+        // Two sink actors persist their pre-committed transactions under their first vnodes.
+        let mut state_table_1 =
+            StateTable::from_table_catalog(&table, store.clone(), Some(vnodes(0..half)));
+        let mut state_table_2 = StateTable::from_table_catalog(
+            &table,
+            store.clone(),
+            Some(vnodes(half..VIRTUAL_NODE_COUNT)),
+        );
+        state_table_1.init_epoch(EpochPair::new_test_epoch(1));
+        state_table_2.init_epoch(EpochPair::new_test_epoch(1));
+        let vnode_1 = SinkExecutor::record_vnode(&state_table_1);
+        let vnode_2 = SinkExecutor::record_vnode(&state_table_2);
+        assert_eq!(vnode_1, 0);
+        assert_eq!(vnode_2, half as VirtualNode);
+        SinkExecutor::persist_pre_committed(&mut state_table_1, vnode_1, &transaction("a", 1, 2));
+        SinkExecutor::persist_pre_committed(&mut state_table_2, vnode_2, &transaction("b", 1, 3));
+        state_table_1
+            .commit(EpochPair::new_test_epoch(2))
+            .await
+            .unwrap();
+        state_table_2
+            .commit(EpochPair::new_test_epoch(2))
+            .await
+            .unwrap();
+
+        // The first one is committed and cleared, then pre-commits another transaction.
+        SinkExecutor::clear_pre_committed(&mut state_table_1, vnode_1)
+            .await
+            .unwrap();
+        SinkExecutor::persist_pre_committed(&mut state_table_1, vnode_1, &transaction("a", 2, 1));
+        state_table_1
+            .commit(EpochPair::new_test_epoch(3))
+            .await
+            .unwrap();
+        state_table_2
+            .commit(EpochPair::new_test_epoch(3))
+            .await
+            .unwrap();
+
+        // After recovery, the transactions are loaded by the actors owning their vnodes.
+        let mut state_table =
+            StateTable::from_table_catalog(&table, store.clone(), Some(vnodes(0..1)));
+        state_table.init_epoch(EpochPair::new_test_epoch(3));
+        assert_eq!(
+            SinkExecutor::load_pre_committed(&state_table)
+                .await
+                .unwrap(),
+            vec![(0, transaction("a", 2, 1))]
+        );
+        let mut state_table = StateTable::from_table_catalog(
+            &table,
+            store.clone(),
+            Some(vnodes(1..VIRTUAL_NODE_COUNT)),
+        );
+        state_table.init_epoch(EpochPair::new_test_epoch(3));
+        assert_eq!(
+            SinkExecutor::load_pre_committed(&state_table)
+                .await
+                .unwrap(),
+            vec![(half as VirtualNode, transaction("b", 1, 3))]
+        );
+
+        // They're removed once resolved.
+        SinkExecutor::clear_pre_committed(&mut state_table, half as VirtualNode)
+            .await
+            .unwrap();
+        state_table
+            .commit(EpochPair::new_test_epoch(4))
+            .await
+            .unwrap();
+        assert!(SinkExecutor::load_pre_committed(&state_table)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[ignore]
     #[tokio::test]
     async fn test_mysqlsink() {
@@ -160,7 +543,6 @@ mod test {
         use piestream_common::array::StreamChunkTestExt;
         use piestream_common::catalog::Field;
         use piestream_common::types::DataType;
-        use piestream_storage::memory::MemoryStateStore;
 
         use crate::executor::Barrier;
 
@@ -181,11 +563,12 @@ mod test {
             ]),
             PkIndices::new(),
             vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
                 Message::Chunk(std::mem::take(&mut StreamChunk::from_pretty(
                     " I I I
             +  3 2 1",
                 ))),
-                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Barrier(Barrier::new_test_barrier(2)),
                 Message::Chunk(std::mem::take(&mut StreamChunk::from_pretty(
                     " I I I
             +  6 5 4",
@@ -193,9 +576,14 @@ mod test {
             ],
         );
 
+        let store = MemoryStateStore::new();
+        let state_table =
+            StateTable::from_table_catalog(&default_sink_internal_table(1), store.clone(), None);
+
         let sink_executor = SinkExecutor::new(
             Box::new(mock),
-            MemoryStateStore::new(),
+            store,
+            state_table,
             Arc::new(StreamingMetrics::unused()),
             properties,
             0,
@@ -207,5 +595,6 @@ mod test {
         executor.next().await.unwrap().unwrap();
         executor.next().await.unwrap().unwrap();
         executor.next().await.unwrap().unwrap();
+        executor.next().await.unwrap().unwrap();
     }
 }
@@ -12,8 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::hash_map::Entry::Vacant;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -30,10 +29,12 @@ use piestream_storage::StateStore;
 use super::top_n_cache::TopNCacheTrait;
 use super::utils::*;
 use super::TopNCache;
+use crate::cache::{CacheMetricsInfo, LruManagerRef, ManagedLruCache};
 use crate::error::StreamResult;
 use crate::executor::error::StreamExecutorResult;
 use crate::executor::managed_state::top_n::ManagedTopNState;
-use crate::executor::{Executor, ExecutorInfo, PkIndices, PkIndicesRef};
+use crate::executor::monitor::StreamingMetrics;
+use crate::executor::{ActorContextRef, Executor, ExecutorInfo, PkIndices, PkIndicesRef};
 
 pub type GroupTopNExecutor<S, const WITH_TIES: bool> =
     TopNExecutorWrapper<InnerGroupTopNExecutorNew<S, WITH_TIES>>;
@@ -49,6 +50,9 @@ impl<S: StateStore> GroupTopNExecutor<S, false> {
         executor_id: u64,
         group_by: Vec<usize>,
         state_table: StateTable<S>,
+        ctx: ActorContextRef,
+        lru_manager: LruManagerRef,
+        metrics: Arc<StreamingMetrics>,
     ) -> StreamResult<Self> {
         let info = input.info();
         let schema = input.schema().clone();
@@ -65,6 +69,9 @@ impl<S: StateStore> GroupTopNExecutor<S, false> {
                 executor_id,
                 group_by,
                 state_table,
+                ctx,
+                lru_manager,
+                metrics,
             )?,
         })
     }
@@ -81,6 +88,9 @@ impl<S: StateStore> GroupTopNExecutor<S, true> {
         executor_id: u64,
         group_by: Vec<usize>,
         state_table: StateTable<S>,
+        ctx: ActorContextRef,
+        lru_manager: LruManagerRef,
+        metrics: Arc<StreamingMetrics>,
     ) -> StreamResult<Self> {
         let info = input.info();
         let schema = input.schema().clone();
@@ -97,6 +107,9 @@ impl<S: StateStore> GroupTopNExecutor<S, true> {
                 executor_id,
                 group_by,
                 state_table,
+                ctx,
+                lru_manager,
+                metrics,
             )?,
         })
     }
@@ -130,7 +143,7 @@ pub struct InnerGroupTopNExecutorNew<S: StateStore, const WITH_TIES: bool> {
     group_by: Vec<usize>,
 
     /// group key -> cache for this group
-    caches: HashMap<Vec<Datum>, TopNCache<WITH_TIES>>,
+    caches: ManagedLruCache<Vec<Datum>, TopNCache<WITH_TIES>>,
 
     /// The number of fields of the ORDER BY clause. Only used when `WITH_TIES` is true.
     order_by_len: usize,
@@ -148,6 +161,9 @@ impl<S: StateStore, const WITH_TIES: bool> InnerGroupTopNExecutorNew<S, WITH_TIE
         executor_id: u64,
         group_by: Vec<usize>,
         state_table: StateTable<S>,
+        ctx: ActorContextRef,
+        lru_manager: LruManagerRef,
+        metrics: Arc<StreamingMetrics>,
     ) -> StreamResult<Self> {
         // order_pairs is superset of pk
         assert!(order_pairs
@@ -163,11 +179,15 @@ impl<S: StateStore, const WITH_TIES: bool> InnerGroupTopNExecutorNew<S, WITH_TIE
 
         let managed_state = ManagedTopNState::<S>::new(state_table, ordered_row_deserializer);
 
+        let identity = format!("TopNExecutorNew {:X}", executor_id);
+        let caches =
+            lru_manager.create_cache(CacheMetricsInfo::new(metrics, ctx.id, identity.clone()));
+
         Ok(Self {
             info: ExecutorInfo {
                 schema: input_info.schema,
                 pk_indices: input_info.pk_indices,
-                identity: format!("TopNExecutorNew {:X}", executor_id),
+                identity,
             },
             schema,
             offset: offset_and_limit.0,
@@ -177,7 +197,7 @@ impl<S: StateStore, const WITH_TIES: bool> InnerGroupTopNExecutorNew<S, WITH_TIE
             internal_key_indices,
             internal_key_order_types,
             group_by,
-            caches: HashMap::new(),
+            caches,
             order_by_len,
         })
     }
@@ -190,8 +210,8 @@ where
     TopNCache<WITH_TIES>: TopNCacheTrait,
 {
     async fn apply_chunk(&mut self, chunk: StreamChunk) -> StreamExecutorResult<StreamChunk> {
-        let mut res_ops = Vec::with_capacity(self.limit);
-        let mut res_rows = Vec::with_capacity(self.limit);
+        let mut res_ops = Vec::with_capacity(chunk.capacity());
+        let mut res_rows = Vec::with_capacity(chunk.capacity());
 
         for (op, row_ref) in chunk.rows() {
             // The pk without group by
@@ -211,14 +231,14 @@ where
 
             // If 'self.caches' does not already have a cache for the current group, create a new
             // cache for it and insert it into `self.caches`
-            if let Vacant(entry) = self.caches.entry(group_key) {
+            if self.caches.get(&group_key).is_none() {
                 let mut topn_cache = TopNCache::new(self.offset, self.limit, self.order_by_len);
                 self.managed_state
                     .init_topn_cache(Some(&pk_prefix), &mut topn_cache)
                     .await?;
-                entry.insert(topn_cache);
+                self.caches.push(group_key, topn_cache);
             }
-            let cache = self.caches.get_mut(&pk_prefix.0).unwrap();
+            let mut cache = self.caches.peek_mut(&pk_prefix.0).unwrap();
 
             // apply the chunk to state table
             match op {
@@ -247,7 +267,10 @@ where
     }
 
     async fn flush_data(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
-        self.managed_state.flush(epoch).await
+        self.managed_state.flush(epoch).await?;
+        self.caches.evict();
+        self.caches.update_epoch(epoch.curr);
+        Ok(())
     }
 
     fn schema(&self) -> &Schema {
@@ -270,6 +293,7 @@ where
 
     async fn init(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
         self.managed_state.state_table.init_epoch(epoch);
+        self.caches.update_epoch(epoch.curr);
         Ok(())
     }
 }
@@ -284,9 +308,10 @@ mod tests {
     use piestream_common::util::sort_util::OrderType;
 
     use super::*;
+    use crate::cache::LruManager;
     use crate::executor::test_utils::top_n_executor::create_in_memory_state_table;
     use crate::executor::test_utils::MockSource;
-    use crate::executor::{Barrier, Message};
+    use crate::executor::{ActorContext, Barrier, Message};
 
     fn create_schema() -> Schema {
         Schema {
@@ -381,6 +406,9 @@ mod tests {
                 1,
                 vec![1],
                 state_table,
+                ActorContext::create(0),
+                LruManager::for_test(),
+                Arc::new(StreamingMetrics::unused()),
             )
             .unwrap(),
         );
@@ -477,6 +505,9 @@ mod tests {
                 1,
                 vec![1],
                 state_table,
+                ActorContext::create(0),
+                LruManager::for_test(),
+                Arc::new(StreamingMetrics::unused()),
             )
             .unwrap(),
         );
@@ -565,6 +596,9 @@ mod tests {
                 1,
                 vec![1, 2],
                 state_table,
+                ActorContext::create(0),
+                LruManager::for_test(),
+                Arc::new(StreamingMetrics::unused()),
             )
             .unwrap(),
         );
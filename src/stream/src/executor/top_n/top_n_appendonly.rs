@@ -137,8 +137,8 @@ impl<S: StateStore> InnerAppendOnlyTopNExecutor<S> {
 #[async_trait]
 impl<S: StateStore> TopNExecutorBase for InnerAppendOnlyTopNExecutor<S> {
     async fn apply_chunk(&mut self, chunk: StreamChunk) -> StreamExecutorResult<StreamChunk> {
-        let mut res_ops = Vec::with_capacity(self.cache.limit);
-        let mut res_rows = Vec::with_capacity(self.cache.limit);
+        let mut res_ops = Vec::with_capacity(chunk.capacity());
+        let mut res_rows = Vec::with_capacity(chunk.capacity());
 
         // apply the chunk to state table
         for (op, row_ref) in chunk.rows() {
@@ -161,10 +161,10 @@ impl<S: StateStore> TopNExecutorBase for InnerAppendOnlyTopNExecutor<S> {
             }
 
             let elem_to_insert_into_middle =
-            if let Some(low_last) = self.cache.low.last_entry()
-                && ordered_pk_row <= *low_last.key() {
+            if let Some((low_last_key, _)) = self.cache.low.last_key_value()
+                && ordered_pk_row <= *low_last_key {
                 // Take the last element of `cache.low` and insert input row to it.
-                let low_last = low_last.remove_entry();
+                let low_last = self.cache.low.pop_last().unwrap();
                 self.cache.low.insert(ordered_pk_row, row);
                 low_last
             } else {
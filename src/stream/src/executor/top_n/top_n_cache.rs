@@ -14,9 +14,12 @@
 
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::ops::Deref;
 
 use async_trait::async_trait;
+use itertools::Itertools;
 use piestream_common::array::{Op, Row};
+use piestream_common::collection::estimate_size::EstimateSize;
 use piestream_common::util::ordered::OrderedRow;
 use piestream_storage::StateStore;
 
@@ -36,19 +39,27 @@ const TOPN_CACHE_HIGH_CAPACITY_FACTOR: usize = 2;
 ///
 /// `OFFSET m FETCH FIRST n ROWS WITH TIES` and `m <= RANK() <= n` are not supported now,
 /// since they have different semantics.
+///
+/// # Memory
+///
+/// `low` and `middle` always hold all the rows in `[0, offset+limit)`, so the cache takes
+/// `O(offset + limit)` rows of memory, plus up to `high_capacity` rows in `high`. For `OFFSET`
+/// without `LIMIT`, the limit is unbounded and `middle` holds every row after the offset, i.e. the
+/// whole input is cached in memory. The planner only generates a single-phase Top-N for such
+/// queries, so that the rows are not cached twice.
 pub struct TopNCache<const WITH_TIES: bool> {
     /// Rows in the range `[0, offset)`
-    pub low: BTreeMap<OrderedRow, Row>,
+    pub low: TopNCacheState,
     /// Rows in the range `[offset, offset+limit)`
     ///
     /// When `WITH_TIES` is true, it also stores ties for the last element,
     /// and thus the size can be larger than `limit`.
-    pub middle: BTreeMap<OrderedRow, Row>,
+    pub middle: TopNCacheState,
     /// Rows in the range `[offset+limit, offset+limit+high_capacity)`
     ///
     /// When `WITH_TIES` is true, it also stores ties for the last element,
     /// and thus the size can be larger than `high_capacity`.
-    pub high: BTreeMap<OrderedRow, Row>,
+    pub high: TopNCacheState,
     pub high_capacity: usize,
     pub offset: usize,
     /// Assumption: `limit != 0`
@@ -58,6 +69,91 @@ pub struct TopNCache<const WITH_TIES: bool> {
     pub order_by_len: usize,
 }
 
+impl<const WITH_TIES: bool> EstimateSize for TopNCache<WITH_TIES> {
+    fn estimated_heap_size(&self) -> usize {
+        self.low.estimated_heap_size()
+            + self.middle.estimated_heap_size()
+            + self.high.estimated_heap_size()
+    }
+}
+
+/// Ordered rows of a range of [`TopNCache`]. The estimated heap size of the rows is maintained on
+/// every modification, so that it's cheap to report the size of the cache.
+#[derive(Default)]
+pub struct TopNCacheState {
+    /// The full copy of the rows in the range.
+    inner: BTreeMap<OrderedRow, Row>,
+    /// Estimated heap size of the keys and values in `inner`.
+    kv_heap_size: usize,
+}
+
+impl TopNCacheState {
+    /// Insert an entry, returning the old value if the key was present.
+    pub fn insert(&mut self, key: OrderedRow, value: Row) -> Option<Row> {
+        let key_size = key.estimated_size();
+        self.kv_heap_size += key_size + value.estimated_size();
+        let old_value = self.inner.insert(key, value);
+        if let Some(old_value) = &old_value {
+            self.kv_heap_size -= key_size + old_value.estimated_size();
+        }
+        old_value
+    }
+
+    /// Remove an entry, returning the value if the key was present.
+    pub fn remove(&mut self, key: &OrderedRow) -> Option<Row> {
+        let value = self.inner.remove(key);
+        if let Some(value) = &value {
+            self.kv_heap_size -= key.estimated_size() + value.estimated_size();
+        }
+        value
+    }
+
+    /// Remove the first (smallest) entry.
+    pub fn pop_first(&mut self) -> Option<(OrderedRow, Row)> {
+        let entry = self.inner.pop_first();
+        if let Some((key, value)) = &entry {
+            self.kv_heap_size -= key.estimated_size() + value.estimated_size();
+        }
+        entry
+    }
+
+    /// Remove the last (largest) entry.
+    pub fn pop_last(&mut self) -> Option<(OrderedRow, Row)> {
+        let entry = self.inner.pop_last();
+        if let Some((key, value)) = &entry {
+            self.kv_heap_size -= key.estimated_size() + value.estimated_size();
+        }
+        entry
+    }
+
+    /// Remove all the entries whose key satisfies `pred`, and return them in order.
+    pub fn drain_filter(
+        &mut self,
+        mut pred: impl FnMut(&OrderedRow) -> bool,
+    ) -> Vec<(OrderedRow, Row)> {
+        let removed = self.inner.drain_filter(|k, _| pred(k)).collect_vec();
+        for (key, value) in &removed {
+            self.kv_heap_size -= key.estimated_size() + value.estimated_size();
+        }
+        removed
+    }
+}
+
+impl Deref for TopNCacheState {
+    type Target = BTreeMap<OrderedRow, Row>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl EstimateSize for TopNCacheState {
+    fn estimated_heap_size(&self) -> usize {
+        // The overhead of the btree-map itself is not counted.
+        self.kv_heap_size
+    }
+}
+
 /// This trait is used as a bound. It is needed since
 /// `TopNCache::<true>::f` and `TopNCache::<false>::f`
 /// don't imply `TopNCache::<WITH_TIES>::f`.
@@ -104,10 +200,13 @@ impl<const WITH_TIES: bool> TopNCache<WITH_TIES> {
             assert!(offset == 0, "OFFSET is not supported with WITH TIES");
         }
         Self {
-            low: BTreeMap::new(),
-            middle: BTreeMap::new(),
-            high: BTreeMap::new(),
-            high_capacity: (offset + limit) * TOPN_CACHE_HIGH_CAPACITY_FACTOR,
+            low: TopNCacheState::default(),
+            middle: TopNCacheState::default(),
+            high: TopNCacheState::default(),
+            // `limit` can be unbounded for `OFFSET` without `LIMIT`
+            high_capacity: offset
+                .saturating_add(limit)
+                .saturating_mul(TOPN_CACHE_HIGH_CAPACITY_FACTOR),
             offset,
             limit,
             order_by_len,
@@ -166,10 +265,10 @@ impl TopNCacheTrait for TopNCache<false> {
         }
 
         let elem_to_compare_with_middle =
-            if let Some(low_last) = self.low.last_entry()
-                && ordered_pk_row <= *low_last.key() {
+            if let Some((low_last_key, _)) = self.low.last_key_value()
+                && ordered_pk_row <= *low_last_key {
                 // Take the last element of `cache.low` and insert input row to it.
-                let low_last = low_last.remove_entry();
+                let low_last = self.low.pop_last().unwrap();
                 self.low.insert(ordered_pk_row, row);
                 low_last
             } else {
@@ -187,11 +286,11 @@ impl TopNCacheTrait for TopNCache<false> {
         }
 
         let elem_to_compare_with_high = {
-            let middle_last = self.middle.last_entry().unwrap();
-            if elem_to_compare_with_middle.0 <= *middle_last.key() {
+            let middle_last_key = self.middle.last_key_value().unwrap().0;
+            if elem_to_compare_with_middle.0 <= *middle_last_key {
                 // If the row in the range of [offset, offset+limit), the largest row in
                 // `cache.middle` needs to be moved to `cache.high`
-                let res = middle_last.remove_entry();
+                let res = self.middle.pop_last().unwrap();
                 res_ops.push(Op::Delete);
                 res_rows.push(res.1.clone());
                 res_ops.push(Op::Insert);
@@ -208,9 +307,9 @@ impl TopNCacheTrait for TopNCache<false> {
             self.high
                 .insert(elem_to_compare_with_high.0, elem_to_compare_with_high.1);
         } else {
-            let high_last = self.high.last_entry().unwrap();
-            if elem_to_compare_with_high.0 <= *high_last.key() {
-                high_last.remove_entry();
+            let high_last_key = self.high.last_key_value().unwrap().0;
+            if elem_to_compare_with_high.0 <= *high_last_key {
+                self.high.pop_last();
                 self.high
                     .insert(elem_to_compare_with_high.0, elem_to_compare_with_high.1);
             }
@@ -329,9 +428,9 @@ impl TopNCacheTrait for TopNCache<true> {
                 // We evict the last row and its ties only if the number of remaining rows still is
                 // still larger than limit.
                 if self.middle.len() - num_ties + 1 >= self.limit {
-                    while let Some(middle_last) = self.middle.last_entry()
-                    && middle_last.key().starts_with(&middle_last_order_by) {
-                        let middle_last = middle_last.remove_entry();
+                    while let Some((middle_last_key, _)) = self.middle.last_key_value()
+                    && middle_last_key.starts_with(&middle_last_order_by) {
+                        let middle_last = self.middle.pop_last().unwrap();
                         res_ops.push(Op::Delete);
                         res_rows.push(middle_last.1.clone());
                         self.high.insert(middle_last.0, middle_last.1);
@@ -341,7 +440,7 @@ impl TopNCacheTrait for TopNCache<true> {
                     let high_last = self.high.pop_last().unwrap();
                     let high_last_order_by = high_last.0.prefix(self.order_by_len);
                     self.high
-                        .drain_filter(|k, _| k.starts_with(&high_last_order_by));
+                        .drain_filter(|k| k.starts_with(&high_last_order_by));
                 }
 
                 res_ops.push(Op::Insert);
@@ -363,9 +462,9 @@ impl TopNCacheTrait for TopNCache<true> {
                     self.high
                         .insert(elem_to_compare_with_high.0, elem_to_compare_with_high.1);
                 } else {
-                    let high_last = self.high.last_entry().unwrap();
-                    if elem_to_compare_with_high.0 <= *high_last.key() {
-                        high_last.remove_entry();
+                    let high_last_key = self.high.last_key_value().unwrap().0;
+                    if elem_to_compare_with_high.0 <= *high_last_key {
+                        self.high.pop_last();
                         self.high
                             .insert(elem_to_compare_with_high.0, elem_to_compare_with_high.1);
                     }
@@ -429,7 +528,7 @@ impl TopNCacheTrait for TopNCache<true> {
                 // in high cache.
                 for (ordered_pk_row, row) in self
                     .high
-                    .drain_filter(|k, _| k.starts_with(&high_first_order_by))
+                    .drain_filter(|k| k.starts_with(&high_first_order_by))
                 {
                     if !ordered_pk_row.starts_with(&high_first_order_by) {
                         break;
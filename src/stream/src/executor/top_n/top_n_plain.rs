@@ -205,8 +205,8 @@ where
     TopNCache<WITH_TIES>: TopNCacheTrait,
 {
     async fn apply_chunk(&mut self, chunk: StreamChunk) -> StreamExecutorResult<StreamChunk> {
-        let mut res_ops = Vec::with_capacity(self.cache.limit);
-        let mut res_rows = Vec::with_capacity(self.cache.limit);
+        let mut res_ops = Vec::with_capacity(chunk.capacity());
+        let mut res_rows = Vec::with_capacity(chunk.capacity());
 
         // apply the chunk to state table
         for (op, row_ref) in chunk.rows() {
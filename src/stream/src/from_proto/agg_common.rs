@@ -38,16 +38,23 @@ pub fn build_agg_call_from_prost(
             DataType::from(arg.get_type()?),
             arg.get_input()?.column_idx as usize,
         ),
-        [agg_arg, extra_arg] if agg_kind == AggKind::StringAgg => AggArgs::Binary(
-            [
-                DataType::from(agg_arg.get_type()?),
-                DataType::from(extra_arg.get_type()?),
-            ],
-            [
-                agg_arg.get_input()?.column_idx as usize,
-                extra_arg.get_input()?.column_idx as usize,
-            ],
-        ),
+        [agg_arg, extra_arg]
+            if matches!(
+                agg_kind,
+                AggKind::StringAgg | AggKind::PercentileCont | AggKind::PercentileDisc
+            ) =>
+        {
+            AggArgs::Binary(
+                [
+                    DataType::from(agg_arg.get_type()?),
+                    DataType::from(extra_arg.get_type()?),
+                ],
+                [
+                    agg_arg.get_input()?.column_idx as usize,
+                    extra_arg.get_input()?.column_idx as usize,
+                ],
+            )
+        }
         _ => bail!("Too many/few arguments for {:?}", agg_kind),
     };
     let mut order_pairs = vec![];
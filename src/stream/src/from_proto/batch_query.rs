@@ -79,6 +79,7 @@ impl ExecutorBuilder for BatchQueryExecutorBuilder {
             } else {
                 None
             },
+            ..Default::default()
         };
         let value_indices = table_desc
             .get_value_indices()
@@ -27,7 +27,7 @@ impl ExecutorBuilder for DynamicFilterExecutorBuilder {
         params: ExecutorParams,
         node: &StreamNode,
         store: impl StateStore,
-        _stream: &mut LocalStreamManagerCore,
+        stream: &mut LocalStreamManagerCore,
     ) -> StreamResult<BoxedExecutor> {
         let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::DynamicFilter)?;
         let [source_l, source_r]: [_; 2] = params.input.try_into().unwrap();
@@ -70,6 +70,7 @@ impl ExecutorBuilder for DynamicFilterExecutorBuilder {
             state_table_l,
             state_table_r,
             is_right_table_writer,
+            stream.context.lru_manager.clone(),
             params.executor_stats,
         )))
     }
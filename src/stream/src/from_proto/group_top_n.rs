@@ -27,7 +27,7 @@ impl ExecutorBuilder for GroupTopNExecutorBuilder {
         mut params: ExecutorParams,
         node: &StreamNode,
         store: impl StateStore,
-        _stream: &mut LocalStreamManagerCore,
+        stream: &mut LocalStreamManagerCore,
     ) -> StreamResult<BoxedExecutor> {
         let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::GroupTopN)?;
         let group_by = node
@@ -50,6 +50,9 @@ impl ExecutorBuilder for GroupTopNExecutorBuilder {
                 params.executor_id,
                 group_by,
                 state_table,
+                params.actor_context,
+                stream.context.lru_manager.clone(),
+                params.executor_stats,
             )?
             .boxed())
         } else {
@@ -62,6 +65,9 @@ impl ExecutorBuilder for GroupTopNExecutorBuilder {
                 params.executor_id,
                 group_by,
                 state_table,
+                params.actor_context,
+                stream.context.lru_manager.clone(),
+                params.executor_stats,
             )?
             .boxed())
         }
@@ -36,10 +36,9 @@ pub struct HashAggExecutorDispatcherArgs<S: StateStore> {
     group_key_indices: Vec<usize>,
     group_key_types: Vec<DataType>,
     pk_indices: PkIndices,
-    group_by_cache_size: usize,
     extreme_cache_size: usize,
     executor_id: u64,
-    lru_manager: Option<LruManagerRef>,
+    lru_manager: LruManagerRef,
     metrics: Arc<StreamingMetrics>,
 }
 
@@ -56,7 +55,6 @@ impl<S: StateStore> HashKeyDispatcher for HashAggExecutorDispatcherArgs<S> {
             self.pk_indices,
             self.executor_id,
             self.group_key_indices,
-            self.group_by_cache_size,
             self.extreme_cache_size,
             self.lru_manager,
             self.metrics,
@@ -116,7 +114,6 @@ impl ExecutorBuilder for HashAggExecutorBuilder {
             group_key_indices,
             group_key_types,
             pk_indices: params.pk_indices,
-            group_by_cache_size: stream.config.developer.unsafe_stream_hash_agg_cache_size,
             extreme_cache_size: stream.config.developer.unsafe_stream_extreme_cache_size,
             executor_id: params.executor_id,
             lru_manager: stream.context.lru_manager.clone(),
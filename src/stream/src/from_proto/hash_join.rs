@@ -110,7 +110,6 @@ impl ExecutorBuilder for HashJoinExecutorBuilder {
             executor_id: params.executor_id,
             cond: condition,
             op_info: params.op_info,
-            cache_size: stream.config.developer.unsafe_stream_join_cache_size,
             state_table_l,
             degree_state_table_l,
             state_table_r,
@@ -138,12 +137,11 @@ struct HashJoinExecutorDispatcherArgs<S: StateStore> {
     executor_id: u64,
     cond: Option<BoxedExpression>,
     op_info: String,
-    cache_size: usize,
     state_table_l: StateTable<S>,
     degree_state_table_l: StateTable<S>,
     state_table_r: StateTable<S>,
     degree_state_table_r: StateTable<S>,
-    lru_manager: Option<LruManagerRef>,
+    lru_manager: LruManagerRef,
     is_append_only: bool,
     metrics: Arc<StreamingMetrics>,
     join_type_proto: JoinTypeProto,
@@ -170,7 +168,6 @@ impl<S: StateStore> HashKeyDispatcher for HashJoinExecutorDispatcherArgs<S> {
                         self.executor_id,
                         self.cond,
                         self.op_info,
-                        self.cache_size,
                         self.state_table_l,
                         self.degree_state_table_l,
                         self.state_table_r,
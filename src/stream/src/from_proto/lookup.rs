@@ -54,6 +54,7 @@ impl ExecutorBuilder for LookupExecutorBuilder {
         );
 
         Ok(Box::new(LookupExecutor::new(LookupExecutorParams {
+            ctx: params.actor_context,
             schema: Schema::new(node.fields.iter().map(Field::from).collect()),
             arrangement,
             stream,
@@ -65,11 +66,9 @@ impl ExecutorBuilder for LookupExecutorBuilder {
             arrange_join_key_indices: lookup.arrange_key.iter().map(|x| *x as usize).collect(),
             column_mapping: lookup.column_mapping.iter().map(|x| *x as usize).collect(),
             state_table,
+            executor_id: params.executor_id,
             lru_manager: stream_manager.context.lru_manager.clone(),
-            cache_size: stream_manager
-                .config
-                .developer
-                .unsafe_stream_join_cache_size,
+            metrics: params.executor_stats,
         })))
     }
 }
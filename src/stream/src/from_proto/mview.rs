@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use piestream_common::util::sort_util::OrderPair;
+use piestream_pb::stream_plan::HandleConflictBehavior;
 
 use super::*;
 use crate::executor::MaterializeExecutor;
@@ -46,6 +47,7 @@ impl ExecutorBuilder for MaterializeExecutorBuilder {
             params.actor_context,
             params.vnode_bitmap.map(Arc::new),
             table,
+            node.handle_pk_conflict_behavior(),
         );
 
         Ok(executor.boxed())
@@ -85,6 +87,7 @@ impl ExecutorBuilder for ArrangeExecutorBuilder {
             params.actor_context,
             vnodes,
             table,
+            HandleConflictBehavior::NoCheck,
         );
 
         Ok(executor.boxed())
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use piestream_common::catalog::{ColumnId, TableId};
+use piestream_storage::table::streaming_table::state_table::StateTable;
 
 use super::*;
 use crate::executor::SinkExecutor;
@@ -36,9 +39,13 @@ impl ExecutorBuilder for SinkExecutorBuilder {
             .map(|i| ColumnId::from(*i))
             .collect::<Vec<ColumnId>>();
 
+        let vnodes = params.vnode_bitmap.map(Arc::new);
+        let state_table = StateTable::from_table_catalog(node.get_table()?, store.clone(), vnodes);
+
         Ok(Box::new(SinkExecutor::new(
             materialize_executor,
             store,
+            state_table,
             stream.streaming_metrics.clone(),
             node.properties.clone(),
             params.executor_id,
@@ -25,6 +25,7 @@ use tokio::sync::mpsc::{Receiver, Sender};
 
 use crate::cache::{LruManager, LruManagerRef};
 use crate::error::StreamResult;
+use crate::executor::monitor::StreamingMetrics;
 use crate::executor::Message;
 
 mod barrier_manager;
@@ -84,7 +85,7 @@ pub struct SharedContext {
 
     pub(crate) barrier_manager: Arc<Mutex<LocalBarrierManager>>,
 
-    pub(crate) lru_manager: Option<LruManagerRef>,
+    pub(crate) lru_manager: LruManagerRef,
 }
 
 impl std::fmt::Debug for SharedContext {
@@ -100,23 +101,21 @@ impl SharedContext {
         addr: HostAddr,
         state_store: StateStoreImpl,
         config: &StreamingConfig,
-        enable_managed_cache: bool,
+        streaming_metrics: Arc<StreamingMetrics>,
     ) -> Self {
-        let create_lru_manager = || {
-            let mgr = LruManager::new(
-                config.total_memory_available_bytes,
-                config.barrier_interval_ms,
-            );
-            // Run a background memory monitor
-            tokio::spawn(mgr.clone().run());
-            mgr
-        };
+        let lru_manager = LruManager::new(
+            config.total_memory_available_bytes,
+            config.barrier_interval_ms,
+            streaming_metrics,
+        );
+        // Run a background memory monitor
+        tokio::spawn(lru_manager.clone().run());
         Self {
             channel_map: Default::default(),
             actor_infos: Default::default(),
             addr,
             compute_client_pool: ComputeClientPool::default(),
-            lru_manager: enable_managed_cache.then(create_lru_manager),
+            lru_manager,
             barrier_manager: Arc::new(Mutex::new(LocalBarrierManager::new(state_store))),
         }
     }
@@ -131,7 +130,7 @@ impl SharedContext {
             barrier_manager: Arc::new(Mutex::new(LocalBarrierManager::new(
                 StateStoreImpl::for_test(),
             ))),
-            lru_manager: None,
+            lru_manager: LruManager::for_test(),
         }
     }
 
@@ -27,6 +27,7 @@ use piestream_common::config::StreamingConfig;
 use piestream_common::util::addr::HostAddr;
 use piestream_hummock_sdk::LocalSstableInfo;
 use piestream_pb::common::ActorInfo;
+use piestream_pb::monitor_service::ActorStats;
 use piestream_pb::stream_plan::stream_node::NodeBody;
 use piestream_pb::stream_plan::StreamNode;
 use piestream_pb::{stream_plan, stream_service};
@@ -143,7 +144,6 @@ impl LocalStreamManager {
         streaming_metrics: Arc<StreamingMetrics>,
         config: StreamingConfig,
         enable_async_stack_trace: bool,
-        enable_managed_cache: bool,
     ) -> Self {
         Self::with_core(LocalStreamManagerCore::new(
             addr,
@@ -151,7 +151,6 @@ impl LocalStreamManager {
             streaming_metrics,
             config,
             enable_async_stack_trace,
-            enable_managed_cache,
         ))
     }
 
@@ -188,6 +187,33 @@ impl LocalStreamManager {
         }
     }
 
+    /// Get the cumulative statistics of all running actors.
+    pub fn get_actor_stats(&self) -> HashMap<ActorId, ActorStats> {
+        let core = self.core.lock();
+        let metrics = &core.streaming_metrics;
+        core.handles
+            .keys()
+            .map(|actor_id| {
+                let label = actor_id.to_string();
+                let stats = ActorStats {
+                    in_record_count: metrics
+                        .actor_in_record_cnt
+                        .with_label_values(&[&label])
+                        .get(),
+                    out_record_count: metrics
+                        .actor_out_record_cnt
+                        .with_label_values(&[&label])
+                        .get(),
+                    output_blocking_duration_ns: metrics
+                        .actor_output_buffer_blocking_duration_ns
+                        .with_label_values(&[&label])
+                        .get(),
+                };
+                (*actor_id, stats)
+            })
+            .collect()
+    }
+
     /// Broadcast a barrier to all senders. Save a receiver in barrier manager
     pub fn send_barrier(
         &self,
@@ -361,9 +387,13 @@ impl LocalStreamManagerCore {
         streaming_metrics: Arc<StreamingMetrics>,
         config: StreamingConfig,
         enable_async_stack_trace: bool,
-        enable_managed_cache: bool,
     ) -> Self {
-        let context = SharedContext::new(addr, state_store.clone(), &config, enable_managed_cache);
+        let context = SharedContext::new(
+            addr,
+            state_store.clone(),
+            &config,
+            streaming_metrics.clone(),
+        );
         Self::new_inner(
             state_store,
             context,
@@ -393,6 +393,7 @@ fn make_simple_func(func_name: &str, exprs: &[Expr]) -> Function {
         distinct: false,
         order_by: vec![],
         filter: None,
+        within_group: None,
     }
 }
 
@@ -414,6 +415,7 @@ fn make_agg_func(func_name: &str, exprs: &[Expr], _distinct: bool) -> Function {
         distinct: false,
         order_by: vec![],
         filter: None,
+        within_group: None,
     }
 }
 
@@ -65,6 +65,7 @@ impl<'a, R: Rng> SqlGenerator<'a, R> {
                 name: alias.as_str().into(),
                 columns: vec![],
             }),
+            as_of: None,
         };
         table.name = alias; // Rename the table.
         let columns = table.get_qualified_columns();
@@ -60,6 +60,7 @@ pub(crate) fn create_table_factor_from_table(table: &Table) -> TableFactor {
     TableFactor::Table {
         name: ObjectName(vec![Ident::new(&table.name)]),
         alias: None,
+        as_of: None,
     }
 }
 
@@ -83,4 +83,52 @@ impl PsqlError {
     pub fn no_portal_in_execute() -> Self {
         PsqlError::ExecuteError("No portal found".into())
     }
+
+    /// The `SQLSTATE` code to report to the client.
+    pub fn sqlstate(&self) -> &'static str {
+        match self {
+            PsqlError::StartupError(e)
+            | PsqlError::QueryError(e)
+            | PsqlError::ParseError(e)
+            | PsqlError::BindError(e)
+            | PsqlError::ExecuteError(e) => sqlstate_of(e.as_ref()),
+            _ => INTERNAL_ERROR_SQLSTATE,
+        }
+    }
+}
+
+/// The `SQLSTATE` code of `internal_error`, which is reported for the errors without a code.
+pub const INTERNAL_ERROR_SQLSTATE: &str = "XX000";
+
+/// An error reported to the client with the given `SQLSTATE` code. Other errors returned by the
+/// session are reported as [`INTERNAL_ERROR_SQLSTATE`].
+#[derive(Error, Debug)]
+#[error("{source}")]
+pub struct SqlStateError {
+    code: &'static str,
+    source: BoxedError,
+}
+
+impl SqlStateError {
+    pub fn new(code: &'static str, source: impl Into<BoxedError>) -> Self {
+        Self {
+            code,
+            source: source.into(),
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+}
+
+/// The `SQLSTATE` code of an error returned by the session.
+pub fn sqlstate_of(error: &(dyn std::error::Error + 'static)) -> &'static str {
+    if let Some(e) = error.downcast_ref::<PsqlError>() {
+        e.sqlstate()
+    } else if let Some(e) = error.downcast_ref::<SqlStateError>() {
+        e.code()
+    } else {
+        INTERNAL_ERROR_SQLSTATE
+    }
 }
@@ -21,6 +21,7 @@ use byteorder::{BigEndian, ByteOrder};
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use tokio::io::{AsyncRead, AsyncReadExt};
 
+use crate::error::sqlstate_of;
 use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use crate::pg_response::StatementType;
 use crate::pg_server::BoxedError;
@@ -630,8 +631,8 @@ impl<'a> BeMessage<'a> {
             }
 
             BeMessage::ErrorResponse(error) => {
-                // For all the errors set Severity to Error and error code to
-                // 'internal error'.
+                // For all the errors set Severity to Error. The error code is 'internal error'
+                // unless the error carries a `SQLSTATE` code.
 
                 // 'E' signalizes ErrorResponse messages
                 buf.put_u8(b'E');
@@ -640,7 +641,7 @@ impl<'a> BeMessage<'a> {
                     write_cstr(buf, &Bytes::from("ERROR"))?;
 
                     buf.put_u8(b'C'); // SQLSTATE error code
-                    write_cstr(buf, &Bytes::from("XX000"))?;
+                    write_cstr(buf, sqlstate_of(error.as_ref()).as_bytes())?;
 
                     buf.put_u8(b'M'); // the message
                     write_cstr(buf, error.to_string().as_bytes())?;
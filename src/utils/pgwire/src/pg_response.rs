@@ -53,6 +53,9 @@ pub enum StatementType {
     DROP_SCHEMA,
     DROP_DATABASE,
     DROP_USER,
+    ALTER_TABLE,
+    ALTER_MATERIALIZED_VIEW,
+    ALTER_SINK,
     REVOKE_PRIVILEGE,
     // Introduce ORDER_BY statement type cuz Calcite unvalidated AST has SqlKind.ORDER_BY. Note
     // that Statement Type is not designed to be one to one mapping with SqlKind.
@@ -64,6 +67,7 @@ pub enum StatementType {
     UPDATE_USER,
     ABORT,
     FLUSH,
+    CANCEL_COMMAND,
     OTHER,
     // EMPTY is used when query statement is empty (e.g. ";").
     EMPTY,
@@ -126,9 +126,11 @@ mod tests {
     use bytes::Bytes;
     use futures::stream::BoxStream;
     use futures::StreamExt;
+    use tokio_postgres::error::SqlState;
     use tokio_postgres::types::*;
     use tokio_postgres::NoTls;
 
+    use crate::error::SqlStateError;
     use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
     use crate::pg_response::{PgResponse, RowSetResult, StatementType};
     use crate::pg_server::{pg_serve, Session, SessionId, SessionManager, UserAuthenticator};
@@ -162,6 +164,10 @@ mod tests {
             _format: bool,
         ) -> Result<PgResponse<BoxStream<'static, RowSetResult>>, Box<dyn Error + Send + Sync>>
         {
+            if sql.starts_with("SELECT 1/0") {
+                return Err(Box::new(SqlStateError::new("22012", "Division by zero")));
+            }
+
             // split a statement and trim \' around the input param to construct result.
             // Ex:
             //    SELECT 'a','b' -> result: a , b
@@ -351,4 +357,25 @@ mod tests {
             assert_eq!(value, "BB");
         }
     }
+
+    #[tokio::test]
+    async fn test_psql_error_sqlstate() {
+        let session_mgr = Arc::new(MockSessionManager {});
+        tokio::spawn(async move { pg_serve("127.0.0.1:10001", session_mgr).await });
+        // wait for server to start
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        let (client, connection) = tokio_postgres::connect("host=localhost port=10001", NoTls)
+            .await
+            .unwrap();
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                eprintln!("connection error: {}", e);
+            }
+        });
+
+        let err = client.simple_query("SELECT 1/0;").await.unwrap_err();
+        assert_eq!(err.code(), Some(&SqlState::DIVISION_BY_ZERO));
+        assert!(err.to_string().contains("Division by zero"));
+    }
 }